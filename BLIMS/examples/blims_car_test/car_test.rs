@@ -49,7 +49,7 @@ use {defmt_rtt as _, panic_probe as _};
 
 use blims::blims_constants::*;
 use blims::blims_state::BlimsDataIn;
-use blims::{Blims, Hardware};
+use blims::{Blims, BlimsConfig, PwmActuator, WindProfile};
 
 // ============================================================================
 // INTERRUPT BINDINGS
@@ -78,8 +78,8 @@ const I2C_SCL_NUM: u8 = 1;
 // TEST CONFIGURATION — update before each test
 // ============================================================================
 
-const TARGET_LAT: f32 = 42.446610;
-const TARGET_LON: f32 = -76.461304;
+const TARGET_LAT: f64 = 42.446610;
+const TARGET_LON: f64 = -76.461304;
 
 /// 50 ms = 20 Hz, matches FSW cycle_time
 const CYCLE_TIME_MS: u64 = 50;
@@ -93,6 +93,8 @@ const WIND_ALTITUDES_M: [f32; WIND_PROFILE_SIZE] =
     [0.0, 50.0, 100.0, 150.0, 200.0, 250.0, 300.0, 400.0, 500.0, 550.0, 610.0];
 const WIND_DIRS_DEG: [f32; WIND_PROFILE_SIZE] =
     [45.0, 48.0, 52.0, 56.0, 60.0, 64.0, 68.0, 75.0, 80.0, 85.0, 90.0];
+const WIND_SPEEDS_MS: [f32; WIND_PROFILE_SIZE] =
+    [3.0, 3.5, 4.0, 4.5, 5.0, 5.0, 5.5, 6.0, 6.5, 7.0, 7.0];
 const WIND_FROM_DEG: f32 = 45.0;
 
 // ============================================================================
@@ -115,6 +117,11 @@ fn phase_name(phase_id: i8) -> &'static str {
         2 => "UPWIND",
         3 => "DOWNWIND",
         4 => "NEUTRAL",
+        5 => "TRACK",
+        6 => "LOITER",
+        7 => "DOWNWIND_LEG",
+        8 => "BASE",
+        9 => "FINAL",
         _ => "???",
     }
 }
@@ -132,6 +139,7 @@ const NAV_PVT_LEN: usize = 100;
 
 #[derive(Default, Clone)]
 struct UbxNavPvt {
+    itow:     u32,
     fix_type: u8,
     lat:      i32,
     lon:      i32,
@@ -208,6 +216,7 @@ async fn read_nav_pvt(i2c: &mut I2c<'_, I2C0, embassy_rp::i2c::Async>) -> Option
     let p = &buf[6..6 + NAV_PVT_LEN];
 
     Some(UbxNavPvt {
+        itow:     u32::from_le_bytes([p[0], p[1], p[2], p[3]]),
         fix_type: p[20],
         lon:      i32::from_le_bytes([p[28], p[29], p[30], p[31]]),
         lat:      i32::from_le_bytes([p[32], p[33], p[34], p[35]]),
@@ -275,12 +284,14 @@ async fn main(spawner: Spawner) {
     let enable_pin = Output::new(p.PIN_34, Level::High); // pin 34 for av bay, pin 0 for breadboard --- IGNORE ---
 
     // ── BLiMS init ────────────────────────────────────────────────────────────
-    let mut blims = Blims::new(Hardware { pwm, pwm_config, enable_pin });
+    let mut blims = Blims::new(PwmActuator::new(pwm, pwm_config, enable_pin), BlimsConfig::default());
     // Car test — both phases point to same target for simplicity
     blims.set_upwind_target(TARGET_LAT, TARGET_LON);
     blims.set_downwind_target(TARGET_LAT, TARGET_LON);
     blims.set_wind_from_deg(WIND_FROM_DEG);
-    blims.set_wind_profile(&WIND_ALTITUDES_M, &WIND_DIRS_DEG);
+    let wind_profile = WindProfile::from_columns(&WIND_ALTITUDES_M, &WIND_DIRS_DEG, &WIND_SPEEDS_MS)
+        .expect("invalid wind profile");
+    blims.set_wind_profile(&wind_profile);
 
     // Give the motor time to settle at neutral before starting
     Timer::after(Duration::from_secs(5)).await;
@@ -369,6 +380,7 @@ async fn main(spawner: Spawner) {
 
         // ── 3. Pack BlimsDataIn ───────────────────────────────────────────────
         let data_in = BlimsDataIn {
+            itow:        pvt.itow,
             lon:         pvt.lon,
             lat:         pvt.lat,
             altitude_ft: current_alt_ft, //if descent_started { current_alt_ft } else { DESCENT_ALT_FT[0] },
@@ -383,10 +395,13 @@ async fn main(spawner: Spawner) {
             head_acc:    pvt.head_acc,
             fix_type:    pvt.fix_type,
             gps_state:   gps_valid,
+            // GPS heading only; the car test has no IMU
+            yaw_rate_dps:   0.0,
+            yaw_rate_valid: false,
         };
 
         // ── 4. Execute BLiMS ──────────────────────────────────────────────────
-        let data_out = blims.execute(&data_in);
+        let data_out = blims.execute(&data_in, Instant::now().as_millis());
 
         // ── 5. Advance altitude ───────────────────────────────────────────────
         //if descent_started && alt_index < DESCENT_DATA_SIZE - 1 {
@@ -0,0 +1,179 @@
+//! Brakeline calibration on the bench (av bay wiring, see motor_test.rs).
+//!
+//! Steps the ODrive through a sweep of RC pulses, up then down
+//! (`blims::calibration::Characterisation`), and after each one settles asks
+//! over USB serial for the differential it gave: inches measured on the
+//! lines (`3.25`), or the ODrive-reported position in turns with a `t`
+//! suffix (`3.8t`, converted with MOTOR_TURNS_PER_IN). `r` re-sends the
+//! step's pulse. At the end it prints the hysteresis, the worst error of
+//! the nominal linear map and the fitted table to paste into
+//! `BRAKELINE_CALIBRATION` in blims_constants.rs, then parks at neutral.
+//!
+//! Run with:  cargo run --example brakeline_cal --release
+//! then open the USB serial port (e.g. `screen /dev/ttyACM0`).
+
+#![no_std]
+#![no_main]
+
+use core::fmt::Write as _;
+
+use blims::blims_constants::{CAL_SETTLE_MS, MOTOR_TURNS_PER_IN, PULSE_NEUTRAL_US};
+use blims::calibration::FitError;
+use blims::{Actuator, Characterisation, PwmActuator};
+use embassy_executor::Spawner;
+use embassy_futures::join::join;
+use embassy_rp::bind_interrupts;
+use embassy_rp::gpio::{Level, Output};
+use embassy_rp::peripherals::USB;
+use embassy_rp::pwm::{Config as PwmConfig, Pwm};
+use embassy_rp::usb::{Driver, InterruptHandler};
+use embassy_time::Timer;
+use embassy_usb::class::cdc_acm::{CdcAcmClass, State};
+use embassy_usb::driver::EndpointError;
+use embassy_usb::{Builder, Config};
+use fixed::FixedU16;
+use fixed::types::extra::U4;
+use heapless::String;
+use {defmt_rtt as _, panic_probe as _};
+
+bind_interrupts!(struct Irqs {
+    USBCTRL_IRQ => InterruptHandler<USB>;
+});
+
+const TOP: u16 = 65_535;
+const DIVIDER: f32 = 45.78;
+/// Pulses per sweep
+const CAL_POINTS: usize = 9;
+
+type Serial<'d> = CdcAcmClass<'d, Driver<'d, USB>>;
+
+#[embassy_executor::main]
+async fn main(_spawner: Spawner) {
+    let p = embassy_rp::init(Default::default());
+
+    let enable_pin = Output::new(p.PIN_34, Level::Low); // pin 34 for av bay, pin 0 for breadboard
+    let mut pwm_config = PwmConfig::default();
+    pwm_config.top = TOP;
+    pwm_config.divider = FixedU16::<U4>::from_num(DIVIDER);
+    pwm_config.compare_b = 0;
+    pwm_config.enable = true;
+    let pwm = Pwm::new_output_b(p.PWM_SLICE9, p.PIN_35, pwm_config.clone()); // slice 9 pin 35 for av bay
+    let mut actuator = PwmActuator::new(pwm, pwm_config, enable_pin);
+
+    let mut config = Config::new(0xc0de, 0xcafe);
+    config.manufacturer = Some("Cornell Rocketry");
+    config.product = Some("BLiMS brakeline calibration");
+    config.max_power = 100;
+    config.max_packet_size_0 = 64;
+
+    let mut config_descriptor = [0; 256];
+    let mut bos_descriptor = [0; 256];
+    let mut control_buf = [0; 64];
+    let mut state = State::new();
+    let mut builder = Builder::new(
+        Driver::new(p.USB, Irqs),
+        config,
+        &mut config_descriptor,
+        &mut bos_descriptor,
+        &mut [],
+        &mut control_buf,
+    );
+    let mut serial = CdcAcmClass::new(&mut builder, &mut state, 64);
+    let mut usb = builder.build();
+
+    let session = async {
+        loop {
+            serial.wait_connection().await;
+            // Start from neutral each session; a disconnect abandons the sweep
+            actuator.set_pulse_us(PULSE_NEUTRAL_US);
+            let _ = calibrate(&mut serial, &mut actuator).await;
+            actuator.set_pulse_us(PULSE_NEUTRAL_US);
+        }
+    };
+    join(usb.run(), session).await;
+}
+
+async fn calibrate(serial: &mut Serial<'_>, actuator: &mut PwmActuator<'_>) -> Result<(), EndpointError> {
+    let mut cal = Characterisation::new(CAL_POINTS).unwrap();
+    let mut msg: String<512> = String::new();
+    say(serial, "\r\n=== BLiMS brakeline calibration ===\r\n").await?;
+    say(serial, "Enter the differential after each step: inches (3.25) or ODrive turns (3.8t); r re-sends.\r\n").await?;
+    actuator.enable();
+
+    while let Some(pulse) = cal.pulse_us() {
+        actuator.set_pulse_us(pulse);
+        msg.clear();
+        let _ = write!(msg, "Step {}/{}: {:.0} us, settling...\r\n", cal.step() + 1, cal.steps(), pulse);
+        say(serial, &msg).await?;
+        Timer::after_millis(CAL_SETTLE_MS).await;
+
+        loop {
+            say(serial, "measured> ").await?;
+            let line = read_line(serial).await?;
+            let line = line.trim();
+            if line == "r" {
+                actuator.set_pulse_us(pulse);
+                continue;
+            }
+            let measured = match line.strip_suffix('t') {
+                Some(turns) => turns.trim().parse::<f32>().map(|t| t / MOTOR_TURNS_PER_IN),
+                None => line.parse::<f32>(),
+            };
+            match measured {
+                Ok(inches) if inches.is_finite() => {
+                    cal.record(inches);
+                    break;
+                }
+                _ => say(serial, "? inches, turns with a t suffix, or r\r\n").await?,
+            }
+        }
+    }
+    actuator.set_pulse_us(PULSE_NEUTRAL_US);
+
+    msg.clear();
+    match cal.fit() {
+        Ok(fit) => {
+            let _ = write!(
+                msg,
+                "Hysteresis {:.2} in, linear map off by up to {:.2} in\r\n\
+                 pub const BRAKELINE_CALIBRATION: &[(f32, f32)] = {};\r\n",
+                fit.hysteresis_in, fit.linear_error_in, fit.table
+            );
+        }
+        Err(FitError::NotMonotonic) => {
+            let _ = write!(msg, "Differential didn't rise with every step - check for slip or backlash, and rerun\r\n");
+        }
+        Err(FitError::Incomplete) => {}
+    }
+    say(serial, &msg).await
+}
+
+/// Writes `s` in packets short enough not to need a zero-length terminator.
+async fn say(serial: &mut Serial<'_>, s: &str) -> Result<(), EndpointError> {
+    for chunk in s.as_bytes().chunks(63) {
+        serial.write_packet(chunk).await?;
+    }
+    Ok(())
+}
+
+/// Reads up to a CR or LF, echoing; anything past 32 characters is dropped.
+async fn read_line(serial: &mut Serial<'_>) -> Result<String<32>, EndpointError> {
+    let mut line = String::new();
+    let mut buf = [0; 64];
+    loop {
+        let n = serial.read_packet(&mut buf).await?;
+        for chunk in buf[..n].chunks(63) {
+            serial.write_packet(chunk).await?;
+        }
+        for &b in &buf[..n] {
+            if b == b'\r' || b == b'\n' {
+                if !line.is_empty() {
+                    say(serial, "\r\n").await?;
+                    return Ok(line);
+                }
+            } else if b.is_ascii() {
+                let _ = line.push(b as char);
+            }
+        }
+    }
+}
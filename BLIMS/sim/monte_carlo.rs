@@ -0,0 +1,153 @@
+//! monte_carlo.rs — BLiMS Landing-Dispersion Monte Carlo
+//!
+//! Flies `--runs` closed-loop descents (`blims::sim`) through the real
+//! controller, each with the canopy, layered and gusty true wind, deployment
+//! point and heading, turn lag, command latency and GPS dropouts dispersed
+//! about the nominal case (`blims::monte_carlo`), and reports landing-error
+//! CEP, time in each phase and brakeline saturation. BLiMS is given the
+//! nominal wind as its forecast.
+//!
+//! Output:
+//!   stdout  – report
+//!   --csv   – one row per run
+//!   --tracks, --geojson – every run's track, for plotting
+//!
+//! Options:
+//!   --runs N            number of descents, default 200
+//!   --seed N            seed of run 0; run i uses seed + i, default 1
+//!   --dispersion K      scale every spread by K, default 1
+//!   --wind-from DEG     nominal wind direction (degrees FROM), default 200
+//!   --wind-speed M/S    nominal wind speed, default 3
+//!   --start-alt M       main deployment altitude AGL, default 610 (2000 ft)
+//!   --pattern           full landing pattern instead of MVP Upwind/Downwind
+//!   --l1                L1 path following along each leg
+//!   --csv PATH          per-run results
+//!   --tracks PATH       tracks CSV, one row per run per second
+//!   --geojson PATH      tracks and landing points as a GeoJSON
+//!                       FeatureCollection
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use blims::monte_carlo::{self, Dispersion, Run, Stats};
+use blims::sim::{SimConfig, phase_name};
+use blims::{GuidanceMode, TrackingLaw};
+
+struct Args {
+    nominal: SimConfig,
+    dispersion: Dispersion,
+    runs: u32,
+    seed: u64,
+    csv: Option<PathBuf>,
+    tracks: Option<PathBuf>,
+    geojson: Option<PathBuf>,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut a = Args {
+        nominal: SimConfig::default(),
+        dispersion: Dispersion::default(),
+        runs: 200,
+        seed: 1,
+        csv: None,
+        tracks: None,
+        geojson: None,
+    };
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| args.next().ok_or_else(|| format!("{name} needs a value"));
+        let num = |v: String, name: &str| v.parse::<f32>().map_err(|_| format!("bad {name} value {v}"));
+        match arg.as_str() {
+            "--runs" => a.runs = value(&arg)?.parse().map_err(|_| "bad --runs value".to_string())?,
+            "--seed" => a.seed = value(&arg)?.parse().map_err(|_| "bad --seed value".to_string())?,
+            "--dispersion" => a.dispersion = Dispersion::default().scaled(num(value(&arg)?, &arg)?),
+            "--wind-from" => a.nominal.wind_from_deg = num(value(&arg)?, &arg)?,
+            "--wind-speed" => a.nominal.wind_speed_ms = num(value(&arg)?, &arg)?,
+            "--start-alt" => a.nominal.start_alt_m = num(value(&arg)?, &arg)?,
+            "--pattern" => a.nominal.mode = GuidanceMode::Pattern,
+            "--l1" => a.nominal.tracking_law = TrackingLaw::L1,
+            "--csv" => a.csv = Some(PathBuf::from(value(&arg)?)),
+            "--tracks" => a.tracks = Some(PathBuf::from(value(&arg)?)),
+            "--geojson" => a.geojson = Some(PathBuf::from(value(&arg)?)),
+            _ => return Err(format!("unknown argument {arg}")),
+        }
+    }
+    if a.runs == 0 {
+        return Err("--runs must be at least 1".to_string());
+    }
+    Ok(a)
+}
+
+fn write_runs_csv(runs: &[Run], w: &mut impl Write) -> std::io::Result<()> {
+    write!(w, "Run,Seed,Miss_m,Landing_N_m,Landing_E_m,Descent_s,Saturated_s")?;
+    for p in 0..monte_carlo::PHASE_COUNT {
+        write!(w, ",{}_s", phase_name(p as i8))?;
+    }
+    writeln!(w)?;
+    for r in runs {
+        write!(
+            w,
+            "{},{},{:.1},{:.1},{:.1},{:.1},{:.2}",
+            r.index, r.seed, r.miss_m, r.landing_n_m, r.landing_e_m, r.descent_s, r.saturated_s
+        )?;
+        for t in r.phase_s {
+            write!(w, ",{t:.2}")?;
+        }
+        writeln!(w)?;
+    }
+    Ok(())
+}
+
+/// Creates `path` and fills it with `write`.
+fn save(path: &PathBuf, write: impl FnOnce(&mut BufWriter<File>) -> std::io::Result<()>) -> Result<(), String> {
+    let mut w = BufWriter::new(File::create(path).map_err(|e| format!("{}: {e}", path.display()))?);
+    write(&mut w).and_then(|_| w.flush()).map_err(|e| format!("{}: {e}", path.display()))
+}
+
+fn main() -> ExitCode {
+    let a = match parse_args() {
+        Ok(a) => a,
+        Err(e) => {
+            eprintln!("blims-mc: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let runs = monte_carlo::run(&a.nominal, &a.dispersion, a.runs, a.seed);
+    let s = monte_carlo::summarise(&runs);
+    let worst = runs.iter().max_by(|x, y| x.miss_m.total_cmp(&y.miss_m)).unwrap();
+
+    let n = &a.nominal;
+    println!("BLiMS landing dispersion — {} runs, seeds {}..={}", s.runs, a.seed, a.seed + a.runs as u64 - 1);
+    println!(
+        "Nominal: {:?} guidance, wind {:.1} m/s FROM {:.0}°, deployment {:.0} m AGL",
+        n.mode, n.wind_speed_ms, n.wind_from_deg, n.start_alt_m
+    );
+    println!();
+    println!("CEP50 {:.0} m   CEP90 {:.0} m   worst {:.0} m (seed {})", s.cep50_m, s.cep90_m, worst.miss_m, worst.seed);
+    println!("Mean landing offset {:.0} m N, {:.0} m E of the target", s.bias_n_m, s.bias_e_m);
+    println!();
+    println!("{:<18}{}", "", Stats::HEADER);
+    println!("{:<18}{}", "Miss (m)", s.miss.row());
+    println!("{:<18}{}", "Descent (s)", s.descent.row());
+    println!("{:<18}{}", "Saturated (s)", s.saturated.row());
+    for (phase_id, stats) in &s.phases {
+        println!("{:<18}{}", format!("{} (s)", phase_name(*phase_id)), stats.row());
+    }
+
+    let target = n.downwind_target;
+    let saved = [
+        a.csv.as_ref().map(|p| save(p, |w| write_runs_csv(&runs, w))),
+        a.tracks.as_ref().map(|p| save(p, |w| monte_carlo::write_tracks_csv(&runs, w))),
+        a.geojson.as_ref().map(|p| save(p, |w| monte_carlo::write_geojson(&runs, target, w))),
+    ];
+    for result in saved.into_iter().flatten() {
+        if let Err(e) = result {
+            eprintln!("blims-mc: {e}");
+            return ExitCode::FAILURE;
+        }
+    }
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,18 @@
+//! Brakeline actuator interface.
+//!
+//! The controller decides how much brakeline differential it wants and maps
+//! it to an RC pulse through its calibration table (`calibration`); getting
+//! the pulse to the winch is up to the implementation. On the vehicle that
+//! is `PwmActuator` (RC PWM into the ODrive, feature `rp`), in the host
+//! simulator a model of the winch.
+
+/// Whatever moves the brakelines for [`Blims`](crate::Blims).
+pub trait Actuator {
+    /// Command the winch with an RC pulse width in µs, within
+    /// [`PULSE_MIN_US`, `PULSE_MAX_US`](crate::blims_constants).
+    /// Longer = right brake pulled, shorter = left.
+    fn set_pulse_us(&mut self, pulse_us: f32);
+
+    /// Power up the motor driver. Called once when the FSW arms BLiMS.
+    fn enable(&mut self);
+}
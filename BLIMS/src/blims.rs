@@ -1,61 +1,119 @@
-//Phase::Upwind   (alt > 1000 ft) — PI-steer to `wind_from` heading.
-///       Observes: does the parafoil respond to a sustained heading hold?
-///       What are the steady-state motor commands needed to fight crosswind?
-///
-///   Phase::Downwind (200 ft < alt ≤ 1000 ft) — PI-steer to `wind_from + 180°`.
-///       Observes: how quickly does the canopy reverse direction?
-///       What turn rate and settling time does the PI controller produce?
-///
-///   Phase::Neutral  (alt ≤ 200 ft) — motor to neutral, hands off.
-///
-///   Phase::Held     — GPS invalid; motor to neutral.
-///
-/// The full landing-pattern state machine (Track / Loiter / Downwind-leg /
-/// Base / Final) is intentionally absent in this MVP branch.  Re-integrate
-/// from `full-blims` once parafoil dynamics are characterised from flight data.
-
-
-use embassy_rp::gpio::Output;
-use embassy_rp::pwm::{Config as PwmConfig, Pwm};
-use embassy_time::{Duration, Instant};
- 
+//! MVP guidance: PI heading hold toward a waypoint per altitude band.
+//!
+//! ```text
+//!   Phase::Upwind   (alt > 1000 ft) — PI-steer to `wind_from` heading.
+//!       Observes: does the parafoil respond to a sustained heading hold?
+//!       What are the steady-state motor commands needed to fight crosswind?
+//!
+//!   Phase::Downwind (200 ft < alt ≤ 1000 ft) — PI-steer to `wind_from + 180°`.
+//!       Observes: how quickly does the canopy reverse direction?
+//!       What turn rate and settling time does the PI controller produce?
+//!
+//!   Phase::Neutral  (alt ≤ 200 ft) — motor to neutral, hands off.
+//!
+//!   Phase::Held     — GPS invalid; motor to neutral.
+//! ```
+//!
+//! The gains and both boundaries come from [`BlimsConfig`] (defaults as
+//! above). A boundary, once crossed on the way down, only reverts after a
+//! climb of `phase_hysteresis_ft` back above it.
+//!
+//! The PI loop steers on a gyro-aided heading (`heading`); while the
+//! canopy is too slow for a usable GPS heading, or has gone too long
+//! without one, the steering phases hold the brakelines at neutral.
+//!
+//! With [`GuidanceMode::Pattern`] the Upwind/Downwind bands are replaced by
+//! a full landing pattern to the downwind target (see `pattern`):
+//!
+//! ```text
+//!   Phase::Track       — steer to the loiter point (pattern entry).
+//!   Phase::Loiter      — circle it while above PATTERN_ENTRY_ALT_FT.
+//!   Phase::DownwindLeg — with the wind to the base turn point.
+//!   Phase::Base        — crosswind to the final turn point.
+//!   Phase::Final       — into the wind to the target, down to ALT_NEUTRAL_FT.
+//! ```
+//!
+//! The legs are re-planned every cycle from the measured sink rate,
+//! airspeed and wind (the in-flight estimate from `wind` once the canopy has
+//! turned enough, the forecast until then) until the downwind leg starts, then held so the turn points
+//! don't move under the canopy. The pattern only ever moves forward; a GPS
+//! dropout shows Held and resumes the same leg.
+//!
+//! Positions are kept in f64 and all geometry is great-circle (`geo`). Each
+//! steered phase flies a leg, from where the canopy entered the phase (MVP
+//! and Track) or from the previous turn point (pattern legs) to its
+//! waypoint, and reports the cross-track error off it. With
+//! [`TrackingLaw::L1`] the heading setpoint pulls the canopy back onto the
+//! leg instead of only pointing at its end.
+//!
+//! With a [`Geofence`] loaded, BLiMS lands on the downwind target only
+//! while it is safe and within reach; otherwise on the nearest safe point
+//! that is (re-picked until the pattern's downwind leg, after which the turn
+//! points stay put). Every steered heading setpoint is swung away from
+//! keep-out zones in the way, and inside one the canopy steers straight
+//! out. `fence_flags` reports what it is doing.
+//!
+//! The controller owns no hardware and reads no clock: brakeline commands go
+//! through an [`Actuator`] and the caller passes the time into `execute`, so
+//! the same code runs on the RP2350 and in the host simulator.
+
+use crate::actuator::Actuator;
 use crate::blims_constants::*;
-use crate::blims_state::{BlimsDataIn, BlimsDataOut, Phase};
-
-/// Opaque hardware bundle consumed by [`Blims::new`]. - bundles hardware resources into one package
-// single entry point, same scope, these are moved into blims struct, enforces initialization
-pub struct Hardware<'d> {
-    pub pwm:        Pwm<'d>,
-    pub pwm_config: PwmConfig,
-    pub enable_pin: Output<'d>,
-}
-
-pub struct Blims<'d> {
-    // ── Embassy HAL handles ──────────────────────────────────────────────────
-    pwm:        Pwm<'d>,
-    pwm_config: PwmConfig,
-    enable_pin: Output<'d>,
+use crate::blims_state::{BlimsConfig, BlimsDataIn, BlimsDataOut, GuidanceMode, Phase, TrackingLaw};
+use crate::calibration::CalibrationTable;
+use crate::geo::LatLon;
+use crate::geofence::{self, Geofence};
+use crate::heading::HeadingFilter;
+use crate::pattern::{self, Pattern, Point};
+use crate::wind::{Wind, WindEstimator, WindProfile};
+
+pub struct Blims<A: Actuator> {
+    // ── Brakeline actuator ───────────────────────────────────────────────────
+    actuator: A,
+    config:   BlimsConfig,
 
     // ── Pre-flight navigation config ─────────────────────────────────────────
-    target_upwind_lat: f32,
-    target_upwind_lon: f32,
-    target_downwind_lat: f32,
-    target_downwind_lon: f32,
+    target_upwind:   LatLon,
+    target_downwind: LatLon,
 
     activation_time_ms: Option<u64>, // timestamp of first transition out of Held phase, used for InitialHold delay
     /// Surface-level wind direction (degrees FROM, 0 = N, 90 = E).
     /// Used as fallback when no altitude-aware profile is loaded.
     wind_from_deg:     f32,
     ///altitude-aware wind profile loaded before flight
-    wind_profile_size: usize,
-    wind_altitudes_m:  [f32; MAX_WIND_LAYERS],
-    wind_dirs_deg:     [f32; MAX_WIND_LAYERS],
+    wind_profile:      WindProfile,
+    /// Forecast wind speed (m/s), sizes the landing pattern when the
+    /// profile is empty
+    wind_speed_ms:     f32,
+    guidance_mode:     GuidanceMode,
+    tracking_law:      TrackingLaw,
+
+    // ── Landing pattern (GuidanceMode::Pattern) ──────────────────────────────
+    pattern:      Pattern,
+    /// Furthest pattern leg reached (Track until the first fix)
+    pattern_leg:  Phase,
+    /// Measured canopy performance (m/s), low-pass filtered
+    sink_rate_ms: f32,
+    airspeed_ms:  f32,
+    /// In-flight wind from the GPS velocity wind triangle
+    wind:         WindEstimator,
+    /// Gyro-aided heading the PI loop steers on
+    heading:      HeadingFilter,
+
+    // ── Geofence ─────────────────────────────────────────────────────────────
+    geofence:       Geofence,
+    /// Where the canopy is landing: the downwind target, or the nearest
+    /// safe point in reach when that isn't
+    landing_target: LatLon,
+    fence_flags:    u8,
+    /// Last search for a new landing point; `None` before the first
+    fence_search_ms: Option<u64>,
 
     // ── GPS snapshot (refreshed each execute()) ──────────────────────────────
-    gps_lat:   f32,
-    gps_lon:   f32,
-    /// headMot: heading of motion, degrees × 1e5 (raw from GPS)
-    head_mot:  i32,
+    position:  LatLon,
+    vel_n:     i32,
+    vel_e:     i32,
+    vel_d:     i32,
     fix_type:  u8,
     gps_state: bool,
 
@@ -66,44 +124,62 @@ pub struct Blims<'d> {
 
     // ── Phase / motor state ──────────────────────────────────────────────────
     last_phase:     Phase,
+    /// Latched once below ALT_UPWIND / ALT_NEUTRAL, released only above the
+    /// boundary plus the hysteresis
+    below_upwind:   bool,
+    below_neutral:  bool,
     bearing:        f32,
     brakeline_diff_in: f32,   // was: motor_position: f32
-
-    // ── Timing (ms since boot; mirrors blims::flight::currTime/prevTime) ─────
+    /// Differential → RC pulse (BRAKELINE_CALIBRATION unless set)
+    calibration:    CalibrationTable,
+    /// Where the canopy entered the current phase, start of the MVP and
+    /// Track legs
+    leg_start:      LatLon,
+    distance_to_target_m: f32,
+
+    // ── Timing (ms since boot, as passed to execute()) ───────────────────────
     curr_time_ms: u64,
-    prev_time_ms: u64,
+    /// Time of the last execute(); `None` before the first
+    prev_time_ms: Option<u64>,
 }
 
-impl<'d> Blims<'d> {
+impl<A: Actuator> Blims<A> {
     // -------------------------------------------------------------------------
     // Construction  (mirrors BLIMS::begin())
     // -------------------------------------------------------------------------
 
-    /// Initialise BLiMS.
-    ///
-    /// `pwm` and `pwm_config` must already be configured for 50 Hz with
-    /// `top = WRAP_CYCLE_COUNT` and the correct clock divider (see main.rs).
-    /// The enable pin is driven high immediately to activate the motor driver,
-    /// then the motor is parked at neutral.
-    pub fn new(hw: Hardware<'d>) -> Self {
+    /// Initialise BLiMS and park the brakelines at neutral. The motor driver
+    /// stays off until [`enable`](Self::enable).
+    pub fn new(actuator: A, config: BlimsConfig) -> Self {
         let mut b = Self {
-            pwm:        hw.pwm,
-            pwm_config: hw.pwm_config,
-            enable_pin: hw.enable_pin,
-
-            target_upwind_lat:   0.0,
-            target_upwind_lon:   0.0,
-            target_downwind_lat: 0.0,
-            target_downwind_lon: 0.0,
+            actuator,
+            config,
+
+            target_upwind:       LatLon::default(),
+            target_downwind:     LatLon::default(),
             activation_time_ms:  None,  
             wind_from_deg:     0.0,
-            wind_profile_size: 0,
-            wind_altitudes_m:  [0.0; MAX_WIND_LAYERS],
-            wind_dirs_deg:     [0.0; MAX_WIND_LAYERS],
-
-            gps_lat:   0.0,
-            gps_lon:   0.0,
-            head_mot:  0,
+            wind_profile:      WindProfile::EMPTY,
+            wind_speed_ms:     0.0,
+            guidance_mode:     GuidanceMode::Mvp,
+            tracking_law:      TrackingLaw::Bearing,
+
+            pattern:      Pattern::plan(0.0, 0.0, NOMINAL_AIRSPEED_MS, NOMINAL_SINK_RATE_MS),
+            pattern_leg:  Phase::Track,
+            sink_rate_ms: NOMINAL_SINK_RATE_MS,
+            airspeed_ms:  NOMINAL_AIRSPEED_MS,
+            wind:         WindEstimator::new(),
+            heading:      HeadingFilter::new(),
+
+            geofence:        Geofence::EMPTY,
+            landing_target:  LatLon::default(),
+            fence_flags:     0,
+            fence_search_ms: None,
+
+            position:  LatLon::default(),
+            vel_n:     0,
+            vel_e:     0,
+            vel_d:     0,
             fix_type:  0,
             gps_state: false,
 
@@ -112,13 +188,17 @@ impl<'d> Blims<'d> {
             pid_i: 0.0,
 
             last_phase:     Phase::Held,
+            below_upwind:   false,
+            below_neutral:  false,
             bearing:        0.0,
             brakeline_diff_in: NEUTRAL_POS, //now 0.0
+            calibration:    CalibrationTable::new(BRAKELINE_CALIBRATION).unwrap_or(CalibrationTable::LINEAR),
+            leg_start:      LatLon::default(),
+            distance_to_target_m: 0.0,
 
             curr_time_ms: 0,
-            prev_time_ms: 0,
+            prev_time_ms: None,
         };
-        // enable_pin starts LOW — caller must call enable() when ready to activate
         //neutral until first execute() call
         b.set_brakeline_diff(NEUTRAL_POS);
         b
@@ -128,60 +208,157 @@ impl<'d> Blims<'d> {
     // Pre-flight setters
     // -------------------------------------------------------------------------
 
-    pub fn set_upwind_target(&mut self, lat: f32, lon: f32) {
-        self.target_upwind_lat = lat;
-        self.target_upwind_lon = lon;
+    /// Waypoints in decimal degrees.
+    pub fn set_upwind_target(&mut self, lat: f64, lon: f64) {
+        self.target_upwind = LatLon::new(lat, lon);
+    }
+
+    pub fn set_downwind_target(&mut self, lat: f64, lon: f64) {
+        self.target_downwind = LatLon::new(lat, lon);
+        self.landing_target = self.target_downwind;
     }
 
-    pub fn set_downwind_target(&mut self, lat: f32, lon: f32) {
-        self.target_downwind_lat = lat;
-        self.target_downwind_lon = lon;
+    /// Replace the gains and phase altitudes (already validated by
+    /// `BlimsConfig::is_valid`). Change before flight.
+    pub fn set_config(&mut self, config: &BlimsConfig) {
+        self.config = *config;
+    }
+
+    pub fn config(&self) -> &BlimsConfig {
+        &self.config
+    }
+
+    /// Load keep-out zones and the landing zone (already validated by
+    /// `Geofence::from_zones`). Empty turns the fence off.
+    pub fn set_geofence(&mut self, fence: &Geofence) {
+        self.geofence = *fence;
+        self.landing_target = self.target_downwind;
+        self.fence_search_ms = None;
+    }
+
+    pub fn geofence(&self) -> &Geofence {
+        &self.geofence
+    }
+
+    /// Where BLiMS is landing: the downwind target unless the geofence
+    /// moved it.
+    pub fn landing_target(&self) -> LatLon {
+        self.landing_target
     }
 
     //when no altitude-layered profile is available - single surface-level wind-from direction
     pub fn set_wind_from_deg(&mut self, deg: f32) {
-        self.wind_from_deg = Self::wrap360(deg);
+        self.wind_from_deg = wrap360(deg);
+    }
+
+    /// Load a multi-layer wind profile (already validated by
+    /// `WindProfile::from_layers`). An empty profile falls back to
+    /// `wind_from_deg` / `wind_speed_ms`.
+    pub fn set_wind_profile(&mut self, profile: &WindProfile) {
+        self.wind_profile = *profile;
+    }
+
+    pub fn wind_profile(&self) -> &WindProfile {
+        &self.wind_profile
+    }
+
+    /// Forecast wind speed, used to size the landing pattern legs when no
+    /// profile is loaded.
+    pub fn set_wind_speed_ms(&mut self, speed: f32) {
+        self.wind_speed_ms = speed.max(0.0);
     }
 
-    /// Load a multi-layer wind profile. Arrays must be the same length;
-    /// excess layers beyond MAX_WIND_LAYERS are silently truncated.
-    pub fn set_wind_profile(&mut self, altitudes_m: &[f32], directions_deg: &[f32]) {
-        let size = altitudes_m
-            .len()
-            .min(directions_deg.len())
-            .min(MAX_WIND_LAYERS);
-        self.wind_profile_size = size;
-        self.wind_altitudes_m[..size].copy_from_slice(&altitudes_m[..size]);
-        self.wind_dirs_deg[..size].copy_from_slice(&directions_deg[..size]);
+    /// MVP Upwind/Downwind bands or the full landing pattern. Takes effect
+    /// from the next cycle; switch before flight.
+    pub fn set_guidance_mode(&mut self, mode: GuidanceMode) {
+        self.guidance_mode = mode;
+    }
+
+    pub fn guidance_mode(&self) -> GuidanceMode {
+        self.guidance_mode
+    }
+
+    /// Straight at each leg's end point, or L1 path following along it.
+    pub fn set_tracking_law(&mut self, law: TrackingLaw) {
+        self.tracking_law = law;
+    }
+
+    pub fn tracking_law(&self) -> TrackingLaw {
+        self.tracking_law
+    }
+
+    /// In-flight wind estimate, `None` until the canopy has turned through
+    /// `WIND_MIN_ARC_DEG`.
+    pub fn wind_estimate(&self) -> Option<Wind> {
+        self.wind.estimate()
+    }
+
+    /// Wind measured on the way down, for the flight log.
+    pub fn wind_estimator(&self) -> &WindEstimator {
+        &self.wind
+    }
+
+    /// Current landing-pattern plan, relative to the landing target.
+    pub fn pattern(&self) -> &Pattern {
+        &self.pattern
     }
 
     // -------------------------------------------------------------------------
     // Main control loop
     // -------------------------------------------------------------------------
 
-    pub fn execute(&mut self, data_in: &BlimsDataIn) -> BlimsDataOut {
+    /// One guidance cycle. `now_ms` is a monotonic time in milliseconds (ms
+    /// since boot on the vehicle, simulated time on the host).
+    pub fn execute(&mut self, data_in: &BlimsDataIn, now_ms: u64) -> BlimsDataOut {
 
-        self.prev_time_ms = self.curr_time_ms;
-        self.curr_time_ms = Instant::now().as_millis();
-        let dt_ms = self.curr_time_ms.saturating_sub(self.prev_time_ms);
-       
-        // clamp dt: ignore first call (prev = 0) and any gap > 200 ms like after a reset, to avoid integral-windup spikes on the first live cycle
-        let dt = if self.prev_time_ms == 0 || dt_ms > 200 {
-            0.05_f32
-        } else {
-            dt_ms as f32 / 1000.0
+        let dt_ms = self.prev_time_ms.map(|prev| now_ms.saturating_sub(prev));
+        self.prev_time_ms = Some(now_ms);
+        self.curr_time_ms = now_ms;
+
+        // clamp dt: ignore first call and any gap > 200 ms like after a reset, to avoid integral-windup spikes on the first live cycle
+        let dt = match dt_ms {
+            Some(ms) if ms <= 200 => ms as f32 / 1000.0,
+            _ => 0.05_f32,
         };
 
         //intake sensor data
-        self.gps_lat   = data_in.lat as f32 * 1e-7;
-        self.gps_lon   = data_in.lon as f32 * 1e-7;
-        self.head_mot  = data_in.head_mot;
+        self.position  = LatLon::from_e7(data_in.lat, data_in.lon);
+        self.vel_n     = data_in.vel_n;
+        self.vel_e     = data_in.vel_e;
+        self.vel_d     = data_in.vel_d;
         self.fix_type  = data_in.fix_type;
         self.gps_state = data_in.gps_state;
         let altitude_ft = data_in.altitude_ft;
 
         let gps_valid = self.gps_state && self.fix_type >= 2;
+        if gps_valid && altitude_ft > self.config.alt_neutral_ft {
+            self.wind.update(
+                self.vel_n as f32 * 1e-3, self.vel_e as f32 * 1e-3, altitude_ft / FT_PER_M, now_ms,
+            );
+        }
+        let heading = self.heading.update(data_in, now_ms, dt);
         let current_phase = self.determine_phase(altitude_ft, gps_valid);
+        if current_phase.is_pattern() {
+            self.measure_performance();
+        }
+        if gps_valid {
+            self.fence_flags = 0;
+        }
+        if gps_valid && !self.geofence.is_empty() {
+            let p = self.geofence.local_m(self.position);
+            if self.geofence.keep_out_at(p).is_some() {
+                self.fence_flags |= geofence::FENCE_IN_KEEP_OUT;
+            }
+            if !self.geofence.in_landing_zone(p) {
+                self.fence_flags |= geofence::FENCE_OUTSIDE_LZ;
+            }
+            if self.may_move_landing_target(current_phase) {
+                self.update_landing_target(altitude_ft);
+            }
+            if self.landing_target != self.target_downwind {
+                self.fence_flags |= geofence::FENCE_TARGET_MOVED;
+            }
+        }
 
         // ── Phase-change housekeeping ─────────────────────────────────────────
         if current_phase != self.last_phase {
@@ -194,16 +371,29 @@ impl<'d> Blims<'d> {
             // slate is always safer than carrying over a potentially stale term.
             self.error_integral = 0.0;
             self.last_phase = current_phase;
+            self.leg_start = self.position;
+        }
+
+        if gps_valid {
+            let target = match current_phase {
+                Phase::Upwind => self.target_upwind,
+                _             => self.landing_target,
+            };
+            self.distance_to_target_m = self.position.distance_m(target) as f32;
         }
+        let cross_track_m = self
+            .leg(current_phase)
+            .map_or(0.0, |(start, end)| self.position.cross_track_m(start, end) as f32);
 
         self.bearing = match current_phase {
-            Phase::Upwind   => self.calculate_bearing_to(
-                                self.target_upwind_lat, self.target_upwind_lon),
-            Phase::Downwind => self.calculate_bearing_to(
-                                self.target_downwind_lat, self.target_downwind_lon),
+            Phase::Upwind   => self.position.bearing_deg(self.target_upwind) as f32,
+            Phase::Downwind => self.position.bearing_deg(self.landing_target) as f32,
+            Phase::Track | Phase::Loiter | Phase::DownwindLeg | Phase::Base | Phase::Final =>
+                self.get_desired_heading(current_phase, altitude_ft),
     _                       => self.bearing, // hold last value
 };
 
+        let (mut desired_heading_deg, mut heading_error_deg) = (0.0, 0.0);
         match current_phase {
             Phase::Held | Phase::InitialHold => {
                 // No active control – park motor at neutral
@@ -212,18 +402,33 @@ impl<'d> Blims<'d> {
                 self.set_brakeline_diff(NEUTRAL_POS);
             }
 
-            Phase::Upwind | Phase::Downwind => {
+            Phase::Upwind | Phase::Downwind
+            | Phase::Track | Phase::Loiter | Phase::DownwindLeg | Phase::Base | Phase::Final => {
                 // PI heading control.
                 //
                 // desired heading is resolved from wind direction (see
-                // get_desired_heading); current heading comes from GPS
-                // heading-of-motion (degrees × 1e5 → degrees).
+                // get_desired_heading); current heading is GPS
+                // heading-of-motion blended with the gyro (see heading).
                 let desired = self.get_desired_heading(
                     current_phase, altitude_ft,
                 );
-                // headMot is degrees × 1e5 → convert to degrees
-                let current_heading = self.head_mot as f32 * 1e-5;
-                self.execute_pi_control(desired, current_heading, dt);
+                let desired = self.avoid_keep_outs(desired);
+                match heading {
+                    Some(current_heading) => {
+                        desired_heading_deg = desired;
+                        heading_error_deg = compute_heading_error(desired, current_heading);
+                        self.execute_pi_control(desired, current_heading, dt);
+                    }
+                    None => {
+                        // Too slow or too long since a trustworthy heading:
+                        // hold straight rather than chase noise, and don't
+                        // let the integral build on it
+                        self.error_integral = 0.0;
+                        self.pid_p = 0.0;
+                        self.pid_i = 0.0;
+                        self.set_brakeline_diff(NEUTRAL_POS);
+                    }
+                }
             }
 
             Phase::Neutral => {
@@ -233,12 +438,28 @@ impl<'d> Blims<'d> {
             }
         }
 
+        let wind = self.wind.estimate();
         BlimsDataOut {
             brakeline_diff_in: self.brakeline_diff_in,
             pid_p:          self.pid_p,
             pid_i:          self.pid_i,
             bearing:        self.bearing,
             phase_id:       current_phase as i8,
+            wind_speed_ms:  wind.map_or(0.0, |w| w.speed_ms()),
+            wind_from_deg:  wind.map_or(0.0, |w| w.from_deg()),
+            wind_valid:     wind.is_some(),
+            distance_to_target_m: self.distance_to_target_m,
+            cross_track_m,
+            heading_deg:    heading.unwrap_or(0.0),
+            heading_valid:  heading.is_some(),
+            fence_flags:    self.fence_flags,
+            landing_target: self.landing_target,
+            desired_heading_deg,
+            heading_error_deg,
+            error_integral: self.error_integral,
+            dt_s:           dt,
+            gps_valid,
+            activation_time_ms: self.activation_time_ms,
         }
     }
 
@@ -246,33 +467,38 @@ impl<'d> Blims<'d> {
     // Motor
     // =========================================================================
 
-    /// Assert enable HIGH — activates the ODrive motor driver.
+    /// Activate the motor driver.
     pub fn enable(&mut self) {
-        self.enable_pin.set_high();
+        self.actuator.enable();
     }
 
+    /// Command a differential (in), clamped to [MOTOR_MIN, MOTOR_MAX] and
+    /// sent as the pulse the calibration table gives for it.
     pub fn set_brakeline_diff(&mut self, mut position: f32) {
         position = position.clamp(MOTOR_MIN, MOTOR_MAX);
         self.brakeline_diff_in = position;
-        // Map inches [MOTOR_MIN, MOTOR_MAX] = [-9, +9] → normalised [0.0, 1.0].
-        //
-        //   pwm = (position − MOTOR_MIN) / (MOTOR_MAX − MOTOR_MIN)
-        //       = (position + 9.0) / 18.0
-        //
-        // Then map [0, 1] → [5 %, 10 %] duty cycle (1–2 ms standard servo range):
-        //
-        //   duty = 5%·WRAP + pwm · 5%·WRAP
-        //
-        // Verify:  position = -9 → pwm = 0.0 → duty = 5%·WRAP  (1 ms, full left)
-        //          position =  0 → pwm = 0.5 → duty = 7.5%·WRAP (1.5 ms, neutral)
-        //          position = +9 → pwm = 1.0 → duty = 10%·WRAP  (2 ms, full right)
-
-        let pwm_normalized = (position - MOTOR_MIN) / (MOTOR_MAX - MOTOR_MIN);
-        let five_pct = WRAP_CYCLE_COUNT as f32 * 0.05;
-        let duty = (five_pct + pwm_normalized * five_pct) as u16;
-
-        self.pwm_config.compare_b = duty;
-        self.pwm.set_config(&self.pwm_config);
+        self.actuator.set_pulse_us(self.calibration.pulse_us(position));
+    }
+
+    /// Replace the brakeline calibration (e.g. from a bench
+    /// [`Characterisation`](crate::calibration::Characterisation)) and
+    /// re-send the current differential through it.
+    pub fn set_calibration(&mut self, table: &CalibrationTable) {
+        self.calibration = *table;
+        self.set_brakeline_diff(self.brakeline_diff_in);
+    }
+
+    pub fn calibration(&self) -> &CalibrationTable {
+        &self.calibration
+    }
+
+    pub fn actuator(&self) -> &A {
+        &self.actuator
+    }
+
+    /// For actuator-specific controls, e.g. `PwmActuator::set_pwm_output`.
+    pub fn actuator_mut(&mut self) -> &mut A {
+        &mut self.actuator
     }
 
     // =========================================================================
@@ -284,17 +510,18 @@ impl<'d> Blims<'d> {
         current_heading: f32,
         dt: f32,
     ) {
-        let error = Self::compute_heading_error(desired_heading, current_heading);
+        let error = compute_heading_error(desired_heading, current_heading);
 
         // Clamp the accumulated error integral so that the I term can contribute
-        // at most INTEGRAL_MAX_INCHES of brakeline differential.
-        // max_integral [deg·s] = INTEGRAL_MAX_INCHES [in] / KI [in/(deg·s)]
-        let max_integral = INTEGRAL_MAX_INCHES / KI;
+        // at most integral_max_in of brakeline differential.
+        // max_integral [deg·s] = integral_max_in [in] / ki [in/(deg·s)]
+        let BlimsConfig { kp, ki, integral_max_in, .. } = self.config;
+        let max_integral = if ki > 0.0 { integral_max_in / ki } else { 0.0 };
         self.error_integral =
             (self.error_integral + error * dt).clamp(-max_integral, max_integral);
 
-        let p_term = KP * error;
-        let i_term = KI * self.error_integral;
+        let p_term = kp * error;
+        let i_term = ki * self.error_integral;
 
         self.pid_p = p_term;
         self.pid_i = i_term;
@@ -307,11 +534,21 @@ impl<'d> Blims<'d> {
     // =========================================================================
     /// Decision tree:
     /// 1. GPS invalid → Held
-    /// 2. alt ≤ ALT_NEUTRAL_FT → Neutral
-    /// 3. alt > ALT_UPWIND_FT  → Upwind
-    /// 4. otherwise            → Downwind
-
+    /// 2. below alt_neutral_ft → Neutral
+    /// 3. GuidanceMode::Pattern → current pattern leg (advance_pattern)
+    /// 4. above alt_upwind_ft  → Upwind
+    /// 5. otherwise            → Downwind
+    ///
+    /// "Below" a boundary means at or under it, or within the hysteresis
+    /// above it once it has been crossed; tracked through GPS dropouts.
     fn determine_phase(&mut self, altitude_ft: f32, gps_valid: bool) -> Phase {
+        let hysteresis_ft = self.config.phase_hysteresis_ft;
+        let below = |latched: bool, boundary_ft: f32| {
+            altitude_ft <= boundary_ft + if latched { hysteresis_ft } else { 0.0 }
+        };
+        self.below_upwind = below(self.below_upwind, self.config.alt_upwind_ft);
+        self.below_neutral = below(self.below_neutral, self.config.alt_neutral_ft);
+
         // GPS must be valid for any active control
         if !gps_valid {
             return Phase::Held;
@@ -323,15 +560,18 @@ impl<'d> Blims<'d> {
                 self.curr_time_ms
             }
         };
-        if self.curr_time_ms - activation_ms < INITIAL_HOLD_THRESHOLD as u64 {
+        if self.curr_time_ms - activation_ms < self.config.initial_hold_ms as u64 {
             return Phase::InitialHold;
         }
         // Below min altitude – hands off touchdown
-        if altitude_ft <= ALT_NEUTRAL_FT {
+        if self.below_neutral {
             return Phase::Neutral;
         }
+        if self.guidance_mode == GuidanceMode::Pattern {
+            return self.advance_pattern(altitude_ft);
+        }
         // Landing pattern bands
-        if altitude_ft > ALT_UPWIND_FT {
+        if !self.below_upwind {
             Phase::Upwind
         } else {
             Phase::Downwind
@@ -339,6 +579,169 @@ impl<'d> Blims<'d> {
         //communicate with Amira about FSW integration about what data is received where and how 
     }
 
+    /// Landing-pattern leg for this cycle. Re-plans while still tracking to
+    /// or circling the loiter point, then only ever moves on to the next leg:
+    /// on reaching its turn point or dropping through its altitude gate,
+    /// whichever comes first.
+    fn advance_pattern(&mut self, altitude_ft: f32) -> Phase {
+        let p = self.position_m();
+        if matches!(self.pattern_leg, Phase::Track | Phase::Loiter) {
+            let (wind_from_deg, wind_speed_ms) = self.pattern_wind();
+            self.pattern = Pattern::plan(wind_from_deg, wind_speed_ms, self.airspeed_ms, self.sink_rate_ms);
+        }
+        self.pattern_leg = match self.pattern_leg {
+            Phase::Track | Phase::Loiter if altitude_ft <= PATTERN_ENTRY_ALT_FT => Phase::DownwindLeg,
+            Phase::Track if pattern::distance(p, self.pattern.entry()) <= LOITER_CAPTURE_M => Phase::Loiter,
+            Phase::DownwindLeg
+                if altitude_ft <= PATTERN_BASE_ALT_FT
+                    || pattern::distance(p, self.pattern.base_turn()) <= PATTERN_TURN_LEAD_M => Phase::Base,
+            Phase::Base
+                if altitude_ft <= PATTERN_FINAL_ALT_FT
+                    || pattern::distance(p, self.pattern.final_turn()) <= PATTERN_TURN_LEAD_M => Phase::Final,
+            leg => leg,
+        };
+        self.pattern_leg
+    }
+
+    // =========================================================================
+    // Geofence
+    // =========================================================================
+
+    /// The landing point may move until the pattern's downwind leg; after
+    /// that the turn points are fixed around it.
+    fn may_move_landing_target(&self, phase: Phase) -> bool {
+        matches!(phase, Phase::Upwind | Phase::Downwind | Phase::Track | Phase::Loiter)
+    }
+
+    /// Keep the landing point safe and within reach: back to the downwind
+    /// target whenever that is, otherwise hold the current point while it
+    /// still is, otherwise search for the nearest one that is.
+    fn update_landing_target(&mut self, altitude_ft: f32) {
+        let fence = &self.geofence;
+        let (centre, glide_m) = self.reach(altitude_ft);
+        let in_reach = |q: Point, factor: f32| pattern::distance(q, centre) <= glide_m * factor;
+        let commanded = fence.local_m(self.target_downwind);
+        let current = fence.local_m(self.landing_target);
+        let on_commanded = self.landing_target == self.target_downwind;
+
+        if fence.is_safe(commanded) && in_reach(commanded, if on_commanded { 1.0 } else { FENCE_REACH_FACTOR }) {
+            self.landing_target = self.target_downwind;
+            return;
+        }
+        if fence.is_safe(current) && in_reach(current, 1.0) {
+            return;
+        }
+        // The search is the expensive part; don't run it every cycle while
+        // there is nothing to find
+        if self.fence_search_ms.is_some_and(|t| self.curr_time_ms.saturating_sub(t) < FENCE_SEARCH_PERIOD_MS) {
+            self.fence_flags |= geofence::FENCE_NO_SAFE_TARGET;
+            return;
+        }
+        self.fence_search_ms = Some(self.curr_time_ms);
+        match fence.nearest_safe(commanded, centre, glide_m * FENCE_REACH_FACTOR) {
+            Some(q) => self.landing_target = fence.from_local(q),
+            None => self.fence_flags |= geofence::FENCE_NO_SAFE_TARGET,
+        }
+    }
+
+    /// Where the canopy can still land, in the fence frame: the still-air
+    /// glide to the ground (radius, m) about the point the wind carries it
+    /// to meanwhile.
+    fn reach(&self, altitude_ft: f32) -> (Point, f32) {
+        let to_ground_s = (altitude_ft / FT_PER_M).max(0.0) / self.sink_rate_ms.max(1.0);
+        let (wind_from_deg, wind_speed_ms) = self.pattern_wind();
+        let wind_to = (wind_from_deg + 180.0) * DEG_TO_RAD;
+        let drift = wind_speed_ms * to_ground_s;
+        let p = self.geofence.local_m(self.position);
+        let centre = (p.0 + drift * libm::cosf(wind_to), p.1 + drift * libm::sinf(wind_to));
+        (centre, self.airspeed_ms * to_ground_s)
+    }
+
+    /// Swing the heading setpoint `desired` until the track ahead clears
+    /// every keep-out, the smaller swing first, towards the current track
+    /// on a tie, and leading the turn round the long way when the short one
+    /// would sweep across a keep-out; straight out of one the canopy is
+    /// already inside. Returns `desired` if nothing clears.
+    fn avoid_keep_outs(&mut self, desired: f32) -> f32 {
+        let fence = &self.geofence;
+        if fence.is_empty() {
+            return desired;
+        }
+        let p = fence.local_m(self.position);
+        if let Some(zone) = fence.keep_out_at(p) {
+            let exit = fence.exit_point(zone, p);
+            return wrap360(libm::atan2f(exit.1 - p.1, exit.0 - p.0) * RAD_TO_DEG);
+        }
+
+        let (vn, ve) = (self.vel_n as f32 * 1e-3, self.vel_e as f32 * 1e-3);
+        let ground_speed = libm::sqrtf(vn * vn + ve * ve);
+        // No further than the landing point, which is clear by construction
+        let to_target = pattern::distance(p, fence.local_m(self.landing_target));
+        let look_m = (ground_speed * FENCE_LOOKAHEAD_S).max(FENCE_LOOKAHEAD_MIN_M).min(to_target);
+        let clear = |heading: f32| {
+            let h = heading * DEG_TO_RAD;
+            fence.path_clear(p, (p.0 + look_m * libm::cosf(h), p.1 + look_m * libm::sinf(h)))
+        };
+        let track = libm::atan2f(ve, vn) * RAD_TO_DEG;
+        let mut setpoint = desired;
+        if !clear(desired) {
+            let first = if compute_heading_error(track, desired) < 0.0 { -1.0 } else { 1.0 };
+            let steps = (180.0 / FENCE_DEFLECT_STEP_DEG) as i32;
+            setpoint = (1..=steps)
+                .flat_map(|k| [first, -first].map(|side| wrap360(desired + side * k as f32 * FENCE_DEFLECT_STEP_DEG)))
+                .find(|&heading| clear(heading))
+                .unwrap_or(desired);
+            self.fence_flags |= geofence::FENCE_AVOIDING;
+        }
+        if ground_speed * 1e3 < GSPEED_MIN_FOR_HEADING as f32 {
+            return setpoint;
+        }
+
+        // The controller turns the short way onto the setpoint; if that swings
+        // the track across a keep-out, lead it round the long way instead,
+        // never asking for more than a quarter turn at a time
+        let turn_clear = |turn: f32| {
+            let steps = (libm::fabsf(turn) / FENCE_DEFLECT_STEP_DEG) as i32;
+            (1..=steps).all(|k| clear(track + turn.signum() * k as f32 * FENCE_DEFLECT_STEP_DEG))
+        };
+        let short = compute_heading_error(setpoint, track);
+        if turn_clear(short) {
+            return setpoint;
+        }
+        let long = short - 360.0 * short.signum();
+        if !turn_clear(long) {
+            return setpoint;
+        }
+        self.fence_flags |= geofence::FENCE_AVOIDING;
+        wrap360(track + long.signum() * libm::fabsf(long).min(90.0))
+    }
+
+    /// Low-pass the canopy's sink rate and airspeed (ground velocity less
+    /// the wind) for pattern planning.
+    fn measure_performance(&mut self) {
+        let (wind_from_deg, wind_speed_ms) = self.pattern_wind();
+        let wind_to = (wind_from_deg + 180.0) * DEG_TO_RAD;
+        let air_n = self.vel_n as f32 * 1e-3 - wind_speed_ms * libm::cosf(wind_to);
+        let air_e = self.vel_e as f32 * 1e-3 - wind_speed_ms * libm::sinf(wind_to);
+        let airspeed = libm::sqrtf(air_n * air_n + air_e * air_e);
+        let sink = self.vel_d as f32 * 1e-3;
+        self.airspeed_ms += PERF_FILTER_ALPHA * (airspeed - self.airspeed_ms);
+        self.sink_rate_ms += PERF_FILTER_ALPHA * (sink - self.sink_rate_ms);
+    }
+
+    /// Wind the pattern is planned for, (degrees FROM, m/s): the in-flight
+    /// estimate once there is one, otherwise the forecast on final, where it
+    /// matters most.
+    fn pattern_wind(&self) -> (f32, f32) {
+        match self.wind.estimate() {
+            Some(w) => (w.from_deg(), w.speed_ms()),
+            None => self
+                .wind_profile
+                .at(PATTERN_FINAL_ALT_FT / FT_PER_M)
+                .unwrap_or((self.wind_from_deg, self.wind_speed_ms)),
+        }
+    }
+
     // =========================================================================
     // Desired heading per phase
     // =========================================================================
@@ -349,6 +752,7 @@ impl<'d> Blims<'d> {
         altitude_ft: f32,
     ) -> f32 {
         let altitude_m = altitude_ft / FT_PER_M;
+        #[allow(unused_variables)] // for the wind-relative headings commented out below
         let wind_from = self.get_wind_at_altitude(altitude_m);
         // Direction the wind blows TO (opposite of "from")
 
@@ -358,7 +762,7 @@ impl<'d> Blims<'d> {
                 // the west, so the canopy should point west (heading = 270°).
 
                 //wind_from,
-                self.calculate_bearing_to(self.target_upwind_lat, self.target_upwind_lon),
+                self.leg_heading(phase),
 
             Phase::Downwind =>
                 // Head WITH the wind.  Opposite of wind_from by 180°.
@@ -366,9 +770,13 @@ impl<'d> Blims<'d> {
                 // so the commanded heading doesn't shift every cycle as the
                 // canopy drifts.  This makes the PI response cleaner to analyse.
 
-                //Self::wrap360(wind_from + 180.0),
+                //wrap360(wind_from + 180.0),
 
-                self.calculate_bearing_to(self.target_downwind_lat, self.target_downwind_lon),
+                self.leg_heading(phase),
+
+            Phase::Loiter      => self.pattern.loiter_heading(self.position_m()),
+            Phase::Track | Phase::DownwindLeg | Phase::Base | Phase::Final =>
+                self.leg_heading(phase),
 
             // Held / Neutral do not use heading control
             _ => 0.0,
@@ -380,75 +788,79 @@ impl<'d> Blims<'d> {
     // UTILITY FUNCTIONS
     // =========================================================================
 
-    /// Normalise angle to [0, 360)
-    #[inline]
-    pub fn wrap360(mut a: f32) -> f32 {
-        a %= 360.0;
-        if a < 0.0 { a += 360.0; }
-        a
-    }
-
-    /// Normalise angle to (−180, 180]
-    #[inline]
-    pub fn wrap180(mut a: f32) -> f32 {
-        a %= 360.0;
-        if      a >  180.0 { a -= 360.0; }
-        else if a < -180.0 { a += 360.0; }
-        a
-    }
-
-    /// Heading error in (−180, 180]: positive → turn right, negative → turn left.
-    #[inline]
-    pub fn compute_heading_error(desired: f32, actual: f32) -> f32 {
-        Self::wrap180(desired - actual)
+    /// Leg flown in `phase`, (start, end): from the phase entry point to
+    /// the waypoint for the MVP phases and Track, between turn points on
+    /// the pattern. `None` while loitering or not steering.
+    fn leg(&self, phase: Phase) -> Option<(LatLon, LatLon)> {
+        let t = self.landing_target;
+        match phase {
+            Phase::Upwind      => Some((self.leg_start, self.target_upwind)),
+            Phase::Downwind    => Some((self.leg_start, t)),
+            Phase::Track       => Some((self.leg_start, t.from_local(self.pattern.entry()))),
+            Phase::DownwindLeg => Some((t.from_local(self.pattern.entry()), t.from_local(self.pattern.base_turn()))),
+            Phase::Base        => Some((t.from_local(self.pattern.base_turn()), t.from_local(self.pattern.final_turn()))),
+            Phase::Final       => Some((t.from_local(self.pattern.final_turn()), t)),
+            _ => None,
+        }
     }
 
-    /// Bearing from current GPS position to target, degrees [0, 360), 0 = North CW.
-    /// Flat-earth approximation; accurate to <0.1° under 2 km at mid-latitudes.
-    fn calculate_bearing_to(&self, target_lat: f32, target_lon: f32) -> f32 {
-        let d_lat = target_lat - self.gps_lat;
-        let d_lon = target_lon - self.gps_lon;
-        let lat_rad = self.gps_lat * DEG_TO_RAD;
-        let d_lon_corrected = d_lon * libm::cosf(lat_rad);
-        let bearing_rad = libm::atan2f(d_lon_corrected, d_lat);
-        Self::wrap360(bearing_rad * RAD_TO_DEG)
+    /// Heading setpoint along the leg of `phase` under the tracking law:
+    /// the bearing to its end point, or (L1) to the point L1_DISTANCE_M
+    /// beyond the canopy's projection onto it, clamped to the end point.
+    fn leg_heading(&self, phase: Phase) -> f32 {
+        let Some((start, end)) = self.leg(phase) else { return 0.0 };
+        let aim = match self.tracking_law {
+            TrackingLaw::Bearing => end,
+            TrackingLaw::L1 => {
+                let along = self.position.along_track_m(start, end).max(0.0) + L1_DISTANCE_M as f64;
+                if along >= start.distance_m(end) {
+                    end
+                } else {
+                    start.offset(start.bearing_deg(end), along)
+                }
+            }
+        };
+        self.position.bearing_deg(aim) as f32
     }
 
-    /// Distance from current GPS position to target in metres.
-    fn calculate_distance_to_target(&self, target_lat: f32, target_lon: f32) -> f32 {
-        let d_lat = target_lat - self.gps_lat;
-        let d_lon = target_lon - self.gps_lon;
-        // 111 320 m per degree of latitude at equator
-        let lat_rad  = self.gps_lat * DEG_TO_RAD;
-        let d_north  = d_lat * 111_320.0;
-        let d_east   = d_lon * 111_320.0 * libm::cosf(lat_rad);
-        libm::sqrtf(d_north * d_north + d_east * d_east)
+    /// Current GPS position north/east of the landing target (m), the
+    /// frame the landing pattern is planned in.
+    fn position_m(&self) -> Point {
+        self.position.local_m(self.landing_target)
     }
 
     /// Interpolate wind direction (degrees FROM) at the given altitude (metres).
     /// Falls back to the scalar `wind_from_deg` if no profile has been loaded.
     fn get_wind_at_altitude(&self, altitude_m: f32) -> f32 {
-        if self.wind_profile_size == 0 {
-            return self.wind_from_deg;
-        }
-        let alts = &self.wind_altitudes_m[..self.wind_profile_size];
-        let dirs = &self.wind_dirs_deg[..self.wind_profile_size];
-
-        // Clamp to profile bounds
-        if altitude_m <= alts[0] {
-            return dirs[0];
-        }
-        if altitude_m >= alts[self.wind_profile_size - 1] {
-            return dirs[self.wind_profile_size - 1];
-        }
-        // Linear interpolation between bracketing layers
-        for i in 0..self.wind_profile_size - 1 {
-            if altitude_m >= alts[i] && altitude_m < alts[i + 1] {
-                let t = (altitude_m - alts[i]) / (alts[i + 1] - alts[i]);
-                return dirs[i] + t * (dirs[i + 1] - dirs[i]);
-            }
-        }
-        dirs[0] // unreachable, but required for type completeness
+        self.wind_profile
+            .at(altitude_m)
+            .map_or(self.wind_from_deg, |(from_deg, _)| from_deg)
     }
 }
 
+// =============================================================================
+// Angle helpers
+// =============================================================================
+
+/// Normalise angle to [0, 360)
+#[inline]
+pub fn wrap360(mut a: f32) -> f32 {
+    a %= 360.0;
+    if a < 0.0 { a += 360.0; }
+    a
+}
+
+/// Normalise angle to (−180, 180]
+#[inline]
+pub fn wrap180(mut a: f32) -> f32 {
+    a %= 360.0;
+    if      a >  180.0 { a -= 360.0; }
+    else if a < -180.0 { a += 360.0; }
+    a
+}
+
+/// Heading error in (−180, 180]: positive → turn right, negative → turn left.
+#[inline]
+pub fn compute_heading_error(desired: f32, actual: f32) -> f32 {
+    wrap180(desired - actual)
+}
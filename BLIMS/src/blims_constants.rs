@@ -1,50 +1,51 @@
-/// BLIMS Constants
-
-/// Motor Representation: we want to represent normalized PWM values (between 0 and 1) in actual inches to represent physical brakeline differential values
-/// We want to have a max of 4.5 inches of pull to either side (-9 to 9 inches of differential)
-/// `set_motor_position()` is the single conversion point to PWM:
-///
-///   pwm_normalized = (position_in − MOTOR_MIN) / (MOTOR_MAX − MOTOR_MIN)
-///                  = (position_in + 9) / 18       ∈ [0, 1]
-///   duty_cycle     = 5 % + pwm_normalized × 5 %   ∈ [5 %, 10 %]
-
-///________________________|____left_turn____|___neutral___|__right_turn__|
-/// pwm.                   |        0        |     0.5     |       1      |
-/// controller output.     |       -9        |      0      |       9      |
-/// brakeline differential |      -9 in.     |     0 in.   |      9 in.   |
-/// motor turns            |      -10.5      |      0.     |      10.5    |
-
-/// ** Right turn = positive inches.  Left turn = negative inches. **
-
-/// ## ODrive configuration dependency
-///
-/// This mapping requires the ODrive to be configured so that the full
-/// 5–10 % duty-cycle range (pwm_normalized 0 → 1) corresponds to
-/// exactly ±10.5 motor turns.  If `gpio8_pwm_mapping` min/max turns
-/// are ever changed, MOTOR_MIN/MAX here must be updated to match,
-/// and the PI gains must be rescaled accordingly (see below).
-///
-/// ## Gain rescaling from old [0, 1] PWM representation
-///
-///   Old: NEUTRAL=0.5,  authority from neutral = MOTOR_MAX−0.5 = 0.2
-///   New: NEUTRAL=0.0,  authority from neutral = MOTOR_MAX−0.0 = 9.0
-///   Scale factor: 9.0 / 0.2 = 45
-///
-///   new_Kp           = 0.009 × 45 = 0.405
-///   new_Ki           = 0.001 × 45 = 0.045
-///   new_INTEGRAL_MAX = 10.0  × 45 = 450
-///
-/// This preserves identical closed-loop behaviour — a given heading error
-/// applies the same *fraction* of total brakeline authority as before.
-///
-/// ## MVP altitude thresholds (from flight plan, image 2)
-///
-///   Main deploy ≈ 2000 ft AGL  (FSW activates BLiMS)
-///   Upwind leg:   2000 → 1000 ft   head into wind
-///   Downwind leg: 1000 →  200 ft   head with wind toward target
-///   Neutral:              < 200 ft  hands-off landing flare
-
-pub const M_PI: f32 = 3.141_592_653_589_793_238_462_643_383_279_502_88;
+//! BLIMS Constants
+//!
+//! Motor Representation: we want to represent normalized PWM values (between 0 and 1) in actual inches to represent physical brakeline differential values
+//! We want to have a max of 4.5 inches of pull to either side (-9 to 9 inches of differential)
+//! `Blims::set_brakeline_diff()` is the single conversion point to PWM, through
+//! the BRAKELINE_CALIBRATION table; the nominal (uncalibrated) table is
+//!
+//!   pwm_normalized = (position_in − MOTOR_MIN) / (MOTOR_MAX − MOTOR_MIN)
+//!                  = (position_in + 9) / 18       ∈ [0, 1]
+//!   duty_cycle     = 5 % + pwm_normalized × 5 %   ∈ [5 %, 10 %]
+//!
+//!________________________|____left_turn____|___neutral___|__right_turn__|
+//! pwm.                   |        0        |     0.5     |       1      |
+//! controller output.     |       -9        |      0      |       9      |
+//! brakeline differential |      -9 in.     |     0 in.   |      9 in.   |
+//! motor turns            |      -10.5      |      0.     |      10.5    |
+//!
+//! ** Right turn = positive inches.  Left turn = negative inches. **
+//!
+//! ## ODrive configuration dependency
+//!
+//! This mapping requires the ODrive to be configured so that the full
+//! 5–10 % duty-cycle range (pwm_normalized 0 → 1) corresponds to
+//! exactly ±10.5 motor turns.  If `gpio8_pwm_mapping` min/max turns
+//! are ever changed, MOTOR_MIN/MAX here must be updated to match,
+//! and the PI gains must be rescaled accordingly (see below).
+//!
+//! ## Gain rescaling from old [0, 1] PWM representation
+//!
+//!   Old: NEUTRAL=0.5,  authority from neutral = MOTOR_MAX−0.5 = 0.2
+//!   New: NEUTRAL=0.0,  authority from neutral = MOTOR_MAX−0.0 = 9.0
+//!   Scale factor: 9.0 / 0.2 = 45
+//!
+//!   new_Kp           = 0.009 × 45 = 0.405
+//!   new_Ki           = 0.001 × 45 = 0.045
+//!   new_INTEGRAL_MAX = 10.0  × 45 = 450
+//!
+//! This preserves identical closed-loop behaviour — a given heading error
+//! applies the same *fraction* of total brakeline authority as before.
+//!
+//! ## MVP altitude thresholds (from flight plan, image 2)
+//!
+//!   Main deploy ≈ 2000 ft AGL  (FSW activates BLiMS)
+//!   Upwind leg:   2000 → 1000 ft   head into wind
+//!   Downwind leg: 1000 →  200 ft   head with wind toward target
+//!   Neutral:              < 200 ft  hands-off landing flare
+
+pub const M_PI: f32 = core::f32::consts::PI;
 
 // unit conversions
 pub const DEG_TO_RAD: f32 = M_PI / 180.0;
@@ -58,13 +59,34 @@ pub const INITIAL_HOLD_THRESHOLD: u32 = 0; // parafoil stabilization//10_000
 pub const WRAP_CYCLE_COUNT: u16 = 65_535;
 
 // Motor Position Limits (inches of differential) 
-// conversion to raw PWM is done in `set_motor_position()` - every call to this this function uses these limits 
+// conversion to an RC pulse is done in `Blims::set_brakeline_diff()` through the calibration table - every call to this function uses these limits
 pub const NEUTRAL_POS: f32 = 0.0; // straight flight: neutral, both lines equal
 pub const MOTOR_MIN: f32 = -9.0;   // max left: -9 inches, motor reeled 9 in. left of neutral
 pub const MOTOR_MAX: f32 = 9.0;   // max right 9 inches, motor reeled 9 in. right of neutral
 
+// RC PWM into the ODrive (µs pulse in a 20 ms frame): 1000 full left, 1500
+// neutral, 2000 full right by the gpio8_pwm_mapping above
+pub const PWM_PERIOD_US: f32 = 20_000.0;
+pub const PULSE_MIN_US: f32 = 1_000.0;
+pub const PULSE_NEUTRAL_US: f32 = 1_500.0;
+pub const PULSE_MAX_US: f32 = 2_000.0;
+// Motor turns per inch of differential (±10.5 turns over ±9 in), to convert
+// an ODrive-reported position while calibrating
+pub const MOTOR_TURNS_PER_IN: f32 = 10.5 / 9.0;
+
+// Brakeline calibration (calibration.rs): (differential in, pulse µs), both
+// increasing. Every command goes through it. This is the nominal linear map;
+// replace it with the table the brakeline_cal bench example prints.
+pub const BRAKELINE_CALIBRATION: &[(f32, f32)] = &[(MOTOR_MIN, PULSE_MIN_US), (MOTOR_MAX, PULSE_MAX_US)];
+pub const MAX_CAL_POINTS: usize = 16;
+// Bench sweep: settle time before each measurement (ms)
+pub const CAL_SETTLE_MS: u64 = 3_000;
+
 // Maximum number of wind profile layers
 pub const MAX_WIND_LAYERS: usize = 20;
+// Forecast layer limits (WindLayer::is_valid)
+pub const WIND_LAYER_MAX_ALT_M: f32 = 10_000.0;
+pub const WIND_LAYER_MAX_SPEED_MS: f32 = 40.0;
 
 // PI controller gains — revalidate via car testing
 // Units: KP  [in/°],  KI  [in/(°·s)],  INTEGRAL_MAX  [°]
@@ -91,14 +113,92 @@ pub const KI: f32 = 0.002; // in/(deg·s)
 // downwind: when above alt_neutral_ft, downwind (head with wind)
 pub const ALT_UPWIND_FT: f32 = 1000.0; 
 pub const ALT_NEUTRAL_FT: f32  =  200.0; // hands off for landing flare (motor at 0 in.)
+// Once below a phase boundary, climb back this far above it before the
+// phase reverts, so baro noise at the boundary can't flap between phases
+pub const PHASE_HYSTERESIS_FT: f32 = 30.0;
 
 // Minimum groundspeed for reliable GPS heading (mm/s)
 pub const GSPEED_MIN_FOR_HEADING: i32 = 3_000; // 3 m/s
 
-
-
-
-
-
-
-
+// Heading filter (see heading.rs): GPS heading of motion blended with the
+// integrated gyro yaw rate. A fix's weight ramps from 0 at
+// GSPEED_MIN_FOR_HEADING to 1 at GSPEED_FULL_HEADING, and falls to 0 as
+// head_acc approaches HEADING_ACC_MAX_DEG
+pub const GSPEED_FULL_HEADING: i32 = 6_000;      // mm/s
+pub const HEADING_ACC_MAX_DEG: f32 = 30.0;
+pub const HEADING_GPS_TAU_S: f32 = 2.0;          // time constant pulling the gyro estimate onto GPS
+// Steer on the estimate this long after the last usable GPS heading, then
+// hold neutral until one arrives
+pub const HEADING_COAST_MS: u64 = 5_000;         // gyro carrying it
+pub const HEADING_GPS_ONLY_COAST_MS: u64 = 1_500; // no gyro: bridges one missed 1 Hz fix
+
+// Landing pattern (GuidanceMode::Pattern), feet AGL
+// Loiter above PATTERN_ENTRY_ALT_FT, then downwind leg → base → final, each
+// leg long enough to use up its altitude band at the measured sink rate and
+// the ground speed the wind gives on that heading. Final continues through
+// the ALT_NEUTRAL_FT hands-off flare, so its length covers the flare too.
+pub const PATTERN_ENTRY_ALT_FT: f32 = 1000.0; // leave loiter, turn onto downwind
+pub const PATTERN_BASE_ALT_FT: f32  =  600.0; // turn base (or at the base turn point)
+pub const PATTERN_FINAL_ALT_FT: f32 =  350.0; // turn final (or on crossing the final line)
+pub const PATTERN_LEFT_HAND: bool = true;     // left turns onto base/final and in the loiter
+pub const PATTERN_MIN_LEG_M: f32 = 30.0;      // shortest leg planned, whatever the sink rate
+pub const PATTERN_TURN_LEAD_M: f32 = 60.0;    // start a turn this far from its turn point (≈ turn radius)
+
+// Loiter circle, m
+pub const LOITER_RADIUS_M: f32  = 100.0;
+pub const LOITER_CAPTURE_M: f32 = 150.0; // Track → Loiter within this of the loiter point
+
+// Path following (TrackingLaw::L1): aim this far along the leg ahead of the
+// canopy. Shorter pulls back onto the line harder but overshoots; keep it
+// well above the turn radius (airspeed / turn rate ≈ 30 m)
+pub const L1_DISTANCE_M: f32 = 60.0;
+
+// Geofence (geofence.rs): keep-out zones and one landing zone, uploaded
+// before flight
+pub const MAX_FENCE_ZONES: usize = 8;
+pub const MAX_FENCE_VERTICES: usize = 16;
+pub const FENCE_MAX_EXTENT_M: f32 = 10_000.0; // vertices within this of the fence's first one
+pub const FENCE_MARGIN_M: f32 = 25.0;         // landing point / track clearance from keep-out edges (and inside the landing zone edge)
+// Heading bias: the track ahead, this many seconds of ground speed (at
+// least FENCE_LOOKAHEAD_MIN_M), must clear every keep-out; otherwise the
+// setpoint swings by FENCE_DEFLECT_STEP_DEG until it does
+pub const FENCE_LOOKAHEAD_S: f32 = 12.0;
+pub const FENCE_LOOKAHEAD_MIN_M: f32 = 40.0;
+pub const FENCE_DEFLECT_STEP_DEG: f32 = 10.0;
+// Landing point: reach is the still-air glide to the ground (airspeed ×
+// time to ground) drifted with the wind. A new point must lie within
+// FENCE_REACH_FACTOR of it; the point being flown to is kept while it is
+// within the full glide
+pub const FENCE_REACH_FACTOR: f32 = 0.8;
+// Search for a new point: rings FENCE_SEARCH_STEP_M apart around the
+// downwind target, points about as far apart along the part of each ring in
+// reach, out to FENCE_SEARCH_RADIUS_M; at most once per FENCE_SEARCH_PERIOD_MS
+pub const FENCE_SEARCH_STEP_M: f32 = 40.0;
+pub const FENCE_SEARCH_RADIUS_M: f32 = 5_000.0;
+pub const FENCE_SEARCH_PERIOD_MS: u64 = 1_000;
+
+// Canopy performance used to plan the pattern until measured (m/s); see
+// blims::sim::Canopy::NOMINAL
+pub const NOMINAL_AIRSPEED_MS: f32  = 7.5;
+pub const NOMINAL_SINK_RATE_MS: f32 = 8.2;
+// Low-pass weight per cycle for the measured sink rate and airspeed
+pub const PERF_FILTER_ALPHA: f32 = 0.05;
+
+
+
+
+
+
+
+
+
+// In-flight wind estimate (wind.rs): circle fit of GPS ground velocity
+pub const WIND_FIT_POINTS: usize = 36;          // fit points kept, one per WIND_SAMPLE_SPACING_DEG of turn
+pub const WIND_SAMPLE_SPACING_DEG: f32 = 10.0;  // ground-track change before the next fit point
+pub const WIND_SAMPLE_MAX_AGE_MS: u64 = 60_000; // drop older points, ~500 m of descent
+pub const WIND_MIN_ARC_DEG: f32 = 120.0;        // track must sweep this much before the first fit
+pub const WIND_FIT_MIN_AIRSPEED_MS: f32 = 3.0;  // reject fits outside these airspeeds (circle radius)
+pub const WIND_FIT_MAX_AIRSPEED_MS: f32 = 20.0;
+pub const WIND_FIT_MAX_SPEED_MS: f32 = 20.0;    // or with a wind above this
+pub const WIND_FILTER_ALPHA: f32 = 0.3;         // low-pass weight per accepted fit
+pub const WIND_PROFILE_BAND_M: f32 = 100.0;     // logged profile band height; MAX_WIND_LAYERS bands
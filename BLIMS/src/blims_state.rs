@@ -6,14 +6,21 @@
 // 2 = Upwind (head INTO wind - wind_from direction)
 // 3 = Downwind (head WITH wind - wind_from + 180 degrees)
 // 4 = Neutral (below ALT_NEUTRAL_FT, hands-off))
+// Landing pattern (GuidanceMode::Pattern) instead of 2–3:
+// 5 = Track (fly to the loiter point)
+// 6 = Loiter (circle the loiter point until pattern entry altitude)
+// 7 = DownwindLeg (with the wind, abeam the target)
+// 8 = Base (crosswind, toward the final approach line)
+// 9 = Final (into the wind, toward the target)
 
 
-use crate::blims_constants::*;
-
 // ============================================================================
 // Types used by the new Blims controller (blims.rs)
 // ============================================================================
 
+use crate::blims_constants::*;
+use crate::geo::LatLon;
+
 /// Flight phase — value matches the integer logged in the CSV output.
 #[repr(i8)]
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -23,12 +30,104 @@ pub enum Phase {
     Upwind    = 2, // altitude above ALT_UPWIND_FT - observe parafoil response to a controlled heading hold, stabilize canopy after deployment
     Downwind = 3, // altitude between ALT_NEUTRAL_FT and ALT_UPWIND_FT - head with wind toward target, observe turn-reversal dynamics
     Neutral   = 4, // altitude below ALT_NEUTRAL_FT - motor returns to neutral 
+    Track     = 5, // pattern: steer to the loiter point
+    Loiter    = 6, // pattern: circle the loiter point above PATTERN_ENTRY_ALT_FT
+    DownwindLeg = 7, // pattern: with the wind to the base turn point
+    Base      = 8, // pattern: crosswind to the final turn point
+    Final     = 9, // pattern: into the wind to the target until ALT_NEUTRAL_FT
+}
+
+impl Phase {
+    /// One of the landing-pattern legs (GuidanceMode::Pattern).
+    pub fn is_pattern(self) -> bool {
+        matches!(self, Phase::Track | Phase::Loiter | Phase::DownwindLeg | Phase::Base | Phase::Final)
+    }
+}
+
+/// Which guidance law runs between main deployment and ALT_NEUTRAL_FT.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum GuidanceMode {
+    /// MVP: Upwind above ALT_UPWIND_FT, Downwind below, each steering to
+    /// its own waypoint.
+    #[default]
+    Mvp,
+    /// Full landing pattern to the downwind target: Track → Loiter →
+    /// DownwindLeg → Base → Final, legs sized from the measured sink rate and
+    /// the wind.
+    Pattern,
+}
+
+/// How the heading setpoint follows the leg being flown (MVP waypoint legs
+/// and the pattern legs; the loiter circle has its own law).
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum TrackingLaw {
+    /// Steer straight at the leg's end point. Crosswind drift off the leg
+    /// is never flown back out, the path just bends.
+    #[default]
+    Bearing,
+    /// L1 path following: steer at the point L1_DISTANCE_M further along
+    /// the leg than the canopy, which pulls it back onto the line.
+    L1,
+}
+
+/// Controller gains and phase altitudes, passed to `Blims::new` and
+/// changeable before flight; the defaults are the values in
+/// `blims_constants`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BlimsConfig {
+    /// PI gains, in/° and in/(°·s).
+    pub kp: f32,
+    pub ki: f32,
+    /// Most brakeline differential the I term may contribute (in).
+    pub integral_max_in: f32,
+    /// Upwind above, Downwind below (MVP), ft AGL.
+    pub alt_upwind_ft: f32,
+    /// Hands-off flare below, ft AGL.
+    pub alt_neutral_ft: f32,
+    /// Climb back this far above a boundary before its phase reverts (ft).
+    pub phase_hysteresis_ft: f32,
+    /// Brakelines held at neutral this long after the first fix (ms).
+    pub initial_hold_ms: u32,
+}
+
+impl BlimsConfig {
+    /// Rejects values no drop of this canopy could use, so a typo or a
+    /// unit slip (m for ft, s for ms) can't reach the controller. The
+    /// Upwind boundary must clear the Neutral one by the hysteresis.
+    pub fn is_valid(&self) -> bool {
+        let in_range = |v: f32, lo: f32, hi: f32| v.is_finite() && v >= lo && v <= hi;
+        in_range(self.kp, 0.001, 1.0)
+            && in_range(self.ki, 0.0, 0.1)
+            && in_range(self.integral_max_in, 0.0, MOTOR_MAX)
+            && in_range(self.alt_neutral_ft, 0.0, 2_000.0)
+            && in_range(self.phase_hysteresis_ft, 0.0, 200.0)
+            && in_range(self.alt_upwind_ft, self.alt_neutral_ft + self.phase_hysteresis_ft, 10_000.0)
+            && self.initial_hold_ms <= 60_000
+    }
+}
+
+impl Default for BlimsConfig {
+    fn default() -> Self {
+        Self {
+            kp: KP,
+            ki: KI,
+            integral_max_in: INTEGRAL_MAX_INCHES,
+            alt_upwind_ft: ALT_UPWIND_FT,
+            alt_neutral_ft: ALT_NEUTRAL_FT,
+            phase_hysteresis_ft: PHASE_HYSTERESIS_FT,
+            initial_hold_ms: INITIAL_HOLD_THRESHOLD,
+        }
+    }
 }
 
 /// Sensor data passed into Blims::execute() every cycle.
 /// All GPS fields come directly from the u-blox UBX-NAV-PVT message.
-#[derive(Debug, Default)]
+#[derive(Clone, Copy, Debug, Default)]
 pub struct BlimsDataIn {
+    // GPS time of week of the solution (ms); the FSW repeats a solution
+    // every cycle until the next one, this tells them apart
+    pub itow: u32,
+
     // Position
     pub lon: i32, // Longitude  × 1e7
     pub lat: i32, // Latitude   × 1e7
@@ -51,11 +150,16 @@ pub struct BlimsDataIn {
 
     // Accuracy estimates
     pub s_acc:    u32, // Speed acceleration   (mm/s)
-    pub head_acc: u32, // Heading acceleration × 1e5 (degrees)
+    pub head_acc: u32, // Heading accuracy     × 1e5 (degrees)
 
     // GPS status
     pub fix_type:  u8,   // 0=none, 2=2D, 3=3D, 4=3D+DGPS
     pub gps_state: bool, // validity flag from FSW
+
+    // Gyro rate about the vertical, positive turning right (clockwise seen
+    // from above); see heading.rs
+    pub yaw_rate_dps:   f32,
+    pub yaw_rate_valid: bool,
 }
 
 /// Control outputs returned from Blims::execute() every cycle.
@@ -68,139 +172,38 @@ pub struct BlimsDataOut {
     pub pid_p: f32, //degrees of error × KP
     pub pid_i: f32, // degrees * s x Ki
     pub bearing: f32,    // bearing to target, degrees [0, 360)
-    pub phase_id: i8,    // Phase as integer (0–3)
-}
-
-// ============================================================================
-// Legacy types — kept for backwards compatibility
-// ============================================================================
-
-#[derive(Debug)]
-pub enum BLIMSMode {
-    STANDBY,
-    LV,
-}
-
-#[derive(Debug)]
-pub struct BLIMSDataIn {
-    pub lon:         i32,
-    pub lat:         i32,
-    pub altitude_ft: f32,
-    pub h_acc:       u32,
-    pub v_acc:       u32,
-    pub vel_n:       i32,
-    pub vel_e:       i32,
-    pub vel_d:       i32,
-    pub g_speed:     i32,
-    pub head_mot:    i32,
-    pub s_acc:       u32,
-    pub head_acc:    u32,
-    pub fix_type:    u8,
-    pub gps_state:   bool,
-}
-
-#[derive(Clone, Copy, Default, Debug)]
-pub struct BLIMSDataOut {
-    pub brakeline_diff_in: f32,
-    pub pid_p:          f32,
-    pub pid_i:          f32,
-    pub bearing:        f32,
-    pub phase_id:       i8,
-    pub loiter_step:    i8,
-}
-
-#[derive(Debug)]
-pub struct Flight {
-    pub blims_pwm_pin:    u8,
-    pub blims_enable_pin: u8,
-    pub blims_init:       bool,
-    pub flight_mode:      BLIMSMode,
-    pub brakeline_diff_in:   f32,
-    pub data_out:         BLIMSDataOut,
-    pub gps_lon:          f32,
-    pub gps_lat:          f32,
-    pub altitude_ft:      f32,
-    pub h_acc:            u32,
-    pub v_acc:            u32,
-    pub vel_n:            i32,
-    pub vel_e:            i32,
-    pub vel_d:            i32,
-    pub g_speed:          i32,
-    pub head_mot:         i32,
-    pub s_acc:            u32,
-    pub head_acc:         u32,
-    pub fix_type:         u8,
-    pub curr_time:        u32,
-    pub prev_time:        u32,
-    pub time_passed:      u32,
-}
-
-impl Default for Flight {
-    fn default() -> Self {
-        Self {
-            blims_pwm_pin:    0,
-            blims_enable_pin: 0,
-            blims_init:       false,
-            flight_mode:      BLIMSMode::STANDBY,
-            brakeline_diff_in:   0.0,
-            data_out:         BLIMSDataOut::default(),
-            gps_lon:          0.0,
-            gps_lat:          0.0,
-            altitude_ft:      0.0,
-            h_acc:            0,
-            v_acc:            0,
-            vel_n:            0,
-            vel_e:            0,
-            vel_d:            0,
-            g_speed:          0,
-            head_mot:         0,
-            s_acc:            0,
-            head_acc:         0,
-            fix_type:         0,
-            curr_time:        0,
-            prev_time:        0,
-            time_passed:      0,
-        }
-    }
-}
-
-#[derive(Debug)]
-pub struct LV {
-    pub target_lat:        f32,
-    pub target_lon:        f32,
-    pub bearing:           f32,
-    pub prev_error:        f32,
-    pub pid_p:             f32,
-    pub pid_i:             f32,
-    pub gps_state:         bool,
-    pub error_integral:    f32,
-    pub wind_from_deg:     f32,
-    pub wind_profile_size: usize,
-    pub wind_altitudes_m:  [f32; MAX_WIND_LAYERS],
-    pub wind_dirs_deg:     [f32; MAX_WIND_LAYERS],
-}
-
-impl Default for LV {
-    fn default() -> Self {
-        Self {
-            target_lat:        0.0,
-            target_lon:        0.0,
-            bearing:           0.0,
-            prev_error:        0.0,
-            pid_p:             0.0,
-            pid_i:             0.0,
-            gps_state:         false,
-            error_integral:    0.0,
-            wind_from_deg:     0.0,
-            wind_profile_size: 0,
-            wind_altitudes_m:  [0.0; MAX_WIND_LAYERS],
-            wind_dirs_deg:     [0.0; MAX_WIND_LAYERS],
-        }
-    }
-}
-
-#[derive(Debug, Default)]
-pub struct BLIMSState {
-    pub flight: Flight,
-    pub lv:     LV,
+    pub phase_id: i8,    // Phase as integer (0–9)
+    /// In-flight wind estimate (see `wind`); zero until `wind_valid`.
+    pub wind_speed_ms: f32,
+    pub wind_from_deg: f32, // degrees FROM, [0, 360)
+    pub wind_valid: bool,
+    /// Great-circle distance to the active waypoint (m): the upwind target
+    /// in Upwind, the landing target otherwise. Held through GPS dropouts.
+    pub distance_to_target_m: f32,
+    /// Signed distance off the leg being flown (m), positive right of it;
+    /// 0 when not on a leg (loitering, not steering).
+    pub cross_track_m: f32,
+    /// Heading the PI loop steers on (gyro-aided, see `heading`);
+    /// `heading_valid` false while BLiMS holds neutral for want of one.
+    pub heading_deg: f32,
+    pub heading_valid: bool,
+    /// `geofence::FENCE_*` bits for this cycle, held through GPS dropouts;
+    /// 0 with no geofence.
+    pub fence_flags: u8,
+    /// Where BLiMS is landing: the downwind target, or the safe point the
+    /// geofence moved it to.
+    pub landing_target: LatLon,
+    /// Controller internals, for tuning after a drop. Heading the PI loop
+    /// steered for (after keep-out avoidance) and its error from
+    /// `heading_deg`, wrapped to ±180; both 0 when not steering.
+    pub desired_heading_deg: f32,
+    pub heading_error_deg: f32,
+    /// Clamped heading-error integral (deg·s)
+    pub error_integral: f32,
+    /// Step the controller used this cycle (s), after clamping
+    pub dt_s: f32,
+    /// The fix decision: `gps_state` with at least a 2D fix
+    pub gps_valid: bool,
+    /// When control first left Held (ms, `execute`'s clock); `None` before
+    pub activation_time_ms: Option<u64>,
 }
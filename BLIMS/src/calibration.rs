@@ -0,0 +1,192 @@
+//! Brakeline calibration: the RC pulse that gives a wanted differential.
+//!
+//! The ODrive maps its 1–2 ms PWM input linearly onto motor turns, but line
+//! stretch, the spool diameter changing as line winds on, and backlash make
+//! the brakeline differential that results anything but linear. On the bench,
+//! [`Characterisation`] steps the winch through a sweep of pulses, up and then
+//! back down; after each step settles, the operator records the differential,
+//! either measured on the lines or the ODrive-reported position converted
+//! with `MOTOR_TURNS_PER_IN`. [`Characterisation::fit`] averages the two
+//! sweeps into a [`CalibrationTable`], which [`Blims`](crate::Blims) maps
+//! every command through. The two sweeps' spread is reported as the
+//! hysteresis.
+
+use core::fmt;
+
+use crate::blims_constants::*;
+
+/// Piecewise-linear map from brakeline differential (in) to pulse width (µs),
+/// both strictly increasing.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CalibrationTable {
+    points: [(f32, f32); MAX_CAL_POINTS],
+    len: usize,
+}
+
+impl Default for CalibrationTable {
+    fn default() -> Self {
+        Self::LINEAR
+    }
+}
+
+impl CalibrationTable {
+    /// The nominal ±9 in ↔ 1000–2000 µs map.
+    pub const LINEAR: Self = {
+        let mut points = [(0.0, 0.0); MAX_CAL_POINTS];
+        points[0] = (MOTOR_MIN, PULSE_MIN_US);
+        points[1] = (MOTOR_MAX, PULSE_MAX_US);
+        Self { points, len: 2 }
+    };
+
+    /// `None` unless there are 2..=MAX_CAL_POINTS points, both columns
+    /// strictly increase and every pulse is within PULSE_MIN_US..=PULSE_MAX_US.
+    pub fn new(points: &[(f32, f32)]) -> Option<Self> {
+        if !(2..=MAX_CAL_POINTS).contains(&points.len())
+            || !points.iter().all(|&(inches, pulse)| inches.is_finite() && (PULSE_MIN_US..=PULSE_MAX_US).contains(&pulse))
+            || points.windows(2).any(|w| w[1].0 <= w[0].0 || w[1].1 <= w[0].1)
+        {
+            return None;
+        }
+        let mut table = Self { points: [(0.0, 0.0); MAX_CAL_POINTS], len: points.len() };
+        table.points[..points.len()].copy_from_slice(points);
+        Some(table)
+    }
+
+    /// (differential in, pulse µs)
+    pub fn points(&self) -> &[(f32, f32)] {
+        &self.points[..self.len]
+    }
+
+    /// Pulse for `brakeline_in`, interpolated; beyond the table the end
+    /// pulse, the furthest the winch was seen to go.
+    pub fn pulse_us(&self, brakeline_in: f32) -> f32 {
+        interpolate(self.points(), brakeline_in, |p| p)
+    }
+
+    /// Inverse of [`pulse_us`](Self::pulse_us).
+    pub fn brakeline_in(&self, pulse_us: f32) -> f32 {
+        interpolate(self.points(), pulse_us, |(inches, pulse)| (pulse, inches))
+    }
+}
+
+/// Prints the table as the `BRAKELINE_CALIBRATION` initialiser.
+impl fmt::Display for CalibrationTable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "&[")?;
+        for (i, (inches, pulse)) in self.points().iter().enumerate() {
+            write!(f, "{}({:.2}, {:.1})", if i == 0 { "" } else { ", " }, inches, pulse)?;
+        }
+        write!(f, "]")
+    }
+}
+
+/// `y` at `x` along `points` viewed through `xy` as (x, y), x increasing;
+/// held beyond the ends.
+fn interpolate(points: &[(f32, f32)], x: f32, xy: impl Fn((f32, f32)) -> (f32, f32)) -> f32 {
+    let (x0, y0) = xy(points[0]);
+    if x <= x0 {
+        return y0;
+    }
+    for w in points.windows(2) {
+        let ((xa, ya), (xb, yb)) = (xy(w[0]), xy(w[1]));
+        if x <= xb {
+            return ya + (yb - ya) * (x - xa) / (xb - xa);
+        }
+    }
+    xy(points[points.len() - 1]).1
+}
+
+/// Why a sweep couldn't be fitted.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FitError {
+    /// Steps still to measure
+    Incomplete,
+    /// The averaged differential didn't rise with every pulse step (backlash
+    /// bigger than a step, or a slipping line)
+    NotMonotonic,
+}
+
+/// A fitted table and how far the winch strayed from the nominal one.
+#[derive(Clone, Copy, Debug)]
+pub struct CalibrationFit {
+    pub table: CalibrationTable,
+    /// Largest up-sweep / down-sweep difference at one pulse (in)
+    pub hysteresis_in: f32,
+    /// Largest difference from the nominal linear map (in)
+    pub linear_error_in: f32,
+}
+
+/// Bench sweep over `points` pulses evenly spaced from PULSE_MIN_US to
+/// PULSE_MAX_US, visited up then down. For each step: command
+/// [`pulse_us`](Self::pulse_us), wait `CAL_SETTLE_MS`, then
+/// [`record`](Self::record) the differential.
+#[derive(Clone, Copy, Debug)]
+pub struct Characterisation {
+    points: usize,
+    step: usize,
+    measured: [f32; 2 * MAX_CAL_POINTS],
+}
+
+impl Characterisation {
+    /// `None` unless 2 ≤ `points` ≤ MAX_CAL_POINTS.
+    pub fn new(points: usize) -> Option<Self> {
+        (2..=MAX_CAL_POINTS)
+            .contains(&points)
+            .then_some(Self { points, step: 0, measured: [0.0; 2 * MAX_CAL_POINTS] })
+    }
+
+    /// Total steps, both sweeps.
+    pub fn steps(&self) -> usize {
+        2 * self.points
+    }
+
+    /// Steps recorded so far.
+    pub fn step(&self) -> usize {
+        self.step
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.step == self.steps()
+    }
+
+    /// Pulse for the current step; `None` once every step is recorded.
+    pub fn pulse_us(&self) -> Option<f32> {
+        (!self.is_done()).then(|| self.pulse_at(self.point_of(self.step)))
+    }
+
+    /// Differential measured at the current step (in); moves to the next.
+    pub fn record(&mut self, measured_in: f32) {
+        if !self.is_done() {
+            self.measured[self.step] = measured_in;
+            self.step += 1;
+        }
+    }
+
+    /// Table through the mean of the up and down measurements at each pulse.
+    pub fn fit(&self) -> Result<CalibrationFit, FitError> {
+        if !self.is_done() {
+            return Err(FitError::Incomplete);
+        }
+        let mut points = [(0.0, 0.0); MAX_CAL_POINTS];
+        let (mut hysteresis_in, mut linear_error_in) = (0.0_f32, 0.0_f32);
+        for (i, point) in points[..self.points].iter_mut().enumerate() {
+            let (up, down) = (self.measured[i], self.measured[self.steps() - 1 - i]);
+            let pulse = self.pulse_at(i);
+            let inches = (up + down) / 2.0;
+            hysteresis_in = hysteresis_in.max((up - down).abs());
+            linear_error_in = linear_error_in.max((inches - CalibrationTable::LINEAR.brakeline_in(pulse)).abs());
+            *point = (inches, pulse);
+        }
+        let table = CalibrationTable::new(&points[..self.points]).ok_or(FitError::NotMonotonic)?;
+        Ok(CalibrationFit { table, hysteresis_in, linear_error_in })
+    }
+
+    /// Pulse index of `step`: 0..points up, then back down.
+    fn point_of(&self, step: usize) -> usize {
+        if step < self.points { step } else { self.steps() - 1 - step }
+    }
+
+    fn pulse_at(&self, point: usize) -> f32 {
+        PULSE_MIN_US + (PULSE_MAX_US - PULSE_MIN_US) * point as f32 / (self.points - 1) as f32
+    }
+}
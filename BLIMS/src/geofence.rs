@@ -0,0 +1,337 @@
+//! Keep-out zones and the landing zone, uploaded before flight.
+//!
+//! A [`Geofence`] holds up to `MAX_FENCE_ZONES` simple polygons of up to
+//! `MAX_FENCE_VERTICES` vertices: keep-outs (roads, the range head, trees)
+//! and at most one landing zone. BLiMS uses it two ways (see `blims`):
+//!
+//! - the landing point must be *safe*: at least `FENCE_MARGIN_M` clear of
+//!   every keep-out and inside the landing zone, if there is one, by the
+//!   same margin. When the downwind target isn't, or the canopy can no
+//!   longer reach it, [`Geofence::nearest_safe`] picks the safe point
+//!   nearest to it within reach;
+//! - the heading setpoint is swung away from keep-outs until the track
+//!   ahead clears them by `FENCE_MARGIN_M` ([`Geofence::path_clear`]).
+//!
+//! Vertices are kept in degrees for upload, storage and the checksum, and
+//! once more in an f32 north/east frame (m) about the first vertex for the
+//! per-cycle geometry; every vertex must lie within `FENCE_MAX_EXTENT_M` of
+//! it so that frame stays accurate.
+
+use crate::blims_constants::*;
+use crate::geo::LatLon;
+use crate::pattern::{distance, Point};
+
+/// `BlimsDataOut::fence_flags` bits.
+pub const FENCE_IN_KEEP_OUT: u8 = 1 << 0;    // canopy inside a keep-out zone
+pub const FENCE_AVOIDING: u8 = 1 << 1;       // heading setpoint swung away from a keep-out
+pub const FENCE_TARGET_MOVED: u8 = 1 << 2;   // landing somewhere other than the downwind target
+pub const FENCE_NO_SAFE_TARGET: u8 = 1 << 3; // no safe point in reach; still flying to the last one
+pub const FENCE_OUTSIDE_LZ: u8 = 1 << 4;     // canopy outside the landing zone
+
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum ZoneKind {
+    #[default]
+    KeepOut = 0,
+    LandingZone = 1,
+}
+
+impl ZoneKind {
+    /// From the id stored in flash (the `repr`).
+    pub fn from_id(id: u8) -> Option<Self> {
+        match id {
+            0 => Some(Self::KeepOut),
+            1 => Some(Self::LandingZone),
+            _ => None,
+        }
+    }
+}
+
+/// One polygon, vertices in order (either winding), implicitly closed.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Zone {
+    kind: ZoneKind,
+    vertices: [LatLon; MAX_FENCE_VERTICES],
+    len: usize,
+}
+
+impl Zone {
+    const EMPTY: Self = Self {
+        kind: ZoneKind::KeepOut,
+        vertices: [LatLon::new(0.0, 0.0); MAX_FENCE_VERTICES],
+        len: 0,
+    };
+
+    /// `None` with fewer than 3 or more than `MAX_FENCE_VERTICES` vertices
+    /// or one off the globe. The shape is checked by `Geofence::from_zones`.
+    pub fn new(kind: ZoneKind, vertices: &[LatLon]) -> Option<Self> {
+        let on_globe = |v: &LatLon| (-90.0..=90.0).contains(&v.lat_deg) && (-180.0..=180.0).contains(&v.lon_deg);
+        if !(3..=MAX_FENCE_VERTICES).contains(&vertices.len()) || !vertices.iter().all(on_globe) {
+            return None;
+        }
+        let mut zone = Self { kind, ..Self::EMPTY };
+        zone.vertices[..vertices.len()].copy_from_slice(vertices);
+        zone.len = vertices.len();
+        Some(zone)
+    }
+
+    pub fn kind(&self) -> ZoneKind {
+        self.kind
+    }
+
+    pub fn vertices(&self) -> &[LatLon] {
+        &self.vertices[..self.len]
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Geofence {
+    zones: [Zone; MAX_FENCE_ZONES],
+    len: usize,
+    /// First vertex of the first zone, origin of `local`
+    origin: LatLon,
+    /// Zone vertices north/east of `origin` (m)
+    local: [[Point; MAX_FENCE_VERTICES]; MAX_FENCE_ZONES],
+}
+
+impl Default for Geofence {
+    fn default() -> Self {
+        Self::EMPTY
+    }
+}
+
+impl Geofence {
+    pub const EMPTY: Self = Self {
+        zones: [Zone::EMPTY; MAX_FENCE_ZONES],
+        len: 0,
+        origin: LatLon::new(0.0, 0.0),
+        local: [[(0.0, 0.0); MAX_FENCE_VERTICES]; MAX_FENCE_ZONES],
+    };
+
+    /// `None` with more than `MAX_FENCE_ZONES` zones or more than one
+    /// landing zone, or if a zone reaches further than
+    /// `FENCE_MAX_EXTENT_M` from the first vertex, has no area or crosses
+    /// itself.
+    pub fn from_zones(zones: &[Zone]) -> Option<Self> {
+        let landing_zones = zones.iter().filter(|z| z.kind == ZoneKind::LandingZone).count();
+        if zones.len() > MAX_FENCE_ZONES || landing_zones > 1 {
+            return None;
+        }
+        let Some(first) = zones.first() else { return Some(Self::EMPTY) };
+        let mut fence = Self { origin: first.vertices[0], ..Self::EMPTY };
+        for (local, zone) in fence.local.iter_mut().zip(zones) {
+            for (p, v) in local.iter_mut().zip(zone.vertices()) {
+                *p = v.local_m(fence.origin);
+                if distance((0.0, 0.0), *p) > FENCE_MAX_EXTENT_M {
+                    return None;
+                }
+            }
+            if !is_simple(&local[..zone.len]) {
+                return None;
+            }
+        }
+        fence.zones[..zones.len()].copy_from_slice(zones);
+        fence.len = zones.len();
+        Some(fence)
+    }
+
+    pub fn zones(&self) -> &[Zone] {
+        &self.zones[..self.len]
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn has_landing_zone(&self) -> bool {
+        self.zones().iter().any(|z| z.kind == ZoneKind::LandingZone)
+    }
+
+    /// Fletcher-style sum over each zone's kind (one byte) and its vertices'
+    /// latitude and longitude (f64 little-endian), as `WindProfile::checksum`;
+    /// shown in telemetry so the ground can confirm the upload. 0 when empty.
+    pub fn checksum(&self) -> u32 {
+        let (mut a, mut s) = (0u32, 0u32);
+        let mut add = |byte: u8| {
+            a = a.wrapping_add(byte as u32);
+            s = s.wrapping_add(a);
+        };
+        for zone in self.zones() {
+            add(zone.kind as u8);
+            for v in zone.vertices() {
+                v.lat_deg.to_le_bytes().into_iter().chain(v.lon_deg.to_le_bytes()).for_each(&mut add);
+            }
+        }
+        (s << 16) ^ a
+    }
+
+    /// `p` in the fence's north/east frame (m).
+    pub fn local_m(&self, p: LatLon) -> Point {
+        p.local_m(self.origin)
+    }
+
+    /// Inverse of [`local_m`](Self::local_m).
+    pub fn from_local(&self, p: Point) -> LatLon {
+        self.origin.from_local(p)
+    }
+
+    /// Index (into `zones`) of a keep-out containing `p`.
+    pub fn keep_out_at(&self, p: Point) -> Option<usize> {
+        self.polygons().position(|(kind, poly)| kind == ZoneKind::KeepOut && contains(poly, p))
+    }
+
+    /// Inside the landing zone; true if there is none.
+    pub fn in_landing_zone(&self, p: Point) -> bool {
+        self.polygons().all(|(kind, poly)| kind != ZoneKind::LandingZone || contains(poly, p))
+    }
+
+    /// Somewhere to land: `FENCE_MARGIN_M` clear of every keep-out and
+    /// inside the landing zone (if any) by as much.
+    pub fn is_safe(&self, p: Point) -> bool {
+        self.polygons().all(|(kind, poly)| {
+            let inside = contains(poly, p);
+            let clear = edge_distance(poly, p) >= FENCE_MARGIN_M;
+            clear && inside == (kind == ZoneKind::LandingZone)
+        })
+    }
+
+    /// The track `from` → `to` stays `FENCE_MARGIN_M` clear of every
+    /// keep-out, or at least gets no closer to one than `from` already is.
+    /// Keep-outs `from` is inside are ignored; fly out with
+    /// [`exit_point`](Self::exit_point).
+    pub fn path_clear(&self, from: Point, to: Point) -> bool {
+        self.polygons().all(|(kind, poly)| {
+            if kind != ZoneKind::KeepOut || contains(poly, from) {
+                return true;
+            }
+            let limit = FENCE_MARGIN_M.min(edge_distance(poly, from) - 0.5);
+            !contains(poly, to) && edges(poly).all(|(a, b)| segment_distance(from, to, a, b) >= limit)
+        })
+    }
+
+    /// Nearest point on the edge of zone `zone` to `p`, the quickest way out
+    /// of a keep-out.
+    pub fn exit_point(&self, zone: usize, p: Point) -> Point {
+        let poly = &self.local[zone][..self.zones[zone].len];
+        edges(poly)
+            .map(|(a, b)| closest_on_segment(p, a, b))
+            .min_by(|a, b| distance(*a, p).total_cmp(&distance(*b, p)))
+            .unwrap_or(p)
+    }
+
+    /// The safe point nearest `target` inside the disc of `radius` about
+    /// `centre` (the reach), searched in rings `FENCE_SEARCH_STEP_M` apart
+    /// around `target`, along the arc of each that lies in the reach with
+    /// points about as far apart; of a ring's safe points, the one nearest
+    /// `centre`. `None` if there is none within `FENCE_SEARCH_RADIUS_M`.
+    pub fn nearest_safe(&self, target: Point, centre: Point, radius: f32) -> Option<Point> {
+        let reachable = |q: Point| distance(q, centre) <= radius;
+        if reachable(target) && self.is_safe(target) {
+            return Some(target);
+        }
+        let to_centre = distance(target, centre);
+        let towards = libm::atan2f(centre.1 - target.1, centre.0 - target.0);
+        let rings = (FENCE_SEARCH_RADIUS_M / FENCE_SEARCH_STEP_M) as usize;
+        for ring in 1..=rings {
+            let r = ring as f32 * FENCE_SEARCH_STEP_M;
+            if libm::fabsf(to_centre - r) > radius {
+                continue; // ring misses the reach
+            }
+            // Half-angle of the arc inside the reach, either side of `towards`
+            let half = if r + to_centre <= radius {
+                M_PI
+            } else {
+                let cos = (r * r + to_centre * to_centre - radius * radius) / (2.0 * r * to_centre);
+                libm::acosf(cos.clamp(-1.0, 1.0))
+            };
+            let points = libm::ceilf(half * r / FENCE_SEARCH_STEP_M) as i32;
+            let best = (-points..=points)
+                .map(|k| {
+                    let a = towards + half * k as f32 / points.max(1) as f32;
+                    (target.0 + r * libm::cosf(a), target.1 + r * libm::sinf(a))
+                })
+                .filter(|&q| reachable(q) && self.is_safe(q))
+                .min_by(|a, b| distance(*a, centre).total_cmp(&distance(*b, centre)));
+            if best.is_some() {
+                return best;
+            }
+        }
+        None
+    }
+
+    fn polygons(&self) -> impl Iterator<Item = (ZoneKind, &[Point])> {
+        self.zones().iter().zip(&self.local).map(|(z, local)| (z.kind, &local[..z.len]))
+    }
+}
+
+// =============================================================================
+// Polygon geometry, north/east metres
+// =============================================================================
+
+fn edges(poly: &[Point]) -> impl Iterator<Item = (Point, Point)> + '_ {
+    (0..poly.len()).map(move |i| (poly[i], poly[(i + 1) % poly.len()]))
+}
+
+/// Even-odd ray cast.
+fn contains(poly: &[Point], p: Point) -> bool {
+    let mut inside = false;
+    for (a, b) in edges(poly) {
+        if (a.1 > p.1) != (b.1 > p.1) {
+            let n = a.0 + (p.1 - a.1) / (b.1 - a.1) * (b.0 - a.0);
+            if p.0 < n {
+                inside = !inside;
+            }
+        }
+    }
+    inside
+}
+
+fn edge_distance(poly: &[Point], p: Point) -> f32 {
+    edges(poly).map(|(a, b)| distance(p, closest_on_segment(p, a, b))).fold(f32::INFINITY, f32::min)
+}
+
+fn closest_on_segment(p: Point, a: Point, b: Point) -> Point {
+    let (dn, de) = (b.0 - a.0, b.1 - a.1);
+    let len2 = dn * dn + de * de;
+    let t = if len2 > 0.0 { (((p.0 - a.0) * dn + (p.1 - a.1) * de) / len2).clamp(0.0, 1.0) } else { 0.0 };
+    (a.0 + t * dn, a.1 + t * de)
+}
+
+fn cross(o: Point, a: Point, b: Point) -> f32 {
+    (a.0 - o.0) * (b.1 - o.1) - (a.1 - o.1) * (b.0 - o.0)
+}
+
+/// Closest approach of segments a–b and c–d, 0 if they cross.
+fn segment_distance(a: Point, b: Point, c: Point, d: Point) -> f32 {
+    if cross(c, d, a) * cross(c, d, b) < 0.0 && cross(a, b, c) * cross(a, b, d) < 0.0 {
+        return 0.0;
+    }
+    [
+        distance(a, closest_on_segment(a, c, d)),
+        distance(b, closest_on_segment(b, c, d)),
+        distance(c, closest_on_segment(c, a, b)),
+        distance(d, closest_on_segment(d, a, b)),
+    ]
+    .into_iter()
+    .fold(f32::INFINITY, f32::min)
+}
+
+/// Some area, and no two non-neighbouring edges touch.
+fn is_simple(poly: &[Point]) -> bool {
+    let twice_area: f32 = edges(poly).map(|(a, b)| a.0 * b.1 - b.0 * a.1).sum();
+    if libm::fabsf(twice_area) < 2.0 {
+        return false;
+    }
+    let n = poly.len();
+    let edge = |i: usize| (poly[i], poly[(i + 1) % n]);
+    (0..n).all(|i| {
+        (i + 2..n).filter(|&j| (j + 1) % n != i).all(|j| {
+            let ((a, b), (c, d)) = (edge(i), edge(j));
+            segment_distance(a, b, c, d) > 1e-3
+        })
+    })
+}
@@ -0,0 +1,78 @@
+//! Canopy heading estimate for the PI loop.
+//!
+//! GPS heading of motion (`head_mot`) is the ground track: fine in steady
+//! flight, noise at low ground speed, and it only changes once per receiver
+//! solution, so it lags turns. [`HeadingFilter`] integrates the gyro yaw
+//! rate every cycle and pulls the result toward each new GPS heading with
+//! time constant `HEADING_GPS_TAU_S`, scaled by how much that fix can be
+//! trusted: its ground speed (`GSPEED_MIN_FOR_HEADING` to
+//! `GSPEED_FULL_HEADING`) and `head_acc` (up to `HEADING_ACC_MAX_DEG`).
+//! Without a gyro it follows the usable GPS headings as they are.
+//!
+//! The estimate expires `HEADING_COAST_MS` after the last usable GPS
+//! heading (`HEADING_GPS_ONLY_COAST_MS` without a gyro); BLiMS holds the
+//! brakelines neutral until the next one rather than steer on it.
+
+use crate::blims::{wrap180, wrap360};
+use crate::blims_constants::*;
+use crate::blims_state::BlimsDataIn;
+
+#[derive(Default)]
+pub struct HeadingFilter {
+    heading_deg: Option<f32>,
+    /// iTOW of the last GPS solution seen; the FSW repeats a solution
+    /// every cycle until the next one arrives
+    last_solution: Option<u32>,
+    last_solution_ms: Option<u64>,
+    last_usable_ms: Option<u64>,
+}
+
+impl HeadingFilter {
+    pub const fn new() -> Self {
+        Self { heading_deg: None, last_solution: None, last_solution_ms: None, last_usable_ms: None }
+    }
+
+    /// Advance by one cycle of `dt` seconds. Returns the heading (°, [0, 360))
+    /// to steer on, `None` while it can't be trusted.
+    pub fn update(&mut self, data: &BlimsDataIn, now_ms: u64, dt: f32) -> Option<f32> {
+        let yaw_rate = data.yaw_rate_valid.then_some(data.yaw_rate_dps);
+        if let (Some(h), Some(rate)) = (self.heading_deg, yaw_rate) {
+            self.heading_deg = Some(wrap360(h + rate * dt));
+        }
+
+        let gps_valid = data.gps_state && data.fix_type >= 2;
+        if gps_valid && self.last_solution != Some(data.itow) {
+            self.last_solution = Some(data.itow);
+            let since_s = self.last_solution_ms.map_or(f32::INFINITY, |t| now_ms.saturating_sub(t) as f32 / 1000.0);
+            self.last_solution_ms = Some(now_ms);
+
+            let w = Self::weight(data);
+            if w > 0.0 {
+                let gps = wrap360(data.head_mot as f32 * 1e-5);
+                self.heading_deg = Some(match (self.heading_deg, yaw_rate) {
+                    (Some(h), Some(_)) => wrap360(h + wrap180(gps - h) * w * (since_s / HEADING_GPS_TAU_S).min(1.0)),
+                    _ => gps,
+                });
+                self.last_usable_ms = Some(now_ms);
+            }
+        }
+
+        let coast_ms = if yaw_rate.is_some() { HEADING_COAST_MS } else { HEADING_GPS_ONLY_COAST_MS };
+        match self.last_usable_ms {
+            Some(t) if now_ms.saturating_sub(t) <= coast_ms => self.heading_deg,
+            _ => {
+                // Start over from the next usable fix, not from a drifted estimate
+                self.heading_deg = None;
+                None
+            }
+        }
+    }
+
+    /// Trust in this fix's heading of motion, 0 (ignore) to 1.
+    pub fn weight(data: &BlimsDataIn) -> f32 {
+        let speed = (data.g_speed - GSPEED_MIN_FOR_HEADING) as f32
+            / (GSPEED_FULL_HEADING - GSPEED_MIN_FOR_HEADING) as f32;
+        let accuracy = 1.0 - data.head_acc as f32 * 1e-5 / HEADING_ACC_MAX_DEG;
+        speed.clamp(0.0, 1.0) * accuracy.clamp(0.0, 1.0)
+    }
+}
@@ -1,9 +1,37 @@
-#![no_std]
+//! BLiMS parafoil guidance.
+//!
+//! [`Blims`] owns the guidance and PI heading logic; the brakelines move
+//! through an [`Actuator`] and the caller supplies the time, so the library
+//! is hardware-free. Feature `rp` adds [`PwmActuator`] for the RP2350 (and
+//! the firmware binary and examples); feature `std` adds the host simulator
+//! in `sim`, the `blims-sim` binary and the `blims-mc` landing-dispersion
+//! runner (`monte_carlo`).
 
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub mod actuator;
 pub mod blims_constants;
 pub mod blims_state;
 pub mod blims;
+pub mod calibration;
+pub mod geo;
+pub mod geofence;
+pub mod heading;
+#[cfg(feature = "std")]
+pub mod monte_carlo;
+pub mod pattern;
+#[cfg(feature = "rp")]
+pub mod pwm;
+#[cfg(feature = "std")]
+pub mod sim;
+pub mod wind;
 
+pub use actuator::Actuator;
 pub use blims::Blims;
-pub use blims_state::{BlimsDataIn, BlimsDataOut, Phase};
-pub use blims::Hardware;
+pub use blims_state::{BlimsConfig, BlimsDataIn, BlimsDataOut, GuidanceMode, Phase, TrackingLaw};
+pub use calibration::{CalibrationTable, Characterisation};
+pub use geo::LatLon;
+pub use geofence::{Geofence, Zone, ZoneKind};
+#[cfg(feature = "rp")]
+pub use pwm::PwmActuator;
+pub use wind::{Wind, WindEstimator, WindLayer, WindProfile};
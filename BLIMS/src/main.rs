@@ -1,17 +1,12 @@
 #![no_std]
 #![no_main]
  
-mod blims_constants;
-mod blims_state;
-mod blims;
- 
-use blims::{Blims, Hardware};
-use blims_state::BlimsDataIn;
+use blims::{Blims, BlimsConfig, BlimsDataIn, PwmActuator, WindProfile};
  
 use embassy_executor::Spawner;
 use embassy_rp::gpio::{Level, Output};
 use embassy_rp::pwm::{Config as PwmConfig, Pwm};
-use embassy_time::{Duration, Timer};
+use embassy_time::{Duration, Instant, Timer};
 use fixed::FixedU16;
 use fixed::types::extra::U4;
 use {defmt_rtt as _, panic_probe as _};
@@ -31,6 +26,9 @@ const WIND_ALTITUDES_M: [f32; WIND_PROFILE_SIZE] = [
 const WIND_DIRS_DEG: [f32; WIND_PROFILE_SIZE] = [
     270.0, 270.0, 270.0, 270.0, 270.0, 270.0, 270.0, 270.0, 270.0, 270.0, 270.0,
 ];
+const WIND_SPEEDS_MS: [f32; WIND_PROFILE_SIZE] = [
+    4.0, 4.0, 4.0, 4.0, 4.0, 4.0, 4.0, 4.0, 4.0, 4.0, 4.0,
+];
  
 #[embassy_executor::main]
 async fn main(_spawner: Spawner) {
@@ -49,14 +47,16 @@ async fn main(_spawner: Spawner) {
     let pwm = Pwm::new_output_a(p.PWM_SLICE6, p.PIN_28, pwm_config.clone()); //compare_b and slice 9 pin 35 for av bay, compare_a and slice 6 pin 28 for breadboard
  
     // Construct BLiMS (drives enable high and parks motor at neutral)
-    let mut blims = Blims::new(Hardware { pwm, pwm_config, enable_pin });
+    let mut blims = Blims::new(PwmActuator::new(pwm, pwm_config, enable_pin), BlimsConfig::default());
  
     // ── Pre-flight configuration ─────────────────────────────────────────────
     //TODO replace lon, lat with. actual target
     blims.set_upwind_target(42.705565, -77.196310);
     blims.set_downwind_target(42.703311, -77.181125);
     blims.set_wind_from_deg(270.0);   
-    blims.set_wind_profile(&WIND_ALTITUDES_M, &WIND_DIRS_DEG); 
+    let wind_profile = WindProfile::from_columns(&WIND_ALTITUDES_M, &WIND_DIRS_DEG, &WIND_SPEEDS_MS)
+        .expect("invalid wind profile");
+    blims.set_wind_profile(&wind_profile);
  
  
     defmt::println!("BLiMS initialised – waiting for parafoil stabilisation");
@@ -69,6 +69,7 @@ async fn main(_spawner: Spawner) {
         // or mutex from the sensor task.
         
         let data_in = BlimsDataIn {
+            itow:         Instant::now().as_millis() as u32, // a fresh solution each loop
             lat:         424_441_000,   // 42.4441° × 1e7
             lon:        -764_821_000,   // −76.4821° × 1e7
             altitude_ft: 1200.0,        // feet AGL from barometer
@@ -83,9 +84,11 @@ async fn main(_spawner: Spawner) {
             v_acc:        3_000,
             s_acc:        500,
             head_acc:     100_000,       // 1.0° × 1e5
+            yaw_rate_dps:   0.0,
+            yaw_rate_valid: false,       // no IMU on the bench board
         };
  
-        blims.execute(&data_in);
+        blims.execute(&data_in, Instant::now().as_millis());
  
         
  
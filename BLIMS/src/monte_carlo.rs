@@ -0,0 +1,339 @@
+//! Monte Carlo landing dispersion.
+//!
+//! Flies many [`sim::fly`] descents through the real [`crate::Blims`], each
+//! with the canopy, true wind, deployment point and GPS dispersed about a
+//! nominal [`SimConfig`], and summarises landing error (CEP), time in each
+//! phase and time with the brakelines saturated. BLiMS is always given the
+//! nominal wind as its forecast, so the layered, gusty true wind is the
+//! forecast error. Run `i` uses seed `seed + i` for both its dispersion and
+//! its sensor noise, so any one run can be flown again on its own
+//! (`blims-mc --runs 1 --seed S`).
+
+use std::io::{self, Write};
+
+use crate::blims::wrap360;
+use crate::blims_constants::{MAX_WIND_LAYERS, MOTOR_MAX, MOTOR_MIN, NEUTRAL_POS, WIND_LAYER_MAX_ALT_M, WIND_LAYER_MAX_SPEED_MS};
+use crate::geo::LatLon;
+use crate::sim::{self, Rng, SimConfig};
+use crate::wind::{WindLayer, WindProfile};
+
+/// Phase ids are 0..PHASE_COUNT (`Phase as i8`).
+pub const PHASE_COUNT: usize = 10;
+
+/// A command this far or more of the winch travel from neutral counts as
+/// saturated. Not the limit itself: the default gains top out at 8.9 in
+/// (KP × 180° + INTEGRAL_MAX_INCHES) against the 9 in stop.
+pub const SATURATION_FRACTION: f32 = 0.9;
+
+/// How far each run strays from the nominal configuration. The 1-σ values
+/// are normal, the maxima uniform from 0.
+#[derive(Clone, Copy, Debug)]
+pub struct Dispersion {
+    /// Canopy airspeed and sink rate (m/s) and full-brake turn rate (°/s).
+    pub airspeed_sd_ms: f32,
+    pub sink_sd_ms: f32,
+    pub turn_rate_sd_deg_s: f32,
+    /// Turn-rate lag (s) and brakeline command latency (ms).
+    pub turn_tau_max_s: f32,
+    pub latency_max_ms: u64,
+    /// True wind: a layer every `wind_layer_m` from the ground to deployment,
+    /// each off the nominal wind by these 1-σ in speed (m/s) and direction (°).
+    pub wind_layer_m: f32,
+    pub wind_speed_sd_ms: f32,
+    pub wind_dir_sd_deg: f32,
+    /// Gust 1-σ, up to this (m/s).
+    pub gust_max_ms: f32,
+    /// Deployment point about the nominal one (m); heading is uniform.
+    pub start_sd_m: f32,
+    /// Chance per GPS solution of a dropout.
+    pub gps_dropout_rate: f32,
+}
+
+impl Default for Dispersion {
+    fn default() -> Self {
+        Self {
+            airspeed_sd_ms: 0.5,
+            sink_sd_ms: 0.5,
+            turn_rate_sd_deg_s: 3.0,
+            turn_tau_max_s: 1.5,
+            latency_max_ms: 200,
+            wind_layer_m: 100.0,
+            wind_speed_sd_ms: 1.5,
+            wind_dir_sd_deg: 20.0,
+            gust_max_ms: 1.0,
+            start_sd_m: 100.0,
+            gps_dropout_rate: 0.0005,
+        }
+    }
+}
+
+impl Dispersion {
+    /// Every spread multiplied by `k` (0 flies the nominal case each time,
+    /// bar the deployment heading and sensor noise).
+    pub fn scaled(self, k: f32) -> Self {
+        Self {
+            airspeed_sd_ms: self.airspeed_sd_ms * k,
+            sink_sd_ms: self.sink_sd_ms * k,
+            turn_rate_sd_deg_s: self.turn_rate_sd_deg_s * k,
+            turn_tau_max_s: self.turn_tau_max_s * k,
+            latency_max_ms: (self.latency_max_ms as f32 * k) as u64,
+            wind_layer_m: self.wind_layer_m,
+            wind_speed_sd_ms: self.wind_speed_sd_ms * k,
+            wind_dir_sd_deg: self.wind_dir_sd_deg * k,
+            gust_max_ms: self.gust_max_ms * k,
+            start_sd_m: self.start_sd_m * k,
+            gps_dropout_rate: self.gps_dropout_rate * k,
+        }
+    }
+
+    /// The configuration one run flies.
+    pub fn sample(&self, nominal: &SimConfig, seed: u64) -> SimConfig {
+        let mut rng = Rng::new(seed);
+        let mut cfg = SimConfig { seed, ..*nominal };
+        let (from, speed) = nominal.forecast.unwrap_or((nominal.wind_from_deg, nominal.wind_speed_ms));
+        cfg.forecast = Some((from, speed));
+
+        let c = &mut cfg.canopy;
+        c.airspeed_ms = rng.normal(c.airspeed_ms, self.airspeed_sd_ms).max(1.0);
+        c.sink_rate_ms = rng.normal(c.sink_rate_ms, self.sink_sd_ms).max(1.0);
+        c.max_turn_rate_deg_s = rng.normal(c.max_turn_rate_deg_s, self.turn_rate_sd_deg_s).max(1.0);
+        c.turn_tau_s += self.turn_tau_max_s * rng.uniform() as f32;
+        c.latency_ms += (self.latency_max_ms as f64 * rng.uniform()) as u64;
+
+        let n = ((nominal.start_alt_m / self.wind_layer_m.max(1.0)) as usize + 1).min(MAX_WIND_LAYERS);
+        let step = nominal.start_alt_m / (n - 1).max(1) as f32;
+        let mut layers = [WindLayer::default(); MAX_WIND_LAYERS];
+        for (i, layer) in layers[..n].iter_mut().enumerate() {
+            *layer = WindLayer {
+                altitude_m: (i as f32 * step).min(WIND_LAYER_MAX_ALT_M),
+                from_deg: wrap360(rng.normal(nominal.wind_from_deg, self.wind_dir_sd_deg)),
+                speed_ms: rng.normal(nominal.wind_speed_ms, self.wind_speed_sd_ms).clamp(0.0, WIND_LAYER_MAX_SPEED_MS),
+            };
+        }
+        cfg.wind_profile = WindProfile::from_layers(&layers[..n]).unwrap_or(WindProfile::EMPTY);
+        cfg.gust_sd_ms = self.gust_max_ms * rng.uniform() as f32;
+
+        let start = LatLon::new(nominal.start_lat, nominal.start_lon)
+            .from_local((rng.normal(0.0, self.start_sd_m), rng.normal(0.0, self.start_sd_m)));
+        (cfg.start_lat, cfg.start_lon) = (start.lat_deg, start.lon_deg);
+        cfg.start_heading_deg = 360.0 * rng.uniform() as f32;
+        cfg.gps_dropout_rate = self.gps_dropout_rate;
+        cfg
+    }
+}
+
+/// One point of a run's track, a second apart.
+#[derive(Clone, Copy, Debug)]
+pub struct TrackPoint {
+    pub t_s: f32,
+    pub lat: f64,
+    pub lon: f64,
+    pub alt_m: f32,
+    pub phase_id: i8,
+    pub brakeline_in: f32,
+}
+
+/// Outcome of one dispersed descent.
+#[derive(Clone, Debug)]
+pub struct Run {
+    pub index: u32,
+    pub seed: u64,
+    pub landing_lat: f64,
+    pub landing_lon: f64,
+    /// Landing offset from the downwind target (m north, m east) and miss (m).
+    pub landing_n_m: f32,
+    pub landing_e_m: f32,
+    pub miss_m: f32,
+    pub descent_s: f32,
+    /// Time in each phase (s), by phase id.
+    pub phase_s: [f32; PHASE_COUNT],
+    /// Time with the commanded differential saturated (s), see
+    /// [`SATURATION_FRACTION`].
+    pub saturated_s: f32,
+    pub track: Vec<TrackPoint>,
+}
+
+/// Flies one run of the configuration `sample` gave.
+pub fn fly_run(cfg: &SimConfig, index: u32) -> Run {
+    let flight = sim::fly(cfg);
+    let period_s = cfg.period_ms as f32 / 1000.0;
+    let target = LatLon::new(cfg.downwind_target.0, cfg.downwind_target.1);
+    let (landing_n_m, landing_e_m) = LatLon::new(flight.landing_lat, flight.landing_lon).local_m(target);
+    let mut phase_s = [0.0; PHASE_COUNT];
+    let mut saturated_s = 0.0;
+    let mut track = Vec::new();
+    for s in &flight.samples {
+        if let Some(t) = phase_s.get_mut(s.out.phase_id as usize) {
+            *t += period_s;
+        }
+        let command = s.out.brakeline_diff_in - NEUTRAL_POS;
+        if command >= SATURATION_FRACTION * (MOTOR_MAX - NEUTRAL_POS)
+            || command <= SATURATION_FRACTION * (MOTOR_MIN - NEUTRAL_POS)
+        {
+            saturated_s += period_s;
+        }
+        if s.t_ms.is_multiple_of(1000) {
+            track.push(TrackPoint {
+                t_s: s.t_ms as f32 / 1000.0,
+                lat: s.lat,
+                lon: s.lon,
+                alt_m: s.alt_m,
+                phase_id: s.out.phase_id,
+                brakeline_in: s.brakeline,
+            });
+        }
+    }
+    Run {
+        index,
+        seed: cfg.seed,
+        landing_lat: flight.landing_lat,
+        landing_lon: flight.landing_lon,
+        landing_n_m,
+        landing_e_m,
+        miss_m: flight.miss_m(cfg),
+        descent_s: flight.samples.len() as f32 * period_s,
+        phase_s,
+        saturated_s,
+        track,
+    }
+}
+
+/// `runs` dispersed descents about `nominal`; run `i` uses seed `seed + i`.
+pub fn run(nominal: &SimConfig, dispersion: &Dispersion, runs: u32, seed: u64) -> Vec<Run> {
+    (0..runs)
+        .map(|i| fly_run(&dispersion.sample(nominal, seed.wrapping_add(i as u64)), i))
+        .collect()
+}
+
+/// Distribution of one quantity over the runs.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Stats {
+    pub n: usize,
+    pub mean: f32,
+    pub sd: f32,
+    pub min: f32,
+    pub p50: f32,
+    pub p90: f32,
+    pub max: f32,
+}
+
+impl Stats {
+    pub const HEADER: &str = "       mean       sd      min      p50      p90      max";
+
+    pub fn of(values: &[f32]) -> Self {
+        if values.is_empty() {
+            return Self::default();
+        }
+        let mut sorted = values.to_vec();
+        sorted.sort_by(f32::total_cmp);
+        let n = sorted.len();
+        let mean = sorted.iter().sum::<f32>() / n as f32;
+        let var = sorted.iter().map(|v| (v - mean) * (v - mean)).sum::<f32>() / (n.max(2) - 1) as f32;
+        // Nearest rank
+        let pct = |p: f32| sorted[((p * n as f32).ceil() as usize).clamp(1, n) - 1];
+        Self { n, mean, sd: var.sqrt(), min: sorted[0], p50: pct(0.5), p90: pct(0.9), max: sorted[n - 1] }
+    }
+
+    pub fn row(&self) -> String {
+        format!(
+            "{:>11.1}{:>9.1}{:>9.1}{:>9.1}{:>9.1}{:>9.1}",
+            self.mean, self.sd, self.min, self.p50, self.p90, self.max
+        )
+    }
+}
+
+/// What the runs add up to.
+#[derive(Clone, Debug)]
+pub struct Summary {
+    pub runs: usize,
+    /// Radius about the target holding half and nine tenths of the landings (m).
+    pub cep50_m: f32,
+    pub cep90_m: f32,
+    /// Mean landing offset from the target (m north, m east).
+    pub bias_n_m: f32,
+    pub bias_e_m: f32,
+    pub miss: Stats,
+    pub descent: Stats,
+    pub saturated: Stats,
+    /// Time in each phase entered on any run, over all runs (0 where a run
+    /// skipped it).
+    pub phases: Vec<(i8, Stats)>,
+}
+
+pub fn summarise(runs: &[Run]) -> Summary {
+    let stats = |f: &dyn Fn(&Run) -> f32| Stats::of(&runs.iter().map(f).collect::<Vec<_>>());
+    let miss = stats(&|r| r.miss_m);
+    let n = runs.len().max(1) as f32;
+    let phases = (0..PHASE_COUNT)
+        .filter(|&p| runs.iter().any(|r| r.phase_s[p] > 0.0))
+        .map(|p| (p as i8, stats(&|r| r.phase_s[p])))
+        .collect();
+    Summary {
+        runs: runs.len(),
+        cep50_m: miss.p50,
+        cep90_m: miss.p90,
+        bias_n_m: runs.iter().map(|r| r.landing_n_m).sum::<f32>() / n,
+        bias_e_m: runs.iter().map(|r| r.landing_e_m).sum::<f32>() / n,
+        miss,
+        descent: stats(&|r| r.descent_s),
+        saturated: stats(&|r| r.saturated_s),
+        phases,
+    }
+}
+
+/// Every run's track, one row per point.
+pub fn write_tracks_csv(runs: &[Run], w: &mut impl Write) -> io::Result<()> {
+    writeln!(w, "Run,Seed,Time_s,Lat,Lon,Alt_m,Phase,Brakeline_in")?;
+    for r in runs {
+        for p in &r.track {
+            writeln!(
+                w,
+                "{},{},{:.1},{:.7},{:.7},{:.1},{},{:.3}",
+                r.index,
+                r.seed,
+                p.t_s,
+                p.lat,
+                p.lon,
+                p.alt_m,
+                sim::phase_name(p.phase_id),
+                p.brakeline_in
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// GeoJSON FeatureCollection: the target, and per run its track
+/// (LineString) and landing point, tagged with run, seed and miss.
+pub fn write_geojson(runs: &[Run], target: (f64, f64), w: &mut impl Write) -> io::Result<()> {
+    writeln!(w, "{{\"type\":\"FeatureCollection\",\"features\":[")?;
+    write!(
+        w,
+        "{{\"type\":\"Feature\",\"properties\":{{\"kind\":\"target\"}},\
+         \"geometry\":{{\"type\":\"Point\",\"coordinates\":[{:.7},{:.7}]}}}}",
+        target.1, target.0
+    )?;
+    for r in runs {
+        let props = format!("\"run\":{},\"seed\":{},\"miss_m\":{:.1}", r.index, r.seed, r.miss_m);
+        let coords: Vec<String> = r
+            .track
+            .iter()
+            .map(|p| (p.lon, p.lat))
+            .chain([(r.landing_lon, r.landing_lat)])
+            .map(|(lon, lat)| format!("[{lon:.7},{lat:.7}]"))
+            .collect();
+        write!(
+            w,
+            ",\n{{\"type\":\"Feature\",\"properties\":{{\"kind\":\"track\",{props}}},\
+             \"geometry\":{{\"type\":\"LineString\",\"coordinates\":[{}]}}}}",
+            coords.join(",")
+        )?;
+        write!(
+            w,
+            ",\n{{\"type\":\"Feature\",\"properties\":{{\"kind\":\"landing\",{props}}},\
+             \"geometry\":{{\"type\":\"Point\",\"coordinates\":[{:.7},{:.7}]}}}}",
+            r.landing_lon, r.landing_lat
+        )?;
+    }
+    writeln!(w, "\n]}}")
+}
@@ -0,0 +1,117 @@
+//! Landing-pattern geometry for `GuidanceMode::Pattern`.
+//!
+//! Planned in a flat north/east frame (metres) centred on the landing
+//! target `T`, with the final approach flown into the wind. A left-hand
+//! pattern with the wind from the west:
+//!
+//! ```text
+//!    wind →
+//!    T ←────── final ────── F
+//!                           ↑ base
+//!    D ────── downwind ───→ B
+//! ```
+//!
+//! `D` is the pattern entry and the centre of the loiter circle.
+//!
+//! Each leg is sized so it uses up its altitude band (`PATTERN_*_ALT_FT`)
+//! at the measured sink rate and the ground speed the wind gives on that
+//! leg: with the wind on downwind, crabbing across it on base, into it on
+//! final. The final leg runs all the way to the ground, through the
+//! hands-off flare below `ALT_NEUTRAL_FT`.
+
+use crate::blims::wrap360;
+use crate::blims_constants::*;
+
+/// North/east offset from the landing target (m).
+pub type Point = (f32, f32);
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Pattern {
+    /// Unit vector along the final approach (into the wind), north/east.
+    final_dir: Point,
+    /// Unit vector from the final approach line out to the downwind leg.
+    side_dir: Point,
+    /// Leg lengths (m).
+    pub final_m: f32,
+    pub base_m: f32,
+    pub downwind_m: f32,
+}
+
+impl Pattern {
+    /// Plan the pattern for wind blowing from `wind_from_deg` at
+    /// `wind_speed_ms`, and a canopy with the given airspeed and sink rate.
+    pub fn plan(wind_from_deg: f32, wind_speed_ms: f32, airspeed_ms: f32, sink_rate_ms: f32) -> Self {
+        let into_wind = wind_from_deg * DEG_TO_RAD;
+        let final_dir = (libm::cosf(into_wind), libm::sinf(into_wind));
+        // Left-hand: the legs lie to the left of the final approach
+        let side_dir = if PATTERN_LEFT_HAND {
+            (final_dir.1, -final_dir.0)
+        } else {
+            (-final_dir.1, final_dir.0)
+        };
+
+        let w = wind_speed_ms.max(0.0);
+        let v = airspeed_ms.max(1.0);
+        // Ground speed on each leg; a canopy that can't penetrate the wind
+        // still gets a short final rather than none
+        let gs_final = (v - w).max(0.5);
+        let gs_base = libm::sqrtf((v * v - w * w).max(0.25));
+        let gs_downwind = v + w;
+
+        let sink = sink_rate_ms.max(1.0);
+        let leg = |gs: f32, top_ft: f32, bottom_ft: f32| {
+            (gs * (top_ft - bottom_ft) / FT_PER_M / sink).max(PATTERN_MIN_LEG_M)
+        };
+        Self {
+            final_dir,
+            side_dir,
+            final_m: leg(gs_final, PATTERN_FINAL_ALT_FT, 0.0),
+            base_m: leg(gs_base, PATTERN_BASE_ALT_FT, PATTERN_FINAL_ALT_FT),
+            downwind_m: leg(gs_downwind, PATTERN_ENTRY_ALT_FT, PATTERN_BASE_ALT_FT),
+        }
+    }
+
+    /// Final turn point `F`: downwind of the target by the final leg.
+    pub fn final_turn(&self) -> Point {
+        (-self.final_dir.0 * self.final_m, -self.final_dir.1 * self.final_m)
+    }
+
+    /// Base turn point `B`: out from `F` by the base leg.
+    pub fn base_turn(&self) -> Point {
+        let f = self.final_turn();
+        (f.0 + self.side_dir.0 * self.base_m, f.1 + self.side_dir.1 * self.base_m)
+    }
+
+    /// Pattern entry `D`, upwind of `B` by the downwind leg. The loiter
+    /// circle is centred here.
+    pub fn entry(&self) -> Point {
+        let b = self.base_turn();
+        (b.0 + self.final_dir.0 * self.downwind_m, b.1 + self.final_dir.1 * self.downwind_m)
+    }
+
+    /// Heading to fly the loiter circle around the pattern entry from `p`:
+    /// tangent to the circle, turned in toward it when outside and out from
+    /// it when inside, up to 45°.
+    pub fn loiter_heading(&self, p: Point) -> f32 {
+        let c = self.entry();
+        let to_centre = bearing(p, c);
+        let d = distance(p, c);
+        let correction = ((d - LOITER_RADIUS_M) / LOITER_RADIUS_M).clamp(-1.0, 1.0) * 45.0;
+        // Left-hand circles anticlockwise: centre on the left, 90° left of track
+        if PATTERN_LEFT_HAND {
+            wrap360(to_centre + 90.0 - correction)
+        } else {
+            wrap360(to_centre - 90.0 + correction)
+        }
+    }
+}
+
+/// Bearing from `a` to `b`, degrees [0, 360).
+pub fn bearing(a: Point, b: Point) -> f32 {
+    wrap360(libm::atan2f(b.1 - a.1, b.0 - a.0) * RAD_TO_DEG)
+}
+
+pub fn distance(a: Point, b: Point) -> f32 {
+    let (dn, de) = (b.0 - a.0, b.1 - a.1);
+    libm::sqrtf(dn * dn + de * de)
+}
@@ -0,0 +1,60 @@
+//! RP2350 brakeline actuator: RC PWM into the ODrive plus its enable pin
+//! (feature `rp`).
+
+use embassy_rp::gpio::Output;
+use embassy_rp::pwm::{Config as PwmConfig, Pwm};
+
+use crate::actuator::Actuator;
+use crate::blims_constants::*;
+
+pub struct PwmActuator<'d> {
+    pwm:        Pwm<'d>,
+    pwm_config: PwmConfig,
+    enable_pin: Output<'d>,
+    /// False while the FSW drives the ODrive over UART; PWM is held low
+    pwm_output: bool,
+    /// Last commanded pulse (µs)
+    pulse_us:   f32,
+}
+
+impl<'d> PwmActuator<'d> {
+    /// `pwm` and `pwm_config` must already be configured for 50 Hz with
+    /// `top = WRAP_CYCLE_COUNT` and the correct clock divider (see main.rs).
+    /// The enable pin is left as the caller set it; [`Actuator::enable`]
+    /// drives it high.
+    pub fn new(pwm: Pwm<'d>, pwm_config: PwmConfig, enable_pin: Output<'d>) -> Self {
+        Self { pwm, pwm_config, enable_pin, pwm_output: true, pulse_us: PULSE_NEUTRAL_US }
+    }
+
+    /// Turn the RC PWM output on, or hold it low while the FSW commands the
+    /// ODrive over UART. Re-enabling outputs the current brakeline position.
+    pub fn set_pwm_output(&mut self, enabled: bool) {
+        if enabled != self.pwm_output {
+            self.pwm_output = enabled;
+            self.set_pulse_us(self.pulse_us);
+        }
+    }
+}
+
+impl Actuator for PwmActuator<'_> {
+    fn set_pulse_us(&mut self, pulse_us: f32) {
+        self.pulse_us = pulse_us;
+        // The slice wraps every 20 ms (50 Hz), so
+        //
+        //   duty = pulse_us / PWM_PERIOD_US · WRAP
+        //
+        // Verify:  1000 µs → 5%·WRAP   (full left)
+        //          1500 µs → 7.5%·WRAP (neutral)
+        //          2000 µs → 10%·WRAP  (full right)
+
+        let duty = (pulse_us / PWM_PERIOD_US * WRAP_CYCLE_COUNT as f32) as u16;
+
+        self.pwm_config.compare_b = if self.pwm_output { duty } else { 0 };
+        self.pwm.set_config(&self.pwm_config);
+    }
+
+    /// Assert enable HIGH — activates the ODrive motor driver.
+    fn enable(&mut self) {
+        self.enable_pin.set_high();
+    }
+}
@@ -0,0 +1,476 @@
+//! Host parafoil simulator (feature `std`).
+//!
+//! `fly` closes the loop: a 3-DOF parafoil (position, altitude and heading;
+//! constant airspeed and sink rate, turn rate following the brakeline
+//! differential with a lag) drifts in the wind, uniform or layered by
+//! altitude, with optional gusts. Its latest GPS solution (one every
+//! `gps_period_ms`, with noise and optional dropouts) and a noisy, biased
+//! gyro yaw rate go into [`Blims::execute`] at 20 Hz with the simulated
+//! time, and the commanded differential reaches the brakelines after a
+//! latency, through a lagging winch. The run goes from main deployment to
+//! the ground. `monte_carlo` flies many of these with the parameters
+//! dispersed.
+//!
+//! `fly_recorded` takes the altitude and GPS validity from a recorded flight
+//! (`load_csv`) instead of the constant sink, so phase changes happen when
+//! they did on that flight; position and heading are still simulated.
+
+use std::collections::VecDeque;
+use std::path::Path;
+
+use crate::actuator::Actuator;
+use crate::blims::{wrap360, Blims};
+use crate::blims_constants::{DEG_TO_RAD, FT_PER_M, MOTOR_MAX, NEUTRAL_POS};
+use crate::blims_state::{BlimsConfig, BlimsDataIn, BlimsDataOut, GuidanceMode, TrackingLaw};
+use crate::calibration::CalibrationTable;
+use crate::geo::LatLon;
+use crate::geofence::Geofence;
+use crate::wind::{Wind, WindProfile};
+
+/// Canopy performance.
+#[derive(Clone, Copy, Debug)]
+pub struct Canopy {
+    /// Forward airspeed through the air (m/s).
+    pub airspeed_ms: f32,
+    /// Vertical descent rate (m/s).
+    pub sink_rate_ms: f32,
+    /// Turn rate at full brakeline differential (°/s).
+    pub max_turn_rate_deg_s: f32,
+    /// First-order lag of the winch reeling to the commanded differential (s).
+    pub winch_tau_s: f32,
+    /// First-order lag of the turn rate behind the brakeline differential (s).
+    pub turn_tau_s: f32,
+    /// Delay from a brakeline command to the winch acting on it (ms).
+    pub latency_ms: u64,
+}
+
+impl Canopy {
+    /// From L3 Launch 4 telemetry: average ground speed 7.2 m/s, sink
+    /// 8.2 m/s. The turn rate is a guess until the canopy is characterised;
+    /// no turn lag or command latency (`monte_carlo` disperses both).
+    pub const NOMINAL: Canopy = Canopy {
+        airspeed_ms: 7.5,
+        sink_rate_ms: 8.2,
+        max_turn_rate_deg_s: 15.0,
+        winch_tau_s: 0.3,
+        turn_tau_s: 0.0,
+        latency_ms: 0,
+    };
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct SimConfig {
+    pub canopy: Canopy,
+    /// Gains and phase altitudes BLiMS is built with.
+    pub controller: BlimsConfig,
+    pub mode: GuidanceMode,
+    pub tracking_law: TrackingLaw,
+    /// Wind blowing FROM this direction (°, 0 = N, 90 = E) at this speed (m/s).
+    pub wind_from_deg: f32,
+    pub wind_speed_ms: f32,
+    /// True wind by altitude, instead of the uniform one when not empty.
+    pub wind_profile: WindProfile,
+    /// Horizontal gusts on top, each component a first-order Gauss–Markov
+    /// process with this 1-σ (m/s) and correlation time (s).
+    pub gust_sd_ms: f32,
+    pub gust_tau_s: f32,
+    /// Wind loaded into BLiMS before flight, (degrees FROM, m/s); `None`
+    /// gives it the true wind (the profile, if there is one).
+    pub forecast: Option<(f32, f32)>,
+    /// Main deployment altitude, where BLiMS takes over (m AGL).
+    pub start_alt_m: f32,
+    /// Position (decimal degrees) and heading (°) at main deployment.
+    pub start_lat: f64,
+    pub start_lon: f64,
+    pub start_heading_deg: f32,
+    /// Waypoints loaded into BLiMS (decimal degrees).
+    pub upwind_target: (f64, f64),
+    pub downwind_target: (f64, f64),
+    /// Keep-out zones and landing zone loaded into BLiMS.
+    pub geofence: Geofence,
+    /// 1-σ GPS noise: position (m), track (°) and velocity (m/s).
+    pub position_sd_m: f32,
+    pub track_sd_deg: f32,
+    pub velocity_sd_ms: f32,
+    /// GPS solution period (ms); the controller sees the latest solution
+    /// every cycle in between, as on the vehicle.
+    pub gps_period_ms: u64,
+    /// Chance per GPS solution that the fix drops out, and for how long (ms).
+    pub gps_dropout_rate: f32,
+    pub gps_dropout_ms: u64,
+    /// Gyro yaw rate into BLiMS, with 1-σ noise and a constant bias (°/s).
+    pub gyro: bool,
+    pub gyro_sd_dps: f32,
+    pub gyro_bias_dps: f32,
+    /// Controller period (ms).
+    pub period_ms: u64,
+    pub seed: u64,
+}
+
+impl Default for SimConfig {
+    /// Main deployment 2000 ft AGL, 400 m north of the downwind target, with
+    /// the upwind waypoint 250 m upwind of it.
+    fn default() -> Self {
+        let target = (42.698_011_f64, -77.191_106_f64);
+        let wind_from_deg = 200.0;
+        let upwind = offset(target.0, target.1, wind_from_deg, 250.0);
+        let start = offset(target.0, target.1, 0.0, 400.0);
+        Self {
+            canopy: Canopy::NOMINAL,
+            controller: BlimsConfig::default(),
+            mode: GuidanceMode::Mvp,
+            tracking_law: TrackingLaw::Bearing,
+            wind_from_deg,
+            wind_speed_ms: 3.0,
+            wind_profile: WindProfile::EMPTY,
+            gust_sd_ms: 0.0,
+            gust_tau_s: 5.0,
+            forecast: None,
+            start_alt_m: 2000.0 / FT_PER_M,
+            start_lat: start.0,
+            start_lon: start.1,
+            start_heading_deg: 90.0,
+            upwind_target: upwind,
+            downwind_target: target,
+            geofence: Geofence::EMPTY,
+            position_sd_m: 1.0,
+            track_sd_deg: 2.0,
+            velocity_sd_ms: 0.1,
+            gps_period_ms: 50,
+            gps_dropout_rate: 0.0,
+            gps_dropout_ms: 2_000,
+            gyro: true,
+            gyro_sd_dps: 0.5,
+            gyro_bias_dps: 0.2,
+            period_ms: 50,
+            seed: 1,
+        }
+    }
+}
+
+/// Point `dist_m` from (`lat`, `lon`) along `bearing_deg` (great circle).
+pub fn offset(lat: f64, lon: f64, bearing_deg: f32, dist_m: f32) -> (f64, f64) {
+    let p = LatLon::new(lat, lon).offset(bearing_deg as f64, dist_m as f64);
+    (p.lat_deg, p.lon_deg)
+}
+
+/// Great-circle distance between two points (m).
+pub fn distance_m(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f32 {
+    LatLon::new(lat1, lon1).distance_m(LatLon::new(lat2, lon2)) as f32
+}
+
+/// Initial bearing from the first point to the second, degrees [0, 360).
+pub fn bearing_deg(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f32 {
+    wrap360(LatLon::new(lat1, lon1).bearing_deg(LatLon::new(lat2, lon2)) as f32)
+}
+
+/// Brakeline winch: the controller's command, reeled in with a first-order
+/// lag by the physics step. The simulated ODrive follows the nominal
+/// 1000–2000 µs ↔ ±9 in map exactly.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Winch {
+    pub command: f32,
+    pub position: f32,
+    pub enabled: bool,
+}
+
+impl Actuator for Winch {
+    fn set_pulse_us(&mut self, pulse_us: f32) {
+        self.command = CalibrationTable::LINEAR.brakeline_in(pulse_us);
+    }
+
+    fn enable(&mut self) {
+        self.enabled = true;
+    }
+}
+
+/// One controller cycle of a simulated descent.
+#[derive(Clone, Copy, Debug)]
+pub struct Sample {
+    pub t_ms: u64,
+    pub alt_m: f32,
+    pub lat: f64,
+    pub lon: f64,
+    /// Canopy heading and ground track (°).
+    pub heading: f32,
+    pub track: f32,
+    pub ground_speed: f32,
+    /// Actual brakeline differential (in).
+    pub brakeline: f32,
+    pub out: BlimsDataOut,
+}
+
+pub struct Flight {
+    pub samples: Vec<Sample>,
+    /// Where the canopy reached the ground.
+    pub landing_lat: f64,
+    pub landing_lon: f64,
+    /// Wind profile BLiMS estimated on the way down, (band floor m AGL, wind).
+    pub wind_profile: Vec<(f32, Wind)>,
+}
+
+impl Flight {
+    /// Landing miss from the downwind target (m).
+    pub fn miss_m(&self, cfg: &SimConfig) -> f32 {
+        let (lat, lon) = cfg.downwind_target;
+        distance_m(self.landing_lat, self.landing_lon, lat, lon)
+    }
+}
+
+impl SimConfig {
+    /// True wind at `alt_m` before gusts, (north, east) m/s.
+    pub fn wind_at(&self, alt_m: f32) -> (f32, f32) {
+        let (from_deg, speed) = self.wind_profile.at(alt_m).unwrap_or((self.wind_from_deg, self.wind_speed_ms));
+        let to = wrap360(from_deg + 180.0) * DEG_TO_RAD;
+        (speed * to.cos(), speed * to.sin())
+    }
+}
+
+/// SplitMix64 with Box–Muller normals; seeded runs are reproducible.
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in [0, 1).
+    pub fn uniform(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    pub fn normal(&mut self, mean: f32, sd: f32) -> f32 {
+        let u1 = 1.0 - self.uniform();
+        let u2 = self.uniform();
+        mean + sd * ((-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()) as f32
+    }
+}
+
+/// Altitude and GPS validity for one controller cycle.
+struct Frame {
+    alt_m: f32,
+    gps_valid: bool,
+}
+
+/// Flies one closed-loop descent at the canopy's constant sink rate.
+pub fn fly(cfg: &SimConfig) -> Flight {
+    let sink = cfg.canopy.sink_rate_ms;
+    let period_s = cfg.period_ms as f32 / 1000.0;
+    run(cfg, |i| {
+        let alt_m = cfg.start_alt_m - sink * period_s * i as f32;
+        (alt_m > 0.0).then_some(Frame { alt_m, gps_valid: true })
+    })
+}
+
+/// Flies the recorded descent in `rows` (altitude and GPS validity), from
+/// the first row with a valid fix above 100 m. The start position and
+/// heading are taken from that row rather than `cfg`.
+pub fn fly_recorded(cfg: &SimConfig, rows: &[CsvRow]) -> Flight {
+    let Some(first) = rows.iter().position(|r| r.gps_valid && r.altitude_m > 100.0) else {
+        return Flight {
+            samples: Vec::new(),
+            landing_lat: cfg.start_lat,
+            landing_lon: cfg.start_lon,
+            wind_profile: Vec::new(),
+        };
+    };
+    let seed = &rows[first];
+    let cfg = SimConfig {
+        start_lat: seed.lat,
+        start_lon: seed.lon,
+        start_heading_deg: seed.heading,
+        start_alt_m: seed.altitude_m,
+        ..*cfg
+    };
+    let rows = &rows[first..];
+    let t0 = rows[0].time_ms;
+    let mut next = 0;
+    run(&cfg, |i| {
+        // Latest recorded row at or before this cycle's time
+        let t = t0 + i as u64 * cfg.period_ms;
+        while next + 1 < rows.len() && rows[next + 1].time_ms <= t {
+            next += 1;
+        }
+        let row = &rows[next];
+        (next + 1 < rows.len() && row.altitude_m > 0.0)
+            .then_some(Frame { alt_m: row.altitude_m, gps_valid: row.gps_valid })
+    })
+}
+
+/// Closed loop over the frames `frame(i)` yields; stops at the first `None`.
+fn run(cfg: &SimConfig, mut frame: impl FnMut(u32) -> Option<Frame>) -> Flight {
+    let c = &cfg.canopy;
+    let mut rng = Rng::new(cfg.seed);
+    let mut blims = Blims::new(Winch::default(), cfg.controller);
+    blims.set_upwind_target(cfg.upwind_target.0, cfg.upwind_target.1);
+    blims.set_downwind_target(cfg.downwind_target.0, cfg.downwind_target.1);
+    blims.set_geofence(&cfg.geofence);
+    let (forecast_from, forecast_speed) = cfg.forecast.unwrap_or((cfg.wind_from_deg, cfg.wind_speed_ms));
+    blims.set_wind_from_deg(forecast_from);
+    blims.set_wind_speed_ms(forecast_speed);
+    if cfg.forecast.is_none() {
+        blims.set_wind_profile(&cfg.wind_profile);
+    }
+    blims.set_guidance_mode(cfg.mode);
+    blims.set_tracking_law(cfg.tracking_law);
+    blims.enable();
+
+    let period_s = cfg.period_ms as f32 / 1000.0;
+    let (mut lat, mut lon) = (cfg.start_lat, cfg.start_lon);
+    let mut heading = wrap360(cfg.start_heading_deg);
+    let mut brakeline = NEUTRAL_POS;
+    let mut turn_rate = 0.0;
+    let mut gust = (0.0, 0.0);
+    // Commands on their way to the winch, (time sent, differential)
+    let mut in_flight: VecDeque<(u64, f32)> = VecDeque::new();
+    let mut winch_target = NEUTRAL_POS;
+    let mut dropout_until_ms = 0;
+    let mut samples = Vec::new();
+    let mut solution = BlimsDataIn::default();
+
+    let mut i = 0;
+    while let Some(f) = frame(i) {
+        let t_ms = i as u64 * cfg.period_ms;
+        let (mean_n, mean_e) = cfg.wind_at(f.alt_m);
+        let (wind_n, wind_e) = (mean_n + gust.0, mean_e + gust.1);
+        let hdg = heading * DEG_TO_RAD;
+        let (gnd_n, gnd_e) = (c.airspeed_ms * hdg.cos() + wind_n, c.airspeed_ms * hdg.sin() + wind_e);
+        let ground_speed = (gnd_n * gnd_n + gnd_e * gnd_e).sqrt();
+        let track = wrap360(gnd_e.atan2(gnd_n) / DEG_TO_RAD);
+
+        // --- Controller cycle on the latest GPS solution ---
+        if t_ms.is_multiple_of(cfg.gps_period_ms.max(1)) {
+            if t_ms >= dropout_until_ms && rng.uniform() < cfg.gps_dropout_rate as f64 {
+                dropout_until_ms = t_ms + cfg.gps_dropout_ms;
+            }
+            let (gps_lat, gps_lon) = offset(lat, lon, 0.0, rng.normal(0.0, cfg.position_sd_m));
+            let (gps_lat, gps_lon) = offset(gps_lat, gps_lon, 90.0, rng.normal(0.0, cfg.position_sd_m));
+            solution = BlimsDataIn {
+                itow: t_ms as u32,
+                lat: (gps_lat * 1e7).round() as i32,
+                lon: (gps_lon * 1e7).round() as i32,
+                h_acc: 1_000,
+                v_acc: 2_000,
+                vel_n: (rng.normal(gnd_n, cfg.velocity_sd_ms) * 1000.0) as i32,
+                vel_e: (rng.normal(gnd_e, cfg.velocity_sd_ms) * 1000.0) as i32,
+                vel_d: (c.sink_rate_ms * 1000.0) as i32,
+                g_speed: (ground_speed * 1000.0) as i32,
+                head_mot: (wrap360(track + rng.normal(0.0, cfg.track_sd_deg)) * 1e5) as i32,
+                s_acc: 300,
+                head_acc: 100_000,
+                ..Default::default()
+            };
+        }
+        let gps_valid = f.gps_valid && t_ms >= dropout_until_ms;
+        let data_in = BlimsDataIn {
+            altitude_ft: f.alt_m * FT_PER_M,
+            fix_type: if gps_valid { 3 } else { 0 },
+            gps_state: gps_valid,
+            yaw_rate_dps: rng.normal(turn_rate + cfg.gyro_bias_dps, cfg.gyro_sd_dps),
+            yaw_rate_valid: cfg.gyro,
+            ..solution
+        };
+        let out = blims.execute(&data_in, t_ms);
+        samples.push(Sample { t_ms, alt_m: f.alt_m, lat, lon, heading, track, ground_speed, brakeline, out });
+
+        // --- Physics until the next frame ---
+        in_flight.push_back((t_ms, blims.actuator().command));
+        while let Some(&(sent_ms, command)) = in_flight.front() {
+            if sent_ms + c.latency_ms > t_ms {
+                break;
+            }
+            winch_target = command;
+            in_flight.pop_front();
+        }
+        brakeline += (winch_target - brakeline) * lag(period_s, c.winch_tau_s);
+        let steady_rate = brakeline / MOTOR_MAX * c.max_turn_rate_deg_s;
+        turn_rate += (steady_rate - turn_rate) * lag(period_s, c.turn_tau_s);
+        heading = wrap360(heading + turn_rate * period_s);
+        if cfg.gust_sd_ms > 0.0 {
+            let decay = lag(period_s, cfg.gust_tau_s);
+            let kick = cfg.gust_sd_ms * (2.0 * decay).sqrt();
+            gust.0 += -gust.0 * decay + rng.normal(0.0, kick);
+            gust.1 += -gust.1 * decay + rng.normal(0.0, kick);
+        }
+        let next = LatLon::new(lat, lon).from_local((gnd_n * period_s, gnd_e * period_s));
+        (lat, lon) = (next.lat_deg, next.lon_deg);
+        i += 1;
+    }
+    let wind_profile = blims.wind_estimator().profile().collect();
+    Flight { samples, landing_lat: lat, landing_lon: lon, wind_profile }
+}
+
+/// Display name of a `BlimsDataOut::phase_id`.
+pub fn phase_name(phase_id: i8) -> &'static str {
+    match phase_id {
+        0 => "Held",
+        1 => "InitialHold",
+        2 => "Upwind",
+        3 => "Downwind",
+        4 => "Neutral",
+        5 => "Track",
+        6 => "Loiter",
+        7 => "DownwindLeg",
+        8 => "Base",
+        9 => "Final",
+        _ => "?",
+    }
+}
+
+/// Fraction of the way a first-order lag with time constant `tau_s` closes
+/// in `dt_s`; all of it with no lag.
+fn lag(dt_s: f32, tau_s: f32) -> f32 {
+    if tau_s > 0.0 { (dt_s / tau_s).min(1.0) } else { 1.0 }
+}
+
+/// One row of a recorded flight CSV.
+#[derive(Clone, Copy, Debug)]
+pub struct CsvRow {
+    pub time_ms: u64,
+    /// Barometric altitude (m AGL).
+    pub altitude_m: f32,
+    pub gps_valid: bool,
+    /// Decimal degrees and heading of motion (°).
+    pub lat: f64,
+    pub lon: f64,
+    pub heading: f32,
+}
+
+/// Reads a flight-computer CSV export with columns `Timestamp` (s),
+/// `Altitude` (m), `GPS_Status`, `Number_of_Satellites`, `Latitude` and
+/// `Longitude` (degrees × 1e7) and `Heading_of_Motion` (degrees × 1e5), in
+/// any order. A fix needs `GPS_Status` 1 and at least 4 satellites.
+pub fn load_csv(path: &Path) -> Result<Vec<CsvRow>, String> {
+    let text = std::fs::read_to_string(path).map_err(|e| format!("{}: {e}", path.display()))?;
+    let mut lines = text.lines();
+    let header: Vec<&str> = lines.next().unwrap_or_default().split(',').map(str::trim).collect();
+    let col = |name: &str| {
+        header.iter().position(|h| *h == name).ok_or_else(|| format!("{}: no {name} column", path.display()))
+    };
+    let (ts, alt, gps, sats) = (col("Timestamp")?, col("Altitude")?, col("GPS_Status")?, col("Number_of_Satellites")?);
+    let (lat, lon, hm) = (col("Latitude")?, col("Longitude")?, col("Heading_of_Motion")?);
+
+    let mut rows = Vec::new();
+    for line in lines {
+        let f: Vec<&str> = line.split(',').map(str::trim).collect();
+        let num = |i: usize| f.get(i).and_then(|v| v.parse::<f64>().ok());
+        let (Some(t), Some(a)) = (num(ts), num(alt)) else { continue };
+        rows.push(CsvRow {
+            time_ms: (t * 1000.0).round() as u64,
+            altitude_m: a as f32,
+            gps_valid: num(gps) == Some(1.0) && num(sats).unwrap_or(0.0) >= 4.0,
+            lat: num(lat).unwrap_or(0.0) * 1e-7,
+            lon: num(lon).unwrap_or(0.0) * 1e-7,
+            heading: (num(hm).unwrap_or(0.0) * 1e-5) as f32,
+        });
+    }
+    Ok(rows)
+}
@@ -0,0 +1,309 @@
+//! In-flight wind estimate from GPS ground velocity.
+//!
+//! Flying at constant airspeed, the canopy's ground velocity is its air
+//! velocity plus the wind, so as it turns the GPS `vel_n`/`vel_e` trace a
+//! circle centred on the wind vector with the airspeed as radius (the wind
+//! triangle). [`WindEstimator`] keeps one velocity sample per
+//! `WIND_SAMPLE_SPACING_DEG` of ground-track change over the last
+//! `WIND_SAMPLE_MAX_AGE_MS`, and once the track has swept `WIND_MIN_ARC_DEG`
+//! least-squares fits that circle after every new sample. Straight flight
+//! adds nothing, so the estimate comes from turns: the loiter, the pattern
+//! turns, the MVP Upwind → Downwind reversal.
+//!
+//! Accepted fits are low-passed into the current estimate and recorded in
+//! `WIND_PROFILE_BAND_M` altitude bands, so the wind measured on the way
+//! down can be logged and loaded as the profile for the next flight.
+//!
+//! [`WindProfile`] is the forecast loaded before flight (`set_wind_profile`,
+//! uploaded over the umbilical by the FSW): up to `MAX_WIND_LAYERS` of
+//! altitude, direction and speed.
+
+use crate::blims::{wrap180, wrap360};
+use crate::blims_constants::*;
+
+/// Wind blowing TO north/east (m/s), and the airspeed the same fit found.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Wind {
+    pub north_ms: f32,
+    pub east_ms: f32,
+    pub airspeed_ms: f32,
+}
+
+impl Wind {
+    pub fn speed_ms(&self) -> f32 {
+        libm::sqrtf(self.north_ms * self.north_ms + self.east_ms * self.east_ms)
+    }
+
+    /// Direction the wind blows FROM (°, 0 = N, 90 = E), as `set_wind_from_deg`.
+    pub fn from_deg(&self) -> f32 {
+        wrap360(libm::atan2f(-self.east_ms, -self.north_ms) * RAD_TO_DEG)
+    }
+}
+
+#[derive(Clone, Copy, Default)]
+struct VelSample {
+    vel_n: f32,
+    vel_e: f32,
+    track_deg: f32,
+    t_ms: u64,
+}
+
+pub struct WindEstimator {
+    /// Ring buffer of fit points, oldest overwritten first
+    samples: [VelSample; WIND_FIT_POINTS],
+    len: usize,
+    next: usize,
+    estimate: Option<Wind>,
+    /// Latest estimate made in each altitude band, lowest band first
+    profile: [Option<Wind>; MAX_WIND_LAYERS],
+}
+
+impl Default for WindEstimator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WindEstimator {
+    pub const fn new() -> Self {
+        Self {
+            samples: [VelSample { vel_n: 0.0, vel_e: 0.0, track_deg: 0.0, t_ms: 0 }; WIND_FIT_POINTS],
+            len: 0,
+            next: 0,
+            estimate: None,
+            profile: [None; MAX_WIND_LAYERS],
+        }
+    }
+
+    /// Feed one GPS velocity (m/s) at `altitude_m` AGL. Call every cycle
+    /// with a valid fix while the canopy is flying.
+    pub fn update(&mut self, vel_n: f32, vel_e: f32, altitude_m: f32, now_ms: u64) {
+        // Forget samples from too far up the profile
+        while self.len > 0 && now_ms.saturating_sub(self.oldest().t_ms) > WIND_SAMPLE_MAX_AGE_MS {
+            self.len -= 1;
+        }
+
+        let track_deg = wrap360(libm::atan2f(vel_e, vel_n) * RAD_TO_DEG);
+        if self.len > 0 {
+            let last = &self.samples[(self.next + WIND_FIT_POINTS - 1) % WIND_FIT_POINTS];
+            let turned = libm::fabsf(wrap180(track_deg - last.track_deg));
+            if turned < WIND_SAMPLE_SPACING_DEG {
+                return;
+            }
+        }
+        self.samples[self.next] = VelSample { vel_n, vel_e, track_deg, t_ms: now_ms };
+        self.next = (self.next + 1) % WIND_FIT_POINTS;
+        self.len = (self.len + 1).min(WIND_FIT_POINTS);
+
+        if self.arc_deg() < WIND_MIN_ARC_DEG {
+            return;
+        }
+        let Some(fit) = self.fit() else { return };
+        let wind = match self.estimate {
+            Some(w) => Wind {
+                north_ms: w.north_ms + WIND_FILTER_ALPHA * (fit.north_ms - w.north_ms),
+                east_ms: w.east_ms + WIND_FILTER_ALPHA * (fit.east_ms - w.east_ms),
+                airspeed_ms: w.airspeed_ms + WIND_FILTER_ALPHA * (fit.airspeed_ms - w.airspeed_ms),
+            },
+            None => fit,
+        };
+        self.estimate = Some(wind);
+        let band = ((altitude_m.max(0.0) / WIND_PROFILE_BAND_M) as usize).min(MAX_WIND_LAYERS - 1);
+        self.profile[band] = Some(wind);
+    }
+
+    /// Current wind estimate; `None` until the canopy has turned enough.
+    pub fn estimate(&self) -> Option<Wind> {
+        self.estimate
+    }
+
+    /// Estimated profile as (band floor altitude m AGL, wind), lowest first,
+    /// skipping bands with no estimate.
+    pub fn profile(&self) -> impl Iterator<Item = (f32, Wind)> + '_ {
+        self.profile
+            .iter()
+            .enumerate()
+            .filter_map(|(i, w)| w.map(|w| (i as f32 * WIND_PROFILE_BAND_M, w)))
+    }
+
+    fn oldest(&self) -> &VelSample {
+        &self.samples[(self.next + WIND_FIT_POINTS - self.len) % WIND_FIT_POINTS]
+    }
+
+    fn points(&self) -> impl Iterator<Item = &VelSample> {
+        (0..self.len).map(move |i| &self.samples[(self.next + WIND_FIT_POINTS - 1 - i) % WIND_FIT_POINTS])
+    }
+
+    /// Ground-track arc the samples cover: 360° less the widest gap between
+    /// neighbouring tracks.
+    fn arc_deg(&self) -> f32 {
+        let mut tracks = [0.0_f32; WIND_FIT_POINTS];
+        for (t, s) in tracks.iter_mut().zip(self.points()) {
+            *t = s.track_deg;
+        }
+        let tracks = &mut tracks[..self.len];
+        tracks.sort_unstable_by(|a, b| a.total_cmp(b));
+        let mut widest_gap = match (tracks.first(), tracks.last()) {
+            (Some(first), Some(last)) => first + 360.0 - last,
+            _ => return 0.0,
+        };
+        for pair in tracks.windows(2) {
+            widest_gap = widest_gap.max(pair[1] - pair[0]);
+        }
+        360.0 - widest_gap
+    }
+
+    /// Least-squares circle through the velocity samples (Kåsa fit, about
+    /// their mean for f32 conditioning). `None` if the points are degenerate
+    /// or the circle isn't a plausible wind and airspeed.
+    fn fit(&self) -> Option<Wind> {
+        let n = self.len as f32;
+        let (mut mn, mut me) = (0.0, 0.0);
+        for s in self.points() {
+            mn += s.vel_n;
+            me += s.vel_e;
+        }
+        mn /= n;
+        me /= n;
+
+        // Centred x² + y² = A·x + B·y + C, with the centre at (A/2, B/2)
+        let (mut sxx, mut sxy, mut syy, mut sxz, mut syz, mut sz) = (0.0, 0.0, 0.0, 0.0, 0.0, 0.0);
+        for s in self.points() {
+            let (x, y) = (s.vel_n - mn, s.vel_e - me);
+            let z = x * x + y * y;
+            sxx += x * x;
+            sxy += x * y;
+            syy += y * y;
+            sxz += x * z;
+            syz += y * z;
+            sz += z;
+        }
+        let det = sxx * syy - sxy * sxy;
+        if det <= 1e-6 * n * n {
+            return None;
+        }
+        let a = (sxz * syy - syz * sxy) / det;
+        let b = (syz * sxx - sxz * sxy) / det;
+        let (cn, ce) = (a / 2.0, b / 2.0);
+        let radius = libm::sqrtf(sz / n + cn * cn + ce * ce);
+
+        let wind = Wind { north_ms: mn + cn, east_ms: me + ce, airspeed_ms: radius };
+        let plausible = (WIND_FIT_MIN_AIRSPEED_MS..=WIND_FIT_MAX_AIRSPEED_MS).contains(&radius)
+            && wind.speed_ms() <= WIND_FIT_MAX_SPEED_MS;
+        plausible.then_some(wind)
+    }
+}
+
+/// One forecast layer.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct WindLayer {
+    /// m AGL
+    pub altitude_m: f32,
+    /// Degrees FROM, [0, 360)
+    pub from_deg: f32,
+    pub speed_ms: f32,
+}
+
+impl WindLayer {
+    pub fn is_valid(&self) -> bool {
+        (0.0..=WIND_LAYER_MAX_ALT_M).contains(&self.altitude_m)
+            && (0.0..360.0).contains(&self.from_deg)
+            && (0.0..=WIND_LAYER_MAX_SPEED_MS).contains(&self.speed_ms)
+    }
+}
+
+/// Forecast wind profile, layers in strictly increasing altitude.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WindProfile {
+    layers: [WindLayer; MAX_WIND_LAYERS],
+    len: usize,
+}
+
+impl Default for WindProfile {
+    fn default() -> Self {
+        Self::EMPTY
+    }
+}
+
+impl WindProfile {
+    pub const EMPTY: Self = Self {
+        layers: [WindLayer { altitude_m: 0.0, from_deg: 0.0, speed_ms: 0.0 }; MAX_WIND_LAYERS],
+        len: 0,
+    };
+
+    /// `None` if there are more than `MAX_WIND_LAYERS`, any layer is out of
+    /// range, or the altitudes don't strictly increase.
+    pub fn from_layers(layers: &[WindLayer]) -> Option<Self> {
+        if layers.len() > MAX_WIND_LAYERS
+            || !layers.iter().all(WindLayer::is_valid)
+            || layers.windows(2).any(|w| w[1].altitude_m <= w[0].altitude_m)
+        {
+            return None;
+        }
+        let mut profile = Self::EMPTY;
+        profile.layers[..layers.len()].copy_from_slice(layers);
+        profile.len = layers.len();
+        Some(profile)
+    }
+
+    /// Same, from parallel arrays; `None` also if their lengths differ.
+    pub fn from_columns(altitudes_m: &[f32], directions_deg: &[f32], speeds_ms: &[f32]) -> Option<Self> {
+        let n = altitudes_m.len();
+        if directions_deg.len() != n || speeds_ms.len() != n || n > MAX_WIND_LAYERS {
+            return None;
+        }
+        let mut layers = [WindLayer::default(); MAX_WIND_LAYERS];
+        for (i, layer) in layers[..n].iter_mut().enumerate() {
+            *layer = WindLayer { altitude_m: altitudes_m[i], from_deg: directions_deg[i], speed_ms: speeds_ms[i] };
+        }
+        Self::from_layers(&layers[..n])
+    }
+
+    pub fn layers(&self) -> &[WindLayer] {
+        &self.layers[..self.len]
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Fletcher-style sum over each layer's altitude, direction and speed
+    /// (f32 little-endian, in that order), as the flash records use; shown
+    /// in telemetry so the ground can confirm the upload. 0 when empty.
+    pub fn checksum(&self) -> u32 {
+        let (mut a, mut s) = (0u32, 0u32);
+        for l in self.layers() {
+            for v in [l.altitude_m, l.from_deg, l.speed_ms] {
+                for byte in v.to_le_bytes() {
+                    a = a.wrapping_add(byte as u32);
+                    s = s.wrapping_add(a);
+                }
+            }
+        }
+        (s << 16) ^ a
+    }
+
+    /// (degrees FROM, m/s) at `altitude_m`, interpolated between layers the
+    /// short way round and held beyond the ends. `None` when empty.
+    pub fn at(&self, altitude_m: f32) -> Option<(f32, f32)> {
+        let layers = self.layers();
+        let (first, last) = (layers.first()?, layers.last()?);
+        if altitude_m <= first.altitude_m {
+            return Some((first.from_deg, first.speed_ms));
+        }
+        if altitude_m >= last.altitude_m {
+            return Some((last.from_deg, last.speed_ms));
+        }
+        let i = layers.windows(2).position(|w| altitude_m < w[1].altitude_m)?;
+        let (lo, hi) = (&layers[i], &layers[i + 1]);
+        let t = (altitude_m - lo.altitude_m) / (hi.altitude_m - lo.altitude_m);
+        Some((
+            wrap360(lo.from_deg + t * wrap180(hi.from_deg - lo.from_deg)),
+            lo.speed_ms + t * (hi.speed_ms - lo.speed_ms),
+        ))
+    }
+}
@@ -0,0 +1,796 @@
+//! Host tests of the BLiMS guidance and PI logic, open loop against a
+//! recording actuator and closed loop through `blims::sim`.
+//!
+//!     cargo test --no-default-features --features std --target <host triple>
+
+use blims::blims::{compute_heading_error, wrap180, wrap360};
+use blims::blims_constants::*;
+use blims::pattern::{self, Pattern};
+use blims::sim::{self, Canopy, SimConfig};
+use blims::geo::EARTH_RADIUS_M;
+use blims::monte_carlo::{self, Dispersion};
+use blims::geofence::{FENCE_AVOIDING, FENCE_IN_KEEP_OUT, FENCE_NO_SAFE_TARGET, FENCE_OUTSIDE_LZ, FENCE_TARGET_MOVED};
+use blims::heading::HeadingFilter;
+use blims::{
+    Actuator, Blims, BlimsConfig, BlimsDataIn, CalibrationTable, Characterisation, Geofence, GuidanceMode, LatLon, Phase, TrackingLaw, WindEstimator, WindLayer,
+    WindProfile, Zone, ZoneKind,
+};
+
+/// Records every pulse it is given.
+#[derive(Default)]
+struct Recorder {
+    pulses: Vec<f32>,
+    enabled: bool,
+}
+
+impl Actuator for Recorder {
+    fn set_pulse_us(&mut self, pulse_us: f32) {
+        self.pulses.push(pulse_us);
+    }
+
+    fn enable(&mut self) {
+        self.enabled = true;
+    }
+}
+
+const TARGET: (f64, f64) = (42.698, -77.1911);
+
+/// Valid 3D fix at `altitude_ft`, 400 m south of `TARGET` (bearing 0°), moving
+/// along `track_deg`.
+fn fix(altitude_ft: f32, track_deg: f32) -> BlimsDataIn {
+    BlimsDataIn {
+        lat: ((TARGET.0 - 400.0 / 111_195.0) * 1e7).round() as i32,
+        lon: (TARGET.1 * 1e7).round() as i32,
+        altitude_ft,
+        g_speed: 7_500,
+        head_mot: (track_deg * 1e5) as i32,
+        fix_type: 3,
+        gps_state: true,
+        ..Default::default()
+    }
+}
+
+fn controller() -> Blims<Recorder> {
+    let mut blims = Blims::new(Recorder::default(), BlimsConfig::default());
+    blims.set_upwind_target(TARGET.0, TARGET.1);
+    blims.set_downwind_target(TARGET.0, TARGET.1);
+    blims
+}
+
+#[test]
+fn heading_error_wraps() {
+    assert_eq!(wrap360(-90.0), 270.0);
+    assert_eq!(wrap360(725.0), 5.0);
+    assert_eq!(wrap180(270.0), -90.0);
+    assert!((compute_heading_error(10.0, 350.0) - 20.0).abs() < 1e-4);
+    assert!((compute_heading_error(350.0, 10.0) + 20.0).abs() < 1e-4);
+    assert!((compute_heading_error(90.0, 270.0).abs() - 180.0).abs() < 1e-4);
+}
+
+#[test]
+fn actuator_sees_commands_and_enable() {
+    let mut blims = controller();
+    // Parked at neutral on construction, driver off until enabled
+    assert_eq!(blims.actuator().pulses, [PULSE_NEUTRAL_US]);
+    assert!(!blims.actuator().enabled);
+    blims.enable();
+    assert!(blims.actuator().enabled);
+
+    // Target dead ahead of a canopy tracking 90° → turn left
+    let out = blims.execute(&fix(1500.0, 90.0), 0);
+    assert_eq!(out.phase_id, Phase::Upwind as i8);
+    assert!(out.brakeline_diff_in < 0.0);
+    assert_eq!(*blims.actuator().pulses.last().unwrap(), CalibrationTable::LINEAR.pulse_us(out.brakeline_diff_in));
+
+    // Full reversal stays within the motor limits
+    let out = blims.execute(&fix(1500.0, 180.0), 50);
+    assert!(out.brakeline_diff_in >= MOTOR_MIN && out.brakeline_diff_in <= MOTOR_MAX);
+}
+
+#[test]
+fn integral_follows_supplied_time() {
+    let mut blims = controller();
+    // Constant ~30° error; the first cycle integrates a nominal 50 ms, then
+    // the gaps between the supplied times
+    let mut now = 10_000;
+    let first = blims.execute(&fix(1500.0, 330.0), now);
+    let error = first.pid_p / KP;
+    assert!((error - 30.0).abs() < 1.0, "heading error {error}");
+    assert!((first.pid_i - KI * error * 0.05).abs() < 1e-5, "first cycle pid_i {}", first.pid_i);
+    // The diagnostics agree with the terms
+    assert!(first.gps_valid);
+    assert_eq!(first.activation_time_ms, Some(now));
+    assert_eq!(first.dt_s, 0.05);
+    assert!((first.heading_error_deg - error).abs() < 1e-4);
+    assert!((compute_heading_error(first.desired_heading_deg, first.heading_deg) - error).abs() < 1e-4);
+    assert!((KI * first.error_integral - first.pid_i).abs() < 1e-6);
+    for _ in 0..10 {
+        now += 100;
+        blims.execute(&fix(1500.0, 330.0), now);
+    }
+    let out = blims.execute(&fix(1500.0, 330.0), now);
+    // 0.05 s + 10 × 0.1 s + a repeated timestamp (0 s)
+    assert!((out.pid_i - KI * error * 1.05).abs() < 1e-5, "pid_i {}", out.pid_i);
+
+    // A gap past 200 ms counts as a nominal cycle, not a windup spike (on a
+    // fresh GPS solution; the heading of the old one has expired)
+    let mut fresh = fix(1500.0, 330.0);
+    fresh.itow = 1_000;
+    let out = blims.execute(&fresh, now + 5_000);
+    assert!((out.pid_i - KI * error * 1.10).abs() < 1e-5, "pid_i after gap {}", out.pid_i);
+    assert_eq!(out.dt_s, 0.05);
+    assert_eq!(out.activation_time_ms, Some(10_000));
+}
+
+#[test]
+fn no_fix_holds_neutral() {
+    let mut blims = controller();
+    let mut data = fix(1500.0, 90.0);
+    data.fix_type = 0;
+    let out = blims.execute(&data, 0);
+    assert_eq!(out.phase_id, Phase::Held as i8);
+    assert_eq!(out.brakeline_diff_in, NEUTRAL_POS);
+    assert!(!out.gps_valid);
+    assert_eq!(out.activation_time_ms, None);
+    assert_eq!((out.desired_heading_deg, out.heading_error_deg), (0.0, 0.0));
+}
+
+#[test]
+fn phases_follow_altitude() {
+    let cfg = SimConfig::default();
+    let flight = sim::fly(&cfg);
+    let mut phases: Vec<i8> = flight.samples.iter().map(|s| s.out.phase_id).collect();
+    phases.dedup();
+    assert_eq!(phases, [Phase::Upwind as i8, Phase::Downwind as i8, Phase::Neutral as i8]);
+    for s in &flight.samples {
+        let alt_ft = s.alt_m * FT_PER_M;
+        match s.out.phase_id {
+            2 => assert!(alt_ft > ALT_UPWIND_FT),
+            3 => assert!(alt_ft > ALT_NEUTRAL_FT && alt_ft <= ALT_UPWIND_FT),
+            _ => assert!(alt_ft <= ALT_NEUTRAL_FT),
+        }
+        assert!(s.out.brakeline_diff_in >= MOTOR_MIN && s.out.brakeline_diff_in <= MOTOR_MAX);
+    }
+}
+
+#[test]
+fn config_sets_gains_and_phase_altitudes() {
+    let config = BlimsConfig { kp: 2.0 * KP, alt_upwind_ft: 1500.0, alt_neutral_ft: 300.0, ..BlimsConfig::default() };
+    assert!(config.is_valid());
+    let mut blims = controller();
+    blims.set_config(&config);
+    let out = blims.execute(&fix(1600.0, 330.0), 0);
+    assert_eq!(out.phase_id, Phase::Upwind as i8);
+    assert!((out.pid_p / (2.0 * KP) - 30.0).abs() < 1.0, "pid_p {}", out.pid_p);
+    assert_eq!(blims.execute(&fix(1400.0, 330.0), 50).phase_id, Phase::Downwind as i8);
+    assert_eq!(blims.execute(&fix(290.0, 330.0), 100).phase_id, Phase::Neutral as i8);
+
+    let bad = [
+        BlimsConfig { kp: f32::NAN, ..config },
+        BlimsConfig { ki: -0.001, ..config },
+        BlimsConfig { integral_max_in: MOTOR_MAX + 1.0, ..config },
+        // Upwind boundary inside the Neutral one's hysteresis band
+        BlimsConfig { alt_upwind_ft: 310.0, ..config },
+        BlimsConfig { initial_hold_ms: 600_000, ..config },
+    ];
+    for c in bad {
+        assert!(!c.is_valid(), "{c:?}");
+    }
+}
+
+#[test]
+fn phase_boundaries_have_hysteresis() {
+    let mut blims = controller();
+    let mut now = 0;
+    let mut phase = |alt_ft: f32| {
+        now += 50;
+        blims.execute(&fix(alt_ft, 0.0), now).phase_id
+    };
+    assert_eq!(phase(ALT_UPWIND_FT + 5.0), Phase::Upwind as i8);
+    assert_eq!(phase(ALT_UPWIND_FT - 5.0), Phase::Downwind as i8);
+    // Noise back above the boundary doesn't revert the phase…
+    for alt_ft in [ALT_UPWIND_FT + 5.0, ALT_UPWIND_FT + PHASE_HYSTERESIS_FT, ALT_UPWIND_FT - 5.0] {
+        assert_eq!(phase(alt_ft), Phase::Downwind as i8, "at {alt_ft} ft");
+    }
+    // …a real climb does
+    assert_eq!(phase(ALT_UPWIND_FT + PHASE_HYSTERESIS_FT + 1.0), Phase::Upwind as i8);
+
+    assert_eq!(phase(ALT_NEUTRAL_FT), Phase::Neutral as i8);
+    assert_eq!(phase(ALT_NEUTRAL_FT + PHASE_HYSTERESIS_FT), Phase::Neutral as i8);
+    assert_eq!(phase(ALT_NEUTRAL_FT + PHASE_HYSTERESIS_FT + 1.0), Phase::Downwind as i8);
+}
+
+#[test]
+fn canopy_turns_toward_target() {
+    // Start heading directly away from the upwind waypoint, no wind
+    let mut cfg = SimConfig { wind_speed_ms: 0.0, ..SimConfig::default() };
+    let (lat, lon) = cfg.upwind_target;
+    cfg.start_heading_deg = wrap360(sim::bearing_deg(cfg.start_lat, cfg.start_lon, lat, lon) + 180.0);
+    let flight = sim::fly(&cfg);
+
+    // A 180° turn at up to 15°/s is done well within 30 s
+    let s = flight.samples.iter().find(|s| s.t_ms == 30_000).unwrap();
+    let bearing = sim::bearing_deg(s.lat, s.lon, lat, lon);
+    assert!(compute_heading_error(bearing, s.track).abs() < 20.0, "track {} bearing {bearing}", s.track);
+}
+
+#[test]
+fn lands_near_target_in_calm_and_wind() {
+    for (wind, limit_m) in [(0.0, 150.0), (3.0, 150.0)] {
+        let cfg = SimConfig { wind_speed_ms: wind, ..SimConfig::default() };
+        let miss = sim::fly(&cfg).miss_m(&cfg);
+        assert!(miss < limit_m, "{wind} m/s wind: landed {miss:.0} m from target");
+    }
+}
+
+#[test]
+fn seeded_runs_repeat() {
+    let cfg = SimConfig { seed: 7, ..SimConfig::default() };
+    let (a, b) = (sim::fly(&cfg), sim::fly(&cfg));
+    assert_eq!(a.samples.len(), b.samples.len());
+    assert_eq!((a.landing_lat, a.landing_lon), (b.landing_lat, b.landing_lon));
+    let c = sim::fly(&SimConfig { seed: 8, ..cfg });
+    assert_ne!((a.landing_lat, a.landing_lon), (c.landing_lat, c.landing_lon));
+}
+
+#[test]
+fn pattern_is_sized_from_sink_and_wind() {
+    let calm = Pattern::plan(270.0, 0.0, 7.5, 8.2);
+    let slow = Pattern::plan(270.0, 0.0, 7.5, 4.1);
+    assert!((slow.final_m / calm.final_m - 2.0).abs() < 1e-3);
+    assert!((slow.downwind_m / calm.downwind_m - 2.0).abs() < 1e-3);
+
+    // Wind from the west: final runs west into it from a turn point east of
+    // the target, legs to the south (left-hand), longer downwind than final
+    let windy = Pattern::plan(270.0, 3.0, 7.5, 8.2);
+    let t = (0.0, 0.0);
+    assert!(compute_heading_error(90.0, pattern::bearing(t, windy.final_turn())).abs() < 0.1);
+    assert!(windy.base_turn().0 < 0.0 && windy.entry().0 < 0.0);
+    assert!(windy.final_m < calm.final_m && windy.downwind_m > calm.downwind_m);
+    assert!(compute_heading_error(90.0, pattern::bearing(windy.entry(), windy.base_turn())).abs() < 0.1);
+}
+
+#[test]
+fn pattern_resumes_after_gps_dropout() {
+    let mut blims = controller();
+    assert_eq!(blims.guidance_mode(), GuidanceMode::Mvp);
+    blims.set_guidance_mode(GuidanceMode::Pattern);
+
+    assert_eq!(blims.execute(&fix(1500.0, 0.0), 0).phase_id, Phase::Track as i8);
+    assert_eq!(blims.execute(&fix(900.0, 0.0), 50).phase_id, Phase::DownwindLeg as i8);
+    let mut lost = fix(850.0, 0.0);
+    lost.fix_type = 0;
+    assert_eq!(blims.execute(&lost, 100).phase_id, Phase::Held as i8);
+    // Back on the same leg, not Track, even climbing through the gate
+    assert_eq!(blims.execute(&fix(1100.0, 0.0), 150).phase_id, Phase::DownwindLeg as i8);
+    assert_eq!(blims.execute(&fix(500.0, 0.0), 200).phase_id, Phase::Base as i8);
+    assert_eq!(blims.execute(&fix(300.0, 0.0), 250).phase_id, Phase::Final as i8);
+    assert_eq!(blims.execute(&fix(150.0, 0.0), 300).phase_id, Phase::Neutral as i8);
+}
+
+#[test]
+fn pattern_lands_closer_than_mvp() {
+    // A canopy that glides, deployed high enough to loiter
+    let canopy = Canopy { airspeed_ms: 9.0, sink_rate_ms: 4.5, max_turn_rate_deg_s: 20.0, ..Canopy::NOMINAL };
+    let (mut mvp_m, mut pattern_m) = (0.0, 0.0);
+    for wind in [0.0, 2.0, 4.0] {
+        let cfg = SimConfig { canopy, wind_speed_ms: wind, start_alt_m: 1200.0, ..SimConfig::default() };
+        mvp_m += sim::fly(&cfg).miss_m(&cfg);
+
+        let cfg = SimConfig { mode: GuidanceMode::Pattern, ..cfg };
+        let flight = sim::fly(&cfg);
+        let mut phases: Vec<i8> = flight.samples.iter().map(|s| s.out.phase_id).collect();
+        phases.dedup();
+        assert_eq!(
+            phases,
+            [Phase::Track, Phase::Loiter, Phase::DownwindLeg, Phase::Base, Phase::Final, Phase::Neutral]
+                .map(|p| p as i8),
+            "{wind} m/s wind"
+        );
+        let miss = flight.miss_m(&cfg);
+        assert!(miss < 100.0, "{wind} m/s wind: pattern landed {miss:.0} m from target");
+        pattern_m += miss;
+    }
+    assert!(pattern_m < mvp_m, "pattern {:.0} m vs MVP {:.0} m average miss", pattern_m / 3.0, mvp_m / 3.0);
+}
+
+#[test]
+fn wind_estimate_from_circling() {
+    // 8 m/s airspeed in 3 m/s from the west, turning 10°/s
+    let (wind_n, wind_e) = (0.0, 3.0);
+    let mut est = WindEstimator::new();
+    let mut first_fit_deg = None;
+    for i in 0..720 {
+        let heading = (i as f32 * 0.5) * DEG_TO_RAD;
+        est.update(8.0 * heading.cos() + wind_n, 8.0 * heading.sin() + wind_e, 500.0, i * 50);
+        if first_fit_deg.is_none() && est.estimate().is_some() {
+            first_fit_deg = Some(i as f32 * 0.5);
+        }
+    }
+    // Needs WIND_MIN_ARC_DEG of ground track; the track turns unevenly in wind
+    let first = first_fit_deg.expect("no estimate after a full circle");
+    assert!((90.0..=180.0).contains(&first), "first fit after {first}° of turn");
+
+    let w = est.estimate().unwrap();
+    assert!((w.speed_ms() - 3.0).abs() < 0.05, "speed {}", w.speed_ms());
+    assert!(compute_heading_error(270.0, w.from_deg()).abs() < 1.0, "from {}", w.from_deg());
+    assert!((w.airspeed_ms - 8.0).abs() < 0.05, "airspeed {}", w.airspeed_ms);
+    let profile: Vec<_> = est.profile().collect();
+    assert_eq!(profile.len(), 1);
+    assert_eq!(profile[0].0, 500.0);
+}
+
+#[test]
+fn straight_flight_gives_no_wind_estimate() {
+    let mut est = WindEstimator::new();
+    for i in 0..2_000 {
+        est.update(7.0, 2.0, 500.0, i * 50);
+    }
+    assert!(est.estimate().is_none());
+
+    let mut blims = controller();
+    let out = blims.execute(&fix(1500.0, 0.0), 0);
+    assert!(!out.wind_valid);
+    assert_eq!((out.wind_speed_ms, out.wind_from_deg), (0.0, 0.0));
+}
+
+#[test]
+fn pattern_plans_with_estimated_wind() {
+    // Forecast calm, actually 4 m/s from 200°: the loiter measures the wind
+    // before the pattern is committed at PATTERN_ENTRY_ALT_FT
+    let canopy = Canopy { airspeed_ms: 9.0, sink_rate_ms: 4.5, max_turn_rate_deg_s: 20.0, ..Canopy::NOMINAL };
+    let cfg = SimConfig {
+        canopy,
+        mode: GuidanceMode::Pattern,
+        wind_speed_ms: 4.0,
+        forecast: Some((0.0, 0.0)),
+        start_alt_m: 1200.0,
+        ..SimConfig::default()
+    };
+    let flight = sim::fly(&cfg);
+    let entry = flight.samples.iter().find(|s| s.out.phase_id == Phase::DownwindLeg as i8).unwrap();
+    assert!(entry.out.wind_valid);
+    assert!((entry.out.wind_speed_ms - 4.0).abs() < 0.5, "estimated {} m/s", entry.out.wind_speed_ms);
+    assert!(compute_heading_error(200.0, entry.out.wind_from_deg).abs() < 10.0, "from {}", entry.out.wind_from_deg);
+
+    let miss = flight.miss_m(&cfg);
+    assert!(miss < 100.0, "landed {miss:.0} m from target");
+    assert!(flight.wind_profile.len() >= 3, "{} profile bands", flight.wind_profile.len());
+}
+
+#[test]
+fn wind_profile_validates_and_interpolates() {
+    let layer = |altitude_m, from_deg, speed_ms| WindLayer { altitude_m, from_deg, speed_ms };
+    let profile = WindProfile::from_layers(&[layer(0.0, 350.0, 2.0), layer(100.0, 10.0, 6.0)]).unwrap();
+    let (from, speed) = profile.at(50.0).unwrap();
+    assert!(compute_heading_error(0.0, from).abs() < 0.01, "from {from}");
+    assert!((speed - 4.0).abs() < 1e-4);
+    assert_eq!(profile.at(-10.0), Some((350.0, 2.0)));
+    assert_eq!(profile.at(500.0), Some((10.0, 6.0)));
+    assert_eq!(WindProfile::EMPTY.at(50.0), None);
+
+    assert!(WindProfile::from_layers(&[layer(100.0, 0.0, 1.0), layer(100.0, 0.0, 1.0)]).is_none());
+    assert!(WindProfile::from_layers(&[layer(0.0, 360.0, 1.0)]).is_none());
+    assert!(WindProfile::from_layers(&[layer(0.0, 0.0, f32::NAN)]).is_none());
+    assert!(WindProfile::from_layers(&[layer(0.0, 0.0, 1.0); MAX_WIND_LAYERS + 1]).is_none());
+    assert!(WindProfile::from_columns(&[0.0, 50.0], &[0.0], &[1.0, 1.0]).is_none());
+
+    assert_eq!(WindProfile::EMPTY.checksum(), 0);
+    let same = WindProfile::from_columns(&[0.0, 100.0], &[350.0, 10.0], &[2.0, 6.0]).unwrap();
+    assert_eq!(same.checksum(), profile.checksum());
+    let other = WindProfile::from_columns(&[0.0, 100.0], &[350.0, 10.0], &[2.0, 6.5]).unwrap();
+    assert_ne!(other.checksum(), profile.checksum());
+
+    // Loaded as-is into the controller
+    let mut blims = controller();
+    blims.set_wind_profile(&profile);
+    assert_eq!(blims.wind_profile().len(), 2);
+}
+
+#[test]
+fn geodesy_is_great_circle() {
+    // One degree along the equator and along a meridian
+    let origin = LatLon::new(0.0, 0.0);
+    let degree_m = EARTH_RADIUS_M * std::f64::consts::PI / 180.0;
+    assert!((origin.distance_m(LatLon::new(0.0, 1.0)) - degree_m).abs() < 1e-6);
+    assert!((origin.distance_m(LatLon::new(1.0, 0.0)) - degree_m).abs() < 1e-6);
+    assert!((origin.bearing_deg(LatLon::new(0.0, 1.0)) - 90.0).abs() < 1e-9);
+    // Heading east from 60°N the great circle bends toward the pole
+    let north = LatLon::new(60.0, 0.0);
+    assert!(north.bearing_deg(LatLon::new(60.0, 10.0)) < 90.0 - 4.0);
+
+    // One LSB of a 1e7 fix is ~1 cm and is resolved
+    let target = LatLon::new(TARGET.0, TARGET.1);
+    let fix = LatLon::from_e7((TARGET.0 * 1e7).round() as i32 + 1, (TARGET.1 * 1e7).round() as i32);
+    assert!((target.distance_m(fix) - 0.011).abs() < 0.001, "{} m", target.distance_m(fix));
+
+    // Offsets round-trip through distance, bearing and the local frame
+    let p = target.offset(135.0, 2_500.0);
+    assert!((target.distance_m(p) - 2_500.0).abs() < 1e-6);
+    assert!((target.bearing_deg(p) - 135.0).abs() < 1e-6);
+    let (n, e) = p.local_m(target);
+    assert!(target.from_local((n, e)).distance_m(p) < 0.01);
+
+    // Cross-track positive right of the track, along-track from its start
+    let start = target.offset(180.0, 500.0);
+    let right = target.offset(270.0, 1_000.0).offset(90.0, 1_030.0);
+    assert!((right.cross_track_m(start, target) - 30.0).abs() < 0.01);
+    let left = start.offset(0.0, 200.0).offset(270.0, 40.0);
+    assert!((left.cross_track_m(start, target) + 40.0).abs() < 0.01);
+    assert!((left.along_track_m(start, target) - 200.0).abs() < 0.01);
+    assert!(target.offset(180.0, 600.0).along_track_m(start, target) < 0.0);
+}
+
+#[test]
+fn reports_distance_and_cross_track() {
+    let mut blims = controller();
+    blims.enable();
+    // 400 m south of the waypoint, on the line from where the phase began
+    let out = blims.execute(&fix(1500.0, 0.0), 0);
+    assert!((out.distance_to_target_m - 400.0).abs() < 0.5, "{} m", out.distance_to_target_m);
+    assert!(out.cross_track_m.abs() < 0.01);
+
+    // 50 m east of that line → right of a northbound leg
+    let mut east = fix(1500.0, 0.0);
+    east.lon += (50.0 / (111_195.0 * TARGET.0.to_radians().cos()) * 1e7) as i32;
+    let out = blims.execute(&east, 50);
+    assert!((out.cross_track_m - 50.0).abs() < 0.5, "{} m", out.cross_track_m);
+    // Distance held through a dropout
+    let mut lost = east;
+    lost.fix_type = 0;
+    assert_eq!(blims.execute(&lost, 100).distance_to_target_m, out.distance_to_target_m);
+}
+
+#[test]
+fn l1_holds_the_leg_in_crosswind() {
+    // 4 m/s across the legs: steering at the waypoint lets the canopy drift
+    // off the line and crab back in along a curve; L1 flies the line
+    let worst = |law| {
+        let cfg = SimConfig {
+            tracking_law: law,
+            wind_from_deg: 90.0,
+            wind_speed_ms: 4.0,
+            start_heading_deg: 180.0,
+            ..SimConfig::default()
+        };
+        let flight = sim::fly(&cfg);
+        let worst = flight
+            .samples
+            .iter()
+            .filter(|s| s.out.phase_id == Phase::Downwind as i8)
+            .skip(200) // the turn onto the leg
+            .map(|s| s.out.cross_track_m.abs())
+            .fold(0.0, f32::max);
+        (worst, flight.miss_m(&cfg))
+    };
+    let (bearing_m, bearing_miss) = worst(TrackingLaw::Bearing);
+    let (l1_m, l1_miss) = worst(TrackingLaw::L1);
+    println!("cross-track bearing {bearing_m:.1} m, L1 {l1_m:.1} m; miss {bearing_miss:.0} / {l1_miss:.0} m");
+    assert!(l1_m < bearing_m, "L1 {l1_m:.1} m vs bearing {bearing_m:.1} m off the leg");
+    assert!(l1_miss < 150.0, "L1 landed {l1_miss:.0} m from target");
+}
+
+#[test]
+fn slow_or_stale_heading_holds_neutral() {
+    let mut blims = controller();
+    // Too slow for a GPS heading and no gyro: keep the phase, hold straight
+    let mut slow = fix(1500.0, 90.0);
+    slow.g_speed = 2_000;
+    let out = blims.execute(&slow, 0);
+    assert_eq!(out.phase_id, Phase::Upwind as i8);
+    assert!(!out.heading_valid);
+    assert_eq!((out.brakeline_diff_in, out.pid_p, out.pid_i), (NEUTRAL_POS, 0.0, 0.0));
+
+    // A good fix, then the canopy slows: the gyro carries the heading
+    // through the turn for HEADING_COAST_MS
+    let mut gyro = fix(1500.0, 90.0);
+    (gyro.itow, gyro.yaw_rate_dps, gyro.yaw_rate_valid) = (50, -10.0, true);
+    assert!(blims.execute(&gyro, 50).heading_valid);
+    let mut now = 50;
+    let mut last = None;
+    while now < 50 + HEADING_COAST_MS {
+        now += 50;
+        slow.itow = now as u32; // a new solution each cycle, all too slow to use
+        let out = blims.execute(&BlimsDataIn { yaw_rate_dps: -10.0, yaw_rate_valid: true, ..slow }, now);
+        assert!(out.heading_valid, "lost at {now} ms");
+        last = Some(out);
+    }
+    let last = last.unwrap();
+    // 50° of left turn integrated from the gyro; the target is due north
+    assert!(compute_heading_error(40.0, last.heading_deg).abs() < 0.5, "heading {}", last.heading_deg);
+    assert!(last.brakeline_diff_in < 0.0);
+    let out = blims.execute(&BlimsDataIn { yaw_rate_dps: -10.0, yaw_rate_valid: true, ..slow }, now + 100);
+    assert!(!out.heading_valid);
+    assert_eq!(out.brakeline_diff_in, NEUTRAL_POS);
+}
+
+#[test]
+fn heading_filter_weights_gps_by_speed_and_accuracy() {
+    let good = BlimsDataIn { g_speed: 7_000, head_acc: 100_000, ..fix(1500.0, 0.0) };
+    assert!((HeadingFilter::weight(&good) - (1.0 - 1.0 / HEADING_ACC_MAX_DEG)).abs() < 1e-5);
+    assert_eq!(HeadingFilter::weight(&BlimsDataIn { g_speed: GSPEED_MIN_FOR_HEADING, ..good }), 0.0);
+    assert_eq!(HeadingFilter::weight(&BlimsDataIn { head_acc: 4_000_000, ..good }), 0.0);
+    let half = (GSPEED_MIN_FOR_HEADING + GSPEED_FULL_HEADING) / 2;
+    assert!((HeadingFilter::weight(&BlimsDataIn { g_speed: half, head_acc: 0, ..good }) - 0.5).abs() < 1e-5);
+
+    // With a gyro, a lone GPS heading 90° off only moves the estimate part
+    // of the way, by the time since the last solution over HEADING_GPS_TAU_S
+    let mut filter = HeadingFilter::new();
+    let gyro = BlimsDataIn { yaw_rate_valid: true, head_acc: 0, ..good };
+    assert_eq!(filter.update(&gyro, 0, 0.05), Some(0.0));
+    let off = BlimsDataIn { itow: 1_000, head_mot: 90 * 100_000, ..gyro };
+    let h = filter.update(&off, 1_000, 0.05).unwrap();
+    assert!((h - 90.0 * 1.0 / HEADING_GPS_TAU_S).abs() < 1e-3, "heading {h}");
+    // The same solution again is not counted twice
+    assert_eq!(filter.update(&off, 1_050, 0.05), Some(h));
+}
+
+#[test]
+fn gyro_steers_between_slow_gps_solutions() {
+    // 1 Hz GPS as on the vehicle: the track only updates once a second, so
+    // steering on it alone overshoots the turns
+    let miss = |gyro| {
+        let cfg = SimConfig { gps_period_ms: 1_000, gyro, wind_speed_ms: 2.0, ..SimConfig::default() };
+        let flight = sim::fly(&cfg);
+        // Worst error against the true track, through the reversal onto Downwind
+        let err = flight
+            .samples
+            .iter()
+            .filter(|s| s.out.phase_id == Phase::Downwind as i8)
+            .map(|s| compute_heading_error(s.out.heading_deg, s.track).abs())
+            .fold(0.0, f32::max);
+        (err, flight.miss_m(&cfg))
+    };
+    let (gyro_err, gyro_miss) = miss(true);
+    let (gps_err, gps_miss) = miss(false);
+    println!("worst heading error gyro {gyro_err:.1}° vs GPS only {gps_err:.1}°; miss {gyro_miss:.0} / {gps_miss:.0} m");
+    assert!(gyro_err < gps_err);
+    assert!(gyro_miss < 150.0, "landed {gyro_miss:.0} m from target");
+}
+
+/// Rectangle `north_m` × `east_m` centred `offset_m` (north, east) from
+/// `centre`.
+fn rect(kind: ZoneKind, centre: (f64, f64), offset_m: (f32, f32), north_m: f32, east_m: f32) -> Zone {
+    let c = LatLon::new(centre.0, centre.1).from_local(offset_m);
+    let corners = [(1.0, 1.0), (1.0, -1.0), (-1.0, -1.0), (-1.0, 1.0)]
+        .map(|(n, e)| c.from_local((n * north_m / 2.0, e * east_m / 2.0)));
+    Zone::new(kind, &corners).unwrap()
+}
+
+/// Any sample of `flight` inside a keep-out of `fence`.
+fn entered_keep_out(flight: &sim::Flight, fence: &Geofence) -> bool {
+    flight
+        .samples
+        .iter()
+        .any(|s| fence.keep_out_at(fence.local_m(LatLon::new(s.lat, s.lon))).is_some())
+}
+
+#[test]
+fn geofence_validates_and_measures() {
+    let t = TARGET;
+    let keep_out = rect(ZoneKind::KeepOut, t, (0.0, 0.0), 100.0, 100.0);
+    let lz = rect(ZoneKind::LandingZone, t, (0.0, 300.0), 400.0, 400.0);
+    assert!(Zone::new(ZoneKind::KeepOut, &keep_out.vertices()[..2]).is_none());
+    assert!(Zone::new(ZoneKind::KeepOut, &[LatLon::new(91.0, 0.0); 3]).is_none());
+    // A bow tie crosses itself; two landing zones; a zone 20 km away
+    let v = keep_out.vertices();
+    let bow_tie = Zone::new(ZoneKind::KeepOut, &[v[0], v[2], v[1], v[3]]).unwrap();
+    assert!(Geofence::from_zones(&[bow_tie]).is_none());
+    assert!(Geofence::from_zones(&[lz, lz]).is_none());
+    let far = rect(ZoneKind::KeepOut, t, (20_000.0, 0.0), 100.0, 100.0);
+    assert!(Geofence::from_zones(&[keep_out, far]).is_none());
+    assert_eq!(Geofence::from_zones(&[]), Some(Geofence::EMPTY));
+    assert_eq!(Geofence::EMPTY.checksum(), 0);
+
+    let fence = Geofence::from_zones(&[keep_out, lz]).unwrap();
+    assert!(fence.has_landing_zone());
+    assert_ne!(fence.checksum(), Geofence::from_zones(&[keep_out]).unwrap().checksum());
+    let at = |n: f32, e: f32| fence.local_m(LatLon::new(t.0, t.1).from_local((n, e)));
+    assert_eq!(fence.keep_out_at(at(10.0, -20.0)), Some(0));
+    assert_eq!(fence.keep_out_at(at(60.0, 0.0)), None);
+    assert!(!fence.in_landing_zone(at(0.0, 0.0)));
+    // Safe: inside the landing zone and FENCE_MARGIN_M clear of the keep-out
+    assert!(fence.is_safe(at(0.0, 300.0)));
+    assert!(!fence.is_safe(at(0.0, 110.0)), "too close to the landing zone edge");
+    assert!(!fence.is_safe(at(0.0, 600.0)), "outside the landing zone");
+    // Straight through the keep-out is blocked; past it with room, or
+    // heading away from right beside it, is clear
+    assert!(!fence.path_clear(at(0.0, -200.0), at(0.0, 200.0)));
+    assert!(fence.path_clear(at(100.0, -200.0), at(100.0, 200.0)));
+    assert!(fence.path_clear(at(0.0, -60.0), at(0.0, -160.0)));
+    let exit = fence.exit_point(0, at(40.0, 0.0));
+    assert!(pattern::distance(exit, at(50.0, 0.0)) < 0.5, "exit {exit:?}");
+}
+
+#[test]
+fn steers_around_a_keep_out() {
+    // A block across the Upwind leg, 200 m north of the target
+    let fence = Geofence::from_zones(&[rect(ZoneKind::KeepOut, TARGET, (200.0, 0.0), 120.0, 160.0)]).unwrap();
+    let open = SimConfig::default();
+    let fenced = SimConfig { geofence: fence, ..open };
+    let (open_flight, flight) = (sim::fly(&open), sim::fly(&fenced));
+    assert!(entered_keep_out(&open_flight, &fence), "the unfenced flight should cross the block");
+    assert!(!entered_keep_out(&flight, &fence), "flew into the keep-out");
+    assert!(flight.samples.iter().any(|s| s.out.fence_flags & FENCE_AVOIDING != 0));
+    assert!(flight.samples.iter().all(|s| s.out.fence_flags & FENCE_IN_KEEP_OUT == 0));
+    // The detour costs glide, so the landing point may move off the target;
+    // it must stay safe and be where the canopy comes down
+    let last = flight.samples.last().unwrap().out;
+    let landed = LatLon::new(flight.landing_lat, flight.landing_lon);
+    let off = landed.distance_m(last.landing_target);
+    println!(
+        "miss open {:.0} m, fenced {:.0} m; landed {off:.0} m from the landing target",
+        open_flight.miss_m(&open),
+        flight.miss_m(&fenced)
+    );
+    assert!(fence.is_safe(fence.local_m(last.landing_target)));
+    assert!(off < 150.0);
+}
+
+#[test]
+fn moves_a_target_inside_a_keep_out() {
+    let fence = Geofence::from_zones(&[rect(ZoneKind::KeepOut, TARGET, (0.0, 0.0), 200.0, 200.0)]).unwrap();
+    for mode in [GuidanceMode::Mvp, GuidanceMode::Pattern] {
+        let cfg = SimConfig { geofence: fence, mode, ..SimConfig::default() };
+        let flight = sim::fly(&cfg);
+        let last = flight.samples.last().unwrap().out;
+        assert!(last.fence_flags & FENCE_TARGET_MOVED != 0);
+        assert!(fence.is_safe(fence.local_m(last.landing_target)));
+        let landed = LatLon::new(flight.landing_lat, flight.landing_lon);
+        let off = landed.distance_m(last.landing_target);
+        println!("{mode:?}: landing target {:.0} m from the target, landed {off:.0} m from it", flight.miss_m(&cfg));
+        assert!(fence.keep_out_at(fence.local_m(landed)).is_none(), "{mode:?} landed in the keep-out");
+        assert!(off < 150.0, "{mode:?} landed {off:.0} m from the landing target");
+    }
+}
+
+#[test]
+fn picks_a_reachable_point_in_the_landing_zone() {
+    // The target is 3 km east, far beyond the glide from 2000 ft; the
+    // landing zone is a field running east from under the canopy to it
+    let start = SimConfig::default();
+    let origin = (start.start_lat, start.start_lon);
+    let target = sim::offset(origin.0, origin.1, 90.0, 3_000.0);
+    let lz = rect(ZoneKind::LandingZone, origin, (0.0, 1_500.0), 600.0, 3_400.0);
+    let fence = Geofence::from_zones(&[lz]).unwrap();
+    let cfg = SimConfig { downwind_target: target, upwind_target: target, geofence: fence, mode: GuidanceMode::Pattern, ..start };
+    let flight = sim::fly(&cfg);
+    let first = flight.samples.iter().find(|s| s.out.phase_id == Phase::Track as i8).unwrap().out;
+    assert!(first.fence_flags & FENCE_TARGET_MOVED != 0);
+    assert!(flight.samples.iter().all(|s| s.out.fence_flags & FENCE_NO_SAFE_TARGET == 0));
+    let landed = LatLon::new(flight.landing_lat, flight.landing_lon);
+    let last = flight.samples.last().unwrap().out;
+    println!(
+        "landing target {:.0} m east, landed {:.0} m from it, {:.0} m short of the target",
+        LatLon::new(origin.0, origin.1).distance_m(last.landing_target),
+        landed.distance_m(last.landing_target),
+        flight.miss_m(&cfg)
+    );
+    assert!(fence.in_landing_zone(fence.local_m(landed)), "landed outside the landing zone");
+    assert!(last.fence_flags & FENCE_OUTSIDE_LZ == 0);
+}
+
+#[test]
+fn gps_dropouts_hold_neutral_and_lag_slows_turns() {
+    let cfg = SimConfig { gps_dropout_rate: 0.01, ..SimConfig::default() };
+    let flight = sim::fly(&cfg);
+    let held: Vec<_> = flight.samples.iter().filter(|s| s.out.phase_id == Phase::Held as i8).collect();
+    assert!(!held.is_empty(), "no dropouts flown");
+    assert!(held.iter().all(|s| s.out.brakeline_diff_in == NEUTRAL_POS));
+
+    // Same commands, later response: the turn from the initial heading
+    // takes longer to get going with a lagging, late canopy
+    let turned_by = |canopy: Canopy| {
+        let flight = sim::fly(&SimConfig { canopy, ..SimConfig::default() });
+        let start = flight.samples[0].heading;
+        flight.samples.iter().position(|s| wrap180(s.heading - start).abs() > 20.0).unwrap()
+    };
+    let slow = Canopy { turn_tau_s: 2.0, latency_ms: 300, ..Canopy::NOMINAL };
+    assert!(turned_by(slow) > turned_by(Canopy::NOMINAL));
+}
+
+#[test]
+fn monte_carlo_is_reproducible_and_reports() {
+    let nominal = SimConfig::default();
+    let runs = monte_carlo::run(&nominal, &Dispersion::default(), 12, 7);
+    let again = monte_carlo::run(&nominal, &Dispersion::default(), 12, 7);
+    assert_eq!(runs.len(), 12);
+    for (a, b) in runs.iter().zip(&again) {
+        assert_eq!((a.seed, a.miss_m), (b.seed, b.miss_m));
+    }
+    // Run i is seed + i on its own
+    let one = monte_carlo::run(&nominal, &Dispersion::default(), 1, 10);
+    assert_eq!(one[0].miss_m, runs[3].miss_m);
+
+    let s = monte_carlo::summarise(&runs);
+    assert!(s.cep50_m <= s.cep90_m && s.cep90_m <= s.miss.max);
+    assert!(s.cep50_m < 400.0, "CEP50 {:.0} m", s.cep50_m);
+    for r in &runs {
+        let phases: f32 = r.phase_s.iter().sum();
+        assert!((phases - r.descent_s).abs() < 0.01);
+        assert!((r.landing_n_m.hypot(r.landing_e_m) - r.miss_m).abs() < 1.0);
+    }
+    assert!(s.phases.iter().any(|&(p, _)| p == Phase::Upwind as i8));
+
+    let mut geojson = Vec::new();
+    monte_carlo::write_geojson(&runs, nominal.downwind_target, &mut geojson).unwrap();
+    let geojson = String::from_utf8(geojson).unwrap();
+    assert_eq!(geojson.matches("\"LineString\"").count(), 12);
+    assert_eq!(geojson.matches("\"Point\"").count(), 13);
+    let mut csv = Vec::new();
+    monte_carlo::write_tracks_csv(&runs, &mut csv).unwrap();
+    let rows = String::from_utf8(csv).unwrap().lines().count();
+    assert_eq!(rows, 1 + runs.iter().map(|r| r.track.len()).sum::<usize>());
+}
+
+#[test]
+fn calibration_table_maps_both_ways() {
+    let linear = CalibrationTable::LINEAR;
+    assert_eq!(linear.pulse_us(NEUTRAL_POS), PULSE_NEUTRAL_US);
+    assert_eq!(linear.pulse_us(MOTOR_MAX), PULSE_MAX_US);
+    assert_eq!(linear.pulse_us(-20.0), PULSE_MIN_US);
+    assert!((linear.brakeline_in(1750.0) - 4.5).abs() < 1e-4);
+    assert_eq!(CalibrationTable::new(BRAKELINE_CALIBRATION), Some(linear));
+
+    // Kinked: the first 3 in of right pull need half the pulse travel
+    let kinked = CalibrationTable::new(&[(-9.0, 1000.0), (0.0, 1500.0), (3.0, 1750.0), (9.0, 2000.0)]).unwrap();
+    assert!((kinked.pulse_us(1.5) - 1625.0).abs() < 1e-3);
+    assert!((kinked.brakeline_in(kinked.pulse_us(6.0)) - 6.0).abs() < 1e-4);
+    assert_eq!(kinked.to_string(), "&[(-9.00, 1000.0), (0.00, 1500.0), (3.00, 1750.0), (9.00, 2000.0)]");
+
+    // Columns must both increase, pulses stay within 1–2 ms
+    assert!(CalibrationTable::new(&[(0.0, 1500.0)]).is_none());
+    assert!(CalibrationTable::new(&[(-9.0, 1000.0), (9.0, 900.0)]).is_none());
+    assert!(CalibrationTable::new(&[(-9.0, 1000.0), (-9.0, 2000.0)]).is_none());
+    assert!(CalibrationTable::new(&[(-9.0, 900.0), (9.0, 2000.0)]).is_none());
+
+    // Blims sends every command through its table
+    let mut blims = controller();
+    blims.set_calibration(&kinked);
+    assert_eq!(*blims.actuator().pulses.last().unwrap(), 1500.0);
+    blims.set_brakeline_diff(1.5);
+    assert!((*blims.actuator().pulses.last().unwrap() - 1625.0).abs() < 1e-3);
+}
+
+#[test]
+fn characterisation_fits_a_nonlinear_winch() {
+    // Winch that pulls less line per µs towards full right, with 0.4 in of
+    // backlash: reads high on the way up, low on the way down
+    let truth = |pulse: f32| {
+        let x = (pulse - PULSE_NEUTRAL_US) / 500.0;
+        9.0 * x - 2.0 * x * x.abs() + 0.5
+    };
+    let mut cal = Characterisation::new(9).unwrap();
+    assert_eq!(cal.steps(), 18);
+    assert_eq!(cal.fit().unwrap_err(), blims::calibration::FitError::Incomplete);
+    let mut pulses = Vec::new();
+    let mut last = 0.0;
+    while let Some(pulse) = cal.pulse_us() {
+        let backlash = if pulse >= last { 0.2 } else { -0.2 };
+        cal.record(truth(pulse) + backlash);
+        pulses.push(pulse);
+        last = pulse;
+    }
+    assert_eq!(pulses.first(), Some(&PULSE_MIN_US));
+    assert_eq!(pulses[8], PULSE_MAX_US);
+    assert_eq!(pulses.last(), Some(&PULSE_MIN_US));
+
+    let fit = cal.fit().unwrap();
+    assert!((fit.hysteresis_in - 0.4).abs() < 0.01, "hysteresis {:.2} in", fit.hysteresis_in);
+    assert!(fit.linear_error_in > 0.4);
+    // The table's pulse gives the wanted differential to within the
+    // curvature between points, where the linear map is off by over 1 in
+    for want in [-6.0, -2.0, 0.0, 2.5, 6.0] {
+        let got = truth(fit.table.pulse_us(want));
+        assert!((got - want).abs() < 0.15, "wanted {want} in, got {got:.2}");
+    }
+    assert!((truth(CalibrationTable::LINEAR.pulse_us(-6.0)) + 6.0).abs() > 1.0);
+
+    // A line slipping mid-sweep can't be fitted
+    let mut cal = Characterisation::new(4).unwrap();
+    for m in [-9.0, -3.0, -4.0, 9.0, 9.0, -4.0, -3.0, -9.0] {
+        cal.record(m);
+    }
+    assert_eq!(cal.fit().unwrap_err(), blims::calibration::FitError::NotMonotonic);
+    assert!(Characterisation::new(1).is_none());
+}
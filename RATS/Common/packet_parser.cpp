@@ -1,7 +1,9 @@
 #include "packet_parser.h"
+#include "config.h"
 #include <string.h>
 #include <stdio.h>
-#include <time.h>
+#include <stdarg.h>
+#include <math.h>
 
 // Template specialization for reading values from buffer
 template<typename T>
@@ -12,112 +14,356 @@ T PacketParser::readValue(const uint8_t* buffer, size_t& offset) {
     return value;
 }
 
+size_t PacketParser::frameSize(uint32_t sync_word) {
+    switch (sync_word) {
+        case SYNC_WORD:        return RADIO_PACKET_SIZE;
+        case CONFIG_SYNC_WORD: return CONFIG_PACKET_SIZE;
+        case BEACON_SYNC_WORD: return BEACON_PACKET_SIZE;
+        default:               return 0;
+    }
+}
+
 bool PacketParser::parseRadioPacket(const uint8_t* buffer, size_t length, RadioPacket& packet) {
-    // Full 107-byte Radio Packet per RATS specification
-    if (length < 107) return false;
+    // Sync word + 270-byte Packet, in Packet::to_bytes() order
+    if (length < RADIO_PACKET_SIZE) return false;
 
     size_t offset = 0;
 
     // Byte 0-3: Sync word
     packet.sync_word = readValue<uint32_t>(buffer, offset);
 
-    // Byte 4-5: Metadata
-    packet.metadata = readValue<uint16_t>(buffer, offset);
-
-    // Byte 6-9: Milliseconds since boot
-    packet.ms_since_boot = readValue<uint32_t>(buffer, offset);
-
-    // Byte 10-13: Events
-    packet.events = readValue<uint32_t>(buffer, offset);
-
-    // Byte 14-21: Altimeter data
+    // Byte 4-19: Flight mode and altimeter
+    packet.flight_mode = readValue<uint32_t>(buffer, offset);
+    packet.pressure = readValue<float>(buffer, offset);
+    packet.temp = readValue<float>(buffer, offset);
     packet.altitude = readValue<float>(buffer, offset);
-    packet.temperature = readValue<float>(buffer, offset);
 
-    // Byte 22-38: GPS data
-    packet.latitude = readValue<int32_t>(buffer, offset);
-    packet.longitude = readValue<int32_t>(buffer, offset);
-    packet.num_satellites = readValue<uint8_t>(buffer, offset);
-    packet.gps_unix_time = readValue<uint32_t>(buffer, offset);
-    packet.gps_horizontal_accuracy = readValue<uint32_t>(buffer, offset);
-
-    // Byte 39-74: IMU data
-    packet.imu_accel_x = readValue<float>(buffer, offset);
-    packet.imu_accel_y = readValue<float>(buffer, offset);
-    packet.imu_accel_z = readValue<float>(buffer, offset);
-    packet.imu_gyro_x = readValue<float>(buffer, offset);
-    packet.imu_gyro_y = readValue<float>(buffer, offset);
-    packet.imu_gyro_z = readValue<float>(buffer, offset);
-    packet.imu_orient_x = readValue<float>(buffer, offset);
-    packet.imu_orient_y = readValue<float>(buffer, offset);
-    packet.imu_orient_z = readValue<float>(buffer, offset);
-
-    // Byte 75-86: Accelerometer data
+    // Byte 20-35: GPS
+    packet.latitude = readValue<float>(buffer, offset);
+    packet.longitude = readValue<float>(buffer, offset);
+    packet.num_satellites = readValue<uint32_t>(buffer, offset);
+    packet.timestamp = readValue<float>(buffer, offset);
+
+    // Byte 36-71: Magnetometer and IMU
+    packet.mag_x = readValue<float>(buffer, offset);
+    packet.mag_y = readValue<float>(buffer, offset);
+    packet.mag_z = readValue<float>(buffer, offset);
     packet.accel_x = readValue<float>(buffer, offset);
     packet.accel_y = readValue<float>(buffer, offset);
     packet.accel_z = readValue<float>(buffer, offset);
+    packet.gyro_x = readValue<float>(buffer, offset);
+    packet.gyro_y = readValue<float>(buffer, offset);
+    packet.gyro_z = readValue<float>(buffer, offset);
+
+    // Byte 72-83: ADC
+    packet.pt3 = readValue<float>(buffer, offset);
+    packet.pt4 = readValue<float>(buffer, offset);
+    packet.rtd = readValue<float>(buffer, offset);
+
+    // Byte 84-94: Valves, deployment and payload events
+    packet.sv_open = readValue<uint8_t>(buffer, offset);
+    packet.mav_open = readValue<uint8_t>(buffer, offset);
+    packet.ssa_drogue_deployed = readValue<uint8_t>(buffer, offset);
+    packet.ssa_main_deployed = readValue<uint8_t>(buffer, offset);
+    packet.cmd_n1 = readValue<uint8_t>(buffer, offset);
+    packet.cmd_n2 = readValue<uint8_t>(buffer, offset);
+    packet.cmd_n3 = readValue<uint8_t>(buffer, offset);
+    packet.cmd_n4 = readValue<uint8_t>(buffer, offset);
+    packet.cmd_a1 = readValue<uint8_t>(buffer, offset);
+    packet.cmd_a2 = readValue<uint8_t>(buffer, offset);
+    packet.cmd_a3 = readValue<uint8_t>(buffer, offset);
+
+    // Byte 95-102: Airbrakes
+    packet.airbrake_deployment = readValue<float>(buffer, offset);
+    packet.predicted_apogee = readValue<float>(buffer, offset);
+
+    // Byte 103-155: u-blox navigation
+    packet.h_acc = readValue<uint32_t>(buffer, offset);
+    packet.v_acc = readValue<uint32_t>(buffer, offset);
+    packet.vel_n = readValue<double>(buffer, offset);
+    packet.vel_e = readValue<double>(buffer, offset);
+    packet.vel_d = readValue<double>(buffer, offset);
+    packet.g_speed = readValue<double>(buffer, offset);
+    packet.s_acc = readValue<uint32_t>(buffer, offset);
+    packet.head_acc = readValue<uint32_t>(buffer, offset);
+    packet.fix_type = readValue<uint8_t>(buffer, offset);
+    packet.head_mot = readValue<int32_t>(buffer, offset);
+
+    // Byte 156-192: BLiMS
+    packet.blims_brakeline_diff = readValue<float>(buffer, offset);
+    packet.blims_phase_id = readValue<int8_t>(buffer, offset);
+    packet.blims_pid_p = readValue<float>(buffer, offset);
+    packet.blims_pid_i = readValue<float>(buffer, offset);
+    packet.blims_bearing = readValue<float>(buffer, offset);
+    packet.blims_upwind_lat = readValue<float>(buffer, offset);
+    packet.blims_upwind_lon = readValue<float>(buffer, offset);
+    packet.blims_downwind_lat = readValue<float>(buffer, offset);
+    packet.blims_downwind_lon = readValue<float>(buffer, offset);
+    packet.blims_wind_from_deg = readValue<float>(buffer, offset);
+
+    // Byte 193-204: Timing
+    packet.ms_since_boot_cfc = readValue<uint32_t>(buffer, offset);
+    packet.utc_ms = readValue<uint64_t>(buffer, offset);
+
+    // Byte 205-239: ODrives
+    packet.airbrake_measured_deployment = readValue<float>(buffer, offset);
+    packet.airbrake_odrive_state = readValue<uint8_t>(buffer, offset);
+    packet.airbrake_odrive_errors = readValue<uint32_t>(buffer, offset);
+    packet.airbrake_odrive_vbus = readValue<float>(buffer, offset);
+    packet.airbrake_odrive_ibus = readValue<float>(buffer, offset);
+    packet.blims_measured_brakeline = readValue<float>(buffer, offset);
+    packet.blims_odrive_state = readValue<uint8_t>(buffer, offset);
+    packet.blims_odrive_errors = readValue<uint32_t>(buffer, offset);
+    packet.blims_odrive_vbus = readValue<float>(buffer, offset);
+    packet.blims_odrive_ibus = readValue<float>(buffer, offset);
+    packet.odrive_link = readValue<uint8_t>(buffer, offset);
+
+    // Byte 240-261: Airbrake controller and BLiMS estimates
+    packet.airbrake_ctrl_ok = readValue<uint8_t>(buffer, offset);
+    packet.airbrake_est_body_cda = readValue<float>(buffer, offset);
+    packet.airbrake_est_brake_cda = readValue<float>(buffer, offset);
+    packet.blims_wind_est_speed = readValue<float>(buffer, offset);
+    packet.blims_wind_est_from_deg = readValue<float>(buffer, offset);
+    packet.blims_dist_to_target = readValue<float>(buffer, offset);
+    packet.blims_fence_flags = readValue<uint8_t>(buffer, offset);
 
-    // Byte 87-106: ADC and BLiMS data
-    packet.battery_voltage = readValue<float>(buffer, offset);
-    packet.pt3_pressure = readValue<float>(buffer, offset);
-    packet.pt4_pressure = readValue<float>(buffer, offset);
-    packet.rtd_temperature = readValue<float>(buffer, offset);
-    packet.blims_motor_state = readValue<float>(buffer, offset);
+    // Byte 262-265: Payload link
+    packet.payload_link = readValue<uint8_t>(buffer, offset);
+    packet.payload_last_ack = readValue<uint8_t>(buffer, offset);
+    packet.payload_state = readValue<uint8_t>(buffer, offset);
+    packet.payload_failed = readValue<uint8_t>(buffer, offset);
+
+    // Byte 266-273: Supply rails
+    packet.battery_v = readValue<float>(buffer, offset);
+    packet.pyro_v = readValue<float>(buffer, offset);
+
+    return true;
+}
+
+bool PacketParser::parseConfigPacket(const uint8_t* buffer, size_t length, ConfigPacket& packet) {
+    // Sync word + 34-byte ConfigPacket
+    if (length < CONFIG_PACKET_SIZE) return false;
+
+    size_t offset = 0;
+    packet.sync_word = readValue<uint32_t>(buffer, offset);
+    packet.ms_since_boot_cfc = readValue<uint32_t>(buffer, offset);
+    packet.airbrake_target_apogee = readValue<float>(buffer, offset);
+    packet.airbrake_max_rate = readValue<float>(buffer, offset);
+    packet.airbrake_min_vel = readValue<float>(buffer, offset);
+    packet.airbrake_body_cda = readValue<float>(buffer, offset);
+    packet.airbrake_brake_cda = readValue<float>(buffer, offset);
+    packet.blims_wind_layers = readValue<uint8_t>(buffer, offset);
+    packet.blims_wind_checksum = readValue<uint32_t>(buffer, offset);
+    packet.blims_fence_zones = readValue<uint8_t>(buffer, offset);
+    packet.blims_fence_checksum = readValue<uint32_t>(buffer, offset);
 
     return true;
 }
 
+bool PacketParser::parseBeaconPacket(const uint8_t* buffer, size_t length, BeaconPacket& packet) {
+    // Sync word + 28-byte BeaconPacket
+    if (length < BEACON_PACKET_SIZE) return false;
+
+    size_t offset = 0;
+    packet.sync_word = readValue<uint32_t>(buffer, offset);
+    packet.ms_since_boot_cfc = readValue<uint32_t>(buffer, offset);
+    packet.flight_mode = readValue<uint8_t>(buffer, offset);
+    packet.num_satellites = readValue<uint8_t>(buffer, offset);
+    packet.health = readValue<uint16_t>(buffer, offset);
+    packet.latitude = readValue<float>(buffer, offset);
+    packet.longitude = readValue<float>(buffer, offset);
+    packet.fix_age_s = readValue<uint32_t>(buffer, offset);
+    packet.battery_v = readValue<float>(buffer, offset);
+    packet.apogee_ft = readValue<uint32_t>(buffer, offset);
+
+    return true;
+}
+
+bool PacketParser::parseFrame(const uint8_t* buffer, size_t length, RadioFrame& frame) {
+    if (length < sizeof(uint32_t)) return false;
+
+    memcpy(&frame.sync_word, buffer, sizeof(uint32_t));
+    switch (frame.sync_word) {
+        case SYNC_WORD:        return parseRadioPacket(buffer, length, frame.packet);
+        case CONFIG_SYNC_WORD: return parseConfigPacket(buffer, length, frame.config);
+        case BEACON_SYNC_WORD: return parseBeaconPacket(buffer, length, frame.beacon);
+        default:               return false;
+    }
+}
+
+// Appends "key":value pairs to a JSON object, dropping whatever no longer fits
+class JsonWriter {
+public:
+    JsonWriter(char* buffer, size_t size) : buf(buffer), size(size), len(0), first(true) {
+        append("{");
+    }
+
+    void u(const char* key, unsigned long long value) { field(key, "%llu", value); }
+    void i(const char* key, long long value) { field(key, "%lld", value); }
+    void b(const char* key, bool value) { field(key, "%s", value ? "true" : "false"); }
+
+    // Non-finite values (e.g. a failed ADC read) go out as null
+    void f(const char* key, double value, int precision) {
+        if (isfinite(value)) {
+            field(key, "%.*f", precision, value);
+        } else {
+            field(key, "null");
+        }
+    }
+
+    void finish() { append("}"); }
+
+private:
+    char* buf;
+    size_t size;
+    size_t len;
+    bool first;
+
+    void append(const char* fmt, ...) {
+        if (len >= size) return;
+        va_list args;
+        va_start(args, fmt);
+        int n = vsnprintf(buf + len, size - len, fmt, args);
+        va_end(args);
+        if (n > 0) len += (size_t)n;
+    }
+
+    void field(const char* key, const char* fmt, ...) {
+        append(first ? "\"%s\":" : ",\"%s\":", key);
+        first = false;
+        if (len >= size) return;
+        va_list args;
+        va_start(args, fmt);
+        int n = vsnprintf(buf + len, size - len, fmt, args);
+        va_end(args);
+        if (n > 0) len += (size_t)n;
+    }
+};
+
 void PacketParser::radioPacketToJSON(const RadioPacket& packet, char* json_buffer, size_t buffer_size) {
-    // Full JSON output for complete Radio Packet structure
-    // Extract flight mode from metadata (bits 13-15)
-    uint8_t flight_mode = (packet.metadata >> 13) & 0x07;
-
-    // Convert GPS coordinates from micro-degrees to decimal degrees
-    float lat_deg = packet.latitude / 1000000.0f;
-    float lon_deg = packet.longitude / 1000000.0f;
-
-    snprintf(json_buffer, buffer_size,
-        "{"
-        "\"metadata\":%u,"
-        "\"flight_mode\":%u,"
-        "\"ms_since_boot\":%u,"
-        "\"events\":%u,"
-        "\"altitude\":%.2f,"
-        "\"temperature\":%.2f,"
-        "\"latitude\":%.6f,"
-        "\"longitude\":%.6f,"
-        "\"num_satellites\":%u,"
-        "\"gps_unix_time\":%u,"
-        "\"gps_h_accuracy\":%u,"
-        "\"imu_accel\":[%.3f,%.3f,%.3f],"
-        "\"imu_gyro\":[%.3f,%.3f,%.3f],"
-        "\"imu_orient\":[%.3f,%.3f,%.3f],"
-        "\"accel\":[%.3f,%.3f,%.3f],"
-        "\"battery_voltage\":%.2f,"
-        "\"pt3_pressure\":%.2f,"
-        "\"pt4_pressure\":%.2f,"
-        "\"rtd_temp\":%.2f,"
-        "\"blims_motor\":%.2f"
-        "}",
-        packet.metadata,
-        flight_mode,
-        packet.ms_since_boot,
-        packet.events,
-        packet.altitude,
-        packet.temperature,
-        lat_deg,
-        lon_deg,
-        packet.num_satellites,
-        packet.gps_unix_time,
-        packet.gps_horizontal_accuracy,
-        packet.imu_accel_x, packet.imu_accel_y, packet.imu_accel_z,
-        packet.imu_gyro_x, packet.imu_gyro_y, packet.imu_gyro_z,
-        packet.imu_orient_x, packet.imu_orient_y, packet.imu_orient_z,
-        packet.accel_x, packet.accel_y, packet.accel_z,
-        packet.battery_voltage,
-        packet.pt3_pressure,
-        packet.pt4_pressure,
-        packet.rtd_temperature,
-        packet.blims_motor_state
-    );
+    JsonWriter json(json_buffer, buffer_size);
+
+    json.u("sync_word", packet.sync_word);
+
+    json.u("flight_mode", packet.flight_mode);
+    json.f("pressure", packet.pressure, 2);
+    json.f("temp", packet.temp, 2);
+    json.f("altitude", packet.altitude, 2);
+    json.f("latitude", packet.latitude, 6);
+    json.f("longitude", packet.longitude, 6);
+    json.u("num_satellites", packet.num_satellites);
+    json.f("timestamp", packet.timestamp, 3);
+    json.f("mag_x", packet.mag_x, 3);
+    json.f("mag_y", packet.mag_y, 3);
+    json.f("mag_z", packet.mag_z, 3);
+    json.f("accel_x", packet.accel_x, 3);
+    json.f("accel_y", packet.accel_y, 3);
+    json.f("accel_z", packet.accel_z, 3);
+    json.f("gyro_x", packet.gyro_x, 3);
+    json.f("gyro_y", packet.gyro_y, 3);
+    json.f("gyro_z", packet.gyro_z, 3);
+    json.f("pt3", packet.pt3, 2);
+    json.f("pt4", packet.pt4, 2);
+    json.f("rtd", packet.rtd, 2);
+    json.b("sv_2_open", packet.sv_open);
+    json.b("mav_open", packet.mav_open);
+    json.u("ms_since_boot_cfc", packet.ms_since_boot_cfc);
+
+    json.u("ssa_drogue_deployed", packet.ssa_drogue_deployed);
+    json.u("ssa_main_deployed", packet.ssa_main_deployed);
+    json.u("cmd_n1", packet.cmd_n1);
+    json.u("cmd_n2", packet.cmd_n2);
+    json.u("cmd_n3", packet.cmd_n3);
+    json.u("cmd_n4", packet.cmd_n4);
+    json.u("cmd_a1", packet.cmd_a1);
+    json.u("cmd_a2", packet.cmd_a2);
+    json.u("cmd_a3", packet.cmd_a3);
+
+    json.f("airbrake_deployment", packet.airbrake_deployment, 3);
+    json.f("predicted_apogee", packet.predicted_apogee, 1);
+
+    json.u("h_acc", packet.h_acc);
+    json.u("v_acc", packet.v_acc);
+    json.f("vel_n", packet.vel_n, 3);
+    json.f("vel_e", packet.vel_e, 3);
+    json.f("vel_d", packet.vel_d, 3);
+    json.f("g_speed", packet.g_speed, 3);
+    json.u("s_acc", packet.s_acc);
+    json.u("head_acc", packet.head_acc);
+    json.u("fix_type", packet.fix_type);
+    json.i("head_mot", packet.head_mot);
+
+    json.f("blims_brakeline_diff", packet.blims_brakeline_diff, 3);
+    json.i("blims_phase_id", packet.blims_phase_id);
+    json.f("blims_pid_p", packet.blims_pid_p, 4);
+    json.f("blims_pid_i", packet.blims_pid_i, 4);
+    json.f("blims_bearing", packet.blims_bearing, 2);
+    json.f("blims_upwind_lat", packet.blims_upwind_lat, 6);
+    json.f("blims_upwind_lon", packet.blims_upwind_lon, 6);
+    json.f("blims_downwind_lat", packet.blims_downwind_lat, 6);
+    json.f("blims_downwind_lon", packet.blims_downwind_lon, 6);
+    json.f("blims_wind_from_deg", packet.blims_wind_from_deg, 1);
+
+    json.u("utc_ms", packet.utc_ms);
+    json.f("airbrake_measured_deployment", packet.airbrake_measured_deployment, 3);
+    json.u("airbrake_odrive_state", packet.airbrake_odrive_state);
+    json.u("airbrake_odrive_errors", packet.airbrake_odrive_errors);
+    json.f("airbrake_odrive_vbus", packet.airbrake_odrive_vbus, 2);
+    json.f("airbrake_odrive_ibus", packet.airbrake_odrive_ibus, 2);
+    json.f("blims_measured_brakeline", packet.blims_measured_brakeline, 3);
+    json.u("blims_odrive_state", packet.blims_odrive_state);
+    json.u("blims_odrive_errors", packet.blims_odrive_errors);
+    json.f("blims_odrive_vbus", packet.blims_odrive_vbus, 2);
+    json.f("blims_odrive_ibus", packet.blims_odrive_ibus, 2);
+    json.u("odrive_link", packet.odrive_link);
+    json.u("airbrake_ctrl_ok", packet.airbrake_ctrl_ok);
+    json.f("airbrake_est_body_cda", packet.airbrake_est_body_cda, 5);
+    json.f("airbrake_est_brake_cda", packet.airbrake_est_brake_cda, 5);
+    json.f("blims_wind_est_speed", packet.blims_wind_est_speed, 2);
+    json.f("blims_wind_est_from_deg", packet.blims_wind_est_from_deg, 1);
+    json.f("blims_dist_to_target", packet.blims_dist_to_target, 1);
+    json.u("blims_fence_flags", packet.blims_fence_flags);
+    json.u("payload_link", packet.payload_link);
+    json.u("payload_last_ack", packet.payload_last_ack);
+    json.u("payload_state", packet.payload_state);
+    json.u("payload_failed", packet.payload_failed);
+    json.f("battery_v", packet.battery_v, 2);
+    json.f("pyro_v", packet.pyro_v, 2);
+
+    json.finish();
+}
+
+void PacketParser::configPacketToJSON(const ConfigPacket& packet, char* json_buffer, size_t buffer_size) {
+    JsonWriter json(json_buffer, buffer_size);
+
+    json.u("sync_word", packet.sync_word);
+    json.u("ms_since_boot_cfc", packet.ms_since_boot_cfc);
+    json.f("airbrake_target_apogee", packet.airbrake_target_apogee, 1);
+    json.f("airbrake_max_rate", packet.airbrake_max_rate, 3);
+    json.f("airbrake_min_vel", packet.airbrake_min_vel, 2);
+    json.f("airbrake_body_cda", packet.airbrake_body_cda, 5);
+    json.f("airbrake_brake_cda", packet.airbrake_brake_cda, 5);
+    json.u("blims_wind_layers", packet.blims_wind_layers);
+    json.u("blims_wind_checksum", packet.blims_wind_checksum);
+    json.u("blims_fence_zones", packet.blims_fence_zones);
+    json.u("blims_fence_checksum", packet.blims_fence_checksum);
+
+    json.finish();
+}
+
+void PacketParser::beaconPacketToJSON(const BeaconPacket& packet, char* json_buffer, size_t buffer_size) {
+    JsonWriter json(json_buffer, buffer_size);
+
+    json.u("sync_word", packet.sync_word);
+    json.u("ms_since_boot_cfc", packet.ms_since_boot_cfc);
+    json.u("flight_mode", packet.flight_mode);
+    json.u("num_satellites", packet.num_satellites);
+    json.u("health", packet.health);
+    json.f("latitude", packet.latitude, 6);
+    json.f("longitude", packet.longitude, 6);
+    json.u("fix_age_s", packet.fix_age_s);
+    json.f("battery_v", packet.battery_v, 2);
+    json.u("apogee_ft", packet.apogee_ft);
+
+    json.finish();
 }
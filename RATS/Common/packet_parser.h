@@ -6,17 +6,27 @@
 
 class PacketParser {
 public:
-    // Parse raw bytes into RadioPacket structure
+    // Frame length (sync word included) for a sync word, 0 if unknown
+    static size_t frameSize(uint32_t sync_word);
+
+    // Parse raw bytes (starting at the sync word) into the matching structure
     static bool parseRadioPacket(const uint8_t* buffer, size_t length, RadioPacket& packet);
+    static bool parseConfigPacket(const uint8_t* buffer, size_t length, ConfigPacket& packet);
+    static bool parseBeaconPacket(const uint8_t* buffer, size_t length, BeaconPacket& packet);
+
+    // Parse whichever frame type the sync word names
+    static bool parseFrame(const uint8_t* buffer, size_t length, RadioFrame& frame);
 
-    // Convert packet to JSON string (NEW IMPLEMENTATION)
-    // This now generates the flat JSON for TimescaleDB
+    // Convert packet to JSON string
+    // Keys match the TimescaleDB columns (ground-station/*.sql)
     static void radioPacketToJSON(const RadioPacket& packet, char* json_buffer, size_t buffer_size);
-    
+    static void configPacketToJSON(const ConfigPacket& packet, char* json_buffer, size_t buffer_size);
+    static void beaconPacketToJSON(const BeaconPacket& packet, char* json_buffer, size_t buffer_size);
+
 private:
     // Helper to read values from buffer
     template<typename T>
     static T readValue(const uint8_t* buffer, size_t& offset);
 };
 
-#endif // PACKET_PARSER_H
\ No newline at end of file
+#endif // PACKET_PARSER_H
@@ -12,6 +12,7 @@
 #include "sd_logger.h"
 #include "mqtt_client.h"
 #include "inter_pico_uart.h"
+#include "serial_protocol.h"
 
 // Test Mode: Set to 1 for dual-radio test (TX+RX), 0 for normal operation (RX only)
 // Normal operation: Rocket -> RFD900x -> GP1 (RX)
@@ -56,8 +57,8 @@ void core1_entry() {
     printf("[Core 1] MQTT/WiFi disabled (loopback test mode)\n");
 #endif
 
-    char json_buffer[2048];
-    RadioPacket packet;
+    char json_buffer[4096];
+    RadioFrame frame;
     RadioPacket batch_buffer[SD_LOG_BATCH_SIZE];
     uint32_t batch_count = 0;
     uint32_t last_stats_time = 0;
@@ -68,14 +69,23 @@ void core1_entry() {
         MqttClient::poll();
 #endif
 
-        // Wait for packets from Core 0
-        if (queue_try_remove(&packet_queue, &packet)) {
+        // Wait for frames from Core 0
+        if (queue_try_remove(&packet_queue, &frame)) {
             // Convert to JSON (can be slow, that's OK on Core 1)
-            PacketParser::radioPacketToJSON(packet, json_buffer, sizeof(json_buffer));
+            const char* topic = MQTT_TOPIC;
+            if (frame.sync_word == CONFIG_SYNC_WORD) {
+                PacketParser::configPacketToJSON(frame.config, json_buffer, sizeof(json_buffer));
+                topic = MQTT_CONFIG_TOPIC;
+            } else if (frame.sync_word == BEACON_SYNC_WORD) {
+                PacketParser::beaconPacketToJSON(frame.beacon, json_buffer, sizeof(json_buffer));
+                topic = MQTT_BEACON_TOPIC;
+            } else {
+                PacketParser::radioPacketToJSON(frame.packet, json_buffer, sizeof(json_buffer));
+            }
 
-            // Add to batch buffer for SD logging
-            if (sd_ready && batch_count < SD_LOG_BATCH_SIZE) {
-                batch_buffer[batch_count++] = packet;
+            // Add full packets to batch buffer for SD logging
+            if (sd_ready && frame.sync_word == SYNC_WORD && batch_count < SD_LOG_BATCH_SIZE) {
+                batch_buffer[batch_count++] = frame.packet;
 
                 // Write batch when full
                 if (batch_count >= SD_LOG_BATCH_SIZE) {
@@ -88,9 +98,9 @@ void core1_entry() {
                 }
             }
 
-            // Send individual packets over MQTT immediately
+            // Send individual frames over MQTT immediately
             if (mqtt_ready) {
-                MqttClient::publish(json_buffer);
+                MqttClient::publish(json_buffer, topic);
             }
 
         }
@@ -161,8 +171,8 @@ int main() {
     printf("  Pin 9 (TX) -> Pico GP1 (UART0 RX)\n\n");
 #endif
     
-    // Initialize inter-core queue (holds up to 64 packets)
-    queue_init(&packet_queue, sizeof(RadioPacket), 64);
+    // Initialize inter-core queue (holds up to 32 frames)
+    queue_init(&packet_queue, sizeof(RadioFrame), 32);
 
     // Initialize inter-Pico UART (RadioPico -> StepperPico)
     InterPicoUART::init();
@@ -175,9 +185,10 @@ int main() {
     printf("[Core 0] Ready for packets\n\n");
     
     // Core 0 main loop - FAST I/O ONLY
-    // Full 107-byte Radio Packet buffer
-    uint8_t radio_buffer[107];
-    RadioPacket parsed_packet;
+    // Large enough for the longest frame (the full packet)
+    uint8_t radio_buffer[RADIO_PACKET_SIZE];
+    RadioFrame parsed_frame;
+    float last_altitude = 0.0f;  // Beacons carry no altitude; track at the last one heard
     uint32_t packet_count = 0;
     uint32_t last_stats_time = 0;
     
@@ -188,12 +199,12 @@ int main() {
         if (now - last_transmit_time >= 100) {
             last_transmit_time = now;
 
-            // Generate full 107-byte Radio Packet
+            // Generate full Radio Packet
             RadioPacket sim_packet;
             simulator.generateRadioPacket(sim_packet);
 
-            // Serialize to bytes (107 bytes full structure)
-            uint8_t tx_buffer[107];
+            // Serialize to bytes (sync word + 270-byte Packet)
+            uint8_t tx_buffer[RADIO_PACKET_SIZE];
             PacketSimulator::serializeRadioPacket(sim_packet, tx_buffer);
 
             // Transmit over UART0 to RFD900x #1 (or loopback to GP1 via GP0)
@@ -202,40 +213,39 @@ int main() {
             // Debug: confirm transmission
             static uint32_t tx_count = 0;
             if (++tx_count % 10 == 0) {
-                uint8_t flight_mode = (sim_packet.metadata >> 13) & 0x07;
                 printf("[TX] Sent %u packets (Sync: 0x%08X, Mode: %u, Alt: %.1fm)\n",
-                       tx_count, sim_packet.sync_word, flight_mode, sim_packet.altitude);
+                       tx_count, sim_packet.sync_word, sim_packet.flight_mode, sim_packet.altitude);
             }
         }
 #endif
         
-        // Check for packets (non-blocking, interrupt-driven)
+        // Check for frames (non-blocking, interrupt-driven)
         if (RFD900xUART::packetAvailable()) {
-            // Read packet
-            if (RFD900xUART::readPacket(radio_buffer, sizeof(radio_buffer))) {
-                // Validate sync word
-                uint32_t potential_sync;
-                memcpy(&potential_sync, radio_buffer, sizeof(uint32_t));
-
-                if (potential_sync == SYNC_WORD) {
-                    // Parse (fast operation)
-                    if (PacketParser::parseRadioPacket(radio_buffer, sizeof(radio_buffer), parsed_packet)) {
-                        packet_count++;
-
-                        // Send tracking data to Stepper Pico via UART1
-                        InterPicoUART::sendTrackingData(
-                            parsed_packet.latitude,
-                            parsed_packet.longitude,
-                            parsed_packet.altitude
-                        );
-
-                        // Send to Core 1 for processing (non-blocking)
-                        if (!queue_try_add(&packet_queue, &parsed_packet)) {
-                            printf("[Core 0] Warning: Queue full, packet dropped\n");
-                        }
-                    }
-                } else {
-                    printf("[Core 0] Invalid sync word: 0x%08X (expected 0x%08X)\n", potential_sync, SYNC_WORD);
+            // Read frame (the UART only hands out frames behind a known sync word)
+            size_t frame_size = RFD900xUART::readFrame(radio_buffer, sizeof(radio_buffer));
+            if (frame_size > 0 && PacketParser::parseFrame(radio_buffer, frame_size, parsed_frame)) {
+                packet_count++;
+
+                // Send tracking data to Stepper Pico via UART1
+                if (parsed_frame.sync_word == SYNC_WORD) {
+                    last_altitude = parsed_frame.packet.altitude;
+                    InterPicoUART::sendTrackingData(
+                        degrees_to_udeg(parsed_frame.packet.latitude),
+                        degrees_to_udeg(parsed_frame.packet.longitude),
+                        last_altitude
+                    );
+                } else if (parsed_frame.sync_word == BEACON_SYNC_WORD &&
+                           parsed_frame.beacon.fix_age_s != BEACON_NO_FIX) {
+                    InterPicoUART::sendTrackingData(
+                        degrees_to_udeg(parsed_frame.beacon.latitude),
+                        degrees_to_udeg(parsed_frame.beacon.longitude),
+                        last_altitude
+                    );
+                }
+
+                // Send to Core 1 for processing (non-blocking)
+                if (!queue_try_add(&packet_queue, &parsed_frame)) {
+                    printf("[Core 0] Warning: Queue full, frame dropped\n");
                 }
             }
         }
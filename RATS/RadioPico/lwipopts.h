@@ -55,7 +55,7 @@
 // --- 9. MQTT App Options ---
 // These are required by the 'lwip/apps/mqtt.h' header
 #define LWIP_CALLBACK_API               1
-#define MQTT_OUTPUT_RINGBUF_SIZE        2048 // 2KB buffer for outgoing MQTT messages
+#define MQTT_OUTPUT_RINGBUF_SIZE        8192 // 8KB: a full-packet JSON message is about 2.5KB
 
 // --- 10. Debugging Options ---
 #ifndef NDEBUG
@@ -55,12 +55,12 @@ bool MqttClient::init() {
     return true;
 }
 
-void MqttClient::publish(const char *json_payload) {
+void MqttClient::publish(const char *json_payload, const char *topic) {
     if (!state.mqtt_client || !state.connected) {
         return; // Not ready to publish
     }
 
-    err_t err = mqtt_publish(state.mqtt_client, MQTT_TOPIC, json_payload,
+    err_t err = mqtt_publish(state.mqtt_client, topic, json_payload,
                              strlen(json_payload), 0, 0, NULL, NULL);
 
     if (err != ERR_OK) {
@@ -20,9 +20,9 @@ public:
     // Returns true if successful, false if unsuccessful
     static bool init();
 
-    // Publishes a JSON payload
+    // Publishes a JSON payload, to MQTT_TOPIC unless another topic is given
     // Call this from Core 1 after a packet is received
-    static void publish(const char *json_payload);
+    static void publish(const char *json_payload, const char *topic = MQTT_TOPIC);
 
     // This must be called regularly in the Core 1 loop
     // to handle Wi-Fi polling and keep-alives.
@@ -1,4 +1,5 @@
 #include "rfd900x_uart.h"
+#include "packet_parser.h"
 #include "hardware/irq.h"
 #include <stdio.h>
 #include <string.h>
@@ -95,15 +96,9 @@ bool RFD900xUART::findSyncWord(uint32_t& position) {
         return false;
     }
 
-    // Search for sync word (from config.h: 0x3E5D5967 = "CRT!")
+    // Search for any known sync word (config.h: "CRT!", "CFG!", "BCN!")
     for (uint32_t i = 0; i <= available - 4; i++) {
-        uint32_t word = 0;
-        word |= peekBufferByte(i);
-        word |= (uint32_t)peekBufferByte(i + 1) << 8;
-        word |= (uint32_t)peekBufferByte(i + 2) << 16;
-        word |= (uint32_t)peekBufferByte(i + 3) << 24;
-
-        if (word == SYNC_WORD) {
+        if (PacketParser::frameSize(peekWord(i)) != 0) {
             position = i;
             return true;
         }
@@ -112,6 +107,15 @@ bool RFD900xUART::findSyncWord(uint32_t& position) {
     return false;
 }
 
+uint32_t RFD900xUART::peekWord(uint32_t offset) {
+    uint32_t word = 0;
+    word |= peekBufferByte(offset);
+    word |= (uint32_t)peekBufferByte(offset + 1) << 8;
+    word |= (uint32_t)peekBufferByte(offset + 2) << 16;
+    word |= (uint32_t)peekBufferByte(offset + 3) << 24;
+    return word;
+}
+
 bool RFD900xUART::packetAvailable() {
     uint32_t sync_pos;
 
@@ -133,26 +137,27 @@ bool RFD900xUART::packetAvailable() {
         readBufferByte();
     }
 
-    // Check if we have a full packet (40 bytes starting from sync word)
-    return bufferAvailable() >= RADIO_PACKET_SIZE;
+    // Check if we have the whole frame its sync word announces
+    return bufferAvailable() >= PacketParser::frameSize(peekWord(0));
 }
 
-bool RFD900xUART::readPacket(uint8_t* buffer, size_t buffer_size) {
-    if (buffer_size < RADIO_PACKET_SIZE) {
-        return false;
-    }
-    
+size_t RFD900xUART::readFrame(uint8_t* buffer, size_t buffer_size) {
     if (!packetAvailable()) {
-        return false;
+        return 0;
     }
-    
-    // Read the packet
-    for (int i = 0; i < RADIO_PACKET_SIZE; i++) {
+
+    size_t frame_size = PacketParser::frameSize(peekWord(0));
+    if (buffer_size < frame_size) {
+        return 0;
+    }
+
+    // Read the frame, sync word included
+    for (size_t i = 0; i < frame_size; i++) {
         buffer[i] = readBufferByte();
     }
-    
+
     total_packets_received++;
-    return true;
+    return frame_size;
 }
 
 uint32_t RFD900xUART::available() {
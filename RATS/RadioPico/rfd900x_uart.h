@@ -18,12 +18,12 @@ public:
     // Initialize UART for RFD900x
     static void init();
     
-    // Check if a complete packet is available
+    // Check if a complete frame (full packet, config or beacon) is available
     static bool packetAvailable();
     
-    // Read a complete packet into buffer
-    // Returns true if packet read successfully
-    static bool readPacket(uint8_t* buffer, size_t buffer_size);
+    // Read a complete frame, sync word included, into buffer
+    // Returns its length, or 0 if none was read
+    static size_t readFrame(uint8_t* buffer, size_t buffer_size);
     
     // Get number of bytes waiting in receive buffer
     static uint32_t available();
@@ -56,6 +56,9 @@ private:
     
     // Peek byte from circular buffer without removing
     static uint8_t peekBufferByte(uint32_t offset);
+
+    // Peek little-endian word from circular buffer without removing
+    static uint32_t peekWord(uint32_t offset);
 };
 
 #endif // RFD900X_UART_H
\ No newline at end of file
@@ -55,12 +55,23 @@ bool SDLogger::init() {
     printf("[SD] Created log file: %s\n", current_filename);
 
     // Write CSV header for ALL telemetry data fields
-    const char* header = "sync_word,metadata,ms_since_boot,events,altitude,temperature,"
-                        "latitude_deg,longitude_deg,num_satellites,gps_unix_time,gps_horizontal_accuracy,"
-                        "imu_accel_x,imu_accel_y,imu_accel_z,imu_gyro_x,imu_gyro_y,imu_gyro_z,"
-                        "imu_orient_x,imu_orient_y,imu_orient_z,"
-                        "accel_x,accel_y,accel_z,"
-                        "battery_voltage,pt3_pressure,pt4_pressure,rtd_temperature,blims_motor_state\n";
+    // (Packet::CSV_HEADER in fsw/src/packet.rs, behind the sync word)
+    const char* header = "sync_word,flight_mode,pressure,temp,altitude,latitude,longitude,num_satellites,timestamp,"
+                        "mag_x,mag_y,mag_z,accel_x,accel_y,accel_z,gyro_x,gyro_y,gyro_z,pt3,pt4,rtd,"
+                        "sv_open,mav_open,ssa_drogue_deployed,ssa_main_deployed,"
+                        "cmd_n1,cmd_n2,cmd_n3,cmd_n4,cmd_a1,cmd_a2,cmd_a3,"
+                        "airbrake_deployment,predicted_apogee,"
+                        "h_acc,v_acc,vel_n,vel_e,vel_d,g_speed,s_acc,head_acc,fix_type,head_mot,"
+                        "blims_brakeline_diff,blims_phase_id,blims_pid_p,blims_pid_i,blims_bearing,"
+                        "blims_upwind_lat,blims_upwind_lon,blims_downwind_lat,blims_downwind_lon,blims_wind_from_deg,"
+                        "ms_since_boot_cfc,utc_ms,"
+                        "airbrake_measured_deployment,airbrake_odrive_state,airbrake_odrive_errors,"
+                        "airbrake_odrive_vbus,airbrake_odrive_ibus,"
+                        "blims_measured_brakeline,blims_odrive_state,blims_odrive_errors,"
+                        "blims_odrive_vbus,blims_odrive_ibus,odrive_link,"
+                        "airbrake_ctrl_ok,airbrake_est_body_cda,airbrake_est_brake_cda,"
+                        "blims_wind_est_speed,blims_wind_est_from_deg,blims_dist_to_target,blims_fence_flags,"
+                        "payload_link,payload_last_ack,payload_state,payload_failed,battery_v,pyro_v\n";
 
     if (!writeString(header)) {
         printf("[SD] Failed to write header\n");
@@ -83,45 +94,45 @@ bool SDLogger::logPacket(const RadioPacket& packet) {
     }
 
     // Format packet data as CSV line with ALL fields
-    char line[512];  // Increased buffer for all fields
-    float lat_deg = packet.latitude / 1000000.0f;
-    float lon_deg = packet.longitude / 1000000.0f;
-
+    char line[2048];
     int len = snprintf(line, sizeof(line),
-        "0x%08X,%u,%u,0x%08X,%.2f,%.2f,"  // sync, metadata, ms, events, alt, temp
-        "%.6f,%.6f,%u,%u,%u,"              // lat, lon, sats, gps_time, gps_acc
-        "%.3f,%.3f,%.3f,%.3f,%.3f,%.3f,"   // imu accel, gyro
-        "%.2f,%.2f,%.2f,"                   // imu orient
-        "%.3f,%.3f,%.3f,"                   // accel
-        "%.3f,%.2f,%.2f,%.2f,%.3f\n",      // battery, pressures, rtd, blims
-        packet.sync_word,
-        packet.metadata,
-        packet.ms_since_boot,
-        packet.events,
-        packet.altitude,
-        packet.temperature,
-        lat_deg,
-        lon_deg,
-        packet.num_satellites,
-        packet.gps_unix_time,
-        packet.gps_horizontal_accuracy,
-        packet.imu_accel_x,
-        packet.imu_accel_y,
-        packet.imu_accel_z,
-        packet.imu_gyro_x,
-        packet.imu_gyro_y,
-        packet.imu_gyro_z,
-        packet.imu_orient_x,
-        packet.imu_orient_y,
-        packet.imu_orient_z,
-        packet.accel_x,
-        packet.accel_y,
-        packet.accel_z,
-        packet.battery_voltage,
-        packet.pt3_pressure,
-        packet.pt4_pressure,
-        packet.rtd_temperature,
-        packet.blims_motor_state
+        "0x%08X,%u,%.2f,%.2f,%.2f,%.6f,%.6f,%u,%.3f,"     // sync, mode, altimeter, GPS
+        "%.3f,%.3f,%.3f,%.3f,%.3f,%.3f,%.3f,%.3f,%.3f,"   // mag, accel, gyro
+        "%.2f,%.2f,%.2f,"                                   // pt3, pt4, rtd
+        "%u,%u,%u,%u,%u,%u,%u,%u,%u,%u,%u,"                 // valves, SSAs, payload events
+        "%.3f,%.1f,"                                        // airbrake deployment, predicted apogee
+        "%u,%u,%.3f,%.3f,%.3f,%.3f,%u,%u,%u,%d,"            // u-blox navigation
+        "%.3f,%d,%.4f,%.4f,%.2f,%.6f,%.6f,%.6f,%.6f,%.1f,"  // BLiMS
+        "%u,%llu,"                                          // ms since boot, UTC ms
+        "%.3f,%u,%u,%.2f,%.2f,%.3f,%u,%u,%.2f,%.2f,%u,"     // ODrives
+        "%u,%.5f,%.5f,%.2f,%.1f,%.1f,%u,"                   // controller and BLiMS estimates
+        "%u,%u,%u,%u,%.2f,%.2f\n",                          // payload link, supply rails
+        packet.sync_word, packet.flight_mode,
+        packet.pressure, packet.temp, packet.altitude,
+        packet.latitude, packet.longitude, packet.num_satellites, packet.timestamp,
+        packet.mag_x, packet.mag_y, packet.mag_z,
+        packet.accel_x, packet.accel_y, packet.accel_z,
+        packet.gyro_x, packet.gyro_y, packet.gyro_z,
+        packet.pt3, packet.pt4, packet.rtd,
+        packet.sv_open, packet.mav_open, packet.ssa_drogue_deployed, packet.ssa_main_deployed,
+        packet.cmd_n1, packet.cmd_n2, packet.cmd_n3, packet.cmd_n4,
+        packet.cmd_a1, packet.cmd_a2, packet.cmd_a3,
+        packet.airbrake_deployment, packet.predicted_apogee,
+        packet.h_acc, packet.v_acc, packet.vel_n, packet.vel_e, packet.vel_d, packet.g_speed,
+        packet.s_acc, packet.head_acc, packet.fix_type, packet.head_mot,
+        packet.blims_brakeline_diff, packet.blims_phase_id, packet.blims_pid_p, packet.blims_pid_i,
+        packet.blims_bearing, packet.blims_upwind_lat, packet.blims_upwind_lon,
+        packet.blims_downwind_lat, packet.blims_downwind_lon, packet.blims_wind_from_deg,
+        packet.ms_since_boot_cfc, (unsigned long long)packet.utc_ms,
+        packet.airbrake_measured_deployment, packet.airbrake_odrive_state, packet.airbrake_odrive_errors,
+        packet.airbrake_odrive_vbus, packet.airbrake_odrive_ibus,
+        packet.blims_measured_brakeline, packet.blims_odrive_state, packet.blims_odrive_errors,
+        packet.blims_odrive_vbus, packet.blims_odrive_ibus, packet.odrive_link,
+        packet.airbrake_ctrl_ok, packet.airbrake_est_body_cda, packet.airbrake_est_brake_cda,
+        packet.blims_wind_est_speed, packet.blims_wind_est_from_deg, packet.blims_dist_to_target,
+        packet.blims_fence_flags,
+        packet.payload_link, packet.payload_last_ack, packet.payload_state, packet.payload_failed,
+        packet.battery_v, packet.pyro_v
     );
 
     if (len < 0 || len >= (int)sizeof(line)) {
@@ -27,14 +27,22 @@
 #define STRINGIFY(x) #x
 #define TOSTRING(x) STRINGIFY(x)
 #define MQTT_TOPIC MQTT_TOPIC_BASE TOSTRING(RATS_UNIT_ID)
+// Config packets and recovery beacons go to their own topics (and tables)
+#define MQTT_CONFIG_TOPIC "rats/config/" TOSTRING(RATS_UNIT_ID)
+#define MQTT_BEACON_TOPIC "rats/beacon/" TOSTRING(RATS_UNIT_ID)
 
 // --- System Configuration ---
 
 // Packet sync word - "CRT!"
 #define SYNC_WORD 0x3E5D5967
+// Config packet sync word - "CFG!" (1 Hz alongside the full packets)
+#define CONFIG_SYNC_WORD 0x21474643
+// Recovery beacon sync word - "BCN!" (replaces the full packets in beacon mode)
+#define BEACON_SYNC_WORD 0x214E4342
+#define BEACON_NO_FIX 0xFFFFFFFF  // BeaconPacket fix_age_s before any 3D fix
 
 // Telemetry rates
-#define EXPECTED_PACKET_RATE_HZ 10
+#define EXPECTED_PACKET_RATE_HZ 20  // FSW MAIN_LOOP_DELAY_MS = 50
 #define PACKET_INTERVAL_MS (1000 / EXPECTED_PACKET_RATE_HZ)
 
 // Link loss detection
@@ -56,8 +64,10 @@
 #define INTER_PICO_BAUD_RATE 115200
 
 // Buffer sizes
-#define RFD_RX_BUFFER_SIZE 512
-#define RADIO_PACKET_SIZE 107  // Full Radio Packet structure per RATS specification
+#define RFD_RX_BUFFER_SIZE 2048
+#define RADIO_PACKET_SIZE 274  // Sync word + Packet::SIZE (fsw/src/packet.rs)
+#define CONFIG_PACKET_SIZE 38  // Sync word + ConfigPacket::SIZE
+#define BEACON_PACKET_SIZE 32  // Sync word + BeaconPacket::SIZE
 #define TRACKING_DATA_SIZE 12
 
 // SD card logging
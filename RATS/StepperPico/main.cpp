@@ -74,8 +74,8 @@ void setup() {
 }
 
 void handleRadioPacket(const RadioPacket &packet) {
-  rocketLLA.lat = packet.latitude / 1e6; // µdeg to deg
-  rocketLLA.lon = packet.longitude / 1e6;
+  rocketLLA.lat = packet.latitude; // degrees
+  rocketLLA.lon = packet.longitude;
   rocketLLA.alt = packet.altitude;
 
   newPacketAvailable = true;
@@ -50,53 +50,50 @@ void PacketSimulator::updateSimulation() {
   }
 }
 
+// Append a value to the buffer, little-endian like the FSW writes it
+template<typename T>
+static void put(uint8_t* buffer, size_t& offset, const T& value) {
+  memcpy(buffer + offset, &value, sizeof(T));
+  offset += sizeof(T);
+}
+
 void PacketSimulator::generateRadioPacket(RadioPacket &packet) {
   updateSimulation();
 
+  // Start from an all-zero packet: subsystems the simulation doesn't model
+  // (ODrives, payload, airbrake estimates) report nothing
+  memset(&packet, 0, sizeof(packet));
+
   // Sync word
   packet.sync_word = SYNC_WORD; // "CRT!"
 
-  // Metadata: Build 16-bit bitfield with flight mode in bits 13-15
-  uint16_t metadata = 0;
-  metadata |= (1 << 1);             // Altimeter valid
-  metadata |= (1 << 2);             // GPS valid
-  metadata |= (1 << 3);             // IMU valid
-  metadata |= (1 << 4);             // Accelerometer valid
-  metadata |= (1 << 6);             // ADC valid
-  metadata |= (1 << 8);             // SD card valid
-  metadata |= (current_mode << 13); // Flight mode in bits 13-15
-  packet.metadata = metadata;
-
-  // Timestamp
-  packet.ms_since_boot = sim_time_ms;
-
-  // Events (no events for simulation)
-  packet.events = 0;
+  // Flight mode and timestamp
+  packet.flight_mode = current_mode;
+  packet.ms_since_boot_cfc = sim_time_ms;
 
   // Altimeter data
   packet.altitude = sim_altitude;
-  packet.temperature =
-      20.0f - (sim_altitude / 150.0f); // Temperature lapse rate
+  packet.pressure = 101325.0f * powf(1.0f - sim_altitude / 44330.0f, 5.255f);
+  packet.temp = 20.0f - (sim_altitude / 150.0f); // Temperature lapse rate
 
   float t = sim_time_ms / 1000.0f;
+  bool landed = current_mode == MAIN_DEPLOYED && sim_altitude <= 100.0f;
 
-  if (current_mode != MAIN_DEPLOYED || sim_altitude > 100.0f) {
+  if (!landed) {
     // Rocket in flight: drift North but swing East and West smoothly
     // to force the Azimuth tracking motor to sweep back and forth visually
-    packet.latitude = (int32_t)((42.336789f + 0.001f * t) * 1000000.0f);
-    packet.longitude =
-        (int32_t)((-76.497123f + 0.002f * sinf(t * 0.1f)) * 1000000.0f);
+    packet.latitude = 42.336789f + 0.001f * t;
+    packet.longitude = -76.497123f + 0.002f * sinf(t * 0.1f);
   } else {
     // Rocket landed: keep the final frozen position based on the exact time it
     // landed!
-    static bool landed = false;
-    static int32_t final_lat, final_lon;
-
-    if (!landed) {
-      final_lat = (int32_t)((42.336789f + 0.001f * t) * 1000000.0f);
-      final_lon =
-          (int32_t)((-76.497123f + 0.002f * sinf(t * 0.1f)) * 1000000.0f);
-      landed = true;
+    static bool frozen = false;
+    static float final_lat, final_lon;
+
+    if (!frozen) {
+      final_lat = 42.336789f + 0.001f * t;
+      final_lon = -76.497123f + 0.002f * sinf(t * 0.1f);
+      frozen = true;
     }
 
     packet.latitude = final_lat;
@@ -104,124 +101,149 @@ void PacketSimulator::generateRadioPacket(RadioPacket &packet) {
   }
 
   packet.num_satellites = 12;
-  packet.gps_unix_time = 1700000000 + (sim_time_ms / 1000); // Mock Unix time
-  packet.gps_horizontal_accuracy = 2500;                    // 2.5 meters in mm
+  packet.timestamp = (float)((sim_time_ms / 1000) % 86400); // Mock time of day
+  packet.utc_ms = 1700000000000ULL + sim_time_ms;             // Mock UTC
+  packet.h_acc = 2500;                                        // 2.5 meters in mm
+  packet.v_acc = 4000;
+  packet.vel_d = -sim_velocity;
+  packet.fix_type = 3;
 
   // IMU data - simulate some motion
-  packet.imu_accel_x = 0.1f * sinf(t);
-  packet.imu_accel_y = 0.1f * cosf(t);
-  packet.imu_accel_z = (current_mode == MAIN_DEPLOYED && sim_altitude <= 100.0f)
-                           ? 9.81f
-                           : 9.81f + 1.2f * sinf(t * 2.0f);
-  packet.imu_gyro_x = (current_mode == MAIN_DEPLOYED && sim_altitude <= 100.0f)
-                          ? 0.0f
-                          : 5.0f * sinf(t * 0.5f);
-  packet.imu_gyro_y = (current_mode == MAIN_DEPLOYED && sim_altitude <= 100.0f)
-                          ? 0.0f
-                          : 5.0f * cosf(t * 0.5f);
-  packet.imu_gyro_z =
-      (current_mode == MAIN_DEPLOYED && sim_altitude <= 100.0f) ? 0.0f : 1.0f;
-  packet.imu_orient_x =
-      (current_mode == MAIN_DEPLOYED && sim_altitude <= 100.0f)
-          ? 0.0f
-          : 10.0f * sinf(t * 0.2f);
-  packet.imu_orient_y =
-      (current_mode == MAIN_DEPLOYED && sim_altitude <= 100.0f)
-          ? 0.0f
-          : 10.0f * cosf(t * 0.2f);
-  packet.imu_orient_z = 45.0f;
-
-  // Accelerometer data
-  packet.accel_x = 0.05f;
-  packet.accel_y = 0.03f;
-  packet.accel_z = 1.0f; // 1g
+  packet.accel_x = 0.1f * sinf(t);
+  packet.accel_y = 0.1f * cosf(t);
+  packet.accel_z = landed ? 9.81f : 9.81f + 1.2f * sinf(t * 2.0f);
+  packet.gyro_x = landed ? 0.0f : 5.0f * sinf(t * 0.5f);
+  packet.gyro_y = landed ? 0.0f : 5.0f * cosf(t * 0.5f);
+  packet.gyro_z = landed ? 0.0f : 1.0f;
+  packet.mag_x = 20.0f * cosf(t * 0.2f);
+  packet.mag_y = 20.0f * sinf(t * 0.2f);
+  packet.mag_z = -45.0f;
 
   // ADC and BLiMS data
-  packet.battery_voltage = 7.4f - (sim_time_ms / 1000000.0f); // Slow drain
-  packet.pt3_pressure = 800.0f + 50.0f * sinf(t * 0.1f);
-  packet.pt4_pressure = 750.0f + 30.0f * cosf(t * 0.1f);
-  packet.rtd_temperature = 25.0f + 2.0f * sinf(t * 0.05f);
-  packet.blims_motor_state = (current_mode == MAIN_DEPLOYED) ? 2.5f : 0.0f;
+  packet.pt3 = 800.0f + 50.0f * sinf(t * 0.1f);
+  packet.pt4 = 750.0f + 30.0f * cosf(t * 0.1f);
+  packet.rtd = 25.0f + 2.0f * sinf(t * 0.05f);
+  packet.blims_brakeline_diff = (current_mode == MAIN_DEPLOYED) ? 2.5f : 0.0f;
+  packet.ssa_drogue_deployed = (current_mode >= DROGUE_DEPLOYED);
+  packet.ssa_main_deployed = (current_mode == MAIN_DEPLOYED);
+
+  // Supply rails
+  packet.battery_v = 7.4f - (sim_time_ms / 1000000.0f); // Slow drain
+  packet.pyro_v = 7.4f;
 }
 
 void PacketSimulator::serializeRadioPacket(const RadioPacket &packet,
                                            uint8_t *buffer) {
-  // Serialize full 107-byte Radio Packet
+  // Serialize sync word + 270-byte Packet, in Packet::to_bytes() order
   size_t offset = 0;
 
   // Byte 0-3: Sync word
-  memcpy(buffer + offset, &packet.sync_word, sizeof(uint32_t));
-  offset += sizeof(uint32_t);
-
-  // Byte 4-5: Metadata
-  memcpy(buffer + offset, &packet.metadata, sizeof(uint16_t));
-  offset += sizeof(uint16_t);
-
-  // Byte 6-9: Milliseconds since boot
-  memcpy(buffer + offset, &packet.ms_since_boot, sizeof(uint32_t));
-  offset += sizeof(uint32_t);
-
-  // Byte 10-13: Events
-  memcpy(buffer + offset, &packet.events, sizeof(uint32_t));
-  offset += sizeof(uint32_t);
-
-  // Byte 14-21: Altimeter data
-  memcpy(buffer + offset, &packet.altitude, sizeof(float));
-  offset += sizeof(float);
-  memcpy(buffer + offset, &packet.temperature, sizeof(float));
-  offset += sizeof(float);
-
-  // Byte 22-38: GPS data
-  memcpy(buffer + offset, &packet.latitude, sizeof(int32_t));
-  offset += sizeof(int32_t);
-  memcpy(buffer + offset, &packet.longitude, sizeof(int32_t));
-  offset += sizeof(int32_t);
-  memcpy(buffer + offset, &packet.num_satellites, sizeof(uint8_t));
-  offset += sizeof(uint8_t);
-  memcpy(buffer + offset, &packet.gps_unix_time, sizeof(uint32_t));
-  offset += sizeof(uint32_t);
-  memcpy(buffer + offset, &packet.gps_horizontal_accuracy, sizeof(uint32_t));
-  offset += sizeof(uint32_t);
-
-  // Byte 39-74: IMU data
-  memcpy(buffer + offset, &packet.imu_accel_x, sizeof(float));
-  offset += sizeof(float);
-  memcpy(buffer + offset, &packet.imu_accel_y, sizeof(float));
-  offset += sizeof(float);
-  memcpy(buffer + offset, &packet.imu_accel_z, sizeof(float));
-  offset += sizeof(float);
-  memcpy(buffer + offset, &packet.imu_gyro_x, sizeof(float));
-  offset += sizeof(float);
-  memcpy(buffer + offset, &packet.imu_gyro_y, sizeof(float));
-  offset += sizeof(float);
-  memcpy(buffer + offset, &packet.imu_gyro_z, sizeof(float));
-  offset += sizeof(float);
-  memcpy(buffer + offset, &packet.imu_orient_x, sizeof(float));
-  offset += sizeof(float);
-  memcpy(buffer + offset, &packet.imu_orient_y, sizeof(float));
-  offset += sizeof(float);
-  memcpy(buffer + offset, &packet.imu_orient_z, sizeof(float));
-  offset += sizeof(float);
-
-  // Byte 75-86: Accelerometer data
-  memcpy(buffer + offset, &packet.accel_x, sizeof(float));
-  offset += sizeof(float);
-  memcpy(buffer + offset, &packet.accel_y, sizeof(float));
-  offset += sizeof(float);
-  memcpy(buffer + offset, &packet.accel_z, sizeof(float));
-  offset += sizeof(float);
-
-  // Byte 87-106: ADC and BLiMS data
-  memcpy(buffer + offset, &packet.battery_voltage, sizeof(float));
-  offset += sizeof(float);
-  memcpy(buffer + offset, &packet.pt3_pressure, sizeof(float));
-  offset += sizeof(float);
-  memcpy(buffer + offset, &packet.pt4_pressure, sizeof(float));
-  offset += sizeof(float);
-  memcpy(buffer + offset, &packet.rtd_temperature, sizeof(float));
-  offset += sizeof(float);
-  memcpy(buffer + offset, &packet.blims_motor_state, sizeof(float));
-  offset += sizeof(float);
-
-  // Total: 107 bytes
+  put(buffer, offset, packet.sync_word);
+
+  // Byte 4-19: Flight mode and altimeter
+  put(buffer, offset, packet.flight_mode);
+  put(buffer, offset, packet.pressure);
+  put(buffer, offset, packet.temp);
+  put(buffer, offset, packet.altitude);
+
+  // Byte 20-35: GPS
+  put(buffer, offset, packet.latitude);
+  put(buffer, offset, packet.longitude);
+  put(buffer, offset, packet.num_satellites);
+  put(buffer, offset, packet.timestamp);
+
+  // Byte 36-71: Magnetometer and IMU
+  put(buffer, offset, packet.mag_x);
+  put(buffer, offset, packet.mag_y);
+  put(buffer, offset, packet.mag_z);
+  put(buffer, offset, packet.accel_x);
+  put(buffer, offset, packet.accel_y);
+  put(buffer, offset, packet.accel_z);
+  put(buffer, offset, packet.gyro_x);
+  put(buffer, offset, packet.gyro_y);
+  put(buffer, offset, packet.gyro_z);
+
+  // Byte 72-83: ADC
+  put(buffer, offset, packet.pt3);
+  put(buffer, offset, packet.pt4);
+  put(buffer, offset, packet.rtd);
+
+  // Byte 84-94: Valves, deployment and payload events
+  put(buffer, offset, packet.sv_open);
+  put(buffer, offset, packet.mav_open);
+  put(buffer, offset, packet.ssa_drogue_deployed);
+  put(buffer, offset, packet.ssa_main_deployed);
+  put(buffer, offset, packet.cmd_n1);
+  put(buffer, offset, packet.cmd_n2);
+  put(buffer, offset, packet.cmd_n3);
+  put(buffer, offset, packet.cmd_n4);
+  put(buffer, offset, packet.cmd_a1);
+  put(buffer, offset, packet.cmd_a2);
+  put(buffer, offset, packet.cmd_a3);
+
+  // Byte 95-102: Airbrakes
+  put(buffer, offset, packet.airbrake_deployment);
+  put(buffer, offset, packet.predicted_apogee);
+
+  // Byte 103-155: u-blox navigation
+  put(buffer, offset, packet.h_acc);
+  put(buffer, offset, packet.v_acc);
+  put(buffer, offset, packet.vel_n);
+  put(buffer, offset, packet.vel_e);
+  put(buffer, offset, packet.vel_d);
+  put(buffer, offset, packet.g_speed);
+  put(buffer, offset, packet.s_acc);
+  put(buffer, offset, packet.head_acc);
+  put(buffer, offset, packet.fix_type);
+  put(buffer, offset, packet.head_mot);
+
+  // Byte 156-192: BLiMS
+  put(buffer, offset, packet.blims_brakeline_diff);
+  put(buffer, offset, packet.blims_phase_id);
+  put(buffer, offset, packet.blims_pid_p);
+  put(buffer, offset, packet.blims_pid_i);
+  put(buffer, offset, packet.blims_bearing);
+  put(buffer, offset, packet.blims_upwind_lat);
+  put(buffer, offset, packet.blims_upwind_lon);
+  put(buffer, offset, packet.blims_downwind_lat);
+  put(buffer, offset, packet.blims_downwind_lon);
+  put(buffer, offset, packet.blims_wind_from_deg);
+
+  // Byte 193-204: Timing
+  put(buffer, offset, packet.ms_since_boot_cfc);
+  put(buffer, offset, packet.utc_ms);
+
+  // Byte 205-239: ODrives
+  put(buffer, offset, packet.airbrake_measured_deployment);
+  put(buffer, offset, packet.airbrake_odrive_state);
+  put(buffer, offset, packet.airbrake_odrive_errors);
+  put(buffer, offset, packet.airbrake_odrive_vbus);
+  put(buffer, offset, packet.airbrake_odrive_ibus);
+  put(buffer, offset, packet.blims_measured_brakeline);
+  put(buffer, offset, packet.blims_odrive_state);
+  put(buffer, offset, packet.blims_odrive_errors);
+  put(buffer, offset, packet.blims_odrive_vbus);
+  put(buffer, offset, packet.blims_odrive_ibus);
+  put(buffer, offset, packet.odrive_link);
+
+  // Byte 240-261: Airbrake controller and BLiMS estimates
+  put(buffer, offset, packet.airbrake_ctrl_ok);
+  put(buffer, offset, packet.airbrake_est_body_cda);
+  put(buffer, offset, packet.airbrake_est_brake_cda);
+  put(buffer, offset, packet.blims_wind_est_speed);
+  put(buffer, offset, packet.blims_wind_est_from_deg);
+  put(buffer, offset, packet.blims_dist_to_target);
+  put(buffer, offset, packet.blims_fence_flags);
+
+  // Byte 262-265: Payload link
+  put(buffer, offset, packet.payload_link);
+  put(buffer, offset, packet.payload_last_ack);
+  put(buffer, offset, packet.payload_state);
+  put(buffer, offset, packet.payload_failed);
+
+  // Byte 266-273: Supply rails
+  put(buffer, offset, packet.battery_v);
+  put(buffer, offset, packet.pyro_v);
+
+  // Total: RADIO_PACKET_SIZE (274) bytes
 }
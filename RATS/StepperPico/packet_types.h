@@ -3,116 +3,155 @@
 
 #include <stdint.h>
 
-// Flight modes
+// Flight modes (mirrors FlightMode in fsw/src/state.rs)
 enum FlightMode {
     STARTUP = 0,
     STANDBY = 1,
     ASCENT = 2,
-    DROGUE_DEPLOYED = 3,
-    MAIN_DEPLOYED = 4,
-    FAULT = 5
+    COAST = 3,
+    DROGUE_DEPLOYED = 4,
+    MAIN_DEPLOYED = 5,
+    FAULT = 6
 };
 
-// Metadata bitfield structure
-struct Metadata {
-    bool altitude_armed;
-    bool altimeter_valid;
-    bool gps_valid;
-    bool imu_valid;
-    bool accelerometer_valid;
-    bool umbilical_locked;
-    bool adc_valid;
-    bool fram_valid;
-    bool sd_valid;
-    bool gps_fresh;
-    bool safed;
-    bool mav_state;
-    bool sv_state;
-    FlightMode flight_mode;
+// Full Radio Packet Structure (4-byte sync + 270 bytes)
+// Mirrors Packet::to_bytes() in fsw/src/packet.rs; all fields little-endian
+struct RadioPacket {
+    // Byte 0-3: Sync word
+    uint32_t sync_word;              // "CRT!" identifier
+
+    // Byte 4-19: Flight mode and altimeter
+    uint32_t flight_mode;            // FlightMode
+    float pressure;                  // Pa
+    float temp;                      // Celsius
+    float altitude;                  // meters MSL
+
+    // Byte 20-35: GPS
+    float latitude;                  // degrees
+    float longitude;                 // degrees
+    uint32_t num_satellites;
+    float timestamp;                 // GPS time of day
+
+    // Byte 36-71: Magnetometer and IMU
+    float mag_x, mag_y, mag_z;       // uT
+    float accel_x, accel_y, accel_z; // m/s^2
+    float gyro_x, gyro_y, gyro_z;    // deg/s
+
+    // Byte 72-83: ADC
+    float pt3;                       // PSI
+    float pt4;                       // PSI
+    float rtd;                       // Celsius
+
+    // Byte 84-94: Valves, deployment and payload events
+    uint8_t sv_open;
+    uint8_t mav_open;
+    uint8_t ssa_drogue_deployed;
+    uint8_t ssa_main_deployed;
+    uint8_t cmd_n1, cmd_n2, cmd_n3, cmd_n4;
+    uint8_t cmd_a1, cmd_a2, cmd_a3;
+
+    // Byte 95-102: Airbrakes
+    float airbrake_deployment;       // 0.0 retracted .. 1.0 deployed
+    float predicted_apogee;          // meters
+
+    // Byte 103-155: u-blox navigation
+    uint32_t h_acc;                  // mm
+    uint32_t v_acc;                  // mm
+    double vel_n;                    // m/s
+    double vel_e;                    // m/s
+    double vel_d;                    // m/s, positive = descending
+    double g_speed;                  // m/s
+    uint32_t s_acc;                  // mm/s
+    uint32_t head_acc;               // deg*1e5
+    uint8_t fix_type;                // 0 none, 2 2D, 3 3D
+    int32_t head_mot;                // deg*1e5
+
+    // Byte 156-192: BLiMS
+    float blims_brakeline_diff;      // inches
+    int8_t blims_phase_id;
+    float blims_pid_p;
+    float blims_pid_i;
+    float blims_bearing;             // degrees
+    float blims_upwind_lat, blims_upwind_lon;
+    float blims_downwind_lat, blims_downwind_lon;
+    float blims_wind_from_deg;
+
+    // Byte 193-204: Timing
+    uint32_t ms_since_boot_cfc;
+    uint64_t utc_ms;                 // UTC Unix epoch ms, 0 until GPS time sync
+
+    // Byte 205-239: ODrives
+    float airbrake_measured_deployment;
+    uint8_t airbrake_odrive_state;
+    uint32_t airbrake_odrive_errors;
+    float airbrake_odrive_vbus;      // V
+    float airbrake_odrive_ibus;      // A
+    float blims_measured_brakeline;  // inches
+    uint8_t blims_odrive_state;
+    uint32_t blims_odrive_errors;
+    float blims_odrive_vbus;         // V
+    float blims_odrive_ibus;         // A
+    uint8_t odrive_link;             // bit0 airbrake, bit1 BLiMS on UART
+
+    // Byte 240-261: Airbrake controller and BLiMS estimates
+    uint8_t airbrake_ctrl_ok;        // 1 = Core 1 output fresh
+    float airbrake_est_body_cda;     // m^2
+    float airbrake_est_brake_cda;    // m^2
+    float blims_wind_est_speed;      // m/s
+    float blims_wind_est_from_deg;   // degrees FROM
+    float blims_dist_to_target;      // meters
+    uint8_t blims_fence_flags;
+
+    // Byte 262-265: Payload link
+    uint8_t payload_link;            // 0 never heard, 1 up, 2 lost
+    uint8_t payload_last_ack;
+    uint8_t payload_state;
+    uint8_t payload_failed;
+
+    // Byte 266-273: Supply rails
+    float battery_v;                 // V, NaN if the read failed
+    float pyro_v;                    // V, NaN if the read failed
 };
 
-// Events bitfield structure
-struct Events {
-    bool altitude_armed;
-    bool altimeter_init_failed;
-    bool altimeter_read_failed;
-    bool gps_init_failed;
-    bool gps_read_failed;
-    bool imu_init_failed;
-    bool imu_read_failed;
-    bool accel_init_failed;
-    bool accel_read_failed;
-    bool adc_init_failed;
-    bool adc_read_failed;
-    bool fram_init_failed;
-    bool fram_read_failed;
-    bool fram_write_failed;
-    bool sd_init_failed;
-    bool sd_write_failed;
-    bool mav_actuated;
-    bool sv_actuated;
-    bool main_deploy_wait_end;
-    bool main_log_shutoff;
-    bool cycle_overflow;
-    bool unknown_cmd;
-    bool launch_cmd;
-    bool mav_cmd;
-    bool sv_cmd;
-    bool safe_cmd;
-    bool reset_card_cmd;
-    bool reset_fram_cmd;
-    bool state_change_cmd;
-    bool umbilical_disconnected;
+// Config Packet (4-byte sync + 34 bytes), sent at 1 Hz
+// Mirrors ConfigPacket::to_bytes() in fsw/src/packet.rs
+struct ConfigPacket {
+    uint32_t sync_word;              // "CFG!" identifier
+    uint32_t ms_since_boot_cfc;
+    float airbrake_target_apogee;    // m AGL
+    float airbrake_max_rate;         // deployment fraction per second
+    float airbrake_min_vel;          // m/s
+    float airbrake_body_cda;         // m^2
+    float airbrake_brake_cda;        // m^2
+    uint8_t blims_wind_layers;       // 0 = no profile
+    uint32_t blims_wind_checksum;
+    uint8_t blims_fence_zones;       // 0 = no geofence
+    uint32_t blims_fence_checksum;
 };
 
-// Full Radio Packet Structure (107 bytes)
-// Per RATS specification document
-struct RadioPacket {
-    // Byte 0-3: Sync word
-    uint32_t sync_word;              // "CRT!" identifier
+// Recovery Beacon (4-byte sync + 28 bytes), every 5 s once beacon mode starts
+// Mirrors BeaconPacket::to_bytes() in fsw/src/packet.rs
+struct BeaconPacket {
+    uint32_t sync_word;              // "BCN!" identifier
+    uint32_t ms_since_boot_cfc;
+    uint8_t flight_mode;
+    uint8_t num_satellites;
+    uint16_t health;                 // state::health bits
+    float latitude;                  // degrees, last 3D fix
+    float longitude;                 // degrees
+    uint32_t fix_age_s;              // 0xFFFFFFFF = never
+    float battery_v;                 // V
+    uint32_t apogee_ft;
+};
 
-    // Byte 4-5: Metadata (16-bit bitfield)
-    uint16_t metadata;               // See Metadata structure for bit definitions
-
-    // Byte 6-9: Milliseconds since boot
-    uint32_t ms_since_boot;          // Timestamp in milliseconds
-
-    // Byte 10-13: Events (32-bit bitfield)
-    uint32_t events;                 // See Events structure for bit definitions
-
-    // Byte 14-21: Altimeter data
-    float altitude;                  // meters
-    float temperature;               // Celsius
-
-    // Byte 22-38: GPS data
-    int32_t latitude;                // micro-degrees (µdeg)
-    int32_t longitude;               // micro-degrees (µdeg)
-    uint8_t num_satellites;          // Satellites in view
-    uint32_t gps_unix_time;          // Unix timestamp in seconds
-    uint32_t gps_horizontal_accuracy; // millimeters
-
-    // Byte 39-74: IMU data
-    float imu_accel_x;               // m/s^2
-    float imu_accel_y;               // m/s^2
-    float imu_accel_z;               // m/s^2
-    float imu_gyro_x;                // deg/s
-    float imu_gyro_y;                // deg/s
-    float imu_gyro_z;                // deg/s
-    float imu_orient_x;              // degrees
-    float imu_orient_y;              // degrees
-    float imu_orient_z;              // degrees
-
-    // Byte 75-86: Accelerometer data
-    float accel_x;                   // g (gravity)
-    float accel_y;                   // g
-    float accel_z;                   // g
-
-    // Byte 87-106: ADC and BLiMS data
-    float battery_voltage;           // Volts
-    float pt3_pressure;              // PSI
-    float pt4_pressure;              // PSI
-    float rtd_temperature;           // Celsius
-    float blims_motor_state;         // inches
+// One frame off the radio, tagged by its sync word
+struct RadioFrame {
+    uint32_t sync_word;
+    union {
+        RadioPacket packet;
+        ConfigPacket config;
+        BeaconPacket beacon;
+    };
 };
 
-#endif // PACKET_TYPES_H
\ No newline at end of file
+#endif // PACKET_TYPES_H
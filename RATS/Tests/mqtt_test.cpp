@@ -22,7 +22,7 @@
 #include "mqtt_client.h"
 
 // JSON buffer
-char json_buffer[2048];
+char json_buffer[4096];
 
 // Packet simulator
 PacketSimulator simulator;
@@ -0,0 +1,141 @@
+//! The controller proper: one `execute` per FSW sensor frame.
+
+use crate::constants::*;
+use crate::drag_estimator::DragEstimator;
+use crate::estimator::Estimator;
+use crate::predictor::{self, Atmosphere, State};
+use crate::types::{Config, Output, Phase, SensorInput};
+
+pub struct AirbrakeSystem {
+    config: Config,
+    estimator: Estimator,
+    drag_estimator: DragEstimator,
+    atmosphere: Atmosphere,
+    reference_pressure: f32,
+    last_phase: Phase,
+    last_time: Option<f32>,
+    output: Output,
+}
+
+impl AirbrakeSystem {
+    pub fn new() -> Self {
+        Self::with_config(Config::default())
+    }
+
+    pub fn with_config(config: Config) -> Self {
+        Self {
+            config,
+            estimator: Estimator::new(),
+            drag_estimator: DragEstimator::new(config.drag),
+            atmosphere: Atmosphere::default(),
+            reference_pressure: 0.0,
+            last_phase: Phase::Pad,
+            last_time: None,
+            output: Output::default(),
+        }
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Replaces the mission and vehicle parameters. The FSW only sends a new
+    /// config on the pad; mid-flight it would take effect on the next cycle
+    /// and restart the drag estimate from the new model.
+    pub fn set_config(&mut self, config: Config) {
+        self.config = config;
+        self.drag_estimator = DragEstimator::new(config.drag);
+    }
+
+    /// Runs one cycle. Brakes stay retracted on the pad and during boost. In
+    /// coast, above the configured minimum activation velocity, the
+    /// deployment that puts the predicted apogee on the target is searched
+    /// for and the command slews towards it at the configured rate limit;
+    /// below it the brakes hold. Coast predictions use the drag areas
+    /// estimated so far in the coast, starting from the configured ones.
+    pub fn execute(&mut self, input: &SensorInput) -> Output {
+        let dt = match self.last_time {
+            Some(last) if input.time > last && input.time - last <= MAX_DT_S => input.time - last,
+            _ => NOMINAL_DT_S,
+        };
+        self.last_time = Some(input.time);
+
+        if input.reference_pressure != self.reference_pressure {
+            self.reference_pressure = input.reference_pressure;
+            self.atmosphere = Atmosphere::from_pad_pressure(input.reference_pressure);
+        }
+
+        // Entering flight, or coming up mid-flight after a reboot
+        if self.last_phase == Phase::Pad && input.phase != Phase::Pad {
+            self.estimator.start(input);
+            self.drag_estimator = DragEstimator::new(self.config.drag);
+        }
+        self.last_phase = input.phase;
+
+        match input.phase {
+            Phase::Pad => {
+                self.estimator.calibrate(input);
+                self.output = Output { deployment: 0.0, predicted_apogee: input.altitude, drag: self.config.drag };
+            }
+            Phase::Boost => {
+                self.estimator.update(input, dt, 0.0);
+                let state = self.estimator.state();
+                self.output = Output {
+                    deployment: 0.0,
+                    predicted_apogee: predictor::apogee(&self.atmosphere, &self.config.drag, &state, 0.0),
+                    drag: self.config.drag,
+                };
+            }
+            Phase::Coast => {
+                let held = self.output.deployment;
+                let prior = self.estimator.state();
+                let cfg = &self.config;
+                let (model_accel, _) =
+                    predictor::acceleration(&self.atmosphere, &self.drag_estimator.model(), &prior, held);
+                self.estimator.update(input, dt, model_accel);
+                let state = self.estimator.state();
+                if let Some(axial) = self.estimator.axial(input) {
+                    self.drag_estimator.update(&self.atmosphere, &state, axial, held, dt);
+                }
+                let drag = self.drag_estimator.model();
+
+                let deployment = if state.vz >= cfg.min_activation_vel_mps {
+                    let desired = predictor::deployment_for(&self.atmosphere, &drag, &state, cfg.target_apogee_m);
+                    let step = cfg.max_deploy_rate_per_s * dt;
+                    desired.clamp(held - step, held + step).clamp(0.0, 1.0)
+                } else {
+                    held
+                };
+                self.output = Output {
+                    deployment,
+                    predicted_apogee: predictor::apogee(&self.atmosphere, &drag, &state, deployment),
+                    drag,
+                };
+            }
+        }
+        self.output
+    }
+
+    /// Latest estimate, for logging and the host simulator.
+    pub fn state(&self) -> State {
+        self.estimator.state()
+    }
+
+    pub fn estimator(&self) -> &Estimator {
+        &self.estimator
+    }
+
+    pub fn drag_estimator(&self) -> &DragEstimator {
+        &self.drag_estimator
+    }
+
+    pub fn atmosphere(&self) -> &Atmosphere {
+        &self.atmosphere
+    }
+}
+
+impl Default for AirbrakeSystem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,152 @@
+//! Host airbrake simulator.
+//!
+//! Closed loop (default): flies the simulated vehicle with the controller in
+//! the loop and prints one TSV row per 20 Hz cycle to stdout, with the
+//! result and the final in-flight drag estimate on stderr. `--replay FILE` instead runs the estimator and
+//! predictor over a recorded profile (see `profiles/`) and reports the
+//! apogee prediction error.
+
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use controller_in_rust_v3::constants::DEFAULT_PAD_PRESSURE_PA;
+use controller_in_rust_v3::sim::{self, SimConfig};
+
+const USAGE: &str = "usage: airbrake-sim [--seed N] [--thrust-scale X] [--drag-scale X] [--brake-scale X]
+                    [--temp-offset K] [--pad-pressure PA] [--no-imu] [--no-control]
+                    [--target M] [--max-rate X] [--min-vel MPS] [--quiet]
+       airbrake-sim --replay FILE [--pad-pressure PA] [--quiet]";
+
+struct Args {
+    cfg: SimConfig,
+    replay: Option<PathBuf>,
+    quiet: bool,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut args = Args { cfg: SimConfig::default(), replay: None, quiet: false };
+    let mut it = std::env::args().skip(1);
+    while let Some(a) = it.next() {
+        let mut num = |name: &str| -> Result<f32, String> {
+            it.next().and_then(|v| v.parse().ok()).ok_or(format!("{name} needs a number"))
+        };
+        let cfg = &mut args.cfg;
+        match a.as_str() {
+            "--seed" => cfg.seed = num("--seed")? as u64,
+            "--thrust-scale" => cfg.vehicle.thrust *= num("--thrust-scale")?,
+            "--drag-scale" => cfg.vehicle.body_cda *= num("--drag-scale")?,
+            "--brake-scale" => cfg.vehicle.brake_cda *= num("--brake-scale")?,
+            "--temp-offset" => cfg.temp_offset_k = num("--temp-offset")?,
+            "--pad-pressure" => cfg.pad_pressure = num("--pad-pressure")?,
+            "--no-imu" => cfg.no_imu = true,
+            "--no-control" => cfg.control = false,
+            "--target" => cfg.controller.target_apogee_m = num("--target")?,
+            "--max-rate" => cfg.controller.max_deploy_rate_per_s = num("--max-rate")?,
+            "--min-vel" => cfg.controller.min_activation_vel_mps = num("--min-vel")?,
+            "--quiet" | "-q" => args.quiet = true,
+            "--replay" => args.replay = Some(it.next().ok_or("--replay needs a file")?.into()),
+            "-h" | "--help" => return Err(String::new()),
+            s => return Err(format!("unknown argument {s}")),
+        }
+    }
+    Ok(args)
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args() {
+        Ok(a) => a,
+        Err(msg) => {
+            if !msg.is_empty() {
+                eprintln!("{msg}");
+            }
+            eprintln!("{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+
+    match &args.replay {
+        Some(path) => replay(path, args.cfg.pad_pressure, args.quiet),
+        None => fly(&args.cfg, args.quiet),
+    }
+}
+
+fn fly(cfg: &SimConfig, quiet: bool) -> ExitCode {
+    let flight = sim::fly(cfg);
+    if !quiet {
+        println!("t\tphase\talt\tvz\talt_est\tvz_est\tcommand\tbrakes\tpredicted_apogee\tbody_cda\tbrake_cda");
+        for s in &flight.samples {
+            println!(
+                "{:.2}\t{:?}\t{:.1}\t{:.1}\t{:.1}\t{:.1}\t{:.3}\t{:.3}\t{:.1}\t{:.5}\t{:.5}",
+                s.t,
+                s.phase,
+                s.alt,
+                s.vz,
+                s.estimate.alt,
+                s.estimate.vz,
+                s.command,
+                s.brakes,
+                s.predicted_apogee,
+                s.drag.body_cda_m2,
+                s.drag.brake_cda_m2
+            );
+        }
+    }
+    let max_brakes = flight.samples.iter().map(|s| s.brakes).fold(0.0, f32::max);
+    eprintln!(
+        "apogee {:.1} m at {:.2} s (target {:.1} m, error {:+.1} m), max deployment {:.2}",
+        flight.apogee,
+        flight.apogee_t,
+        cfg.controller.target_apogee_m,
+        flight.apogee - cfg.controller.target_apogee_m,
+        max_brakes
+    );
+    if let Some(last) = flight.samples.last() {
+        let v = &cfg.vehicle;
+        eprintln!(
+            "drag estimate: body Cd·A {:.5} m² (truth {:.5}), brake Cd·A {:.5} m² (truth {:.5})",
+            last.drag.body_cda_m2, v.body_cda, last.drag.brake_cda_m2, v.brake_cda
+        );
+    }
+    ExitCode::SUCCESS
+}
+
+fn replay(path: &Path, pad_pressure: f32, quiet: bool) -> ExitCode {
+    let profile = match sim::load_profile(path) {
+        Ok(p) if !p.is_empty() => p,
+        Ok(_) => {
+            eprintln!("{}: no samples", path.display());
+            return ExitCode::from(2);
+        }
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::from(2);
+        }
+    };
+    let pad_pressure = if pad_pressure > 0.0 { pad_pressure } else { DEFAULT_PAD_PRESSURE_PA };
+    let samples = sim::replay(&profile, pad_pressure);
+    let apogee = profile.iter().map(|r| r.altitude).fold(f32::MIN, f32::max);
+
+    if !quiet {
+        println!("t\tphase\talt\talt_est\tvz_est\tpredicted_apogee\terror");
+        for s in &samples {
+            println!(
+                "{:.2}\t{:?}\t{:.1}\t{:.1}\t{:.1}\t{:.1}\t{:+.1}",
+                s.t,
+                s.phase,
+                s.altitude,
+                s.estimate.alt,
+                s.estimate.vz,
+                s.predicted_apogee,
+                s.predicted_apogee - apogee
+            );
+        }
+    }
+    let errors = sim::coast_errors(&samples, apogee);
+    let worst = errors.iter().fold(0.0f32, |w, e| if e.abs() > w.abs() { *e } else { w });
+    let mean = errors.iter().sum::<f32>() / errors.len().max(1) as f32;
+    eprintln!(
+        "recorded apogee {apogee:.1} m; coast prediction error over {} frames: mean {mean:+.1} m, worst {worst:+.1} m",
+        errors.len()
+    );
+    ExitCode::SUCCESS
+}
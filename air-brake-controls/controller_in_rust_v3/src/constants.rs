@@ -0,0 +1,91 @@
+//! Controller constants: mission target, vehicle drag model, estimator,
+//! actuator limits and in-flight drag estimation.
+
+// --- Mission ---
+
+/// Target apogee (m AGL): 10,000 ft.
+pub const TARGET_APOGEE_M: f32 = 3048.0;
+
+// --- Vehicle drag model (burnt-out vehicle) ---
+//
+// Only the ratio Cd·A / m enters the coast dynamics. BODY_CDA_M2 is fitted to
+// the coast of the recorded flight in `profiles/fsw_test_flight.csv`
+// (Cd·A / m ≈ 3.2e-4 m²/kg with a Spaceport America pad), at the 25 kg
+// burnt-out mass.
+
+/// Burnt-out vehicle mass (kg).
+pub const DRY_MASS_KG: f32 = 25.0;
+/// Airframe drag area Cd·A with the brakes retracted (m²).
+pub const BODY_CDA_M2: f32 = 0.0079;
+/// Drag area the airbrakes add at full deployment (m²). Assumed linear in
+/// deployment.
+pub const BRAKE_CDA_M2: f32 = 0.0120;
+
+// --- Physics ---
+
+pub const G: f32 = 9.80665;
+/// Specific gas constant of dry air (J/(kg·K)).
+pub const R_AIR: f32 = 287.05;
+/// Standard sea-level pressure (Pa) and temperature (K), and lapse rate (K/m).
+pub const P0_PA: f32 = 101_325.0;
+pub const T0_K: f32 = 288.15;
+pub const LAPSE_K_PER_M: f32 = 0.0065;
+/// Pad pressure assumed when the FSW has not sent one yet (Pa): Spaceport
+/// America, 1401 m MSL.
+pub const DEFAULT_PAD_PRESSURE_PA: f32 = 85_590.0;
+
+// --- Apogee prediction ---
+
+/// Integration step of the coast prediction (s).
+pub const PREDICT_DT_S: f32 = 0.1;
+/// Upper bound on the predicted coast, so a bad state can't stall Core 1 (s).
+pub const PREDICT_MAX_S: f32 = 60.0;
+/// Binary-search iterations over deployment: 2^-12 resolution.
+pub const SEARCH_ITERATIONS: u32 = 12;
+
+// --- Estimator ---
+
+/// Pad calibration smoothing (per sample) for gravity and gyro bias.
+pub const PAD_CAL_ALPHA: f32 = 0.05;
+/// Minimum pad gravity reading (m/s²) for the IMU to be trusted; an absent
+/// IMU reads all zeros.
+pub const IMU_MIN_GRAVITY: f32 = 5.0;
+/// Alpha-beta gains correcting the IMU-propagated state with the baro.
+pub const ALPHA_IMU: f32 = 0.10;
+pub const BETA_IMU: f32 = 0.005;
+/// Gains when there is no IMU and the state is propagated from the drag
+/// model (Coast) or not at all (Boost).
+pub const ALPHA_BARO: f32 = 0.30;
+pub const BETA_BARO: f32 = 0.05;
+/// Frames further apart than this restart the timestep at nominal (s).
+pub const MAX_DT_S: f32 = 0.5;
+/// FSW cycle period (s).
+pub const NOMINAL_DT_S: f32 = 0.05;
+
+// --- Actuator ---
+
+/// Maximum deployment change per second: full travel in 0.5 s.
+pub const MAX_DEPLOY_RATE_PER_S: f32 = 2.0;
+/// Below this vertical velocity (m/s) the brakes hold position; near apogee
+/// they have no authority left.
+pub const MIN_ACTIVATION_VEL_MPS: f32 = 40.0;
+
+// --- Drag estimation (Coast) ---
+
+/// Prior 1-σ uncertainty of the configured drag areas (fraction).
+pub const DRAG_EST_BODY_SD: f32 = 0.3;
+pub const DRAG_EST_BRAKE_SD: f32 = 0.5;
+/// Random walk of the drag areas (fraction of the prior per √s), mostly Cd
+/// falling with Mach through the coast.
+pub const DRAG_EST_DRIFT_PER_S: f32 = 0.02;
+/// 1-σ error of the measured drag deceleration (m/s²): accelerometer noise
+/// plus attitude and airspeed error.
+pub const DRAG_EST_ACCEL_SD: f32 = 0.5;
+/// Coast frames are skipped for this long after burnout, while the motor
+/// tails off (s).
+pub const DRAG_EST_SETTLE_S: f32 = 0.5;
+/// Below this airspeed (m/s) the drag is too small to measure.
+pub const DRAG_EST_MIN_SPEED_MPS: f32 = 60.0;
+/// The estimate is held within these multiples of the configured drag areas.
+pub const DRAG_EST_MIN_SCALE: f32 = 0.5;
+pub const DRAG_EST_MAX_SCALE: f32 = 2.0;
@@ -0,0 +1,115 @@
+//! Online estimate of the coast drag areas from the measured deceleration.
+//!
+//! In Coast the accelerometer reads drag alone: the specific force along the
+//! airframe is −D/m. With the air density at the estimated altitude and the
+//! estimated airspeed, each frame gives a measured drag area
+//!
+//! ```text
+//! y = 2·m·a_drag / (ρ·V²) = body_cda + deployment · brake_cda
+//! ```
+//!
+//! and a two-parameter recursive least squares over `[1, deployment]`
+//! separates the airframe from the airbrakes. The filter starts from the
+//! configured `DragModel` with `DRAG_EST_*_SD` of prior uncertainty, so until
+//! the brakes have moved the brake term stays at its prior, and a small
+//! random-walk term lets the estimate follow the drop in Cd through the
+//! transonic part of the coast. Each frame is weighted by its dynamic
+//! pressure through the measurement noise, so the slow frames near apogee
+//! barely count.
+//!
+//! Everything not modelled (mass error, a warmer or colder day than ISA,
+//! accelerometer gain) lands in the estimate too, which is what the
+//! predictor wants: it only ever uses ρ·Cd·A / m.
+//!
+//! Without an IMU there is no independent drag measurement and the estimate
+//! stays at the prior.
+
+use crate::constants::*;
+use crate::predictor::{Atmosphere, DragModel, State};
+
+#[derive(Clone, Debug)]
+pub struct DragEstimator {
+    prior: DragModel,
+    /// `[body_cda, brake_cda]` (m²).
+    theta: [f32; 2],
+    /// Covariance of `theta`.
+    p: [[f32; 2]; 2],
+    /// Time since the first Coast frame (s).
+    coast_s: f32,
+    samples: u32,
+}
+
+impl DragEstimator {
+    pub fn new(prior: DragModel) -> Self {
+        let body_sd = DRAG_EST_BODY_SD * prior.body_cda_m2;
+        let brake_sd = DRAG_EST_BRAKE_SD * prior.brake_cda_m2;
+        Self {
+            prior,
+            theta: [prior.body_cda_m2, prior.brake_cda_m2],
+            p: [[body_sd * body_sd, 0.0], [0.0, brake_sd * brake_sd]],
+            coast_s: 0.0,
+            samples: 0,
+        }
+    }
+
+    /// One Coast frame `dt` seconds after the last. `axial` is the
+    /// calibrated specific force along the airframe (m/s², negative under
+    /// drag) and `deployment` the brake position over the last cycle.
+    pub fn update(&mut self, atm: &Atmosphere, state: &State, axial: f32, deployment: f32, dt: f32) {
+        self.coast_s += dt;
+        let v2 = state.vz * state.vz + state.vh * state.vh;
+        if self.coast_s < DRAG_EST_SETTLE_S || v2 < DRAG_EST_MIN_SPEED_MPS * DRAG_EST_MIN_SPEED_MPS {
+            return;
+        }
+        let q = 0.5 * atm.density(state.alt) * v2;
+        let y = -axial * DRY_MASS_KG / q;
+        let r = DRAG_EST_ACCEL_SD * DRY_MASS_KG / q;
+        let phi = [1.0, deployment.clamp(0.0, 1.0)];
+
+        // Random walk between frames
+        let walk = DRAG_EST_DRIFT_PER_S * DRAG_EST_DRIFT_PER_S * dt;
+        self.p[0][0] += walk * self.prior.body_cda_m2 * self.prior.body_cda_m2;
+        self.p[1][1] += walk * self.prior.brake_cda_m2 * self.prior.brake_cda_m2;
+
+        let p_phi = [
+            self.p[0][0] * phi[0] + self.p[0][1] * phi[1],
+            self.p[1][0] * phi[0] + self.p[1][1] * phi[1],
+        ];
+        let s = r * r + phi[0] * p_phi[0] + phi[1] * p_phi[1];
+        let innovation = y - (self.theta[0] * phi[0] + self.theta[1] * phi[1]);
+        for i in 0..2 {
+            self.theta[i] += p_phi[i] / s * innovation;
+            for j in 0..2 {
+                self.p[i][j] -= p_phi[i] * p_phi[j] / s;
+            }
+        }
+        self.samples += 1;
+    }
+
+    /// Current estimate, held within `DRAG_EST_MIN_SCALE`–`DRAG_EST_MAX_SCALE`
+    /// of the prior so a bad stretch of data can't make the predictor wildly
+    /// optimistic or pessimistic.
+    pub fn model(&self) -> DragModel {
+        let bound = |v: f32, prior: f32| v.clamp(prior * DRAG_EST_MIN_SCALE, prior * DRAG_EST_MAX_SCALE);
+        DragModel {
+            body_cda_m2: bound(self.theta[0], self.prior.body_cda_m2),
+            brake_cda_m2: bound(self.theta[1], self.prior.brake_cda_m2),
+        }
+    }
+
+    /// 1-σ uncertainty of `[body_cda, brake_cda]` (m²).
+    pub fn sd(&self) -> [f32; 2] {
+        [libm::sqrtf(self.p[0][0].max(0.0)), libm::sqrtf(self.p[1][1].max(0.0))]
+    }
+
+    /// Frames folded into the estimate so far.
+    pub fn samples(&self) -> u32 {
+        self.samples
+    }
+}
+
+impl Default for DragEstimator {
+    fn default() -> Self {
+        Self::new(DragModel::default())
+    }
+}
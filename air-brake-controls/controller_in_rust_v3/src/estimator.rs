@@ -0,0 +1,112 @@
+//! Altitude and velocity estimation from the baro and IMU.
+//!
+//! On the pad the estimator averages the accelerometer and gyro to learn the
+//! gravity reading (accelerometer scale) and gyro bias. In flight an
+//! alpha-beta filter propagates altitude and vertical velocity with the
+//! measured specific force along the airframe, projected to vertical through
+//! the gyro-integrated tilt, and corrects them with the baro altitude.
+//!
+//! Without an IMU (the pad never saw gravity) the filter propagates with the
+//! drag-model deceleration the caller supplies and leans harder on the baro.
+//!
+//! The FSW levels its IMU frame on the pad, so tilt starts at zero: the rail
+//! angle is not observable here and only the change in attitude is tracked.
+
+use crate::constants::*;
+use crate::predictor::State;
+use crate::types::SensorInput;
+
+const DEG_TO_RAD: f32 = core::f32::consts::PI / 180.0;
+
+#[derive(Clone, Debug)]
+pub struct Estimator {
+    /// Pad accelerometer reading along the airframe (m/s²); `None` until
+    /// the first pad sample.
+    gravity: Option<f32>,
+    gyro_bias: [f32; 3],
+    /// Small-angle tilt from the pad attitude about body X and Z (rad).
+    tilt: [f32; 2],
+    alt: f32,
+    vz: f32,
+}
+
+impl Estimator {
+    pub const fn new() -> Self {
+        Self { gravity: None, gyro_bias: [0.0; 3], tilt: [0.0; 2], alt: 0.0, vz: 0.0 }
+    }
+
+    /// Pad sample: track the gravity reading and gyro bias.
+    pub fn calibrate(&mut self, input: &SensorInput) {
+        let axial = -input.accel_y;
+        let gyro = [input.gyro_x, input.gyro_y, input.gyro_z];
+        match self.gravity {
+            None => {
+                self.gravity = Some(axial);
+                self.gyro_bias = gyro;
+            }
+            Some(ref mut g) => {
+                *g += PAD_CAL_ALPHA * (axial - *g);
+                for (bias, rate) in self.gyro_bias.iter_mut().zip(gyro) {
+                    *bias += PAD_CAL_ALPHA * (rate - *bias);
+                }
+            }
+        }
+    }
+
+    /// Starts flight tracking from rest at the current baro altitude.
+    pub fn start(&mut self, input: &SensorInput) {
+        self.alt = input.altitude;
+        self.vz = 0.0;
+        self.tilt = [0.0; 2];
+    }
+
+    /// True if the pad calibration saw gravity on the accelerometer.
+    pub fn imu_ok(&self) -> bool {
+        self.gravity.is_some_and(|g| g > IMU_MIN_GRAVITY)
+    }
+
+    /// Specific force along the airframe (m/s², positive forwards), scaled
+    /// by the pad gravity reading; `None` without an IMU.
+    pub fn axial(&self, input: &SensorInput) -> Option<f32> {
+        // Scale by the pad reading so a gain error doesn't look like thrust
+        self.gravity.filter(|&g| g > IMU_MIN_GRAVITY).map(|g| -input.accel_y * G / g)
+    }
+
+    /// One flight sample `dt` seconds after the last. `model_accel` is the
+    /// vertical acceleration to propagate with when there is no IMU.
+    pub fn update(&mut self, input: &SensorInput, dt: f32, model_accel: f32) {
+        let (accel, alpha, beta) = match self.axial(input) {
+            Some(axial) => {
+                self.tilt[0] += (input.gyro_x - self.gyro_bias[0]) * DEG_TO_RAD * dt;
+                self.tilt[1] += (input.gyro_z - self.gyro_bias[2]) * DEG_TO_RAD * dt;
+                (axial * libm::cosf(self.tilt()) - G, ALPHA_IMU, BETA_IMU)
+            }
+            None => (model_accel, ALPHA_BARO, BETA_BARO),
+        };
+
+        self.alt += self.vz * dt + 0.5 * accel * dt * dt;
+        self.vz += accel * dt;
+
+        let residual = input.altitude - self.alt;
+        self.alt += alpha * residual;
+        self.vz += beta / dt * residual;
+    }
+
+    /// Tilt from the pad attitude (rad).
+    pub fn tilt(&self) -> f32 {
+        libm::sqrtf(self.tilt[0] * self.tilt[0] + self.tilt[1] * self.tilt[1])
+    }
+
+    /// Current state, with the horizontal speed inferred from the tilt
+    /// (velocity along the airframe).
+    pub fn state(&self) -> State {
+        let vh = self.vz.max(0.0) * libm::tanf(self.tilt().min(1.2));
+        State { alt: self.alt, vz: self.vz, vh }
+    }
+}
+
+impl Default for Estimator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
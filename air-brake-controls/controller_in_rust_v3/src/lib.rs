@@ -0,0 +1,28 @@
+//! Airbrake apogee controller, run on FSW Core 1 (see `AIRBRAKE_REFERENCE.md`).
+//!
+//! `AirbrakeSystem::execute` takes one sensor frame per FSW cycle and returns
+//! the airbrake deployment (0.0–1.0) and the predicted apogee. The pipeline is
+//! estimator (baro + IMU → altitude, vertical and horizontal velocity),
+//! predictor (numerically integrated coast to apogee at a given deployment)
+//! and a binary search over deployment for the one that lands on the target.
+//! In coast the drag areas the predictor uses are refined online from the
+//! measured deceleration (`drag_estimator`).
+//!
+//! The library is `no_std`. The `std` feature (on by default) adds the host
+//! simulator in `sim` and the `airbrake-sim` binary.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub mod airbrakes;
+pub mod constants;
+pub mod drag_estimator;
+pub mod estimator;
+pub mod predictor;
+#[cfg(feature = "std")]
+pub mod sim;
+pub mod types;
+
+pub use airbrakes::AirbrakeSystem;
+pub use drag_estimator::DragEstimator;
+pub use predictor::DragModel;
+pub use types::{Config, Output, Phase, SensorInput};
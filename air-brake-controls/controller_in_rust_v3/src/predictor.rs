@@ -0,0 +1,126 @@
+//! Apogee prediction: integrates the coast from the current state with the
+//! drag model at a fixed deployment, and searches for the deployment that
+//! hits a target apogee.
+
+use crate::constants::*;
+
+/// Kinematic state in the vertical plane of flight. Altitude is m AGL,
+/// velocities m/s with `vz` positive up and `vh` the horizontal speed.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct State {
+    pub alt: f32,
+    pub vz: f32,
+    pub vh: f32,
+}
+
+/// ISA troposphere anchored at the pad, from the pad's static pressure.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Atmosphere {
+    pad_msl: f32,
+}
+
+impl Atmosphere {
+    /// A pressure outside anything a pad could read (including the 0.0 the
+    /// FSW sends before it has latched one) falls back to
+    /// `DEFAULT_PAD_PRESSURE_PA`.
+    pub fn from_pad_pressure(pressure: f32) -> Self {
+        let p = if (50_000.0..=110_000.0).contains(&pressure) { pressure } else { DEFAULT_PAD_PRESSURE_PA };
+        // NOAA pressure altitude, as in the FSW's bmp390 driver
+        let pad_msl = 44_330.0 * (1.0 - libm::powf(p / P0_PA, 0.190_295));
+        Self { pad_msl }
+    }
+
+    pub fn pad_msl(&self) -> f32 {
+        self.pad_msl
+    }
+
+    /// Air density (kg/m³) at `agl` metres above the pad.
+    pub fn density(&self, agl: f32) -> f32 {
+        let t = T0_K - LAPSE_K_PER_M * (self.pad_msl + agl);
+        let p = P0_PA * libm::powf(t / T0_K, 5.255_88);
+        p / (R_AIR * t)
+    }
+}
+
+impl Default for Atmosphere {
+    fn default() -> Self {
+        Self::from_pad_pressure(DEFAULT_PAD_PRESSURE_PA)
+    }
+}
+
+/// Drag areas of the burnt-out vehicle, relative to `DRY_MASS_KG`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DragModel {
+    /// Airframe Cd·A with the brakes retracted (m²).
+    pub body_cda_m2: f32,
+    /// Cd·A the airbrakes add at full deployment (m²), linear in deployment.
+    pub brake_cda_m2: f32,
+}
+
+impl DragModel {
+    /// Drag area of the vehicle at `deployment` (m²).
+    pub fn area(&self, deployment: f32) -> f32 {
+        self.body_cda_m2 + self.brake_cda_m2 * deployment.clamp(0.0, 1.0)
+    }
+}
+
+impl Default for DragModel {
+    fn default() -> Self {
+        Self { body_cda_m2: BODY_CDA_M2, brake_cda_m2: BRAKE_CDA_M2 }
+    }
+}
+
+/// Acceleration (m/s², vertical and horizontal) of the burnt-out vehicle:
+/// gravity plus drag opposing the velocity, no wind.
+pub fn acceleration(atm: &Atmosphere, drag: &DragModel, s: &State, deployment: f32) -> (f32, f32) {
+    let speed = libm::sqrtf(s.vz * s.vz + s.vh * s.vh);
+    let k = 0.5 * atm.density(s.alt) * drag.area(deployment) / DRY_MASS_KG * speed;
+    (-G - k * s.vz, -k * s.vh)
+}
+
+/// Apogee (m AGL) reached from `state` with the brakes held at `deployment`.
+/// Midpoint (RK2) integration at `PREDICT_DT_S`; the last partial step is
+/// closed with constant deceleration.
+pub fn apogee(atm: &Atmosphere, drag: &DragModel, state: &State, deployment: f32) -> f32 {
+    let mut s = *state;
+    let dt = PREDICT_DT_S;
+    let mut t = 0.0;
+    while s.vz > 0.0 && t < PREDICT_MAX_S {
+        let (az, ah) = acceleration(atm, drag, &s, deployment);
+        let mid = State { alt: s.alt + 0.5 * dt * s.vz, vz: s.vz + 0.5 * dt * az, vh: s.vh + 0.5 * dt * ah };
+        let (az_mid, ah_mid) = acceleration(atm, drag, &mid, deployment);
+        let vz_next = s.vz + dt * az_mid;
+        if vz_next <= 0.0 {
+            // Decelerating at az_mid, the rest of the climb is vz² / 2|a|
+            return s.alt + s.vz * s.vz / (-2.0 * az_mid);
+        }
+        s.alt += dt * mid.vz;
+        s.vz = vz_next;
+        s.vh += dt * ah_mid;
+        t += dt;
+    }
+    s.alt
+}
+
+/// Deployment in [0, 1] whose predicted apogee is closest to `target`.
+/// Apogee falls monotonically with deployment, so a binary search of
+/// `SEARCH_ITERATIONS` steps is enough; targets outside the reachable range
+/// saturate at 0 (can't climb higher) or 1.
+pub fn deployment_for(atm: &Atmosphere, drag: &DragModel, state: &State, target: f32) -> f32 {
+    if apogee(atm, drag, state, 0.0) <= target {
+        return 0.0;
+    }
+    if apogee(atm, drag, state, 1.0) >= target {
+        return 1.0;
+    }
+    let (mut lo, mut hi) = (0.0f32, 1.0f32);
+    for _ in 0..SEARCH_ITERATIONS {
+        let mid = 0.5 * (lo + hi);
+        if apogee(atm, drag, state, mid) > target {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    0.5 * (lo + hi)
+}
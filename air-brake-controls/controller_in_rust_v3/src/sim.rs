@@ -0,0 +1,377 @@
+//! Host flight simulator (feature `std`).
+//!
+//! `fly` closes the loop: a 2-DOF point-mass rocket (thrust and drag along
+//! the velocity, gravity turn off a tilted rail, ISA air) produces noisy
+//! baro/IMU frames at the FSW rate, the controller's deployment drives the
+//! simulated brakes through a rate-limited, lagging actuator, and the run
+//! ends at the true apogee, where the FSW stops feeding the controller.
+//!
+//! `replay` runs the estimator and predictor open loop over a recorded
+//! altitude profile, brakes retracted, to check the prediction against the
+//! apogee that was actually flown.
+
+use std::path::Path;
+
+use crate::airbrakes::AirbrakeSystem;
+use crate::constants::{self, G, NOMINAL_DT_S};
+use crate::estimator::Estimator;
+use crate::predictor::{self, Atmosphere, DragModel, State};
+use crate::types::{Config, Phase, SensorInput};
+
+/// Truth vehicle. Drag areas default to the controller's model; scale them
+/// to test model error.
+#[derive(Clone, Copy, Debug)]
+pub struct Vehicle {
+    pub dry_mass: f32,
+    pub prop_mass: f32,
+    /// Mean thrust over the burn (N).
+    pub thrust: f32,
+    pub burn_s: f32,
+    pub body_cda: f32,
+    /// Added Cd·A at full deployment (m²).
+    pub brake_cda: f32,
+    /// Rail angle from vertical (°).
+    pub rail_angle_deg: f32,
+}
+
+impl Vehicle {
+    /// Sized to fly about like the recorded flight: ~280 m/s at burnout and
+    /// ~3.45 km apogee with the brakes retracted, so there is ~400 m to take
+    /// out for the 10k ft target.
+    pub const NOMINAL: Vehicle = Vehicle {
+        dry_mass: constants::DRY_MASS_KG,
+        prop_mass: 5.0,
+        thrust: 2_150.0,
+        burn_s: 4.5,
+        body_cda: constants::BODY_CDA_M2,
+        brake_cda: constants::BRAKE_CDA_M2,
+        rail_angle_deg: 3.0,
+    };
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct SimConfig {
+    pub vehicle: Vehicle,
+    /// Pad static pressure (Pa).
+    pub pad_pressure: f32,
+    /// Air temperature offset from ISA (K); the controller assumes ISA.
+    pub temp_offset_k: f32,
+    /// 1-σ sensor noise: baro (m), accelerometer (m/s²), gyro (°/s).
+    pub baro_sd: f32,
+    pub accel_sd: f32,
+    pub gyro_sd: f32,
+    /// Accelerometer gain error (fraction), which the pad calibration should
+    /// absorb.
+    pub accel_scale_error: f32,
+    /// No IMU: the accelerometer and gyro read zero.
+    pub no_imu: bool,
+    /// Time on the pad before ignition (s).
+    pub pad_s: f32,
+    /// Boost phase length: the FSW sends Coast when the MAV closes (s).
+    pub boost_phase_s: f32,
+    /// Brake mechanism: maximum rate (per s) and first-order lag (s).
+    pub brake_rate: f32,
+    pub brake_tau_s: f32,
+    /// When false the brakes stay retracted whatever the controller says.
+    pub control: bool,
+    /// Controller parameters, as the FSW would send them.
+    pub controller: Config,
+    pub seed: u64,
+}
+
+impl Default for SimConfig {
+    fn default() -> Self {
+        Self {
+            vehicle: Vehicle::NOMINAL,
+            pad_pressure: constants::DEFAULT_PAD_PRESSURE_PA,
+            temp_offset_k: 0.0,
+            baro_sd: 1.0,
+            accel_sd: 0.3,
+            gyro_sd: 0.05,
+            accel_scale_error: 0.0,
+            no_imu: false,
+            pad_s: 2.0,
+            boost_phase_s: 6.0,
+            brake_rate: 2.5,
+            brake_tau_s: 0.1,
+            control: true,
+            controller: Config::default(),
+            seed: 1,
+        }
+    }
+}
+
+/// One controller cycle of a simulated flight.
+#[derive(Clone, Copy, Debug)]
+pub struct Sample {
+    pub t: f32,
+    pub phase: Phase,
+    pub alt: f32,
+    pub vz: f32,
+    pub estimate: State,
+    /// Commanded and actual deployment.
+    pub command: f32,
+    pub brakes: f32,
+    pub predicted_apogee: f32,
+    /// Drag model the controller predicted with (the in-flight estimate in
+    /// Coast).
+    pub drag: DragModel,
+}
+
+pub struct Flight {
+    pub apogee: f32,
+    pub apogee_t: f32,
+    pub samples: Vec<Sample>,
+}
+
+/// SplitMix64 with Box–Muller normals; seeded runs are reproducible.
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in [0, 1).
+    pub fn uniform(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    pub fn normal(&mut self, mean: f32, sd: f32) -> f32 {
+        let u1 = 1.0 - self.uniform();
+        let u2 = self.uniform();
+        mean + sd * ((-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()) as f32
+    }
+}
+
+/// Simulation substeps per controller cycle.
+const SUBSTEPS: u32 = 10;
+/// Rail length (m): the rocket can't turn until it leaves the rail.
+const RAIL_M: f32 = 5.0;
+
+/// Flies one closed-loop flight from the pad to the true apogee.
+pub fn fly(cfg: &SimConfig) -> Flight {
+    let v = &cfg.vehicle;
+    let atm = Atmosphere::from_pad_pressure(cfg.pad_pressure);
+    let density = |alt: f32| {
+        // ISA density rescaled to the warmer or colder day at the same pressure
+        let t_isa = constants::T0_K - constants::LAPSE_K_PER_M * (atm.pad_msl() + alt);
+        atm.density(alt) * t_isa / (t_isa + cfg.temp_offset_k)
+    };
+    let mut rng = Rng::new(cfg.seed);
+    let mut system = AirbrakeSystem::with_config(cfg.controller);
+
+    let dt = NOMINAL_DT_S / SUBSTEPS as f32;
+    let mass_flow = v.prop_mass / v.burn_s;
+    let mut prop = v.prop_mass;
+    // Flight-path angle from vertical, position and velocity
+    let mut theta = v.rail_angle_deg.to_radians();
+    let (mut alt, mut vz, mut vh) = (0.0f32, 0.0f32, 0.0f32);
+    let mut brakes = 0.0f32;
+    let mut command = 0.0f32;
+    let mut specific_force = G;
+    let mut pitch_rate = 0.0f32;
+    let mut t = 0.0f32;
+    let mut samples = Vec::new();
+
+    loop {
+        // --- Controller cycle on this frame ---
+        let phase = if t < cfg.pad_s {
+            Phase::Pad
+        } else if t < cfg.pad_s + cfg.boost_phase_s {
+            Phase::Boost
+        } else {
+            Phase::Coast
+        };
+        let (accel_y, gyro_x) = if cfg.no_imu {
+            (0.0, 0.0)
+        } else {
+            (
+                -specific_force * (1.0 + cfg.accel_scale_error) + rng.normal(0.0, cfg.accel_sd),
+                pitch_rate.to_degrees() + rng.normal(0.0, cfg.gyro_sd),
+            )
+        };
+        let input = SensorInput {
+            time: t,
+            altitude: alt + rng.normal(0.0, cfg.baro_sd),
+            vel_d: -vz,
+            reference_pressure: cfg.pad_pressure,
+            gyro_x,
+            gyro_y: 0.0,
+            gyro_z: 0.0,
+            accel_x: 0.0,
+            accel_y,
+            accel_z: 0.0,
+            phase,
+        };
+        let out = system.execute(&input);
+        if cfg.control {
+            command = out.deployment;
+        }
+        samples.push(Sample {
+            t,
+            phase,
+            alt,
+            vz,
+            estimate: system.state(),
+            command: out.deployment,
+            brakes,
+            predicted_apogee: out.predicted_apogee,
+            drag: out.drag,
+        });
+
+        // --- Physics until the next frame ---
+        for _ in 0..SUBSTEPS {
+            let burning = t >= cfg.pad_s && prop > 0.0;
+            let thrust = if burning { v.thrust } else { 0.0 };
+            if burning {
+                prop = (prop - mass_flow * dt).max(0.0);
+            }
+            let mass = v.dry_mass + prop;
+
+            let target = command.clamp(0.0, 1.0);
+            let rate = ((target - brakes) / cfg.brake_tau_s).clamp(-cfg.brake_rate, cfg.brake_rate);
+            brakes = (brakes + rate * dt).clamp(0.0, 1.0);
+
+            let speed = (vz * vz + vh * vh).sqrt();
+            let drag = 0.5 * density(alt) * speed * speed * (v.body_cda + v.brake_cda * brakes);
+            let axial = thrust - drag;
+            if alt <= 0.0 && axial <= mass * G {
+                // Sitting on the pad
+                specific_force = G;
+                t += dt;
+                continue;
+            }
+            specific_force = axial / mass;
+            let (sin, cos) = theta.sin_cos();
+            vz += (specific_force * cos - G) * dt;
+            vh += specific_force * sin * dt;
+            alt += vz * dt;
+            // Gravity turn once off the rail
+            let new_theta = if alt > RAIL_M { vh.atan2(vz) } else { theta };
+            pitch_rate = (new_theta - theta) / dt;
+            theta = new_theta;
+            t += dt;
+
+            if vz <= 0.0 && alt > RAIL_M {
+                return Flight { apogee: alt, apogee_t: t, samples };
+            }
+        }
+    }
+}
+
+/// One row of a recorded profile.
+#[derive(Clone, Copy, Debug)]
+pub struct ProfileRow {
+    pub time: f32,
+    pub altitude: f32,
+    pub phase: Phase,
+}
+
+/// Reads a profile CSV: `time_s,altitude_m,phase` with phase `pad`,
+/// `boost` or `coast`. Lines starting with `#` and the header are skipped.
+pub fn load_profile(path: &Path) -> Result<Vec<ProfileRow>, String> {
+    let text = std::fs::read_to_string(path).map_err(|e| format!("{}: {e}", path.display()))?;
+    let mut rows = Vec::new();
+    for (n, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with("time") {
+            continue;
+        }
+        let bad = || format!("{}:{}: expected time_s,altitude_m,phase", path.display(), n + 1);
+        let mut cols = line.split(',').map(str::trim);
+        let time = cols.next().and_then(|c| c.parse().ok()).ok_or_else(bad)?;
+        let altitude = cols.next().and_then(|c| c.parse().ok()).ok_or_else(bad)?;
+        let phase = match cols.next() {
+            Some("pad") => Phase::Pad,
+            Some("boost") => Phase::Boost,
+            Some("coast") => Phase::Coast,
+            _ => return Err(bad()),
+        };
+        rows.push(ProfileRow { time, altitude, phase });
+    }
+    Ok(rows)
+}
+
+/// One replayed frame: the estimate and the retracted-brakes apogee the
+/// controller would have predicted from it.
+#[derive(Clone, Copy, Debug)]
+pub struct ReplaySample {
+    pub t: f32,
+    pub phase: Phase,
+    pub altitude: f32,
+    pub estimate: State,
+    pub predicted_apogee: f32,
+}
+
+/// Runs the estimator and predictor over a baro-only recorded profile with
+/// the brakes retracted, as the recording was flown.
+pub fn replay(profile: &[ProfileRow], pad_pressure: f32) -> Vec<ReplaySample> {
+    let atm = Atmosphere::from_pad_pressure(pad_pressure);
+    let drag = DragModel::default();
+    let mut est = Estimator::new();
+    let mut last: Option<ProfileRow> = None;
+    let mut out = Vec::with_capacity(profile.len());
+    for row in profile {
+        let input = SensorInput {
+            time: row.time,
+            altitude: row.altitude,
+            vel_d: 0.0,
+            reference_pressure: pad_pressure,
+            gyro_x: 0.0,
+            gyro_y: 0.0,
+            gyro_z: 0.0,
+            accel_x: 0.0,
+            accel_y: 0.0,
+            accel_z: 0.0,
+            phase: row.phase,
+        };
+        let dt = last.map_or(NOMINAL_DT_S, |l| row.time - l.time);
+        match row.phase {
+            Phase::Pad => est.calibrate(&input),
+            Phase::Boost | Phase::Coast => {
+                if last.is_none_or(|l| l.phase == Phase::Pad) {
+                    est.start(&input);
+                }
+                let model_accel = if row.phase == Phase::Coast {
+                    predictor::acceleration(&atm, &drag, &est.state(), 0.0).0
+                } else {
+                    0.0
+                };
+                est.update(&input, dt, model_accel);
+            }
+        }
+        let estimate = est.state();
+        out.push(ReplaySample {
+            t: row.time,
+            phase: row.phase,
+            altitude: row.altitude,
+            estimate,
+            predicted_apogee: predictor::apogee(&atm, &drag, &estimate, 0.0),
+        });
+        last = Some(*row);
+    }
+    out
+}
+
+/// Prediction errors (m) against the flown `apogee` over the part of the
+/// coast the controller acts on: from 1 s after Coast begins until the
+/// estimated climb rate drops below `MIN_ACTIVATION_VEL_MPS`.
+pub fn coast_errors(samples: &[ReplaySample], apogee: f32) -> Vec<f32> {
+    let Some(coast_start) = samples.iter().find(|s| s.phase == Phase::Coast).map(|s| s.t) else {
+        return Vec::new();
+    };
+    samples
+        .iter()
+        .filter(|s| s.t >= coast_start + 1.0 && s.estimate.vz >= constants::MIN_ACTIVATION_VEL_MPS)
+        .map(|s| s.predicted_apogee - apogee)
+        .collect()
+}
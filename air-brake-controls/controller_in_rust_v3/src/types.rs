@@ -0,0 +1,88 @@
+//! Controller input, output and configuration, as exchanged with the FSW
+//! airbrake task.
+
+use crate::constants::{MAX_DEPLOY_RATE_PER_S, MIN_ACTIVATION_VEL_MPS, TARGET_APOGEE_M};
+use crate::predictor::DragModel;
+
+/// Flight phase as seen by the controller. The FSW stops sending frames
+/// after Coast, so there is no descent phase.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Phase {
+    /// Startup / Standby: calibrate, brakes retracted.
+    Pad,
+    /// Ascent (MAV open): track the trajectory, brakes retracted.
+    Boost,
+    /// Coast: active control.
+    Coast,
+}
+
+/// One FSW sensor frame. Axes are the FSW's calibrated IMU frame, which
+/// reads `[0, -g, 0]` on the pad: body Y runs along the airframe.
+#[derive(Clone, Copy, Debug)]
+pub struct SensorInput {
+    /// Monotonic time (s).
+    pub time: f32,
+    /// Barometric altitude (m AGL once the FSW has latched the pad altitude).
+    pub altitude: f32,
+    /// GPS down velocity (m/s). 1 Hz and lagging, so only informational.
+    pub vel_d: f32,
+    /// Pad static pressure (Pa); sets the air density model.
+    pub reference_pressure: f32,
+    /// Gyro rates (°/s).
+    pub gyro_x: f32,
+    pub gyro_y: f32,
+    pub gyro_z: f32,
+    /// Accelerometer (m/s²).
+    pub accel_x: f32,
+    pub accel_y: f32,
+    pub accel_z: f32,
+    pub phase: Phase,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Output {
+    /// Commanded deployment, 0.0 (retracted) to 1.0 (fully deployed).
+    pub deployment: f32,
+    /// Predicted apogee (m AGL) at the commanded deployment.
+    pub predicted_apogee: f32,
+    /// Drag model behind the prediction: the configured one until Coast,
+    /// then the in-flight estimate.
+    pub drag: DragModel,
+}
+
+/// Mission and vehicle parameters the FSW can change before flight; the
+/// defaults are the values in `constants`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Config {
+    /// Target apogee (m AGL).
+    pub target_apogee_m: f32,
+    /// Maximum deployment change per second.
+    pub max_deploy_rate_per_s: f32,
+    /// Below this vertical velocity (m/s) the brakes hold position.
+    pub min_activation_vel_mps: f32,
+    pub drag: DragModel,
+}
+
+impl Config {
+    /// Rejects values no flight of this airframe could use, so a typo or a
+    /// unit slip (ft for m, cm² for m²) can't reach the controller.
+    pub fn is_valid(&self) -> bool {
+        let in_range = |v: f32, lo: f32, hi: f32| v.is_finite() && v >= lo && v <= hi;
+        in_range(self.target_apogee_m, 100.0, 20_000.0)
+            && in_range(self.max_deploy_rate_per_s, 0.05, 10.0)
+            && in_range(self.min_activation_vel_mps, 0.0, 500.0)
+            && in_range(self.drag.body_cda_m2, 0.0001, 0.1)
+            && in_range(self.drag.brake_cda_m2, 0.0, 0.1)
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            target_apogee_m: TARGET_APOGEE_M,
+            max_deploy_rate_per_s: MAX_DEPLOY_RATE_PER_S,
+            min_activation_vel_mps: MIN_ACTIVATION_VEL_MPS,
+            drag: DragModel::default(),
+        }
+    }
+}
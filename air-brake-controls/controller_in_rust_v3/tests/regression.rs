@@ -0,0 +1,171 @@
+//! Regression tests: apogee prediction against the recorded flight,
+//! closed-loop apogee error of the simulated vehicle across model errors,
+//! and the in-flight drag estimate against the simulated vehicle.
+
+use std::path::Path;
+
+use controller_in_rust_v3::constants::{DEFAULT_PAD_PRESSURE_PA, MAX_DEPLOY_RATE_PER_S, NOMINAL_DT_S, TARGET_APOGEE_M};
+use controller_in_rust_v3::predictor::{self, Atmosphere, DragModel, State};
+use controller_in_rust_v3::sim::{self, SimConfig};
+use controller_in_rust_v3::types::{Config, Phase};
+
+/// A change to the default simulation.
+type Tweak = fn(&mut SimConfig);
+
+fn recorded_flight() -> Vec<sim::ProfileRow> {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("profiles/fsw_test_flight.csv");
+    sim::load_profile(&path).unwrap()
+}
+
+fn fly_with(f: impl FnOnce(&mut SimConfig)) -> sim::Flight {
+    let mut cfg = SimConfig::default();
+    f(&mut cfg);
+    sim::fly(&cfg)
+}
+
+#[test]
+fn recorded_flight_prediction() {
+    let profile = recorded_flight();
+    let apogee = profile.iter().map(|r| r.altitude).fold(f32::MIN, f32::max);
+    let samples = sim::replay(&profile, DEFAULT_PAD_PRESSURE_PA);
+    let errors = sim::coast_errors(&samples, apogee);
+
+    assert!(errors.len() > 200, "only {} coast frames checked", errors.len());
+    let mean = errors.iter().sum::<f32>() / errors.len() as f32;
+    let worst = errors.iter().fold(0.0f32, |w, e| w.max(e.abs()));
+    assert!(mean.abs() < 10.0, "mean prediction error {mean:.1} m");
+    assert!(worst < 30.0, "worst prediction error {worst:.1} m");
+}
+
+#[test]
+fn nominal_flight_hits_target() {
+    let flight = fly_with(|_| {});
+    let error = flight.apogee - TARGET_APOGEE_M;
+    assert!(error.abs() < 5.0, "apogee error {error:.1} m");
+}
+
+#[test]
+fn model_errors_stay_near_target() {
+    let cases: [(&str, Tweak); 9] = [
+        ("hot motor", |c| c.vehicle.thrust *= 1.05),
+        ("low airframe drag", |c| c.vehicle.body_cda *= 0.8),
+        ("high airframe drag", |c| c.vehicle.body_cda *= 1.2),
+        ("weak brakes", |c| c.vehicle.brake_cda *= 0.7),
+        ("strong brakes", |c| c.vehicle.brake_cda *= 1.3),
+        ("hot day", |c| c.temp_offset_k = 20.0),
+        ("cold day", |c| c.temp_offset_k = -20.0),
+        ("accelerometer gain error", |c| c.accel_scale_error = 0.03),
+        ("no IMU", |c| c.no_imu = true),
+    ];
+    for (name, f) in cases {
+        let error = fly_with(f).apogee - TARGET_APOGEE_M;
+        assert!(error.abs() < 5.0, "{name}: apogee error {error:.1} m");
+    }
+}
+
+#[test]
+fn drag_estimate_matches_flown_drag() {
+    let cases: [(&str, Tweak); 4] = [
+        ("nominal", |_| {}),
+        ("high airframe drag", |c| c.vehicle.body_cda *= 1.2),
+        ("weak brakes", |c| c.vehicle.brake_cda *= 0.7),
+        ("strong brakes, low airframe drag", |c| {
+            c.vehicle.brake_cda *= 1.3;
+            c.vehicle.body_cda *= 0.8;
+        }),
+    ];
+    for (name, f) in cases {
+        let mut cfg = SimConfig::default();
+        f(&mut cfg);
+        let v = cfg.vehicle;
+        let flight = sim::fly(&cfg);
+        // The brakes sit near one position for most of the coast, so only the
+        // drag area at the flown deployment is well determined
+        let errors: Vec<f32> = flight
+            .samples
+            .iter()
+            .filter(|s| s.phase == Phase::Coast && s.t > cfg.pad_s + cfg.boost_phase_s + 2.0 && s.vz > 80.0)
+            .map(|s| s.drag.area(s.brakes) / (v.body_cda + v.brake_cda * s.brakes) - 1.0)
+            .collect();
+        assert!(errors.len() > 100, "{name}: only {} frames checked", errors.len());
+        let mean = errors.iter().sum::<f32>() / errors.len() as f32;
+        assert!(mean.abs() < 0.02, "{name}: mean drag area error {:+.1}%", mean * 100.0);
+    }
+}
+
+#[test]
+fn drag_estimate_needs_imu() {
+    let flight = fly_with(|c| {
+        c.no_imu = true;
+        c.vehicle.body_cda *= 1.2;
+    });
+    assert!(flight.samples.iter().all(|s| s.drag == DragModel::default()));
+}
+
+#[test]
+fn underpowered_flight_never_deploys() {
+    let controlled = fly_with(|c| c.vehicle.thrust *= 0.9);
+    let ballistic = fly_with(|c| {
+        c.vehicle.thrust *= 0.9;
+        c.control = false;
+    });
+    assert!(controlled.apogee < TARGET_APOGEE_M);
+    assert!(controlled.samples.iter().all(|s| s.command == 0.0));
+    assert_eq!(controlled.apogee, ballistic.apogee);
+}
+
+#[test]
+fn brakes_only_move_in_coast_and_within_rate() {
+    let flight = fly_with(|c| c.vehicle.thrust *= 1.05);
+    let step = MAX_DEPLOY_RATE_PER_S * NOMINAL_DT_S + 1e-4;
+    for pair in flight.samples.windows(2) {
+        let s = pair[1];
+        assert!((0.0..=1.0).contains(&s.command), "t={:.2}: command {}", s.t, s.command);
+        if s.phase != Phase::Coast {
+            assert_eq!(s.command, 0.0, "t={:.2}: deployed in {:?}", s.t, s.phase);
+        }
+        assert!((s.command - pair[0].command).abs() <= step, "t={:.2}: slew too fast", s.t);
+    }
+}
+
+#[test]
+fn apogee_falls_with_deployment() {
+    let atm = Atmosphere::default();
+    let drag = DragModel::default();
+    let state = State { alt: 1200.0, vz: 240.0, vh: 15.0 };
+    let apogees: Vec<f32> = (0..=10).map(|i| predictor::apogee(&atm, &drag, &state, i as f32 / 10.0)).collect();
+    assert!(apogees.windows(2).all(|w| w[1] < w[0]), "{apogees:?}");
+
+    let target = 0.5 * (apogees[0] + apogees[10]);
+    let d = predictor::deployment_for(&atm, &drag, &state, target);
+    assert!((predictor::apogee(&atm, &drag, &state, d) - target).abs() < 1.0);
+    assert_eq!(predictor::deployment_for(&atm, &drag, &state, apogees[0] + 1.0), 0.0);
+    assert_eq!(predictor::deployment_for(&atm, &drag, &state, apogees[10] - 1.0), 1.0);
+}
+
+#[test]
+fn configured_target_and_rate_are_followed() {
+    let flight = fly_with(|c| {
+        c.controller.target_apogee_m = 2950.0;
+        c.controller.max_deploy_rate_per_s = 1.0;
+    });
+    let error = flight.apogee - 2950.0;
+    assert!(error.abs() < 5.0, "apogee error {error:.1} m");
+    let step = 1.0 * NOMINAL_DT_S + 1e-4;
+    assert!(flight.samples.windows(2).all(|w| (w[1].command - w[0].command).abs() <= step), "slew above 1.0/s");
+}
+
+#[test]
+fn config_limits() {
+    assert!(Config::default().is_valid());
+    let feet = Config { target_apogee_m: 30_000.0, ..Config::default() };
+    let cm2 = Config { drag: DragModel { body_cda_m2: 79.0, ..DragModel::default() }, ..Config::default() };
+    let nan = Config { max_deploy_rate_per_s: f32::NAN, ..Config::default() };
+    assert!(!feet.is_valid() && !cm2.is_valid() && !nan.is_valid());
+}
+
+#[test]
+fn seeded_runs_repeat() {
+    let cfg = SimConfig { seed: 7, ..SimConfig::default() };
+    assert_eq!(sim::fly(&cfg).apogee, sim::fly(&cfg).apogee);
+}
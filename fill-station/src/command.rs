@@ -127,6 +127,54 @@ pub enum Command {
     /// Disarm the FSW key (force Standby → Startup)
     FswKeyDisarm,
     FswSetBlimsTarget { upwind_lat: f32, upwind_lon: f32, downwind_lat: f32, downwind_lon: f32 },
+    /// Set the airbrake controller config (accepted by the FSW only in Startup)
+    FswSetAirbrakeConfig {
+        target_apogee_m: f32,
+        max_deploy_rate_per_s: f32,
+        min_activation_vel_mps: f32,
+        body_cda_m2: f32,
+        brake_cda_m2: f32,
+    },
+    /// Set the BLiMS controller gains and phase altitudes (accepted by the
+    /// FSW only in Startup)
+    FswSetBlimsConfig {
+        kp: f32,
+        ki: f32,
+        integral_max_in: f32,
+        alt_upwind_ft: f32,
+        alt_neutral_ft: f32,
+        phase_hysteresis_ft: f32,
+        initial_hold_ms: u32,
+    },
+    /// Upload a BLiMS wind profile (accepted by the FSW only in Startup);
+    /// an empty list clears it
+    FswSetWindProfile { layers: Vec<WindLayer> },
+    /// Upload the BLiMS geofence (accepted by the FSW only in Startup); an
+    /// empty list clears it
+    FswSetGeofence { zones: Vec<GeofenceZone> },
+}
+
+/// One BLiMS wind profile layer: altitude (m AGL), direction the wind
+/// blows FROM (degrees), speed (m/s)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct WindLayer {
+    pub altitude_m: f32,
+    pub from_deg: f32,
+    pub speed_ms: f32,
+}
+
+/// One BLiMS geofence polygon: vertices as [lat, lon] decimal degrees
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GeofenceZone {
+    pub kind: GeofenceZoneKind,
+    pub vertices: Vec<[f64; 2]>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GeofenceZoneKind {
+    KeepOut,
+    LandingZone,
 }
 
 /// Response sent back to WebSocket clients after command execution
@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 
 /// Number of comma-separated fields in a `$TELEM,` line, matching the FSW
 /// emitter in `fsw/src/umbilical.rs`. Must be kept in sync on both sides.
-pub const TELEM_FIELD_COUNT: usize = 54;
+pub const TELEM_FIELD_COUNT: usize = 88;
 
 /// FSW telemetry packet parsed from CSV text lines.
 /// The FSW emits lines like: `$TELEM,0,101325.0,25.0,0.0,...,0,0\n`
@@ -70,6 +70,53 @@ pub struct FswTelemetry {
     pub blims_wind_from_deg: f32,
     // CFC boot time
     pub ms_since_boot_cfc: u32,
+    // UTC Unix epoch ms from FSW GPS time sync (0 = not synced)
+    pub utc_ms: u64,
+    // ODrive readback (holds the last reading while a link is down)
+    pub airbrake_measured_deployment: f32,
+    pub airbrake_odrive_state: u8,
+    pub airbrake_odrive_errors: u32,
+    pub airbrake_odrive_vbus: f32,
+    pub airbrake_odrive_ibus: f32,
+    pub blims_measured_brakeline: f32,
+    pub blims_odrive_state: u8,
+    pub blims_odrive_errors: u32,
+    pub blims_odrive_vbus: f32,
+    pub blims_odrive_ibus: f32,
+    // bit0 = airbrake ODrive on UART, bit1 = BLiMS ODrive on UART (0 = RC PWM)
+    pub odrive_link: u8,
+    // 1 = Core 1 airbrake controller output fresh, 0 = stale (airbrakes retracted)
+    pub airbrake_ctrl_ok: u8,
+    // Airbrake controller config in use (set pre-flight with fsw_set_airbrake_config)
+    pub airbrake_target_apogee: f32,
+    pub airbrake_max_rate: f32,
+    pub airbrake_min_vel: f32,
+    pub airbrake_body_cda: f32,
+    pub airbrake_brake_cda: f32,
+    // Core 1 in-flight drag estimate (the configured areas until Coast)
+    pub airbrake_est_body_cda: f32,
+    pub airbrake_est_brake_cda: f32,
+    // BLiMS in-flight wind estimate (0 until the canopy has turned enough)
+    pub blims_wind_est_speed: f32,
+    pub blims_wind_est_from_deg: f32,
+    // BLiMS forecast wind profile loaded with <WP,...> (0 layers = none)
+    pub blims_wind_layers: u8,
+    pub blims_wind_checksum: u32,
+    // BLiMS distance to the active waypoint
+    pub blims_dist_to_target: f32,
+    // BLiMS geofence: FENCE_* flags this cycle, and the fence loaded with <GF,...>
+    pub blims_fence_flags: u8,
+    pub blims_fence_zones: u8,
+    pub blims_fence_checksum: u32,
+    // Payload board link: 0 never heard / 1 up / 2 lost, last acked command
+    // id (1–4 = N1–N4, 5–7 = A1–A3), its reported state, commands unacked
+    pub payload_link: u8,
+    pub payload_last_ack: u8,
+    pub payload_state: u8,
+    pub payload_failed: u8,
+    // Supply rails (V): flight battery and pyro rail (NaN if not measured)
+    pub battery_v: f32,
+    pub pyro_v: f32,
 }
 
 impl FswTelemetry {
@@ -134,6 +181,40 @@ impl FswTelemetry {
             blims_downwind_lon:     fields[51].trim().parse().ok()?,
             blims_wind_from_deg:    fields[52].trim().parse().ok()?,
             ms_since_boot_cfc:      fields[53].trim().parse().ok()?,
+            utc_ms:                 fields[54].trim().parse().ok()?,
+            airbrake_measured_deployment: fields[55].trim().parse().ok()?,
+            airbrake_odrive_state:  fields[56].trim().parse().ok()?,
+            airbrake_odrive_errors: fields[57].trim().parse().ok()?,
+            airbrake_odrive_vbus:   fields[58].trim().parse().ok()?,
+            airbrake_odrive_ibus:   fields[59].trim().parse().ok()?,
+            blims_measured_brakeline: fields[60].trim().parse().ok()?,
+            blims_odrive_state:     fields[61].trim().parse().ok()?,
+            blims_odrive_errors:    fields[62].trim().parse().ok()?,
+            blims_odrive_vbus:      fields[63].trim().parse().ok()?,
+            blims_odrive_ibus:      fields[64].trim().parse().ok()?,
+            odrive_link:            fields[65].trim().parse().ok()?,
+            airbrake_ctrl_ok:       fields[66].trim().parse().ok()?,
+            airbrake_target_apogee: fields[67].trim().parse().ok()?,
+            airbrake_max_rate:      fields[68].trim().parse().ok()?,
+            airbrake_min_vel:       fields[69].trim().parse().ok()?,
+            airbrake_body_cda:      fields[70].trim().parse().ok()?,
+            airbrake_brake_cda:     fields[71].trim().parse().ok()?,
+            airbrake_est_body_cda:  fields[72].trim().parse().ok()?,
+            airbrake_est_brake_cda: fields[73].trim().parse().ok()?,
+            blims_wind_est_speed:   fields[74].trim().parse().ok()?,
+            blims_wind_est_from_deg: fields[75].trim().parse().ok()?,
+            blims_wind_layers:      fields[76].trim().parse().ok()?,
+            blims_wind_checksum:    fields[77].trim().parse().ok()?,
+            blims_dist_to_target:   fields[78].trim().parse().ok()?,
+            blims_fence_flags:      fields[79].trim().parse().ok()?,
+            blims_fence_zones:      fields[80].trim().parse().ok()?,
+            blims_fence_checksum:   fields[81].trim().parse().ok()?,
+            payload_link:           fields[82].trim().parse().ok()?,
+            payload_last_ack:       fields[83].trim().parse().ok()?,
+            payload_state:          fields[84].trim().parse().ok()?,
+            payload_failed:         fields[85].trim().parse().ok()?,
+            battery_v:              fields[86].trim().parse().ok()?,
+            pyro_v:                 fields[87].trim().parse().ok()?,
         })
     }
 
@@ -20,7 +20,7 @@ use tracing_subscriber::fmt;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 use tungstenite::Message;
 
-use crate::command::{ActuatorState, AdcReadings, Command, CommandResponse, UmbilicalReadings};
+use crate::command::{ActuatorState, AdcReadings, Command, CommandResponse, GeofenceZoneKind, UmbilicalReadings};
 #[cfg(any(target_os = "linux", target_os = "android"))]
 use crate::command::ChannelReading;
 use crate::hardware::Hardware;
@@ -71,6 +71,17 @@ const LOADCELL_OFFSET: f32 = -14.9;
 // UMBILICAL CONFIGURATION
 // ============================================================================
 
+/// BLiMS wind profile layers the FSW accepts (MAX_WIND_LAYERS in BLIMS)
+const WIND_PROFILE_MAX_LAYERS: usize = 20;
+
+/// BLiMS geofence limits the FSW accepts (MAX_FENCE_ZONES / MAX_FENCE_VERTICES in BLIMS)
+const GEOFENCE_MAX_ZONES: usize = 8;
+const GEOFENCE_MAX_VERTICES: usize = 16;
+
+/// Gap between `<WP,...>` and `<GF,...>` upload commands. The FSW reads one command per USB
+/// packet and pauses 100 ms after each, so back-to-back writes would merge.
+const UPLOAD_CHUNK_INTERVAL: Duration = Duration::from_millis(150);
+
 /// Serial device path for the umbilical CDC-ACM port
 #[cfg(any(target_os = "linux", target_os = "android"))]
 const UMBILICAL_DEVICE: &str = "/dev/ttyACM0";
@@ -838,6 +849,123 @@ async fn execute_command(
                 }
             }
         }
+        Command::FswSetAirbrakeConfig { target_apogee_m, max_deploy_rate_per_s, min_activation_vel_mps, body_cda_m2, brake_cda_m2 } => {
+            // Range checks live on the FSW (Config::is_valid); only refuse what can't be sent
+            if ![target_apogee_m, max_deploy_rate_per_s, min_activation_vel_mps, body_cda_m2, brake_cda_m2].iter().all(|v| v.is_finite()) {
+                error!("FSW SetAirbrakeConfig rejected: non-finite value");
+                CommandResponse::Error
+            } else {
+                let msg = format!(
+                    "<AK,{:.1},{:.3},{:.1},{:.6},{:.6}>",
+                    target_apogee_m, max_deploy_rate_per_s, min_activation_vel_mps, body_cda_m2, brake_cda_m2
+                );
+                info!("Sending FSW SetAirbrakeConfig: {}", msg);
+                match umb_cmd_tx.try_send(msg) {
+                    Ok(_) => CommandResponse::Success,
+                    Err(e) => { error!("Failed to send FSW command: {}", e); CommandResponse::Error }
+                }
+            }
+        }
+        Command::FswSetBlimsConfig { kp, ki, integral_max_in, alt_upwind_ft, alt_neutral_ft, phase_hysteresis_ft, initial_hold_ms } => {
+            // Range checks live on the FSW (BlimsConfig::is_valid); only refuse what can't be sent
+            if ![kp, ki, integral_max_in, alt_upwind_ft, alt_neutral_ft, phase_hysteresis_ft].iter().all(|v| v.is_finite()) {
+                error!("FSW SetBlimsConfig rejected: non-finite value");
+                CommandResponse::Error
+            } else {
+                let msg = format!(
+                    "<BK,{:.5},{:.5},{:.3},{:.1},{:.1},{:.1},{}>",
+                    kp, ki, integral_max_in, alt_upwind_ft, alt_neutral_ft, phase_hysteresis_ft, initial_hold_ms
+                );
+                info!("Sending FSW SetBlimsConfig: {}", msg);
+                match umb_cmd_tx.try_send(msg) {
+                    Ok(_) => CommandResponse::Success,
+                    Err(e) => { error!("Failed to send FSW command: {}", e); CommandResponse::Error }
+                }
+            }
+        }
+        Command::FswSetWindProfile { layers } => {
+            // Range and ordering checks live on the FSW (WindProfile::from_layers);
+            // only refuse what can't be sent
+            if layers.len() > WIND_PROFILE_MAX_LAYERS {
+                error!("FSW SetWindProfile rejected: {} layers, max {}", layers.len(), WIND_PROFILE_MAX_LAYERS);
+                return CommandResponse::Error;
+            }
+            if !layers.iter().all(|l| [l.altitude_m, l.from_deg, l.speed_ms].iter().all(|v| v.is_finite())) {
+                error!("FSW SetWindProfile rejected: non-finite value");
+                return CommandResponse::Error;
+            }
+            let mut msgs = Vec::with_capacity(layers.len() + 2);
+            if layers.is_empty() {
+                msgs.push("<WP,X>".to_string());
+            } else {
+                msgs.push(format!("<WP,B,{}>", layers.len()));
+                for (i, l) in layers.iter().enumerate() {
+                    msgs.push(format!("<WP,L,{},{},{},{}>", i, l.altitude_m, l.from_deg, l.speed_ms));
+                }
+                msgs.push("<WP,C>".to_string());
+            }
+            info!("Sending FSW SetWindProfile: {} layers in {} commands", layers.len(), msgs.len());
+            // The FSW takes one command per USB packet, so pace them instead
+            // of letting the umbilical task write them back-to-back. Each step
+            // is echoed as a `$WP,...` line in the debug log.
+            let tx = umb_cmd_tx.clone();
+            smol::spawn(async move {
+                for msg in msgs {
+                    if let Err(e) = tx.send(msg).await {
+                        error!("Failed to send FSW command: {}", e);
+                        return;
+                    }
+                    Timer::after(UPLOAD_CHUNK_INTERVAL).await;
+                }
+            }).detach();
+            CommandResponse::Success
+        }
+        Command::FswSetGeofence { zones } => {
+            // Polygon checks live on the FSW (Geofence::from_zones); only
+            // refuse what can't be sent
+            if zones.len() > GEOFENCE_MAX_ZONES {
+                error!("FSW SetGeofence rejected: {} zones, max {}", zones.len(), GEOFENCE_MAX_ZONES);
+                return CommandResponse::Error;
+            }
+            if zones.iter().any(|z| !(3..=GEOFENCE_MAX_VERTICES).contains(&z.vertices.len())) {
+                error!("FSW SetGeofence rejected: zones need 3 to {} vertices", GEOFENCE_MAX_VERTICES);
+                return CommandResponse::Error;
+            }
+            if !zones.iter().flat_map(|z| &z.vertices).all(|v| v.iter().all(|x| x.is_finite())) {
+                error!("FSW SetGeofence rejected: non-finite vertex");
+                return CommandResponse::Error;
+            }
+            let mut msgs = Vec::new();
+            if zones.is_empty() {
+                msgs.push("<GF,X>".to_string());
+            } else {
+                msgs.push(format!("<GF,B,{}>", zones.len()));
+                for (i, z) in zones.iter().enumerate() {
+                    let kind = match z.kind {
+                        GeofenceZoneKind::KeepOut => 'K',
+                        GeofenceZoneKind::LandingZone => 'L',
+                    };
+                    msgs.push(format!("<GF,Z,{},{},{}>", i, kind, z.vertices.len()));
+                    for (j, [lat, lon]) in z.vertices.iter().enumerate() {
+                        msgs.push(format!("<GF,V,{},{},{:.7},{:.7}>", i, j, lat, lon));
+                    }
+                }
+                msgs.push("<GF,C>".to_string());
+            }
+            info!("Sending FSW SetGeofence: {} zones in {} commands", zones.len(), msgs.len());
+            // Paced like the wind profile; each step is echoed as `$GF,...`
+            let tx = umb_cmd_tx.clone();
+            smol::spawn(async move {
+                for msg in msgs {
+                    if let Err(e) = tx.send(msg).await {
+                        error!("Failed to send FSW command: {}", e);
+                        return;
+                    }
+                    Timer::after(UPLOAD_CHUNK_INTERVAL).await;
+                }
+            }).detach();
+            CommandResponse::Success
+        }
     }
 }
 
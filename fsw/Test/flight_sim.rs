@@ -25,6 +25,7 @@ pub async fn simulate_flight_simple(flight_loop: &mut FlightLoop) {
     Timer::after_secs(2).await;
     log::info!("[SIM] Testing Startup -> Standby");
     flight_loop.set_umbilical(true); // Umbilical must be connected now
+    flight_loop.set_flash_wiped(true); // Flash wiped over the umbilical
     flight_loop.set_key_switch(true); // And key switch armed
     flight_loop.simulate_cycle().await;
 
@@ -81,7 +82,7 @@ pub async fn simulate_flight_simple(flight_loop: &mut FlightLoop) {
         if _i % 20 == 0 {
             log::info!("[SIM] Current Simulated Altitude: {:.2}m", alt);
         }
-        flight_loop.set_altitude(*alt as f32);
+        flight_loop.set_altitude(*alt);
         Timer::after_millis(10).await; // Reduced delay to speed up simulation loop
 
         // Run logic for this altitude
@@ -93,7 +94,7 @@ pub async fn simulate_flight_simple(flight_loop: &mut FlightLoop) {
 
         // ASCENT Checks
         if mode == FlightMode::Ascent {
-            if flight_loop.alt_armed && *alt as f32 > constants::ARMING_ALTITUDE {
+            if flight_loop.alt_armed && *alt > constants::ARMING_ALTITUDE {
                 // Altimeter arming verification happens continuously
             }
 
@@ -137,14 +138,12 @@ pub async fn simulate_flight_simple(flight_loop: &mut FlightLoop) {
         }
 
         // MAIN Checks
-        if mode == FlightMode::MainDeployed {
-            if !main_deployed_verified {
-                log::info!(
-                    "\n[SIM] SUCCESS: Transitioned to MainDeployed at {:.2}m",
-                    alt
-                );
-                main_deployed_verified = true;
-            }
+        if mode == FlightMode::MainDeployed && !main_deployed_verified {
+            log::info!(
+                "\n[SIM] SUCCESS: Transitioned to MainDeployed at {:.2}m",
+                alt
+            );
+            main_deployed_verified = true;
         }
     }
 
@@ -292,6 +291,7 @@ pub async fn simulate_stability_scenarios(flight_loop: &mut FlightLoop) {
 
     // 2. Standby Stability
     log::info!("[STABILITY SIM] Testing Standby Stability");
+    flight_loop.set_flash_wiped(true);
     flight_loop.set_key_switch(true);
     flight_loop.set_umbilical(true); // Umbilical must be connected to transition to Standby
     flight_loop.set_launch_command(false);
@@ -334,7 +334,8 @@ pub async fn simulate_stability_scenarios(flight_loop: &mut FlightLoop) {
 
     // 4. Ascent Stability
     log::info!("[STABILITY SIM] Testing Ascent Stability");
-    // Get back to Ascent
+    // Get back to Ascent (key-low in Standby cleared the wipe)
+    flight_loop.set_flash_wiped(true);
     flight_loop.set_key_switch(true);
     flight_loop.simulate_cycle().await; // Standby
     flight_loop.set_umbilical(true);
@@ -468,12 +469,124 @@ pub async fn simulate_extra_features(flight_loop: &mut FlightLoop) {
 
     Timer::after_millis(100).await;
 
+    // 3. Airbrake ground test profiles
+    log::info!("[EXTRA FEATURE SIM] Testing airbrake ground test lockout and profiles");
+    simulate_airbrake_ground_tests(flight_loop).await;
+
+    Timer::after_millis(100).await;
+
+    // 4. Airbrake controller config
+    log::info!("[EXTRA FEATURE SIM] Testing airbrake controller config lockout and persistence");
+    simulate_airbrake_config(flight_loop).await;
+
     Timer::after_millis(100).await;
 
     log::info!("  EXTRA FEATURES SIMULATION FULLY COMPLETE ");
     Timer::after_millis(1000).await; // Flush logs before stopping
 }
 
+async fn simulate_airbrake_ground_tests(flight_loop: &mut FlightLoop) {
+    use crate::airbrake_test::TestProfile;
+    use crate::umbilical::{self, UmbilicalCommand};
+
+    let deployment = |fl: &FlightLoop| fl.flight_state.airbrake_system.current_deployment();
+    let check = |ok: bool, what: &str| {
+        if ok {
+            log::info!("[EXTRA FEATURE SIM] SUCCESS: {}", what);
+        } else {
+            log::error!("[EXTRA FEATURE SIM] FAILED: {}", what);
+        }
+    };
+    let step = TestProfile::Step { target: 0.5 };
+
+    flight_loop.set_flight_mode(FlightMode::Startup);
+    umbilical::push_command(UmbilicalCommand::AirbrakeTest(step));
+    flight_loop.simulate_cycle().await;
+    check(!flight_loop.airbrake_test_running() && deployment(flight_loop) == 0.0, "Airbrake step rejected without test mode");
+
+    umbilical::push_command(UmbilicalCommand::AirbrakeTestEnable);
+    umbilical::push_command(UmbilicalCommand::AirbrakeTest(step));
+    flight_loop.simulate_cycle().await;
+    check(flight_loop.airbrake_test_running() && deployment(flight_loop) == 0.5, "Airbrake step to 50% in test mode");
+
+    umbilical::push_command(UmbilicalCommand::RetractAirbrakes);
+    flight_loop.simulate_cycle().await;
+    check(!flight_loop.airbrake_test_running() && deployment(flight_loop) == 0.0, "Airbrake retract aborts the step");
+
+    // 1.0/s sweep: peaks at 1 s, back to 0 at 2 s
+    umbilical::push_command(UmbilicalCommand::AirbrakeTest(TestProfile::Sweep { rate: 1.0 }));
+    flight_loop.simulate_cycle().await;
+    Timer::after_millis(500).await;
+    flight_loop.simulate_cycle().await;
+    let rising = deployment(flight_loop);
+    Timer::after_millis(1000).await;
+    flight_loop.simulate_cycle().await;
+    let falling = deployment(flight_loop);
+    check((rising - 0.5).abs() < 0.05 && (falling - 0.5).abs() < 0.05, "Airbrake sweep passes 50% going out and coming back");
+    Timer::after_millis(600).await;
+    flight_loop.simulate_cycle().await;
+    check(!flight_loop.airbrake_test_running() && deployment(flight_loop) == 0.0, "Airbrake sweep ends retracted");
+
+    umbilical::push_command(UmbilicalCommand::AirbrakeTest(TestProfile::Chirp { f0_hz: 1.0, f1_hz: 3.0, duration_s: 2.0 }));
+    let (mut lo, mut hi) = (1.0f32, 0.0f32);
+    flight_loop.simulate_cycle().await;
+    while flight_loop.airbrake_test_running() {
+        lo = lo.min(deployment(flight_loop));
+        hi = hi.max(deployment(flight_loop));
+        Timer::after_millis(constants::MAIN_LOOP_DELAY_MS).await;
+        flight_loop.simulate_cycle().await;
+    }
+    let band = constants::AIRBRAKE_TEST_CHIRP_CENTER - constants::AIRBRAKE_TEST_CHIRP_AMPLITUDE
+        ..=constants::AIRBRAKE_TEST_CHIRP_CENTER + constants::AIRBRAKE_TEST_CHIRP_AMPLITUDE;
+    check(band.contains(&lo) && band.contains(&hi) && hi - lo > 0.5, "Airbrake chirp stays in band and swings");
+
+    umbilical::push_command(UmbilicalCommand::AirbrakeTest(TestProfile::Chirp { f0_hz: 1.0, f1_hz: 20.0, duration_s: 2.0 }));
+    flight_loop.simulate_cycle().await;
+    check(!flight_loop.airbrake_test_running(), "Airbrake chirp above the loop's limit rejected");
+
+    // Leaving Startup aborts the profile and clears test mode
+    umbilical::push_command(UmbilicalCommand::AirbrakeTest(step));
+    flight_loop.simulate_cycle().await;
+    flight_loop.set_flight_mode(FlightMode::Standby);
+    flight_loop.simulate_cycle().await;
+    flight_loop.set_flight_mode(FlightMode::Startup);
+    umbilical::push_command(UmbilicalCommand::AirbrakeTest(step));
+    flight_loop.simulate_cycle().await;
+    check(!flight_loop.airbrake_test_running() && deployment(flight_loop) == 0.0, "Airbrake test mode cleared on leaving Startup");
+}
+
+async fn simulate_airbrake_config(flight_loop: &mut FlightLoop) {
+    use crate::umbilical::{self, UmbilicalCommand};
+    use controller_in_rust_v3::Config;
+
+    let check = |ok: bool, what: &str| {
+        if ok {
+            log::info!("[EXTRA FEATURE SIM] SUCCESS: {}", what);
+        } else {
+            log::error!("[EXTRA FEATURE SIM] FAILED: {}", what);
+        }
+    };
+    let original = flight_loop.flight_state.airbrake_config;
+    let config = Config { target_apogee_m: 2500.0, max_deploy_rate_per_s: 1.5, ..Config::default() };
+
+    flight_loop.set_flight_mode(FlightMode::Startup);
+    umbilical::push_command(UmbilicalCommand::SetAirbrakeConfig(Config { target_apogee_m: 50.0, ..config }));
+    flight_loop.simulate_cycle().await;
+    check(flight_loop.flight_state.airbrake_config == original, "Airbrake config with out-of-range target rejected");
+
+    flight_loop.set_flight_mode(FlightMode::Standby);
+    umbilical::push_command(UmbilicalCommand::SetAirbrakeConfig(config));
+    flight_loop.simulate_cycle().await;
+    check(flight_loop.flight_state.airbrake_config == original, "Airbrake config rejected outside Startup");
+
+    flight_loop.set_flight_mode(FlightMode::Startup);
+    umbilical::push_command(UmbilicalCommand::SetAirbrakeConfig(config));
+    flight_loop.simulate_cycle().await;
+    check(flight_loop.flight_state.airbrake_config == config, "Airbrake config applied in Startup");
+    let stored = flight_loop.flight_state.stored_airbrake_config().await;
+    check(stored == Some(config), "Airbrake config persisted to flash");
+}
+
 // Runs a test for Onboard QSPI Flash storage
 pub async fn simulate_flash_storage(flight_loop: &mut FlightLoop) {
     log::info!("\n--- STARTING QSPI FLASH SIMULATION ---");
@@ -628,7 +741,7 @@ pub async fn simulate_real_flight(flight_loop: &mut FlightLoop) {
                     flight_loop.sim_accel_y_override = Some(accel_ms2);
                     flight_loop.sim_accel_z_override = Some(accel_ms2);
 
-                    if alt_index % 50 == 0 {
+                    if alt_index.is_multiple_of(50) {
                         log::info!(
                             "[SIM] alt: {:.1}m  vel_up: {:.1}m/s  accel: {:.2}m/s2  idx: {}  mode: {}",
                             alt_m, vel_up_ms, accel_ms2, alt_index,
@@ -648,6 +761,24 @@ pub async fn simulate_real_flight(flight_loop: &mut FlightLoop) {
         Timer::after_millis(constants::MAIN_LOOP_DELAY_MS).await;
     }
 
+    // On the ground: hold the last altitude until landing is detected and
+    // the apogee readout has had time to play
+    if flight_loop.flight_state.flight_mode == FlightMode::MainDeployed {
+        flight_loop.sim_vel_d_override = Some(0.0);
+        flight_loop.sim_accel_y_override = Some(G_TO_MS2);
+        flight_loop.sim_accel_z_override = Some(G_TO_MS2);
+        let on_ground = Instant::now();
+        while on_ground.elapsed().as_millis() < constants::LANDED_STILL_MS + 15_000 {
+            flight_loop.execute().await;
+            Timer::after_millis(constants::MAIN_LOOP_DELAY_MS).await;
+        }
+        if flight_loop.landed() {
+            log::info!("[SIM] SUCCESS: Landing detected, apogee {} ft", flight_loop.apogee_ft());
+        } else {
+            log::error!("[SIM] FAILED: Landing not detected on the ground");
+        }
+    }
+
     flight_loop.sim_altitude_override = None;
     flight_loop.sim_vel_d_override = None;
     flight_loop.sim_key_armed_override = None;
@@ -709,12 +840,13 @@ pub async fn simulate_blims_descent(flight_loop: &mut FlightLoop) {
         if i % 20 == 0 {
             let p = &flight_loop.flight_state.packet;
             log::info!(
-                "[BLIMS SIM] i={:4}  alt={:.1}ft  brakeline={:.4}in  phase={}  bearing={:.1}deg",
+                "[BLIMS SIM] i={:4}  alt={:.1}ft  brakeline={:.4}in  phase={}  bearing={:.1}deg  dist={:.0}m",
                 i,
                 alt_ft,
                 p.blims_brakeline_diff,
                 p.blims_phase_id,
                 p.blims_bearing,
+                p.blims_dist_to_target,
             );
         }
 
@@ -811,13 +943,28 @@ pub async fn simulate_launch_sequence(flight_loop: &mut FlightLoop) {
         flight_loop.simulate_cycle().await;
     }
 
-    // FinalVent no longer exists as a LaunchStage — SV now reopens via the one-shot
-    // recovery vent in check_transitions when entering DrogueDeployed/MainDeployed/Fault.
+    if flight_loop.flight_state.flight_mode == FlightMode::DrogueDeployed {
+        log::info!("[LAUNCH SIM] SUCCESS: Apogee triggered DrogueDeployed.");
+    } else {
+        log::error!(
+            "[LAUNCH SIM] FAILED: Apogee did not trigger DrogueDeployed. Mode: {:?}",
+            flight_loop.flight_state.flight_mode
+        );
+    }
+
+    // FinalVent no longer exists as a LaunchStage — SV reopens DROGUE_DEPLOY_DELAY_MS
+    // after drogue entry. Hold above main-deploy altitude so main doesn't fire first.
+    flight_loop.set_altitude(constants::MAIN_DEPLOY_ALTITUDE + 100.0);
+    let drogue_wait = Instant::now();
+    while drogue_wait.elapsed().as_millis() < constants::DROGUE_DEPLOY_DELAY_MS + 100 {
+        flight_loop.simulate_cycle().await;
+        Timer::after_millis(50).await;
+    }
     if flight_loop.flight_state.flight_mode == FlightMode::DrogueDeployed && flight_loop.sv_open {
-        log::info!("[LAUNCH SIM] SUCCESS: Apogee triggered DrogueDeployed and recovery vent opened SV.");
+        log::info!("[LAUNCH SIM] SUCCESS: SV opened after drogue deploy delay.");
     } else {
         log::error!(
-            "[LAUNCH SIM] FAILED: Apogee did not open SV. Mode: {:?}, SV: {}",
+            "[LAUNCH SIM] FAILED: SV not open after drogue deploy delay. Mode: {:?}, SV: {}",
             flight_loop.flight_state.flight_mode,
             flight_loop.sv_open
         );
@@ -831,6 +978,11 @@ pub async fn simulate_launch_sequence(flight_loop: &mut FlightLoop) {
 pub async fn simulate_payload_commands(flight_loop: &mut FlightLoop) {
     log::info!("\n--- STARTING PAYLOAD COMMAND SIMULATION ---");
 
+    // Two cycles from boot for the payload board to ack the HELLO, so the
+    // commands below go out as soon as they are sent
+    flight_loop.simulate_cycle().await;
+    flight_loop.simulate_cycle().await;
+
     // 1. Test Command::N1 in Startup
     log::info!("[SIM] Testing N1 command in Startup mode...");
     flight_loop.set_flight_mode(FlightMode::Startup);
@@ -903,5 +1055,24 @@ pub async fn simulate_payload_commands(flight_loop: &mut FlightLoop) {
     flight_loop.simulate_cycle().await;
     // (Logs confirm "UMBILICAL: Sent N4")
 
+    // Each command above goes out as a framed CMD; the payload board must
+    // have acknowledged every one, ending with the umbilical N4. The ASCII
+    // link gets no ACKs to check.
+    #[cfg(feature = "payload_frames")]
+    {
+        let payload = &flight_loop.flight_state.payload;
+        let last_ack = payload.last_acked().map(|c| c.name());
+        if payload.pending() == 0 && payload.failed() == 0 && last_ack == Some("N4") {
+            log::info!("[SIM] SUCCESS: Payload acknowledged every command (last: N4).");
+        } else {
+            log::error!(
+                "[SIM] FAILED: Payload commands not all acknowledged ({} pending, {} failed, last ACK {:?})",
+                payload.pending(),
+                payload.failed(),
+                last_ack
+            );
+        }
+    }
+
     log::info!("--- PAYLOAD COMMAND SIMULATION COMPLETE ---\n");
 }
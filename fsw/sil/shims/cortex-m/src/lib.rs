@@ -0,0 +1,14 @@
+//! Host stand-in for `cortex-m`. Only `SCB::sys_reset()` is used by the FSW.
+
+pub mod peripheral {
+    pub struct SCB;
+
+    impl SCB {
+        /// A reboot can't be simulated in-process (statics, flash image and
+        /// sim clock would all have to restart), so treat it as the end of
+        /// the run. Scenarios never reboot on the expected path.
+        pub fn sys_reset() -> ! {
+            panic!("SCB::sys_reset() requested — reboot is not simulated");
+        }
+    }
+}
@@ -0,0 +1,358 @@
+//! Host stand-in for the slice of `embassy-rp` the flight software and BLiMS
+//! touch: GPIO, PWM, UART and the watchdog.
+//!
+//! Constructors take a plain GPIO number (or UART index) instead of a `Peri`
+//! token; everything the FSW calls afterwards has the real signature. Pin
+//! levels, PWM duty and UART traffic are recorded in process-wide tables so
+//! the SIL harness can drive inputs and observe actuators.
+
+pub mod gpio {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    /// RP2350B GPIO count.
+    pub const PIN_COUNT: usize = 48;
+
+    static LEVELS: [AtomicBool; PIN_COUNT] = [const { AtomicBool::new(false) }; PIN_COUNT];
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum Level {
+        Low,
+        High,
+    }
+
+    impl From<bool> for Level {
+        fn from(high: bool) -> Self {
+            if high { Level::High } else { Level::Low }
+        }
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum Pull {
+        None,
+        Up,
+        Down,
+    }
+
+    /// Current level of a pin, whether driven by an `Output` or set by the
+    /// harness with [`set_input`].
+    pub fn level(pin: u8) -> bool {
+        LEVELS[pin as usize].load(Ordering::Relaxed)
+    }
+
+    /// Drives an input pin from the harness (arming switches, timepulse).
+    pub fn set_input(pin: u8, high: bool) {
+        LEVELS[pin as usize].store(high, Ordering::Relaxed);
+    }
+
+    pub struct Output<'d> {
+        pin: u8,
+        _p: core::marker::PhantomData<&'d ()>,
+    }
+
+    impl Output<'_> {
+        pub fn new(pin: u8, initial: Level) -> Self {
+            set_input(pin, initial == Level::High);
+            Self { pin, _p: core::marker::PhantomData }
+        }
+
+        pub fn set_high(&mut self) {
+            set_input(self.pin, true);
+        }
+
+        pub fn set_low(&mut self) {
+            set_input(self.pin, false);
+        }
+
+        pub fn set_level(&mut self, level: Level) {
+            set_input(self.pin, level == Level::High);
+        }
+
+        pub fn toggle(&mut self) {
+            set_input(self.pin, !level(self.pin));
+        }
+
+        pub fn is_set_high(&self) -> bool {
+            level(self.pin)
+        }
+
+        pub fn is_set_low(&self) -> bool {
+            !level(self.pin)
+        }
+    }
+
+    pub struct Input<'d> {
+        pin: u8,
+        _p: core::marker::PhantomData<&'d ()>,
+    }
+
+    impl Input<'_> {
+        pub fn new(pin: u8, pull: Pull) -> Self {
+            match pull {
+                Pull::Up => set_input(pin, true),
+                Pull::Down => set_input(pin, false),
+                Pull::None => {}
+            }
+            Self { pin, _p: core::marker::PhantomData }
+        }
+
+        pub fn is_high(&self) -> bool {
+            level(self.pin)
+        }
+
+        pub fn is_low(&self) -> bool {
+            !level(self.pin)
+        }
+
+        /// Resolves on the next low→high transition. The sim clock only
+        /// advances when every task is idle, so this polls on a 1 ms timer.
+        pub async fn wait_for_rising_edge(&mut self) {
+            let mut last = self.is_high();
+            loop {
+                embassy_time::Timer::after_millis(1).await;
+                let now = self.is_high();
+                if now && !last {
+                    return;
+                }
+                last = now;
+            }
+        }
+    }
+}
+
+pub mod pwm {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use crate::gpio::PIN_COUNT;
+
+    static DUTY: [AtomicU32; PIN_COUNT] = [const { AtomicU32::new(0) }; PIN_COUNT];
+
+    /// Last duty cycle written to a PWM pin, as a fraction of the period.
+    pub fn duty(pin: u8) -> f32 {
+        f32::from_bits(DUTY[pin as usize].load(Ordering::Relaxed))
+    }
+
+    fn set_duty(pin: u8, fraction: f32) {
+        DUTY[pin as usize].store(fraction.to_bits(), Ordering::Relaxed);
+    }
+
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
+    pub struct Config {
+        pub top: u16,
+        pub divider: u8,
+        pub compare_a: u16,
+        pub compare_b: u16,
+        pub invert_a: bool,
+        pub invert_b: bool,
+        pub phase_correct: bool,
+        pub enable: bool,
+    }
+
+    impl Default for Config {
+        fn default() -> Self {
+            Self {
+                top: 0xFFFF,
+                divider: 1,
+                compare_a: 0,
+                compare_b: 0,
+                invert_a: false,
+                invert_b: false,
+                phase_correct: false,
+                enable: true,
+            }
+        }
+    }
+
+    #[derive(Clone, Copy)]
+    enum Channel {
+        A,
+        B,
+    }
+
+    pub struct Pwm<'d> {
+        pin: u8,
+        channel: Channel,
+        config: Config,
+        _p: core::marker::PhantomData<&'d ()>,
+    }
+
+    impl Pwm<'_> {
+        pub fn new_output_a(pin: u8, config: Config) -> Self {
+            Self::new(pin, Channel::A, config)
+        }
+
+        pub fn new_output_b(pin: u8, config: Config) -> Self {
+            Self::new(pin, Channel::B, config)
+        }
+
+        fn new(pin: u8, channel: Channel, config: Config) -> Self {
+            let mut pwm = Self { pin, channel, config: Config::default(), _p: core::marker::PhantomData };
+            pwm.set_config(&config);
+            pwm
+        }
+
+        pub fn set_config(&mut self, config: &Config) {
+            self.config = config.clone();
+            let compare = match self.channel {
+                Channel::A => config.compare_a,
+                Channel::B => config.compare_b,
+            };
+            set_duty(self.pin, compare as f32 / (config.top as f32 + 1.0));
+        }
+    }
+
+    impl embedded_hal::pwm::ErrorType for Pwm<'_> {
+        type Error = core::convert::Infallible;
+    }
+
+    impl embedded_hal::pwm::SetDutyCycle for Pwm<'_> {
+        fn max_duty_cycle(&self) -> u16 {
+            self.config.top.max(1)
+        }
+
+        fn set_duty_cycle(&mut self, duty: u16) -> Result<(), Self::Error> {
+            match self.channel {
+                Channel::A => self.config.compare_a = duty,
+                Channel::B => self.config.compare_b = duty,
+            }
+            set_duty(self.pin, duty as f32 / self.max_duty_cycle() as f32);
+            Ok(())
+        }
+    }
+}
+
+pub mod uart {
+    use core::future::poll_fn;
+    use core::task::{Poll, Waker};
+    use std::collections::VecDeque;
+    use std::sync::Mutex;
+
+    /// UART0 (payload) and UART1 (radio).
+    pub const PORT_COUNT: usize = 2;
+
+    struct Port {
+        rx: VecDeque<u8>,
+        rx_waker: Option<Waker>,
+        tx: Vec<u8>,
+    }
+
+    static PORTS: [Mutex<Port>; PORT_COUNT] = [const {
+        Mutex::new(Port { rx: VecDeque::new(), rx_waker: None, tx: Vec::new() })
+    }; PORT_COUNT];
+
+    /// Queues bytes for the FSW to read, as if sent by the far end.
+    pub fn inject_rx(port: usize, data: &[u8]) {
+        let mut p = PORTS[port].lock().unwrap();
+        p.rx.extend(data);
+        if let Some(w) = p.rx_waker.take() {
+            w.wake();
+        }
+    }
+
+    /// Drains and returns everything the FSW has written to the port.
+    pub fn take_tx(port: usize) -> Vec<u8> {
+        core::mem::take(&mut PORTS[port].lock().unwrap().tx)
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum Error {
+        Overrun,
+        Break,
+        Parity,
+        Framing,
+    }
+
+    pub struct Async;
+    pub struct Blocking;
+
+    pub struct UartTx<'d, M> {
+        port: usize,
+        _p: core::marker::PhantomData<(&'d (), M)>,
+    }
+
+    impl<M> UartTx<'_, M> {
+        pub async fn write(&mut self, buffer: &[u8]) -> Result<(), Error> {
+            PORTS[self.port].lock().unwrap().tx.extend_from_slice(buffer);
+            Ok(())
+        }
+    }
+
+    pub struct UartRx<'d, M> {
+        port: usize,
+        _p: core::marker::PhantomData<(&'d (), M)>,
+    }
+
+    impl<M> UartRx<'_, M> {
+        /// Fills `buffer` completely, waiting for injected bytes as needed.
+        pub async fn read(&mut self, buffer: &mut [u8]) -> Result<(), Error> {
+            let mut filled = 0;
+            poll_fn(|cx| {
+                let mut p = PORTS[self.port].lock().unwrap();
+                while filled < buffer.len() {
+                    match p.rx.pop_front() {
+                        Some(b) => {
+                            buffer[filled] = b;
+                            filled += 1;
+                        }
+                        None => {
+                            p.rx_waker = Some(cx.waker().clone());
+                            return Poll::Pending;
+                        }
+                    }
+                }
+                Poll::Ready(Ok(()))
+            })
+            .await
+        }
+    }
+
+    pub struct Uart<'d, M> {
+        tx: UartTx<'d, M>,
+        rx: UartRx<'d, M>,
+    }
+
+    impl<'d, M> Uart<'d, M> {
+        pub fn new(port: usize) -> Self {
+            Self {
+                tx: UartTx { port, _p: core::marker::PhantomData },
+                rx: UartRx { port, _p: core::marker::PhantomData },
+            }
+        }
+
+        pub async fn write(&mut self, buffer: &[u8]) -> Result<(), Error> {
+            self.tx.write(buffer).await
+        }
+
+        pub async fn read(&mut self, buffer: &mut [u8]) -> Result<(), Error> {
+            self.rx.read(buffer).await
+        }
+
+        pub fn split(self) -> (UartTx<'d, M>, UartRx<'d, M>) {
+            (self.tx, self.rx)
+        }
+    }
+}
+
+pub mod watchdog {
+    use embassy_time::Duration;
+
+    /// No-op watchdog: the sim clock never stalls, so there is nothing to
+    /// reset from.
+    pub struct Watchdog;
+
+    impl Watchdog {
+        pub fn new() -> Self {
+            Watchdog
+        }
+
+        pub fn start(&mut self, _period: Duration) {}
+
+        pub fn feed(&mut self, _period: Duration) {}
+    }
+
+    impl Default for Watchdog {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+}
@@ -0,0 +1,187 @@
+//! In-process bench for the library-level tests: boots the FSW on in-memory
+//! flash against the simulated world and steps the flight loop one cycle at
+//! a time, so a test can check `FlightState` and the packet directly instead
+//! of parsing what the `fsw-sil` binary prints. Tests are ordinary
+//! `#[test]` functions that assert from inside their scenario task.
+
+use std::process::Command;
+
+use embassy_executor::Spawner;
+use embassy_futures::select::select;
+use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+use embassy_sync::signal::Signal;
+use embassy_time::{Instant, Timer};
+
+use crate::constants::{self, TEST_ACCS_LST, TEST_ALTS_LST};
+use crate::flight_loop::FlightLoop;
+use crate::state::FlightMode;
+use crate::umbilical::{self, UmbilicalCommand};
+use crate::{airbrake_task, buzzer, flash, ground, module, payload, radio, runtime, world};
+
+const M_PER_FT: f64 = 1.0 / 3.28084;
+const G_MS2: f32 = 9.81;
+
+/// Set in the copy of the test binary that [`run`] starts for one test
+const CHILD_ENV: &str = "FSW_SIL_BENCH_CHILD";
+
+static STOP_CORE1: Signal<CriticalSectionRawMutex, ()> = Signal::new();
+
+/// Runs `init` under the sim runtime with everything the flight loop talks
+/// to besides itself: Core 1's airbrake controller, the ODrive tasks, a
+/// ground station that only listens, and the buzzer and radio listeners.
+/// The test's own task must call `runtime::finish()` when it's done.
+///
+/// The sim clock, the executor's task pools and the FSW's statics are
+/// process-wide, so each test runs in its own copy of the test binary,
+/// filtered to the calling `#[test]`; a panic there fails the caller with
+/// the child's message.
+pub fn run(max_sim_ms: u64, init: impl FnOnce(Spawner)) {
+    if std::env::var_os(CHILD_ENV).is_none() {
+        return run_in_child();
+    }
+    let result = runtime::run(max_sim_ms, |spawner| {
+        spawner.spawn(core1_task()).unwrap();
+        module::spawn_odrive_tasks(spawner);
+        spawner.spawn(ground::ground_station_task(ground::Script::Listen, None, None, None, None, Vec::new())).unwrap();
+        spawner.spawn(buzzer::listen_task()).unwrap();
+        spawner.spawn(radio::listen_task()).unwrap();
+        init(spawner);
+    });
+    if let Err(e) = result {
+        panic!("run aborted: {e}");
+    }
+}
+
+/// Re-runs the calling test alone in a fresh process. libtest names each
+/// test's thread after the test.
+fn run_in_child() {
+    let thread = std::thread::current();
+    let test = thread.name().filter(|&n| n != "main").expect("bench::run must be called from a #[test]");
+    let out = Command::new(std::env::current_exe().unwrap())
+        .args([test, "--exact", "--nocapture", "--test-threads=1"])
+        .env(CHILD_ENV, "1")
+        .output()
+        .expect("failed to re-run the test binary");
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(stdout.contains("running 1 test"), "{test} not found in its own test binary:\n{stdout}");
+    if !out.status.success() {
+        panic!("{}", String::from_utf8_lossy(&out.stderr).trim());
+    }
+}
+
+/// Core 1's airbrake controller until [`stop_core1`].
+#[embassy_executor::task]
+async fn core1_task() {
+    select(airbrake_task::run(), STOP_CORE1.wait()).await;
+}
+
+/// Drops the Core 1 airbrake controller so it stops answering, as a hung or
+/// crashed Core 1 would.
+pub fn stop_core1() {
+    STOP_CORE1.signal(());
+}
+
+/// Powers the board up on the pad with empty flash.
+pub async fn boot() -> FlightLoop {
+    flash::open_in_memory();
+    world::set(world::World::PAD);
+    payload::reset();
+    FlightLoop::new(module::init_flight_state().await)
+}
+
+/// Power-cycles the board, keeping whatever is on flash.
+pub async fn reboot() -> FlightLoop {
+    FlightLoop::new(module::init_flight_state().await)
+}
+
+/// One flight-loop cycle as the firmware main loop runs it.
+async fn cycle(flight_loop: &mut FlightLoop) {
+    flight_loop.flight_state.cycle_count += 1;
+    flight_loop.execute().await;
+    Timer::after_millis(flight_loop.loop_delay_ms()).await;
+}
+
+/// Runs `n` flight-loop cycles with the umbilical connected.
+pub async fn cycles(flight_loop: &mut FlightLoop, n: u32) {
+    for _ in 0..n {
+        umbilical::inject_heartbeat();
+        cycle(flight_loop).await;
+    }
+}
+
+/// Wipes flash, key-arms and sends `<L>` as the ground station does for
+/// `real_flight`. Any config the test wants on board must already be
+/// queued; panics if the board doesn't reach Ascent.
+pub async fn launch(flight_loop: &mut FlightLoop) {
+    send(UmbilicalCommand::WipeFlash);
+    send(UmbilicalCommand::KeyArm);
+    for _ in 0..5 * 20 {
+        if flight_loop.flight_state.flight_mode == FlightMode::Standby {
+            break;
+        }
+        cycles(flight_loop, 1).await;
+    }
+    send(UmbilicalCommand::Launch);
+    cycles(flight_loop, 1).await;
+    assert_eq!(flight_loop.flight_state.flight_mode, FlightMode::Ascent, "launch");
+}
+
+/// The flight `simulate_real_flight` flies (`TEST_ALTS_LST` in ft,
+/// `TEST_ACCS_LST` in g), one sample per cycle, umbilical pulled.
+#[derive(Default)]
+pub struct RecordedFlight {
+    next: usize,
+    prev_alt_m: f64,
+}
+
+impl RecordedFlight {
+    /// Feeds the next sample and runs a cycle on it; false once the record
+    /// has run out.
+    pub async fn step(&mut self, flight_loop: &mut FlightLoop) -> bool {
+        let (Some(&alt_ft), Some(&accel_g)) = (TEST_ALTS_LST.get(self.next), TEST_ACCS_LST.get(self.next)) else {
+            return false;
+        };
+        let dt_s = constants::MAIN_LOOP_DELAY_MS as f64 / 1000.0;
+        let alt_m = alt_ft as f64 * M_PER_FT;
+        let accel = accel_g as f32 * G_MS2;
+        flight_loop.sim_altitude_override = Some(alt_m as f32);
+        flight_loop.sim_vel_d_override = Some(-(alt_m - self.prev_alt_m) / dt_s);
+        flight_loop.sim_accel_y_override = Some(accel);
+        flight_loop.sim_accel_z_override = Some(accel);
+        self.prev_alt_m = alt_m;
+        self.next += 1;
+        cycle(flight_loop).await;
+        true
+    }
+
+    /// Flies whatever is left of the record.
+    pub async fn finish(&mut self, flight_loop: &mut FlightLoop) {
+        while self.step(flight_loop).await {}
+    }
+
+    /// Rests on the ground at the last altitude for `ms` of sim time.
+    pub async fn rest(&mut self, flight_loop: &mut FlightLoop, ms: u64) {
+        flight_loop.sim_vel_d_override = Some(0.0);
+        flight_loop.sim_accel_y_override = Some(G_MS2);
+        flight_loop.sim_accel_z_override = Some(G_MS2);
+        let start = Instant::now();
+        while start.elapsed().as_millis() < ms {
+            cycle(flight_loop).await;
+        }
+    }
+}
+
+/// Queues `cmd` as if it had come in over the umbilical; the next cycle
+/// handles it.
+pub fn send(cmd: UmbilicalCommand) {
+    umbilical::push_command(cmd);
+}
+
+/// Sends `cmds` one per cycle, as the ground station paces an upload (the
+/// umbilical command queue only holds a few).
+pub async fn uplink(flight_loop: &mut FlightLoop, cmds: impl IntoIterator<Item = UmbilicalCommand>) {
+    for cmd in cmds {
+        send(cmd);
+        cycles(flight_loop, 1).await;
+    }
+}
@@ -0,0 +1,469 @@
+//! Monte Carlo robustness harness for the flight-event logic.
+//!
+//! Flies thousands of randomised 1-DOF trajectories through the real
+//! `FlightLoop` on the SIL stand-ins. Each run arms and launches over the
+//! umbilical like the pad crew, then closes the loop through the hardware:
+//! the model burns while the MAV servo is open, drogue and main drag appear
+//! when their SSA pins go high, and the altimeter sees the trajectory with
+//! noise, dropouts and spikes. Reports apogee-detection timing, deploy
+//! altitudes and N2 timing, and flags missed or early events.
+//!
+//! Run `i` uses seed `--seed + i`; `--runs 1 --seed S --verbose` replays
+//! one trajectory with full FSW logs.
+//!
+//! Exit codes: 0 no anomalies, 1 at least one anomaly, 2 usage error,
+//! 3 sim deadlock.
+
+mod model;
+mod rng;
+mod stats;
+
+use std::io::Write as _;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::sync::Mutex;
+
+use embassy_rp::{gpio, pwm};
+use embassy_time::{Instant, Timer};
+use fsw_sil::flight_loop::FlightLoop;
+use fsw_sil::driver::payload::PayloadCommand;
+use fsw_sil::module::{self, pins};
+use fsw_sil::state::FlightMode;
+use fsw_sil::umbilical::{self, UmbilicalCommand};
+use fsw_sil::world::{self, World};
+use fsw_sil::{airbrake_task, constants, flash, ground, logger, payload, runtime};
+
+use model::{Actuators, Flight, PAD_MSL_M, Vehicle};
+use rng::Rng;
+
+/// Physics sub-steps per 50 ms flight-loop cycle.
+const SUBSTEPS: u32 = 10;
+/// Give up on a run that hasn't landed after this long (s).
+const MAX_RUN_S: f64 = 900.0;
+/// N2's vertical-speed threshold (50 ft/s).
+const N2_SPEED_MS: f64 = 50.0 / 3.28084;
+
+#[derive(Clone)]
+struct Config {
+    runs: u32,
+    seed: u64,
+    dispersion: f64,
+    baro_noise_m: f64,
+    dropout_rate: f64,
+    dropout_max: u32,
+    spike_rate: f64,
+    spike_m: f64,
+    gust_sd: f64,
+    apogee_tol_s: f64,
+    main_tol_m: f64,
+    csv: Option<PathBuf>,
+    verbose: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            runs: 1000,
+            seed: 1,
+            dispersion: 1.0,
+            baro_noise_m: 1.0,
+            dropout_rate: 0.005,
+            dropout_max: 2,
+            spike_rate: 0.002,
+            spike_m: 100.0,
+            gust_sd: 2.0,
+            apogee_tol_s: 2.0,
+            main_tol_m: 30.0,
+            csv: None,
+            verbose: false,
+        }
+    }
+}
+
+const USAGE: &str = "usage: monte-carlo [--runs N] [--seed S] [--dispersion X] [--baro-noise M]
+                   [--dropout-rate P] [--dropout-max N] [--spike-rate P] [--spike M]
+                   [--gust M/S] [--apogee-tol S] [--main-tol M] [--csv FILE] [--verbose]
+
+  --runs N          trajectories to fly (1000)
+  --seed S          seed of run 0; run i uses S+i (1)
+  --dispersion X    scale on the vehicle's 1-sigma tolerances, 0 = nominal (1)
+  --baro-noise M    altimeter noise, 1-sigma metres (1.0)
+  --dropout-rate P  per-read chance a baro dropout starts (0.005)
+  --dropout-max N   longest dropout in consecutive reads (2)
+  --spike-rate P    per-read chance of a baro spike (0.002)
+  --spike M         spike size, metres up or down (100)
+  --gust M/S        vertical gust, 1-sigma (2.0)
+  --apogee-tol S    drogue/N2 timing window around the true event (2.0)
+  --main-tol M      main deploy window around MAIN_DEPLOY_ALTITUDE (30)
+  --csv FILE        write one row per run
+  --verbose         print FSW logs (use with --runs 1)";
+
+fn parse_args() -> Result<Config, String> {
+    fn value<T: std::str::FromStr>(it: &mut impl Iterator<Item = String>, flag: &str) -> Result<T, String> {
+        it.next().and_then(|v| v.parse().ok()).ok_or(format!("{flag} needs a number"))
+    }
+    let mut cfg = Config::default();
+    let mut it = std::env::args().skip(1);
+    while let Some(a) = it.next() {
+        match a.as_str() {
+            "--runs" => cfg.runs = value(&mut it, &a)?,
+            "--seed" => cfg.seed = value(&mut it, &a)?,
+            "--dispersion" => cfg.dispersion = value(&mut it, &a)?,
+            "--baro-noise" => cfg.baro_noise_m = value(&mut it, &a)?,
+            "--dropout-rate" => cfg.dropout_rate = value(&mut it, &a)?,
+            "--dropout-max" => cfg.dropout_max = value::<u32>(&mut it, &a)?.max(1),
+            "--spike-rate" => cfg.spike_rate = value(&mut it, &a)?,
+            "--spike" => cfg.spike_m = value(&mut it, &a)?,
+            "--gust" => cfg.gust_sd = value(&mut it, &a)?,
+            "--apogee-tol" => cfg.apogee_tol_s = value(&mut it, &a)?,
+            "--main-tol" => cfg.main_tol_m = value(&mut it, &a)?,
+            "--csv" => cfg.csv = Some(it.next().ok_or("--csv needs a path")?.into()),
+            "--verbose" | "-v" => cfg.verbose = true,
+            "-h" | "--help" => return Err(String::new()),
+            s => return Err(format!("unknown argument {s}")),
+        }
+    }
+    Ok(cfg)
+}
+
+/// True state at the moment the FSW fired an output. `t` is seconds after liftoff.
+#[derive(Clone, Copy)]
+struct Event {
+    t: f64,
+    alt: f64,
+    vel: f64,
+}
+
+#[derive(Clone, Default)]
+struct Outcome {
+    seed: u64,
+    apogee: f64,
+    t_apogee: f64,
+    /// When N2's condition first truly held: ascending slower than 50 ft/s
+    /// above `N2_ARM_ALTITUDE_M`.
+    t_n2_due: Option<f64>,
+    drogue: Option<Event>,
+    main: Option<Event>,
+    n2: Option<Event>,
+    fault: Option<f64>,
+    landed: Option<f64>,
+}
+
+/// A missed, early or late event: `kind` groups the report, `detail` names the run's numbers.
+struct Anomaly {
+    kind: &'static str,
+    detail: String,
+}
+
+impl Outcome {
+    fn anomalies(&self, cfg: &Config) -> Vec<Anomaly> {
+        let mut out = Vec::new();
+        let mut flag = |kind, detail: String| out.push(Anomaly { kind, detail });
+        let main_alt = constants::MAIN_DEPLOY_ALTITUDE as f64;
+        if let Some(t) = self.fault {
+            flag("fault", format!("Fault at {t:.1} s"));
+        }
+        match self.drogue {
+            None => flag("missed drogue", "missed drogue".into()),
+            Some(d) if d.t < self.t_apogee - cfg.apogee_tol_s => flag(
+                "early drogue",
+                format!("drogue {:.1} s before apogee at {:.0} m/s", self.t_apogee - d.t, d.vel),
+            ),
+            Some(d) if d.t > self.t_apogee + cfg.apogee_tol_s => {
+                flag("late drogue", format!("drogue {:.1} s after apogee", d.t - self.t_apogee))
+            }
+            Some(_) => {}
+        }
+        match self.main {
+            None => flag("missed main", "missed main".into()),
+            Some(m) if m.alt > main_alt + cfg.main_tol_m => flag("early main", format!("main at {:.0} m", m.alt)),
+            Some(m) if m.alt < main_alt - cfg.main_tol_m => flag("late main", format!("main at {:.0} m", m.alt)),
+            Some(_) => {}
+        }
+        match (self.n2, self.t_n2_due) {
+            (None, _) => flag("missed N2", "missed N2".into()),
+            (Some(n), None) => flag("early N2", format!("N2 at {:.1} s but never due", n.t)),
+            (Some(n), Some(due)) if n.t < due - cfg.apogee_tol_s => {
+                flag("early N2", format!("N2 {:.1} s early at {:.0} m/s", due - n.t, n.vel))
+            }
+            (Some(n), Some(due)) if n.t > due + cfg.apogee_tol_s => {
+                flag("late N2", format!("N2 {:.1} s late", n.t - due))
+            }
+            (Some(_), Some(_)) => {}
+        }
+        out
+    }
+}
+
+static OUTCOMES: Mutex<Vec<Outcome>> = Mutex::new(Vec::new());
+
+fn main() -> ExitCode {
+    let cfg = match parse_args() {
+        Ok(c) => c,
+        Err(msg) => {
+            if !msg.is_empty() {
+                eprintln!("{msg}");
+            }
+            eprintln!("{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+
+    logger::init(false);
+    log::set_max_level(if cfg.verbose { log::LevelFilter::Info } else { log::LevelFilter::Off });
+
+    let result = runtime::run(u64::MAX, |spawner| {
+        spawner.spawn(airbrake_task::airbrake_core1_task()).unwrap();
+        module::spawn_odrive_tasks(spawner);
+        spawner.spawn(ground::ground_station_task(ground::Script::Listen, None, None, None, None, Vec::new())).unwrap();
+        spawner.spawn(mc_main(cfg.clone())).unwrap();
+    });
+    if let Err(e) = result {
+        eprintln!("monte-carlo: aborted: {e}");
+        return ExitCode::from(3);
+    }
+
+    let outcomes = std::mem::take(&mut *OUTCOMES.lock().unwrap());
+    if let Some(path) = &cfg.csv
+        && let Err(e) = write_csv(path, &outcomes, &cfg)
+    {
+        eprintln!("cannot write {}: {e}", path.display());
+        return ExitCode::from(2);
+    }
+    if report(&outcomes, &cfg) { ExitCode::SUCCESS } else { ExitCode::from(1) }
+}
+
+#[embassy_executor::task]
+async fn mc_main(cfg: Config) {
+    for i in 0..cfg.runs {
+        let outcome = fly(&cfg, cfg.seed.wrapping_add(i as u64)).await;
+        OUTCOMES.lock().unwrap().push(outcome);
+        if !cfg.verbose {
+            eprint!("\r{}/{} runs", i + 1, cfg.runs);
+        }
+    }
+    if !cfg.verbose {
+        eprintln!();
+    }
+    runtime::finish();
+}
+
+/// Altimeter fault state carried between reads.
+#[derive(Default)]
+struct Baro {
+    dropout_left: u32,
+    /// A dropout only starts after a good read, so bursts never merge past `--dropout-max`.
+    failed_last: bool,
+}
+
+impl Baro {
+    fn sense(&mut self, flight: &Flight, cfg: &Config, rng: &mut Rng) -> World {
+        let mut w = World::PAD;
+        let mut alt = flight.alt + rng.normal(0.0, cfg.baro_noise_m);
+        let (spike, spike_up) = (rng.chance(cfg.spike_rate), rng.chance(0.5));
+        if spike {
+            alt += if spike_up { cfg.spike_m } else { -cfg.spike_m };
+        }
+        let (dropout, len) = (rng.chance(cfg.dropout_rate), rng.next_u64());
+        if self.dropout_left == 0 && !self.failed_last && dropout {
+            self.dropout_left = 1 + (len % cfg.dropout_max as u64) as u32;
+        }
+        self.failed_last = self.dropout_left > 0;
+        if self.failed_last {
+            self.dropout_left -= 1;
+            w.baro_ok = false;
+        }
+        w.pressure = model::pressure_at(PAD_MSL_M + alt) as f32;
+        // +Y points aft, so upward specific force reads negative
+        w.accel = [0.0, -flight.specific_force as f32, 0.0];
+        w.vel_d = -flight.vel;
+        w
+    }
+}
+
+async fn fly(cfg: &Config, seed: u64) -> Outcome {
+    let mut rng = Rng::new(seed);
+    let vehicle = Vehicle::dispersed(&mut rng, cfg.dispersion);
+    let mut flight = Flight::new(vehicle, constants::MAV_OPEN_DURATION_MS as f64 / 1000.0, cfg.gust_sd);
+    let mut baro = Baro::default();
+
+    // Boot on a whole second so a run behaves the same alone or mid-batch
+    let now = Instant::now().as_millis();
+    Timer::at(Instant::from_millis((now / 1000 + 1) * 1000)).await;
+    flash::open_in_memory();
+    // Clean pad reading for sensor init; faults are injected from the first cycle
+    world::set(World { pressure: model::pressure_at(PAD_MSL_M) as f32, ..World::PAD });
+    payload::reset();
+    let mut flight_loop = FlightLoop::new(module::init_flight_state().await);
+
+    let mut out = Outcome { seed, ..Default::default() };
+    let mut act = Actuators::default();
+    let mut armed = false;
+    let mut launched = false;
+    let dt = constants::MAIN_LOOP_DELAY_MS as f64 / 1000.0 / SUBSTEPS as f64;
+    // Seconds since boot; event times are reported relative to liftoff
+    let mut t = 0.0;
+    let mut t_liftoff = None;
+
+    while t < MAX_RUN_S {
+        let mode = flight_loop.flight_state.flight_mode;
+        if !flight.lifted {
+            // Pad crew: heartbeats until the umbilical pulls, wipe + arm, launch
+            umbilical::inject_heartbeat();
+            if mode == FlightMode::Startup && !armed {
+                umbilical::push_command(UmbilicalCommand::WipeFlash);
+                umbilical::push_command(UmbilicalCommand::KeyArm);
+                armed = true;
+            } else if mode == FlightMode::Standby && !launched {
+                umbilical::push_command(UmbilicalCommand::Launch);
+                launched = true;
+            }
+        }
+
+        world::set(baro.sense(&flight, cfg, &mut rng));
+        flight_loop.flight_state.cycle_count += 1;
+        flight_loop.execute().await;
+
+        let since_liftoff = t - t_liftoff.unwrap_or(t);
+        let now = Event { t: since_liftoff, alt: flight.alt, vel: flight.vel };
+        act.mav_open = pwm::duty(pins::MAV) > 0.5;
+        if !act.drogue && gpio::level(pins::SSA_DROGUE) {
+            act.drogue = true;
+            out.drogue = Some(now);
+        }
+        if !act.main && gpio::level(pins::SSA_MAIN) {
+            act.main = true;
+            out.main = Some(now);
+        }
+        if out.n2.is_none() && payload::executed().contains(&PayloadCommand::N2.id()) {
+            out.n2 = Some(now);
+        }
+        if out.fault.is_none() && flight_loop.flight_state.flight_mode == FlightMode::Fault {
+            out.fault = Some(since_liftoff);
+        }
+
+        if flight.landed {
+            out.landed = Some(since_liftoff);
+            break;
+        }
+        // Nothing left to judge once main is out
+        if out.main.is_some_and(|m| since_liftoff > m.t + 1.0) {
+            break;
+        }
+
+        for _ in 0..SUBSTEPS {
+            flight.step(dt, act, &mut rng);
+            t += dt;
+            if flight.lifted && t_liftoff.is_none() {
+                t_liftoff = Some(t);
+            }
+            let Some(t0) = t_liftoff else { continue };
+            if flight.alt > out.apogee {
+                out.apogee = flight.alt;
+                out.t_apogee = t - t0;
+            }
+            if out.t_n2_due.is_none()
+                && flight.vel > 0.0
+                && flight.vel < N2_SPEED_MS
+                && flight.alt > constants::N2_ARM_ALTITUDE_M as f64
+            {
+                out.t_n2_due = Some(t - t0);
+            }
+        }
+        Timer::after_millis(constants::MAIN_LOOP_DELAY_MS).await;
+    }
+    out
+}
+
+/// Prints the summary; returns true if no run had an anomaly.
+fn report(outcomes: &[Outcome], cfg: &Config) -> bool {
+    let main_alt = constants::MAIN_DEPLOY_ALTITUDE as f64;
+    let drogues = || outcomes.iter().filter_map(|o| o.drogue.map(|d| (o, d)));
+    let mains = || outcomes.iter().filter_map(|o| o.main);
+
+    println!(
+        "Monte Carlo: {} runs from seed {} (dispersion x{}, baro noise {} m, dropouts {}/read up to {}, \
+         spikes {}/read of {} m, gusts {} m/s)",
+        outcomes.len(),
+        cfg.seed,
+        cfg.dispersion,
+        cfg.baro_noise_m,
+        cfg.dropout_rate,
+        cfg.dropout_max,
+        cfg.spike_rate,
+        cfg.spike_m,
+        cfg.gust_sd
+    );
+    println!("{}", stats::HEADER);
+    println!("{}", stats::row("apogee (m AGL)", outcomes.iter().map(|o| o.apogee).collect()));
+    println!("{}", stats::row("apogee detect delay (s)", drogues().map(|(o, d)| d.t - o.t_apogee).collect()));
+    println!("{}", stats::row("drogue below apogee (m)", drogues().map(|(o, d)| o.apogee - d.alt).collect()));
+    println!("{}", stats::row("drogue speed (m/s)", drogues().map(|(_, d)| d.vel.abs()).collect()));
+    println!("{}", stats::row("main deploy alt (m AGL)", mains().map(|m| m.alt).collect()));
+    println!("{}", stats::row("main deploy error (m)", mains().map(|m| m.alt - main_alt).collect()));
+    println!(
+        "{}",
+        stats::row(
+            "N2 timing error (s)",
+            outcomes.iter().filter_map(|o| Some(o.n2?.t - o.t_n2_due?)).collect()
+        )
+    );
+
+    let flagged: Vec<(u64, Vec<Anomaly>)> = outcomes
+        .iter()
+        .map(|o| (o.seed, o.anomalies(cfg)))
+        .filter(|(_, a)| !a.is_empty())
+        .collect();
+    if flagged.is_empty() {
+        println!("\nno missed, early or late events");
+        return true;
+    }
+
+    let mut kinds: Vec<(&str, usize)> = Vec::new();
+    for a in flagged.iter().flat_map(|(_, list)| list) {
+        match kinds.iter_mut().find(|(k, _)| *k == a.kind) {
+            Some((_, n)) => *n += 1,
+            None => kinds.push((a.kind, 1)),
+        }
+    }
+    let counts: Vec<String> = kinds.iter().map(|(k, n)| format!("{k}: {n}")).collect();
+    println!("\n{} of {} runs flagged — {}", flagged.len(), outcomes.len(), counts.join(", "));
+    for (seed, list) in flagged.iter().take(10) {
+        let details: Vec<&str> = list.iter().map(|a| a.detail.as_str()).collect();
+        println!("  seed {seed}: {}", details.join("; "));
+    }
+    if flagged.len() > 10 {
+        println!("  ... {} more (see --csv)", flagged.len() - 10);
+    }
+    println!("replay one with: monte-carlo --runs 1 --seed {} --verbose", flagged[0].0);
+    false
+}
+
+fn write_csv(path: &PathBuf, outcomes: &[Outcome], cfg: &Config) -> std::io::Result<()> {
+    let mut f = std::io::BufWriter::new(std::fs::File::create(path)?);
+    writeln!(
+        f,
+        "seed,apogee_m,apogee_t_s,drogue_t_s,drogue_alt_m,drogue_vel_ms,main_t_s,main_alt_m,n2_t_s,n2_due_t_s,fault_t_s,landed_t_s,anomalies"
+    )?;
+    let opt = |v: Option<f64>| v.map(|x| format!("{x:.2}")).unwrap_or_default();
+    for o in outcomes {
+        writeln!(
+            f,
+            "{},{:.2},{:.2},{},{},{},{},{},{},{},{},{},\"{}\"",
+            o.seed,
+            o.apogee,
+            o.t_apogee,
+            opt(o.drogue.map(|e| e.t)),
+            opt(o.drogue.map(|e| e.alt)),
+            opt(o.drogue.map(|e| e.vel)),
+            opt(o.main.map(|e| e.t)),
+            opt(o.main.map(|e| e.alt)),
+            opt(o.n2.map(|e| e.t)),
+            opt(o.t_n2_due),
+            opt(o.fault),
+            opt(o.landed),
+            o.anomalies(cfg).iter().map(|a| a.detail.as_str()).collect::<Vec<_>>().join("; ")
+        )?;
+    }
+    f.flush()
+}
@@ -0,0 +1,148 @@
+//! 1-DOF vertical rocket model: hybrid motor gated by the MAV, body drag,
+//! drogue and main canopies gated by the SSA outputs, and a vertical gust
+//! process. Altitudes are metres AGL, velocities positive up.
+
+use crate::rng::Rng;
+
+/// Spaceport America pad elevation (m MSL).
+pub const PAD_MSL_M: f64 = 1_401.0;
+const G: f64 = 9.80665;
+
+/// Standard-atmosphere static pressure (Pa) at `msl` metres; the inverse of
+/// the altitude formula in `driver/bmp390.rs`.
+pub fn pressure_at(msl: f64) -> f64 {
+    101_325.0 * (1.0 - msl / 44_330.0).powf(1.0 / 0.190295)
+}
+
+fn density_at(msl: f64) -> f64 {
+    pressure_at(msl) / (287.05 * (288.15 - 0.0065 * msl))
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Vehicle {
+    pub dry_mass: f64,
+    pub prop_mass: f64,
+    /// Mean thrust while the MAV is open and oxidiser remains (N).
+    pub thrust: f64,
+    /// Drag area Cd·A (m²) of the airframe and each canopy.
+    pub body_cda: f64,
+    pub drogue_cda: f64,
+    pub main_cda: f64,
+}
+
+impl Vehicle {
+    /// Sized for a ~3 km (10k ft) apogee with a 6 s burn.
+    pub const NOMINAL: Vehicle = Vehicle {
+        dry_mass: 24.0,
+        prop_mass: 5.0,
+        thrust: 1_550.0,
+        body_cda: 0.0095,
+        drogue_cda: 0.70,
+        main_cda: 10.0,
+    };
+
+    /// Nominal vehicle with each parameter perturbed by its 1-σ tolerance
+    /// times `scale` (0 gives the nominal vehicle).
+    pub fn dispersed(rng: &mut Rng, scale: f64) -> Vehicle {
+        let n = Vehicle::NOMINAL;
+        let mut f = |sd: f64| rng.normal(1.0, sd * scale).max(0.5);
+        Vehicle {
+            dry_mass: n.dry_mass * f(0.02),
+            prop_mass: n.prop_mass * f(0.03),
+            thrust: n.thrust * f(0.05),
+            body_cda: n.body_cda * f(0.10),
+            drogue_cda: n.drogue_cda * f(0.10),
+            main_cda: n.main_cda * f(0.10),
+        }
+    }
+}
+
+/// What the flight software is commanding this step.
+#[derive(Clone, Copy, Default)]
+pub struct Actuators {
+    pub mav_open: bool,
+    pub drogue: bool,
+    pub main: bool,
+}
+
+pub struct Flight {
+    vehicle: Vehicle,
+    /// Oxidiser mass flow; the nominal load burns for the MAV open window.
+    mass_flow: f64,
+    prop_left: f64,
+    gust_sd: f64,
+    /// Vertical wind (m/s, up).
+    gust: f64,
+    pub alt: f64,
+    pub vel: f64,
+    /// Non-gravitational acceleration (m/s², up) — what an accelerometer senses.
+    pub specific_force: f64,
+    pub lifted: bool,
+    pub landed: bool,
+}
+
+impl Flight {
+    /// Gust correlation time (s).
+    const GUST_TAU: f64 = 2.0;
+
+    pub fn new(vehicle: Vehicle, burn_s: f64, gust_sd: f64) -> Self {
+        Self {
+            vehicle,
+            mass_flow: Vehicle::NOMINAL.prop_mass / burn_s,
+            prop_left: vehicle.prop_mass,
+            gust_sd,
+            gust: 0.0,
+            alt: 0.0,
+            vel: 0.0,
+            specific_force: G,
+            lifted: false,
+            landed: false,
+        }
+    }
+
+    pub fn step(&mut self, dt: f64, act: Actuators, rng: &mut Rng) {
+        if self.landed {
+            return;
+        }
+        let v = &self.vehicle;
+        let burning = act.mav_open && self.prop_left > 0.0;
+        let thrust = if burning { v.thrust } else { 0.0 };
+        if burning {
+            self.prop_left = (self.prop_left - self.mass_flow * dt).max(0.0);
+        }
+        let mass = v.dry_mass + self.prop_left;
+
+        // Ornstein–Uhlenbeck gust with standard deviation `gust_sd`
+        let tau = Self::GUST_TAU;
+        self.gust += -self.gust / tau * dt + self.gust_sd * (2.0 * dt / tau).sqrt() * rng.normal(0.0, 1.0);
+
+        let mut cda = v.body_cda;
+        if act.drogue {
+            cda += v.drogue_cda;
+        }
+        if act.main {
+            cda += v.main_cda;
+        }
+        let airspeed = self.vel - self.gust;
+        let drag = -0.5 * density_at(PAD_MSL_M + self.alt) * airspeed * airspeed.abs() * cda;
+        let sf = (thrust + drag) / mass;
+
+        if !self.lifted {
+            if sf <= G {
+                // Held by the rail
+                self.specific_force = G;
+                return;
+            }
+            self.lifted = true;
+        }
+        self.specific_force = sf;
+        self.vel += (sf - G) * dt;
+        self.alt += self.vel * dt;
+        if self.alt <= 0.0 && self.vel < 0.0 {
+            self.alt = 0.0;
+            self.vel = 0.0;
+            self.specific_force = G;
+            self.landed = true;
+        }
+    }
+}
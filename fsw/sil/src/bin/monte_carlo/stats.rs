@@ -0,0 +1,48 @@
+//! Distribution summaries for the report.
+
+pub struct Summary {
+    pub n: usize,
+    pub mean: f64,
+    pub sd: f64,
+    pub min: f64,
+    pub p5: f64,
+    pub p50: f64,
+    pub p95: f64,
+    pub max: f64,
+}
+
+impl Summary {
+    pub fn of(mut values: Vec<f64>) -> Option<Summary> {
+        if values.is_empty() {
+            return None;
+        }
+        values.sort_by(f64::total_cmp);
+        let n = values.len();
+        let mean = values.iter().sum::<f64>() / n as f64;
+        let var = values.iter().map(|v| (v - mean) * (v - mean)).sum::<f64>() / n as f64;
+        // Nearest-rank percentile
+        let pct = |p: f64| values[((p / 100.0 * n as f64).ceil() as usize).clamp(1, n) - 1];
+        Some(Summary {
+            n,
+            mean,
+            sd: var.sqrt(),
+            min: values[0],
+            p5: pct(5.0),
+            p50: pct(50.0),
+            p95: pct(95.0),
+            max: values[n - 1],
+        })
+    }
+}
+
+pub const HEADER: &str = "                               n     mean       sd      min       p5      p50      p95      max";
+
+pub fn row(label: &str, values: Vec<f64>) -> String {
+    match Summary::of(values) {
+        Some(s) => format!(
+            "{label:<26}{:>6}{:>9.2}{:>9.2}{:>9.2}{:>9.2}{:>9.2}{:>9.2}{:>9.2}",
+            s.n, s.mean, s.sd, s.min, s.p5, s.p50, s.p95, s.max
+        ),
+        None => format!("{label:<26}{:>6}", 0),
+    }
+}
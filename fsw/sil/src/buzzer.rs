@@ -0,0 +1,109 @@
+//! Ear on the FSW's buzzer pin.
+//!
+//! Samples the PWM duty on `pins::BUZZER` and records each tone, then
+//! decodes what was heard the way someone standing by the rocket would:
+//! numbers (the post-landing apogee readout) and Morse-like codes (the
+//! fault code), using the tone lengths in `actuator::BuzzPattern`.
+//!
+//! Patterns are told apart by the silence between them: `BuzzPattern`
+//! never leaves more than `DIGIT_GAP_MS` between tones, and repeats wait
+//! `BUZZ_REPEAT_PAUSE_MS`.
+
+use core::cell::RefCell;
+
+use critical_section::Mutex;
+use embassy_time::Timer;
+
+use crate::actuator::BuzzPattern;
+use crate::module::pins;
+use crate::runtime;
+use crate::state::health;
+
+const SAMPLE_MS: u64 = 10;
+/// Silence that ends one pattern
+const PATTERN_GAP_MS: u64 = 2_000;
+/// Silence that ends a letter or digit within a pattern
+const SYMBOL_GAP_MS: u64 = (BuzzPattern::SYMBOL_GAP_MS as u64 + BuzzPattern::LETTER_GAP_MS as u64) / 2;
+/// Tone lengths either side of these are short/long, digit beep/zero,
+/// letter/attention
+const LONG_MS: u64 = (BuzzPattern::SHORT_MS as u64 + BuzzPattern::LONG_MS as u64) / 2;
+const ZERO_MS: u64 = (BuzzPattern::DIGIT_BEEP_MS as u64 + BuzzPattern::ZERO_MS as u64) / 2;
+const ATTENTION_MS: u64 = (BuzzPattern::LONG_MS as u64 + BuzzPattern::ATTENTION_MS as u64) / 2;
+
+/// (start, end) of each tone heard, in sim ms
+static TONES: Mutex<RefCell<Vec<(u64, u64)>>> = Mutex::new(RefCell::new(Vec::new()));
+
+#[embassy_executor::task]
+pub async fn listen_task() {
+    let mut start = None;
+    loop {
+        let on = embassy_rp::pwm::duty(pins::BUZZER) > 0.0;
+        let now = runtime::now_ms();
+        match (on, start) {
+            (true, None) => start = Some(now),
+            (false, Some(s)) => {
+                critical_section::with(|cs| TONES.borrow_ref_mut(cs).push((s, now)));
+                start = None;
+            }
+            _ => {}
+        }
+        Timer::after_millis(SAMPLE_MS).await;
+    }
+}
+
+/// Tone lengths heard, split into patterns and each pattern into
+/// letters/digits.
+fn patterns() -> Vec<Vec<Vec<u64>>> {
+    let tones = critical_section::with(|cs| TONES.borrow_ref(cs).clone());
+    let mut patterns: Vec<Vec<Vec<u64>>> = Vec::new();
+    let mut last_end = None;
+    for (start, end) in tones {
+        match last_end {
+            Some(e) if start - e < SYMBOL_GAP_MS => patterns.last_mut().unwrap().last_mut().unwrap().push(end - start),
+            Some(e) if start - e < PATTERN_GAP_MS => patterns.last_mut().unwrap().push(vec![end - start]),
+            _ => patterns.push(vec![vec![end - start]]),
+        }
+        last_end = Some(end);
+    }
+    patterns
+}
+
+/// Every pattern heard that reads as a number.
+pub fn numbers() -> Vec<u32> {
+    patterns()
+        .iter()
+        .filter_map(|pattern| {
+            pattern.iter().try_fold(0u32, |n, digit| {
+                let d = match digit.as_slice() {
+                    [t] if *t >= ZERO_MS => 0,
+                    beeps if beeps.len() <= 9 && beeps.iter().all(|&t| t < ZERO_MS) => beeps.len() as u32,
+                    _ => return None,
+                };
+                n.checked_mul(10)?.checked_add(d)
+            })
+        })
+        .collect()
+}
+
+/// Every fault code heard (patterns led by the attention tone), as
+/// space-separated letters of `.` and `-`.
+pub fn codes() -> Vec<String> {
+    patterns()
+        .iter()
+        .filter(|pattern| matches!(pattern[0].as_slice(), [t] if *t >= ATTENTION_MS))
+        .map(|pattern| {
+            let letters: Vec<String> = pattern[1..]
+                .iter()
+                .map(|letter| letter.iter().map(|&t| if t >= LONG_MS { '-' } else { '.' }).collect())
+                .collect();
+            letters.join(" ")
+        })
+        .collect()
+}
+
+/// The fault code `BuzzPattern::fault_code` sounds for `bits`, as `codes`
+/// renders it.
+pub fn code_for(bits: u16) -> String {
+    let letters: Vec<&str> = health::CODES.iter().filter(|(bit, _)| bits & bit != 0).map(|(_, c)| *c).collect();
+    letters.join(" ")
+}
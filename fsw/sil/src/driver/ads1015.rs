@@ -0,0 +1,54 @@
+//! Mock ADS1015: scaled PT3/PT4/RTD values from the simulated world.
+
+use core::sync::atomic::{AtomicBool, Ordering};
+
+use crate::module::{NotResponding, SharedI2c};
+use crate::packet::Packet;
+use crate::world;
+
+static POWERED_DOWN: AtomicBool = AtomicBool::new(false);
+
+/// Whether the FSW has stopped the ADC's conversions.
+pub fn powered_down() -> bool {
+    POWERED_DOWN.load(Ordering::Relaxed)
+}
+
+pub struct Ads1015Sensor {
+    initialized: bool,
+}
+
+impl Ads1015Sensor {
+    pub fn unavailable(_i2c_bus: &'static SharedI2c) -> Self {
+        Self { initialized: false }
+    }
+
+    pub async fn new(_i2c_bus: &'static SharedI2c) -> Self {
+        Self { initialized: world::get().adc_ok }
+    }
+
+    pub async fn power_down(&mut self) -> Result<(), NotResponding> {
+        if !self.initialized {
+            return Ok(());
+        }
+        if !world::get().adc_ok {
+            return Err(NotResponding);
+        }
+        self.initialized = false;
+        POWERED_DOWN.store(true, Ordering::Relaxed);
+        Ok(())
+    }
+
+    pub async fn read_into_packet(&mut self, packet: &mut Packet) -> Result<(), NotResponding> {
+        if !self.initialized {
+            return Ok(());
+        }
+        let w = world::get();
+        if !w.adc_ok {
+            return Err(NotResponding);
+        }
+        packet.pt3 = w.pt3;
+        packet.pt4 = w.pt4;
+        packet.rtd = w.rtd;
+        Ok(())
+    }
+}
@@ -0,0 +1,68 @@
+//! Mock BMP390: pressure and temperature from the simulated world.
+
+use core::sync::atomic::{AtomicBool, Ordering};
+
+use embassy_rp::gpio::Output;
+
+use crate::module::{NotResponding, SharedSpi};
+use crate::world;
+
+static ASLEEP: AtomicBool = AtomicBool::new(false);
+
+/// Whether the FSW has put the altimeter in sleep mode.
+pub fn asleep() -> bool {
+    ASLEEP.load(Ordering::Relaxed)
+}
+
+pub struct Bmp390Sensor<'a> {
+    _cs: Option<Output<'a>>,
+    altimeter_init: bool,
+}
+
+impl<'a> Bmp390Sensor<'a> {
+    pub async fn new(_spi_bus: &'static SharedSpi, cs: Output<'a>) -> Self {
+        Self { _cs: Some(cs), altimeter_init: world::get().baro_ok }
+    }
+
+    pub async fn read_into_packet(&mut self, packet: &mut crate::packet::Packet) -> Result<(), NotResponding> {
+        let w = world::get();
+        if !self.altimeter_init || !w.baro_ok {
+            return Err(NotResponding);
+        }
+        // Same sanity backstop as the driver
+        if !(crate::constants::PRESSURE_MIN_PA..=crate::constants::PRESSURE_MAX_PA).contains(&w.pressure) {
+            return Err(NotResponding);
+        }
+        packet.pressure = w.pressure;
+        packet.temp = w.temp;
+        packet.altitude = pressure_to_altitude(w.pressure);
+        Ok(())
+    }
+
+    pub async fn sleep(&mut self) -> Result<(), NotResponding> {
+        if !self.altimeter_init {
+            return Ok(());
+        }
+        if !world::get().baro_ok {
+            return Err(NotResponding);
+        }
+        self.altimeter_init = false;
+        ASLEEP.store(true, Ordering::Relaxed);
+        Ok(())
+    }
+
+    pub fn is_init(&self) -> bool {
+        self.altimeter_init
+    }
+
+    pub fn unavailable() -> Self {
+        Self { _cs: None, altimeter_init: false }
+    }
+}
+
+/// Pressure altitude (m MSL) from static pressure (Pa) using the NOAA formula.
+/// Kept identical to `src/driver/bmp390.rs`.
+pub fn pressure_to_altitude(pressure: f32) -> f32 {
+    let sea_level_pa = 101325.0;
+    44330.0 * (1.0 - libm::powf(pressure / sea_level_pa, 0.190295))
+}
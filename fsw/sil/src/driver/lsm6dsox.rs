@@ -0,0 +1,57 @@
+//! Mock LSM6DSOX: accel and gyro from the simulated world.
+
+use core::sync::atomic::{AtomicBool, Ordering};
+
+use crate::module::{NotResponding, SharedI2c};
+use crate::world;
+
+static POWERED_DOWN: AtomicBool = AtomicBool::new(false);
+
+/// Whether the FSW has put the IMU in power-down.
+pub fn powered_down() -> bool {
+    POWERED_DOWN.load(Ordering::Relaxed)
+}
+
+pub struct Lsm6dsoxSensor {
+    initialized: bool,
+}
+
+impl Lsm6dsoxSensor {
+    pub fn unavailable(_i2c_bus: &'static SharedI2c) -> Self {
+        Self { initialized: false }
+    }
+
+    pub async fn new(_i2c_bus: &'static SharedI2c) -> Self {
+        Self { initialized: world::get().imu_ok }
+    }
+
+    pub async fn probe(&mut self) -> bool {
+        world::get().imu_ok
+    }
+
+    pub async fn power_down(&mut self) -> Result<(), NotResponding> {
+        if !self.initialized {
+            return Ok(());
+        }
+        if !world::get().imu_ok {
+            return Err(NotResponding);
+        }
+        self.initialized = false;
+        POWERED_DOWN.store(true, Ordering::Relaxed);
+        Ok(())
+    }
+
+    pub async fn read_into_packet(&mut self, packet: &mut crate::packet::Packet) -> Result<(), NotResponding> {
+        // Like the driver, an uninitialised IMU reads as a silent no-op
+        if !self.initialized {
+            return Ok(());
+        }
+        let w = world::get();
+        if !w.imu_ok {
+            return Err(NotResponding);
+        }
+        [packet.accel_x, packet.accel_y, packet.accel_z] = w.accel;
+        [packet.gyro_x, packet.gyro_y, packet.gyro_z] = w.gyro;
+        Ok(())
+    }
+}
@@ -0,0 +1,20 @@
+//! Drivers for the SIL build: mock sensors that sample `crate::world`, plus
+//! the real flash and radio drivers running on the emulated buses.
+
+pub mod ads1015;
+pub mod bmp390;
+pub mod lsm6dsox;
+#[path = "../../../src/driver/odrive.rs"]
+#[allow(dead_code, unused_imports)]
+pub mod odrive;
+#[path = "../../../src/driver/onboard_flash.rs"]
+#[allow(dead_code, unused_imports)]
+pub mod onboard_flash;
+#[path = "../../../src/driver/payload.rs"]
+#[allow(dead_code, unused_imports)]
+pub mod payload;
+#[path = "../../../src/driver/rfd900x.rs"]
+#[allow(dead_code, unused_imports)]
+pub mod rfd900x;
+pub mod supply;
+pub mod ublox_max_m10s;
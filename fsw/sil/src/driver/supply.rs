@@ -0,0 +1,14 @@
+//! Mock supply monitor: battery and pyro rail voltages from the simulated
+//! world.
+
+use crate::module::NotResponding;
+use crate::world;
+
+pub struct SupplyMonitor;
+
+impl SupplyMonitor {
+    pub async fn read(&mut self) -> Result<(f32, f32), NotResponding> {
+        let w = world::get();
+        Ok((w.battery_v, w.pyro_v))
+    }
+}
@@ -0,0 +1,101 @@
+//! Mock MAX-M10S: a 1 Hz NAV-PVT solution built from the simulated world.
+
+use core::marker::PhantomData;
+
+use embassy_time::Instant;
+
+use crate::module::{I2cDevice, SharedI2c};
+use crate::time_sync::GpsUtc;
+use crate::world;
+
+/// Matches the receiver's configured measurement rate (CFG-RATE 1000 ms).
+const SOLUTION_PERIOD_MS: u64 = 1000;
+/// GPS week, the period of iTOW
+const WEEK_MS: u64 = 604_800_000;
+
+#[derive(Debug)]
+pub enum GpsError {
+    I2cError,
+    NoData,
+}
+
+pub struct UbloxMaxM10s<'a, I2C> {
+    next_solution_ms: u64,
+    last_utc: Option<GpsUtc>,
+    position_e7: (i32, i32),
+    itow: u32,
+    _bus: PhantomData<(&'a (), I2C)>,
+}
+
+impl UbloxMaxM10s<'static, I2cDevice<'static>> {
+    pub fn new(_i2c_bus: &'static SharedI2c) -> Self {
+        Self { next_solution_ms: 0, last_utc: None, position_e7: (0, 0), itow: 0, _bus: PhantomData }
+    }
+}
+
+impl<I2C> UbloxMaxM10s<'_, I2C> {
+    pub async fn configure(&mut self) -> Result<(), GpsError> {
+        if world::get().gps_ok { Ok(()) } else { Err(GpsError::I2cError) }
+    }
+
+    pub async fn probe(&mut self) -> bool {
+        world::get().gps_ok
+    }
+
+    pub fn take_utc(&mut self) -> Option<GpsUtc> {
+        self.last_utc.take()
+    }
+
+    pub fn position_e7(&self) -> (i32, i32) {
+        self.position_e7
+    }
+
+    pub fn itow(&self) -> u32 {
+        self.itow
+    }
+
+    pub async fn read_into_packet(&mut self, packet: &mut crate::packet::Packet) -> Result<(), GpsError> {
+        let w = world::get();
+        if !w.gps_ok {
+            return Err(GpsError::I2cError);
+        }
+        let now_ms = Instant::now().as_millis();
+        if now_ms < self.next_solution_ms {
+            return Err(GpsError::NoData);
+        }
+        // Solutions land on whole seconds, like the receiver's epochs
+        self.next_solution_ms = (now_ms / SOLUTION_PERIOD_MS + 1) * SOLUTION_PERIOD_MS;
+
+        let epoch_ms = w.utc_at_boot_ms + now_ms;
+        let second_of_day = (epoch_ms / 1000) % 86_400;
+
+        packet.latitude = w.latitude as f32;
+        packet.longitude = w.longitude as f32;
+        self.position_e7 = (libm::round(w.latitude * 1e7) as i32, libm::round(w.longitude * 1e7) as i32);
+        self.itow = (epoch_ms % WEEK_MS) as u32;
+        packet.num_satellites = w.num_satellites;
+        packet.timestamp = second_of_day as f32;
+        packet.h_acc = w.h_acc;
+        packet.v_acc = w.v_acc;
+        packet.vel_n = w.vel_n;
+        packet.vel_e = w.vel_e;
+        packet.vel_d = w.vel_d;
+        packet.g_speed = libm::hypot(w.vel_n, w.vel_e);
+        packet.s_acc = 300;
+        // Heading of motion degrades as the speed falls, as on the receiver:
+        // about sAcc / gSpeed radians, 180° when stationary
+        let head_acc_deg = (0.3 / packet.g_speed.max(1e-3)).to_degrees().min(180.0);
+        packet.head_acc = (head_acc_deg * 1e5) as u32;
+        packet.fix_type = w.fix_type;
+        let mut heading = libm::atan2(w.vel_e, w.vel_n).to_degrees();
+        if heading < 0.0 {
+            heading += 360.0;
+        }
+        packet.head_mot = (heading * 1e5) as i32;
+
+        if w.fix_type >= 3 {
+            self.last_utc = Some(GpsUtc { epoch_ms, t_acc_ns: 50 });
+        }
+        Ok(())
+    }
+}
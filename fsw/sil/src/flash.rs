@@ -0,0 +1,177 @@
+//! File-backed W25Q128JV emulator behind the `SpiDevice` the real
+//! `onboard_flash` driver talks to.
+//!
+//! Implements the commands the driver issues (WREN, READ, PAGE PROGRAM,
+//! SECTOR ERASE, RDSR1, JEDEC ID) with NOR semantics: programming can only
+//! clear bits, wraps within the 256-byte page, and is ignored unless the
+//! write-enable latch is set. Every program/erase is written through to the
+//! backing file, so an image can be kept between runs (snapshot recovery)
+//! or inspected afterwards.
+
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::Path;
+use std::sync::Mutex;
+
+use embedded_hal_async::spi::{ErrorKind, ErrorType, Operation};
+
+pub const FLASH_SIZE: usize = 16 * 1024 * 1024;
+const PAGE_SIZE: usize = 256;
+const SECTOR_SIZE: usize = 4096;
+/// Winbond, W25Q 3V, 128 Mbit
+const JEDEC_ID: [u8; 3] = [0xEF, 0x40, 0x18];
+
+const CMD_WREN: u8 = 0x06;
+const CMD_WRDI: u8 = 0x04;
+const CMD_READ: u8 = 0x03;
+const CMD_PAGE_PROG: u8 = 0x02;
+const CMD_SECTOR_ERASE: u8 = 0x20;
+const CMD_RDSR1: u8 = 0x05;
+const CMD_JEDEC_ID: u8 = 0x9F;
+
+const STATUS_WEL: u8 = 0x02;
+
+struct Chip {
+    mem: Vec<u8>,
+    /// Backing image; `None` for a scratch chip that lives only in memory.
+    file: Option<File>,
+    write_enabled: bool,
+}
+
+static CHIP: Mutex<Option<Chip>> = Mutex::new(None);
+
+/// Opens (or creates) the backing image. With `fresh`, the image starts
+/// fully erased; otherwise existing contents are kept.
+pub fn open(path: &Path, fresh: bool) -> std::io::Result<()> {
+    let mut file = OpenOptions::new().read(true).write(true).create(true).truncate(fresh).open(path)?;
+    let mut mem = Vec::with_capacity(FLASH_SIZE);
+    file.read_to_end(&mut mem)?;
+    if mem.len() != FLASH_SIZE {
+        mem.resize(FLASH_SIZE, 0xFF);
+        file.seek(SeekFrom::Start(0))?;
+        file.write_all(&mem)?;
+    }
+    *CHIP.lock().unwrap() = Some(Chip { mem, file: Some(file), write_enabled: false });
+    Ok(())
+}
+
+/// Installs a fully erased chip with no backing file, replacing any open one.
+pub fn open_in_memory() {
+    *CHIP.lock().unwrap() = Some(Chip { mem: vec![0xFF; FLASH_SIZE], file: None, write_enabled: false });
+}
+
+/// Copy of the whole chip, for tests that walk what the FSW logged. Empty
+/// if no chip is open.
+pub fn contents() -> Vec<u8> {
+    CHIP.lock().unwrap().as_ref().map(|c| c.mem.clone()).unwrap_or_default()
+}
+
+impl Chip {
+    fn persist(&mut self, start: usize, len: usize) {
+        let Some(file) = self.file.as_mut() else {
+            return;
+        };
+        let ok = file.seek(SeekFrom::Start(start as u64)).is_ok()
+            && file.write_all(&self.mem[start..start + len]).is_ok();
+        if !ok {
+            log::error!("SIL flash: failed to write backing file");
+        }
+    }
+
+    /// Runs one chip-select-low transaction.
+    fn transaction(&mut self, ops: &mut [Operation<'_, u8>]) {
+        // Bytes clocked in so far: command, then address, then data.
+        let mut tx: Vec<u8> = Vec::new();
+        let mut read_pos = 0usize;
+        for op in ops.iter_mut() {
+            match op {
+                Operation::Write(data) => tx.extend_from_slice(data),
+                Operation::Transfer(read, write) => {
+                    tx.extend_from_slice(write);
+                    self.fill(&tx, &mut read_pos, read);
+                }
+                Operation::TransferInPlace(buf) => {
+                    tx.extend_from_slice(buf);
+                    self.fill(&tx, &mut read_pos, buf);
+                }
+                Operation::Read(buf) => self.fill(&tx, &mut read_pos, buf),
+                Operation::DelayNs(_) => {}
+            }
+        }
+
+        match tx.first().copied() {
+            Some(CMD_WREN) => self.write_enabled = true,
+            Some(CMD_WRDI) => self.write_enabled = false,
+            Some(CMD_PAGE_PROG) if tx.len() >= 4 && self.write_enabled => {
+                let addr = address(&tx);
+                let page = addr & !(PAGE_SIZE - 1);
+                for (i, &b) in tx[4..].iter().take(PAGE_SIZE).enumerate() {
+                    let a = page + (addr + i) % PAGE_SIZE;
+                    self.mem[a] &= b;
+                }
+                self.persist(page, PAGE_SIZE);
+                self.write_enabled = false;
+            }
+            Some(CMD_SECTOR_ERASE) if tx.len() >= 4 && self.write_enabled => {
+                let sector = address(&tx) & !(SECTOR_SIZE - 1);
+                self.mem[sector..sector + SECTOR_SIZE].fill(0xFF);
+                self.persist(sector, SECTOR_SIZE);
+                self.write_enabled = false;
+            }
+            _ => {}
+        }
+    }
+
+    /// Serves a read phase of the current command.
+    fn fill(&self, tx: &[u8], read_pos: &mut usize, buf: &mut [u8]) {
+        match tx.first().copied() {
+            Some(CMD_READ) if tx.len() >= 4 => {
+                let start = address(tx) + *read_pos;
+                for (i, b) in buf.iter_mut().enumerate() {
+                    *b = self.mem[(start + i) % FLASH_SIZE];
+                }
+            }
+            // Never busy: program and erase complete instantly
+            Some(CMD_RDSR1) => buf.fill(if self.write_enabled { STATUS_WEL } else { 0 }),
+            Some(CMD_JEDEC_ID) => {
+                for (i, b) in buf.iter_mut().enumerate() {
+                    *b = JEDEC_ID.get(*read_pos + i).copied().unwrap_or(0);
+                }
+            }
+            _ => buf.fill(0xFF),
+        }
+        *read_pos += buf.len();
+    }
+}
+
+fn address(tx: &[u8]) -> usize {
+    (((tx[1] as usize) << 16) | ((tx[2] as usize) << 8) | tx[3] as usize) % FLASH_SIZE
+}
+
+/// The flash chip as seen through its chip select on the shared SPI bus.
+pub struct SpiFlash<'a> {
+    _cs: embassy_rp::gpio::Output<'a>,
+}
+
+impl<'a> SpiFlash<'a> {
+    pub fn new(cs: embassy_rp::gpio::Output<'a>) -> Self {
+        Self { _cs: cs }
+    }
+}
+
+impl ErrorType for SpiFlash<'_> {
+    type Error = ErrorKind;
+}
+
+impl embedded_hal_async::spi::SpiDevice for SpiFlash<'_> {
+    async fn transaction(&mut self, operations: &mut [Operation<'_, u8>]) -> Result<(), Self::Error> {
+        match CHIP.lock().unwrap().as_mut() {
+            Some(chip) => {
+                chip.transaction(operations);
+                Ok(())
+            }
+            // No image opened: behave like an unpopulated footprint
+            None => Err(ErrorKind::Other),
+        }
+    }
+}
@@ -0,0 +1,126 @@
+//! Simulated ground station on the far end of the umbilical.
+//!
+//! Drains the FSW's outbound text channel (what would go over USB CDC),
+//! tracks flight mode from `$TELEM` lines, optionally records them verbatim
+//! (readable by `tools/heartbeat_dashboard.parse_telemetry`), and plays
+//! the operator for scenarios that wait on umbilical commands.
+//! `--airbrake-target` has the operator set the airbrake target apogee
+//! (`<AK,...>`, other parameters at their defaults) before arming,
+//! `--blims-alt-upwind` likewise the BLiMS Upwind/Downwind boundary
+//! (`<BK,...>`), and
+//! `--wind-profile` and `--geofence` have them upload a BLiMS wind profile
+//! (`<WP,...>`) and geofence (`<GF,...>`), one command per telemetry line as
+//! the fill-station paces it.
+
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::Write;
+
+use crate::umbilical::{self, UmbilicalCommand};
+use blims::{BlimsConfig, LatLon, WindLayer, ZoneKind};
+use controller_in_rust_v3::Config as AirbrakeConfig;
+
+/// Operator actions for a scenario.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Script {
+    /// Only listen.
+    Listen,
+    /// Wipe flash and key-arm in Startup (after any airbrake and BLiMS
+    /// config, wind profile and geofence), then send `<L>` once in Standby.
+    ArmAndLaunch,
+    /// Force MainDeployed (`<MR>`) on first telemetry.
+    ForceMain,
+}
+
+#[embassy_executor::task]
+pub async fn ground_station_task(
+    script: Script,
+    mut telem_out: Option<File>,
+    airbrake_target: Option<f32>,
+    blims_alt_upwind: Option<f32>,
+    wind_profile: Option<Vec<WindLayer>>,
+    geofence: Vec<(ZoneKind, Vec<LatLon>)>,
+) {
+    let mut uplink: VecDeque<UmbilicalCommand> = VecDeque::new();
+    if let Some(layers) = wind_profile {
+        uplink.push_back(UmbilicalCommand::WindProfileBegin(layers.len() as u8));
+        for (i, layer) in layers.into_iter().enumerate() {
+            uplink.push_back(UmbilicalCommand::WindProfileLayer(i as u8, layer));
+        }
+        uplink.push_back(UmbilicalCommand::WindProfileCommit);
+    }
+    if !geofence.is_empty() {
+        uplink.push_back(UmbilicalCommand::GeofenceBegin(geofence.len() as u8));
+        for (i, (kind, vertices)) in geofence.into_iter().enumerate() {
+            uplink.push_back(UmbilicalCommand::GeofenceZone(i as u8, kind, vertices.len() as u8));
+            for (j, v) in vertices.into_iter().enumerate() {
+                uplink.push_back(UmbilicalCommand::GeofenceVertex(i as u8, j as u8, v));
+            }
+        }
+        uplink.push_back(UmbilicalCommand::GeofenceCommit);
+    }
+    let mut line: Vec<u8> = Vec::new();
+    let mut armed = false;
+    let mut launched = false;
+    let mut forced = false;
+
+    loop {
+        let chunk = umbilical::recv_outbound().await;
+        for &b in chunk.iter() {
+            if b != b'\n' {
+                line.push(b);
+                continue;
+            }
+            let text = String::from_utf8_lossy(&line).into_owned();
+            line.clear();
+
+            let Some(fields) = text.strip_prefix("$TELEM,") else {
+                if !text.trim().is_empty() {
+                    log::info!("UMB> {}", text.trim_end());
+                }
+                continue;
+            };
+            if let Some(f) = telem_out.as_mut() {
+                let _ = writeln!(f, "{}", text);
+            }
+            let Some(mode) = fields.split(',').next().and_then(|m| m.parse::<u32>().ok()) else {
+                continue;
+            };
+
+            match script {
+                Script::ArmAndLaunch if mode == 0 && !uplink.is_empty() => {
+                    let cmd = uplink.pop_front().unwrap();
+                    log::info!("GROUND: sending {:?}", cmd);
+                    umbilical::push_command(cmd);
+                }
+                Script::ArmAndLaunch if mode == 0 && !armed => {
+                    if let Some(target) = airbrake_target {
+                        log::info!("GROUND: sending <AK> with target apogee {target} m");
+                        let config = AirbrakeConfig { target_apogee_m: target, ..AirbrakeConfig::default() };
+                        umbilical::push_command(UmbilicalCommand::SetAirbrakeConfig(config));
+                    }
+                    if let Some(alt_upwind_ft) = blims_alt_upwind {
+                        log::info!("GROUND: sending <BK> with Upwind/Downwind boundary {alt_upwind_ft} ft");
+                        let config = BlimsConfig { alt_upwind_ft, ..BlimsConfig::default() };
+                        umbilical::push_command(UmbilicalCommand::SetBlimsConfig(config));
+                    }
+                    log::info!("GROUND: sending <W> and <KA>");
+                    umbilical::push_command(UmbilicalCommand::WipeFlash);
+                    umbilical::push_command(UmbilicalCommand::KeyArm);
+                    armed = true;
+                }
+                Script::ArmAndLaunch if mode == 1 && !launched => {
+                    log::info!("GROUND: sending <L>");
+                    umbilical::push_command(UmbilicalCommand::Launch);
+                    launched = true;
+                }
+                Script::ForceMain if !forced => {
+                    log::info!("GROUND: sending <MR>");
+                    umbilical::push_command(UmbilicalCommand::MainMode);
+                    forced = true;
+                }
+                _ => {}
+            }
+        }
+    }
+}
@@ -0,0 +1,59 @@
+//! Host stand-ins for the flight software: the FSW sources included by path,
+//! plus simulated time, mock drivers and a simulated world underneath them.
+//! Shared by the `fsw-sil` scenario runner and the `monte-carlo` harness.
+//!
+//! The FSW modules only allow `dead_code` and `unused_imports` (items used
+//! by the firmware build alone); every other lint, clippy included, applies.
+
+#[path = "../../src/actuator.rs"]
+#[allow(dead_code, unused_imports)]
+pub mod actuator;
+#[path = "../../src/airbrake_task.rs"]
+#[allow(dead_code, unused_imports)]
+pub mod airbrake_task;
+#[path = "../../src/airbrake_test.rs"]
+#[allow(dead_code, unused_imports)]
+pub mod airbrake_test;
+pub mod bench;
+pub mod buzzer;
+#[path = "../../src/constants.rs"]
+#[allow(dead_code, unused_imports)]
+pub mod constants;
+pub mod driver;
+pub mod flash;
+#[path = "../../src/flight_loop.rs"]
+#[allow(dead_code, unused_imports)]
+pub mod flight_loop;
+#[path = "../../Test/flight_sim.rs"]
+#[allow(dead_code, unused_imports)]
+pub mod flight_sim;
+pub mod ground;
+#[path = "../../src/hil.rs"]
+#[allow(dead_code, unused_imports)]
+pub mod hil;
+pub mod logger;
+pub mod module;
+pub mod odrive;
+#[path = "../../src/odrive_task.rs"]
+#[allow(dead_code, unused_imports)]
+pub mod odrive_task;
+pub mod payload;
+#[path = "../../src/packet.rs"]
+#[allow(dead_code, unused_imports)]
+pub mod packet;
+pub mod radio;
+pub mod runtime;
+#[path = "../../src/state.rs"]
+#[allow(dead_code, unused_imports)]
+pub mod state;
+#[path = "../../src/time_sync.rs"]
+#[allow(dead_code, unused_imports)]
+pub mod time_sync;
+#[path = "../../src/umbilical.rs"]
+#[allow(dead_code, unused_imports)]
+pub mod umbilical;
+#[path = "../../src/watchdog.rs"]
+#[allow(dead_code, unused_imports)]
+pub mod watchdog;
+pub mod world;
+
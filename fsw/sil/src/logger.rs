@@ -0,0 +1,63 @@
+//! stdout logger stamped with sim time. Also tallies the `[... SIM] SUCCESS`
+//! and `[... SIM] FAILED` verdicts that flight_sim.rs logs, which decide the
+//! process exit code.
+
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+
+use crate::runtime;
+
+static QUIET: AtomicBool = AtomicBool::new(false);
+static PASSED: AtomicU32 = AtomicU32::new(0);
+static FAILED: AtomicU32 = AtomicU32::new(0);
+
+struct SimLogger;
+
+static LOGGER: SimLogger = SimLogger;
+
+/// In quiet mode only warnings, errors and pass/fail verdicts print.
+pub fn init(quiet: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+    let _ = log::set_logger(&LOGGER);
+    log::set_max_level(log::LevelFilter::Info);
+}
+
+/// (checks passed, checks failed) so far.
+pub fn verdicts() -> (u32, u32) {
+    (PASSED.load(Ordering::Relaxed), FAILED.load(Ordering::Relaxed))
+}
+
+impl log::Log for SimLogger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &log::Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let msg = record.args().to_string();
+        let verdict = if msg.contains("SIM] FAILED") {
+            FAILED.fetch_add(1, Ordering::Relaxed);
+            true
+        } else if msg.contains("SIM] SUCCESS") {
+            PASSED.fetch_add(1, Ordering::Relaxed);
+            true
+        } else {
+            false
+        };
+
+        if QUIET.load(Ordering::Relaxed) && record.level() > log::Level::Warn && !verdict {
+            return;
+        }
+        let ms = runtime::now_ms();
+        let mut out = std::io::stdout().lock();
+        for line in msg.trim_matches('\n').lines() {
+            let _ = writeln!(out, "[{:>6}.{:03}] {:<5} {}", ms / 1000, ms % 1000, record.level(), line);
+        }
+    }
+
+    fn flush(&self) {
+        let _ = std::io::stdout().flush();
+    }
+}
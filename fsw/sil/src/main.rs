@@ -0,0 +1,691 @@
+//! Software-in-the-loop build of the flight software for x86 Linux.
+//!
+//! The flight logic (`state`, `flight_loop`, `actuator`, `airbrake_task`,
+//! `packet`, `umbilical`, flash and radio drivers) and `Test/flight_sim.rs`
+//! are the firmware sources, included by path, and the airbrake controller is
+//! the real `controller_in_rust_v3` crate. Underneath them sit host stand-ins:
+//! simulated Embassy time (`runtime`), mock sensors sampling a simulated
+//! world (`driver`, `world`), a file-backed NOR flash (`flash`), and shims for
+//! `embassy-rp` and `cortex-m` (`shims/`).
+//!
+//! One process runs one scenario, like flashing one `sim_*` build. With no
+//! scenario argument every compiled-in scenario runs, each in a child
+//! process so statics, flash and the sim clock start clean.
+//!
+//! Exit codes: 0 all checks passed, 1 a `[... SIM] FAILED` check, 2 usage
+//! error, 3 deadlock or sim-time budget exceeded, 101 panic.
+
+use std::path::PathBuf;
+use std::process::{Command, ExitCode};
+
+use embassy_futures::select::select;
+use embassy_time::Timer;
+
+use blims::{LatLon, WindLayer, ZoneKind};
+
+use fsw_sil::driver::payload::LinkState;
+use fsw_sil::driver::{ads1015, bmp390, lsm6dsox};
+use fsw_sil::packet::BeaconPacket;
+use fsw_sil::{airbrake_task, buzzer, constants, flash, flight_loop, flight_sim, ground, logger, module, odrive, odrive_task, packet, payload, radio, runtime, state, world};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Scenario {
+    #[cfg(feature = "sim_simple")]
+    Simple,
+    #[cfg(feature = "sim_launch")]
+    Launch,
+    #[cfg(feature = "sim_fault")]
+    Fault,
+    #[cfg(feature = "sim_stability")]
+    Stability,
+    #[cfg(feature = "sim_extra")]
+    Extra,
+    #[cfg(feature = "sim_flash")]
+    Flash,
+    #[cfg(feature = "sim_real_flight")]
+    RealFlight,
+    #[cfg(feature = "sim_blims")]
+    Blims,
+    #[cfg(feature = "sim_payload")]
+    Payload,
+}
+
+/// Compiled-in scenarios, in the order the firmware's `main` runs them.
+const SCENARIOS: &[(&str, Scenario)] = &[
+    #[cfg(feature = "sim_simple")]
+    ("simple", Scenario::Simple),
+    #[cfg(feature = "sim_launch")]
+    ("launch", Scenario::Launch),
+    #[cfg(feature = "sim_fault")]
+    ("fault", Scenario::Fault),
+    #[cfg(feature = "sim_stability")]
+    ("stability", Scenario::Stability),
+    #[cfg(feature = "sim_extra")]
+    ("extra", Scenario::Extra),
+    #[cfg(feature = "sim_flash")]
+    ("flash", Scenario::Flash),
+    #[cfg(feature = "sim_real_flight")]
+    ("real_flight", Scenario::RealFlight),
+    #[cfg(feature = "sim_blims")]
+    ("blims", Scenario::Blims),
+    #[cfg(feature = "sim_payload")]
+    ("payload", Scenario::Payload),
+];
+
+struct Args {
+    scenario: Option<String>,
+    flash: Option<PathBuf>,
+    telem: Option<PathBuf>,
+    max_sim_s: u64,
+    odrive_fault_s: Option<u64>,
+    payload_fault_s: Option<u64>,
+    payload_drop: u32,
+    core1_hang_s: Option<u64>,
+    ground_s: Option<u64>,
+    gps_loss_s: Option<u64>,
+    low_battery_s: Option<u64>,
+    airbrake_target: Option<f32>,
+    blims_alt_upwind: Option<f32>,
+    wind_profile: Option<Vec<WindLayer>>,
+    geofence: Vec<(ZoneKind, Vec<LatLon>)>,
+    quiet: bool,
+    list: bool,
+}
+
+const USAGE: &str =
+    "usage: fsw-sil [--quiet] [--flash IMAGE] [--telem FILE] [--max-sim-s N] [--odrive-fault-s N] [--payload-fault-s N] [--payload-drop N] [--core1-hang-s N] [--ground-s N] [--gps-loss-s N] [--low-battery-s N] [--airbrake-target M] [--blims-alt-upwind FT] [--wind-profile ALT/FROM/SPEED,...] [--geofence K|L:LAT/LON,...]... [--list] [SCENARIO]";
+
+fn parse_args() -> Result<Args, String> {
+    let mut args = Args {
+        scenario: None,
+        flash: None,
+        telem: None,
+        max_sim_s: 3600,
+        odrive_fault_s: None,
+        payload_fault_s: None,
+        payload_drop: 0,
+        core1_hang_s: None,
+        ground_s: None,
+        gps_loss_s: None,
+        low_battery_s: None,
+        airbrake_target: None,
+        blims_alt_upwind: None,
+        wind_profile: None,
+        geofence: Vec::new(),
+        quiet: false,
+        list: false,
+    };
+    let mut it = std::env::args().skip(1);
+    while let Some(a) = it.next() {
+        match a.as_str() {
+            "--quiet" | "-q" => args.quiet = true,
+            "--list" => args.list = true,
+            "--flash" => args.flash = Some(it.next().ok_or("--flash needs a path")?.into()),
+            "--telem" => args.telem = Some(it.next().ok_or("--telem needs a path")?.into()),
+            "--max-sim-s" => {
+                args.max_sim_s = it
+                    .next()
+                    .and_then(|v| v.parse().ok())
+                    .ok_or("--max-sim-s needs a number of seconds")?
+            }
+            "--odrive-fault-s" => {
+                args.odrive_fault_s = Some(
+                    it.next()
+                        .and_then(|v| v.parse().ok())
+                        .ok_or("--odrive-fault-s needs a number of seconds")?,
+                )
+            }
+            "--payload-fault-s" => {
+                args.payload_fault_s = Some(
+                    it.next()
+                        .and_then(|v| v.parse().ok())
+                        .ok_or("--payload-fault-s needs a number of seconds")?,
+                )
+            }
+            "--payload-drop" => {
+                args.payload_drop = it
+                    .next()
+                    .and_then(|v| v.parse().ok())
+                    .ok_or("--payload-drop needs a number of ACKs")?
+            }
+            "--core1-hang-s" => {
+                args.core1_hang_s = Some(
+                    it.next()
+                        .and_then(|v| v.parse().ok())
+                        .ok_or("--core1-hang-s needs a number of seconds")?,
+                )
+            }
+            "--ground-s" => {
+                args.ground_s = Some(
+                    it.next()
+                        .and_then(|v| v.parse().ok())
+                        .ok_or("--ground-s needs a number of seconds")?,
+                )
+            }
+            "--gps-loss-s" => {
+                args.gps_loss_s = Some(
+                    it.next()
+                        .and_then(|v| v.parse().ok())
+                        .ok_or("--gps-loss-s needs a number of seconds")?,
+                )
+            }
+            "--low-battery-s" => {
+                args.low_battery_s = Some(
+                    it.next()
+                        .and_then(|v| v.parse().ok())
+                        .ok_or("--low-battery-s needs a number of seconds")?,
+                )
+            }
+            "--airbrake-target" => {
+                args.airbrake_target = Some(
+                    it.next()
+                        .and_then(|v| v.parse().ok())
+                        .ok_or("--airbrake-target needs an apogee in metres")?,
+                )
+            }
+            "--blims-alt-upwind" => {
+                args.blims_alt_upwind = Some(
+                    it.next()
+                        .and_then(|v| v.parse().ok())
+                        .ok_or("--blims-alt-upwind needs an altitude in feet")?,
+                )
+            }
+            "--wind-profile" => {
+                args.wind_profile = Some(
+                    it.next()
+                        .as_deref()
+                        .and_then(parse_wind_profile)
+                        .ok_or("--wind-profile needs layers as ALT_M/FROM_DEG/SPEED_MS,...")?,
+                )
+            }
+            "--geofence" => args.geofence.push(
+                it.next()
+                    .as_deref()
+                    .and_then(parse_zone)
+                    .ok_or("--geofence needs a zone as K|L:LAT/LON,LAT/LON,...")?,
+            ),
+            "-h" | "--help" => return Err(String::new()),
+            s if s.starts_with('-') => return Err(format!("unknown option {s}")),
+            s => args.scenario = Some(s.to_string()),
+        }
+    }
+    Ok(args)
+}
+
+/// `0/270/3,300/280/5` → layers. Ranges are left to the FSW, which is what
+/// the upload exercises.
+fn parse_wind_profile(spec: &str) -> Option<Vec<WindLayer>> {
+    spec.split(',')
+        .map(|layer| {
+            let mut v = layer.split('/').map(|f| f.trim().parse::<f32>().ok());
+            let l = WindLayer { altitude_m: v.next()??, from_deg: v.next()??, speed_ms: v.next()?? };
+            v.next().is_none().then_some(l)
+        })
+        .collect()
+}
+
+/// `K:42.70/-77.18,42.71/-77.18,42.71/-77.17` → a keep-out (`L` a landing
+/// zone). Polygon checks are left to the FSW, as above.
+fn parse_zone(spec: &str) -> Option<(ZoneKind, Vec<LatLon>)> {
+    let (kind, vertices) = spec.split_once(':')?;
+    let kind = match kind {
+        "K" => ZoneKind::KeepOut,
+        "L" => ZoneKind::LandingZone,
+        _ => return None,
+    };
+    let vertices = vertices
+        .split(',')
+        .map(|v| {
+            let (lat, lon) = v.split_once('/')?;
+            Some(LatLon::new(lat.trim().parse().ok()?, lon.trim().parse().ok()?))
+        })
+        .collect::<Option<Vec<_>>>()?;
+    Some((kind, vertices))
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args() {
+        Ok(a) => a,
+        Err(msg) => {
+            if !msg.is_empty() {
+                eprintln!("{msg}");
+            }
+            eprintln!("{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+
+    if args.list {
+        for (name, _) in SCENARIOS {
+            println!("{name}");
+        }
+        return ExitCode::SUCCESS;
+    }
+
+    let Some(name) = args.scenario.as_deref() else {
+        return run_all();
+    };
+    let Some(&(name, scenario)) = SCENARIOS.iter().find(|(n, _)| *n == name) else {
+        eprintln!("unknown scenario '{name}' (see --list)");
+        return ExitCode::from(2);
+    };
+
+    logger::init(args.quiet);
+
+    // A given image is kept as-is so a run can resume from its snapshot ring;
+    // the default is a fresh, fully erased image per scenario.
+    let (flash_path, fresh) = match args.flash {
+        Some(p) => (p, false),
+        None => (std::env::temp_dir().join(format!("fsw-sil-{name}-{}.bin", std::process::id())), true),
+    };
+    if let Err(e) = flash::open(&flash_path, fresh) {
+        eprintln!("cannot open flash image {}: {e}", flash_path.display());
+        return ExitCode::from(2);
+    }
+    let telem = match args.telem.as_ref().map(std::fs::File::create).transpose() {
+        Ok(f) => f,
+        Err(e) => {
+            eprintln!("cannot create telemetry file: {e}");
+            return ExitCode::from(2);
+        }
+    };
+
+    payload::drop_acks(args.payload_drop);
+    let result = runtime::run(args.max_sim_s * 1000, |spawner| {
+        match args.core1_hang_s {
+            Some(s) => spawner.spawn(core1_hang_task(s)).unwrap(),
+            None => spawner.spawn(airbrake_task::airbrake_core1_task()).unwrap(),
+        }
+        module::spawn_odrive_tasks(spawner);
+        spawner.spawn(ground::ground_station_task(ground_script(scenario), telem, args.airbrake_target, args.blims_alt_upwind, args.wind_profile.clone(), args.geofence.clone())).unwrap();
+        spawner.spawn(sil_main(scenario, args.ground_s, args.gps_loss_s)).unwrap();
+        spawner.spawn(buzzer::listen_task()).unwrap();
+        spawner.spawn(radio::listen_task()).unwrap();
+        if let Some(s) = args.odrive_fault_s {
+            spawner.spawn(odrive_fault_task(s)).unwrap();
+        }
+        if let Some(s) = args.payload_fault_s {
+            spawner.spawn(payload_fault_task(s)).unwrap();
+        }
+        if let Some(s) = args.gps_loss_s {
+            spawner.spawn(gps_loss_task(s)).unwrap();
+        }
+        if let Some(s) = args.low_battery_s {
+            spawner.spawn(low_battery_task(s)).unwrap();
+        }
+    });
+    if fresh {
+        let _ = std::fs::remove_file(&flash_path);
+    }
+
+    let (passed, failed) = logger::verdicts();
+    let sim_ms = runtime::now_ms();
+    match result {
+        Ok(()) => {
+            println!(
+                "SIL {name}: {passed} passed, {failed} failed, {}.{:03} s simulated",
+                sim_ms / 1000,
+                sim_ms % 1000
+            );
+            if failed == 0 { ExitCode::SUCCESS } else { ExitCode::from(1) }
+        }
+        Err(e) => {
+            println!("SIL {name}: aborted: {e}");
+            ExitCode::from(3)
+        }
+    }
+}
+
+/// Runs every compiled-in scenario in its own child process.
+fn run_all() -> ExitCode {
+    let exe = std::env::current_exe().expect("current_exe");
+    let passthrough: Vec<String> = std::env::args().skip(1).collect();
+    let mut failures = Vec::new();
+    for (name, _) in SCENARIOS {
+        let status = Command::new(&exe).args(&passthrough).arg(name).status();
+        if !matches!(status, Ok(s) if s.success()) {
+            failures.push(*name);
+        }
+    }
+    if failures.is_empty() {
+        println!("SIL: all {} scenarios passed", SCENARIOS.len());
+        ExitCode::SUCCESS
+    } else {
+        println!("SIL: failed: {}", failures.join(", "));
+        ExitCode::from(1)
+    }
+}
+
+fn ground_script(scenario: Scenario) -> ground::Script {
+    match scenario {
+        #[cfg(feature = "sim_real_flight")]
+        Scenario::RealFlight => ground::Script::ArmAndLaunch,
+        #[cfg(feature = "sim_blims")]
+        Scenario::Blims => ground::Script::ForceMain,
+        #[allow(unreachable_patterns)]
+        _ => ground::Script::Listen,
+    }
+}
+
+/// Cuts both ODrive UART links at `after_s` of sim time so the run exercises
+/// the RC PWM fallback.
+#[embassy_executor::task]
+async fn odrive_fault_task(after_s: u64) {
+    Timer::after_secs(after_s).await;
+    log::warn!("SIL: cutting both ODrive UART links");
+    odrive::set_connected(odrive::AIRBRAKE, false);
+    odrive::set_connected(odrive::BLIMS, false);
+}
+
+/// Cuts the payload UART link at `after_s` of sim time so the run exercises
+/// link-loss reporting.
+#[embassy_executor::task]
+async fn payload_fault_task(after_s: u64) {
+    Timer::after_secs(after_s).await;
+    log::warn!("SIL: cutting the payload UART link");
+    payload::set_connected(false);
+}
+
+/// Drops the GPS fix at `after_s` of sim time (the receiver keeps answering
+/// with no fix, as when the antenna is buried on landing).
+#[embassy_executor::task]
+async fn gps_loss_task(after_s: u64) {
+    Timer::after_secs(after_s).await;
+    log::warn!("SIL: dropping the GPS fix");
+    world::set(world::World { fix_type: 0, num_satellites: 0, ..world::get() });
+}
+
+/// Holds the flight battery below `BATTERY_MIN_ARM_V` for the first
+/// `secs` of sim time, then puts it back, as when a flat pack on the pad is
+/// swapped for a charged one.
+#[embassy_executor::task]
+async fn low_battery_task(secs: u64) {
+    let charged = world::get().battery_v;
+    world::set(world::World { battery_v: constants::BATTERY_MIN_ARM_V - 0.6, ..world::get() });
+    Timer::after_secs(secs).await;
+    log::warn!("SIL: swapping in a charged battery");
+    world::set(world::World { battery_v: charged, ..world::get() });
+}
+
+/// Runs the airbrake controller like Core 1 does, then drops it at `after_s`
+/// of sim time so it stops answering, as a hung or crashed Core 1 would.
+#[embassy_executor::task]
+async fn core1_hang_task(after_s: u64) {
+    select(airbrake_task::run(), Timer::after_secs(after_s)).await;
+    log::warn!("SIL: stopping the Core 1 airbrake controller");
+}
+
+/// End-of-run payload link checks, after one more link update to pick up
+/// the replies to the last cycle's frames.
+async fn check_payload(state: &mut state::FlightState) {
+    let mode = state.flight_mode as u8;
+    state.update_payload(mode).await;
+    let pl = &state.payload;
+    let executed = payload::executed();
+    let frames = payload::command_frames();
+    match (payload::connected(), pl.link_state()) {
+        (true, LinkState::Up) => log::info!(
+            "[PAYLOAD SIM] SUCCESS: payload link up, {} command(s) acted on from {frames} CMD frame(s)",
+            executed.len()
+        ),
+        (false, LinkState::Lost) => log::info!("[PAYLOAD SIM] SUCCESS: payload link reported lost after the cut"),
+        (connected, link) => {
+            log::error!("[PAYLOAD SIM] FAILED: payload link {link:?} with the sim link connected={connected}")
+        }
+    }
+    if payload::connected() {
+        match pl.failed() {
+            0 => log::info!("[PAYLOAD SIM] SUCCESS: no payload command went unacknowledged"),
+            n => log::error!("[PAYLOAD SIM] FAILED: {n} payload command(s) unacknowledged"),
+        }
+    }
+    if let Some(cmd) = pl.last_acked() {
+        if executed.contains(&cmd.id()) {
+            log::info!("[PAYLOAD SIM] SUCCESS: last acknowledged command {} was acted on", cmd.name());
+        } else {
+            log::error!("[PAYLOAD SIM] FAILED: {} acknowledged but never acted on", cmd.name());
+        }
+    }
+}
+
+/// End-of-run buzzer checks: what the buzzer pin sounded must decode back to
+/// the apogee once landed, and to the fault code after booting in Fault.
+fn check_buzzer(flight_loop: &flight_loop::FlightLoop) {
+    if flight_loop.landed() {
+        let apogee_ft = flight_loop.apogee_ft();
+        let heard = buzzer::numbers();
+        if heard.contains(&apogee_ft) {
+            log::info!("[BUZZER SIM] SUCCESS: heard the apogee readout {apogee_ft} ft");
+        } else {
+            log::error!("[BUZZER SIM] FAILED: apogee {apogee_ft} ft, heard numbers {heard:?}");
+        }
+    }
+    if flight_loop.booted_in_fault() {
+        let code = buzzer::code_for(flight_loop.fault_bits());
+        let heard = buzzer::codes();
+        if heard.contains(&code) {
+            log::info!("[BUZZER SIM] SUCCESS: heard the fault code \"{code}\"");
+        } else {
+            log::error!("[BUZZER SIM] FAILED: fault code \"{code}\", heard {heard:?}");
+        }
+    }
+}
+
+/// Keeps the flight loop running for `secs` after the scenario, holding the
+/// last altitude at rest, so long post-landing behaviour (the recovery
+/// beacon) can run. Sleeps between cycles as the firmware loop does and
+/// returns the cycles run in beacon mode and the sim time they spanned.
+async fn hold_on_ground(flight_loop: &mut flight_loop::FlightLoop, secs: u64) -> (u64, u64) {
+    log::info!("SIL: holding on the ground for {secs} s");
+    flight_loop.sim_altitude_override = Some(flight_loop.flight_state.packet.altitude);
+    flight_loop.sim_vel_d_override = Some(0.0);
+    flight_loop.sim_accel_y_override = Some(9.81);
+    flight_loop.sim_accel_z_override = Some(9.81);
+    let start = runtime::now_ms();
+    let mut beacon_start = None;
+    let mut beacon_cycles = 0;
+    while runtime::now_ms() - start < secs * 1000 {
+        if flight_loop.flight_state.beacon_mode() {
+            beacon_start.get_or_insert(runtime::now_ms());
+            beacon_cycles += 1;
+        }
+        flight_loop.execute().await;
+        Timer::after_millis(flight_loop.loop_delay_ms()).await;
+    }
+    flight_loop.sim_altitude_override = None;
+    flight_loop.sim_vel_d_override = None;
+    flight_loop.sim_accel_y_override = None;
+    flight_loop.sim_accel_z_override = None;
+    (beacon_cycles, beacon_start.map_or(0, |t| runtime::now_ms() - t))
+}
+
+/// End-of-run recovery beacon checks: once in beacon mode the radio must
+/// carry only beacons, one per `BEACON_PERIOD_MS`, with the IMU, altimeter
+/// and ADC powered down, the loop running well under 20 Hz (`cycles` in
+/// `beacon_ms`), and the last fix (and its age, if `gps_loss_s` dropped the
+/// fix) in the last beacon.
+fn check_beacon(gps_loss_s: Option<u64>, (cycles, beacon_ms): (u64, u64)) {
+    radio::poll();
+    let beacons = radio::beacons();
+    let Some(&(first_ms, _)) = beacons.first() else {
+        log::error!("[BEACON SIM] FAILED: beacon mode on but no beacon received");
+        return;
+    };
+    match radio::packet_times().iter().filter(|&&t| t > first_ms).count() {
+        0 => log::info!("[BEACON SIM] SUCCESS: no full telemetry after the first beacon"),
+        n => log::error!("[BEACON SIM] FAILED: {n} full packet(s) after the first beacon"),
+    }
+    let period = constants::BEACON_PERIOD_MS;
+    let gaps: Vec<u64> = beacons.windows(2).map(|w| w[1].0 - w[0].0).collect();
+    let off = gaps.iter().filter(|&&g| g.abs_diff(period) > 2 * constants::BEACON_LOOP_DELAY_MS).count();
+    if off == 0 {
+        log::info!("[BEACON SIM] SUCCESS: {} beacon(s), one every {} ms", beacons.len(), period);
+    } else {
+        log::error!("[BEACON SIM] FAILED: {off} of {} beacon gap(s) off the {period} ms period", gaps.len());
+    }
+    let w = world::get();
+    let sensors = [
+        ("IMU", lsm6dsox::powered_down(), w.imu_ok),
+        ("altimeter", bmp390::asleep(), w.baro_ok),
+        ("ADC", ads1015::powered_down(), w.adc_ok),
+    ];
+    let awake: Vec<&str> = sensors.iter().filter(|&&(_, off, ok)| ok && !off).map(|&(name, ..)| name).collect();
+    // A 20 Hz loop would have run beacon_ms / MAIN_LOOP_DELAY_MS cycles
+    let full_rate = beacon_ms / constants::MAIN_LOOP_DELAY_MS;
+    if !awake.is_empty() {
+        log::error!("[BEACON SIM] FAILED: {} still powered in beacon mode", awake.join(", "));
+    } else if cycles * 2 > full_rate {
+        log::error!("[BEACON SIM] FAILED: {cycles} cycles in {beacon_ms} ms of beacon mode; the loop didn't slow down");
+    } else {
+        log::info!("[BEACON SIM] SUCCESS: IMU, altimeter and ADC powered down; {cycles} cycles in {beacon_ms} ms (20 Hz: {full_rate})");
+    }
+
+    let &(last_ms, last) = beacons.last().unwrap();
+    let at_pad = last.latitude == w.latitude as f32 && last.longitude == w.longitude as f32;
+    // Ages are whole seconds and fixes land once a second
+    let expected_age_s = gps_loss_s.map(|s| (last_ms / 1000).saturating_sub(s));
+    let age_ok = match expected_age_s {
+        Some(age) => last.fix_age_s != BeaconPacket::NO_FIX && last.fix_age_s.abs_diff(age as u32) <= 1,
+        None => last.fix_age_s <= 1,
+    };
+    if at_pad && age_ok && last.battery_v == w.battery_v {
+        log::info!(
+            "[BEACON SIM] SUCCESS: last beacon at {:.6}, {:.6}, fix age {} s, apogee {} ft, battery {:.2} V",
+            last.latitude,
+            last.longitude,
+            last.fix_age_s,
+            last.apogee_ft,
+            last.battery_v
+        );
+    } else {
+        log::error!(
+            "[BEACON SIM] FAILED: last beacon at {:.6}, {:.6} with fix age {} s, battery {} V (expected the pad, age {} s, {} V)",
+            last.latitude,
+            last.longitude,
+            last.fix_age_s,
+            last.battery_v,
+            expected_age_s.unwrap_or(0),
+            w.battery_v
+        );
+    }
+}
+
+/// Mirrors the firmware `main`: build the flight state on the stand-in
+/// peripherals, then run the scenario the way the matching `sim_*` block does.
+#[embassy_executor::task]
+async fn sil_main(scenario: Scenario, ground_s: Option<u64>, gps_loss_s: Option<u64>) {
+    log::info!("Booting Cornell Rocketry FSW (SIL)...");
+    let flight_state = module::init_flight_state().await;
+    #[allow(unused_variables)]
+    let blims = module::init_blims();
+    #[allow(unused_mut)]
+    let mut flight_loop = flight_loop::FlightLoop::new(flight_state);
+
+    match scenario {
+        #[cfg(feature = "sim_simple")]
+        Scenario::Simple => {
+            log::info!("Starting Flight Simulation (Simple)...");
+            flight_sim::simulate_flight_simple(&mut flight_loop).await;
+        }
+        #[cfg(feature = "sim_launch")]
+        Scenario::Launch => flight_sim::simulate_launch_sequence(&mut flight_loop).await,
+        #[cfg(feature = "sim_fault")]
+        Scenario::Fault => {
+            log::info!("Starting Flight Simulation (Fault)...");
+            flight_sim::simulate_fault_scenarios(&mut flight_loop).await;
+        }
+        #[cfg(feature = "sim_stability")]
+        Scenario::Stability => {
+            log::info!("Starting Flight Simulation (Stability)...");
+            flight_sim::simulate_stability_scenarios(&mut flight_loop).await;
+        }
+        #[cfg(feature = "sim_extra")]
+        Scenario::Extra => {
+            log::info!("Starting Flight Simulation (Extra Features)...");
+            flight_sim::simulate_extra_features(&mut flight_loop).await;
+        }
+        #[cfg(feature = "sim_flash")]
+        Scenario::Flash => {
+            log::info!("Starting Flight Simulation (QSPI Flash Storage)...");
+            flight_sim::simulate_flash_storage(&mut flight_loop).await;
+        }
+        #[cfg(feature = "sim_real_flight")]
+        Scenario::RealFlight => {
+            log::info!("Starting Real Flight Simulation...");
+            flight_loop.set_blims(blims);
+            let snap_altitude = flight_loop.flight_state.packet.altitude;
+            flight_loop.flight_state.packet = packet::Packet::default();
+            flight_loop.flight_state.packet.altitude = snap_altitude;
+            flight_sim::simulate_real_flight(&mut flight_loop).await;
+        }
+        #[cfg(feature = "sim_blims")]
+        Scenario::Blims => {
+            log::info!("Starting BLiMS Descent Simulation...");
+            // Canopy drifting under the main at about its nominal ground
+            // speed, so BLiMS has a heading of motion to steer on
+            world::set(world::World { vel_n: 1.0, vel_e: 7.0, vel_d: 8.2, ..world::get() });
+            flight_loop.set_blims(blims);
+            flight_sim::simulate_blims_descent(&mut flight_loop).await;
+        }
+        #[cfg(feature = "sim_payload")]
+        Scenario::Payload => {
+            log::info!("Starting Payload Ground Command Simulation...");
+            flight_sim::simulate_payload_commands(&mut flight_loop).await;
+        }
+    }
+    let beacon_cycles = match ground_s {
+        Some(secs) => hold_on_ground(&mut flight_loop, secs).await,
+        None => (0, 0),
+    };
+    log::info!("Simulation Complete.");
+
+    // UART and RC PWM must never command an ODrive at the same time, and
+    // whichever path is live must have brought the motor to where the PWM
+    // pulse for the setpoint maps
+    let state = &flight_loop.flight_state;
+    let setpoints = [
+        (
+            "airbrake",
+            odrive::AIRBRAKE,
+            odrive::turns_at(odrive::AIRBRAKE, state.airbrake_system.pulse_us()),
+            odrive_task::get_airbrake_readback().link_up,
+        ),
+        (
+            "BLiMS",
+            odrive::BLIMS,
+            odrive::turns_at(
+                odrive::BLIMS,
+                state.blims_calibration().pulse_us(state.packet.blims_brakeline_diff),
+            ),
+            odrive_task::get_blims_readback().link_up,
+        ),
+    ];
+    for (name, id, setpoint, link_up) in setpoints {
+        match odrive::conflicts(id) {
+            0 => log::info!("[ODRIVE SIM] SUCCESS: {name} ODrive never saw UART and PWM commands together"),
+            n => log::error!("[ODRIVE SIM] FAILED: {name} ODrive got {n} UART setpoints while PWM was pulsing"),
+        }
+        let path = if link_up { "UART" } else { "RC PWM" };
+        let pos = odrive::position(id);
+        if (pos - setpoint).abs() < 0.1 {
+            log::info!("[ODRIVE SIM] SUCCESS: {name} ODrive at {pos:.3} turns via {path} (setpoint {setpoint:.3})");
+        } else {
+            log::error!("[ODRIVE SIM] FAILED: {name} ODrive at {pos:.3} turns via {path}, setpoint {setpoint:.3}");
+        }
+    }
+
+    // The payload board must have acted on whatever it acknowledged, and the
+    // link state the FSW reports must match whether the sim cut the link.
+    // Scenarios that never run a full cycle never start the link.
+    if payload::heartbeats() > 0 {
+        check_payload(&mut flight_loop.flight_state).await;
+    }
+    check_buzzer(&flight_loop);
+    if flight_loop.flight_state.beacon_mode() {
+        check_beacon(gps_loss_s, beacon_cycles);
+    }
+
+    // Let the ground station drain what the last cycle sent
+    Timer::after_millis(constants::MAIN_LOOP_DELAY_MS).await;
+    runtime::finish();
+}
@@ -0,0 +1,165 @@
+//! SIL counterpart of `src/module.rs`: the same init functions and bus type
+//! names, backed by host stand-ins. Pin numbers match the flight board so
+//! the harness can observe outputs by GPIO number.
+
+use embassy_rp::gpio::{Input, Level, Output, Pull};
+use embassy_rp::pwm::{Config as PwmConfig, Pwm};
+use embassy_rp::uart::{self, Uart};
+
+use crate::actuator::{AirbrakeActuator, Buzzer, Mav, SV, Ssa};
+
+/// Board GPIO assignments (see `main.rs` / `module.rs` in the firmware).
+pub mod pins {
+    pub const FLASH_CS: u8 = 6;
+    pub const ALTIMETER_CS: u8 = 7;
+    pub const ARMING_SWITCH: u8 = 10;
+    pub const BUZZER: u8 = 21;
+    pub const BLIMS_ENABLE: u8 = 34;
+    pub const BLIMS_PWM: u8 = 35;
+    pub const SSA_DROGUE: u8 = 36;
+    pub const AIRBRAKE_ENABLE: u8 = 37;
+    pub const AIRBRAKE_PWM: u8 = 38;
+    pub const SSA_MAIN: u8 = 39;
+    pub const MAV: u8 = 40;
+    pub const CFC_ARM: u8 = 41;
+    pub const SV: u8 = 47;
+}
+
+/// UART indices for `embassy_rp::uart::{inject_rx, take_tx}`.
+pub const PAYLOAD_UART: usize = 0;
+pub const RADIO_UART: usize = 1;
+
+/// ODrive UART links end at the simulated ODrives in `crate::odrive`.
+pub type AirbrakeOdriveLink = crate::odrive::SimOdriveLink;
+pub type BlimsOdriveLink = crate::odrive::SimOdriveLink;
+
+/// The payload UART ends at the simulated payload board in `crate::payload`.
+pub type PayloadUartLink = crate::payload::SimPayloadLink;
+
+/// Stand-in for a shared bus. The mock sensors read the simulated world
+/// directly; only the flash emulator sits behind a device type.
+pub struct SimBus;
+
+pub type SharedI2c = SimBus;
+pub type I2cDevice<'a> = &'a SimBus;
+
+pub type SharedSpi = SimBus;
+pub type SpiDevice<'a> = crate::flash::SpiFlash<'a>;
+
+/// Error returned by a mock sensor whose fault flag is set in the world.
+#[derive(Debug)]
+pub struct NotResponding;
+
+static BUS: SimBus = SimBus;
+
+pub fn init_shared_i2c() -> &'static SharedI2c {
+    &BUS
+}
+
+pub fn init_shared_spi() -> &'static SharedSpi {
+    &BUS
+}
+
+// Payload UART
+pub fn init_uart0() -> Uart<'static, uart::Async> {
+    Uart::new(PAYLOAD_UART)
+}
+
+// RFD900x radio UART
+pub fn init_uart1() -> Uart<'static, uart::Async> {
+    Uart::new(RADIO_UART)
+}
+
+pub fn init_actuators() -> (Ssa<'static>, Buzzer<'static>, Mav<'static>, SV<'static>) {
+    let ssa = Ssa::new(
+        Output::new(pins::SSA_DROGUE, Level::Low),
+        Output::new(pins::SSA_MAIN, Level::Low),
+    );
+    let mut buzzer_config = PwmConfig::default();
+    buzzer_config.top = 6249;
+    let buzzer = Buzzer::new(Pwm::new_output_b(pins::BUZZER, buzzer_config));
+    let mut mav_config = PwmConfig::default();
+    mav_config.top = 3030;
+    let mav = Mav::new(Pwm::new_output_a(pins::MAV, mav_config));
+    let sv = SV::new(Output::new(pins::SV, Level::High)); // Active Low, so High (Closed)
+    (ssa, buzzer, mav, sv)
+}
+
+pub fn init_airbrake() -> AirbrakeActuator<'static> {
+    let enable = Output::new(pins::AIRBRAKE_ENABLE, Level::Low);
+    let mut config = PwmConfig::default();
+    config.top = 59999;
+    AirbrakeActuator::new(enable, Pwm::new_output_a(pins::AIRBRAKE_PWM, config))
+}
+
+pub fn init_blims() -> blims::Blims<blims::PwmActuator<'static>> {
+    use blims::blims_constants::WRAP_CYCLE_COUNT;
+
+    let enable = Output::new(pins::BLIMS_ENABLE, Level::Low);
+    let mut config = PwmConfig::default();
+    config.top = WRAP_CYCLE_COUNT;
+    let pwm = Pwm::new_output_b(pins::BLIMS_PWM, config.clone());
+    blims::Blims::new(blims::PwmActuator::new(pwm, config, enable), blims::BlimsConfig::default())
+}
+
+pub fn init_odrive_links() -> (AirbrakeOdriveLink, BlimsOdriveLink) {
+    (
+        crate::odrive::SimOdriveLink::new(crate::odrive::AIRBRAKE),
+        crate::odrive::SimOdriveLink::new(crate::odrive::BLIMS),
+    )
+}
+
+/// Starts the ODrive tasks on the simulated links, as the firmware `main`
+/// does. Once per run: the tasks outlive any one `FlightState`.
+pub fn spawn_odrive_tasks(spawner: embassy_executor::Spawner) {
+    use crate::driver::odrive::Odrive;
+    use crate::odrive_task::{airbrake_odrive_task, blims_odrive_task};
+
+    let (airbrake, blims) = init_odrive_links();
+    spawner.spawn(airbrake_odrive_task(Odrive::new(airbrake, "airbrake"))).unwrap();
+    spawner.spawn(blims_odrive_task(Odrive::new(blims, "BLiMS"))).unwrap();
+}
+
+pub fn init_onboard_flash(
+    _spi_bus: &'static SharedSpi,
+    cs: Output<'static>,
+) -> crate::driver::onboard_flash::OnboardFlash<'static> {
+    crate::driver::onboard_flash::OnboardFlash::new(crate::flash::SpiFlash::new(cs))
+}
+
+pub fn init_supply_monitor() -> crate::driver::supply::SupplyMonitor {
+    crate::driver::supply::SupplyMonitor
+}
+
+/// Builds the flight state on fresh stand-in peripherals, the way the
+/// firmware `main` does before any mode runs.
+pub async fn init_flight_state() -> crate::state::FlightState {
+    let i2c_bus = init_shared_i2c();
+    let spi_bus = init_shared_spi();
+    let uart = init_uart1();
+    let altimeter_cs = Output::new(pins::ALTIMETER_CS, Level::High);
+    let arming_switch = Input::new(pins::ARMING_SWITCH, Pull::Down);
+    let cfc_arm = Input::new(pins::CFC_ARM, Pull::Down);
+    let (ssa, buzzer, mav, sv) = init_actuators();
+    let flash_cs = Output::new(pins::FLASH_CS, Level::High);
+    let flash = init_onboard_flash(spi_bus, flash_cs);
+    let airbrake_system = init_airbrake();
+
+    crate::state::FlightState::new(
+        i2c_bus,
+        spi_bus,
+        altimeter_cs,
+        arming_switch,
+        cfc_arm,
+        uart,
+        ssa,
+        buzzer,
+        mav,
+        sv,
+        airbrake_system,
+        flash,
+        crate::payload::SimPayloadLink,
+        init_supply_monitor(),
+    )
+    .await
+}
@@ -0,0 +1,223 @@
+//! Simulated ODrive S1s on the far end of the FSW's ODrive UART links.
+//!
+//! Each one speaks the ASCII subset `driver/odrive.rs` uses (`p`, `f`, `r`),
+//! checks and adds `*checksum`s, and moves its motor toward `input_pos` with
+//! a first-order lag. Like the real ODrive's RC PWM input mapping, a valid
+//! pulse on the FSW's PWM pin also sets `input_pos` through
+//! `gpio8_pwm_mapping`, and a held-low pin leaves it alone; a `p` arriving
+//! while the pin is pulsing is counted as a conflict, since on hardware the
+//! two sources would fight.
+//!
+//! `set_connected(id, false)` cuts a link (writes dropped, no replies) to
+//! exercise the PWM fallback; `set_pwm_mapping` changes what the FSW reads
+//! back at link-up.
+
+use core::cell::RefCell;
+use std::collections::VecDeque;
+
+use critical_section::Mutex;
+
+use crate::driver::odrive::{OdriveError, OdriveLink, checksum};
+use crate::module::pins;
+use crate::runtime;
+
+pub const AIRBRAKE: usize = 0;
+pub const BLIMS: usize = 1;
+
+/// Position response time constant
+const TAU_S: f32 = 0.05;
+const VBUS_V: f32 = 24.0;
+/// RC PWM frame period both PWM outputs run at
+const PWM_PERIOD_US: f32 = 20_000.0;
+/// Airbrake `gpio8_pwm_mapping` (turns at 1000 µs, 2000 µs). The FSW reads
+/// it at link-up, so any travel works; this one is deliberately not a round
+/// number.
+const AIRBRAKE_PWM_MAPPING: (f32, f32) = (0.0, 3.7);
+/// BLiMS `gpio8_pwm_mapping`, as in `BLIMS/odrive_config.json`
+const BLIMS_PWM_MAPPING: (f32, f32) = (-10.5, 10.5);
+
+struct SimOdrive {
+    connected: bool,
+    /// `gpio8_pwm_mapping` min/max; None until first used, then the default
+    pwm_mapping: Option<(f32, f32)>,
+    pos: f32,
+    vel: f32,
+    input_pos: f32,
+    last_ms: u64,
+    conflicts: u32,
+    line: Vec<u8>,
+    rx: VecDeque<u8>,
+}
+
+impl SimOdrive {
+    const fn new() -> Self {
+        Self {
+            connected: true,
+            pwm_mapping: None,
+            pos: 0.0,
+            vel: 0.0,
+            input_pos: 0.0,
+            last_ms: 0,
+            conflicts: 0,
+            line: Vec::new(),
+            rx: VecDeque::new(),
+        }
+    }
+}
+
+static ODRIVES: [Mutex<RefCell<SimOdrive>>; 2] = [const { Mutex::new(RefCell::new(SimOdrive::new())) }; 2];
+
+fn with<R>(id: usize, f: impl FnOnce(&mut SimOdrive) -> R) -> R {
+    critical_section::with(|cs| {
+        let mut od = ODRIVES[id].borrow_ref_mut(cs);
+        step(id, &mut od);
+        f(&mut od)
+    })
+}
+
+fn pwm_pin(id: usize) -> u8 {
+    if id == AIRBRAKE { pins::AIRBRAKE_PWM } else { pins::BLIMS_PWM }
+}
+
+/// The turns at 1000 µs / 2000 µs this ODrive's PWM input maps to.
+fn pwm_mapping(id: usize, od: &mut SimOdrive) -> (f32, f32) {
+    *od.pwm_mapping.get_or_insert(if id == AIRBRAKE { AIRBRAKE_PWM_MAPPING } else { BLIMS_PWM_MAPPING })
+}
+
+fn mapped_turns((min, max): (f32, f32), pulse_us: f32) -> f32 {
+    let frac = ((pulse_us - 1000.0) / 1000.0).clamp(0.0, 1.0);
+    min + frac * (max - min)
+}
+
+/// Input position from the RC PWM pin, or None while it isn't pulsing.
+fn pwm_input(id: usize, od: &mut SimOdrive) -> Option<f32> {
+    let pulse_us = embassy_rp::pwm::duty(pwm_pin(id)) * PWM_PERIOD_US;
+    if !(900.0..=2100.0).contains(&pulse_us) {
+        return None;
+    }
+    Some(mapped_turns(pwm_mapping(id, od), pulse_us))
+}
+
+/// Advances the motor to the current sim time.
+fn step(id: usize, od: &mut SimOdrive) {
+    if let Some(input) = pwm_input(id, od) {
+        od.input_pos = input;
+    }
+    let now = runtime::now_ms();
+    let dt = (now - od.last_ms) as f32 / 1000.0;
+    od.last_ms = now;
+    if dt > 0.0 {
+        let prev = od.pos;
+        od.pos += (od.input_pos - od.pos) * (1.0 - (-dt / TAU_S).exp());
+        od.vel = (od.pos - prev) / dt;
+    }
+}
+
+fn handle_line(id: usize, od: &mut SimOdrive, line: &str) {
+    let line = line.trim_end_matches('\r');
+    let (body, checked) = match line.rsplit_once('*') {
+        Some((body, sum)) => {
+            if sum.parse::<u8>().ok() != Some(checksum(body.as_bytes())) {
+                return; // the ODrive drops corrupted commands silently
+            }
+            (body, true)
+        }
+        None => (line, false),
+    };
+
+    let mut words = body.split_ascii_whitespace();
+    let reply = match (words.next(), words.next(), words.next()) {
+        (Some("p"), Some("0"), Some(pos)) => {
+            if let Ok(pos) = pos.parse::<f32>() {
+                if pwm_input(id, od).is_some() {
+                    od.conflicts += 1;
+                }
+                od.input_pos = pos;
+            }
+            return;
+        }
+        (Some("f"), Some("0"), None) => format!("{:.4} {:.4}", od.pos, od.vel),
+        (Some("r"), Some(prop), None) => match prop {
+            "vbus_voltage" => format!("{VBUS_V:.4}"),
+            "ibus" => format!("{:.4}", 0.02 + 0.5 * od.vel.abs()),
+            "axis0.current_state" => "8".to_string(),
+            "axis0.active_errors" | "axis0.disarm_reason" => "0".to_string(),
+            "config.gpio8_pwm_mapping.min" => format!("{:.4}", pwm_mapping(id, od).0),
+            "config.gpio8_pwm_mapping.max" => format!("{:.4}", pwm_mapping(id, od).1),
+            _ => "invalid property".to_string(),
+        },
+        _ => "invalid command format".to_string(),
+    };
+    let reply = if checked { format!("{reply}*{}\n", checksum(reply.as_bytes())) } else { format!("{reply}\n") };
+    od.rx.extend(reply.as_bytes());
+}
+
+/// Connects or cuts the UART link to one simulated ODrive.
+pub fn set_connected(id: usize, connected: bool) {
+    with(id, |od| {
+        od.connected = connected;
+        od.line.clear();
+        od.rx.clear();
+    });
+}
+
+/// Sets the `gpio8_pwm_mapping` the ODrive reports and its PWM input uses.
+pub fn set_pwm_mapping(id: usize, min: f32, max: f32) {
+    with(id, |od| od.pwm_mapping = Some((min, max)));
+}
+
+/// Where a `pulse_us` pulse on the PWM pin would put the motor (turns).
+pub fn turns_at(id: usize, pulse_us: f32) -> f32 {
+    with(id, |od| mapped_turns(pwm_mapping(id, od), pulse_us))
+}
+
+/// Current motor position (turns).
+pub fn position(id: usize) -> f32 {
+    with(id, |od| od.pos)
+}
+
+/// `p` commands received while the PWM pin was also pulsing.
+pub fn conflicts(id: usize) -> u32 {
+    with(id, |od| od.conflicts)
+}
+
+/// FSW end of one simulated ODrive's UART link.
+pub struct SimOdriveLink {
+    id: usize,
+}
+
+impl SimOdriveLink {
+    pub fn new(id: usize) -> Self {
+        Self { id }
+    }
+}
+
+impl OdriveLink for SimOdriveLink {
+    async fn write(&mut self, data: &[u8]) -> Result<(), OdriveError> {
+        let id = self.id;
+        with(id, |od| {
+            if !od.connected {
+                return;
+            }
+            for &b in data {
+                if b == b'\n' {
+                    let line = String::from_utf8_lossy(&od.line).into_owned();
+                    od.line.clear();
+                    handle_line(id, od, &line);
+                } else {
+                    od.line.push(b);
+                }
+            }
+        });
+        Ok(())
+    }
+
+    async fn read_byte(&mut self) -> Result<u8, OdriveError> {
+        // Replies are queued as soon as the command is written; an empty
+        // queue means nothing more is coming, so wait for the caller's timeout.
+        match with(self.id, |od| if od.connected { od.rx.pop_front() } else { None }) {
+            Some(b) => Ok(b),
+            None => core::future::pending().await,
+        }
+    }
+}
@@ -0,0 +1,218 @@
+//! Simulated payload board on the far end of the FSW's payload UART.
+//!
+//! Speaks the frame format in `driver/payload.rs`: ACKs every `CMD` with its
+//! seq, acts on a command only the first time its seq arrives (a retry is
+//! acked again but not re-run), and answers each `HEARTBEAT` with a `STATUS`.
+//! A `HELLO` (the CFC has booted) clears the retry dedupe and is acked.
+//! Without the `payload_frames` feature it reads the FSW's ASCII lines
+//! instead, acting on every command line and replying to nothing.
+//!
+//! `set_connected(false)` cuts the link (writes dropped, no replies) to
+//! exercise link-loss reporting; `drop_acks(n)` loses the next `n` ACKs on
+//! the wire, so the FSW retries a command the board has already acted on.
+
+// Built for one protocol at a time; the other's handling goes unused
+#![cfg_attr(not(feature = "payload_frames"), allow(dead_code))]
+
+use core::cell::RefCell;
+use std::collections::VecDeque;
+
+use critical_section::Mutex;
+
+use crate::driver::payload::{
+    FrameDecoder, MAX_FRAME, PayloadCommand, PayloadError, PayloadLink, encode, frame_type,
+};
+
+/// Board state reported in STATUS: 0 until the first heartbeat, then 1
+const STATE_READY: u8 = 1;
+const VBAT_MV: u16 = 7_400;
+
+struct SimPayload {
+    connected: bool,
+    decoder: FrameDecoder,
+    rx: VecDeque<u8>,
+    state: u8,
+    /// Seq of the last command acted on, for retry dedupe
+    last_seq: Option<u8>,
+    /// Command ids acted on, in order
+    executed: Vec<u8>,
+    command_frames: u32,
+    heartbeats: u32,
+    drop_acks: u32,
+    /// ASCII line being received
+    #[cfg_attr(feature = "payload_frames", allow(dead_code))]
+    line: String,
+    /// ASCII command lines received, heartbeats left out
+    lines: Vec<String>,
+}
+
+impl SimPayload {
+    const fn new() -> Self {
+        Self {
+            connected: true,
+            decoder: FrameDecoder::new(),
+            rx: VecDeque::new(),
+            state: 0,
+            last_seq: None,
+            executed: Vec::new(),
+            command_frames: 0,
+            heartbeats: 0,
+            drop_acks: 0,
+            line: String::new(),
+            lines: Vec::new(),
+        }
+    }
+
+    fn reply(&mut self, kind: u8, seq: u8, body: &[u8]) {
+        let mut frame = [0u8; MAX_FRAME];
+        let n = encode(kind, seq, body, &mut frame);
+        self.rx.extend(&frame[..n]);
+    }
+
+    fn handle(&mut self, kind: u8, seq: u8, body: &[u8]) {
+        match kind {
+            frame_type::CMD => {
+                self.command_frames += 1;
+                let Some(&id) = body.first() else {
+                    return;
+                };
+                if self.last_seq != Some(seq) {
+                    log::info!("PAYLOAD SIM: {} (seq {})", PayloadCommand::name_of(id), seq);
+                    self.executed.push(id);
+                    self.last_seq = Some(seq);
+                }
+                if self.drop_acks > 0 {
+                    self.drop_acks -= 1;
+                    return;
+                }
+                self.reply(frame_type::ACK, seq, &[id]);
+            }
+            frame_type::HELLO => {
+                self.last_seq = None;
+                if self.drop_acks > 0 {
+                    self.drop_acks -= 1;
+                    return;
+                }
+                self.reply(frame_type::ACK, seq, &[0]);
+            }
+            frame_type::HEARTBEAT => {
+                self.heartbeats += 1;
+                self.state = STATE_READY;
+                let vbat = VBAT_MV.to_le_bytes();
+                let last_cmd = self.executed.last().copied().unwrap_or(0);
+                self.reply(frame_type::STATUS, seq, &[self.state, 0, vbat[0], vbat[1], last_cmd]);
+            }
+            _ => {}
+        }
+    }
+}
+
+impl SimPayload {
+    /// One ASCII line from the FSW, newline stripped
+    #[cfg_attr(feature = "payload_frames", allow(dead_code))]
+    fn handle_line(&mut self, line: String) {
+        if line == "A" {
+            self.heartbeats += 1;
+            return;
+        }
+        let name = line.split(',').next().unwrap_or_default();
+        if let Some(id) = (1..=7).find(|&id| PayloadCommand::name_of(id) == name) {
+            log::info!("PAYLOAD SIM: {}", line);
+            self.command_frames += 1;
+            self.executed.push(id);
+        }
+        self.lines.push(line);
+    }
+}
+
+static PAYLOAD: Mutex<RefCell<SimPayload>> = Mutex::new(RefCell::new(SimPayload::new()));
+
+fn with<R>(f: impl FnOnce(&mut SimPayload) -> R) -> R {
+    critical_section::with(|cs| f(&mut PAYLOAD.borrow_ref_mut(cs)))
+}
+
+/// Connects or cuts the payload UART link.
+pub fn set_connected(connected: bool) {
+    with(|pl| {
+        pl.connected = connected;
+        pl.decoder = FrameDecoder::new();
+        pl.rx.clear();
+    });
+}
+
+pub fn connected() -> bool {
+    with(|pl| pl.connected)
+}
+
+/// Loses the next `n` ACKs on the wire (STATUS replies still pass).
+pub fn drop_acks(n: u32) {
+    with(|pl| pl.drop_acks = n);
+}
+
+/// Command ids the board has acted on, in order (1–4 = N1–N4, 5–7 = A1–A3).
+pub fn executed() -> Vec<u8> {
+    with(|pl| pl.executed.clone())
+}
+
+/// ASCII command lines received, in order (no heartbeats).
+pub fn lines() -> Vec<String> {
+    with(|pl| pl.lines.clone())
+}
+
+/// Command frames received, retries included.
+pub fn command_frames() -> u32 {
+    with(|pl| pl.command_frames)
+}
+
+/// Heartbeats received; 0 means the scenario never ran the payload link.
+pub fn heartbeats() -> u32 {
+    with(|pl| pl.heartbeats)
+}
+
+/// Powers the board back up from scratch, for back-to-back runs in one process.
+pub fn reset() {
+    with(|pl| *pl = SimPayload::new());
+}
+
+/// FSW end of the payload UART link.
+pub struct SimPayloadLink;
+
+impl PayloadLink for SimPayloadLink {
+    async fn write(&mut self, data: &[u8]) -> Result<(), PayloadError> {
+        with(|pl| {
+            if !pl.connected {
+                return;
+            }
+            for &b in data {
+                #[cfg(feature = "payload_frames")]
+                // A corrupted frame is dropped, as the payload firmware does
+                if let Some(Ok(frame)) = pl.decoder.push(b) {
+                    pl.handle(frame.kind, frame.seq, frame.body());
+                }
+                #[cfg(not(feature = "payload_frames"))]
+                match b {
+                    b'\n' => {
+                        let line = core::mem::take(&mut pl.line);
+                        pl.handle_line(line);
+                    }
+                    _ => pl.line.push(b as char),
+                }
+            }
+        });
+        Ok(())
+    }
+
+    fn try_read(&mut self, buf: &mut [u8]) -> usize {
+        // Replies are queued as soon as the frame is written
+        with(|pl| {
+            if !pl.connected {
+                return 0;
+            }
+            let n = buf.len().min(pl.rx.len());
+            for (dst, b) in buf.iter_mut().zip(pl.rx.drain(..n)) {
+                *dst = b;
+            }
+            n
+        })
+    }
+}
@@ -0,0 +1,109 @@
+//! Ground receiver on the far end of the FSW's RFD900x link.
+//!
+//! Drains the radio UART and splits what the FSW sent into frames by sync
+//! word: full telemetry (`SYNC_WORD` + `Packet::SIZE` bytes), recovery
+//! beacons (`BEACON_SYNC_WORD` + `BeaconPacket::SIZE` bytes) and config
+//! packets (`CONFIG_SYNC_WORD` + `ConfigPacket::SIZE` bytes), recording when
+//! each arrived so checks can look at the beacon period and at what the FSW
+//! still sent after switching to beacons.
+
+use core::cell::RefCell;
+
+use critical_section::Mutex;
+use embassy_time::Timer;
+
+use crate::driver::rfd900x::Rfd900x;
+use crate::module::RADIO_UART;
+use crate::packet::{BeaconPacket, ConfigPacket, Packet};
+use crate::runtime;
+
+const POLL_MS: u64 = 50;
+
+struct Receiver {
+    buf: Vec<u8>,
+    /// Arrival of each full packet, in sim ms
+    packets: Vec<u64>,
+    beacons: Vec<(u64, BeaconPacket)>,
+    configs: Vec<(u64, ConfigPacket)>,
+    /// Bytes skipped hunting for a sync word
+    skipped: usize,
+}
+
+static RX: Mutex<RefCell<Receiver>> = Mutex::new(RefCell::new(Receiver {
+    buf: Vec::new(),
+    packets: Vec::new(),
+    beacons: Vec::new(),
+    configs: Vec::new(),
+    skipped: 0,
+}));
+
+#[embassy_executor::task]
+pub async fn listen_task() {
+    loop {
+        poll();
+        Timer::after_millis(POLL_MS).await;
+    }
+}
+
+/// Takes whatever the FSW has written to the radio since the last poll.
+pub fn poll() {
+    let bytes = embassy_rp::uart::take_tx(RADIO_UART);
+    let now = runtime::now_ms();
+    critical_section::with(|cs| {
+        let rx = &mut *RX.borrow_ref_mut(cs);
+        rx.buf.extend_from_slice(&bytes);
+        loop {
+            let sync = match rx.buf.get(..4) {
+                Some(s) if s == Rfd900x::SYNC_WORD => Rfd900x::SYNC_WORD,
+                Some(s) if s == Rfd900x::BEACON_SYNC_WORD => Rfd900x::BEACON_SYNC_WORD,
+                Some(s) if s == Rfd900x::CONFIG_SYNC_WORD => Rfd900x::CONFIG_SYNC_WORD,
+                Some(_) => {
+                    rx.buf.remove(0);
+                    rx.skipped += 1;
+                    continue;
+                }
+                None => break,
+            };
+            let len = match sync {
+                Rfd900x::BEACON_SYNC_WORD => BeaconPacket::SIZE,
+                Rfd900x::CONFIG_SYNC_WORD => ConfigPacket::SIZE,
+                _ => Packet::SIZE,
+            };
+            if rx.buf.len() < 4 + len {
+                break;
+            }
+            match sync {
+                Rfd900x::BEACON_SYNC_WORD => {
+                    let b = BeaconPacket::from_bytes(&rx.buf[4..]);
+                    rx.beacons.push((now, b));
+                }
+                Rfd900x::CONFIG_SYNC_WORD => {
+                    let c = ConfigPacket::from_bytes(&rx.buf[4..]);
+                    rx.configs.push((now, c));
+                }
+                _ => rx.packets.push(now),
+            }
+            rx.buf.drain(..4 + len);
+        }
+    });
+}
+
+/// Arrival times of the full telemetry packets received.
+pub fn packet_times() -> Vec<u64> {
+    critical_section::with(|cs| RX.borrow_ref(cs).packets.clone())
+}
+
+/// Beacons received, with their arrival times.
+pub fn beacons() -> Vec<(u64, BeaconPacket)> {
+    critical_section::with(|cs| RX.borrow_ref(cs).beacons.clone())
+}
+
+/// Config packets received, with their arrival times.
+pub fn configs() -> Vec<(u64, ConfigPacket)> {
+    critical_section::with(|cs| RX.borrow_ref(cs).configs.clone())
+}
+
+/// Bytes that were not part of any frame.
+pub fn skipped() -> usize {
+    critical_section::with(|cs| RX.borrow_ref(cs).skipped)
+}
@@ -0,0 +1,128 @@
+//! Simulated Embassy time and a single-threaded executor that drives it.
+//!
+//! The clock only moves when every task is idle: the executor polls until
+//! nothing is pending, then jumps straight to the earliest timer deadline.
+//! A 20 Hz flight loop therefore costs only its compute time, and a run is
+//! fully deterministic — the same inputs always give the same log.
+
+use core::cell::RefCell;
+use core::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use core::task::Waker;
+
+use critical_section::Mutex;
+use embassy_executor::{Spawner, raw};
+use embassy_time_driver::Driver;
+
+static NOW_TICKS: AtomicU64 = AtomicU64::new(0);
+static ALARMS: Mutex<RefCell<Vec<(u64, Waker)>>> = Mutex::new(RefCell::new(Vec::new()));
+static PENDED: AtomicBool = AtomicBool::new(false);
+static FINISHED: AtomicBool = AtomicBool::new(false);
+
+struct SimClock;
+
+impl Driver for SimClock {
+    fn now(&self) -> u64 {
+        NOW_TICKS.load(Ordering::Relaxed)
+    }
+
+    fn schedule_wake(&self, at: u64, waker: &Waker) {
+        if at <= self.now() {
+            waker.wake_by_ref();
+            return;
+        }
+        critical_section::with(|cs| {
+            let mut alarms = ALARMS.borrow_ref_mut(cs);
+            // A task re-registers on every poll; keep one entry per waker
+            // at its earliest deadline.
+            match alarms.iter_mut().find(|(_, w)| w.will_wake(waker)) {
+                Some(entry) => entry.0 = entry.0.min(at),
+                None => alarms.push((at, waker.clone())),
+            }
+        });
+    }
+}
+
+embassy_time_driver::time_driver_impl!(static CLOCK: SimClock = SimClock);
+
+#[unsafe(export_name = "__pender")]
+fn pender(_context: *mut ()) {
+    PENDED.store(true, Ordering::Release);
+}
+
+/// Sim milliseconds since boot.
+pub fn now_ms() -> u64 {
+    NOW_TICKS.load(Ordering::Relaxed) * 1000 / embassy_time_driver::TICK_HZ
+}
+
+/// Jumps the clock to the earliest pending deadline and wakes every task due
+/// by then. Returns false if no task is waiting on time.
+fn advance() -> bool {
+    let due: Vec<Waker> = critical_section::with(|cs| {
+        let mut alarms = ALARMS.borrow_ref_mut(cs);
+        let Some(next) = alarms.iter().map(|(at, _)| *at).min() else {
+            return Vec::new();
+        };
+        NOW_TICKS.fetch_max(next, Ordering::Relaxed);
+        let mut due = Vec::new();
+        alarms.retain(|(at, w)| {
+            if *at <= next {
+                due.push(w.clone());
+                false
+            } else {
+                true
+            }
+        });
+        due
+    });
+    let any = !due.is_empty();
+    for w in due {
+        w.wake();
+    }
+    any
+}
+
+/// Ends the run once the current poll returns. Called by the scenario task.
+pub fn finish() {
+    FINISHED.store(true, Ordering::Release);
+}
+
+pub enum RunError {
+    /// Every task is blocked on something other than time.
+    Deadlock { at_ms: u64 },
+    /// The sim clock passed the run's time budget.
+    Timeout { at_ms: u64 },
+}
+
+impl core::fmt::Display for RunError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            RunError::Deadlock { at_ms } => write!(f, "deadlock at {at_ms} ms: no runnable task and no pending timer"),
+            RunError::Timeout { at_ms } => write!(f, "sim-time budget exceeded at {at_ms} ms"),
+        }
+    }
+}
+
+/// Runs the executor until [`finish`] is called.
+pub fn run(max_sim_ms: u64, init: impl FnOnce(Spawner)) -> Result<(), RunError> {
+    let executor: &'static raw::Executor = Box::leak(Box::new(raw::Executor::new(core::ptr::null_mut())));
+    init(executor.spawner());
+
+    loop {
+        PENDED.store(false, Ordering::Release);
+        // SAFETY: single-threaded; `poll` is never re-entered.
+        unsafe { executor.poll() };
+
+        if FINISHED.load(Ordering::Acquire) {
+            return Ok(());
+        }
+        if PENDED.load(Ordering::Acquire) {
+            continue;
+        }
+        if !advance() {
+            return Err(RunError::Deadlock { at_ms: now_ms() });
+        }
+        if now_ms() > max_sim_ms {
+            return Err(RunError::Timeout { at_ms: now_ms() });
+        }
+    }
+}
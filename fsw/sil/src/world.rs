@@ -0,0 +1,80 @@
+//! The simulated environment the mock drivers sample. Defaults to a rocket
+//! sitting on the pad at Spaceport America; a harness can replace it between
+//! flight-loop cycles to fly a trajectory or inject sensor faults.
+
+use std::sync::Mutex;
+
+#[derive(Clone, Copy, Debug)]
+pub struct World {
+    /// Static pressure at the altimeter (Pa).
+    pub pressure: f32,
+    pub temp: f32,
+    /// Body-frame specific force (m/s²); +Y is along the airframe, so a
+    /// rocket at rest on the rail reads about -g on Y.
+    pub accel: [f32; 3],
+    pub gyro: [f32; 3],
+    pub latitude: f64,
+    pub longitude: f64,
+    pub num_satellites: u32,
+    pub fix_type: u8,
+    pub vel_n: f64,
+    pub vel_e: f64,
+    pub vel_d: f64,
+    pub h_acc: u32,
+    pub v_acc: u32,
+    /// Unix epoch ms at sim boot; GPS solutions report this plus sim time.
+    pub utc_at_boot_ms: u64,
+    pub pt3: f32,
+    pub pt4: f32,
+    pub rtd: f32,
+    /// Flight battery and pyro rail (V), as the supply monitor measures them
+    pub battery_v: f32,
+    pub pyro_v: f32,
+    // Fault injection: a sensor that isn't `ok` fails every read (and init,
+    // if cleared before boot).
+    pub baro_ok: bool,
+    pub imu_ok: bool,
+    pub gps_ok: bool,
+    pub adc_ok: bool,
+}
+
+impl World {
+    pub const PAD: World = World {
+        pressure: 86_000.0,
+        temp: 25.0,
+        accel: [0.0, -9.81, 0.0],
+        gyro: [0.0; 3],
+        latitude: 32.9405,
+        longitude: -106.9110,
+        num_satellites: 12,
+        fix_type: 3,
+        vel_n: 0.0,
+        vel_e: 0.0,
+        vel_d: 0.0,
+        h_acc: 1_500,
+        v_acc: 2_500,
+        // 2026-06-20T14:00:00Z
+        utc_at_boot_ms: 1_781_964_000_000,
+        pt3: 0.0,
+        pt4: 0.0,
+        rtd: 25.0,
+        battery_v: 8.2,
+        pyro_v: 8.2,
+        baro_ok: true,
+        imu_ok: true,
+        gps_ok: true,
+        adc_ok: true,
+    };
+}
+
+static WORLD: Mutex<World> = Mutex::new(World::PAD);
+
+/// Current environment.
+pub fn get() -> World {
+    *WORLD.lock().unwrap()
+}
+
+/// Replaces the environment the next sensor reads will see.
+pub fn set(world: World) {
+    *WORLD.lock().unwrap() = world;
+}
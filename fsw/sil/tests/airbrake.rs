@@ -0,0 +1,135 @@
+//! The airbrake controller on Core 1 through the recorded flight: config
+//! over the umbilical and what the flight loop does when Core 1 stops
+//! answering.
+
+use controller_in_rust_v3::Config as AirbrakeConfig;
+use controller_in_rust_v3::constants::{DRAG_EST_MAX_SCALE, DRAG_EST_MIN_SCALE};
+use embassy_time::Instant;
+use fsw_sil::bench::{self, RecordedFlight};
+use fsw_sil::state::FlightMode;
+use fsw_sil::umbilical::UmbilicalCommand;
+use fsw_sil::{constants, radio, runtime};
+
+/// Below the flight's ~1050 m apogee, so the controller deploys in Coast
+const TARGET_M: f32 = 600.0;
+/// Cycles between the first deployment and the Core 1 stop
+const STOP_AFTER_CYCLES: u32 = 20;
+/// AIRBRAKE_CORE1_TIMEOUT_MS plus one 50 ms loop cycle, in cycles
+const DETECT_CYCLES: u32 = ((constants::AIRBRAKE_CORE1_TIMEOUT_MS + constants::MAIN_LOOP_DELAY_MS) / constants::MAIN_LOOP_DELAY_MS) as u32;
+
+/// Sets the target apogee over the umbilical, then flies. From Standby on
+/// the config must hold the new target and the 1 Hz config packets on the
+/// radio must echo it, the airbrakes must deploy during
+/// Coast, and the drag estimate Core 1 reports must stay within the bounds
+/// it holds it to around the configured model (this flight's coast is too
+/// slow for it to move far). A reboot on the same flash, with no config
+/// sent, must come up with the stored target.
+#[test]
+fn target_apogee_config() {
+    bench::run(300_000, |spawner| spawner.spawn(target_apogee_config_scenario()).unwrap());
+}
+
+#[embassy_executor::task]
+async fn target_apogee_config_scenario() {
+    let mut flight_loop = bench::boot().await;
+    let config = AirbrakeConfig { target_apogee_m: TARGET_M, ..AirbrakeConfig::default() };
+    bench::send(UmbilicalCommand::SetAirbrakeConfig(config));
+    bench::launch(&mut flight_loop).await;
+    radio::poll();
+    let launch_ms = Instant::now().as_millis();
+
+    let mut flight = RecordedFlight::default();
+    let (mut off_target, mut unbounded, mut coast, mut deployed) = (0, 0, 0, 0);
+    while flight.step(&mut flight_loop).await {
+        let (p, config) = (&flight_loop.flight_state.packet, &flight_loop.flight_state.config);
+        if config.airbrake_target_apogee != TARGET_M {
+            off_target += 1;
+        }
+        let body = p.airbrake_est_body_cda / config.airbrake_body_cda;
+        if !(DRAG_EST_MIN_SCALE..=DRAG_EST_MAX_SCALE).contains(&body) || !p.airbrake_est_brake_cda.is_finite() {
+            unbounded += 1;
+        }
+        if flight_loop.flight_state.flight_mode == FlightMode::Coast {
+            coast += 1;
+            if p.airbrake_deployment > 0.0 {
+                deployed += 1;
+            }
+        }
+    }
+    assert_eq!(off_target, 0, "cycles where the config did not hold the {TARGET_M} m target");
+    radio::poll();
+    let configs: Vec<_> = radio::configs().into_iter().filter(|&(t, _)| t > launch_ms).collect();
+    let wrong = configs.iter().filter(|(_, c)| c.airbrake_target_apogee != TARGET_M).count();
+    assert_eq!(wrong, 0, "config packets without the {TARGET_M} m target");
+    let period = constants::CONFIG_PACKET_PERIOD_MS;
+    let gaps: Vec<u64> = configs.windows(2).map(|w| w[1].0 - w[0].0).collect();
+    assert!(
+        !gaps.is_empty() && gaps.iter().all(|&g| g.abs_diff(period) <= constants::MAIN_LOOP_DELAY_MS),
+        "config packet gaps {gaps:?} off the {period} ms period"
+    );
+    assert_eq!(unbounded, 0, "cycles with the drag estimate missing or outside its bounds");
+    assert!(deployed > 0, "airbrakes never deployed in {coast} Coast cycles with a {TARGET_M} m target");
+
+    let mut flight_loop = bench::reboot().await;
+    bench::cycles(&mut flight_loop, 1).await;
+    assert_eq!(flight_loop.flight_state.config.airbrake_target_apogee, TARGET_M, "target after reboot");
+    runtime::finish();
+}
+
+/// Flies with a target low enough that the airbrakes deploy in Coast, and
+/// stops the Core 1 controller a second after they do. Until the stop the
+/// packet must report a healthy controller every cycle; once its output is
+/// older than `AIRBRAKE_CORE1_TIMEOUT_MS` (plus a cycle) it must report it
+/// stale with the airbrakes retracted for the rest of Coast.
+#[test]
+fn core1_stall_retracts() {
+    bench::run(300_000, |spawner| spawner.spawn(core1_stall_retracts_scenario()).unwrap());
+}
+
+#[embassy_executor::task]
+async fn core1_stall_retracts_scenario() {
+    let mut flight_loop = bench::boot().await;
+    let config = AirbrakeConfig { target_apogee_m: TARGET_M, ..AirbrakeConfig::default() };
+    bench::send(UmbilicalCommand::SetAirbrakeConfig(config));
+    bench::launch(&mut flight_loop).await;
+
+    let mut flight = RecordedFlight::default();
+    let (mut early, mut deployed, mut stale) = (0, 0, 0);
+    // First Coast cycle after detection that still looked healthy or deployed
+    let mut missed = None;
+    let mut stopped_at = None;
+    let mut cycle = 0;
+    while flight.step(&mut flight_loop).await {
+        cycle += 1;
+        let p = &flight_loop.flight_state.packet;
+        let coast = flight_loop.flight_state.flight_mode == FlightMode::Coast;
+        match stopped_at {
+            None => {
+                if p.airbrake_ctrl_ok != 1 {
+                    early += 1;
+                }
+                if coast && p.airbrake_deployment > 0.0 {
+                    deployed += 1;
+                    if deployed == STOP_AFTER_CYCLES {
+                        bench::stop_core1();
+                        stopped_at = Some(cycle);
+                    }
+                }
+            }
+            Some(stop) if cycle > stop + DETECT_CYCLES && coast => {
+                if p.airbrake_ctrl_ok == 0 && p.airbrake_deployment == 0.0 {
+                    stale += 1;
+                } else {
+                    missed.get_or_insert((cycle - stop, p.airbrake_ctrl_ok, p.airbrake_deployment));
+                }
+            }
+            Some(_) => {}
+        }
+    }
+    assert_eq!(early, 0, "cycles reported stale before Core 1 was stopped");
+    assert!(stopped_at.is_some(), "airbrakes deployed in only {deployed} Coast cycles with a {TARGET_M} m target");
+    // (cycles after the stop, ctrl ok, deployment)
+    assert_eq!(missed, None, "Coast cycle after the stop still healthy or deployed");
+    assert!(stale > 0, "Coast ended before the stale controller was detected");
+    runtime::finish();
+}
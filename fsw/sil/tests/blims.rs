@@ -0,0 +1,332 @@
+//! BLiMS on the bench: the wind profile, geofence and config uploads over
+//! the umbilical, the diagnostics it logs under the main, and its brakeline
+//! calibration on both ODrive command paths.
+
+use blims::blims_constants::ALT_UPWIND_FT;
+use blims::geofence::FENCE_IN_KEEP_OUT;
+use blims::{Actuator, BlimsConfig, CalibrationTable, Geofence, LatLon, Phase, WindLayer, WindProfile, Zone, ZoneKind};
+use embassy_time::Timer;
+use fsw_sil::bench::{self, RecordedFlight};
+use fsw_sil::flight_loop::FlightLoop;
+use fsw_sil::packet::{
+    BlimsDiagRecord, ConfigPacket, FastRecord, Packet, SessionRecord, BLIMS_DIAG_RECORD_TAG, CONFIG_RECORD_TAG,
+    FAST_RECORD_TAG, FULL_RECORD_TAG, SESSION_RECORD_TAG,
+};
+use fsw_sil::state::{FlightMode, FlightState};
+use fsw_sil::umbilical::UmbilicalCommand;
+use fsw_sil::{flash, module, odrive, odrive_task, runtime};
+
+/// (altitude m, from °, speed m/s)
+const PROFILE: [(f32, f32, f32); 4] = [(0.0, 350.0, 3.0), (150.0, 5.0, 4.0), (300.0, 20.0, 5.5), (600.0, 40.0, 8.0)];
+const DESCENDING: [(f32, f32, f32); 3] = [(0.0, 270.0, 3.0), (300.0, 270.0, 5.0), (200.0, 270.0, 4.0)];
+
+fn layers(table: &[(f32, f32, f32)]) -> Vec<WindLayer> {
+    table.iter().map(|&(altitude_m, from_deg, speed_ms)| WindLayer { altitude_m, from_deg, speed_ms }).collect()
+}
+
+async fn upload_profile(flight_loop: &mut FlightLoop, layers: Vec<WindLayer>) {
+    let begin = UmbilicalCommand::WindProfileBegin(layers.len() as u8);
+    let each = layers.into_iter().enumerate().map(|(i, l)| UmbilicalCommand::WindProfileLayer(i as u8, l));
+    let cmds = std::iter::once(begin).chain(each).chain([UmbilicalCommand::WindProfileCommit]);
+    bench::uplink(flight_loop, cmds).await;
+}
+
+/// Whether the board holds `expected` and the config packet echoes it.
+fn holds_profile(flight_loop: &FlightLoop, expected: &WindProfile) -> bool {
+    let p = &flight_loop.flight_state.config;
+    flight_loop.flight_state.blims_wind_profile.checksum() == expected.checksum()
+        && p.blims_wind_layers as usize == expected.len()
+        && p.blims_wind_checksum == expected.checksum()
+}
+
+/// Uploads a wind profile (`<WP,B>`, one `<WP,L>` per layer, `<WP,C>`),
+/// then a second one whose layers aren't in ascending altitude, which must
+/// be refused and leave the first in place. Through the recorded flight the
+/// packet must echo the profile's layer count and checksum, and a reboot on
+/// the same flash must come up with it.
+#[test]
+fn wind_profile_upload() {
+    bench::run(300_000, |spawner| spawner.spawn(wind_profile_upload_scenario()).unwrap());
+}
+
+#[embassy_executor::task]
+async fn wind_profile_upload_scenario() {
+    let expected = WindProfile::from_layers(&layers(&PROFILE)).unwrap();
+    let mut flight_loop = bench::boot().await;
+
+    upload_profile(&mut flight_loop, layers(&PROFILE)).await;
+    assert!(holds_profile(&flight_loop, &expected), "{} layers uploaded, board holds {}", PROFILE.len(), flight_loop.flight_state.blims_wind_profile.len());
+    upload_profile(&mut flight_loop, layers(&DESCENDING)).await;
+    assert!(holds_profile(&flight_loop, &expected), "descending profile replaced the uploaded one");
+
+    bench::launch(&mut flight_loop).await;
+    let mut flight = RecordedFlight::default();
+    let mut missing = 0;
+    while flight.step(&mut flight_loop).await {
+        if !holds_profile(&flight_loop, &expected) {
+            missing += 1;
+        }
+    }
+    assert_eq!(missing, 0, "cycles where the packet did not echo the profile");
+
+    let mut flight_loop = bench::reboot().await;
+    bench::cycles(&mut flight_loop, 1).await;
+    assert!(holds_profile(&flight_loop, &expected), "stored profile not restored on reboot");
+    runtime::finish();
+}
+
+const KEEP_OUT: [(f64, f64); 4] = [(32.9455, -106.917), (32.9455, -106.905), (32.9355, -106.905), (32.9355, -106.917)];
+
+fn vertices(corners: &[(f64, f64)]) -> Vec<LatLon> {
+    corners.iter().map(|&(lat, lon)| LatLon::new(lat, lon)).collect()
+}
+
+async fn upload_fence(flight_loop: &mut FlightLoop, kind: ZoneKind, vertices: Vec<LatLon>) {
+    let zone = UmbilicalCommand::GeofenceZone(0, kind, vertices.len() as u8);
+    let each = vertices.into_iter().enumerate().map(|(j, v)| UmbilicalCommand::GeofenceVertex(0, j as u8, v));
+    let cmds = [UmbilicalCommand::GeofenceBegin(1), zone].into_iter().chain(each).chain([UmbilicalCommand::GeofenceCommit]);
+    bench::uplink(flight_loop, cmds).await;
+}
+
+/// Whether the board holds `expected` and the config packet echoes it.
+fn holds_fence(flight_loop: &FlightLoop, expected: &Geofence) -> bool {
+    let p = &flight_loop.flight_state.config;
+    flight_loop.flight_state.blims_geofence.checksum() == expected.checksum()
+        && p.blims_fence_zones as usize == expected.len()
+        && p.blims_fence_checksum == expected.checksum()
+}
+
+/// Uploads a geofence (`<GF,B>`, `<GF,Z>` and one `<GF,V>` per vertex per
+/// zone, `<GF,C>`): a keep-out around the point the canopy comes down over.
+/// A second upload with a polygon that crosses itself must be refused and
+/// leave the first in place. Through the recorded flight the packet must
+/// echo the fence's zone count and checksum and flag the canopy inside the
+/// keep-out under the main only, and a reboot on the same flash must come
+/// up with the fence.
+#[test]
+fn geofence_upload() {
+    bench::run(300_000, |spawner| spawner.spawn(geofence_upload_scenario()).unwrap());
+}
+
+#[embassy_executor::task]
+async fn geofence_upload_scenario() {
+    let expected = Geofence::from_zones(&[Zone::new(ZoneKind::KeepOut, &vertices(&KEEP_OUT)).unwrap()]).unwrap();
+    let mut flight_loop = bench::boot().await;
+    flight_loop.set_blims(module::init_blims());
+
+    upload_fence(&mut flight_loop, ZoneKind::KeepOut, vertices(&KEEP_OUT)).await;
+    assert!(holds_fence(&flight_loop, &expected), "keep-out uploaded, board holds {} zones", flight_loop.flight_state.blims_geofence.len());
+    let bow_tie = vertices(&[KEEP_OUT[0], KEEP_OUT[2], KEEP_OUT[1], KEEP_OUT[3]]);
+    upload_fence(&mut flight_loop, ZoneKind::KeepOut, bow_tie).await;
+    assert!(holds_fence(&flight_loop, &expected), "self-crossing geofence replaced the uploaded one");
+
+    bench::launch(&mut flight_loop).await;
+    let mut flight = RecordedFlight::default();
+    let (mut missing, mut flagged, mut stray) = (0, 0, 0);
+    while flight.step(&mut flight_loop).await {
+        if !holds_fence(&flight_loop, &expected) {
+            missing += 1;
+        }
+        let in_keep_out = flight_loop.flight_state.packet.blims_fence_flags & FENCE_IN_KEEP_OUT != 0;
+        match (in_keep_out, flight_loop.flight_state.flight_mode == FlightMode::MainDeployed) {
+            (true, true) => flagged += 1,
+            (true, false) => stray += 1,
+            _ => {}
+        }
+    }
+    assert_eq!(missing, 0, "cycles where the packet did not echo the fence");
+    assert!(flagged > 0, "canopy never flagged inside the keep-out");
+    assert_eq!(stray, 0, "cycles flagged inside the keep-out outside MainDeployed");
+
+    let mut flight_loop = bench::reboot().await;
+    bench::cycles(&mut flight_loop, 1).await;
+    assert!(holds_fence(&flight_loop, &expected), "stored geofence not restored on reboot");
+    runtime::finish();
+}
+
+const FT_PER_M: f32 = 3.28084;
+const BOUNDARY_FT: f32 = 1500.0;
+
+/// Sets the Upwind/Downwind boundary to 1500 ft (`<BK,...>` with the other
+/// values at their defaults), then sends a boundary below the Neutral one,
+/// which must be refused and leave 1500 ft in place. Under the main the
+/// canopy must switch to Downwind at 1500 ft rather than 1000, and a reboot
+/// on the same flash must load the stored config.
+#[test]
+fn phase_boundary_config() {
+    bench::run(300_000, |spawner| spawner.spawn(phase_boundary_config_scenario()).unwrap());
+}
+
+#[embassy_executor::task]
+async fn phase_boundary_config_scenario() {
+    let mut flight_loop = bench::boot().await;
+    flight_loop.set_blims(module::init_blims());
+
+    let config = BlimsConfig { alt_upwind_ft: BOUNDARY_FT, ..BlimsConfig::default() };
+    bench::uplink(&mut flight_loop, [UmbilicalCommand::SetBlimsConfig(config)]).await;
+    let below_neutral = BlimsConfig { alt_upwind_ft: 100.0, ..BlimsConfig::default() };
+    bench::uplink(&mut flight_loop, [UmbilicalCommand::SetBlimsConfig(below_neutral)]).await;
+    assert_eq!(flight_loop.flight_state.blims_config.alt_upwind_ft, BOUNDARY_FT, "boundary after an out-of-range 100 ft");
+
+    bench::launch(&mut flight_loop).await;
+    let mut flight = RecordedFlight::default();
+    let mut downwind_ft = None;
+    while flight.step(&mut flight_loop).await {
+        let p = &flight_loop.flight_state.packet;
+        if downwind_ft.is_none()
+            && flight_loop.flight_state.flight_mode == FlightMode::MainDeployed
+            && p.blims_phase_id == Phase::Downwind as i8
+        {
+            downwind_ft = Some(p.altitude * FT_PER_M);
+        }
+    }
+    // Within a couple of 20 Hz cycles of the boundary at the canopy's sink rate
+    assert!(
+        downwind_ft.is_some_and(|a| a <= BOUNDARY_FT && a > BOUNDARY_FT - 20.0),
+        "Downwind from {downwind_ft:?} ft, expected just under {BOUNDARY_FT} (default {ALT_UPWIND_FT})"
+    );
+
+    let flight_loop = bench::reboot().await;
+    assert_eq!(flight_loop.flight_state.blims_config.alt_upwind_ft, BOUNDARY_FT, "boundary after reboot");
+    runtime::finish();
+}
+
+/// Start of the data-log region (see onboard_flash.rs)
+const STORAGE_OFFSET: usize = 0x200000;
+
+struct Diag {
+    ms: u32,
+    /// Flight mode of the fast record logged just before it
+    flight_mode: Option<u32>,
+    pid_i: f32,
+    error_integral: f32,
+    dt: f32,
+    activation_ms: u32,
+}
+
+fn f32le(b: &[u8], off: usize) -> f32 {
+    f32::from_le_bytes(b[off..off + 4].try_into().unwrap())
+}
+fn u32le(b: &[u8], off: usize) -> u32 {
+    u32::from_le_bytes(b[off..off + 4].try_into().unwrap())
+}
+
+/// Every diag record in the log, walked the way dump_flash does.
+fn diag_records(image: &[u8]) -> Vec<Diag> {
+    let mut diags = Vec::new();
+    let mut flight_mode = None;
+    let mut i = STORAGE_OFFSET;
+    while i < image.len() && image[i] != 0xFF {
+        let size = match image[i] {
+            FAST_RECORD_TAG => FastRecord::SIZE,
+            FULL_RECORD_TAG => Packet::SIZE,
+            SESSION_RECORD_TAG => SessionRecord::SIZE,
+            BLIMS_DIAG_RECORD_TAG => BlimsDiagRecord::SIZE,
+            CONFIG_RECORD_TAG => ConfigPacket::SIZE,
+            tag => panic!("unknown tag {tag:#04X} at {i:#x}"),
+        };
+        let p = &image[i + 1..i + 1 + size];
+        match image[i] {
+            FAST_RECORD_TAG => flight_mode = Some(u32le(p, 4)),
+            BLIMS_DIAG_RECORD_TAG => diags.push(Diag {
+                ms: u32le(p, 0),
+                flight_mode,
+                pid_i: f32le(p, 24),
+                error_integral: f32le(p, 28),
+                dt: f32le(p, 32),
+                activation_ms: u32le(p, 40),
+            }),
+            _ => {}
+        }
+        i += 1 + size;
+    }
+    diags
+}
+
+/// Flies with BLiMS attached and walks the data log on the in-memory
+/// flash. Diagnostics records must appear at the 20 Hz guidance rate only
+/// while BLiMS is armed under the main, one per cycle (each step is the
+/// time since the previous record), and agree with themselves: the I term
+/// is the integral times Ki and the activation time is set once and never
+/// moves.
+#[test]
+fn diag_records_logged() {
+    bench::run(300_000, |spawner| spawner.spawn(diag_records_logged_scenario()).unwrap());
+}
+
+#[embassy_executor::task]
+async fn diag_records_logged_scenario() {
+    let mut flight_loop = bench::boot().await;
+    flight_loop.set_blims(module::init_blims());
+    bench::launch(&mut flight_loop).await;
+    RecordedFlight::default().finish(&mut flight_loop).await;
+
+    let diags = diag_records(&flash::contents());
+    assert!(diags.len() >= 100, "only {} diag records", diags.len());
+    assert!(diags.iter().all(|d| d.flight_mode == Some(FlightMode::MainDeployed as u32)), "diag record logged outside MainDeployed");
+    // One record per guidance cycle: each step is the time since the last
+    if let Some(w) = diags.windows(2).find(|w| {
+        let gap = w[1].ms - w[0].ms;
+        gap == 0 || gap > 200 || (w[1].dt * 1000.0 - gap as f32).abs() > 0.5
+    }) {
+        panic!("records at {} and {} ms with dt {} s — a cycle missing", w[0].ms, w[1].ms, w[1].dt);
+    }
+    let ki = BlimsConfig::default().ki;
+    if let Some(d) = diags.iter().find(|d| (d.pid_i - ki * d.error_integral).abs() > 1e-4) {
+        panic!("pid_i {} ≠ Ki × integral {} at {} ms", d.pid_i, d.error_integral, d.ms);
+    }
+    let activation = diags.iter().map(|d| d.activation_ms).find(|&ms| ms != 0);
+    assert!(
+        activation.is_some_and(|a| diags.iter().all(|d| d.activation_ms == 0 || d.activation_ms == a)),
+        "activation time {activation:?} not set once"
+    );
+    runtime::finish();
+}
+
+/// Bench-style table: softer near neutral, steeper toward full left
+const TABLE: &[(f32, f32)] = &[(-9.0, 1000.0), (-3.0, 1350.0), (0.0, 1480.0), (4.0, 1750.0), (9.0, 2000.0)];
+
+/// Holds `diff` for 4 s of flight-loop cycles (long enough for the link to
+/// drop or come back) and returns where the motor settled, or None if the
+/// link didn't end up in the expected state.
+async fn hold(state: &mut FlightState, diff: f32, link_up: bool) -> Option<f32> {
+    if let Some(b) = &mut state.blims {
+        b.set_brakeline_diff(diff);
+    }
+    state.packet.blims_brakeline_diff = diff;
+    for _ in 0..80 {
+        state.update_odrives();
+        Timer::after_millis(50).await;
+    }
+    (odrive_task::get_blims_readback().link_up == link_up).then(|| odrive::position(odrive::BLIMS))
+}
+
+/// Commands differentials through a non-linear brakeline calibration over
+/// both ODrive command paths: RC PWM (UART link cut) and UART (link up).
+/// For the same differential the motor must end up in the same place either
+/// way, and the measured brakeline must read back the commanded one.
+#[test]
+fn calibration_on_both_odrive_paths() {
+    bench::run(120_000, |spawner| spawner.spawn(calibration_on_both_odrive_paths_scenario()).unwrap());
+}
+
+#[embassy_executor::task]
+async fn calibration_on_both_odrive_paths_scenario() {
+    flash::open_in_memory();
+    let mut state = module::init_flight_state().await;
+    let mut blims = module::init_blims();
+    blims.actuator_mut().enable();
+    blims.set_calibration(&CalibrationTable::new(TABLE).unwrap());
+    state.set_blims(blims);
+
+    for diff in [-6.0, -1.5, 2.0, 7.0] {
+        odrive::set_connected(odrive::BLIMS, false);
+        let pwm = hold(&mut state, diff, false).await.unwrap_or_else(|| panic!("{diff:+} in: link still up over PWM"));
+        odrive::set_connected(odrive::BLIMS, true);
+        let uart = hold(&mut state, diff, true).await.unwrap_or_else(|| panic!("{diff:+} in: link down over UART"));
+        assert!((pwm - uart).abs() < 0.02, "{diff:+} in: motor at {pwm} turns over PWM, {uart} over UART");
+        let measured = state.packet.blims_measured_brakeline;
+        assert!((measured - diff).abs() <= 0.02, "{diff:+} in: measured brakeline {measured:.3} in over UART");
+    }
+    runtime::finish();
+}
@@ -0,0 +1,256 @@
+//! Board-level behaviour on the bench: the supply gate on arming, altimeter
+//! dropouts, the buzzer's readouts and the recovery beacon after landing.
+
+use embassy_time::Instant;
+use fsw_sil::actuator::{BuzzPattern, Tone};
+use fsw_sil::bench::{self, RecordedFlight};
+use fsw_sil::driver::{ads1015, bmp390, lsm6dsox};
+use fsw_sil::flight_loop::FlightLoop;
+use fsw_sil::packet::BeaconPacket;
+use fsw_sil::state::{FlightMode, SensorState, health};
+use fsw_sil::umbilical::UmbilicalCommand;
+use fsw_sil::{buzzer, constants, radio, runtime, world};
+
+/// Seconds of flight-loop cycles per supply step, at 20 Hz
+const STEP_CYCLES: u32 = 5 * 20;
+
+fn set_battery(battery_v: f32) {
+    world::set(world::World { battery_v, ..world::get() });
+}
+
+/// Checks the supply verdict, the health bit, the flight mode and both
+/// rails in the packet after a step.
+fn check_supply(step: &str, flight_loop: &FlightLoop, battery_v: f32, supply_ok: bool, mode: FlightMode) {
+    let state = &flight_loop.flight_state;
+    assert_eq!(state.supply_ok(), supply_ok, "{step}: supply_ok");
+    assert_eq!(state.health() & health::LOW_VOLTAGE == 0, supply_ok, "{step}: LOW_VOLTAGE bit");
+    assert_eq!(state.flight_mode, mode, "{step}: flight mode");
+    let (battery, pyro) = (state.packet.battery_v, state.packet.pyro_v);
+    assert!((battery - battery_v).abs() <= 0.05, "{step}: packet battery {battery:.2} V, world {battery_v:.2} V");
+    assert!(pyro >= constants::PYRO_MIN_ARM_V, "{step}: packet pyro {pyro:.2} V");
+}
+
+/// Boots on the pad with a flat battery and key-arms after a flash wipe:
+/// the supply must read low, the board must stay in Startup, and a battery
+/// just above the minimum must not clear it (hysteresis). Once a charged
+/// battery is in, the key arm that is still standing must take it to
+/// Standby. Both rails must be in the packet throughout.
+#[test]
+fn low_supply_holds_arming() {
+    bench::run(60_000, |spawner| spawner.spawn(low_supply_holds_arming_scenario()).unwrap());
+}
+
+#[embassy_executor::task]
+async fn low_supply_holds_arming_scenario() {
+    let mut flight_loop = bench::boot().await;
+    let charged = world::get().battery_v;
+
+    let flat = constants::BATTERY_MIN_ARM_V - 0.6;
+    set_battery(flat);
+    bench::send(UmbilicalCommand::WipeFlash);
+    bench::send(UmbilicalCommand::KeyArm);
+    bench::cycles(&mut flight_loop, STEP_CYCLES).await;
+    check_supply("flat battery", &flight_loop, flat, false, FlightMode::Startup);
+
+    let marginal = constants::BATTERY_MIN_ARM_V + constants::SUPPLY_HYSTERESIS_V / 2.0;
+    set_battery(marginal);
+    bench::cycles(&mut flight_loop, STEP_CYCLES).await;
+    check_supply("battery inside the hysteresis band", &flight_loop, marginal, false, FlightMode::Startup);
+
+    set_battery(charged);
+    bench::cycles(&mut flight_loop, STEP_CYCLES).await;
+    check_supply("charged battery", &flight_loop, charged, true, FlightMode::Standby);
+    runtime::finish();
+}
+
+/// Arms on the pad, then has one altimeter read fail. The packet keeps last
+/// cycle's AGL altitude, which the flight loop must not convert to AGL a
+/// second time: the altitude must stay near the pad's 0 m through the
+/// dropout instead of falling by the pad elevation, which could trip the
+/// main deploy altitude.
+#[test]
+fn altimeter_dropout_keeps_agl() {
+    bench::run(60_000, |spawner| spawner.spawn(altimeter_dropout_keeps_agl_scenario()).unwrap());
+}
+
+#[embassy_executor::task]
+async fn altimeter_dropout_keeps_agl_scenario() {
+    let mut flight_loop = bench::boot().await;
+    bench::send(UmbilicalCommand::WipeFlash);
+    bench::send(UmbilicalCommand::KeyArm);
+    bench::cycles(&mut flight_loop, STEP_CYCLES).await;
+    assert_eq!(flight_loop.flight_state.flight_mode, FlightMode::Standby, "key armed on the pad");
+    let pad_m = flight_loop.flight_state.arming_altitude;
+    assert!(pad_m > 100.0, "arming altitude {pad_m} m, expected the pad's MSL elevation");
+
+    world::set(world::World { baro_ok: false, ..world::get() });
+    bench::cycles(&mut flight_loop, 1).await;
+    let during = flight_loop.flight_state.packet.altitude;
+    world::set(world::World { baro_ok: true, ..world::get() });
+    bench::cycles(&mut flight_loop, 1).await;
+    let after = flight_loop.flight_state.packet.altitude;
+
+    assert!(during.abs() < 1.0, "AGL altitude {during} m during the dropout, pad at {pad_m} m MSL");
+    assert!(after.abs() < 1.0, "AGL altitude {after} m after the dropout");
+    assert_eq!(flight_loop.flight_state.flight_mode, FlightMode::Standby, "one failed read");
+    runtime::finish();
+}
+
+#[test]
+fn buzz_patterns() {
+    // 3460: three, four and six short beeps, then one long tone for the zero
+    let tones = BuzzPattern::number(3460);
+    let lengths: Vec<u16> = tones.tones().iter().map(|t| t.on_ms).collect();
+    assert_eq!(lengths.len(), 14, "number(3460) gave tones {lengths:?}");
+    assert_eq!(lengths.last(), Some(&BuzzPattern::ZERO_MS), "number(3460) gave tones {lengths:?}");
+    assert!(lengths[..13].iter().all(|&t| t == BuzzPattern::DIGIT_BEEP_MS), "number(3460) gave tones {lengths:?}");
+    let digit_gaps = tones.tones().iter().filter(|t| t.off_ms == BuzzPattern::DIGIT_GAP_MS).count();
+    assert_eq!(digit_gaps, 4, "number(3460) digit gaps");
+
+    // Attention tone, then A (.-) and P (.--.)
+    let code = BuzzPattern::fault_code(health::ALTIMETER | health::PAYLOAD, &health::CODES);
+    let (s, l) = (BuzzPattern::SHORT_MS, BuzzPattern::LONG_MS);
+    let on: Vec<u16> = code.tones().iter().map(|t| t.on_ms).collect();
+    assert_eq!(on, [BuzzPattern::ATTENTION_MS, s, l, s, l, l, s], "fault code for A+P");
+
+    assert_eq!(BuzzPattern::beeps(2).tones(), [Tone { on_ms: 100, off_ms: 100 }; 2]);
+}
+
+/// Listens to the buzzer pin: the recorded flight must end with the apogee
+/// read out in feet once landed, and a board rebooted after going to Fault
+/// must sound the fault code with the recovered-Fault letter.
+#[test]
+fn buzzer_readouts() {
+    bench::run(300_000, |spawner| spawner.spawn(buzzer_readouts_scenario()).unwrap());
+}
+
+#[embassy_executor::task]
+async fn buzzer_readouts_scenario() {
+    let mut flight_loop = bench::boot().await;
+    bench::launch(&mut flight_loop).await;
+    let mut flight = RecordedFlight::default();
+    flight.finish(&mut flight_loop).await;
+    // Landing detection, then time for the readout to play
+    flight.rest(&mut flight_loop, constants::LANDED_STILL_MS + 15_000).await;
+    assert!(flight_loop.landed(), "landing not detected on the ground");
+    let apogee_ft = flight_loop.apogee_ft();
+    assert!(buzzer::numbers().contains(&apogee_ft), "apogee {apogee_ft} ft, heard numbers {:?}", buzzer::numbers());
+
+    // Altimeter lost at Startup: Fault, persisted for the next boot
+    let mut flight_loop = bench::boot().await;
+    flight_loop.set_key_switch(true);
+    flight_loop.set_altimeter_state(SensorState::INVALID);
+    flight_loop.simulate_cycle().await;
+    assert_eq!(flight_loop.flight_state.flight_mode, FlightMode::Fault, "invalid altimeter at Startup");
+
+    let mut flight_loop = bench::reboot().await;
+    // Two repeats of the fault code
+    bench::cycles(&mut flight_loop, 8 * 20).await;
+    assert!(flight_loop.booted_in_fault(), "reboot after Fault not flagged");
+    let code = buzzer::code_for(flight_loop.fault_bits());
+    assert!(code.contains(&buzzer::code_for(health::RECOVERED_FAULT)), "fault code \"{code}\" has no recovered-Fault letter");
+    assert!(buzzer::codes().contains(&code), "fault code \"{code}\", heard {:?}", buzzer::codes());
+    runtime::finish();
+}
+
+#[test]
+fn beacon_layout() {
+    let beacon = BeaconPacket {
+        ms_since_boot_cfc: 1_240_750,
+        flight_mode: 5,
+        num_satellites: 12,
+        health: 0x0024,
+        latitude: 32.9405,
+        longitude: -106.911,
+        fix_age_s: 640,
+        battery_v: f32::NAN,
+        apogee_ft: 3460,
+    };
+    let bytes = beacon.to_bytes();
+    let back = BeaconPacket::from_bytes(&bytes);
+    // NaN never compares equal, so check the battery by its bits
+    assert_eq!(BeaconPacket { battery_v: 0.0, ..back }, BeaconPacket { battery_v: 0.0, ..beacon });
+    assert_eq!(back.battery_v.to_bits(), beacon.battery_v.to_bits());
+    assert_eq!(bytes[16..20], 640u32.to_le_bytes(), "fix age not at bytes 16..20");
+}
+
+/// Main deploys about 41 s into the recorded flight
+const GROUND_MS: u64 = 1_300_000;
+const GPS_LOSS_MS: u64 = 600_000;
+
+/// Rests the recorded flight on the ground past the main's
+/// `MAIN_LOG_TIMEOUT_MS` with the GPS fix dropped: beacon mode must not
+/// start before the timeout, then the radio must carry only beacons, at
+/// `BEACON_PERIOD_MS`, with the IMU, altimeter and ADC powered down, the
+/// loop well under 20 Hz, and the last fix with its age growing from the
+/// drop.
+#[test]
+fn recovery_beacon() {
+    bench::run(GROUND_MS + 60_000, |spawner| spawner.spawn(recovery_beacon_scenario()).unwrap());
+}
+
+#[embassy_executor::task]
+async fn recovery_beacon_scenario() {
+    let mut flight_loop = bench::boot().await;
+    bench::launch(&mut flight_loop).await;
+    let mut flight = RecordedFlight::default();
+    let mut main_ms = None;
+    while flight.step(&mut flight_loop).await {
+        if main_ms.is_none() && flight_loop.flight_state.flight_mode == FlightMode::MainDeployed {
+            main_ms = Some(Instant::now().as_millis());
+        }
+    }
+    let main_ms = main_ms.expect("main never deployed");
+
+    // Sim ms and cycle count at the first cycle in beacon mode
+    let mut beacon_start = None;
+    let mut gps_lost = false;
+    while Instant::now().as_millis() < GROUND_MS {
+        let now = Instant::now().as_millis();
+        if !gps_lost && now >= GPS_LOSS_MS {
+            // The receiver keeps answering with no fix, as when the antenna
+            // is buried on landing
+            world::set(world::World { fix_type: 0, num_satellites: 0, ..world::get() });
+            gps_lost = true;
+        }
+        let beacon_mode = flight_loop.flight_state.beacon_mode();
+        assert!(beacon_mode || beacon_start.is_none(), "beacon mode dropped at {now} ms");
+        if beacon_mode && beacon_start.is_none() {
+            beacon_start = Some((now, flight_loop.flight_state.cycle_count));
+        }
+        flight.rest(&mut flight_loop, 1_000).await;
+    }
+    let (start_ms, start_cycle) = beacon_start.expect("no beacon mode by the end of the rest");
+    assert!(start_ms >= main_ms + constants::MAIN_LOG_TIMEOUT_MS, "beacon mode at {start_ms} ms, main deployed at {main_ms} ms");
+
+    radio::poll();
+    let beacons = radio::beacons();
+    let first_ms = beacons.first().expect("beacon mode on but no beacon received").0;
+    let late = radio::packet_times().iter().filter(|&&t| t > first_ms).count();
+    assert_eq!(late, 0, "full packets after the first beacon");
+    let period = constants::BEACON_PERIOD_MS;
+    let gaps: Vec<u64> = beacons.windows(2).map(|w| w[1].0 - w[0].0).collect();
+    assert!(
+        gaps.iter().all(|&g| g.abs_diff(period) <= 2 * constants::BEACON_LOOP_DELAY_MS),
+        "beacon gaps {gaps:?} off the {period} ms period"
+    );
+
+    assert!(lsm6dsox::powered_down(), "IMU still powered in beacon mode");
+    assert!(bmp390::asleep(), "altimeter still powered in beacon mode");
+    assert!(ads1015::powered_down(), "ADC still powered in beacon mode");
+    let beacon_ms = Instant::now().as_millis() - start_ms;
+    let cycles = (flight_loop.flight_state.cycle_count - start_cycle) as u64;
+    // A 20 Hz loop would have run beacon_ms / MAIN_LOOP_DELAY_MS cycles
+    let full_rate = beacon_ms / constants::MAIN_LOOP_DELAY_MS;
+    assert!(cycles * 2 <= full_rate, "{cycles} cycles in {beacon_ms} ms of beacon mode; the loop didn't slow down");
+
+    let &(last_ms, last) = beacons.last().unwrap();
+    let pad = world::World::PAD;
+    assert_eq!((last.latitude, last.longitude), (pad.latitude as f32, pad.longitude as f32), "last beacon position");
+    // Ages are whole seconds and fixes land once a second
+    let expected_age_s = ((last_ms - GPS_LOSS_MS) / 1000) as u32;
+    assert_ne!(last.fix_age_s, BeaconPacket::NO_FIX, "last beacon has no fix");
+    assert!(last.fix_age_s.abs_diff(expected_age_s) <= 1, "last beacon fix age {} s, expected {expected_age_s}", last.fix_age_s);
+    assert_eq!(last.apogee_ft, flight_loop.apogee_ft(), "last beacon apogee");
+    runtime::finish();
+}
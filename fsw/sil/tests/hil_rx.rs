@@ -0,0 +1,37 @@
+//! Feeds USB packets that mix `$HIL` frames with `<X>` commands and
+//! heartbeats through the umbilical's receive path: every frame must reach
+//! the HIL assembler and every command must come out for the parser, in
+//! whichever order they share a packet or however a frame is split.
+
+use fsw_sil::hil::{self, HilReceiver};
+use fsw_sil::umbilical::strip_heartbeats;
+
+/// What the command parser sees after one packet: the receive path takes
+/// the frame bytes first, then the heartbeats.
+fn receive(rx: &mut HilReceiver, packet: &[u8]) -> Vec<u8> {
+    let mut buf = packet.to_vec();
+    let n = rx.take_frames(&mut buf);
+    let n = strip_heartbeats(&mut buf[..n]);
+    buf.truncate(n);
+    buf
+}
+
+#[test]
+fn frames_and_commands_separated() {
+    let mut rx = HilReceiver::new();
+
+    assert_eq!(receive(&mut rx, b"$HIL,B,1000,101325.0,20.0\n<L>"), b"<L>", "frame then launch");
+    assert_eq!(hil::read_baro().map(|b| b.pressure).ok(), Some(101325.0), "baro frame ahead of <L>");
+
+    // An IMU frame split across two packets, with a heartbeat and a command
+    // behind its tail
+    assert_eq!(receive(&mut rx, b"$HIL,I,1050,0.0,-9.81,0.0,0.1,0.2,0.3"), b"", "frame head");
+    assert!(rx.in_progress(), "split IMU frame not held for its tail");
+    assert_eq!(receive(&mut rx, b"\n<H><KA>"), b"<KA>", "frame tail, heartbeat, key arm");
+    assert_eq!(hil::read_imu().map(|i| i.accel[1]).ok(), Some(-9.81), "split IMU frame");
+
+    assert_eq!(receive(&mut rx, b"<W>$HIL,B,1100,90000.0,15.0\r\n"), b"<W>", "command then frame");
+    assert_eq!(hil::read_baro().map(|b| b.pressure).ok(), Some(90000.0), "frame behind a command");
+    assert_eq!(receive(&mut rx, b"$HIL,B,1150,90001.0,15.0\n<H>$HIL,B,1200,90002.0,15.0\n"), b"", "heartbeat between frames");
+    assert_eq!(hil::read_baro().map(|b| b.pressure).ok(), Some(90002.0), "frames around a heartbeat");
+}
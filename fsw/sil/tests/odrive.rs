@@ -0,0 +1,145 @@
+//! Both ODrive S1 links against the simulated ODrives: UART setpoints and
+//! encoder readback while linked, the RC PWM fallback once a link drops,
+//! and the PWM mapping each ODrive reports at link-up.
+
+use embassy_time::Timer;
+use fsw_sil::bench;
+use fsw_sil::odrive_task::{self, AIRBRAKE_ODRIVE_SETPOINT, BLIMS_ODRIVE_SETPOINT, OdriveReadback};
+use fsw_sil::state::FlightMode;
+use fsw_sil::umbilical::UmbilicalCommand;
+use fsw_sil::{constants, module, odrive, runtime, world};
+
+/// Cycles flown under the main before the cut, and after it
+const LINKED_CYCLES: u32 = 60 * 20;
+const CUT_CYCLES: u32 = 20 * 20;
+/// Both ODrives reporting in `packet.odrive_link`
+const BOTH_LINKS: u8 = 0b11;
+/// Canopy sink per cycle (about 6 m/s at 20 Hz)
+const SINK_PER_CYCLE_M: f32 = 0.3;
+
+/// Steers BLiMS under the main with both UART links up, then cuts them.
+/// While linked the packet must report both links up and the encoder
+/// readback must track the commanded brakeline; after the cut it must
+/// report the RC PWM fallback, each motor must end where its PWM pulse puts
+/// it, and neither ODrive may ever get UART and PWM commands together.
+#[test]
+fn link_loss_falls_back_to_pwm() {
+    bench::run(300_000, |spawner| spawner.spawn(link_loss_falls_back_to_pwm_scenario()).unwrap());
+}
+
+#[embassy_executor::task]
+async fn link_loss_falls_back_to_pwm_scenario() {
+    let mut flight_loop = bench::boot().await;
+    // Canopy drifting at about its nominal ground speed, so BLiMS has a
+    // heading of motion to steer on
+    world::set(world::World { vel_n: 1.0, vel_e: 7.0, vel_d: 8.2, ..world::get() });
+    flight_loop.set_blims(module::init_blims());
+    bench::cycles(&mut flight_loop, 2 * 20).await;
+    assert_eq!(flight_loop.flight_state.packet.odrive_link, BOTH_LINKS, "links after 2 s on the pad");
+
+    bench::send(UmbilicalCommand::MainMode);
+    bench::cycles(&mut flight_loop, 1).await;
+    assert_eq!(flight_loop.flight_state.flight_mode, FlightMode::MainDeployed, "forced main");
+    flight_loop.main_chutes_deployed = true;
+    flight_loop.drogue_deployed = true;
+
+    let mut alt_m = 600.0;
+    let (mut unlinked, mut worst) = (0, 0.0_f32);
+    let (mut prev_diff, mut last_step) = (0.0, 0);
+    for i in 0..LINKED_CYCLES {
+        flight_loop.sim_altitude_override = Some(alt_m);
+        bench::cycles(&mut flight_loop, 1).await;
+        alt_m -= SINK_PER_CYCLE_M;
+        let p = &flight_loop.flight_state.packet;
+        // The motor takes a few cycles to follow a step in the command
+        // (BLiMS starting to steer, a phase change)
+        if (p.blims_brakeline_diff - prev_diff).abs() > 0.1 {
+            last_step = i;
+        }
+        prev_diff = p.blims_brakeline_diff;
+        if p.odrive_link != BOTH_LINKS {
+            unlinked += 1;
+        } else if i >= last_step + 5 {
+            worst = worst.max((p.blims_measured_brakeline - p.blims_brakeline_diff).abs());
+        }
+    }
+    assert_eq!(unlinked, 0, "cycles with a link down before the cut");
+    assert!(worst <= 0.1, "measured brakeline off by up to {worst:.3} in while linked");
+
+    odrive::set_connected(odrive::AIRBRAKE, false);
+    odrive::set_connected(odrive::BLIMS, false);
+    let mut still_linked = 0;
+    for i in 0..CUT_CYCLES {
+        flight_loop.sim_altitude_override = Some(alt_m);
+        bench::cycles(&mut flight_loop, 1).await;
+        alt_m -= SINK_PER_CYCLE_M;
+        // A second for the exchanges to time out
+        if i >= 20 && flight_loop.flight_state.packet.odrive_link != 0 {
+            still_linked += 1;
+        }
+    }
+    assert_eq!(still_linked, 0, "cycles with a link reported up after the cut");
+
+    let state = &flight_loop.flight_state;
+    let setpoints = [
+        ("airbrake", odrive::AIRBRAKE, state.airbrake_system.pulse_us(), odrive_task::get_airbrake_readback().link_up),
+        (
+            "BLiMS",
+            odrive::BLIMS,
+            state.blims_calibration().pulse_us(state.packet.blims_brakeline_diff),
+            odrive_task::get_blims_readback().link_up,
+        ),
+    ];
+    for (name, id, pulse_us, link_up) in setpoints {
+        let (pos, want) = (odrive::position(id), odrive::turns_at(id, pulse_us));
+        assert!(!link_up, "{name}: link still up after the cut");
+        assert!((pos - want).abs() <= 0.1, "{name}: motor at {pos:.3} turns, PWM pulse maps to {want:.3}");
+        assert_eq!(odrive::conflicts(id), 0, "{name}: UART setpoints while PWM was pulsing");
+    }
+    runtime::finish();
+}
+
+/// Publishes the setpoints once per flight-loop period for `ms` of sim time.
+async fn publish(airbrake_us: f32, blims_us: f32, ms: u64) {
+    for _ in 0..ms / 50 {
+        AIRBRAKE_ODRIVE_SETPOINT.signal(airbrake_us);
+        BLIMS_ODRIVE_SETPOINT.signal(blims_us);
+        Timer::after_millis(50).await;
+    }
+}
+
+fn assert_at(name: &str, id: usize, rb: OdriveReadback, pulse_us: f32) {
+    let want = odrive::turns_at(id, pulse_us);
+    let pos = odrive::position(id);
+    assert!(rb.link_up, "{name}: link down");
+    assert!((pos - want).abs() <= 0.01, "{name}: motor at {pos:.3} turns, {pulse_us} µs maps to {want:.3}");
+    let readback = rb.position_pulse_us;
+    assert!(readback.is_some_and(|p| (p - pulse_us).abs() < 2.0), "{name}: readback {readback:?} µs, setpoint {pulse_us} µs");
+}
+
+/// Runs the ODrive tasks without a flight loop. The airbrake ODrive reports
+/// a travel the FSW has never been told about: its UART setpoints and
+/// readback must still land where the same RC pulse would. The BLiMS ODrive
+/// starts with an unusable mapping: its link must stay down (PWM in
+/// command) until the mapping is fixed.
+#[test]
+fn setpoints_follow_odrive_mapping() {
+    bench::run(60_000, |spawner| spawner.spawn(setpoints_follow_odrive_mapping_scenario()).unwrap());
+}
+
+#[embassy_executor::task]
+async fn setpoints_follow_odrive_mapping_scenario() {
+    odrive::set_pwm_mapping(odrive::BLIMS, 2.0, 2.0);
+    publish(1750.0, 1500.0, 2_000).await;
+
+    assert_at("airbrake", odrive::AIRBRAKE, odrive_task::get_airbrake_readback(), 1750.0);
+    let bl = odrive_task::get_blims_readback();
+    assert!(!bl.link_up, "BLiMS: link up with a zero-span mapping");
+    assert_eq!(bl.position_pulse_us, None, "BLiMS: readback with a zero-span mapping");
+
+    odrive::set_pwm_mapping(odrive::BLIMS, -10.5, 10.5);
+    publish(1250.0, 1800.0, constants::ODRIVE_REPROBE_MS + 1_000).await;
+    assert_at("airbrake", odrive::AIRBRAKE, odrive_task::get_airbrake_readback(), 1250.0);
+    assert_at("BLiMS", odrive::BLIMS, odrive_task::get_blims_readback(), 1800.0);
+    runtime::finish();
+}
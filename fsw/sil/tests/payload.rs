@@ -0,0 +1,114 @@
+//! The payload UART link: the frame format and the CFC's retries, ACK
+//! tracking and link health against the simulated payload board.
+
+#![cfg(feature = "payload_frames")]
+
+use embassy_time::Instant;
+use fsw_sil::bench::{self, RecordedFlight};
+use fsw_sil::driver::payload::{FrameDecoder, LinkState, MAX_FRAME, PayloadCommand, PayloadError, crc16, encode, frame_type};
+use fsw_sil::umbilical::UmbilicalCommand;
+use fsw_sil::{constants, payload, runtime};
+
+#[test]
+fn frame_format() {
+    // CRC-16/CCITT-FALSE check value, so the payload firmware can match it
+    assert_eq!(crc16(b"123456789"), 0x29B1, "crc16 check value");
+
+    let mut frame = [0u8; MAX_FRAME];
+    let body = [6, 1, 2, 3, 4, 5, 6, 7, 8];
+    let n = encode(frame_type::CMD, 42, &body, &mut frame);
+    let mut decoder = FrameDecoder::new();
+    // Line noise ahead of the frame is skipped
+    let decoded: Vec<_> = [0x00, 0x13].iter().chain(&frame[..n]).filter_map(|&b| decoder.push(b)).collect();
+    match decoded.as_slice() {
+        [Ok(f)] if f.kind == frame_type::CMD && f.seq == 42 && f.body() == body => {}
+        other => panic!("CMD frame round trip gave {other:?}"),
+    }
+
+    frame[5] ^= 0x01;
+    let decoded: Vec<_> = frame[..n].iter().filter_map(|&b| decoder.push(b)).collect();
+    assert!(matches!(decoded.as_slice(), [Err(PayloadError::Crc)]), "corrupted frame gave {decoded:?}");
+}
+
+const DROP_ACKS: u32 = 3;
+const FAULT_MS: u64 = 40_000;
+/// Nothing heard for `PAYLOAD_LINK_TIMEOUT_MS`, plus a few heartbeats
+const LOST_AFTER_MS: u64 = FAULT_MS + 3_500;
+
+/// Flies the recorded flight with the payload board's first ACKs lost and
+/// its link cut partway down. N2 must be retried until acked and acted on
+/// only once; the packet must show the link up with N2 as the last ACK
+/// before the cut, then the link lost and N3/N4 counted as unacknowledged
+/// after it.
+#[test]
+fn retries_and_link_loss() {
+    bench::run(300_000, |spawner| spawner.spawn(retries_and_link_loss_scenario()).unwrap());
+}
+
+#[embassy_executor::task]
+async fn retries_and_link_loss_scenario() {
+    let mut flight_loop = bench::boot().await;
+    bench::launch(&mut flight_loop).await;
+    // After launch, so the HELLO ACK has already gone through
+    payload::drop_acks(DROP_ACKS);
+
+    let n2 = PayloadCommand::N2.id();
+    let mut flight = RecordedFlight::default();
+    // Command frames the board had seen when N2's ACK came back
+    let mut n2_frames = None;
+    let (mut down_early, mut wrong_ack, mut still_up) = (0, 0, 0);
+    while flight.step(&mut flight_loop).await {
+        let now = Instant::now().as_millis();
+        if now >= FAULT_MS && payload::connected() {
+            payload::set_connected(false);
+        }
+        let p = &flight_loop.flight_state.packet;
+        if n2_frames.is_none() && p.payload_last_ack == n2 {
+            n2_frames = Some(payload::command_frames());
+        }
+        if now > 1_000 && now < FAULT_MS && p.payload_link != LinkState::Up as u8 {
+            down_early += 1;
+        }
+        if n2_frames.is_some() && p.payload_last_ack != n2 {
+            wrong_ack += 1;
+        }
+        if now > LOST_AFTER_MS && p.payload_link != LinkState::Lost as u8 {
+            still_up += 1;
+        }
+    }
+    // On the ground long enough for landing and the last retries to run out
+    flight.rest(&mut flight_loop, constants::LANDED_STILL_MS + 15_000).await;
+
+    assert_eq!(n2_frames, Some(1 + DROP_ACKS), "frames sent for N2 by its ACK");
+    assert_eq!(payload::executed().iter().filter(|&&id| id == n2).count(), 1, "times N2 was acted on");
+    assert_eq!(down_early, 0, "cycles with the payload link not up before the cut");
+    assert_eq!(wrong_ack, 0, "cycles with the last ACK not N2 after N2");
+    assert_eq!(still_up, 0, "cycles with the payload link not reported lost after the cut");
+    assert_eq!(flight_loop.flight_state.packet.payload_failed, 2, "unacknowledged commands (N3 and N4)");
+    runtime::finish();
+}
+
+/// Sends N1 over the umbilical, power-cycles the CFC and sends it again
+/// straight away. The second N1 reuses seq 0 from the fresh boot; the
+/// payload board must still act on it, so N1 runs twice.
+#[test]
+fn command_after_reboot() {
+    bench::run(30_000, |spawner| spawner.spawn(command_after_reboot_scenario()).unwrap());
+}
+
+#[embassy_executor::task]
+async fn command_after_reboot_scenario() {
+    let n1 = PayloadCommand::N1.id();
+    let mut flight_loop = bench::boot().await;
+    bench::cycles(&mut flight_loop, 20).await;
+    bench::uplink(&mut flight_loop, [UmbilicalCommand::PayloadN1]).await;
+    bench::cycles(&mut flight_loop, 20).await;
+    assert_eq!(payload::executed(), [n1], "commands acted on before the reboot");
+
+    let mut flight_loop = bench::reboot().await;
+    bench::uplink(&mut flight_loop, [UmbilicalCommand::PayloadN1]).await;
+    bench::cycles(&mut flight_loop, 20).await;
+    assert_eq!(payload::executed(), [n1, n1], "commands acted on after the reboot");
+    assert_eq!(flight_loop.flight_state.payload.pending(), 0, "commands still awaiting an ACK");
+    runtime::finish();
+}
@@ -0,0 +1,35 @@
+//! The payload UART link in flight builds (no `payload_frames`): the ASCII
+//! lines the current payload firmware reads. Run with
+//! `cargo test --no-default-features --features sim_all --test payload_ascii`.
+
+#![cfg(not(feature = "payload_frames"))]
+
+use embassy_time::Instant;
+use fsw_sil::bench;
+use fsw_sil::driver::payload::LinkState;
+use fsw_sil::umbilical::UmbilicalCommand;
+use fsw_sil::{payload, runtime};
+
+/// Sends N1 and A2 over the umbilical on the pad. Each must go out once as
+/// its line, A2 with altitude and velocity, beside a 1 Hz `A` heartbeat,
+/// and with nothing read back the link must stay never heard.
+#[test]
+fn command_lines() {
+    bench::run(30_000, |spawner| spawner.spawn(command_lines_scenario()).unwrap());
+}
+
+#[embassy_executor::task]
+async fn command_lines_scenario() {
+    let mut flight_loop = bench::boot().await;
+    bench::cycles(&mut flight_loop, 20).await;
+    bench::uplink(&mut flight_loop, [UmbilicalCommand::PayloadN1, UmbilicalCommand::PayloadA2]).await;
+    bench::cycles(&mut flight_loop, 100).await;
+
+    // Unarmed on the pad the altitude is still MSL
+    let a2 = format!("A2,{:.1},0.0", flight_loop.flight_state.packet.altitude);
+    assert_eq!(payload::lines(), ["N1", a2.as_str()], "lines sent");
+    let (heartbeats, secs) = (payload::heartbeats(), Instant::now().as_secs());
+    assert!(heartbeats.abs_diff(secs as u32) <= 1, "{heartbeats} heartbeats in {secs} s");
+    assert_eq!(flight_loop.flight_state.packet.payload_link, LinkState::NeverHeard as u8, "payload link");
+    runtime::finish();
+}
@@ -0,0 +1,53 @@
+//! Runs every compiled-in flight_sim.rs scenario through the SIL binary and
+//! fails if any reports a `FAILED` check, deadlocks or panics. Also runs the
+//! first scenario twice and requires byte-identical output, since the sim
+//! clock makes runs deterministic.
+
+use std::process::{Command, ExitCode, Output};
+
+const EXE: &str = env!("CARGO_BIN_EXE_fsw-sil");
+
+fn run(args: &[&str]) -> Output {
+    Command::new(EXE).args(args).output().expect("failed to start fsw-sil")
+}
+
+fn main() -> ExitCode {
+    let list = run(&["--list"]);
+    let scenarios: Vec<String> = String::from_utf8_lossy(&list.stdout).lines().map(str::to_owned).collect();
+    let mut failed = Vec::new();
+
+    for name in &scenarios {
+        let out = run(&["--quiet", name]);
+        let stdout = String::from_utf8_lossy(&out.stdout);
+        let summary = stdout.lines().last().unwrap_or("");
+        if out.status.success() {
+            println!("ok    {summary}");
+        } else {
+            println!("FAIL  {name} ({})", out.status);
+            for line in stdout.lines().filter(|l| l.contains("FAILED") || l.contains("aborted")) {
+                println!("      {line}");
+            }
+            print!("{}", String::from_utf8_lossy(&out.stderr));
+            failed.push(name.clone());
+        }
+    }
+
+    if let Some(first) = scenarios.first() {
+        let a = run(&[first]);
+        let b = run(&[first]);
+        if a.stdout == b.stdout {
+            println!("ok    {first}: two runs produced identical output");
+        } else {
+            println!("FAIL  {first}: output differs between runs");
+            failed.push(format!("{first} (determinism)"));
+        }
+    }
+
+    if failed.is_empty() {
+        println!("\n{} scenarios passed", scenarios.len());
+        ExitCode::SUCCESS
+    } else {
+        println!("\nfailed: {}", failed.join(", "));
+        ExitCode::FAILURE
+    }
+}
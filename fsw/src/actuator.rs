@@ -48,18 +48,18 @@ impl<'a> Ssa<'a> {
     pub fn update(&mut self) {
         let now = Instant::now();
 
-        if let Some(time) = self.drogue_off_time {
-            if now >= time {
-                self.drogue_pin.set_low();
-                self.drogue_off_time = None;
-            }
+        if let Some(time) = self.drogue_off_time
+            && now >= time
+        {
+            self.drogue_pin.set_low();
+            self.drogue_off_time = None;
         }
 
-        if let Some(time) = self.main_off_time {
-            if now >= time {
-                self.main_pin.set_low();
-                self.main_off_time = None;
-            }
+        if let Some(time) = self.main_off_time
+            && now >= time
+        {
+            self.main_pin.set_low();
+            self.main_off_time = None;
         }
     }
 }
@@ -71,12 +71,124 @@ impl<'a> Ssa<'a> {
 // CFC_ARM_Indicator (GPIO 21): PWM output at 400 Hz driving the buzzer + LED.
 // CFC_ARM (GPIO 41):           Input (Pull::Down) — off-board arming signal.
 
+/// One step of a buzzer pattern: tone for `on_ms`, then silence for `off_ms`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tone {
+    pub on_ms: u16,
+    pub off_ms: u16,
+}
+
+/// Longest pattern the buzzer holds: a five-digit readout of nines is 45 tones.
+pub const BUZZ_PATTERN_LEN: usize = 64;
+
+/// A sequence of tones for `Buzzer::play`. Tones past `BUZZ_PATTERN_LEN`
+/// are dropped.
+#[derive(Debug, Clone, Default)]
+pub struct BuzzPattern {
+    tones: heapless::Vec<Tone, BUZZ_PATTERN_LEN>,
+}
+
+impl BuzzPattern {
+    // Morse-like codes: short and long tones, gaps between symbols and letters
+    pub const SHORT_MS: u16 = 100;
+    pub const LONG_MS: u16 = 400;
+    pub const SYMBOL_GAP_MS: u16 = 150;
+    pub const LETTER_GAP_MS: u16 = 700;
+    /// Lead-in tone ahead of a fault code
+    pub const ATTENTION_MS: u16 = 1000;
+
+    // Numeric readout: digit d is d short beeps, 0 is one long tone
+    pub const DIGIT_BEEP_MS: u16 = 150;
+    pub const DIGIT_BEEP_GAP_MS: u16 = 250;
+    pub const ZERO_MS: u16 = 600;
+    pub const DIGIT_GAP_MS: u16 = 1000;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends one tone.
+    pub fn tone(mut self, on_ms: u16, off_ms: u16) -> Self {
+        let _ = self.tones.push(Tone { on_ms, off_ms });
+        self
+    }
+
+    /// Lengthens the silence after the last tone to at least `ms`.
+    fn gap(mut self, ms: u16) -> Self {
+        if let Some(last) = self.tones.last_mut() {
+            last.off_ms = last.off_ms.max(ms);
+        }
+        self
+    }
+
+    /// `n` identical 100 ms beeps, the original `buzz(n)` pattern.
+    pub fn beeps(n: u32) -> Self {
+        (0..n).fold(Self::new(), |p, _| p.tone(100, 100))
+    }
+
+    /// Appends a Morse-like code: `.` short, `-` long, space between letters.
+    pub fn code(self, code: &str) -> Self {
+        code.chars().fold(self, |p, c| match c {
+            '.' => p.tone(Self::SHORT_MS, Self::SYMBOL_GAP_MS),
+            '-' => p.tone(Self::LONG_MS, Self::SYMBOL_GAP_MS),
+            _ => p.gap(Self::LETTER_GAP_MS),
+        })
+    }
+
+    pub fn morse(code: &str) -> Self {
+        Self::new().code(code)
+    }
+
+    /// `n` read out digit by digit, most significant first.
+    pub fn number(n: u32) -> Self {
+        let mut digits = [0u8; 10];
+        let mut len = 0;
+        let mut rest = n;
+        loop {
+            digits[len] = (rest % 10) as u8;
+            len += 1;
+            rest /= 10;
+            if rest == 0 {
+                break;
+            }
+        }
+        digits[..len].iter().rev().fold(Self::new(), |p, &d| {
+            let p = if d == 0 {
+                p.tone(Self::ZERO_MS, Self::DIGIT_BEEP_GAP_MS)
+            } else {
+                (0..d).fold(p, |p, _| p.tone(Self::DIGIT_BEEP_MS, Self::DIGIT_BEEP_GAP_MS))
+            };
+            p.gap(Self::DIGIT_GAP_MS)
+        })
+    }
+
+    /// Attention tone, then the code of each set bit in `bits`, in table order.
+    pub fn fault_code(bits: u16, codes: &[(u16, &str)]) -> Self {
+        let lead = Self::new().tone(Self::ATTENTION_MS, Self::LETTER_GAP_MS);
+        codes
+            .iter()
+            .filter(|(bit, _)| bits & bit != 0)
+            .fold(lead, |p, (_, code)| p.code(code).gap(Self::LETTER_GAP_MS))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tones.is_empty()
+    }
+
+    pub fn tones(&self) -> &[Tone] {
+        &self.tones
+    }
+}
+
 pub struct Buzzer<'a> {
     /// GPIO 21 – CFC_ARM_Indicator, PWM at 400 Hz (50% duty = tone on, 0% = off)
     pwm: Pwm<'a>,
-    remaining_beeps: u32,
+    pattern: BuzzPattern,
+    step: usize,
     next_toggle_time: Option<Instant>,
     is_on: bool,
+    /// None until the first pattern finishes
+    idle_since: Option<Instant>,
 }
 
 impl<'a> Buzzer<'a> {
@@ -88,9 +200,11 @@ impl<'a> Buzzer<'a> {
     pub fn new(pwm: Pwm<'a>) -> Self {
         Self {
             pwm,
-            remaining_beeps: 0,
+            pattern: BuzzPattern::new(),
+            step: 0,
             next_toggle_time: None,
             is_on: false,
+            idle_since: None,
         }
     }
 
@@ -103,45 +217,63 @@ impl<'a> Buzzer<'a> {
         let _ = self.pwm.set_duty_cycle_fraction(0, Self::TOP);
     }
 
+    fn start_tone(&mut self) {
+        let on_ms = self.pattern.tones[self.step].on_ms;
+        self.set_on();
+        self.is_on = true;
+        self.next_toggle_time = Some(Instant::now() + Duration::from_millis(on_ms as u64));
+    }
+
     /// Request `num` beeps (100 ms on / 100 ms off each).
-    /// Ignored if a beep sequence is already in progress.
+    /// Ignored if a pattern is already in progress.
     pub fn buzz(&mut self, num: u32) {
-        if self.remaining_beeps == 0 {
-            self.remaining_beeps = num;
-            self.set_on();
-            self.is_on = true;
-            self.next_toggle_time =
-                Some(Instant::now() + embassy_time::Duration::from_millis(100));
+        self.play(&BuzzPattern::beeps(num));
+    }
+
+    /// Start `pattern`. Ignored (returns false) if a pattern is already in progress.
+    pub fn play(&mut self, pattern: &BuzzPattern) -> bool {
+        if self.is_busy() || pattern.is_empty() {
+            return false;
         }
+        self.pattern = pattern.clone();
+        self.step = 0;
+        self.start_tone();
+        true
+    }
+
+    /// True while a pattern is playing, including the silence after its last tone.
+    pub fn is_busy(&self) -> bool {
+        self.next_toggle_time.is_some()
     }
 
-    /// Call every loop cycle. Manages on/off beep pattern via PWM duty cycle.
+    /// Time since the last pattern finished: 0 while one is playing,
+    /// `u64::MAX` if none has played since boot.
+    pub fn idle_ms(&self) -> u64 {
+        match self.idle_since {
+            _ if self.is_busy() => 0,
+            Some(t) => t.elapsed().as_millis(),
+            None => u64::MAX,
+        }
+    }
+
+    /// Call every loop cycle. Steps through the pattern via PWM duty cycle.
     pub fn update(&mut self) {
-        if let Some(time) = self.next_toggle_time {
-            if Instant::now() >= time {
-                if self.is_on {
-                    // End of ON phase
-                    self.set_off();
-                    self.is_on = false;
-                    self.remaining_beeps -= 1;
-
-                    if self.remaining_beeps > 0 {
-                        // Gap before next beep
-                        self.next_toggle_time =
-                            Some(Instant::now() + embassy_time::Duration::from_millis(100));
-                    } else {
-                        self.next_toggle_time = None;
-                    }
+        if let Some(time) = self.next_toggle_time
+            && Instant::now() >= time
+        {
+            if self.is_on {
+                // End of tone — silence before the next one
+                self.set_off();
+                self.is_on = false;
+                let off_ms = self.pattern.tones[self.step].off_ms;
+                self.next_toggle_time = Some(Instant::now() + Duration::from_millis(off_ms as u64));
+            } else {
+                self.step += 1;
+                if self.step < self.pattern.tones.len() {
+                    self.start_tone();
                 } else {
-                    // End of gap phase — start next beep
-                    if self.remaining_beeps > 0 {
-                        self.set_on();
-                        self.is_on = true;
-                        self.next_toggle_time =
-                            Some(Instant::now() + embassy_time::Duration::from_millis(100));
-                    } else {
-                        self.next_toggle_time = None;
-                    }
+                    self.next_toggle_time = None;
+                    self.idle_since = Some(Instant::now());
                 }
             }
         }
@@ -232,10 +364,10 @@ impl<'a> Mav<'a> {
 
     /// Must be called periodically to handle timed open()
     pub fn update(&mut self) {
-        if let Some(deadline) = self.open_deadline {
-            if Instant::now() >= deadline {
-                self.close();
-            }
+        if let Some(deadline) = self.open_deadline
+            && Instant::now() >= deadline
+        {
+            self.close();
         }
     }
 
@@ -256,11 +388,17 @@ impl<'a> Mav<'a> {
 //
 // GPIO 37 = ENABLE output (High = enable ODrive, Low = disable)
 // GPIO 38 = PWM signal to ODrive RC PWM IN
+//
+// When the ODrive UART link (driver/odrive.rs) is up it carries the setpoint
+// and the PWM output is held low; the ODrive keeps its last PWM-mapped
+// position while no pulses arrive. The PWM resumes on link loss. The UART
+// setpoint is this pulse width run through the ODrive's own PWM mapping.
 
 pub struct AirbrakeActuator<'a> {
     enable: Output<'a>,
     pwm: Pwm<'a>,
     current_deployment: f32,
+    pwm_output: bool,
 }
 
 impl<'a> AirbrakeActuator<'a> {
@@ -277,6 +415,7 @@ impl<'a> AirbrakeActuator<'a> {
             enable,
             pwm,
             current_deployment: 0.0,
+            pwm_output: true,
         };
         ab.retract(); // safe starting position
         ab
@@ -296,13 +435,22 @@ impl<'a> AirbrakeActuator<'a> {
     /// `deployment`: 0.0 = fully retracted, 1.0 = fully deployed.
     /// Outputs a proportional RC PWM pulse between 1000 µs and 2000 µs.
     pub fn set_deployment(&mut self, deployment: f32) {
-        let dep = deployment.clamp(0.0, 1.0);
-        self.current_deployment = dep;
+        self.current_deployment = deployment.clamp(0.0, 1.0);
+        self.set_pulse_width((self.pulse_us() + 0.5) as u16);
+    }
 
+    /// RC pulse width (µs) for the current deployment; also the UART
+    /// setpoint, so both paths put the motor in the same place.
+    pub fn pulse_us(&self) -> f32 {
         let span = (Self::SERVO_OPEN_US - Self::SERVO_CLOSE_US) as f32;
-        let pulse_us = Self::SERVO_CLOSE_US as f32 + span * dep;
+        Self::SERVO_CLOSE_US as f32 + span * self.current_deployment
+    }
 
-        self.set_pulse_width((pulse_us + 0.5) as u16);
+    /// Deployment (0.0–1.0 inside the travel) a pulse width maps to; the
+    /// inverse of [`pulse_us`](Self::pulse_us), for the ODrive readback.
+    pub fn deployment_at(pulse_us: f32) -> f32 {
+        let span = (Self::SERVO_OPEN_US - Self::SERVO_CLOSE_US) as f32;
+        (pulse_us - Self::SERVO_CLOSE_US as f32) / span
     }
 
     /// Fully retract the airbrakes (1000 µs pulse).
@@ -315,7 +463,24 @@ impl<'a> AirbrakeActuator<'a> {
         self.current_deployment
     }
 
+    /// Turn the RC PWM output on (fallback path) or hold it low while the
+    /// UART link drives the ODrive. Re-enabling outputs the current deployment.
+    pub fn set_pwm_output(&mut self, enabled: bool) {
+        if enabled == self.pwm_output {
+            return;
+        }
+        self.pwm_output = enabled;
+        if enabled {
+            self.set_deployment(self.current_deployment);
+        } else {
+            let _ = self.pwm.set_duty_cycle_fraction(0, Self::SERVO_PERIOD_US);
+        }
+    }
+
     fn set_pulse_width(&mut self, pulse_us: u16) {
+        if !self.pwm_output {
+            return;
+        }
         let pulse = pulse_us.clamp(Self::SERVO_CLOSE_US, Self::SERVO_OPEN_US);
         let _ = self
             .pwm
@@ -362,12 +527,12 @@ impl<'a> SV<'a> {
     }
 
     pub fn update(&mut self) {
-        if let Some(time) = self.open_delay {
-            if Instant::now() >= time {
-                self.pin.set_low();
-                self.state_open = true;
-                self.open_delay = None;
-            }
+        if let Some(time) = self.open_delay
+            && Instant::now() >= time
+        {
+            self.pin.set_low();
+            self.state_open = true;
+            self.open_delay = None;
         }
     }
 
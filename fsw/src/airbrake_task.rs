@@ -1,12 +1,27 @@
 //! Airbrake controller — runs on Core 1.
+//!
+//! The hardware watchdog only covers Core 0's loop, so Core 1 publishes a
+//! heartbeat counter and an output timestamp alongside its output. Core 0
+//! checks them every cycle through [`Core1Monitor`] and retracts the airbrakes
+//! if either stops moving.
+//!
+//! The controller's target apogee, rate limit and drag model come from Core 0
+//! through [`AIRBRAKE_CONFIG`]: once at boot (from flash) and again whenever
+//! the config is changed over the umbilical on the pad. The controller's
+//! in-flight drag estimate comes back with each output
+//! ([`get_drag_estimate`]) so Core 0 can log it.
 
 use core::sync::atomic::{AtomicU32, Ordering};
 
 use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
 use embassy_sync::signal::Signal;
+use embassy_time::{Duration, Instant};
+
+use crate::constants;
 
 use controller_in_rust_v3::airbrakes::AirbrakeSystem;
-use controller_in_rust_v3::types::{Phase, SensorInput as ControllerInput};
+use controller_in_rust_v3::predictor::DragModel;
+use controller_in_rust_v3::types::{Config, Phase, SensorInput as ControllerInput};
 
 #[derive(Clone, Copy)]
 pub enum AirbrakePhase {
@@ -30,9 +45,17 @@ pub struct AirbrakeInput {
 }
 
 pub static AIRBRAKE_INPUT: Signal<CriticalSectionRawMutex, AirbrakeInput> = Signal::new();
+/// New controller config; picked up before the next input is processed
+pub static AIRBRAKE_CONFIG: Signal<CriticalSectionRawMutex, Config> = Signal::new();
 
 static AIRBRAKE_DEPLOYMENT: AtomicU32 = AtomicU32::new(0);
 static AIRBRAKE_PREDICTED_APOGEE: AtomicU32 = AtomicU32::new(0);
+static AIRBRAKE_EST_BODY_CDA: AtomicU32 = AtomicU32::new(0);
+static AIRBRAKE_EST_BRAKE_CDA: AtomicU32 = AtomicU32::new(0);
+/// Incremented each time Core 1 picks up an input
+static AIRBRAKE_HEARTBEAT: AtomicU32 = AtomicU32::new(0);
+/// ms since boot (wrapping) when Core 1 last published an output
+static AIRBRAKE_OUTPUT_MS: AtomicU32 = AtomicU32::new(0);
 
 pub fn get_deployment() -> f32 {
     f32::from_bits(AIRBRAKE_DEPLOYMENT.load(Ordering::Acquire))
@@ -42,12 +65,108 @@ pub fn get_predicted_apogee() -> f32 {
     f32::from_bits(AIRBRAKE_PREDICTED_APOGEE.load(Ordering::Acquire))
 }
 
+/// Drag areas behind the latest prediction: the configured ones until
+/// Coast, then Core 1's estimate from the measured deceleration.
+pub fn get_drag_estimate() -> DragModel {
+    DragModel {
+        body_cda_m2: f32::from_bits(AIRBRAKE_EST_BODY_CDA.load(Ordering::Acquire)),
+        brake_cda_m2: f32::from_bits(AIRBRAKE_EST_BRAKE_CDA.load(Ordering::Acquire)),
+    }
+}
+
+/// Watches Core 1 from Core 0. Only call [`check`](Self::check) on cycles
+/// that signal `AIRBRAKE_INPUT`; Core 1 is idle otherwise.
+pub struct Core1Monitor {
+    heartbeat: u32,
+    heartbeat_at: Instant,
+    output_ms: u32,
+    output_at: Instant,
+    healthy: bool,
+}
+
+impl Default for Core1Monitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Core1Monitor {
+    pub fn new() -> Self {
+        let now = Instant::now();
+        Self {
+            heartbeat: AIRBRAKE_HEARTBEAT.load(Ordering::Acquire),
+            heartbeat_at: now,
+            output_ms: AIRBRAKE_OUTPUT_MS.load(Ordering::Acquire),
+            output_at: now,
+            healthy: true,
+        }
+    }
+
+    /// Last verdict from [`check`](Self::check)
+    pub fn healthy(&self) -> bool {
+        self.healthy
+    }
+
+    /// Returns false if the heartbeat or the output timestamp hasn't changed
+    /// for `AIRBRAKE_CORE1_TIMEOUT_MS`, or the output isn't a number.
+    pub fn check(&mut self) -> bool {
+        let now = Instant::now();
+        let heartbeat = AIRBRAKE_HEARTBEAT.load(Ordering::Acquire);
+        if heartbeat != self.heartbeat {
+            self.heartbeat = heartbeat;
+            self.heartbeat_at = now;
+        }
+        let output_ms = AIRBRAKE_OUTPUT_MS.load(Ordering::Acquire);
+        if output_ms != self.output_ms {
+            self.output_ms = output_ms;
+            self.output_at = now;
+        }
+
+        let timeout = Duration::from_millis(constants::AIRBRAKE_CORE1_TIMEOUT_MS);
+        let heartbeat_age = now - self.heartbeat_at;
+        let output_age = now - self.output_at;
+        let healthy = heartbeat_age <= timeout && output_age <= timeout && get_deployment().is_finite();
+        if healthy != self.healthy {
+            if healthy {
+                log::info!("AIRBRAKE: Core 1 controller output fresh again");
+            } else {
+                log::error!(
+                    "AIRBRAKE: Core 1 controller stale (heartbeat {} ms, output {} ms ago) — retracting",
+                    heartbeat_age.as_millis(),
+                    output_age.as_millis()
+                );
+            }
+            self.healthy = healthy;
+        }
+        healthy
+    }
+
+    /// Restarts the timeouts; call on cycles that don't signal Core 1 so the
+    /// idle time isn't counted against it.
+    pub fn idle(&mut self) {
+        let now = Instant::now();
+        self.heartbeat_at = now;
+        self.output_at = now;
+    }
+}
+
 #[embassy_executor::task]
 pub async fn airbrake_core1_task() {
+    run().await
+}
+
+/// Controller loop. Split from the task so the SIL can drop it mid-flight to
+/// stand in for a hung Core 1.
+pub async fn run() {
     let mut system = AirbrakeSystem::new();
 
     loop {
         let input = AIRBRAKE_INPUT.wait().await;
+        AIRBRAKE_HEARTBEAT.fetch_add(1, Ordering::Release);
+
+        if let Some(config) = AIRBRAKE_CONFIG.try_take() {
+            system.set_config(config);
+        }
 
         let ctrl_phase = match input.phase {
             AirbrakePhase::Pad   => Phase::Pad,
@@ -71,5 +190,8 @@ pub async fn airbrake_core1_task() {
 
         AIRBRAKE_DEPLOYMENT.store(output.deployment.to_bits(), Ordering::Release);
         AIRBRAKE_PREDICTED_APOGEE.store(output.predicted_apogee.to_bits(), Ordering::Release);
+        AIRBRAKE_EST_BODY_CDA.store(output.drag.body_cda_m2.to_bits(), Ordering::Release);
+        AIRBRAKE_EST_BRAKE_CDA.store(output.drag.brake_cda_m2.to_bits(), Ordering::Release);
+        AIRBRAKE_OUTPUT_MS.store(Instant::now().as_millis() as u32, Ordering::Release);
     }
 }
@@ -0,0 +1,93 @@
+//! Airbrake ground test profiles.
+//!
+//! Bench characterisation of the airbrake mechanism from the umbilical:
+//!   - Step: jump to a deployment fraction, hold, then retract, so one run
+//!     captures both the opening and the closing response.
+//!   - Sweep: ramp 0 → 1 → 0 at a fixed rate (fraction per second).
+//!   - Chirp: sine about `AIRBRAKE_TEST_CHIRP_CENTER` whose frequency rises
+//!     linearly from f0 to f1, for a frequency-response estimate.
+//!
+//! Profiles only run in Startup with the test-enable flag set (`<AT1>`);
+//! `FlightLoop::run_airbrake_test` aborts a profile the moment either goes
+//! away. The commanded deployment goes through `AirbrakeActuator` like the
+//! controller's output, so `airbrake_deployment` (commanded) and
+//! `airbrake_measured_deployment` (ODrive encoder) land side by side in every
+//! 20 Hz flash record.
+
+use embassy_time::Instant;
+
+use crate::constants;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TestProfile {
+    /// Hold `target` for `AIRBRAKE_TEST_STEP_HOLD_MS`, then retract
+    Step { target: f32 },
+    /// 0 → 1 → 0 at `rate` deployment fraction per second
+    Sweep { rate: f32 },
+    /// Linear chirp from `f0_hz` to `f1_hz` over `duration_s`
+    Chirp { f0_hz: f32, f1_hz: f32, duration_s: f32 },
+}
+
+impl TestProfile {
+    /// Rejects parameters the mechanism or the 20 Hz loop can't follow.
+    pub fn is_valid(&self) -> bool {
+        match *self {
+            TestProfile::Step { target } => (0.0..=1.0).contains(&target),
+            TestProfile::Sweep { rate } => rate > 0.0 && rate <= constants::AIRBRAKE_TEST_MAX_RATE,
+            TestProfile::Chirp { f0_hz, f1_hz, duration_s } => {
+                f0_hz > 0.0
+                    && f0_hz <= f1_hz
+                    && f1_hz <= constants::AIRBRAKE_TEST_MAX_CHIRP_HZ
+                    && duration_s > 0.0
+                    && duration_s <= constants::AIRBRAKE_TEST_MAX_DURATION_S
+            }
+        }
+    }
+
+    /// Profile length in seconds
+    pub fn duration_s(&self) -> f32 {
+        match *self {
+            TestProfile::Step { .. } => constants::AIRBRAKE_TEST_STEP_HOLD_MS as f32 / 1000.0,
+            TestProfile::Sweep { rate } => 2.0 / rate,
+            TestProfile::Chirp { duration_s, .. } => duration_s,
+        }
+    }
+
+    /// Commanded deployment `t` seconds into the profile.
+    pub fn deployment_at(&self, t: f32) -> f32 {
+        let dep = match *self {
+            TestProfile::Step { target } => target,
+            TestProfile::Sweep { rate } => {
+                let up = t * rate;
+                if up <= 1.0 { up } else { 2.0 - up }
+            }
+            TestProfile::Chirp { f0_hz, f1_hz, duration_s } => {
+                // Phase of a linear chirp: 2π (f0 t + (f1 − f0) t² / 2T)
+                let phase = 2.0 * core::f32::consts::PI * (f0_hz * t + (f1_hz - f0_hz) * t * t / (2.0 * duration_s));
+                constants::AIRBRAKE_TEST_CHIRP_CENTER + constants::AIRBRAKE_TEST_CHIRP_AMPLITUDE * libm::sinf(phase)
+            }
+        };
+        dep.clamp(0.0, 1.0)
+    }
+}
+
+/// A profile in progress.
+pub struct AirbrakeTest {
+    pub profile: TestProfile,
+    start: Instant,
+}
+
+impl AirbrakeTest {
+    pub fn start(profile: TestProfile) -> Self {
+        Self { profile, start: Instant::now() }
+    }
+
+    /// Deployment to command now, or `None` once the profile has finished.
+    pub fn command(&self) -> Option<f32> {
+        let t = self.start.elapsed().as_millis() as f32 / 1000.0;
+        if t >= self.profile.duration_s() {
+            return None;
+        }
+        Some(self.profile.deployment_at(t))
+    }
+}
@@ -84,16 +84,32 @@ pub const G_TO_MS2: f32 = 9.80665;
 
 // Landing-zone target (switched to at <1000 ft AGL)
 pub const BLIMS_DOWNWIND_TARGET_LAT: f32 = 42.703311;
+#[allow(clippy::excessive_precision)] // surveyed coordinate, kept as given
 pub const BLIMS_DOWNWIND_TARGET_LON: f32 = -77.181125;
 // Upwind waypoint â€” steer here after main deploy (>1000 ft AGL) so the
 // parafoil drifts into position upwind of the landing zone. For wind from
 // 270Â° (west), offset ~500 m west of the landing target.
 pub const BLIMS_UPWIND_TARGET_LAT: f32 = 42.705565;
+#[allow(clippy::excessive_precision)]
 pub const BLIMS_UPWIND_TARGET_LON: f32 = -77.196310;
 pub const BLIMS_WIND_FROM_DEG: f32 = 270.0;
+// Forecast wind speed, sizes the landing-pattern legs (m/s)
+pub const BLIMS_WIND_SPEED_MS: f32 = 0.0;
+// MVP Upwind/Downwind bands, or the full landing pattern to the downwind
+// target (Track / Loiter / DownwindLeg / Base / Final)
+pub const BLIMS_GUIDANCE_MODE: blims::GuidanceMode = blims::GuidanceMode::Mvp;
+// Steer straight at each waypoint, or L1 path following along the leg to it
+// (flies back onto the line after crosswind drift)
+pub const BLIMS_TRACKING_LAW: blims::TrackingLaw = blims::TrackingLaw::Bearing;
+// BLiMS heading filter yaw rate = sign × gyro_y. Y is the rocket's long axis
+// (vertical on the pad, and hanging under the main); +1 if a right turn seen
+// from above reads positive with the avionics bay in its flight orientation
+pub const BLIMS_YAW_GYRO_SIGN: f32 = 1.0;
 
 pub const FLASH_LOGGING_PERIOD_MS: u64 = 50;   // Fast record rate: 20 Hz
 pub const FULL_LOGGING_PERIOD_MS:  u64 = 1000; // Full record rate:  1 Hz
+/// `ConfigPacket` rate over the radio (1 Hz)
+pub const CONFIG_PACKET_PERIOD_MS: u64 = 1000;
 pub const SNAPSHOT_LOGGING_PERIOD_MS: u64 = 200; // Snapshot ring rate: 5 Hz
 
 // IÂ²C/SPI timeouts: if a bus transaction hangs (e.g. GPS NACK holds SDA low),
@@ -145,7 +161,17 @@ pub const MAIN_DEPLOY_DELAY_MS: u64 = 1000; // talk to R&P to find this exact nu
 
 pub const MAIN_DEPLOY_ALTITUDE: f32 = 610.0; // ? find exact number
 
+/// Time under the main after which the FSW stops logging data to flash and
+/// switches to the recovery beacon: IMU, altimeter and ADS1015 powered down,
+/// only the GPS read, and a `BeaconPacket` every `BEACON_PERIOD_MS` in place
+/// of full telemetry.
 pub const MAIN_LOG_TIMEOUT_MS: u64 = 1_200_000; // 20 minutes
+pub const BEACON_PERIOD_MS: u64 = 5_000;
+/// Loop cycle time in beacon mode while the buzzer is idle (2 Hz). Beacons
+/// go out on the first cycle past `BEACON_PERIOD_MS`, so up to this late.
+pub const BEACON_LOOP_DELAY_MS: u64 = 500;
+/// Silence between apogee readouts in beacon mode
+pub const BEACON_BUZZ_PAUSE_MS: u64 = 30_000;
 
 /// Minimum altitude (m AGL) the rocket must exceed before the N2 velocity
 /// check is armed. Prevents false triggers during early coast.
@@ -154,6 +180,22 @@ pub const N2_ARM_ALTITUDE_M: f32 = 500.0;
 pub const UMBILICAL_TIMEOUT_MS: u64 = 15_000; // 15 seconds
 pub const STARTUP_BUZZ_PERIOD_MS: u64 = 5_000; // periodic status buzz in Startup
 
+// Buzzer status patterns for BuzzPattern::morse ('.' short, '-' long)
+pub const BUZZ_UMBILICAL_CONNECTED: &str = ".";
+pub const BUZZ_UMBILICAL_DISCONNECTED: &str = "-";
+pub const BUZZ_ARM_ACK: &str = "--";
+pub const BUZZ_ARM_REJECT: &str = "-..-";
+pub const BUZZ_KEY_DISARM: &str = "..";
+/// Silence between repeats of the post-landing apogee readout and of the
+/// Fault code.
+pub const BUZZ_REPEAT_PAUSE_MS: u64 = 3_000;
+
+/// Landed once, under the main, the altitude has stayed within
+/// `LANDED_ALT_BAND_M` of where it started for `LANDED_STILL_MS`. The real
+/// flight data holds within the band for 7.2 s during the descent.
+pub const LANDED_STILL_MS: u64 = 10_000;
+pub const LANDED_ALT_BAND_M: f32 = 2.0;
+
 /// Maximum age of the most recent umbilical heartbeat (`<H>`) before the link
 /// is considered down. Independent of `UMBILICAL_TIMEOUT_MS`, which gates the
 /// vent-on-disconnect action.
@@ -180,6 +222,92 @@ pub const ADS1015_PT4_SCALE_B: f32 = -13.27332;
 pub const ADS1015_RTD_SCALE_M: f32 = 0.21656;
 pub const ADS1015_RTD_SCALE_B: f32 = -259.74;
 
+// Supply Monitoring (RP2350 ADC, driver/supply.rs)
+// Battery on GPIO 42 (ADC2), pyro rail on GPIO 43 (ADC3), each through a
+// 100k/10k divider so a full 2S pack reads well inside the 3.3 V range
+// (pins and dividers not yet checked against the CFC schematic, see
+// RATS/SystemDoc.md section 3.7)
+pub const SUPPLY_ADC_VREF: f32 = 3.3;
+pub const SUPPLY_BATTERY_DIVIDER: f32 = 11.0;
+pub const SUPPLY_PYRO_DIVIDER: f32 = 11.0;
+/// Conversions averaged per reading
+pub const SUPPLY_SAMPLES: u32 = 4;
+/// Below either minimum, Startup→Standby arming is refused and the
+/// LOW_VOLTAGE health bit is set (2S LiPo: 8.4 V full, 7.4 V nominal)
+pub const BATTERY_MIN_ARM_V: f32 = 7.4;
+pub const PYRO_MIN_ARM_V: f32 = 7.4;
+/// A low rail must recover this far above its minimum to clear LOW_VOLTAGE,
+/// so a sagging battery logs one low-voltage event, not one per cycle
+pub const SUPPLY_HYSTERESIS_V: f32 = 0.2;
+
+// GPS Time Sync
+
+/// A NAV-PVT-derived UTC offset is only replaced by a higher-latency sample
+/// once it is this old, so crystal drift between CFC and GPS is tracked
+/// without letting I²C polling jitter move the mapping every second.
+pub const TIME_SYNC_MAX_AGE_MS: u64 = 60_000;
+
+// ODrive S1 UART links (`driver/odrive.rs`)
+
+// PIO0 UART pins are chosen in main.rs (GPIO 16/17 airbrake, 18/19 BLiMS).
+// The ODrives need `config.enable_uart_a = true` with the ASCII protocol.
+/// Must match `config.uart_a_baudrate` on both ODrives
+pub const ODRIVE_UART_BAUD: u32 = 115_200;
+
+/// Per-reply timeout. A feedback reply is ~20 bytes (~1.7 ms at 115200) plus
+/// the ODrive's turnaround, so this only trips on a dead or garbled link.
+pub const ODRIVE_REPLY_TIMEOUT_MS: u64 = 5;
+/// Good exchanges in a row before the UART link takes over from RC PWM
+pub const ODRIVE_LINK_UP_COUNT: u8 = 3;
+/// Failed exchanges in a row before falling back to RC PWM
+pub const ODRIVE_LINK_DOWN_COUNT: u8 = 3;
+/// While the link is down, probe it this often instead of every cycle so a
+/// missing ODrive costs its task at most one timeout per second.
+pub const ODRIVE_REPROBE_MS: u64 = 1_000;
+
+// Payload board link on UART0 (`driver/payload.rs`)
+
+/// Re-send an unacknowledged command after this long: two loop cycles, so a
+/// reply that lands just after one cycle's read isn't mistaken for a miss.
+pub const PAYLOAD_ACK_TIMEOUT_MS: u64 = 100;
+/// Sends of one command (first + retries) before it's reported unacknowledged
+pub const PAYLOAD_MAX_TRIES: u8 = 10;
+/// Heartbeat period; the payload board answers each with a STATUS
+pub const PAYLOAD_HEARTBEAT_MS: u64 = 1_000;
+/// Link is lost after this long without a valid frame (3 missed STATUS replies)
+pub const PAYLOAD_LINK_TIMEOUT_MS: u64 = 3_000;
+/// Commands waiting behind the one awaiting an ACK
+pub const PAYLOAD_QUEUE_LEN: usize = 8;
+
+// Core 1 airbrake controller liveness (`airbrake_task.rs`)
+
+/// Core 0 retracts the airbrakes if Core 1's heartbeat or output hasn't
+/// moved for this long: 5 loop cycles, so one slow controller step is fine
+pub const AIRBRAKE_CORE1_TIMEOUT_MS: u64 = 250;
+
+// Airbrake ground test profiles (`airbrake_test.rs`)
+
+/// How long a step profile holds its target before retracting
+pub const AIRBRAKE_TEST_STEP_HOLD_MS: u64 = 5_000;
+/// Fastest sweep (deployment fraction per second): full travel in 0.5 s
+pub const AIRBRAKE_TEST_MAX_RATE: f32 = 2.0;
+/// Highest chirp frequency: a quarter of the 20 Hz command rate keeps at
+/// least four setpoints per cycle
+pub const AIRBRAKE_TEST_MAX_CHIRP_HZ: f32 = 5.0;
+pub const AIRBRAKE_TEST_MAX_DURATION_S: f32 = 120.0;
+/// Chirp swings ±AMPLITUDE about CENTER so it never hits the hard stops
+pub const AIRBRAKE_TEST_CHIRP_CENTER: f32 = 0.5;
+pub const AIRBRAKE_TEST_CHIRP_AMPLITUDE: f32 = 0.4;
+
+// Hardware-in-the-loop (`hil` feature)
+
+/// An injected baro/IMU/ADC frame older than this reads as a failed sensor
+/// read (5 flight-loop cycles)
+pub const HIL_FRAME_TIMEOUT_MS: u64 = 250;
+
+/// GPS solutions arrive at 1-10 Hz, so injected GPS gets a longer leash
+pub const HIL_GPS_TIMEOUT_MS: u64 = 2_000;
+
 pub const TEST_ALTS_LST: [f32; 1057] = [-0.076, -0.006, -0.036, 0.141, 1.434, 1.957, 3.09, 3.912, 5.814, 7.38, 8.925, 11.04, 12.964, 15.951, 18.352, 20.445, 23.105, 26.643, 29.964, 33.709, 37.113, 41.102, 44.728, 49.263, 53.819, 57.937, 63.191, 67.945, 72.55, 78.785, 83.845, 90.337, 96.256, 102.477, 108.109, 114.974, 122.041, 128.885, 136.455, 144.079, 150.681, 158.435, 167.009, 174.495, 183.021, 191.367, 200.051, 209.279, 217.749, 227.779, 236.863, 246.656, 255.806, 266.058, 276.857, 286.941, 297.339, 308.406, 319.184, 329.849, 341.336, 352.652, 364.517, 376.273, 388.16, 399.786, 412.15, 424.627, 436.554, 449.684, 462.608, 475.032, 488.436, 502.416, 515.252, 529.292, 542.421, 556.645, 570.157, 584.083, 598.332, 611.734, 626.248, 639.705, 654.375, 667.977, 682.191, 696.938, 710.956, 724.255, 738.601, 752.205, 766.473, 780.691, 794.846, 808.866, 822.694, 836.818, 850.192, 864.236, 877.933, 892.344, 905.276, 918.841, 932.119, 945.525, 959.361, 972.729, 986.285, 999.355, 1012.587, 1017.875, 1025.271, 1036.325, 1049.536, 1062.801, 1075.567, 1088.093, 1101.442, 1114.215, 1126.789, 1140.061, 1152.707, 1165.684, 1177.583, 1190.299, 1202.959, 1215.274, 1227.761, 1240.289, 1252.611, 1264.314, 1277.078, 1289.311, 1300.621, 1312.866, 1325.038, 1336.349, 1348.767, 1360.94, 1372.745, 1383.849, 1395.181, 1406.57, 1418.976, 1430.368, 1441.704, 1453.358, 1464.841, 1476.499, 1487.143, 1498.932, 1509.394, 1521.243, 1532.705, 1543.117, 1554.306, 1565.284, 1576.347, 1587.413, 1597.897, 1609.556, 1620.143, 1630.451, 1641.191, 1651.718, 1662.484, 1673.296, 1683.826, 1694.606, 1704.829, 1714.856, 1725.672, 1735.655, 1746.37, 1756.551, 1766.427, 1776.234, 1786.676, 1797.195, 1807.525, 1817.06, 1827.11, 1837.448, 1846.847, 1856.92, 1867.141, 1876.672, 1885.585, 1896.244, 1905.927, 1915.08, 1925.345, 1934.327, 1943.882, 1952.87, 1962.803, 1972.752, 1981.831, 1990.785, 2000.427, 2009.464, 2018.981, 2028.125, 2037.424, 2046.765, 2055.769, 2064.157, 2073.547, 2082.435, 2091.395, 2099.85, 2109.383, 2117.741, 2126.765, 2135.824, 2144.595, 2152.516, 2162.047, 2170.337, 2179.267, 2187.514, 2196.073, 2204.085, 2213.27, 2221.351, 2229.804, 2238.123, 2246.444, 2255.029, 2262.885, 2271.514, 2279.599, 2287.898, 2296.086, 2303.339, 2311.385, 2319.824, 2328.091, 2335.771, 2343.726, 2351.393, 2359.209, 2367.443, 2374.765, 2382.877, 2390.618, 2398.585, 2405.913, 2413.956, 2420.958, 2428.594, 2436.043, 2443.408, 2451.229, 2459.154, 2466.196, 2473.236, 2480.356, 2488.229, 2495.148, 2502.52, 2509.952, 2517.548, 2524.894, 2531.431, 2538.81, 2545.485, 2553.075, 2559.685, 2566.84, 2573.621, 2581.065, 2587.593, 2594.18, 2601.0, 2607.369, 2614.992, 2621.255, 2628.336, 2634.689, 2641.478, 2647.696, 2654.948, 2661.447, 2667.983, 2674.121, 2680.95, 2686.897, 2693.63, 2700.311, 2706.796, 2713.019, 2719.176, 2724.885, 2731.237, 2737.602, 2743.605, 2749.916, 2756.438, 2762.538, 2767.768, 2773.661, 2780.137, 2785.936, 2792.028, 2798.048, 2804.799, 2809.995, 2815.603, 2821.651, 2827.748, 2832.965, 2839.414, 2844.191, 2850.039, 2855.692, 2861.112, 2867.116, 2873.021, 2877.884, 2883.817, 2889.116, 2894.192, 2899.664, 2905.175, 2910.427, 2915.953, 2921.19, 2926.361, 2931.584, 2936.98, 2942.417, 2947.813, 2952.272, 2957.399, 2962.434, 2968.116, 2972.602, 2977.79, 2983.173, 2987.088, 2992.966, 2997.864, 3002.207, 3006.985, 3012.151, 3016.976, 3021.614, 3026.852, 3030.852, 3035.846, 3040.049, 3044.986, 3050.001, 3054.674, 3058.646, 3063.634, 3067.919, 3072.401, 3076.631, 3081.453, 3084.67, 3089.407, 3094.376, 3097.756, 3102.135, 3106.774, 3110.438, 3114.629, 3118.767, 3123.56, 3127.873, 3132.16, 3135.773, 3139.172, 3143.064, 3147.696, 3151.417, 3155.515, 3159.272, 3163.518, 3167.427, 3170.645, 3174.852, 3178.713, 3182.622, 3185.965, 3189.168, 3193.493, 3197.209, 3200.963, 3203.806, 3207.689, 3211.337, 3214.68, 3218.406, 3221.526, 3225.321, 3228.257, 3232.211, 3234.915, 3238.7, 3242.135, 3245.871, 3248.332, 3252.214, 3255.084, 3258.371, 3261.09, 3263.916, 3267.993, 3270.85, 3273.417, 3276.471, 3279.308, 3282.576, 3286.269, 3288.977, 3291.676, 3294.15, 3297.243, 3300.39, 3302.969, 3305.399, 3308.355, 3310.785, 3314.098, 3316.64, 3319.158, 3322.011, 3323.923, 3326.432, 3329.708, 3331.938, 3334.859, 3337.248, 3339.079, 3341.75, 3344.032, 3346.938, 3349.088, 3351.298, 3353.632, 3355.889, 3357.945, 3360.566, 3362.37, 3364.929, 3366.441, 3368.88, 3370.3, 3372.898, 3375.228, 3377.463, 3379.295, 3380.712, 3383.394, 3385.397, 3386.993, 3388.466, 3390.378, 3392.15, 3394.077, 3395.789, 3397.668, 3398.855, 3400.686, 3402.661, 3404.41, 3405.946, 3407.149, 3409.287, 3410.808, 3412.611, 3413.634, 3415.383, 3416.896, 3417.338, 3419.077, 3420.82, 3422.117, 3423.547, 3425.007, 3425.879, 3427.308, 3428.311, 3430.218, 3431.378, 3432.099, 3433.298, 3434.653, 3435.061, 3436.607, 3437.743, 3438.725, 3439.536, 3440.786, 3441.388, 3442.6, 3442.733, 3444.177, 3445.01, 3446.033, 3446.805, 3447.363, 3448.186, 3448.706, 3449.33, 3450.026, 3451.03, 3451.765, 3451.869, 3453.179, 3453.384, 3453.336, 3453.96, 3454.536, 3454.617, 3454.831, 3456.374, 3456.416, 3456.832, 3456.942, 3457.296, 3457.313, 3457.618, 3457.77, 3458.384, 3458.79, 3458.907, 3458.58, 3459.565, 3458.983, 3458.421, 3459.493, 3459.532, 3459.177, 3458.985, 3458.722, 3457.44, 3454.492, 3448.934, 3443.434, 3437.636, 3430.891, 3422.271, 3412.255, 3401.202, 3389.312, 3377.287, 3365.633, 3352.595, 3340.34, 3327.528, 3315.128, 3302.523, 3290.41, 3277.161, 3265.25, 3252.543, 3240.19, 3228.065, 3215.834, 3202.947, 3190.633, 3177.963, 3164.613, 3152.533, 3140.114, 3127.974, 3115.58, 3103.196, 3090.505, 3078.701, 3065.779, 3053.664, 3041.024, 3028.258, 3016.243, 3004.478, 2991.562, 2979.702, 2967.657, 2954.725, 2942.521, 2930.882, 2917.731, 2905.068, 2893.118, 2881.355, 2868.9, 2855.346, 2843.743, 2831.387, 2820.021, 2807.583, 2795.184, 2782.777, 2770.326, 2758.332, 2746.847, 2734.011, 2722.783, 2710.199, 2698.205, 2685.297, 2673.869, 2661.414, 2648.967, 2636.826, 2624.952, 2612.391, 2600.403, 2588.823, 2576.851, 2564.52, 2552.631, 2540.587, 2528.18, 2516.361, 2504.003, 2492.24, 2479.842, 2468.077, 2456.092, 2444.058, 2431.616, 2420.013, 2408.166, 2396.474, 2383.658, 2372.304, 2359.834, 2348.793, 2336.264, 2324.302, 2312.242, 2300.508, 2288.511, 2276.947, 2264.622, 2252.977, 2241.234, 2228.627, 2217.291, 2205.628, 2193.637, 2181.401, 2170.099, 2157.975, 2147.068, 2134.72, 2122.573, 2110.541, 2099.706, 2087.102, 2074.835, 2063.278, 2052.017, 2039.823, 2028.456, 2016.38, 2004.576, 1992.999, 1981.727, 1969.607, 1958.13, 1946.868, 1934.574, 1922.856, 1910.929, 1899.386, 1887.508, 1876.266, 1864.694, 1853.083, 1841.25, 1829.453, 1818.19, 1806.412, 1794.821, 1782.988, 1772.041, 1760.929, 1748.273, 1736.824, 1724.876, 1713.568, 1702.089, 1690.521, 1679.328, 1667.669, 1655.778, 1644.411, 1633.097, 1621.426, 1609.794, 1598.185, 1586.323, 1575.222, 1564.257, 1552.55, 1540.867, 1529.987, 1518.137, 1506.663, 1494.731, 1483.562, 1471.95, 1460.031, 1449.679, 1437.562, 1426.066, 1414.815, 1403.9, 1392.266, 1380.849, 1369.722, 1358.127, 1347.353, 1334.44, 1323.738, 1312.847, 1301.163, 1290.012, 1278.911, 1267.148, 1255.945, 1244.966, 1233.794, 1222.059, 1210.825, 1199.435, 1188.34, 1177.267, 1165.565, 1154.663, 1143.535, 1131.957, 1120.871, 1109.309, 1097.854, 1086.797, 1075.589, 1064.796, 1053.918, 1042.341, 1030.864, 1020.284, 1009.724, 997.737, 986.374, 975.95, 964.426, 952.944, 942.171, 930.492, 919.805, 908.1, 897.288, 886.332, 875.133, 864.062, 853.43, 841.704, 830.459, 820.686, 809.282, 798.054, 786.54, 775.628, 763.793, 753.671, 742.562, 731.883, 720.32, 709.423, 697.849, 687.641, 676.525, 665.577, 654.048, 643.037, 632.74, 621.27, 610.376, 599.108, 588.076, 577.939, 566.828, 556.331, 545.278, 533.667, 522.975, 512.118, 501.048, 490.869, 479.331, 468.33, 457.992, 446.841, 436.258, 425.337, 414.111, 404.131, 392.61, 381.884, 370.888, 360.283, 349.542, 338.473, 327.593, 316.958, 305.947, 295.135, 284.101, 284.87, 284.452, 284.525, 284.155, 284.606, 284.636, 284.038, 284.362, 284.312, 284.128, 283.975, 283.578, 284.441, 284.411, 284.34, 284.302, 283.964, 284.357, 284.083, 283.821, 284.337, 283.302, 284.443, 283.551, 284.024, 283.691, 283.751, 283.935, 283.5, 283.793, 283.712, 283.444, 283.5, 284.091, 283.658, 284.211, 283.638, 283.825, 283.445, 283.531, 283.569, 283.709, 283.309, 283.551, 283.008, 283.457, 283.063, 283.755, 283.488, 283.034, 283.076, 283.493, 283.053, 283.064, 283.368, 283.927, 283.189, 283.828, 283.206, 283.584, 282.738, 283.31, 283.37, 282.766, 282.99, 282.966, 283.579, 283.036, 282.734, 282.414, 282.506, 282.994, 282.984, 282.937, 282.543, 282.277, 283.01, 282.46, 281.937, 282.188, 282.768, 282.421, 282.642, 282.036, 281.929, 282.154, 282.129, 282.037, 282.176, 281.968, 281.93, 282.26, 281.807, 281.948, 281.989, 281.217, 281.528, 281.531, 280.768, 281.099, 280.927, 280.69, 280.95, 281.278, 281.09, 281.343, 281.665, 280.346, 281.008, 280.042, 280.284, 279.785, 279.676, 279.28, 279.526, 279.217, 278.99, 278.404, 278.282, 277.65, 276.775, 275.521, 274.681, 272.775, 271.071, 268.536, 266.7, 266.42, 266.415, 262.904, 259.862, 258.094, 255.125, 252.374, 249.38, 248.813, 247.59, 245.056, 244.009, 242.552, 239.955, 238.078, 236.242, 233.642, 230.716, 228.434, 226.978, 225.488, 225.015, 222.258, 219.543, 215.844, 212.867, 210.028, 207.012, 204.552, 201.859, 199.305, 196.655, 193.368, 190.845, 187.879, 184.847, 183.563, 181.02, 177.394, 177.046, 176.22, 173.14, 170.006, 167.207, 164.115, 161.742, 160.623, 158.506, 156.209, 153.621, 150.662, 147.982, 145.148, 141.939, 140.179, 138.597, 136.201, 133.777, 131.574, 128.479, 125.88, 123.13, 121.146, 117.676, 114.946, 112.197, 109.709, 107.304, 106.857, 105.149, 103.748, 101.869, 99.009, 96.46, 94.855, 93.377, 89.904, 87.924, 84.979, 82.112, 79.313, 75.755, 74.093, 71.398, 68.265, 65.808, 62.952, 60.164, 58.027, 57.502, 54.187, 51.507, 48.265, 46.081, 42.49, 39.778, 38.358, 37.096, 33.849, 30.804, 28.611, 26.399, 22.784, 20.763, 17.873, 15.123, 13.098, 11.522, 8.902, 5.818, 4.35, 0.736, 0.075 ];
 
 pub const TEST_ACCS_LST: [f64;1057] = [1.056000, 0.969000, 0.999000, 0.969000, 0.952000, 1.031500, 0.942000, 1.008000, 0.988000, 1.021500, 1.101000, 0.975500, 0.972000, 1.182500, 1.016000, 1.155000, 5.208000, 5.721500, 8.322000, 7.055000, 6.652000, 6.502500, 6.921000, 6.788000, 6.828000, 7.210000, 7.262000, 7.050000, 7.096000, 7.111500, 7.092000, 7.632000, 7.472000, 7.294500, 7.330000, 7.557500, 7.050000, 6.517000, 6.814000, 6.144000, 6.520000, 6.632000, 6.369000, 6.583000, 5.312000, 7.075500, 6.147000, 6.144000, 6.600000, 5.836000, 5.238000, 5.386500, 6.102000, 5.480000, 5.832000, 4.738000, 6.296000, 3.508500, 4.996000, 5.722500, 5.556000, 2.889000, 4.881000, 0.960000, 0.576000, 3.377000, 1.170000, 0.937000, 0.478000, 0.264000, -0.652000, 0.612000, 0.192000, -2.131000, 0.359000, -1.236000, -2.322000, -1.096000, -0.381000, -1.537500, -2.408000, -0.351000, 0.519000, -1.789500, -0.144000, -1.790000, 0.428000, -1.345500, -1.088000, -1.599000, 0.177000, -0.607500, -0.726000, -2.821500, -0.782000, -0.624000, 2.400000, -0.827500, -0.248000, 0.670500, -1.826000, -0.472500, -0.183000, -0.523000, -0.652000, 0.201000, -0.086000, -0.455500, -0.426000, -0.429500, -0.195000, -0.604500, -1.008000, 1.478500, 0.117000, -0.731000, -0.186000, -0.364500, -0.217000, 0.015000, -0.336000, -1.200000, -0.928000, 0.289500, -0.668000, -0.367000, 0.456000, -1.120500, -0.240000, 0.006000, -0.787000, -0.350500, -0.654000, -0.189500, -0.177000, 0.691500, 0.056000, -1.096500, 0.261000, -0.344500, -0.630000, -0.166500, -0.256000, 0.549500, -0.480000, -0.027000, -0.025000, -0.325500, 0.036000, -0.245000, 0.330000, -0.066000, -0.640000, -0.159000, -0.639000, -0.031000, -0.138000, -0.411000, 0.252000, -0.217500, 0.128000, -0.353000, -0.471000, -0.818000, -0.250000, -0.048000, 0.074000, 0.008500, 0.240000, 0.157000, -0.111000, -0.057000, -0.410000, 0.121500, 0.360000, 0.404500, -0.152000, -0.252000, -0.055000, 0.246000, -0.282000, -0.049500, -0.044000, 0.297000, -0.288000, 0.323500, -0.558000, -0.359500, -0.360000, -0.363000, 0.703000, -0.017000, -0.720000, -0.275500, -0.157000, -0.004500, -0.160000, -0.481000, -0.276000, -0.103000, -0.272000, -0.280500, 0.223000, -0.241500, -0.186000, -0.010500, -0.181000, 0.003000, -0.168000, 0.122000, 0.120000, -0.190000, -0.034000, 0.040500, -0.129000, -0.220000, 0.024000, 0.117500, -0.253000, -0.085500, -0.054000, -0.123000, 0.045000, -0.161000, -0.176000, -0.312000, -0.091000, -0.013000, -0.024000, -0.183500, 0.180000, -0.123000, -0.052000, -0.214000, 0.153000, -0.045500, 0.016000, 0.019500, -0.167000, -0.038000, -0.072000, 0.038000, 0.124000, -0.165000, -0.014000, 0.094000, 0.048000, 0.040500, 0.000000, -0.010500, 0.015000, -0.025500, 0.042000, 0.164500, -0.034000, 0.181500, 0.032000, -0.074000, -0.168000, -0.044000, -0.146000, 0.033000, 0.013000, -0.081000, 0.108000, -0.139000, 0.161000, -0.030000, 0.094000, -0.121000, -0.030000, -0.137500, -0.040000, -0.022500, -0.059000, 0.246000, -0.204000, 0.018500, -0.045000, 0.105000, -0.180000, -0.074000, -0.153000, 0.194000, -0.028000, -0.121500, -0.249000, -0.049000, -0.096000, -0.192000, -0.116000, -0.144000, 0.076000, -0.097000, -0.144000, -0.081000, -0.016000, -0.007500, 0.375000, 0.093000, -0.024000, 0.544500, -1.314000, 1.116000, 0.264000, -0.070000, -0.219000, 0.277000, -0.064000, -0.022500, -0.337000, -0.141500, 0.120000, -0.073000, -0.399000, -0.277500, 0.208000, -0.449500, -0.372000, -0.053000, -0.448000, -0.240000, -0.160000, -2.387500, -3.810000, -2.165500, -1.400000, -1.831500, 0.532000, -0.556500, -0.675000, -0.373500, 0.040000, 0.016500, -1.031000, -1.123500, -0.072000, -0.863000, -2.089000, -0.564000, -0.582000, 0.561000, -0.150000, 0.439500, -0.112000, 0.589500, 0.878000, -0.242500, 0.108000, -0.253500, -0.611000, -0.004500, -0.448000, -0.201000, 0.513000, -0.388000, -0.306000, -0.120000, -0.068000, -0.303000, 0.000000, -0.048000, -0.124000, 0.295500, 0.064000, 0.202500, -0.144000, -0.406000, 0.104000, -0.096000, -0.668000, -1.267000, -2.232000, -0.777000, 0.194000, -0.891000, -0.616000, -0.444500, -1.212000, -0.990500, -1.016000, -0.756000, -1.105000, -0.394000, -0.768000, -0.531500, -0.999000, -0.583500, -1.576000, -0.526000, -0.969000, -0.945000, -0.720000, -1.024500, -1.953000, -0.906000, -0.174000, -0.888500, -0.887000, -1.060500, -1.072000, -0.838000, -1.185000, -0.611500, -0.946000, -1.041000, -0.790000, -1.088500, -1.488000, -1.263000, -1.759000, -0.952500, -0.862000, -1.027000, -0.237000, -0.916000, 1.872000, -1.411500, -0.738000, -0.768000, -0.462000, -0.260000, -0.334000, -0.378000, 3.720000, 0.118500, 0.582000, 0.191000, 1.124000, 0.420000, 0.135000, 0.164000, -0.312000, -0.230500, -0.227000, -1.239000, -0.538000, 0.127500, -1.416000, -0.686500, -0.420000, -0.271500, -0.476000, -0.291000, -0.744000, -0.760500, -0.555000, -0.484500, -0.656000, -0.976000, -0.558000, -0.622500, -3.452000, 1.395000, 1.382000, 0.882500, -0.084000, -0.093500, -0.379000, -0.141000, -0.554000, -0.203000, 0.000000, 0.041500, 0.352000, 0.423000, 0.543000, 0.075000, -0.354000, -0.289500, -0.051000, 0.022500, 0.388000, -0.373000, -0.111000, -0.259500, -0.982000, -0.537000, -0.845000, -0.725500, -0.864000, -0.831500, -0.553000, -0.694500, -0.346000, -0.206500, 0.339000, 0.744500, 0.248000, 0.207000, -0.082000, 0.385500, 0.036000, 0.064500, 0.355000, -0.220500, 0.096000, 0.028500, 0.240000, -0.150500, 0.290000, 0.093000, -0.102000, 0.146500, 3.060000, 0.756500, 0.273000, 0.088500, 1.544000, -0.936000, -1.068000, -0.424000, -0.048000, -0.021000, -0.567000, -0.240000, -0.696000, 0.290500, -0.161000, -0.687000, -0.296000, -0.510000, -1.068000, -0.772000, -0.760000, -0.567000, -1.105000, -0.815500, -0.384000, 0.259000, 0.417000, 0.157500, 0.226000, -0.233000, 0.345000, -0.475500, 0.064000, -0.097500, -0.284000, 0.429000, 0.108000, -0.267000, -0.266000, -0.135000, 0.544000, -0.204500, 0.459000, 0.565000, 0.064000, -0.036000, -0.084000, -0.415500, -0.324000, -0.460000, 0.285000, 0.348000, 0.186000, -0.184000, 0.066000, 0.245500, -0.080000, -0.274500, 0.325000, -0.371000, -0.420000, 0.202500, -0.334000, -0.283500, -0.136000, 0.430500, -0.075000, 0.239000, -0.446000, -1.045500, -0.364000, -0.328000, -0.288000, -0.403000, -0.144000, -0.183000, -0.126000, -0.288000, -0.330000, -0.754000, -0.396000, -0.123000, 0.212000, -0.173000, -0.426000, -0.301000, 0.171000, -0.504000, 1.144000, 0.102500, 0.075000, -0.707500, 0.568000, 0.456000, -0.209000, -1.403000, -0.144000, 0.201500, -0.096000, 0.513000, 0.068000, -0.207000, -0.351000, 0.363000, 0.048000, 0.349500, -0.035000, -0.843500, -0.864000, 0.031000, -0.061000, -0.652500, -1.248000, -0.166000, -0.201000, 0.228500, -0.532000, -0.202500, -0.277000, -0.606000, -0.168000, 0.098500, -0.832000, -0.796500, -0.962000, -0.532000, -0.693000, -0.616500, -1.380000, -1.212000, -0.591000, -0.957000, -0.876000, -2.098000, -0.807000, -0.795000, 0.240000, -0.556000, 0.081000, -2.964000, 0.070000, -0.918000, 0.145000, 0.248500, 0.204000, -0.737500, 0.007000, -0.463500, -1.252000, -0.043500, -0.483000, -0.511000, -0.024000, -0.085500, -0.560000, -0.947500, -0.240000, -0.574500, -1.150000, -1.906500, -0.636000, -0.029000, -0.960000, 0.024500, -0.024000, -0.601500, -0.426000, -0.259000, -0.144000, -0.464500, -2.179000, -1.360500, -0.204000, -0.188000, -0.933000, -0.411500, -1.964000, -0.190500, -0.527000, -0.094500, -0.504000, -0.010500, -0.217000, -0.132000, -0.192000, -3.120000, -0.231000, -0.240000, -0.378000, -0.303000, 0.008000, -0.162000, -0.012000, -0.192000, 0.089000, -0.091500, -0.196000, -0.196000, -0.027000, -0.049000, 0.336000, 0.148500, -0.310000, -0.048000, -0.114000, -0.228500, 0.188000, 0.216000, -0.316000, 0.462000, -0.345000, 0.003500, -0.190000, -0.277500, 0.341000, -0.019500, -0.192000, -0.173500, -0.048000, 0.000000, -0.228000, -0.479500, -0.237000, -0.291500, 0.040000, 0.211500, -0.117000, -0.214500, -0.732000, -0.357500, 0.088000, -0.855000, -0.688000, -0.654000, 0.465000, -0.201500, -0.450000, -3.061500, -0.930000, -0.599500, -0.072000, -0.305500, 0.083000, -0.331500, 0.236000, -0.217000, -0.327000, -0.550000, -0.240000, -0.325500, 0.321000, -0.227000, -0.594000, -0.066500, -0.226000, 0.090000, 0.324000, 0.315500, 0.117000, -0.664000, 0.224000, -0.004500, -0.268000, -0.518000, -0.192000, -0.265500, -0.384000, 0.033000, -0.220000, -0.538000, -0.708000, 0.063000, -0.228000, -0.447000, 0.427000, 0.065000, 0.396000, -0.205000, -0.319000, -0.150000, -0.544000, -0.323000, -0.750000, -0.595000, 0.204000, -1.177500, -0.245000, -0.988000, -0.732000, -0.480000, -0.528000, -0.262500, -0.774000, -1.351500, -0.294000, 0.069500, 0.048000, -0.262500, 0.515000, -0.390500, -0.018000, 0.092000, -0.192000, -0.678000, -0.420000, 0.375500, -0.375000, 0.859500, -0.122000, -0.163500, -0.111000, 0.229500, 0.120000, -0.174000, -0.602000, -0.103500, -0.400000, 0.233500, -0.177000, -0.506000, -0.968000, -0.424500, -0.391000, -0.136500, -0.798000, -0.892000, -0.947000, -1.422000, -1.616000, -1.392500, -2.034000, -1.722500, -1.838000, -1.629000, -1.205000, -1.560500, -1.644000, -0.807500, -1.958000, -1.686000, -2.792000, -2.294000, -1.401000, -1.723000, -2.040000, -1.465500, -2.164000, -2.225500, -1.698000, -2.359500, -1.066000, -1.885500, -1.896000, -1.057500, -1.551000, -1.704500, -0.678000, -0.697500, -0.066000, 0.081500, 0.336000, -0.596000, -0.471000, -0.229500, -1.702000, -0.825000, -0.828000, -0.038500, -1.060000, -0.985500, -0.630000, -0.373000, -0.966000, -0.517500, -0.414000, -1.042500, -0.344000, -0.699000, -0.711000, -0.557500, -0.022000, -0.276000, -0.385000, -0.357000, -0.384000, -0.251500, -0.798000, -0.021000, -0.366000, -0.516500, -1.290000, 0.000500, -0.304000, -0.381000, 0.788000, -0.705500, -1.242000, -0.249000, -0.532000, -0.517500, -1.016000, -0.521000, -0.606000, -1.003500, -0.884000, -0.606000, -0.554000, -0.663500, -0.144000, -0.383000, -0.131000, -0.075000, -0.236000, -0.337500, -0.087000, 0.007500, -0.148000, -0.520500, -0.490000, -0.356000, -0.864000, -0.226000, -0.051000, 0.076500, -0.848000, -0.414500, 0.366000, -0.130500, -0.324000, -0.387000, -0.281000, -0.248000, -0.444000, -0.265000, -0.410000, -0.646500, -0.248000, -0.219000, -0.387000, -0.510000, -1.384000, -0.690000, 0.259000, -0.511500, -0.528000, -1.173500, 0.233000, -0.549000, -0.060000, -0.174000, -0.225000, -0.256500, -0.844000, -0.100500, -0.340000, -0.668500, -1.008000, 0.343000, -0.357000, -0.720000, -0.594000, -0.427000, -0.519000, -0.744500, -0.384000, -0.529500, -0.388000, -0.442500, -0.210000, 0.260000, -1.421000, -2.490000, -1.984000, -0.676000, -0.921000, -0.649500, -1.286000, -0.882000, -0.364000, -0.851500, -0.948000, -0.422000, -1.009000, -1.780500, -1.246000, -1.129000, -1.002000, -1.160500, -1.440000, -0.156000, -1.023000, -1.385000, -1.554000, -0.559500, -0.413000, -0.889500, -0.356000, -1.096000, -0.846000, -1.055500, -1.304000, -0.534000, -0.378000, 0.174000, -0.240000, 0.481000, -0.005000, 0.114000, -0.280000, 0.045500, 0.066000, -0.940500, -0.020000, 0.595500, -0.092000, 0.020000, -0.024000, 0.136500, 0.027000, 19.321500, -0.752000, 0.311500, 0.165000, -0.075000, -0.102000, 0.402000, -0.240000, -0.521500, -0.468000, -1.302000, -0.474000, -0.063000, 0.116000, -0.563000, -0.120000, -0.600500, -0.144000, -0.139500, -0.444000, -0.227000, -0.216000, -0.704500, -0.646000, -0.723000, -0.532000, -0.343000, -0.561000, -0.827000, -0.102000, -1.971000, -2.641000, -1.720500, -1.488000];
\ No newline at end of file
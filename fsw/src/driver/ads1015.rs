@@ -143,6 +143,20 @@ impl Ads1015Sensor {
         Ok(value)
     }
 
+    /// Stop conversions. Writing the config with MODE = single-shot and
+    /// OS = 0 starts none, and between single-shot conversions the ADS1015
+    /// sits in power-down. Reads are a silent no-op afterwards, as when
+    /// uninitialised.
+    pub async fn power_down(&mut self) -> Result<(), Ads1015Error<<I2cDevice<'static> as embedded_hal_async::i2c::ErrorType>::Error>> {
+        if !self.initialized {
+            return Ok(());
+        }
+        self.write_register(REG_CONFIG, CFG_BASE & !CFG_OS_START).await?;
+        self.initialized = false;
+        log::info!("ADS1015 powered down");
+        Ok(())
+    }
+
     /// Read channels 1, 2, and 3 into the packet.
     /// Stores scaled values.
     pub async fn read_into_packet(&mut self, packet: &mut Packet) -> Result<(), Ads1015Error<<I2cDevice<'static> as embedded_hal_async::i2c::ErrorType>::Error>> {
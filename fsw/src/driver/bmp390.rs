@@ -80,13 +80,26 @@ impl<'a> Bmp390Sensor<'a> {
         packet.pressure = pressure;
         packet.temp = meas.temperature();
 
-        // Calculate altitude using the NOAA formula
-        let sea_level_pa = 101325.0;
-        packet.altitude = 44330.0 * (1.0 - libm::powf(pressure / sea_level_pa, 0.190295));
+        packet.altitude = pressure_to_altitude(pressure);
 
         Ok(())
     }
 
+    /// Put the BMP390 in sleep mode (PWR_CTRL mode 00, both measurements
+    /// off): no conversions, lowest current. Reads fail with `NotConnected`
+    /// afterwards, as when uninitialised.
+    pub async fn sleep(&mut self) -> Result<(), bmp390_rs::error::Bmp390Error<<SpiDevice<'a> as embedded_hal_async::spi::ErrorType>::Error>> {
+        use bmp390_rs::register::pwr_ctrl::{PowerMode, PwrCtrl, PwrCtrlCfg};
+
+        let Some(sensor) = self.sensor.as_mut() else {
+            return Ok(());
+        };
+        sensor.write::<PwrCtrl>(&PwrCtrlCfg { press_en: false, temp_en: false, mode: PowerMode::Sleep }).await?;
+        self.sensor = None;
+        log::info!("BMP390 in sleep mode");
+        Ok(())
+    }
+
     /// Check if the sensor was successfully initialized
     pub fn is_init(&self) -> bool {
         self.altimeter_init
@@ -100,4 +113,10 @@ impl<'a> Bmp390Sensor<'a> {
             altimeter_init: false,
         }
     }
-}
\ No newline at end of file
+}
+
+/// Pressure altitude (m MSL) from static pressure (Pa) using the NOAA formula.
+pub fn pressure_to_altitude(pressure: f32) -> f32 {
+    let sea_level_pa = 101325.0;
+    44330.0 * (1.0 - libm::powf(pressure / sea_level_pa, 0.190295))
+}
@@ -109,6 +109,19 @@ impl Lsm6dsoxSensor {
         }
     }
 
+    /// Put the accelerometer and gyro in power-down (ODR 0). Reads are a
+    /// silent no-op afterwards, as when uninitialised.
+    pub async fn power_down(&mut self) -> Result<(), Lsm6dsoxError<<I2cDevice<'static> as embedded_hal_async::i2c::ErrorType>::Error>> {
+        if !self.initialized {
+            return Ok(());
+        }
+        self.write_register(REG_CTRL1_XL, 0x00).await?;
+        self.write_register(REG_CTRL2_G, 0x00).await?;
+        self.initialized = false;
+        log::info!("LSM6DSOX powered down");
+        Ok(())
+    }
+
     pub async fn read_into_packet(&mut self,packet: &mut crate::packet::Packet,) -> Result<(), Lsm6dsoxError<<I2cDevice<'static> as embedded_hal_async::i2c::ErrorType>::Error>> {
         if !self.initialized {
             return Ok(());
@@ -3,6 +3,9 @@
 pub mod bmp390;
 pub mod onboard_flash;
 pub mod lsm6dsox;
+pub mod odrive;
+pub mod payload;
 pub mod rfd900x;
 pub mod ublox_max_m10s;
 pub mod ads1015;
+pub mod supply;
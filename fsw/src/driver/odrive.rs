@@ -0,0 +1,363 @@
+//! ODrive S1 ASCII protocol driver
+//!
+//! Talks to an ODrive S1 over its UART A (115200 8N1) with the ASCII
+//! protocol: a position setpoint, encoder feedback, and one slower read per
+//! cycle (bus voltage, bus current, axis state, active errors, disarm
+//! reason) in round-robin. Every command carries a `*checksum`, so the ODrive
+//! drops a corrupted command and this driver drops a corrupted reply.
+//!
+//! The ODrive's RC PWM input stays configured as the fallback. The UART link
+//! takes over after `ODRIVE_LINK_UP_COUNT` good exchanges in a row and hands
+//! back to PWM after `ODRIVE_LINK_DOWN_COUNT` failures in a row; the owner
+//! checks [`Odrive::link_up`] each cycle to decide which path drives the
+//! motor. While down, the link is re-probed every `ODRIVE_REPROBE_MS`.
+//!
+//! Setpoints are RC pulse widths (µs), the unit the PWM path already
+//! speaks. Before taking over, the driver reads the ODrive's own
+//! `gpio8_pwm_mapping` min/max and converts with it, so a UART setpoint puts
+//! the motor where the same pulse on the PWM pin would, and the encoder
+//! position converts back the same way. If the mapping can't be read the
+//! link stays down and PWM keeps command.
+//!
+//! The byte transport is the [`OdriveLink`] trait: PIO UART on the flight
+//! board (`module.rs`), a simulated ODrive in the SIL build.
+
+use core::fmt::Write;
+
+use embassy_time::{Duration, Instant, with_timeout};
+
+use crate::constants;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OdriveError {
+    /// No complete reply within `ODRIVE_REPLY_TIMEOUT_MS`
+    Timeout,
+    /// Reply checksum didn't match
+    Checksum,
+    /// Reply wasn't the number(s) we asked for (e.g. "invalid property")
+    Parse,
+    /// Reply longer than any valid answer
+    Overflow,
+    /// `gpio8_pwm_mapping` min and max too close together to convert with
+    Mapping,
+}
+
+/// Byte transport to one ODrive.
+#[allow(async_fn_in_trait)]
+pub trait OdriveLink {
+    async fn write(&mut self, data: &[u8]) -> Result<(), OdriveError>;
+    /// Waits for the next received byte; the driver bounds the wait.
+    async fn read_byte(&mut self) -> Result<u8, OdriveError>;
+}
+
+/// `AxisState` values the FSW cares about (ODrive firmware 0.6)
+pub mod axis_state {
+    pub const UNDEFINED: u8 = 0;
+    pub const IDLE: u8 = 1;
+    pub const CLOSED_LOOP_CONTROL: u8 = 8;
+}
+
+/// Latest readback. Values hold their last good reading while the link is
+/// down; check [`Odrive::link_up`] before trusting them.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OdriveStatus {
+    /// Encoder position (turns)
+    pub pos_turns: f32,
+    /// Encoder velocity (turns/s)
+    pub vel_turns_s: f32,
+    pub vbus_v: f32,
+    pub ibus_a: f32,
+    pub axis_state: u8,
+    /// `axis0.active_errors` bitmask (0 = healthy)
+    pub active_errors: u32,
+    /// `axis0.disarm_reason`: the error that last disarmed the axis
+    pub disarm_reason: u32,
+}
+
+/// RC PWM pulse range (µs) the ODrive spreads `gpio8_pwm_mapping` over
+const PULSE_MIN_US: f32 = 1000.0;
+const PULSE_MAX_US: f32 = 2000.0;
+/// Narrowest mapping (turns end to end) the driver will convert with
+const MIN_MAPPING_SPAN_TURNS: f32 = 0.01;
+
+/// The ODrive's `gpio8_pwm_mapping`: motor position (turns) at a 1000 µs
+/// and a 2000 µs pulse, linear in between.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PwmMapping {
+    pub min: f32,
+    pub max: f32,
+}
+
+impl PwmMapping {
+    /// Position the ODrive moves to for `pulse_us` on its PWM input.
+    pub fn turns(&self, pulse_us: f32) -> f32 {
+        let frac = (pulse_us.clamp(PULSE_MIN_US, PULSE_MAX_US) - PULSE_MIN_US) / (PULSE_MAX_US - PULSE_MIN_US);
+        self.min + frac * (self.max - self.min)
+    }
+
+    /// Pulse width that maps to `turns`. Not clamped, so a motor past either
+    /// end reads as a pulse outside 1000–2000 µs.
+    pub fn pulse_us(&self, turns: f32) -> f32 {
+        PULSE_MIN_US + (turns - self.min) / (self.max - self.min) * (PULSE_MAX_US - PULSE_MIN_US)
+    }
+}
+
+#[derive(Clone, Copy)]
+enum SlowRead {
+    Vbus,
+    Ibus,
+    AxisState,
+    ActiveErrors,
+    DisarmReason,
+}
+
+impl SlowRead {
+    const ROTATION: [SlowRead; 5] = [
+        SlowRead::Vbus,
+        SlowRead::AxisState,
+        SlowRead::ActiveErrors,
+        SlowRead::Ibus,
+        SlowRead::DisarmReason,
+    ];
+
+    fn command(self) -> &'static str {
+        match self {
+            SlowRead::Vbus => "r vbus_voltage",
+            SlowRead::Ibus => "r ibus",
+            SlowRead::AxisState => "r axis0.current_state",
+            SlowRead::ActiveErrors => "r axis0.active_errors",
+            SlowRead::DisarmReason => "r axis0.disarm_reason",
+        }
+    }
+}
+
+/// Longest reply we accept: "-123.456789 -1234.56789*255"
+const MAX_REPLY: usize = 48;
+
+pub struct Odrive<L: OdriveLink> {
+    link: L,
+    /// Used in log lines ("airbrake", "BLiMS")
+    name: &'static str,
+    pub status: OdriveStatus,
+    /// Read from the ODrive before the first link-up
+    mapping: Option<PwmMapping>,
+    link_up: bool,
+    good_count: u8,
+    miss_count: u8,
+    next_probe: Option<Instant>,
+    slow_index: usize,
+}
+
+impl<L: OdriveLink> Odrive<L> {
+    pub fn new(link: L, name: &'static str) -> Self {
+        Self {
+            link,
+            name,
+            status: OdriveStatus::default(),
+            mapping: None,
+            link_up: false,
+            good_count: 0,
+            miss_count: 0,
+            next_probe: None,
+            slow_index: 0,
+        }
+    }
+
+    /// True while the UART link owns the setpoint; false means RC PWM does.
+    pub fn link_up(&self) -> bool {
+        self.link_up
+    }
+
+    /// The ODrive's PWM mapping, once a link-up has read it.
+    pub fn mapping(&self) -> Option<PwmMapping> {
+        self.mapping
+    }
+
+    /// Encoder position as the RC pulse (µs) that maps to it, once the
+    /// mapping is known.
+    pub fn position_pulse_us(&self) -> Option<f32> {
+        self.mapping.map(|m| m.pulse_us(self.status.pos_turns))
+    }
+
+    /// Call once per flight-loop cycle with the pulse width (µs) the PWM
+    /// path is outputting for the current setpoint. Sends the setpoint only
+    /// while the link is up; while probing it just reads, so PWM stays the
+    /// only source of commands.
+    pub async fn update(&mut self, pulse_us: f32) {
+        if let Some(at) = self.next_probe {
+            if Instant::now() < at {
+                return;
+            }
+            self.next_probe = None;
+        }
+
+        let setpoint = match self.mapping {
+            Some(m) if self.link_up => Some(m.turns(pulse_us)),
+            _ => None,
+        };
+        match self.exchange(setpoint).await {
+            Ok(()) => {
+                self.miss_count = 0;
+                if !self.link_up {
+                    self.good_count += 1;
+                    if self.good_count >= constants::ODRIVE_LINK_UP_COUNT {
+                        self.take_over().await;
+                    }
+                }
+            }
+            Err(e) => {
+                self.good_count = 0;
+                self.drain().await;
+                if self.link_up {
+                    self.miss_count += 1;
+                    if self.miss_count >= constants::ODRIVE_LINK_DOWN_COUNT {
+                        self.link_up = false;
+                        self.miss_count = 0;
+                        log::warn!("ODrive {}: UART link lost ({:?}), falling back to RC PWM", self.name, e);
+                        self.schedule_probe();
+                    }
+                } else {
+                    self.schedule_probe();
+                }
+            }
+        }
+    }
+
+    /// Reads the PWM mapping and, if it's usable, hands the setpoint to the
+    /// UART link. Otherwise PWM keeps command and the link is re-probed.
+    async fn take_over(&mut self) {
+        self.good_count = 0;
+        match self.read_mapping().await {
+            Ok(m) => {
+                if self.mapping != Some(m) {
+                    log::info!("ODrive {}: gpio8_pwm_mapping {:.4} to {:.4} turns", self.name, m.min, m.max);
+                }
+                self.mapping = Some(m);
+                self.link_up = true;
+                log::info!("ODrive {}: UART link up, taking over from RC PWM", self.name);
+            }
+            Err(e) => {
+                self.drain().await;
+                log::warn!("ODrive {}: can't read gpio8_pwm_mapping ({:?}), staying on RC PWM", self.name, e);
+                self.schedule_probe();
+            }
+        }
+    }
+
+    async fn read_mapping(&mut self) -> Result<PwmMapping, OdriveError> {
+        let mut buf = [0u8; MAX_REPLY];
+        self.send("r config.gpio8_pwm_mapping.min").await?;
+        let min = parse_f32(Some(self.read_reply(&mut buf).await?.trim()))?;
+        self.send("r config.gpio8_pwm_mapping.max").await?;
+        let max = parse_f32(Some(self.read_reply(&mut buf).await?.trim()))?;
+        if (max - min).abs() < MIN_MAPPING_SPAN_TURNS {
+            return Err(OdriveError::Mapping);
+        }
+        Ok(PwmMapping { min, max })
+    }
+
+    fn schedule_probe(&mut self) {
+        self.next_probe = Some(Instant::now() + Duration::from_millis(constants::ODRIVE_REPROBE_MS));
+    }
+
+    /// One cycle's traffic: optional setpoint, feedback, one slow read.
+    async fn exchange(&mut self, setpoint: Option<f32>) -> Result<(), OdriveError> {
+        if let Some(pos) = setpoint {
+            let mut cmd = heapless::String::<32>::new();
+            let _ = write!(cmd, "p 0 {:.4}", pos);
+            self.send(&cmd).await?;
+        }
+
+        let mut buf = [0u8; MAX_REPLY];
+        self.send("f 0").await?;
+        let reply = self.read_reply(&mut buf).await?;
+        let mut fields = reply.split_ascii_whitespace();
+        let pos = parse_f32(fields.next())?;
+        let vel = parse_f32(fields.next())?;
+        self.status.pos_turns = pos;
+        self.status.vel_turns_s = vel;
+
+        let slow = SlowRead::ROTATION[self.slow_index];
+        self.slow_index = (self.slow_index + 1) % SlowRead::ROTATION.len();
+        self.send(slow.command()).await?;
+        let reply = self.read_reply(&mut buf).await?.trim();
+        match slow {
+            SlowRead::Vbus => self.status.vbus_v = parse_f32(Some(reply))?,
+            SlowRead::Ibus => self.status.ibus_a = parse_f32(Some(reply))?,
+            SlowRead::AxisState => self.status.axis_state = reply.parse().map_err(|_| OdriveError::Parse)?,
+            SlowRead::ActiveErrors => {
+                let errors = reply.parse().map_err(|_| OdriveError::Parse)?;
+                if errors != self.status.active_errors {
+                    log::warn!("ODrive {}: active_errors = {:#x}", self.name, errors);
+                }
+                self.status.active_errors = errors;
+            }
+            SlowRead::DisarmReason => self.status.disarm_reason = reply.parse().map_err(|_| OdriveError::Parse)?,
+        }
+        Ok(())
+    }
+
+    /// Sends `cmd*checksum\n`.
+    async fn send(&mut self, cmd: &str) -> Result<(), OdriveError> {
+        let mut line = heapless::String::<48>::new();
+        let _ = writeln!(line, "{}*{}", cmd, checksum(cmd.as_bytes()));
+        self.link.write(line.as_bytes()).await
+    }
+
+    /// Reads one `\n`-terminated reply into `buf` and returns it without the
+    /// line ending or checksum. Replies to checksummed commands carry one.
+    async fn read_reply<'b>(&mut self, buf: &'b mut [u8; MAX_REPLY]) -> Result<&'b str, OdriveError> {
+        let timeout = Duration::from_millis(constants::ODRIVE_REPLY_TIMEOUT_MS);
+        let link = &mut self.link;
+        let len = with_timeout(timeout, async {
+            let mut len = 0;
+            loop {
+                let b = link.read_byte().await?;
+                if b == b'\n' {
+                    return Ok(len);
+                }
+                if len == buf.len() {
+                    return Err(OdriveError::Overflow);
+                }
+                buf[len] = b;
+                len += 1;
+            }
+        })
+        .await
+        .map_err(|_| OdriveError::Timeout)??;
+
+        let line = core::str::from_utf8(&buf[..len]).map_err(|_| OdriveError::Parse)?;
+        let line = line.trim_end_matches('\r');
+        match line.rsplit_once('*') {
+            Some((body, sum)) => {
+                let sum: u8 = sum.parse().map_err(|_| OdriveError::Checksum)?;
+                if sum != checksum(body.as_bytes()) {
+                    return Err(OdriveError::Checksum);
+                }
+                Ok(body)
+            }
+            None => Ok(line),
+        }
+    }
+
+    /// Discards whatever is left of a late or partial reply so it can't be
+    /// read as the answer to the next command.
+    async fn drain(&mut self) {
+        for _ in 0..MAX_REPLY * 2 {
+            match with_timeout(Duration::from_millis(1), self.link.read_byte()).await {
+                Ok(Ok(_)) => {}
+                _ => break,
+            }
+        }
+    }
+}
+
+/// ASCII protocol checksum: XOR of every byte before the `*`.
+pub fn checksum(bytes: &[u8]) -> u8 {
+    bytes.iter().fold(0, |acc, b| acc ^ b)
+}
+
+fn parse_f32(field: Option<&str>) -> Result<f32, OdriveError> {
+    let v: f32 = field.ok_or(OdriveError::Parse)?.parse().map_err(|_| OdriveError::Parse)?;
+    if v.is_finite() { Ok(v) } else { Err(OdriveError::Parse) }
+}
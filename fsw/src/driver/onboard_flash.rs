@@ -7,8 +7,14 @@
 //! - Shared SPI bus support with embassy-embedded-hal
 
 use embedded_hal_async::spi::SpiDevice;
-use crate::packet::{Packet, FastRecord, FAST_RECORD_TAG, FULL_RECORD_TAG};
+use crate::packet::{
+    Packet, BlimsDiagRecord, ConfigPacket, FastRecord, SessionRecord,
+    BLIMS_DIAG_RECORD_TAG, CONFIG_RECORD_TAG, FAST_RECORD_TAG, FULL_RECORD_TAG, SESSION_RECORD_TAG,
+};
 use crate::module::SpiDevice as SpiDeviceType;
+use controller_in_rust_v3::{Config as AirbrakeConfig, DragModel};
+use blims::blims_constants::{MAX_FENCE_VERTICES, MAX_FENCE_ZONES, MAX_WIND_LAYERS};
+use blims::{BlimsConfig, Geofence, LatLon, WindLayer, WindProfile, Zone, ZoneKind};
 
 /// Total flash size: 16 MiB
 const FLASH_SIZE: u32 = 16 * 1024 * 1024;
@@ -25,9 +31,37 @@ const SNAPSHOT_RING_BASE: u32 = 0x100000;
 const SNAPSHOT_RING_SIZE: u32 = 0x10000;
 pub const SNAPSHOT_RECORD_SIZE: u32 = 64;
 const SNAPSHOT_MAGIC: [u8; 2] = [0x5A, 0xA5];
+
+/// Airbrake controller config: one record in the sector after the snapshot
+/// ring, rewritten (erase + program) whenever the config changes on the pad.
+const AIRBRAKE_CONFIG_BASE: u32 = SNAPSHOT_RING_BASE + SNAPSHOT_RING_SIZE;
+const AIRBRAKE_CONFIG_SIZE: usize = 26;
+const AIRBRAKE_CONFIG_MAGIC: [u8; 2] = [0xAB, 0xC0];
+
+/// BLiMS forecast wind profile: one record in the sector after the airbrake
+/// config, rewritten whenever a profile is uploaded or cleared on the pad.
+const WIND_PROFILE_BASE: u32 = AIRBRAKE_CONFIG_BASE + SECTOR_SIZE;
+const WIND_PROFILE_LAYER_SIZE: usize = 12;
+const WIND_PROFILE_SIZE: usize = 3 + MAX_WIND_LAYERS * WIND_PROFILE_LAYER_SIZE + 4;
+const WIND_PROFILE_MAGIC: [u8; 2] = [0xB1, 0x3D];
+
+/// BLiMS geofence: one record in the sector after the wind profile,
+/// rewritten whenever a geofence is uploaded or cleared on the pad. Spans
+/// several pages.
+const GEOFENCE_BASE: u32 = WIND_PROFILE_BASE + SECTOR_SIZE;
+const GEOFENCE_ZONE_SIZE: usize = 2 + MAX_FENCE_VERTICES * 16;
+const GEOFENCE_SIZE: usize = 3 + MAX_FENCE_ZONES * GEOFENCE_ZONE_SIZE + 4;
+const GEOFENCE_MAGIC: [u8; 2] = [0xB1, 0x6F];
+
+/// BLiMS controller config: one record in the sector after the geofence,
+/// rewritten whenever the config changes on the pad.
+const BLIMS_CONFIG_BASE: u32 = GEOFENCE_BASE + SECTOR_SIZE;
+const BLIMS_CONFIG_SIZE: usize = 34;
+const BLIMS_CONFIG_MAGIC: [u8; 2] = [0xB1, 0xC0];
 const SNAPSHOT_EMPTY_SEQ: u32 = 0xFFFF_FFFF;
 
-#[derive(Debug, defmt::Format)]
+#[derive(Debug)]
+#[cfg_attr(target_os = "none", derive(defmt::Format))]
 pub enum Error {
     Read,
     Write,
@@ -75,7 +109,7 @@ pub struct Snapshot {
 }
 
 impl Snapshot {
-    fn to_bytes(&self) -> [u8; 64] {
+    fn to_bytes(self) -> [u8; 64] {
         let mut b = [0xFFu8; 64];
         b[0..2].copy_from_slice(&SNAPSHOT_MAGIC);
         b[2..6].copy_from_slice(&self.seq.to_le_bytes());
@@ -237,7 +271,7 @@ impl<'a> OnboardFlash<'a> {
         Ok(())
     }
 
-    /// Append a 20 Hz fast record (tag byte + 84 payload bytes = 85 bytes total).
+    /// Append a 20 Hz fast record (tag byte + 102 payload bytes = 103 bytes total).
     pub async fn append_fast_record(&mut self, fast: &FastRecord) -> Result<(), Error> {
         let payload = fast.to_bytes();
         let mut buf = [0u8; 1 + FastRecord::SIZE];
@@ -246,7 +280,7 @@ impl<'a> OnboardFlash<'a> {
         self.append_raw(&buf).await
     }
 
-    /// Append a 5 Hz full record (tag byte + 199 payload bytes = 200 bytes total).
+    /// Append a 1 Hz full record (tag byte + 270 payload bytes = 271 bytes total).
     pub async fn append_full_record(&mut self, packet: &Packet) -> Result<(), Error> {
         let payload = packet.to_bytes();
         let mut buf = [0u8; 1 + Packet::SIZE];
@@ -255,6 +289,32 @@ impl<'a> OnboardFlash<'a> {
         self.append_raw(&buf).await
     }
 
+    /// Append a session header (tag byte + 22 payload bytes = 23 bytes total).
+    pub async fn append_session_record(&mut self, session: &SessionRecord) -> Result<(), Error> {
+        let payload = session.to_bytes();
+        let mut buf = [0u8; 1 + SessionRecord::SIZE];
+        buf[0] = SESSION_RECORD_TAG;
+        buf[1..].copy_from_slice(&payload);
+        self.append_raw(&buf).await
+    }
+
+    /// Append a BLiMS diagnostics record (tag byte + 44 payload bytes = 45 bytes total).
+    pub async fn append_blims_diag_record(&mut self, diag: &BlimsDiagRecord) -> Result<(), Error> {
+        let payload = diag.to_bytes();
+        let mut buf = [0u8; 1 + BlimsDiagRecord::SIZE];
+        buf[0] = BLIMS_DIAG_RECORD_TAG;
+        buf[1..].copy_from_slice(&payload);
+        self.append_raw(&buf).await
+    }
+
+    pub async fn append_config_record(&mut self, config: &ConfigPacket) -> Result<(), Error> {
+        let payload = config.to_bytes();
+        let mut buf = [0u8; 1 + ConfigPacket::SIZE];
+        buf[0] = CONFIG_RECORD_TAG;
+        buf[1..].copy_from_slice(&payload);
+        self.append_raw(&buf).await
+    }
+
     async fn append_raw(&mut self, data: &[u8]) -> Result<(), Error> {
         let mut current_data = data;
         while !current_data.is_empty() {
@@ -263,7 +323,7 @@ impl<'a> OnboardFlash<'a> {
             }
 
             // Sector Erase if aligned
-            if self.write_offset % SECTOR_SIZE == 0 {
+            if self.write_offset.is_multiple_of(SECTOR_SIZE) {
                 self.erase_sector(self.write_offset).await?;
             }
 
@@ -333,7 +393,11 @@ impl<'a> OnboardFlash<'a> {
     pub async fn write_packet(&mut self, packet: &Packet) -> Result<(), Error> {
         self.erase_sector(Self::PACKET_OFFSET).await?;
         let bytes = packet.to_bytes();
-        self.program_page(Self::PACKET_OFFSET, &bytes).await
+        // Packet is longer than one page; program it a page at a time
+        for (i, page) in bytes.chunks(PAGE_SIZE as usize).enumerate() {
+            self.program_page(Self::PACKET_OFFSET + i as u32 * PAGE_SIZE, page).await?;
+        }
+        Ok(())
     }
 
     pub async fn read_packet(&mut self) -> Result<Packet, Error> {
@@ -400,7 +464,7 @@ impl<'a> OnboardFlash<'a> {
         snap.seq = self.snapshot_next_seq;
         let bytes = snap.to_bytes();
 
-        if self.snapshot_offset % SECTOR_SIZE == 0 {
+        if self.snapshot_offset.is_multiple_of(SECTOR_SIZE) {
             self.erase_sector(self.snapshot_offset).await?;
         }
         self.program_page(self.snapshot_offset, &bytes).await?;
@@ -426,12 +490,11 @@ impl<'a> OnboardFlash<'a> {
             let addr = SNAPSHOT_RING_BASE + i * SNAPSHOT_RECORD_SIZE;
             self.read(addr, &mut buf).await?;
             crate::watchdog::feed();
-            if let Some(snap) = Snapshot::from_bytes(&buf) {
-                if snap.seq != SNAPSHOT_EMPTY_SEQ
-                    && best.as_ref().map_or(true, |b| snap.seq > b.seq)
-                {
-                    best = Some(snap);
-                }
+            if let Some(snap) = Snapshot::from_bytes(&buf)
+                && snap.seq != SNAPSHOT_EMPTY_SEQ
+                && best.as_ref().is_none_or(|b| snap.seq > b.seq)
+            {
+                best = Some(snap);
             }
         }
         Ok(best)
@@ -450,10 +513,217 @@ impl<'a> OnboardFlash<'a> {
         Ok(())
     }
 
+    /// Store the airbrake controller config, replacing the previous one.
+    ///
+    /// Layout: magic[2], target_apogee_m, max_deploy_rate_per_s,
+    /// min_activation_vel_mps, body_cda_m2, brake_cda_m2 (f32 LE each), crc u32.
+    pub async fn write_airbrake_config(&mut self, cfg: &AirbrakeConfig) -> Result<(), Error> {
+        let mut b = [0xFFu8; AIRBRAKE_CONFIG_SIZE];
+        b[0..2].copy_from_slice(&AIRBRAKE_CONFIG_MAGIC);
+        b[2..6].copy_from_slice(&cfg.target_apogee_m.to_le_bytes());
+        b[6..10].copy_from_slice(&cfg.max_deploy_rate_per_s.to_le_bytes());
+        b[10..14].copy_from_slice(&cfg.min_activation_vel_mps.to_le_bytes());
+        b[14..18].copy_from_slice(&cfg.drag.body_cda_m2.to_le_bytes());
+        b[18..22].copy_from_slice(&cfg.drag.brake_cda_m2.to_le_bytes());
+        let crc = Snapshot::crc(&b[0..22]);
+        b[22..26].copy_from_slice(&crc.to_le_bytes());
+
+        self.erase_sector(AIRBRAKE_CONFIG_BASE).await?;
+        self.program_page(AIRBRAKE_CONFIG_BASE, &b).await
+    }
+
+    /// Read the stored airbrake controller config. `None` if nothing has
+    /// been stored or the record is corrupt; range checks are the caller's.
+    pub async fn read_airbrake_config(&mut self) -> Result<Option<AirbrakeConfig>, Error> {
+        let mut b = [0u8; AIRBRAKE_CONFIG_SIZE];
+        self.read(AIRBRAKE_CONFIG_BASE, &mut b).await?;
+        if b[0..2] != AIRBRAKE_CONFIG_MAGIC {
+            return Ok(None);
+        }
+        let stored_crc = u32::from_le_bytes([b[22], b[23], b[24], b[25]]);
+        if stored_crc != Snapshot::crc(&b[0..22]) {
+            return Ok(None);
+        }
+        let f32at = |i: usize| f32::from_le_bytes([b[i], b[i+1], b[i+2], b[i+3]]);
+        Ok(Some(AirbrakeConfig {
+            target_apogee_m: f32at(2),
+            max_deploy_rate_per_s: f32at(6),
+            min_activation_vel_mps: f32at(10),
+            drag: DragModel {
+                body_cda_m2: f32at(14),
+                brake_cda_m2: f32at(18),
+            },
+        }))
+    }
+
+    /// Store the BLiMS wind profile, replacing the previous one.
+    ///
+    /// Layout: magic[2], layer count u8, then per layer altitude_m,
+    /// from_deg, speed_ms (f32 LE each, unused layers 0xFF), crc u32.
+    pub async fn write_wind_profile(&mut self, profile: &WindProfile) -> Result<(), Error> {
+        let mut b = [0xFFu8; WIND_PROFILE_SIZE];
+        b[0..2].copy_from_slice(&WIND_PROFILE_MAGIC);
+        b[2] = profile.len() as u8;
+        for (i, layer) in profile.layers().iter().enumerate() {
+            let o = 3 + i * WIND_PROFILE_LAYER_SIZE;
+            b[o..o+4].copy_from_slice(&layer.altitude_m.to_le_bytes());
+            b[o+4..o+8].copy_from_slice(&layer.from_deg.to_le_bytes());
+            b[o+8..o+12].copy_from_slice(&layer.speed_ms.to_le_bytes());
+        }
+        let crc_at = WIND_PROFILE_SIZE - 4;
+        let crc = Snapshot::crc(&b[0..crc_at]);
+        b[crc_at..].copy_from_slice(&crc.to_le_bytes());
+
+        self.erase_sector(WIND_PROFILE_BASE).await?;
+        self.program_page(WIND_PROFILE_BASE, &b).await
+    }
+
+    /// Read the stored BLiMS wind profile. `None` if nothing has been
+    /// stored, the record is corrupt, or its layers no longer validate.
+    pub async fn read_wind_profile(&mut self) -> Result<Option<WindProfile>, Error> {
+        let mut b = [0u8; WIND_PROFILE_SIZE];
+        self.read(WIND_PROFILE_BASE, &mut b).await?;
+        if b[0..2] != WIND_PROFILE_MAGIC {
+            return Ok(None);
+        }
+        let crc_at = WIND_PROFILE_SIZE - 4;
+        let stored_crc = u32::from_le_bytes([b[crc_at], b[crc_at+1], b[crc_at+2], b[crc_at+3]]);
+        if stored_crc != Snapshot::crc(&b[0..crc_at]) {
+            return Ok(None);
+        }
+        let count = b[2] as usize;
+        if count > MAX_WIND_LAYERS {
+            return Ok(None);
+        }
+        let f32at = |i: usize| f32::from_le_bytes([b[i], b[i+1], b[i+2], b[i+3]]);
+        let mut layers = [WindLayer::default(); MAX_WIND_LAYERS];
+        for (i, layer) in layers[..count].iter_mut().enumerate() {
+            let o = 3 + i * WIND_PROFILE_LAYER_SIZE;
+            *layer = WindLayer { altitude_m: f32at(o), from_deg: f32at(o + 4), speed_ms: f32at(o + 8) };
+        }
+        Ok(WindProfile::from_layers(&layers[..count]))
+    }
+
+    /// Store the BLiMS geofence, replacing the previous one.
+    ///
+    /// Layout: magic[2], zone count u8, then per zone kind u8, vertex count
+    /// u8 and MAX_FENCE_VERTICES × (lat_deg, lon_deg) (f64 LE each, unused
+    /// vertices and zones 0xFF), crc u32.
+    pub async fn write_geofence(&mut self, fence: &Geofence) -> Result<(), Error> {
+        let mut b = [0xFFu8; GEOFENCE_SIZE];
+        b[0..2].copy_from_slice(&GEOFENCE_MAGIC);
+        b[2] = fence.len() as u8;
+        for (i, zone) in fence.zones().iter().enumerate() {
+            let o = 3 + i * GEOFENCE_ZONE_SIZE;
+            b[o] = zone.kind() as u8;
+            b[o+1] = zone.vertices().len() as u8;
+            for (j, v) in zone.vertices().iter().enumerate() {
+                let o = o + 2 + j * 16;
+                b[o..o+8].copy_from_slice(&v.lat_deg.to_le_bytes());
+                b[o+8..o+16].copy_from_slice(&v.lon_deg.to_le_bytes());
+            }
+        }
+        let crc_at = GEOFENCE_SIZE - 4;
+        let crc = Snapshot::crc(&b[0..crc_at]);
+        b[crc_at..].copy_from_slice(&crc.to_le_bytes());
+
+        self.erase_sector(GEOFENCE_BASE).await?;
+        for (i, page) in b.chunks(PAGE_SIZE as usize).enumerate() {
+            self.program_page(GEOFENCE_BASE + i as u32 * PAGE_SIZE, page).await?;
+        }
+        Ok(())
+    }
+
+    /// Read the stored BLiMS geofence. `None` if nothing has been stored,
+    /// the record is corrupt, or its zones no longer validate.
+    pub async fn read_geofence(&mut self) -> Result<Option<Geofence>, Error> {
+        let mut b = [0u8; GEOFENCE_SIZE];
+        self.read(GEOFENCE_BASE, &mut b).await?;
+        if b[0..2] != GEOFENCE_MAGIC {
+            return Ok(None);
+        }
+        let crc_at = GEOFENCE_SIZE - 4;
+        let stored_crc = u32::from_le_bytes([b[crc_at], b[crc_at+1], b[crc_at+2], b[crc_at+3]]);
+        if stored_crc != Snapshot::crc(&b[0..crc_at]) {
+            return Ok(None);
+        }
+        let count = b[2] as usize;
+        if count > MAX_FENCE_ZONES {
+            return Ok(None);
+        }
+        let f64at = |i: usize| f64::from_le_bytes(b[i..i+8].try_into().unwrap());
+        let mut zones = heapless::Vec::<Zone, MAX_FENCE_ZONES>::new();
+        for i in 0..count {
+            let o = 3 + i * GEOFENCE_ZONE_SIZE;
+            let (Some(kind), len) = (ZoneKind::from_id(b[o]), b[o+1] as usize) else {
+                return Ok(None);
+            };
+            if len > MAX_FENCE_VERTICES {
+                return Ok(None);
+            }
+            let mut vertices = [LatLon::default(); MAX_FENCE_VERTICES];
+            for (j, v) in vertices[..len].iter_mut().enumerate() {
+                let o = o + 2 + j * 16;
+                *v = LatLon::new(f64at(o), f64at(o + 8));
+            }
+            let Some(zone) = Zone::new(kind, &vertices[..len]) else {
+                return Ok(None);
+            };
+            let _ = zones.push(zone);
+        }
+        Ok(Geofence::from_zones(&zones))
+    }
+
+    /// Store the BLiMS controller config, replacing the previous one.
+    ///
+    /// Layout: magic[2], kp, ki, integral_max_in, alt_upwind_ft,
+    /// alt_neutral_ft, phase_hysteresis_ft (f32 LE each), initial_hold_ms
+    /// u32 LE, crc u32.
+    pub async fn write_blims_config(&mut self, cfg: &BlimsConfig) -> Result<(), Error> {
+        let mut b = [0xFFu8; BLIMS_CONFIG_SIZE];
+        b[0..2].copy_from_slice(&BLIMS_CONFIG_MAGIC);
+        b[2..6].copy_from_slice(&cfg.kp.to_le_bytes());
+        b[6..10].copy_from_slice(&cfg.ki.to_le_bytes());
+        b[10..14].copy_from_slice(&cfg.integral_max_in.to_le_bytes());
+        b[14..18].copy_from_slice(&cfg.alt_upwind_ft.to_le_bytes());
+        b[18..22].copy_from_slice(&cfg.alt_neutral_ft.to_le_bytes());
+        b[22..26].copy_from_slice(&cfg.phase_hysteresis_ft.to_le_bytes());
+        b[26..30].copy_from_slice(&cfg.initial_hold_ms.to_le_bytes());
+        let crc = Snapshot::crc(&b[0..30]);
+        b[30..34].copy_from_slice(&crc.to_le_bytes());
+
+        self.erase_sector(BLIMS_CONFIG_BASE).await?;
+        self.program_page(BLIMS_CONFIG_BASE, &b).await
+    }
+
+    /// Read the stored BLiMS controller config. `None` if nothing has been
+    /// stored or the record is corrupt; range checks are the caller's.
+    pub async fn read_blims_config(&mut self) -> Result<Option<BlimsConfig>, Error> {
+        let mut b = [0u8; BLIMS_CONFIG_SIZE];
+        self.read(BLIMS_CONFIG_BASE, &mut b).await?;
+        if b[0..2] != BLIMS_CONFIG_MAGIC {
+            return Ok(None);
+        }
+        let stored_crc = u32::from_le_bytes([b[30], b[31], b[32], b[33]]);
+        if stored_crc != Snapshot::crc(&b[0..30]) {
+            return Ok(None);
+        }
+        let f32at = |i: usize| f32::from_le_bytes([b[i], b[i+1], b[i+2], b[i+3]]);
+        Ok(Some(BlimsConfig {
+            kp: f32at(2),
+            ki: f32at(6),
+            integral_max_in: f32at(10),
+            alt_upwind_ft: f32at(14),
+            alt_neutral_ft: f32at(18),
+            phase_hysteresis_ft: f32at(22),
+            initial_hold_ms: u32::from_le_bytes([b[26], b[27], b[28], b[29]]),
+        }))
+    }
+
     pub async fn wipe_storage(&mut self) -> Result<(), Error> {
         // Only erase sectors that have been written to, not all 3584 sectors
-        let end = (self.write_offset + SECTOR_SIZE - 1) / SECTOR_SIZE * SECTOR_SIZE;
-        let total_sectors = ((end - STORAGE_OFFSET) / SECTOR_SIZE) as u32;
+        let end = self.write_offset.div_ceil(SECTOR_SIZE) * SECTOR_SIZE;
+        let total_sectors = (end - STORAGE_OFFSET) / SECTOR_SIZE;
         let mut addr = STORAGE_OFFSET;
         let mut done: u32 = 0;
         // Print progress every PROGRESS_EVERY sectors so the serial monitor
@@ -467,7 +737,7 @@ impl<'a> OnboardFlash<'a> {
             crate::watchdog::feed();
             addr += SECTOR_SIZE;
             done += 1;
-            if done % PROGRESS_EVERY == 0 || done == total_sectors {
+            if done.is_multiple_of(PROGRESS_EVERY) || done == total_sectors {
                 let mut msg = heapless::String::<48>::new();
                 let _ = core::fmt::write(
                     &mut msg,
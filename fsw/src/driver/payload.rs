@@ -0,0 +1,547 @@
+//! Payload board link over UART0
+//!
+//! Flight builds speak the ASCII protocol the payload firmware reads today:
+//! one line per event (`N1\n`–`N4\n`, `A1\n`, `A3\n`, and
+//! `A2,<alt m>,<vel m/s>\n`) and `A\n` every `PAYLOAD_HEARTBEAT_MS`, with
+//! nothing read back. The link then reports `LinkState::NeverHeard` and no
+//! ACKs.
+//!
+//! With the `payload_frames` feature it speaks the framed, acknowledged
+//! protocol in `PAYLOAD_REFERENCE.md` instead; keep it off until the
+//! payload firmware ships the same spec. Every message in either direction
+//! is one binary frame:
+//!
+//! ```text
+//! 0xA5 | type | seq | len | body[len] | crc16 (LE)
+//! ```
+//!
+//! where the CRC is CRC-16/CCITT-FALSE over `type..body`. The FSW sends a
+//! `CMD` frame per payload event (N1–N4, A1–A3) and a `HEARTBEAT` carrying
+//! the flight mode every `PAYLOAD_HEARTBEAT_MS`. The payload board answers a
+//! command with an `ACK` echoing its seq and a heartbeat with a `STATUS`.
+//!
+//! Commands go one at a time. The head of the queue is re-sent every
+//! `PAYLOAD_ACK_TIMEOUT_MS` until acked, up to `PAYLOAD_MAX_TRIES` sends,
+//! then logged as unacknowledged and dropped so later events still go out.
+//! A retry keeps its seq, so the payload board acks a repeat of the last seq
+//! it acted on without acting on it again.
+//!
+//! Seqs restart at 0 when the CFC boots, so a command after a reboot can
+//! carry the seq the payload board last acted on. Before its first command
+//! the FSW sends `HELLO` until acked; the payload board forgets its last seq
+//! on `HELLO`, and commands queued meanwhile wait for the ACK.
+//!
+//! The link is up while a valid frame has arrived within the last
+//! `PAYLOAD_LINK_TIMEOUT_MS`; frames with a bad CRC or length are dropped.
+//!
+//! The byte transport is the [`PayloadLink`] trait: hardware UART0 on the
+//! flight board (`module.rs`), a simulated payload board in the SIL build.
+
+use embassy_time::{Duration, Instant};
+
+use crate::constants;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PayloadError {
+    /// UART write failed
+    Uart,
+    /// Frame CRC didn't match
+    Crc,
+    /// Frame body longer than `MAX_BODY`
+    Length,
+    /// Command queue full; the command was not sent
+    QueueFull,
+}
+
+/// Byte transport to the payload board.
+#[allow(async_fn_in_trait)]
+pub trait PayloadLink {
+    async fn write(&mut self, data: &[u8]) -> Result<(), PayloadError>;
+    /// Copies bytes already received into `buf` without waiting and
+    /// returns how many; 0 when nothing is buffered.
+    fn try_read(&mut self, buf: &mut [u8]) -> usize;
+}
+
+/// Start-of-frame byte
+pub const SOF: u8 = 0xA5;
+/// Longest frame body either side sends
+pub const MAX_BODY: usize = 16;
+/// SOF, type, seq, len, body, CRC
+pub const MAX_FRAME: usize = 4 + MAX_BODY + 2;
+
+/// Frame `type` byte. FSW → payload below 0x80, payload → FSW above.
+pub mod frame_type {
+    /// Body: command id, then any arguments (A2: altitude, velocity as f32 LE)
+    pub const CMD: u8 = 0x01;
+    /// Body: flight mode
+    pub const HEARTBEAT: u8 = 0x02;
+    /// Sent once per CFC boot; body: empty
+    pub const HELLO: u8 = 0x03;
+    /// seq echoes the command's; body: command id (0 for `HELLO`)
+    pub const ACK: u8 = 0x81;
+    /// Body: state, flags, battery mV (u16 LE), last command id acted on
+    pub const STATUS: u8 = 0x82;
+}
+
+/// Payload events the FSW sends
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PayloadCommand {
+    N1,
+    N2,
+    N3,
+    N4,
+    A1,
+    /// Fault with altitude (m) and vertical velocity (m/s) at the fault
+    A2 { alt_m: f32, vel_ms: f32 },
+    A3,
+}
+
+impl PayloadCommand {
+    /// Wire id, also reported in telemetry as `payload_last_ack`
+    pub fn id(self) -> u8 {
+        match self {
+            PayloadCommand::N1 => 1,
+            PayloadCommand::N2 => 2,
+            PayloadCommand::N3 => 3,
+            PayloadCommand::N4 => 4,
+            PayloadCommand::A1 => 5,
+            PayloadCommand::A2 { .. } => 6,
+            PayloadCommand::A3 => 7,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            PayloadCommand::N1 => "N1",
+            PayloadCommand::N2 => "N2",
+            PayloadCommand::N3 => "N3",
+            PayloadCommand::N4 => "N4",
+            PayloadCommand::A1 => "A1",
+            PayloadCommand::A2 { .. } => "A2",
+            PayloadCommand::A3 => "A3",
+        }
+    }
+
+    /// Name for a wire id, for logs on the receiving side
+    pub fn name_of(id: u8) -> &'static str {
+        match id {
+            0 => "none",
+            1 => "N1",
+            2 => "N2",
+            3 => "N3",
+            4 => "N4",
+            5 => "A1",
+            6 => "A2",
+            7 => "A3",
+            _ => "?",
+        }
+    }
+
+    fn body(self, buf: &mut [u8; MAX_BODY]) -> usize {
+        buf[0] = self.id();
+        match self {
+            PayloadCommand::A2 { alt_m, vel_ms } => {
+                buf[1..5].copy_from_slice(&alt_m.to_le_bytes());
+                buf[5..9].copy_from_slice(&vel_ms.to_le_bytes());
+                9
+            }
+            _ => 1,
+        }
+    }
+}
+
+/// One decoded frame
+#[derive(Debug, Clone, Copy)]
+pub struct Frame {
+    pub kind: u8,
+    pub seq: u8,
+    len: u8,
+    body: [u8; MAX_BODY],
+}
+
+impl Frame {
+    pub fn body(&self) -> &[u8] {
+        &self.body[..self.len as usize]
+    }
+}
+
+/// Writes one frame into `out` and returns its length.
+pub fn encode(kind: u8, seq: u8, body: &[u8], out: &mut [u8; MAX_FRAME]) -> usize {
+    let len = body.len().min(MAX_BODY);
+    out[0] = SOF;
+    out[1] = kind;
+    out[2] = seq;
+    out[3] = len as u8;
+    out[4..4 + len].copy_from_slice(&body[..len]);
+    let crc = crc16(&out[1..4 + len]);
+    out[4 + len..6 + len].copy_from_slice(&crc.to_le_bytes());
+    6 + len
+}
+
+/// Byte-at-a-time frame parser. Bytes before a SOF are skipped, so it
+/// resynchronises on the next frame after noise or a dropped frame.
+pub struct FrameDecoder {
+    buf: [u8; MAX_FRAME],
+    len: usize,
+}
+
+impl Default for FrameDecoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FrameDecoder {
+    pub const fn new() -> Self {
+        Self { buf: [0; MAX_FRAME], len: 0 }
+    }
+
+    /// Feeds one byte; returns a result once a whole frame has arrived.
+    pub fn push(&mut self, b: u8) -> Option<Result<Frame, PayloadError>> {
+        if self.len == 0 && b != SOF {
+            return None;
+        }
+        self.buf[self.len] = b;
+        self.len += 1;
+        if self.len < 4 {
+            return None;
+        }
+        let body_len = self.buf[3] as usize;
+        if body_len > MAX_BODY {
+            self.len = 0;
+            return Some(Err(PayloadError::Length));
+        }
+        if self.len < 6 + body_len {
+            return None;
+        }
+        self.len = 0;
+        let crc = u16::from_le_bytes([self.buf[4 + body_len], self.buf[5 + body_len]]);
+        if crc != crc16(&self.buf[1..4 + body_len]) {
+            return Some(Err(PayloadError::Crc));
+        }
+        let mut body = [0u8; MAX_BODY];
+        body[..body_len].copy_from_slice(&self.buf[4..4 + body_len]);
+        Some(Ok(Frame { kind: self.buf[1], seq: self.buf[2], len: body_len as u8, body }))
+    }
+}
+
+/// CRC-16/CCITT-FALSE (poly 0x1021, init 0xFFFF)
+pub fn crc16(bytes: &[u8]) -> u16 {
+    let mut crc: u16 = 0xFFFF;
+    for &b in bytes {
+        crc ^= (b as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 { (crc << 1) ^ 0x1021 } else { crc << 1 };
+        }
+    }
+    crc
+}
+
+/// Latest `STATUS` from the payload board. Holds its last value while the
+/// link is down; check [`Payload::link_state`] before trusting it.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PayloadStatus {
+    /// Payload board's own state machine (defined by the payload firmware)
+    pub state: u8,
+    pub flags: u8,
+    pub vbat_mv: u16,
+    /// Last command id the payload board acted on (0 = none)
+    pub last_cmd: u8,
+}
+
+/// Link health, as reported in telemetry `payload_link`
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(u8)]
+pub enum LinkState {
+    /// Nothing valid received since boot
+    NeverHeard = 0,
+    Up = 1,
+    /// Was up, nothing valid for `PAYLOAD_LINK_TIMEOUT_MS`
+    Lost = 2,
+}
+
+#[derive(Clone, Copy)]
+struct Pending {
+    cmd: PayloadCommand,
+    seq: u8,
+}
+
+// The ASCII link uses only `link` and `last_heartbeat`
+#[cfg_attr(not(feature = "payload_frames"), allow(dead_code))]
+pub struct Payload<L: PayloadLink> {
+    link: L,
+    decoder: FrameDecoder,
+    queue: heapless::Deque<Pending, { constants::PAYLOAD_QUEUE_LEN }>,
+    next_seq: u8,
+    /// `HELLO` acked; commands go out only after it
+    synced: bool,
+    last_hello: Option<Instant>,
+    /// Sends of the queue head so far
+    tries: u8,
+    last_send: Option<Instant>,
+    last_heartbeat: Option<Instant>,
+    heartbeat_seq: u8,
+    last_heard: Option<Instant>,
+    link_state: LinkState,
+    pub status: PayloadStatus,
+    last_acked: Option<PayloadCommand>,
+    failed: u8,
+    bad_frames: u32,
+}
+
+impl<L: PayloadLink> Payload<L> {
+    pub fn new(link: L) -> Self {
+        Self {
+            link,
+            decoder: FrameDecoder::new(),
+            queue: heapless::Deque::new(),
+            next_seq: 0,
+            synced: false,
+            last_hello: None,
+            tries: 0,
+            last_send: None,
+            last_heartbeat: None,
+            heartbeat_seq: 0,
+            last_heard: None,
+            link_state: LinkState::NeverHeard,
+            status: PayloadStatus::default(),
+            last_acked: None,
+            failed: 0,
+            bad_frames: 0,
+        }
+    }
+
+    pub fn link_state(&self) -> LinkState {
+        self.link_state
+    }
+
+    /// Most recent command the payload board acknowledged
+    pub fn last_acked(&self) -> Option<PayloadCommand> {
+        self.last_acked
+    }
+
+    /// Commands dropped after `PAYLOAD_MAX_TRIES` sends without an ACK
+    pub fn failed(&self) -> u8 {
+        self.failed
+    }
+
+    /// Frames dropped for a bad CRC or length
+    pub fn bad_frames(&self) -> u32 {
+        self.bad_frames
+    }
+
+    /// Commands queued or awaiting an ACK
+    pub fn pending(&self) -> usize {
+        self.queue.len()
+    }
+}
+
+/// The ASCII protocol: each command is written once, nothing comes back.
+#[cfg(not(feature = "payload_frames"))]
+impl<L: PayloadLink> Payload<L> {
+    pub async fn send(&mut self, cmd: PayloadCommand) -> Result<(), PayloadError> {
+        let mut line = heapless::String::<32>::new();
+        let _ = match cmd {
+            PayloadCommand::A2 { alt_m, vel_ms } => {
+                core::fmt::write(&mut line, format_args!("A2,{:.1},{:.1}\n", alt_m, vel_ms))
+            }
+            _ => core::fmt::write(&mut line, format_args!("{}\n", cmd.name())),
+        };
+        self.link.write(line.as_bytes()).await
+    }
+
+    /// Call once per flight-loop cycle; sends the heartbeat when due.
+    pub async fn update(&mut self, _flight_mode: u8) {
+        let now = Instant::now();
+        let heartbeat_due = self
+            .last_heartbeat
+            .is_none_or(|at| now - at >= Duration::from_millis(constants::PAYLOAD_HEARTBEAT_MS));
+        if heartbeat_due {
+            if let Err(e) = self.link.write(b"A\n").await {
+                log::error!("PAYLOAD: heartbeat write failed: {:?}", e);
+            }
+            self.last_heartbeat = Some(now);
+        }
+    }
+}
+
+#[cfg(feature = "payload_frames")]
+impl<L: PayloadLink> Payload<L> {
+
+    /// Queues `cmd` and sends it straight away if nothing is awaiting an
+    /// ACK; otherwise it goes out once the commands ahead of it are done
+    /// (and `HELLO` has been acked).
+    pub async fn send(&mut self, cmd: PayloadCommand) -> Result<(), PayloadError> {
+        let seq = self.next_seq;
+        self.queue.push_back(Pending { cmd, seq }).map_err(|_| {
+            log::error!("PAYLOAD: queue full, {} not sent", cmd.name());
+            PayloadError::QueueFull
+        })?;
+        self.next_seq = self.next_seq.wrapping_add(1);
+        if self.queue.len() == 1 && self.synced {
+            self.transmit_head().await;
+        }
+        Ok(())
+    }
+
+    /// Call once per flight-loop cycle. Reads what the payload board sent,
+    /// re-sends an unacknowledged command when due, sends the heartbeat and
+    /// updates link health.
+    pub async fn update(&mut self, flight_mode: u8) {
+        self.receive();
+        let now = Instant::now();
+
+        if !self.synced {
+            let hello_due = self
+                .last_hello
+                .is_none_or(|at| now - at >= Duration::from_millis(constants::PAYLOAD_ACK_TIMEOUT_MS));
+            if hello_due {
+                let mut frame = [0u8; MAX_FRAME];
+                let n = encode(frame_type::HELLO, 0, &[], &mut frame);
+                if let Err(e) = self.link.write(&frame[..n]).await {
+                    log::error!("PAYLOAD: hello write failed: {:?}", e);
+                }
+                self.last_hello = Some(now);
+            }
+        } else if !self.queue.is_empty() {
+            let due = match self.last_send {
+                Some(at) => now - at >= Duration::from_millis(constants::PAYLOAD_ACK_TIMEOUT_MS),
+                None => true, // head just moved up after an ACK
+            };
+            if due && self.last_send.is_some() && self.tries >= constants::PAYLOAD_MAX_TRIES {
+                if let Some(p) = self.queue.pop_front() {
+                    log::error!("PAYLOAD: {} not acknowledged after {} tries", p.cmd.name(), self.tries);
+                }
+                self.failed = self.failed.saturating_add(1);
+                self.tries = 0;
+                self.last_send = None;
+                if !self.queue.is_empty() {
+                    self.transmit_head().await;
+                }
+            } else if due {
+                if self.last_send.is_some()
+                    && let Some(p) = self.queue.front()
+                {
+                    log::warn!("PAYLOAD: no ACK for {}, re-sending (try {})", p.cmd.name(), self.tries + 1);
+                }
+                self.transmit_head().await;
+            }
+        }
+
+        let heartbeat_due = self
+            .last_heartbeat
+            .is_none_or(|at| now - at >= Duration::from_millis(constants::PAYLOAD_HEARTBEAT_MS));
+        if heartbeat_due {
+            let mut frame = [0u8; MAX_FRAME];
+            let n = encode(frame_type::HEARTBEAT, self.heartbeat_seq, &[flight_mode], &mut frame);
+            self.heartbeat_seq = self.heartbeat_seq.wrapping_add(1);
+            if let Err(e) = self.link.write(&frame[..n]).await {
+                log::error!("PAYLOAD: heartbeat write failed: {:?}", e);
+            }
+            self.last_heartbeat = Some(now);
+        }
+
+        let heard = self
+            .last_heard
+            .is_some_and(|at| now - at < Duration::from_millis(constants::PAYLOAD_LINK_TIMEOUT_MS));
+        match (self.link_state, heard) {
+            (LinkState::Up, false) => {
+                self.link_state = LinkState::Lost;
+                log::warn!(
+                    "PAYLOAD: link lost (nothing heard for {} ms)",
+                    constants::PAYLOAD_LINK_TIMEOUT_MS
+                );
+            }
+            (LinkState::NeverHeard | LinkState::Lost, true) => {
+                self.link_state = LinkState::Up;
+                log::info!("PAYLOAD: link up");
+            }
+            _ => {}
+        }
+    }
+
+    /// Sends (or re-sends) the command at the head of the queue.
+    async fn transmit_head(&mut self) {
+        let Some(p) = self.queue.front().copied() else {
+            return;
+        };
+        let mut body = [0u8; MAX_BODY];
+        let len = p.cmd.body(&mut body);
+        let mut frame = [0u8; MAX_FRAME];
+        let n = encode(frame_type::CMD, p.seq, &body[..len], &mut frame);
+        self.tries += 1;
+        self.last_send = Some(Instant::now());
+        if let Err(e) = self.link.write(&frame[..n]).await {
+            log::error!("PAYLOAD: {} write failed: {:?}", p.cmd.name(), e);
+        }
+    }
+
+    /// Handles every byte already received, without waiting for more.
+    fn receive(&mut self) {
+        let mut buf = [0u8; MAX_FRAME];
+        for _ in 0..8 {
+            let n = self.link.try_read(&mut buf);
+            if n == 0 {
+                break;
+            }
+            for &b in &buf[..n] {
+                match self.decoder.push(b) {
+                    Some(Ok(frame)) => self.handle(frame),
+                    Some(Err(_)) => self.bad_frames += 1,
+                    None => {}
+                }
+            }
+        }
+    }
+
+    fn handle(&mut self, frame: Frame) {
+        self.last_heard = Some(Instant::now());
+        match frame.kind {
+            frame_type::ACK if !self.synced => {
+                // Only the HELLO's ACK; nothing else has been sent this boot
+                self.synced = frame.body().first() == Some(&0);
+                if self.synced {
+                    log::info!("PAYLOAD: hello acknowledged");
+                }
+            }
+            frame_type::ACK => {
+                // A late ACK for a command already acked or given up on is ignored
+                let Some(p) = self.queue.front().copied() else {
+                    return;
+                };
+                if p.seq != frame.seq {
+                    return;
+                }
+                self.queue.pop_front();
+                log::info!("PAYLOAD: {} acknowledged (try {})", p.cmd.name(), self.tries);
+                self.last_acked = Some(p.cmd);
+                self.tries = 0;
+                self.last_send = None;
+            }
+            frame_type::STATUS => {
+                let body = frame.body();
+                if body.len() < 5 {
+                    self.bad_frames += 1;
+                    return;
+                }
+                let status = PayloadStatus {
+                    state: body[0],
+                    flags: body[1],
+                    vbat_mv: u16::from_le_bytes([body[2], body[3]]),
+                    last_cmd: body[4],
+                };
+                if status.state != self.status.state || status.flags != self.status.flags {
+                    log::info!(
+                        "PAYLOAD: status state={} flags={:#04x} vbat={} mV last_cmd={}",
+                        status.state,
+                        status.flags,
+                        status.vbat_mv,
+                        PayloadCommand::name_of(status.last_cmd)
+                    );
+                }
+                self.status = status;
+            }
+            _ => {}
+        }
+    }
+}
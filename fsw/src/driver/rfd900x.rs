@@ -16,7 +16,11 @@ pub struct Rfd900x<'a> {
 }
 
 impl<'a> Rfd900x<'a> {
-    const SYNC_WORD: [u8; 4] = [0x67, 0x59, 0x5D, 0x3E]; // "CRT!"
+    pub const SYNC_WORD: [u8; 4] = [0x67, 0x59, 0x5D, 0x3E]; // "CRT!"
+    /// Leads a `BeaconPacket` instead of a full `Packet`
+    pub const BEACON_SYNC_WORD: [u8; 4] = [0x42, 0x43, 0x4E, 0x21]; // "BCN!"
+    /// Leads a `ConfigPacket`
+    pub const CONFIG_SYNC_WORD: [u8; 4] = [0x43, 0x46, 0x47, 0x21]; // "CFG!"
 
     /// Create a new RFD900x driver instance
     ///
@@ -24,19 +28,32 @@ impl<'a> Rfd900x<'a> {
     /// * `uart` - Configured UART1 peripheral (9600 baud)
     pub fn new(uart: Uart<'a, Async>) -> Self {
         Self {
-            uart: uart,
+            uart,
             sync_word: 0x3E5D5967, // CRT!
         }
     }
+    /// The sync word is written as the first bytes.
+    ///
     /// # Returns
     /// * `Ok(())` on success
     /// * `Err(())` on transmission error
-    /// sync word is written as the first byte
     pub async fn send(&mut self, data: &[u8]) -> Result<(), Error> {
         self.uart.write(&Self::SYNC_WORD).await?;
         self.uart.write(data).await?;
 
-        return Ok(());
+        Ok(())
+    }
+
+    /// Send a recovery beacon (`BeaconPacket` bytes) behind `BEACON_SYNC_WORD`
+    pub async fn send_beacon(&mut self, data: &[u8]) -> Result<(), Error> {
+        self.uart.write(&Self::BEACON_SYNC_WORD).await?;
+        self.uart.write(data).await
+    }
+
+    /// Send the flight configuration (`ConfigPacket` bytes) behind `CONFIG_SYNC_WORD`
+    pub async fn send_config(&mut self, data: &[u8]) -> Result<(), Error> {
+        self.uart.write(&Self::CONFIG_SYNC_WORD).await?;
+        self.uart.write(data).await
     }
 
     /// Read data from the radio into the provided buffer
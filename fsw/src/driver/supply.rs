@@ -0,0 +1,46 @@
+//! Supply rail monitor on the RP2350's own ADC
+//!
+//! The flight battery and the pyro rail each come in through a resistor
+//! divider (`SUPPLY_*_DIVIDER`) on a spare ADC pin: battery on GPIO 42
+//! (ADC2), pyro rail on GPIO 43 (ADC3). Unlike the ADS1015 this sits on no
+//! shared bus, so it keeps reading in beacon mode.
+
+use embassy_rp::adc::{Adc, Async, Channel, Error};
+
+use crate::constants;
+
+/// Full scale of the 12-bit conversion
+const ADC_COUNTS: f32 = 4096.0;
+
+pub struct SupplyMonitor {
+    adc: Adc<'static, Async>,
+    battery: Channel<'static>,
+    pyro: Channel<'static>,
+}
+
+impl SupplyMonitor {
+    pub fn new(adc: Adc<'static, Async>, battery: Channel<'static>, pyro: Channel<'static>) -> Self {
+        Self { adc, battery, pyro }
+    }
+
+    /// Battery and pyro rail voltages (V), each the average of
+    /// `SUPPLY_SAMPLES` conversions.
+    pub async fn read(&mut self) -> Result<(f32, f32), Error> {
+        let mut battery = 0u32;
+        let mut pyro = 0u32;
+        for _ in 0..constants::SUPPLY_SAMPLES {
+            battery += self.adc.read(&mut self.battery).await? as u32;
+            pyro += self.adc.read(&mut self.pyro).await? as u32;
+        }
+        Ok((
+            to_volts(battery, constants::SUPPLY_BATTERY_DIVIDER),
+            to_volts(pyro, constants::SUPPLY_PYRO_DIVIDER),
+        ))
+    }
+}
+
+/// Summed raw counts to rail voltage ahead of a `divider`:1 divider
+fn to_volts(sum: u32, divider: f32) -> f32 {
+    let raw = sum as f32 / constants::SUPPLY_SAMPLES as f32;
+    raw / ADC_COUNTS * constants::SUPPLY_ADC_VREF * divider
+}
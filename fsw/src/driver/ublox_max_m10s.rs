@@ -3,6 +3,8 @@ use embassy_embedded_hal::shared_bus::asynch::i2c::I2cDevice as SharedI2cDevice;
 use embedded_hal_async::i2c::I2c as I2cTrait;
 use ublox::{FixedLinearBuffer, PacketRef, Parser};
 
+use crate::time_sync::{self, GpsUtc};
+
 /// I2C address for MAX-M10S GPS module
 const GPS_I2C_ADDR: u8 = 0x42;
 
@@ -12,6 +14,10 @@ const GPS_DATA_STREAM_REG: u8 = 0xFF;
 /// Maximum bytes to read per I2C transaction
 const MAX_READ_BYTES: usize = 255;
 
+/// NAV-PVT `valid` bits that must all be set before the UTC fields are trusted:
+/// validDate (0x01), validTime (0x02), fullyResolved (0x04)
+const PVT_TIME_VALID_MASK: u8 = 0x07;
+
 /// Error types for GPS operations
 #[derive(Debug)]
 pub enum GpsError {
@@ -25,6 +31,13 @@ pub enum GpsError {
 pub struct UbloxMaxM10s<'a, I2C> {
     i2c: I2C,
     parser: Parser<FixedLinearBuffer<'a>>,
+    /// UTC of the most recent NAV-PVT with valid, fully resolved time.
+    /// Taken (and cleared) by `take_utc()`.
+    last_utc: Option<GpsUtc>,
+    /// Latest NAV-PVT position, degrees × 1e7 as received. The packet's f32
+    /// degrees only resolve ~1 m; guidance uses this.
+    position_e7: (i32, i32),
+    itow: u32,
 }
 
 // UBX protocol constants
@@ -60,6 +73,9 @@ impl UbloxMaxM10s<'static, I2cDevice<'static>> {
         Self {
             i2c: i2c_device,
             parser: Parser::new(buf),
+            last_utc: None,
+            position_e7: (0, 0),
+            itow: 0,
         }
     }
 }
@@ -201,6 +217,22 @@ where
         Ok(bytes_to_read)
     }
 
+    /// Returns the UTC of the last NAV-PVT with valid time, if one arrived
+    /// since the previous call.
+    pub fn take_utc(&mut self) -> Option<GpsUtc> {
+        self.last_utc.take()
+    }
+
+    /// Latest position at full receiver resolution, (lat, lon) degrees × 1e7.
+    pub fn position_e7(&self) -> (i32, i32) {
+        self.position_e7
+    }
+
+    /// GPS time of week of the latest solution (ms); identifies it.
+    pub fn itow(&self) -> u32 {
+        self.itow
+    }
+
     /// Read GPS data and update the packet
     ///
     /// This function reads data from the GPS module, parses NAV-PVT messages,
@@ -231,6 +263,11 @@ where
                             // Update packet directly
                             packet.latitude = pvt.lat_degrees() as f32;
                             packet.longitude = pvt.lon_degrees() as f32;
+                            self.position_e7 = (
+                                libm::round(pvt.lat_degrees() * 1e7) as i32,
+                                libm::round(pvt.lon_degrees() * 1e7) as i32,
+                            );
+                            self.itow = pvt.itow();
                             packet.num_satellites = pvt.num_satellites() as u32;
 
                             // Calculate timestamp from GPS time
@@ -251,6 +288,23 @@ where
                             packet.fix_type  = pvt.fix_type() as u8;
                             // heading of motion: ublox gives degrees, BlimsDataIn wants deg*1e5
                             packet.head_mot  = (pvt.heading_degrees() * 1e5) as i32;
+
+                            // Full UTC only once the receiver has resolved date and time
+                            if pvt.valid() & PVT_TIME_VALID_MASK == PVT_TIME_VALID_MASK {
+                                self.last_utc = time_sync::epoch_ms_from_utc(
+                                    pvt.year(),
+                                    pvt.month(),
+                                    pvt.day(),
+                                    pvt.hour(),
+                                    pvt.min(),
+                                    pvt.sec(),
+                                    pvt.nanosecond(),
+                                )
+                                .map(|epoch_ms| GpsUtc {
+                                    epoch_ms,
+                                    t_acc_ns: pvt.time_accuracy(),
+                                });
+                            }
                         }
                         _ => {
                             // Ignore other packet types
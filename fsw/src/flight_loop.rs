@@ -1,10 +1,17 @@
 use core::f32;
 use embassy_time::{Duration, Instant, Timer};
 
+use crate::airbrake_task::Core1Monitor;
+use crate::actuator::BuzzPattern;
+use crate::airbrake_test::{AirbrakeTest, TestProfile};
 use crate::constants;
-use crate::state::SensorState;
+use crate::driver::payload::PayloadCommand;
+use crate::state::{health, SensorState};
 use crate::state::{FlightMode, FlightState};
 use crate::umbilical::{self, UmbilicalCommand};
+use controller_in_rust_v3::Config as AirbrakeConfig;
+use blims::blims_constants::{MAX_FENCE_VERTICES, MAX_FENCE_ZONES, MAX_WIND_LAYERS};
+use blims::{BlimsConfig, Geofence, LatLon, WindLayer, WindProfile, Zone, ZoneKind};
 
 // TODO: Add //CHALLENGE_# to each fault with its solution
 // TODO: Remove some bools and edit FlightLoop to be able to trigger events with methods
@@ -55,8 +62,14 @@ pub struct FlightLoop {
     // Flash logging timing
     last_flash_log: Option<Instant>,
     last_full_log: Option<Instant>,
-    last_heartbeat: Option<Instant>,
     last_startup_buzz: Option<Instant>,
+    last_beacon: Option<Instant>,
+
+    // Buzzer readouts: apogee after landing, fault code in Fault
+    apogee_m: f32,
+    landed_check: Option<(f32, Instant)>,
+    landed: bool,
+    booted_in_fault: bool,
 
     // Launch sequence
     pub launch_sequence_stage: LaunchStage,
@@ -73,6 +86,9 @@ pub struct FlightLoop {
     // Fault signaling
     fault_signal_sent: bool,
     last_alt: f32,
+    /// Vertical velocity from this cycle's and last cycle's altitude, set
+    /// after the sensor read so the command handlers see a real value
+    alt_rate_ms: f32,
 
     // Overpressure latch — once PT3 has been above the threshold for 3
     // consecutive cycles we open SV and fault. Single-sample noise spikes
@@ -80,6 +96,20 @@ pub struct FlightLoop {
     overpressure_triggered: bool,
     overpressure_count: u8,
 
+    /// Airbrake ground test: `<AT1>` sets the flag (and enables the ODrive),
+    /// `<AT0>` or leaving Startup clears it and aborts any running profile.
+    airbrake_test_enabled: bool,
+    airbrake_test: Option<AirbrakeTest>,
+
+    /// BLiMS wind profile being uploaded with `<WP,...>`: staged layers
+    /// from `<WP,B,n>` until `<WP,C>` validates and applies them.
+    wind_upload: Option<WindUpload>,
+    /// BLiMS geofence being uploaded with `<GF,...>`, likewise until `<GF,C>`.
+    fence_upload: Option<FenceUpload>,
+
+    /// Core 1 heartbeat/output staleness check; airbrakes retract while stale
+    core1_monitor: Core1Monitor,
+
     /// Sim only: if Some, overrides altitude + forces altimeter VALID after read_sensors().
     /// Set to None in normal flight — zero cost.
     pub sim_altitude_override: Option<f32>,
@@ -167,6 +197,11 @@ impl FlightLoop {
                 (LaunchStage::None, None)
             };
 
+        let booted_in_fault = recovered == FlightMode::Fault;
+        if booted_in_fault {
+            log::error!("Booted in Fault; sounding the fault code");
+        }
+
         Self {
             flight_state,
             key_armed,
@@ -187,7 +222,8 @@ impl FlightLoop {
             alt_index: 0,
             filtered_alt: [-1.0; 3],
             drogue_entry_time: None,
-            main_entry_time: None,
+            // A reboot under the main restarts the beacon timeout from boot
+            main_entry_time: main_chutes_deployed.then(Instant::now),
             airbrakes_logged: false,
             umbilical_disconnect_time: None,
             vent_signal_sent: false,
@@ -197,8 +233,12 @@ impl FlightLoop {
             key_prev: false,
             last_flash_log: None,
             last_full_log: None,
-            last_heartbeat: None,
             last_startup_buzz: None,
+            last_beacon: None,
+            apogee_m: 0.0,
+            landed_check: None,
+            landed: false,
+            booted_in_fault,
             launch_sequence_stage,
             launch_stage_start_time,
             recovery_vent_sent: false,
@@ -209,8 +249,14 @@ impl FlightLoop {
             n4_sent: false,
             fault_signal_sent: false,
             last_alt: 0.0,
+            alt_rate_ms: 0.0,
             overpressure_triggered: false,
             overpressure_count: 0,
+            airbrake_test_enabled: false,
+            wind_upload: None,
+            fence_upload: None,
+            airbrake_test: None,
+            core1_monitor: Core1Monitor::new(),
             sim_altitude_override: None,
             sim_vel_d_override: None,
             sim_key_armed_override: None,
@@ -232,6 +278,101 @@ impl FlightLoop {
 
     pub fn set_flight_mode(&mut self, mode: FlightMode) {
         self.flight_state.flight_mode = mode;
+        // Forced modes can be undone within a cycle; drop test mode now
+        if mode != FlightMode::Startup {
+            self.run_airbrake_test();
+        }
+    }
+
+    /// Startup status: the fault code if any `health` bit is set, otherwise
+    /// the umbilical state.
+    fn buzz_status(&mut self) {
+        let bits = self.fault_bits();
+        if bits != 0 {
+            log::warn!("Health {:#06x}; sounding the fault code", bits);
+            self.flight_state
+                .buzz_pattern("fault code", &BuzzPattern::fault_code(bits, &health::CODES));
+        } else {
+            self.buzz_umbilical();
+        }
+    }
+
+    fn buzz_umbilical(&mut self) {
+        if self.flight_state.umbilical_connected {
+            self.flight_state
+                .buzz_pattern("umbilical connected", &BuzzPattern::morse(constants::BUZZ_UMBILICAL_CONNECTED));
+        } else {
+            self.flight_state
+                .buzz_pattern("umbilical disconnected", &BuzzPattern::morse(constants::BUZZ_UMBILICAL_DISCONNECTED));
+        }
+    }
+
+    /// `health` bits for the fault code, with `RECOVERED_FAULT` if the board
+    /// came up in Fault.
+    pub fn fault_bits(&self) -> u16 {
+        let recovered = if self.booted_in_fault { health::RECOVERED_FAULT } else { 0 };
+        self.flight_state.health() | recovered
+    }
+
+    pub fn booted_in_fault(&self) -> bool {
+        self.booted_in_fault
+    }
+
+    /// Highest altitude since arming, in whole feet. A reboot mid-flight
+    /// starts it again from the recovered altitude.
+    pub fn apogee_ft(&self) -> u32 {
+        (self.apogee_m.max(0.0) * 3.28084).round() as u32
+    }
+
+    pub fn landed(&self) -> bool {
+        self.landed
+    }
+
+    /// Sleep before the next cycle: `BEACON_LOOP_DELAY_MS` in beacon mode,
+    /// except while a buzzer pattern plays (its tones are stepped once per
+    /// cycle), otherwise `MAIN_LOOP_DELAY_MS`.
+    pub fn loop_delay_ms(&self) -> u64 {
+        if self.flight_state.beacon_mode() && !self.flight_state.buzzer.is_busy() {
+            constants::BEACON_LOOP_DELAY_MS
+        } else {
+            constants::MAIN_LOOP_DELAY_MS
+        }
+    }
+
+    /// Latch `landed` once the altitude has held within `LANDED_ALT_BAND_M`
+    /// for `LANDED_STILL_MS`. Call each MainDeployed cycle.
+    fn update_landed(&mut self) {
+        if self.landed || !self.flight_state.altimeter_read_ok() {
+            return;
+        }
+        let alt = self.flight_state.packet.altitude;
+        match self.landed_check {
+            Some((start_alt, since)) if (alt - start_alt).abs() <= constants::LANDED_ALT_BAND_M => {
+                if since.elapsed().as_millis() >= constants::LANDED_STILL_MS {
+                    self.landed = true;
+                    log::info!("Landed at {:.1} m; apogee {} ft", alt, self.apogee_ft());
+                }
+            }
+            _ => self.landed_check = Some((alt, Instant::now())),
+        }
+    }
+
+    /// One `BeaconPacket` every `BEACON_PERIOD_MS`.
+    async fn transmit_beacon(&mut self) {
+        if self
+            .last_beacon
+            .is_some_and(|t| t.elapsed().as_millis() < constants::BEACON_PERIOD_MS)
+        {
+            return;
+        }
+        self.last_beacon = Some(Instant::now());
+        let (apogee_ft, bits) = (self.apogee_ft(), self.fault_bits());
+        self.flight_state.transmit_beacon(apogee_ft, bits).await;
+    }
+
+    /// A2 carries altitude and a one-cycle vertical velocity estimate
+    fn a2_command(&self) -> PayloadCommand {
+        PayloadCommand::A2 { alt_m: self.flight_state.packet.altitude, vel_ms: self.alt_rate_ms }
     }
 
     pub async fn execute(&mut self) {
@@ -275,7 +416,9 @@ impl FlightLoop {
             self.flight_state.cfc_arm_active = cfc_arm;
         }
 
-        if self.sim_altitude_override.is_none() {
+        // A failed read leaves last cycle's AGL value in the packet; converting
+        // it again would drop the altitude by arming_altitude for that cycle.
+        if self.sim_altitude_override.is_none() && self.flight_state.altimeter_read_ok() {
             // If arming_altitude was lost (power cycle into Standby or later mode),
             // latch it now from the raw MSL reading before the AGL conversion runs,
             // so this very cycle also shows correct AGL altitude.
@@ -300,6 +443,7 @@ impl FlightLoop {
             // Convert raw altitude to AGL (above ground level)
             self.flight_state.packet.altitude -= self.flight_state.arming_altitude;
         }
+        self.alt_rate_ms = (self.flight_state.packet.altitude - self.last_alt) * 20.0;
 
         // 2b. Forward latest sensor data to the airbrake controller on Core 1.
         // Signal::signal() is non-blocking and always delivers the most recent
@@ -312,7 +456,8 @@ impl FlightLoop {
         };
         if let Some(phase) = airbrake_phase {
             crate::airbrake_task::AIRBRAKE_INPUT.signal(crate::airbrake_task::AirbrakeInput {
-                time:     self.flight_state.packet.timestamp,
+                // Monotonic; packet.timestamp is GPS time of day and only ticks at 1 Hz
+                time:     self.flight_state.packet.ms_since_boot_cfc as f32 / 1000.0,
                 altitude: self.flight_state.packet.altitude,
                 vel_d:    self.flight_state.packet.vel_d as f32,
                 reference_pressure: self.flight_state.reference_pressure,
@@ -324,6 +469,9 @@ impl FlightLoop {
                 accel_z:  self.flight_state.packet.accel_z,
                 phase,
             });
+            self.core1_monitor.check();
+        } else {
+            self.core1_monitor.idle();
         }
 
         // 2c. Overpressure latch: if PT3 exceeds the threshold, open SV and
@@ -355,31 +503,58 @@ impl FlightLoop {
             self.flight_state.flight_mode,
             FlightMode::Ascent | FlightMode::Coast
         ) {
-            let deployment = crate::airbrake_task::get_deployment();
-            self.flight_state.airbrake_system.set_deployment(deployment);
+            if self.core1_monitor.healthy() {
+                let deployment = crate::airbrake_task::get_deployment();
+                self.flight_state.airbrake_system.set_deployment(deployment);
+            } else {
+                self.flight_state.airbrake_system.retract();
+            }
+        }
+        self.run_airbrake_test();
+        self.flight_state.packet.airbrake_deployment = self.flight_state.airbrake_system.current_deployment();
+        self.flight_state.packet.predicted_apogee = crate::airbrake_task::get_predicted_apogee();
+        self.flight_state.packet.airbrake_ctrl_ok = self.core1_monitor.healthy() as u8;
+        let cfg = self.flight_state.airbrake_config;
+        self.flight_state.config.airbrake_target_apogee = cfg.target_apogee_m;
+        self.flight_state.config.airbrake_max_rate = cfg.max_deploy_rate_per_s;
+        self.flight_state.config.airbrake_min_vel = cfg.min_activation_vel_mps;
+        self.flight_state.config.airbrake_body_cda = cfg.drag.body_cda_m2;
+        self.flight_state.config.airbrake_brake_cda = cfg.drag.brake_cda_m2;
+        let wind = &self.flight_state.blims_wind_profile;
+        self.flight_state.config.blims_wind_layers = wind.len() as u8;
+        self.flight_state.config.blims_wind_checksum = wind.checksum();
+        let fence = &self.flight_state.blims_geofence;
+        self.flight_state.config.blims_fence_zones = fence.len() as u8;
+        self.flight_state.config.blims_fence_checksum = fence.checksum();
+        let estimate = crate::airbrake_task::get_drag_estimate();
+        self.flight_state.packet.airbrake_est_body_cda = estimate.body_cda_m2;
+        self.flight_state.packet.airbrake_est_brake_cda = estimate.brake_cda_m2;
+
+        // ODrive setpoints out, readback in; RC PWM takes over if a link is down
+        self.flight_state.update_odrives();
+
+        // Payload link: ACKs, retries, 1 Hz heartbeat, link health into telemetry
+        let mode = self.flight_state.flight_mode as u8;
+        self.flight_state.update_payload(mode).await;
+
+        // 5. Transmit telemetry (radio + USB umbilical binary frames), or
+        // only the recovery beacon once in beacon mode
+        if self.flight_state.beacon_mode() {
+            self.transmit_beacon().await;
+        } else {
+            self.flight_state.transmit().await;
         }
 
-        // 5. Transmit telemetry (radio + USB umbilical binary frames)
-        self.flight_state.transmit().await;
-
-        // Save packet to QSPI Flash
+        // Save packet to QSPI Flash (data logging ends with beacon mode)
         let now = Instant::now();
 
-        // 6. Payload Heartbeat (1 Hz)
-        let should_heartbeat = match self.last_heartbeat {
-            None => true,
-            Some(last) => now.duration_since(last).as_millis() >= 1000,
-        };
-        if should_heartbeat {
-            let _ = self.flight_state.payload_uart.write(b"A\n").await;
-            self.last_heartbeat = Some(now);
-        }
-        let should_log = match self.last_flash_log {
-            None => true,
-            Some(last) => {
-                now.duration_since(last).as_millis() >= constants::FLASH_LOGGING_PERIOD_MS
-            }
-        };
+        let should_log = !self.flight_state.beacon_mode()
+            && match self.last_flash_log {
+                None => true,
+                Some(last) => {
+                    now.duration_since(last).as_millis() >= constants::FLASH_LOGGING_PERIOD_MS
+                }
+            };
 
         if should_log {
             let write_full = match self.last_full_log {
@@ -418,41 +593,41 @@ impl FlightLoop {
                         self.flight_state.flight_mode,
                         FlightMode::Startup | FlightMode::Standby
                     ) {
-                        let _ = self.flight_state.payload_uart.write(b"N1\n").await;
+                        let _ = self.flight_state.payload.send(PayloadCommand::N1).await;
                         log::info!("PAYLOAD: Sent N1 (Camera Deploy)");
                         self.flight_state.packet.cmd_n1 = 1;
                     }
                 }
                 Command::N2 => {
-                    let _ = self.flight_state.payload_uart.write(b"N2\n").await;
+                    let _ = self.flight_state.payload.send(PayloadCommand::N2).await;
                     log::info!("PAYLOAD: Sent N2");
                     self.flight_state.packet.cmd_n2 = 1;
                 }
                 Command::N3 => {
-                    let _ = self.flight_state.payload_uart.write(b"N3\n").await;
+                    let _ = self.flight_state.payload.send(PayloadCommand::N3).await;
                     log::info!("PAYLOAD: Sent N3");
                     self.n3_sent = true;
                     self.flight_state.packet.cmd_n3 = 1;
                 }
                 Command::N4 => {
-                    let _ = self.flight_state.payload_uart.write(b"N4\n").await;
+                    let _ = self.flight_state.payload.send(PayloadCommand::N4).await;
                     log::info!("PAYLOAD: Sent N4");
                     self.n4_sent = true;
                     self.flight_state.packet.cmd_n4 = 1;
                 }
                 Command::A1 => {
                     log::warn!("A1");
-                    let _ = self.flight_state.payload_uart.write(b"A1\n").await;
+                    let _ = self.flight_state.payload.send(PayloadCommand::A1).await;
                     self.flight_state.packet.cmd_a1 = 1;
                 }
                 Command::A2 => {
                     log::warn!("A2");
-                    let _ = self.flight_state.payload_uart.write(b"A2\n").await;
+                    let _ = self.flight_state.payload.send(self.a2_command()).await;
                     self.flight_state.packet.cmd_a2 = 1;
                 }
                 Command::A3 => {
                     log::warn!("A3");
-                    let _ = self.flight_state.payload_uart.write(b"A3\n").await;
+                    let _ = self.flight_state.payload.send(PayloadCommand::A3).await;
                     self.flight_state.packet.cmd_a3 = 1;
                 }
                 Command::ForceMode(mode_val) => {
@@ -532,41 +707,41 @@ impl FlightLoop {
                         self.flight_state.flight_mode,
                         FlightMode::Startup | FlightMode::Standby
                     ) {
-                        let _ = self.flight_state.payload_uart.write(b"N1\n").await;
+                        let _ = self.flight_state.payload.send(PayloadCommand::N1).await;
                         log::info!("UMBILICAL: Sent N1 (Camera Deploy)");
                         self.flight_state.packet.cmd_n1 = 1;
                     }
                 }
                 UmbilicalCommand::PayloadN2 => {
-                    let _ = self.flight_state.payload_uart.write(b"N2\n").await;
+                    let _ = self.flight_state.payload.send(PayloadCommand::N2).await;
                     log::info!("UMBILICAL: Sent N2");
                     self.flight_state.packet.cmd_n2 = 1;
                 }
                 UmbilicalCommand::PayloadN3 => {
-                    let _ = self.flight_state.payload_uart.write(b"N3\n").await;
+                    let _ = self.flight_state.payload.send(PayloadCommand::N3).await;
                     log::info!("UMBILICAL: Sent N3");
                     self.n3_sent = true;
                     self.flight_state.packet.cmd_n3 = 1;
                 }
                 UmbilicalCommand::PayloadN4 => {
-                    let _ = self.flight_state.payload_uart.write(b"N4\n").await;
+                    let _ = self.flight_state.payload.send(PayloadCommand::N4).await;
                     log::info!("UMBILICAL: Sent N4");
                     self.n4_sent = true;
                     self.flight_state.packet.cmd_n4 = 1;
                 }
                 UmbilicalCommand::PayloadA1 => {
                     log::warn!("UMBILICAL: Sent A1");
-                    let _ = self.flight_state.payload_uart.write(b"A1\n").await;
+                    let _ = self.flight_state.payload.send(PayloadCommand::A1).await;
                     self.flight_state.packet.cmd_a1 = 1;
                 }
                 UmbilicalCommand::PayloadA2 => {
                     log::warn!("UMBILICAL: Sent A2");
-                    let _ = self.flight_state.payload_uart.write(b"A2\n").await;
+                    let _ = self.flight_state.payload.send(self.a2_command()).await;
                     self.flight_state.packet.cmd_a2 = 1;
                 }
                 UmbilicalCommand::PayloadA3 => {
                     log::warn!("UMBILICAL: Sent A3");
-                    let _ = self.flight_state.payload_uart.write(b"A3\n").await;
+                    let _ = self.flight_state.payload.send(PayloadCommand::A3).await;
                     self.flight_state.packet.cmd_a3 = 1;
                 }
                 UmbilicalCommand::WipeFramReboot => {
@@ -587,7 +762,7 @@ impl FlightLoop {
                     log::warn!("UMBILICAL CMD: Key Disarm");
                     self.key_armed = false;
                     self.flight_state.key_armed = false;
-                    self.flight_state.buzz(1);
+                    self.flight_state.buzz_pattern("key disarm", &BuzzPattern::morse(constants::BUZZ_KEY_DISARM));
                     self.last_startup_buzz = None; // buzz immediately on next Startup cycle
                 }
                 UmbilicalCommand::SetBlimsTarget { upwind_lat, upwind_lon, downwind_lat, downwind_lon } => {
@@ -614,11 +789,51 @@ impl FlightLoop {
                     log::warn!("UMBILICAL CMD: Force Main Mode");
                     self.set_flight_mode(FlightMode::MainDeployed);
                 }
+                UmbilicalCommand::AirbrakeTestEnable => {
+                    if self.flight_state.flight_mode == FlightMode::Startup {
+                        log::warn!("UMBILICAL CMD: Airbrake test mode ENABLED — ODrive enabled");
+                        self.airbrake_test_enabled = true;
+                        self.flight_state.airbrake_system.enable();
+                    } else {
+                        log::warn!("UMBILICAL CMD: Airbrake test mode rejected — only allowed in Startup");
+                    }
+                }
+                UmbilicalCommand::AirbrakeTestDisable => {
+                    log::warn!("UMBILICAL CMD: Airbrake test mode disabled");
+                    self.disable_airbrake_test();
+                }
+                UmbilicalCommand::AirbrakeTest(profile) => {
+                    log::warn!("UMBILICAL CMD: Airbrake test {:?}", profile);
+                    self.start_airbrake_test(profile);
+                }
                 UmbilicalCommand::DeployAirbrakes => {
-                    log::warn!("UMBILICAL CMD: Deploy Airbrakes (disabled — no controller)");
+                    log::warn!("UMBILICAL CMD: Deploy Airbrakes (step test to 100%)");
+                    self.start_airbrake_test(TestProfile::Step { target: 1.0 });
                 }
                 UmbilicalCommand::RetractAirbrakes => {
-                    log::warn!("UMBILICAL CMD: Retract Airbrakes (disabled — no controller)");
+                    log::warn!("UMBILICAL CMD: Retract Airbrakes");
+                    self.stop_airbrake_test();
+                }
+                UmbilicalCommand::SetAirbrakeConfig(cfg) => {
+                    log::warn!("UMBILICAL CMD: Set airbrake config {:?}", cfg);
+                    self.set_airbrake_config(cfg).await;
+                }
+                UmbilicalCommand::SetBlimsConfig(cfg) => {
+                    log::warn!("UMBILICAL CMD: Set BLiMS config {:?}", cfg);
+                    self.set_blims_config(cfg).await;
+                }
+                UmbilicalCommand::WindProfileBegin(_)
+                | UmbilicalCommand::WindProfileLayer(..)
+                | UmbilicalCommand::WindProfileCommit
+                | UmbilicalCommand::WindProfileClear => {
+                    self.handle_wind_profile(cmd).await;
+                }
+                UmbilicalCommand::GeofenceBegin(_)
+                | UmbilicalCommand::GeofenceZone(..)
+                | UmbilicalCommand::GeofenceVertex(..)
+                | UmbilicalCommand::GeofenceCommit
+                | UmbilicalCommand::GeofenceClear => {
+                    self.handle_geofence(cmd).await;
                 }
                 UmbilicalCommand::TriggerBLiMS => {
                     log::warn!("UMBILICAL CMD: Trigger BLiMS nudge");
@@ -632,6 +847,263 @@ impl FlightLoop {
         }
     }
 
+    /// Apply a new airbrake controller config if the vehicle is in Startup.
+    /// Returns true if it was accepted.
+    pub async fn set_airbrake_config(&mut self, cfg: AirbrakeConfig) -> bool {
+        if self.flight_state.flight_mode != FlightMode::Startup {
+            log::warn!("AIRBRAKE CONFIG: rejected — only allowed in Startup");
+            return false;
+        }
+        if !cfg.is_valid() {
+            log::warn!("AIRBRAKE CONFIG: rejected — parameters out of range: {:?}", cfg);
+            return false;
+        }
+        if !self.flight_state.set_airbrake_config(cfg).await {
+            log::warn!("AIRBRAKE CONFIG: applied but not saved; reverts on reboot");
+        }
+        log::info!("AIRBRAKE CONFIG: target apogee {:.1} m", cfg.target_apogee_m);
+        true
+    }
+
+    /// Apply new BLiMS gains and phase altitudes if the vehicle is in
+    /// Startup. Replies `$BK,OK,<values>` or `$BK,ERR,<reason>`; returns
+    /// true if accepted.
+    pub async fn set_blims_config(&mut self, cfg: BlimsConfig) -> bool {
+        if self.flight_state.flight_mode != FlightMode::Startup {
+            log::warn!("BLIMS CONFIG: rejected — only allowed in Startup");
+            blims_config_reply(format_args!("ERR,MODE"));
+            return false;
+        }
+        if !cfg.is_valid() {
+            log::warn!("BLIMS CONFIG: rejected — parameters out of range: {:?}", cfg);
+            blims_config_reply(format_args!("ERR,RANGE"));
+            return false;
+        }
+        if !self.flight_state.set_blims_config(cfg).await {
+            log::warn!("BLIMS CONFIG: applied but not saved; reverts on reboot");
+        }
+        log::info!(
+            "BLIMS CONFIG: kp {} ki {} upwind {:.0} ft neutral {:.0} ft",
+            cfg.kp, cfg.ki, cfg.alt_upwind_ft, cfg.alt_neutral_ft
+        );
+        blims_config_reply(format_args!(
+            "OK,{:.4},{:.4},{:.2},{:.0},{:.0},{:.0},{}",
+            cfg.kp, cfg.ki, cfg.integral_max_in, cfg.alt_upwind_ft, cfg.alt_neutral_ft,
+            cfg.phase_hysteresis_ft, cfg.initial_hold_ms
+        ));
+        true
+    }
+
+    /// One step of the `<WP,...>` wind profile upload, Startup only. Every
+    /// step is echoed as a `$WP,...` line so the ground can check it; the
+    /// commit replies `$WP,OK,<layers>,<checksum>` or `$WP,ERR,<reason>`.
+    async fn handle_wind_profile(&mut self, cmd: UmbilicalCommand) {
+        if self.flight_state.flight_mode != FlightMode::Startup {
+            log::warn!("WIND PROFILE: rejected — only allowed in Startup");
+            self.wind_upload = None;
+            wind_profile_reply(format_args!("ERR,MODE"));
+            return;
+        }
+        match cmd {
+            UmbilicalCommand::WindProfileBegin(count) => {
+                if count as usize > MAX_WIND_LAYERS {
+                    log::warn!("WIND PROFILE: rejected — {} layers, max {}", count, MAX_WIND_LAYERS);
+                    self.wind_upload = None;
+                    wind_profile_reply(format_args!("ERR,COUNT"));
+                    return;
+                }
+                log::info!("WIND PROFILE: upload of {} layers started", count);
+                self.wind_upload = Some(WindUpload::new(count));
+                wind_profile_reply(format_args!("B,{}", count));
+            }
+            UmbilicalCommand::WindProfileLayer(index, layer) => {
+                let Some(upload) = &mut self.wind_upload else {
+                    wind_profile_reply(format_args!("ERR,NOBEGIN"));
+                    return;
+                };
+                if !upload.set(index, layer) {
+                    wind_profile_reply(format_args!("ERR,INDEX"));
+                    return;
+                }
+                wind_profile_reply(format_args!(
+                    "L,{},{},{},{}",
+                    index, layer.altitude_m, layer.from_deg, layer.speed_ms
+                ));
+            }
+            UmbilicalCommand::WindProfileCommit => {
+                let Some(upload) = self.wind_upload.take() else {
+                    wind_profile_reply(format_args!("ERR,NOBEGIN"));
+                    return;
+                };
+                let Some(layers) = upload.layers() else {
+                    log::warn!("WIND PROFILE: rejected — layers missing");
+                    wind_profile_reply(format_args!("ERR,MISSING"));
+                    return;
+                };
+                let Some(profile) = WindProfile::from_layers(layers) else {
+                    log::warn!("WIND PROFILE: rejected — layers out of range or not ascending");
+                    wind_profile_reply(format_args!("ERR,RANGE"));
+                    return;
+                };
+                self.apply_wind_profile(profile).await;
+            }
+            UmbilicalCommand::WindProfileClear => {
+                self.wind_upload = None;
+                self.apply_wind_profile(WindProfile::EMPTY).await;
+            }
+            _ => {}
+        }
+    }
+
+    async fn apply_wind_profile(&mut self, profile: WindProfile) {
+        if !self.flight_state.set_blims_wind_profile(profile).await {
+            log::warn!("WIND PROFILE: applied but not saved; reverts on reboot");
+        }
+        log::info!("WIND PROFILE: {} layers, checksum {:08X}", profile.len(), profile.checksum());
+        wind_profile_reply(format_args!("OK,{},{:08X}", profile.len(), profile.checksum()));
+    }
+
+    /// One step of the `<GF,...>` geofence upload, Startup only, echoed as
+    /// `$GF,...` like the wind profile; the commit replies
+    /// `$GF,OK,<zones>,<checksum>` or `$GF,ERR,<reason>`.
+    async fn handle_geofence(&mut self, cmd: UmbilicalCommand) {
+        if self.flight_state.flight_mode != FlightMode::Startup {
+            log::warn!("GEOFENCE: rejected — only allowed in Startup");
+            self.fence_upload = None;
+            geofence_reply(format_args!("ERR,MODE"));
+            return;
+        }
+        match cmd {
+            UmbilicalCommand::GeofenceBegin(count) => {
+                if count as usize > MAX_FENCE_ZONES {
+                    log::warn!("GEOFENCE: rejected — {} zones, max {}", count, MAX_FENCE_ZONES);
+                    self.fence_upload = None;
+                    geofence_reply(format_args!("ERR,COUNT"));
+                    return;
+                }
+                log::info!("GEOFENCE: upload of {} zones started", count);
+                self.fence_upload = Some(FenceUpload::new(count));
+                geofence_reply(format_args!("B,{}", count));
+            }
+            UmbilicalCommand::GeofenceZone(zone, kind, len) => {
+                let Some(upload) = &mut self.fence_upload else {
+                    geofence_reply(format_args!("ERR,NOBEGIN"));
+                    return;
+                };
+                if !(3..=MAX_FENCE_VERTICES).contains(&(len as usize)) {
+                    geofence_reply(format_args!("ERR,COUNT"));
+                    return;
+                }
+                if !upload.set_zone(zone, kind, len) {
+                    geofence_reply(format_args!("ERR,INDEX"));
+                    return;
+                }
+                geofence_reply(format_args!("Z,{},{},{}", zone, zone_letter(kind), len));
+            }
+            UmbilicalCommand::GeofenceVertex(zone, vertex, at) => {
+                let Some(upload) = &mut self.fence_upload else {
+                    geofence_reply(format_args!("ERR,NOBEGIN"));
+                    return;
+                };
+                if !upload.set_vertex(zone, vertex, at) {
+                    geofence_reply(format_args!("ERR,INDEX"));
+                    return;
+                }
+                geofence_reply(format_args!("V,{},{},{:.7},{:.7}", zone, vertex, at.lat_deg, at.lon_deg));
+            }
+            UmbilicalCommand::GeofenceCommit => {
+                let Some(upload) = self.fence_upload.take() else {
+                    geofence_reply(format_args!("ERR,NOBEGIN"));
+                    return;
+                };
+                let Some(polygons) = upload.polygons() else {
+                    log::warn!("GEOFENCE: rejected — zones or vertices missing");
+                    geofence_reply(format_args!("ERR,MISSING"));
+                    return;
+                };
+                let zones: Option<heapless::Vec<Zone, MAX_FENCE_ZONES>> =
+                    polygons.map(|(kind, vertices)| Zone::new(kind, vertices)).collect();
+                let Some(fence) = zones.and_then(|z| Geofence::from_zones(&z)) else {
+                    log::warn!("GEOFENCE: rejected — zones out of range, self-crossing or two landing zones");
+                    geofence_reply(format_args!("ERR,RANGE"));
+                    return;
+                };
+                self.apply_geofence(fence).await;
+            }
+            UmbilicalCommand::GeofenceClear => {
+                self.fence_upload = None;
+                self.apply_geofence(Geofence::EMPTY).await;
+            }
+            _ => {}
+        }
+    }
+
+    async fn apply_geofence(&mut self, fence: Geofence) {
+        if !self.flight_state.set_blims_geofence(fence).await {
+            log::warn!("GEOFENCE: applied but not saved; reverts on reboot");
+        }
+        log::info!("GEOFENCE: {} zones, checksum {:08X}", fence.len(), fence.checksum());
+        geofence_reply(format_args!("OK,{},{:08X}", fence.len(), fence.checksum()));
+    }
+
+    /// Start a ground test profile if the vehicle is in Startup with test
+    /// mode enabled. Replaces any profile already running.
+    fn start_airbrake_test(&mut self, profile: TestProfile) {
+        if self.flight_state.flight_mode != FlightMode::Startup || !self.airbrake_test_enabled {
+            log::warn!("AIRBRAKE TEST: rejected — requires Startup and test mode (<AT1>)");
+            return;
+        }
+        if !profile.is_valid() {
+            log::warn!("AIRBRAKE TEST: rejected — parameters out of range: {:?}", profile);
+            return;
+        }
+        log::info!("AIRBRAKE TEST: starting {:?} ({:.1} s)", profile, profile.duration_s());
+        self.airbrake_test = Some(AirbrakeTest::start(profile));
+    }
+
+    /// Abort any running profile and retract.
+    fn stop_airbrake_test(&mut self) {
+        let running = self.airbrake_test.take();
+        if let Some(test) = &running {
+            log::info!("AIRBRAKE TEST: {:?} stopped, retracting", test.profile);
+        }
+        if running.is_some() || self.flight_state.flight_mode == FlightMode::Startup {
+            self.flight_state.airbrake_system.retract();
+        }
+    }
+
+    fn disable_airbrake_test(&mut self) {
+        self.stop_airbrake_test();
+        if self.airbrake_test_enabled {
+            self.airbrake_test_enabled = false;
+            self.flight_state.airbrake_system.disable();
+        }
+    }
+
+    /// True while a ground test profile is driving the airbrakes.
+    pub fn airbrake_test_running(&self) -> bool {
+        self.airbrake_test.is_some()
+    }
+
+    /// Advance the running ground test profile one cycle. Test mode never
+    /// survives leaving Startup, so a profile can't outlive arming.
+    fn run_airbrake_test(&mut self) {
+        if self.airbrake_test_enabled && self.flight_state.flight_mode != FlightMode::Startup {
+            log::warn!("AIRBRAKE TEST: left Startup — test mode disabled");
+            self.disable_airbrake_test();
+            return;
+        }
+        let Some(test) = &self.airbrake_test else { return };
+        match test.command() {
+            Some(deployment) => self.flight_state.airbrake_system.set_deployment(deployment),
+            None => {
+                log::info!("AIRBRAKE TEST: {:?} complete", test.profile);
+                self.airbrake_test = None;
+                self.flight_state.airbrake_system.retract();
+            }
+        }
+    }
+
     /// If PT3 (scaled PSI) exceeds `PT3_OVERPRESSURE_THRESHOLD` for 3
     /// consecutive cycles, latch SV open and force Fault. One-shot: once
     /// fired, further calls are a no-op so sensor noise can't re-issue
@@ -666,11 +1138,16 @@ impl FlightLoop {
         }
     }
 
+    #[allow(clippy::collapsible_if, clippy::unnecessary_unwrap)] // mode transitions kept as written
     pub async fn check_transitions(&mut self) {
         // Retrieve current values for easier access
         let _packet = &self.flight_state.packet;
         let _mode = self.flight_state.flight_mode;
 
+        if self.alt_armed && self.flight_state.altimeter_read_ok() {
+            self.apogee_m = self.apogee_m.max(self.flight_state.packet.altitude);
+        }
+
         // One-shot vent: open SV on first entry to any recovery/fault mode.
         if !self.recovery_vent_sent
             && matches!(
@@ -684,16 +1161,22 @@ impl FlightLoop {
             self.recovery_vent_sent = true;
         }
 
-        // Key rising edge: acknowledge arming, or reject if no wipe was done.
+        // Key rising edge: acknowledge arming, or reject if no wipe was done
+        // or the supply is low.
         let key_now = self.flight_state.key_armed;
         if key_now && !self.key_prev {
             log::info!("Key armed detected: arming signal received");
-            if self.flash_wiped {
-                self.flight_state.buzz(2); // arm ack
-            } else {
+            if !self.flash_wiped {
                 log::warn!("Arming blocked: flash not wiped");
                 crate::umbilical::print_str("Arming blocked: wipe flash first\n");
-                self.flight_state.buzz(5); // distinct reject pattern
+                self.flight_state.buzz_pattern("arm reject", &BuzzPattern::morse(constants::BUZZ_ARM_REJECT));
+            } else if !self.flight_state.supply_ok() {
+                let (battery, pyro) = (self.flight_state.packet.battery_v, self.flight_state.packet.pyro_v);
+                log::warn!("Arming blocked: supply low (battery {:.2} V, pyro {:.2} V)", battery, pyro);
+                crate::umbilical::print_str("Arming blocked: supply voltage low\n");
+                self.flight_state.buzz_pattern("arm reject", &BuzzPattern::morse(constants::BUZZ_ARM_REJECT));
+            } else {
+                self.flight_state.buzz_pattern("arm ack", &BuzzPattern::morse(constants::BUZZ_ARM_ACK));
             }
         }
         self.key_prev = key_now;
@@ -720,7 +1203,7 @@ impl FlightLoop {
                     if self.umbilical_disconnect_time.is_none() {
                         self.umbilical_disconnect_time = Some(Instant::now());
                     } else if let Some(disconnect_time) = self.umbilical_disconnect_time {
-                        if disconnect_time.elapsed().as_millis() as u64
+                        if disconnect_time.elapsed().as_millis()
                             > constants::UMBILICAL_TIMEOUT_MS
                             && !self.vent_signal_sent
                         {
@@ -731,17 +1214,14 @@ impl FlightLoop {
                         }
                     }
                 }
-                // Periodic status buzz: 2 beeps if umbilical connected, 3 if not, every 5s
+                // Periodic status buzz every 5s: the fault code if anything has
+                // failed, otherwise short if umbilical connected, long if not
                 let should_buzz = match self.last_startup_buzz {
                     None => true,
-                    Some(last) => last.elapsed().as_millis() as u64 >= constants::STARTUP_BUZZ_PERIOD_MS,
+                    Some(last) => last.elapsed().as_millis() >= constants::STARTUP_BUZZ_PERIOD_MS,
                 };
                 if should_buzz {
-                    if self.flight_state.umbilical_connected {
-                        self.flight_state.buzz(2);
-                    } else {
-                        self.flight_state.buzz(3);
-                    }
+                    self.buzz_status();
                     self.last_startup_buzz = Some(Instant::now());
                 }
                 if self.flight_state.altimeter_state == crate::state::SensorState::INVALID {
@@ -751,10 +1231,12 @@ impl FlightLoop {
                     log::error!("Altimeter invalid at Startup; transitioning to Fault");
                     return;
                 }
-                // LV: arming is driven by key arm command, umbilical connection, and flash wipe
+                // LV: arming is driven by key arm command, umbilical connection, flash wipe
+                // and both supply rails above their minimums
                 if self.flight_state.key_armed
                     && self.flight_state.umbilical_connected
                     && self.flash_wiped
+                    && self.flight_state.supply_ok()
                 {
                     if self.flight_state.altimeter_state == crate::state::SensorState::VALID {
                         // Record arming altitude (TODO: implement into storage)
@@ -787,7 +1269,7 @@ impl FlightLoop {
                     self.umbilical_disconnect_time = None;
                     self.vent_signal_sent = false;
                     if !self.umbilical_prev {
-                        self.flight_state.buzz(2); // just reconnected
+                        self.buzz_umbilical(); // just reconnected
                     }
                 } else {
                     log::info!("Umbilical disconnected");
@@ -796,7 +1278,7 @@ impl FlightLoop {
                     if self.umbilical_disconnect_time.is_none() {
                         self.umbilical_disconnect_time = Some(Instant::now());
                     } else if let Some(disconnect_time) = self.umbilical_disconnect_time {
-                        if disconnect_time.elapsed().as_millis() as u64
+                        if disconnect_time.elapsed().as_millis()
                             > constants::UMBILICAL_TIMEOUT_MS
                             && !self.vent_signal_sent
                         {
@@ -807,7 +1289,7 @@ impl FlightLoop {
                         }
                     }
                     if self.umbilical_prev {
-                        self.flight_state.buzz(3); // just disconnected
+                        self.buzz_umbilical(); // just disconnected
                     }
                 }
                 self.umbilical_prev = self.flight_state.umbilical_connected;
@@ -910,7 +1392,7 @@ impl FlightLoop {
                         if vert_speed_ft_s < 50.0 {
                             self.n2_low_speed_count += 1;
                             if self.n2_low_speed_count >= 5 {
-                                let _ = self.flight_state.payload_uart.write(b"N2\n").await;
+                                let _ = self.flight_state.payload.send(PayloadCommand::N2).await;
                                 log::info!("PAYLOAD: Sent N2 (vert={:.1} ft/s)", vert_speed_ft_s);
                                 self.n2_sent = true;
                                 self.flight_state.packet.cmd_n2 = 1;
@@ -951,6 +1433,12 @@ impl FlightLoop {
                         // Retract Airbrakes at apogee
                         self.flight_state.airbrake_system.set_deployment(0.0);
                         log::info!("Airbrakes retracted at apogee");
+                        let estimate = crate::airbrake_task::get_drag_estimate();
+                        log::info!(
+                            "AIRBRAKE: coast drag estimate body Cd·A {:.5} m², brake Cd·A {:.5} m²",
+                            estimate.body_cda_m2,
+                            estimate.brake_cda_m2
+                        );
 
                         // Deploy Drogue
                         self.flight_state.trigger_drogue().await;
@@ -987,7 +1475,7 @@ impl FlightLoop {
                     if self.low_alt_time.is_none() {
                         self.low_alt_time = Some(Instant::now());
                     } else if self.low_alt_time.unwrap().elapsed().as_millis() >= 1000 {
-                        let _ = self.flight_state.payload_uart.write(b"N3\n").await;
+                        let _ = self.flight_state.payload.send(PayloadCommand::N3).await;
                         log::info!("PAYLOAD: Sent N3");
                         self.n3_sent = true;
                         self.flight_state.packet.cmd_n3 = 1;
@@ -999,7 +1487,7 @@ impl FlightLoop {
                 // Open SV 5s after drogue deploy
                 if !self.sv_open {
                     if let Some(entry_time) = self.drogue_entry_time {
-                        if entry_time.elapsed().as_millis() as u64 >= constants::DROGUE_DEPLOY_DELAY_MS {
+                        if entry_time.elapsed().as_millis() >= constants::DROGUE_DEPLOY_DELAY_MS {
                             log::warn!("Opening SV {}ms after drogue deploy", entry_time.elapsed().as_millis());
                             self.flight_state.open_sv(0).await;
                             self.sv_open = true;
@@ -1057,7 +1545,7 @@ impl FlightLoop {
                     if self.low_alt_time.is_none() {
                         self.low_alt_time = Some(Instant::now());
                     } else if self.low_alt_time.unwrap().elapsed().as_millis() >= 1000 {
-                        let _ = self.flight_state.payload_uart.write(b"N3\n").await;
+                        let _ = self.flight_state.payload.send(PayloadCommand::N3).await;
                         log::info!("PAYLOAD: Sent N3");
                         self.n3_sent = true;
                         self.flight_state.packet.cmd_n3 = 1;
@@ -1072,7 +1560,7 @@ impl FlightLoop {
                     let ay = self.flight_state.packet.accel_y;
                     let az = self.flight_state.packet.accel_z;
                     if ax.abs() > 50.0 || ay.abs() > 50.0 || az.abs() > 50.0 {
-                        let _ = self.flight_state.payload_uart.write(b"N4\n").await;
+                        let _ = self.flight_state.payload.send(PayloadCommand::N4).await;
                         log::info!("PAYLOAD: Sent N4");
                         self.n4_sent = true;
                         self.flight_state.packet.cmd_n4 = 1;
@@ -1081,32 +1569,55 @@ impl FlightLoop {
 
                 // BLiMS: upwind target >1000 ft, downwind target <1000 ft, neutral <200 ft
                 self.flight_state.run_blims();
+
+                if !self.flight_state.beacon_mode()
+                    && self
+                        .main_entry_time
+                        .is_some_and(|t| t.elapsed().as_millis() >= constants::MAIN_LOG_TIMEOUT_MS)
+                {
+                    self.flight_state.enter_beacon_mode().await;
+                }
+
+                // On the ground: beep out apogee in feet, repeating (less
+                // often in beacon mode, which assumes landed)
+                self.update_landed();
+                let (readout, pause) = if self.flight_state.beacon_mode() {
+                    (true, constants::BEACON_BUZZ_PAUSE_MS)
+                } else {
+                    (self.landed, constants::BUZZ_REPEAT_PAUSE_MS)
+                };
+                if readout && self.flight_state.buzzer.idle_ms() >= pause {
+                    let apogee_ft = self.apogee_ft();
+                    self.flight_state
+                        .buzz_pattern("apogee readout", &BuzzPattern::number(apogee_ft));
+                }
             }
             FlightMode::Fault => {
                 if !self.fault_signal_sent {
                     if self.drogue_deployed {
-                        match self.flight_state.payload_uart.write(b"A3\n").await {
-                            Ok(_) => {
+                        match self.flight_state.payload.send(PayloadCommand::A3).await {
+                            Ok(()) => {
                                 log::warn!("A3 — fault after apogee");
                                 self.flight_state.packet.cmd_a3 = 1;
                             }
-                            Err(e) => log::error!("PAYLOAD UART write A3 failed: {:?}", e),
+                            Err(e) => log::error!("PAYLOAD: A3 not sent: {:?}", e),
                         }
                     } else {
-                        let alt = self.flight_state.packet.altitude;
-                        let vel = (alt - self.last_alt) * 20.0;
-                        let mut buf = heapless::String::<32>::new();
-                        let _ = core::fmt::write(&mut buf, format_args!("A2,{:.1},{:.1}\n", alt, vel));
-                        match self.flight_state.payload_uart.write(buf.as_bytes()).await {
-                            Ok(_) => {
+                        match self.flight_state.payload.send(self.a2_command()).await {
+                            Ok(()) => {
                                 log::warn!("A2 — fault before apogee");
                                 self.flight_state.packet.cmd_a2 = 1;
                             }
-                            Err(e) => log::error!("PAYLOAD UART write A2 failed: {:?}", e),
+                            Err(e) => log::error!("PAYLOAD: A2 not sent: {:?}", e),
                         }
                     }
                     self.fault_signal_sent = true;
                 }
+                if self.flight_state.buzzer.idle_ms() >= constants::BUZZ_REPEAT_PAUSE_MS {
+                    let bits = self.fault_bits();
+                    self.flight_state
+                        .buzz_pattern("fault code", &BuzzPattern::fault_code(bits, &health::CODES));
+                }
                 log::error!("Flight mode is Fault");
             }
         }
@@ -1127,6 +1638,10 @@ impl FlightLoop {
         //self.key_armed = armed;
     }
 
+    pub fn set_flash_wiped(&mut self, wiped: bool) {
+        self.flash_wiped = wiped;
+    }
+
     pub fn set_umbilical(&mut self, connected: bool) {
         self.flight_state.umbilical_connected = connected;
         self.umbilical_state = connected;
@@ -1140,7 +1655,7 @@ impl FlightLoop {
         self.flight_state.altimeter_state = state;
     }
 
-    pub fn set_blims(&mut self, blims: blims::Blims<'static>) {
+    pub fn set_blims(&mut self, blims: blims::Blims<blims::PwmActuator<'static>>) {
         self.flight_state.set_blims(blims);
     }
 
@@ -1179,6 +1694,7 @@ impl FlightLoop {
         self.sv_open = open;
     }
 
+ #[allow(clippy::collapsible_if)] // launch stages kept as written
  pub async fn handle_launch_sequence(&mut self) {
         let sequence_now = Instant::now();
         match self.launch_sequence_stage {
@@ -1248,7 +1764,7 @@ impl FlightLoop {
     }
 
     pub fn get_altitude(&mut self) -> f32 {
-        return self.flight_state.packet.altitude;
+        self.flight_state.packet.altitude
     }
 
     // Run a simulation cycle: skips hardware reads/writes, only logic.
@@ -1267,5 +1783,126 @@ impl FlightLoop {
 
         // Continuously update actuators so timers and physical pins actually output during simulation tests
         self.flight_state.update_actuators().await;
+        self.run_airbrake_test();
+
+        // Payload link, so commands sent above get their ACKs
+        let mode = self.flight_state.flight_mode as u8;
+        self.flight_state.update_payload(mode).await;
+    }
+}
+
+/// Layers of a `<WP,...>` upload received so far.
+struct WindUpload {
+    count: u8,
+    layers: [WindLayer; MAX_WIND_LAYERS],
+    received: u32, // bit per layer index
+}
+
+impl WindUpload {
+    fn new(count: u8) -> Self {
+        Self { count, layers: [WindLayer::default(); MAX_WIND_LAYERS], received: 0 }
+    }
+
+    /// False if `index` is past the announced count. Resending a layer
+    /// overwrites it.
+    fn set(&mut self, index: u8, layer: WindLayer) -> bool {
+        if index >= self.count {
+            return false;
+        }
+        self.layers[index as usize] = layer;
+        self.received |= 1 << index;
+        true
+    }
+
+    /// All announced layers, or `None` if any are missing.
+    fn layers(&self) -> Option<&[WindLayer]> {
+        let all = (1u32 << self.count) - 1;
+        (self.received == all).then(|| &self.layers[..self.count as usize])
     }
 }
+
+/// Sends `$WP,<args>` back over the umbilical.
+fn wind_profile_reply(args: core::fmt::Arguments) {
+    let mut line = heapless::String::<64>::new();
+    let _ = core::fmt::write(&mut line, format_args!("$WP,{}\n", args));
+    umbilical::print_str(&line);
+}
+
+/// Sends `$BK,<args>` back over the umbilical.
+fn blims_config_reply(args: core::fmt::Arguments) {
+    let mut line = heapless::String::<64>::new();
+    let _ = core::fmt::write(&mut line, format_args!("$BK,{}\n", args));
+    umbilical::print_str(&line);
+}
+
+/// Zones of a `<GF,...>` upload received so far.
+struct FenceUpload {
+    count: u8,
+    zones: [Option<(ZoneKind, u8)>; MAX_FENCE_ZONES], // kind, vertices to expect
+    vertices: [[LatLon; MAX_FENCE_VERTICES]; MAX_FENCE_ZONES],
+    received: [u16; MAX_FENCE_ZONES], // bit per vertex index
+}
+
+impl FenceUpload {
+    fn new(count: u8) -> Self {
+        Self {
+            count,
+            zones: [None; MAX_FENCE_ZONES],
+            vertices: [[LatLon::default(); MAX_FENCE_VERTICES]; MAX_FENCE_ZONES],
+            received: [0; MAX_FENCE_ZONES],
+        }
+    }
+
+    /// False if `zone` is past the announced count. Resending a zone
+    /// header drops the vertices received for it.
+    fn set_zone(&mut self, zone: u8, kind: ZoneKind, len: u8) -> bool {
+        if zone >= self.count {
+            return false;
+        }
+        self.zones[zone as usize] = Some((kind, len));
+        self.received[zone as usize] = 0;
+        true
+    }
+
+    /// False if the zone hasn't been announced or `vertex` is past its
+    /// count. Resending a vertex overwrites it.
+    fn set_vertex(&mut self, zone: u8, vertex: u8, at: LatLon) -> bool {
+        match self.zones.get(zone as usize) {
+            Some(Some((_, len))) if vertex < *len => {
+                self.vertices[zone as usize][vertex as usize] = at;
+                self.received[zone as usize] |= 1 << vertex;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Every announced zone with its vertices, or `None` if any zone or
+    /// vertex is missing.
+    fn polygons(&self) -> Option<impl Iterator<Item = (ZoneKind, &[LatLon])>> {
+        let zones = &self.zones[..self.count as usize];
+        let complete = zones
+            .iter()
+            .zip(&self.received)
+            .all(|(zone, &got)| zone.is_some_and(|(_, len)| u32::from(got) == (1u32 << len) - 1));
+        complete.then(|| {
+            zones.iter().zip(&self.vertices).filter_map(|(zone, vertices)| {
+                zone.map(|(kind, len)| (kind, &vertices[..len as usize]))
+            })
+        })
+    }
+}
+
+fn zone_letter(kind: ZoneKind) -> char {
+    match kind {
+        ZoneKind::KeepOut => 'K',
+        ZoneKind::LandingZone => 'L',
+    }
+}
+
+/// Sends `$GF,<args>` back over the umbilical.
+fn geofence_reply(args: core::fmt::Arguments) {
+    let mut line = heapless::String::<64>::new();
+    let _ = core::fmt::write(&mut line, format_args!("$GF,{}\n", args));
+    umbilical::print_str(&line);
+}
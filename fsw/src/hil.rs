@@ -0,0 +1,293 @@
+//! Hardware-in-the-loop sensor injection.
+//!
+//! Only compiled with the `hil` feature. A host (see `tools/hil_runner.py`)
+//! streams timestamped sensor frames over the USB umbilical; `read_sensors()`
+//! takes the latest frame for each sensor instead of talking to the drivers,
+//! and everything downstream — AGL conversion, transitions, airbrakes, BLiMS,
+//! actuators, flash, telemetry — runs exactly as in flight.
+//!
+//! Frames are newline-terminated ASCII so they sit alongside the `<X>`
+//! command tokens on the same CDC channel:
+//!
+//! ```text
+//! $HIL,B,<t_ms>,<pressure_pa>,<temp_c>
+//! $HIL,I,<t_ms>,<ax>,<ay>,<az>,<gx>,<gy>,<gz>[,<mx>,<my>,<mz>]
+//! $HIL,G,<t_ms>,<lat>,<lon>,<sats>,<fix_type>,<vel_n>,<vel_e>,<vel_d>,<h_acc_mm>,<v_acc_mm>
+//! $HIL,A,<t_ms>,<pt3>,<pt4>,<rtd>
+//! ```
+//!
+//! `t_ms` is the host's simulation clock. Units match the packet (m/s², °/s,
+//! scaled ADC values), so a frame is what the driver would have produced.
+//! A sensor whose frames stop arriving reads as a failed read once its
+//! frame is older than the timeout, so sensor-loss handling is exercised too.
+
+use core::cell::RefCell;
+
+use embassy_sync::blocking_mutex::{Mutex, raw::CriticalSectionRawMutex};
+use embassy_time::{Instant, Timer};
+
+use crate::constants;
+
+/// Longest frame line accepted (a full GPS frame is ~110 bytes).
+const MAX_LINE: usize = 160;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum HilError {
+    /// No frame has been received for this sensor yet, or (GPS only) no new
+    /// solution since the last read.
+    NoData,
+    /// The latest frame is older than the sensor's timeout.
+    Stale,
+}
+
+#[derive(Copy, Clone, Default)]
+pub struct BaroFrame {
+    pub pressure: f32,
+    pub temp: f32,
+}
+
+#[derive(Copy, Clone, Default)]
+pub struct ImuFrame {
+    pub accel: [f32; 3],
+    pub gyro: [f32; 3],
+    pub mag: Option<[f32; 3]>,
+}
+
+#[derive(Copy, Clone, Default)]
+pub struct GpsFrame {
+    pub t_ms: u32,
+    pub latitude: f32,
+    pub longitude: f32,
+    pub num_satellites: u32,
+    pub fix_type: u8,
+    pub vel_n: f64,
+    pub vel_e: f64,
+    pub vel_d: f64,
+    pub h_acc: u32,
+    pub v_acc: u32,
+}
+
+#[derive(Copy, Clone, Default)]
+pub struct AdcFrame {
+    pub pt3: f32,
+    pub pt4: f32,
+    pub rtd: f32,
+}
+
+#[derive(Copy, Clone)]
+struct Slot<T> {
+    frame: T,
+    /// Boot ms (truncated) at which the frame arrived.
+    rx_ms: u32,
+    /// Cleared when read; only used for GPS, which reports NoData between fixes.
+    fresh: bool,
+}
+
+struct Frames {
+    baro: Option<Slot<BaroFrame>>,
+    imu: Option<Slot<ImuFrame>>,
+    gps: Option<Slot<GpsFrame>>,
+    adc: Option<Slot<AdcFrame>>,
+    rejected: u32,
+}
+
+static FRAMES: Mutex<CriticalSectionRawMutex, RefCell<Frames>> = Mutex::new(RefCell::new(Frames {
+    baro: None,
+    imu: None,
+    gps: None,
+    adc: None,
+    rejected: 0,
+}));
+
+fn now_ms() -> u32 {
+    Instant::now().as_millis() as u32
+}
+
+fn latest<T: Copy>(slot: &Option<Slot<T>>, timeout_ms: u64) -> Result<T, HilError> {
+    match slot {
+        None => Err(HilError::NoData),
+        Some(s) if now_ms().wrapping_sub(s.rx_ms) as u64 > timeout_ms => Err(HilError::Stale),
+        Some(s) => Ok(s.frame),
+    }
+}
+
+/// Latest barometer frame.
+pub fn read_baro() -> Result<BaroFrame, HilError> {
+    FRAMES.lock(|f| latest(&f.borrow().baro, constants::HIL_FRAME_TIMEOUT_MS))
+}
+
+/// Latest IMU frame.
+pub fn read_imu() -> Result<ImuFrame, HilError> {
+    FRAMES.lock(|f| latest(&f.borrow().imu, constants::HIL_FRAME_TIMEOUT_MS))
+}
+
+/// Latest ADC frame.
+pub fn read_adc() -> Result<AdcFrame, HilError> {
+    FRAMES.lock(|f| latest(&f.borrow().adc, constants::HIL_FRAME_TIMEOUT_MS))
+}
+
+/// Next GPS solution. Like the real receiver, returns `NoData` between
+/// solutions and only reports `Stale` once they stop altogether.
+pub fn take_gps() -> Result<GpsFrame, HilError> {
+    FRAMES.lock(|f| {
+        let mut f = f.borrow_mut();
+        let frame = latest(&f.gps, constants::HIL_GPS_TIMEOUT_MS)?;
+        match f.gps.as_mut() {
+            Some(s) if s.fresh => {
+                s.fresh = false;
+                Ok(frame)
+            }
+            _ => Err(HilError::NoData),
+        }
+    })
+}
+
+/// Blocks until the host has started streaming barometer and IMU frames.
+/// Called once before the flight loop starts so the first cycles don't see
+/// missing sensors and fault at Startup.
+pub async fn wait_for_stream() {
+    log::info!("HIL: waiting for host sensor stream...");
+    loop {
+        let ready = FRAMES.lock(|f| {
+            let f = f.borrow();
+            f.baro.is_some() && f.imu.is_some()
+        });
+        if ready {
+            break;
+        }
+        Timer::after_millis(constants::MAIN_LOOP_DELAY_MS).await;
+    }
+    log::info!("HIL: sensor stream detected, starting flight loop");
+}
+
+/// Assembles `$HIL,...\n` lines from USB packets. Owned by the umbilical
+/// receiver task; a frame may span several 64-byte packets and one packet
+/// may carry several frames, with `<X>` commands before, between or after
+/// them.
+pub struct HilReceiver {
+    line: heapless::Vec<u8, MAX_LINE>,
+    overflowed: bool,
+}
+
+impl Default for HilReceiver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HilReceiver {
+    pub const fn new() -> Self {
+        Self { line: heapless::Vec::new(), overflowed: false }
+    }
+
+    /// True while a partial frame is buffered.
+    pub fn in_progress(&self) -> bool {
+        !self.line.is_empty() || self.overflowed
+    }
+
+    /// Takes the frame bytes out of one packet: everything from a `$` up to
+    /// its `\n` (or the end of the packet, continued by the next one) goes to
+    /// the assembler. The bytes outside frames are moved to the front of
+    /// `data` for the command parser; returns how many there are.
+    pub fn take_frames(&mut self, data: &mut [u8]) -> usize {
+        let mut rest = 0;
+        for i in 0..data.len() {
+            let b = data[i];
+            if !self.in_progress() && b != b'$' {
+                // CR/LF between frames is line noise, not part of a command
+                if b != b'\r' && b != b'\n' {
+                    data[rest] = b;
+                    rest += 1;
+                }
+            } else if b == b'\n' {
+                if !self.overflowed {
+                    handle_line(&self.line);
+                } else {
+                    FRAMES.lock(|f| f.borrow_mut().rejected += 1);
+                }
+                self.line.clear();
+                self.overflowed = false;
+            } else if b == b'\r' {
+                // Tolerate CRLF line endings
+            } else if self.line.push(b).is_err() {
+                self.overflowed = true;
+            }
+        }
+        rest
+    }
+}
+
+fn handle_line(line: &[u8]) {
+    let rx_ms = now_ms();
+    let ok = core::str::from_utf8(line)
+        .ok()
+        .and_then(|s| store_frame(s, rx_ms))
+        .is_some();
+    if !ok {
+        let count = FRAMES.lock(|f| {
+            let mut f = f.borrow_mut();
+            f.rejected += 1;
+            f.rejected
+        });
+        log::warn!("HIL: rejected frame ({} total)", count);
+    }
+}
+
+/// Parses one frame and stores it. Returns `None` if the line is malformed.
+fn store_frame(line: &str, rx_ms: u32) -> Option<()> {
+    let mut parts = line.split(',');
+    if parts.next()? != "$HIL" {
+        return None;
+    }
+    let kind = parts.next()?;
+    let t_ms = parts.next()?.parse::<u32>().ok()?;
+    let mut next_f32 = || parts.next().and_then(|p| p.parse::<f32>().ok());
+
+    match kind {
+        "B" => {
+            let frame = BaroFrame { pressure: next_f32()?, temp: next_f32()? };
+            FRAMES.lock(|f| f.borrow_mut().baro = Some(Slot { frame, rx_ms, fresh: true }));
+        }
+        "I" => {
+            let accel = [next_f32()?, next_f32()?, next_f32()?];
+            let gyro = [next_f32()?, next_f32()?, next_f32()?];
+            // Magnetometer is optional; take it only if all three are present
+            let mag = match (next_f32(), next_f32(), next_f32()) {
+                (Some(x), Some(y), Some(z)) => Some([x, y, z]),
+                _ => None,
+            };
+            let frame = ImuFrame { accel, gyro, mag };
+            FRAMES.lock(|f| f.borrow_mut().imu = Some(Slot { frame, rx_ms, fresh: true }));
+        }
+        "G" => {
+            let latitude = next_f32()?;
+            let longitude = next_f32()?;
+            let num_satellites = next_f32()? as u32;
+            let fix_type = next_f32()? as u8;
+            let vel_n = next_f32()? as f64;
+            let vel_e = next_f32()? as f64;
+            let vel_d = next_f32()? as f64;
+            let h_acc = next_f32()? as u32;
+            let v_acc = next_f32()? as u32;
+            let frame = GpsFrame {
+                t_ms,
+                latitude,
+                longitude,
+                num_satellites,
+                fix_type,
+                vel_n,
+                vel_e,
+                vel_d,
+                h_acc,
+                v_acc,
+            };
+            FRAMES.lock(|f| f.borrow_mut().gps = Some(Slot { frame, rx_ms, fresh: true }));
+        }
+        "A" => {
+            let frame = AdcFrame { pt3: next_f32()?, pt4: next_f32()?, rtd: next_f32()? };
+            FRAMES.lock(|f| f.borrow_mut().adc = Some(Slot { frame, rx_ms, fresh: true }));
+        }
+        _ => return None,
+    }
+    Some(())
+}
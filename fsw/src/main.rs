@@ -14,6 +14,7 @@ use {defmt_rtt as _, panic_probe as _};
 
 pub mod actuator;
 pub mod airbrake_task;
+mod airbrake_test;
 mod constants;
 mod driver;
 mod flight_loop;
@@ -30,9 +31,13 @@ mod flight_loop;
 ))]
 #[path = "../Test/flight_sim.rs"]
 mod flight_sim;
+#[cfg(feature = "hil")]
+mod hil;
 mod module;
+mod odrive_task;
 mod packet;
 mod state;
+mod time_sync;
 pub mod umbilical;
 mod watchdog;
 
@@ -93,7 +98,9 @@ async fn main(spawner: Spawner) {
     spawner.spawn(payload_loopback_task(payload_rx).unwrap());
 
     #[cfg(not(feature = "test_payload_uart"))]
-    drop(payload_rx); // not needed in flight
+    spawner.spawn(payload_rx_task(payload_rx).unwrap());
+
+    let payload_link = module::PayloadUartLink::new(payload_tx);
 
     let altimeter_cs = Output::new(p.PIN_7, Level::High);
 
@@ -106,6 +113,14 @@ async fn main(spawner: Spawner) {
     // CFC_ARM (GPIO 41): off-board arming signal, input with pull-down
     let cfc_arm = embassy_rp::gpio::Input::new(p.PIN_41, embassy_rp::gpio::Pull::Down);
 
+    // GPS TIMEPULSE (GPIO 22): rising edge at the top of each UTC second.
+    // Only with the `gps_timepulse` feature; route the MAX-M10S TIMEPULSE
+    // pad here before enabling it
+    #[cfg(feature = "gps_timepulse")]
+    spawner.spawn(time_sync::timepulse_task(
+        embassy_rp::gpio::Input::new(p.PIN_22, embassy_rp::gpio::Pull::Down),
+    ).unwrap());
+
     // CFC_ARM_Indicator (GPIO 21): PWM at 400 Hz, drives buzzer + LED on-board
     let (ssa, buzzer, mav, sv) = module::init_actuators(
         p.PIN_36,
@@ -120,6 +135,16 @@ async fn main(spawner: Spawner) {
     let flash_cs = Output::new(p.PIN_6, embassy_rp::gpio::Level::High);
     let flash = module::init_onboard_flash(spi_bus, flash_cs);
     let airbrake_system = module::init_airbrake(p.PIN_37, p.PWM_SLICE11, p.PIN_38);
+    // PIO0 UART to each ODrive's UART A: GPIO 16/17 airbrake, 18/19 BLiMS.
+    // Not yet checked against the CFC schematic (RATS/SystemDoc.md 3.7)
+    let (airbrake_odrive, blims_odrive) =
+        module::init_odrive_links(p.PIO0, p.PIN_16, p.PIN_17, p.PIN_18, p.PIN_19);
+    // ODrive UART exchanges run beside the flight loop, not inside it
+    spawner.spawn(odrive_task::airbrake_odrive_task(driver::odrive::Odrive::new(airbrake_odrive, "airbrake")).unwrap());
+    spawner.spawn(odrive_task::blims_odrive_task(driver::odrive::Odrive::new(blims_odrive, "BLiMS")).unwrap());
+    // Battery (GPIO 42) and pyro rail (GPIO 43) on the RP2350 ADC.
+    // Not yet checked against the CFC schematic (RATS/SystemDoc.md 3.7)
+    let supply = module::init_supply_monitor(p.ADC, p.PIN_42, p.PIN_43);
     log::info!("INIT [4/8]: Actuators and GPIO ready");
 
     log::info!("INIT [5/8]: Spawning Core 1 (airbrake controller)...");
@@ -146,7 +171,8 @@ async fn main(spawner: Spawner) {
         sv,
         airbrake_system,
         flash,
-        payload_tx,
+        payload_link,
+        supply,
     )
     .await;
     log::info!("INIT [6/8]: Flight State initialized — all sensors probed");
@@ -565,8 +591,10 @@ async fn main(spawner: Spawner) {
             crate::umbilical::push_command(cmd);
 
             // Run one flight loop tick: check_umbilical_commands will dequeue
-            // the command and write e.g. "N1\n" to GPIO 12.
-            // The spawned payload_loopback_task reads GPIO 13 and logs SUCCESS.
+            // the command and send its CMD frame on GPIO 32. The spawned
+            // payload_loopback_task reads GPIO 33 and logs each frame it
+            // decodes. Nothing ACKs, so each command is re-sent until
+            // PAYLOAD_MAX_TRIES and then logged as unacknowledged.
             flight_loop.execute().await;
 
             test_cycle += 1;
@@ -786,6 +814,10 @@ async fn main(spawner: Spawner) {
     {
         let mut flight_loop = flight_loop::FlightLoop::new(flight_state);
         flight_loop.set_blims(blims);
+        // HIL: hold off until the host is streaming so Startup doesn't see
+        // a missing altimeter and fault before the first frame lands.
+        #[cfg(feature = "hil")]
+        hil::wait_for_stream().await;
         log::info!("FLIGHT LOOP: FlightLoop created, starting watchdog...");
         crate::watchdog::init(Watchdog::new(p.WATCHDOG));
         log::info!("FLIGHT LOOP: Watchdog started ({} ms timeout). Loop running.", constants::WATCHDOG_TIMEOUT_MS);
@@ -805,9 +837,10 @@ async fn main(spawner: Spawner) {
 
             let elapsed = start.elapsed().as_millis();
 
-            // Feed again immediately after execute() before sleep
-            // This prevents the 50 ms Timer delay from counting toward the timeout
-            crate::watchdog::feed();
+            // Feed again immediately after execute(), long enough to cover
+            // the sleep below (50 ms, or BEACON_LOOP_DELAY_MS in beacon mode)
+            let delay_ms = flight_loop.loop_delay_ms();
+            crate::watchdog::feed_for(Duration::from_millis(delay_ms + constants::WATCHDOG_TIMEOUT_MS as u64));
 
             if flight_loop.flight_state.cycle_count <= 3 {
                 log::info!("FLIGHT LOOP: Cycle {} complete in {} ms", flight_loop.flight_state.cycle_count, elapsed);
@@ -825,27 +858,50 @@ async fn main(spawner: Spawner) {
                 led.toggle();
             }
 
-            Timer::after_millis(constants::MAIN_LOOP_DELAY_MS).await;
+            Timer::after_millis(delay_ms).await;
+        }
+    }
+}
+
+/// Forwards every byte from the payload board to `module::PAYLOAD_RX`,
+/// where the flight loop's `Payload` driver picks it up.
+#[cfg(not(feature = "test_payload_uart"))]
+#[embassy_executor::task]
+async fn payload_rx_task(mut rx: UartRx<'static, Async>) -> ! {
+    let mut b = [0u8; 1];
+    loop {
+        match rx.read(&mut b).await {
+            Ok(()) => {
+                // A full pipe means the flight loop has stalled; drop the byte
+                let _ = module::PAYLOAD_RX.try_write(&b);
+            }
+            Err(e) => log::warn!("PAYLOAD UART RX error: {:?}", e),
         }
     }
 }
 
-/// Reads every byte that arrives on the RX pin and logs it.
+/// Decodes every frame that arrives on the RX pin and logs it.
 /// When GPIO 32 (TX) is jumpered to GPIO 33 (RX) this confirms the
-/// real payload signal (e.g. "N1\n") was successfully transmitted.
+/// payload frames (CMD, HEARTBEAT) are transmitted intact.
+#[cfg(feature = "test_payload_uart")]
 #[embassy_executor::task]
 async fn payload_loopback_task(mut rx: UartRx<'static, Async>) -> ! {
-    log::info!("LOOPBACK: Monitor task running. Waiting for signals...");
-    // Signals are "N1\n", "N2\n", "N3\n", "N4\n" — all 3 bytes
-    let mut buf = [0u8; 3];
+    use crate::driver::payload::{FrameDecoder, PayloadCommand, frame_type};
+
+    log::info!("LOOPBACK: Monitor task running. Waiting for frames...");
+    let mut decoder = FrameDecoder::new();
+    let mut b = [0u8; 1];
     loop {
-        match embassy_time::with_timeout(embassy_time::Duration::from_secs(5), rx.read(&mut buf))
-            .await
-        {
-            Ok(Ok(())) => {
-                let s = core::str::from_utf8(&buf).unwrap_or("<?>");
-                log::info!("LOOPBACK SUCCESS: [{}]", s.trim());
-            }
+        match embassy_time::with_timeout(Duration::from_secs(5), rx.read(&mut b)).await {
+            Ok(Ok(())) => match decoder.push(b[0]) {
+                Some(Ok(frame)) if frame.kind == frame_type::CMD => {
+                    let id = frame.body().first().copied().unwrap_or(0);
+                    log::info!("LOOPBACK SUCCESS: CMD {} seq={}", PayloadCommand::name_of(id), frame.seq);
+                }
+                Some(Ok(frame)) => log::info!("LOOPBACK: frame type {:#04x} seq={}", frame.kind, frame.seq),
+                Some(Err(e)) => log::error!("LOOPBACK: bad frame ({:?})", e),
+                None => {}
+            },
             Ok(Err(e)) => log::error!("LOOPBACK RX error: {:?}", e),
             Err(_) => log::warn!("LOOPBACK: no data in 5s — is the jumper connected?"),
         }
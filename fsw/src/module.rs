@@ -2,14 +2,17 @@
 use crate::constants;
 use embassy_embedded_hal::shared_bus::asynch::i2c::I2cDevice as SharedI2cDevice;
 use embassy_embedded_hal::shared_bus::asynch::spi::SpiDevice as SharedSpiDevice;
+use embassy_rp::adc::InterruptHandler as AdcInterruptHandler;
 use embassy_rp::dma::InterruptHandler as DmaInterruptHandler;
 use embassy_rp::gpio::Output;
 use embassy_rp::i2c::{Config as I2cConfig, I2c, InterruptHandler as I2cInterruptHandler};
 use embassy_rp::peripherals::{
-    DMA_CH0, DMA_CH1, DMA_CH2, DMA_CH3, DMA_CH4, DMA_CH5, DMA_CH6, I2C0, PIN_0, PIN_1, PIN_2,
-    PIN_3, PIN_4, PIN_8, PIN_9, PIN_21, PIN_32, PIN_33, PIN_34, PIN_35, PIN_36, PIN_37, PIN_38,
-    PIN_39, PIN_40, PIN_47, PWM_SLICE2, PWM_SLICE9, PWM_SLICE8, PWM_SLICE11, SPI0, UART0, UART1, USB,
+    ADC, DMA_CH0, DMA_CH1, DMA_CH2, DMA_CH3, DMA_CH4, DMA_CH5, DMA_CH6, I2C0, PIN_0, PIN_1, PIN_2,
+    PIN_3, PIN_4, PIN_8, PIN_9, PIN_16, PIN_17, PIN_18, PIN_19, PIN_21, PIN_32, PIN_33, PIN_34, PIN_35, PIN_36, PIN_37, PIN_38,
+    PIN_39, PIN_40, PIN_42, PIN_43, PIN_47, PIO0, PWM_SLICE2, PWM_SLICE9, PWM_SLICE8, PWM_SLICE11, SPI0, UART0, UART1, USB,
 };
+use embassy_rp::pio::{InterruptHandler as PioInterruptHandler, Pio};
+use embassy_rp::pio_programs::uart::{PioUartRx, PioUartRxProgram, PioUartTx, PioUartTxProgram};
 use embassy_rp::spi::{Config as SpiConfig, Spi};
 use embassy_rp::uart::{Config as UartConfig, InterruptHandler as UartInterruptHandler, Uart};
 use embassy_rp::usb::{Driver, InterruptHandler as UsbInterruptHandler};
@@ -34,6 +37,8 @@ bind_interrupts!(pub struct Irqs {
     I2C0_IRQ => I2cInterruptHandler<I2C0>;
     UART0_IRQ => UartInterruptHandler<UART0>;
     UART1_IRQ => UartInterruptHandler<UART1>;
+    PIO0_IRQ_0 => PioInterruptHandler<PIO0>;
+    ADC_IRQ_FIFO => AdcInterruptHandler;
     DMA_IRQ_0 => DmaInterruptHandler<DMA_CH0>, DmaInterruptHandler<DMA_CH1>, DmaInterruptHandler<DMA_CH2>, DmaInterruptHandler<DMA_CH3>, DmaInterruptHandler<DMA_CH4>, DmaInterruptHandler<DMA_CH5>, DmaInterruptHandler<DMA_CH6>;
 });
 
@@ -262,7 +267,7 @@ pub fn init_blims(
     enable_pin: Peri<'static, PIN_34>,//34
     pwm_slice:  Peri<'static, PWM_SLICE9>, //9
     pwm_pin:    Peri<'static, PIN_35>,//35
-) -> blims::Blims<'static> {
+) -> blims::Blims<blims::PwmActuator<'static>> {
     use blims::blims_constants::WRAP_CYCLE_COUNT;
 
     let enable = Output::new(enable_pin, embassy_rp::gpio::Level::Low);
@@ -272,7 +277,26 @@ pub fn init_blims(
     config.top = WRAP_CYCLE_COUNT;
     config.divider = 46u8.into();
     let pwm = Pwm::new_output_b(pwm_slice, pwm_pin, config.clone());
-    blims::Blims::new(blims::Hardware { pwm, pwm_config: config, enable_pin: enable })
+    blims::Blims::new(blims::PwmActuator::new(pwm, config, enable), blims::BlimsConfig::default())
+}
+
+use crate::driver::supply::SupplyMonitor;
+use embassy_rp::adc::{Adc, Channel as AdcChannel, Config as AdcConfig};
+use embassy_rp::gpio::Pull;
+
+/// Initialize the supply rail monitor on the RP2350 ADC
+///
+/// GPIO 42 = ADC2, flight battery through SUPPLY_BATTERY_DIVIDER
+/// GPIO 43 = ADC3, pyro rail through SUPPLY_PYRO_DIVIDER
+pub fn init_supply_monitor(
+    adc: Peri<'static, ADC>,
+    battery_pin: Peri<'static, PIN_42>,
+    pyro_pin: Peri<'static, PIN_43>,
+) -> SupplyMonitor {
+    let adc = Adc::new(adc, Irqs, AdcConfig::default());
+    let battery = AdcChannel::new_pin(battery_pin, Pull::None);
+    let pyro = AdcChannel::new_pin(pyro_pin, Pull::None);
+    SupplyMonitor::new(adc, battery, pyro)
 }
 
 /// Initialize onboard SPI flash for packet storage
@@ -285,3 +309,84 @@ pub fn init_onboard_flash(
     let spi_device = SharedSpiDevice::new(spi_bus, cs);
     crate::driver::onboard_flash::OnboardFlash::new(spi_device)
 }
+
+use crate::driver::odrive::{OdriveError, OdriveLink};
+
+/// One ODrive UART link on a pair of PIO0 state machines. Both hardware
+/// UARTs are taken (payload, radio), so the ODrives get PIO UARTs.
+pub struct PioOdriveLink<const TX: usize, const RX: usize> {
+    tx: PioUartTx<'static, PIO0, TX>,
+    rx: PioUartRx<'static, PIO0, RX>,
+}
+
+impl<const TX: usize, const RX: usize> OdriveLink for PioOdriveLink<TX, RX> {
+    async fn write(&mut self, data: &[u8]) -> Result<(), OdriveError> {
+        for &b in data {
+            self.tx.write_u8(b).await;
+        }
+        Ok(())
+    }
+
+    async fn read_byte(&mut self) -> Result<u8, OdriveError> {
+        Ok(self.rx.read_u8().await)
+    }
+}
+
+pub type AirbrakeOdriveLink = PioOdriveLink<0, 1>;
+pub type BlimsOdriveLink = PioOdriveLink<2, 3>;
+
+/// Initialize the ODrive UART links on PIO0
+///
+/// GPIO 16/17 = airbrake ODrive UART A TX/RX (SM0/SM1)
+/// GPIO 18/19 = BLiMS ODrive UART A TX/RX (SM2/SM3)
+pub fn init_odrive_links(
+    pio0: Peri<'static, PIO0>,
+    airbrake_tx: Peri<'static, PIN_16>,
+    airbrake_rx: Peri<'static, PIN_17>,
+    blims_tx: Peri<'static, PIN_18>,
+    blims_rx: Peri<'static, PIN_19>,
+) -> (AirbrakeOdriveLink, BlimsOdriveLink) {
+    let Pio { mut common, sm0, sm1, sm2, sm3, .. } = Pio::new(pio0, Irqs);
+    let tx_program = PioUartTxProgram::new(&mut common);
+    let rx_program = PioUartRxProgram::new(&mut common);
+    let baud = constants::ODRIVE_UART_BAUD;
+
+    let airbrake = PioOdriveLink {
+        tx: PioUartTx::new(baud, &mut common, sm0, airbrake_tx, &tx_program),
+        rx: PioUartRx::new(baud, &mut common, sm1, airbrake_rx, &rx_program),
+    };
+    let blims = PioOdriveLink {
+        tx: PioUartTx::new(baud, &mut common, sm2, blims_tx, &tx_program),
+        rx: PioUartRx::new(baud, &mut common, sm3, blims_rx, &rx_program),
+    };
+    (airbrake, blims)
+}
+
+use crate::driver::payload::{PayloadError, PayloadLink};
+use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+use embassy_sync::pipe::Pipe;
+
+/// Bytes received from the payload board, filled by `payload_rx_task` in
+/// `main.rs` so the flight loop never waits on the UART itself.
+pub static PAYLOAD_RX: Pipe<CriticalSectionRawMutex, 256> = Pipe::new();
+
+/// Payload board link on UART0 (GPIO 32/33)
+pub struct PayloadUartLink {
+    tx: uart::UartTx<'static, uart::Async>,
+}
+
+impl PayloadUartLink {
+    pub fn new(tx: uart::UartTx<'static, uart::Async>) -> Self {
+        Self { tx }
+    }
+}
+
+impl PayloadLink for PayloadUartLink {
+    async fn write(&mut self, data: &[u8]) -> Result<(), PayloadError> {
+        self.tx.write(data).await.map_err(|_| PayloadError::Uart)
+    }
+
+    fn try_read(&mut self, buf: &mut [u8]) -> usize {
+        PAYLOAD_RX.try_read(buf).unwrap_or(0)
+    }
+}
@@ -0,0 +1,92 @@
+//! ODrive UART exchanges, one task per ODrive on Core 0.
+//!
+//! An exchange is up to three commands and two replies, each reply with its
+//! own timeout, and a dead link also pays for a drain. Running them here
+//! instead of inline keeps that off the 50 ms flight loop: the loop publishes
+//! each cycle's setpoint through [`AIRBRAKE_ODRIVE_SETPOINT`] /
+//! [`BLIMS_ODRIVE_SETPOINT`] and reads back whatever the last exchange
+//! finished with ([`get_airbrake_readback`], [`get_blims_readback`]). A slow
+//! exchange only means the next setpoint replaces the one it missed.
+
+use core::cell::Cell;
+
+use embassy_sync::blocking_mutex::Mutex;
+use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+use embassy_sync::signal::Signal;
+
+use crate::driver::odrive::{Odrive, OdriveLink, OdriveStatus};
+use crate::module::{AirbrakeOdriveLink, BlimsOdriveLink};
+
+/// RC pulse width (µs) the airbrake PWM path is outputting this cycle
+pub static AIRBRAKE_ODRIVE_SETPOINT: Signal<CriticalSectionRawMutex, f32> = Signal::new();
+/// RC pulse width (µs) the BLiMS PWM path is outputting this cycle
+pub static BLIMS_ODRIVE_SETPOINT: Signal<CriticalSectionRawMutex, f32> = Signal::new();
+
+/// One ODrive's state after its latest exchange.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OdriveReadback {
+    /// True while the UART link owns the setpoint; false means RC PWM does
+    pub link_up: bool,
+    pub status: OdriveStatus,
+    /// Encoder position as the RC pulse (µs) that maps to it; None until
+    /// the first link-up has read the ODrive's PWM mapping
+    pub position_pulse_us: Option<f32>,
+}
+
+type Readback = Mutex<CriticalSectionRawMutex, Cell<OdriveReadback>>;
+
+/// Before the first exchange: link down, nothing read
+const NO_READBACK: OdriveReadback = OdriveReadback {
+    link_up: false,
+    status: OdriveStatus {
+        pos_turns: 0.0,
+        vel_turns_s: 0.0,
+        vbus_v: 0.0,
+        ibus_a: 0.0,
+        axis_state: 0,
+        active_errors: 0,
+        disarm_reason: 0,
+    },
+    position_pulse_us: None,
+};
+
+static AIRBRAKE_READBACK: Readback = Mutex::new(Cell::new(NO_READBACK));
+static BLIMS_READBACK: Readback = Mutex::new(Cell::new(NO_READBACK));
+
+pub fn get_airbrake_readback() -> OdriveReadback {
+    AIRBRAKE_READBACK.lock(|r| r.get())
+}
+
+pub fn get_blims_readback() -> OdriveReadback {
+    BLIMS_READBACK.lock(|r| r.get())
+}
+
+#[embassy_executor::task]
+pub async fn airbrake_odrive_task(odrive: Odrive<AirbrakeOdriveLink>) {
+    run(odrive, &AIRBRAKE_ODRIVE_SETPOINT, &AIRBRAKE_READBACK).await
+}
+
+#[embassy_executor::task]
+pub async fn blims_odrive_task(odrive: Odrive<BlimsOdriveLink>) {
+    run(odrive, &BLIMS_ODRIVE_SETPOINT, &BLIMS_READBACK).await
+}
+
+/// One exchange per published setpoint, so the ODrive sees the flight
+/// loop's rate at most.
+async fn run<L: OdriveLink>(
+    mut odrive: Odrive<L>,
+    setpoint: &Signal<CriticalSectionRawMutex, f32>,
+    readback: &Readback,
+) -> ! {
+    loop {
+        let pulse_us = setpoint.wait().await;
+        odrive.update(pulse_us).await;
+        readback.lock(|r| {
+            r.set(OdriveReadback {
+                link_up: odrive.link_up(),
+                status: odrive.status,
+                position_pulse_us: odrive.position_pulse_us(),
+            })
+        });
+    }
+}
@@ -80,10 +80,48 @@ pub struct Packet {
     pub blims_wind_from_deg: f32,
     // monotonic clock: milliseconds since CFC boot (resets to 0 on reboot)
     pub ms_since_boot_cfc: u32,
+    // absolute time: UTC Unix epoch ms from GPS time sync (0 = not yet synced)
+    pub utc_ms: u64,
+    // ODrive UART readback (driver/odrive.rs); holds the last good reading
+    // while the link bit in odrive_link is clear
+    pub airbrake_measured_deployment: f32, // from encoder, same scale as airbrake_deployment
+    pub airbrake_odrive_state: u8,         // axis0.current_state (8 = closed loop)
+    pub airbrake_odrive_errors: u32,       // axis0.active_errors bitmask
+    pub airbrake_odrive_vbus: f32,         // V
+    pub airbrake_odrive_ibus: f32,         // A
+    pub blims_measured_brakeline: f32,     // from encoder, inches (same scale as blims_brakeline_diff)
+    pub blims_odrive_state: u8,
+    pub blims_odrive_errors: u32,
+    pub blims_odrive_vbus: f32,
+    pub blims_odrive_ibus: f32,
+    // bit 0 = airbrake ODrive on UART, bit 1 = BLiMS ODrive on UART (clear = RC PWM fallback)
+    pub odrive_link: u8,
+    // 1 = Core 1 airbrake controller output fresh, 0 = stale (airbrakes held retracted)
+    pub airbrake_ctrl_ok: u8,
+    // Core 1 in-flight drag estimate (the configured areas until Coast)
+    pub airbrake_est_body_cda: f32,  // m²
+    pub airbrake_est_brake_cda: f32, // m²
+    // BLiMS in-flight wind estimate (0 until the canopy has turned enough)
+    pub blims_wind_est_speed: f32,    // m/s
+    pub blims_wind_est_from_deg: f32, // degrees FROM
+    // BLiMS great-circle distance to the active waypoint (held through GPS dropouts)
+    pub blims_dist_to_target: f32,    // m
+    // BLiMS geofence: geofence::FENCE_* bits this cycle
+    pub blims_fence_flags: u8,
+    // Payload board link (driver/payload.rs): payload::LinkState, id of the
+    // last command it acknowledged (0 = none), its reported state, and
+    // commands given up on after PAYLOAD_MAX_TRIES
+    pub payload_link: u8,
+    pub payload_last_ack: u8,
+    pub payload_state: u8,
+    pub payload_failed: u8,
+    // Supply rails (driver/supply.rs), V: flight battery and pyro rail
+    pub battery_v: f32,
+    pub pyro_v: f32,
 }
 
 impl Packet {
-    pub const SIZE: usize = 193;
+    pub const SIZE: usize = 270;
 
     pub fn to_bytes(&self) -> [u8; Self::SIZE] {
         let mut data = [0u8; Self::SIZE];
@@ -141,6 +179,31 @@ impl Packet {
         data[181..185].copy_from_slice(&self.blims_downwind_lon.to_le_bytes());
         data[185..189].copy_from_slice(&self.blims_wind_from_deg.to_le_bytes());
         data[189..193].copy_from_slice(&self.ms_since_boot_cfc.to_le_bytes());
+        data[193..201].copy_from_slice(&self.utc_ms.to_le_bytes());
+        data[201..205].copy_from_slice(&self.airbrake_measured_deployment.to_le_bytes());
+        data[205] = self.airbrake_odrive_state;
+        data[206..210].copy_from_slice(&self.airbrake_odrive_errors.to_le_bytes());
+        data[210..214].copy_from_slice(&self.airbrake_odrive_vbus.to_le_bytes());
+        data[214..218].copy_from_slice(&self.airbrake_odrive_ibus.to_le_bytes());
+        data[218..222].copy_from_slice(&self.blims_measured_brakeline.to_le_bytes());
+        data[222] = self.blims_odrive_state;
+        data[223..227].copy_from_slice(&self.blims_odrive_errors.to_le_bytes());
+        data[227..231].copy_from_slice(&self.blims_odrive_vbus.to_le_bytes());
+        data[231..235].copy_from_slice(&self.blims_odrive_ibus.to_le_bytes());
+        data[235] = self.odrive_link;
+        data[236] = self.airbrake_ctrl_ok;
+        data[237..241].copy_from_slice(&self.airbrake_est_body_cda.to_le_bytes());
+        data[241..245].copy_from_slice(&self.airbrake_est_brake_cda.to_le_bytes());
+        data[245..249].copy_from_slice(&self.blims_wind_est_speed.to_le_bytes());
+        data[249..253].copy_from_slice(&self.blims_wind_est_from_deg.to_le_bytes());
+        data[253..257].copy_from_slice(&self.blims_dist_to_target.to_le_bytes());
+        data[257] = self.blims_fence_flags;
+        data[258] = self.payload_link;
+        data[259] = self.payload_last_ack;
+        data[260] = self.payload_state;
+        data[261] = self.payload_failed;
+        data[262..266].copy_from_slice(&self.battery_v.to_le_bytes());
+        data[266..270].copy_from_slice(&self.pyro_v.to_le_bytes());
         data
     }
 
@@ -204,17 +267,42 @@ impl Packet {
             blims_downwind_lon:     f32::from_le_bytes(bytes[181..185].try_into().unwrap()),
             blims_wind_from_deg:    f32::from_le_bytes(bytes[185..189].try_into().unwrap()),
             ms_since_boot_cfc:      u32::from_le_bytes(bytes[189..193].try_into().unwrap()),
+            utc_ms:                 u64::from_le_bytes(bytes[193..201].try_into().unwrap()),
+            airbrake_measured_deployment: f32::from_le_bytes(bytes[201..205].try_into().unwrap()),
+            airbrake_odrive_state:        bytes[205],
+            airbrake_odrive_errors:       u32::from_le_bytes(bytes[206..210].try_into().unwrap()),
+            airbrake_odrive_vbus:         f32::from_le_bytes(bytes[210..214].try_into().unwrap()),
+            airbrake_odrive_ibus:         f32::from_le_bytes(bytes[214..218].try_into().unwrap()),
+            blims_measured_brakeline:     f32::from_le_bytes(bytes[218..222].try_into().unwrap()),
+            blims_odrive_state:           bytes[222],
+            blims_odrive_errors:          u32::from_le_bytes(bytes[223..227].try_into().unwrap()),
+            blims_odrive_vbus:            f32::from_le_bytes(bytes[227..231].try_into().unwrap()),
+            blims_odrive_ibus:            f32::from_le_bytes(bytes[231..235].try_into().unwrap()),
+            odrive_link:                  bytes[235],
+            airbrake_ctrl_ok:             bytes[236],
+            airbrake_est_body_cda:        f32::from_le_bytes(bytes[237..241].try_into().unwrap()),
+            airbrake_est_brake_cda:       f32::from_le_bytes(bytes[241..245].try_into().unwrap()),
+            blims_wind_est_speed:         f32::from_le_bytes(bytes[245..249].try_into().unwrap()),
+            blims_wind_est_from_deg:      f32::from_le_bytes(bytes[249..253].try_into().unwrap()),
+            blims_dist_to_target:         f32::from_le_bytes(bytes[253..257].try_into().unwrap()),
+            blims_fence_flags:            bytes[257],
+            payload_link:                 bytes[258],
+            payload_last_ack:             bytes[259],
+            payload_state:                bytes[260],
+            payload_failed:               bytes[261],
+            battery_v:                    f32::from_le_bytes(bytes[262..266].try_into().unwrap()),
+            pyro_v:                       f32::from_le_bytes(bytes[266..270].try_into().unwrap()),
         }
     }
 
-    pub const CSV_HEADER: &'static str = "flight_mode,pressure,temp,altitude,latitude,longitude,num_satellites,timestamp,mag_x,mag_y,mag_z,accel_x,accel_y,accel_z,gyro_x,gyro_y,gyro_z,pt3,pt4,rtd,sv_open,mav_open,ssa_drogue_deployed,ssa_main_deployed,cmd_n1,cmd_n2,cmd_n3,cmd_n4,cmd_a1,cmd_a2,cmd_a3,airbrake_deployment,predicted_apogee,h_acc,v_acc,vel_n,vel_e,vel_d,g_speed,s_acc,head_acc,fix_type,head_mot,blims_brakeline_diff,blims_phase_id,blims_pid_p,blims_pid_i,blims_bearing,blims_upwind_lat,blims_upwind_lon,blims_downwind_lat,blims_downwind_lon,blims_wind_from_deg,ms_since_boot_cfc\n";
+    pub const CSV_HEADER: &'static str = "flight_mode,pressure,temp,altitude,latitude,longitude,num_satellites,timestamp,mag_x,mag_y,mag_z,accel_x,accel_y,accel_z,gyro_x,gyro_y,gyro_z,pt3,pt4,rtd,sv_open,mav_open,ssa_drogue_deployed,ssa_main_deployed,cmd_n1,cmd_n2,cmd_n3,cmd_n4,cmd_a1,cmd_a2,cmd_a3,airbrake_deployment,predicted_apogee,h_acc,v_acc,vel_n,vel_e,vel_d,g_speed,s_acc,head_acc,fix_type,head_mot,blims_brakeline_diff,blims_phase_id,blims_pid_p,blims_pid_i,blims_bearing,blims_upwind_lat,blims_upwind_lon,blims_downwind_lat,blims_downwind_lon,blims_wind_from_deg,ms_since_boot_cfc,utc_ms,airbrake_measured_deployment,airbrake_odrive_state,airbrake_odrive_errors,airbrake_odrive_vbus,airbrake_odrive_ibus,blims_measured_brakeline,blims_odrive_state,blims_odrive_errors,blims_odrive_vbus,blims_odrive_ibus,odrive_link,airbrake_ctrl_ok,airbrake_est_body_cda,airbrake_est_brake_cda,blims_wind_est_speed,blims_wind_est_from_deg,blims_dist_to_target,blims_fence_flags,payload_link,payload_last_ack,payload_state,payload_failed,battery_v,pyro_v\n";
 
     pub fn to_csv(&self, buf: &mut [u8]) -> usize {
         use core::fmt::Write;
         let mut wrapper = WriteWrapper::new(buf);
-        let _ = write!(
+        let _ = writeln!(
             wrapper,
-            "{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{}\n",
+            "{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{}",
             self.flight_mode,
             self.pressure,
             self.temp,
@@ -269,6 +357,31 @@ impl Packet {
             self.blims_downwind_lon,
             self.blims_wind_from_deg,
             self.ms_since_boot_cfc,
+            self.utc_ms,
+            self.airbrake_measured_deployment,
+            self.airbrake_odrive_state,
+            self.airbrake_odrive_errors,
+            self.airbrake_odrive_vbus,
+            self.airbrake_odrive_ibus,
+            self.blims_measured_brakeline,
+            self.blims_odrive_state,
+            self.blims_odrive_errors,
+            self.blims_odrive_vbus,
+            self.blims_odrive_ibus,
+            self.odrive_link,
+            self.airbrake_ctrl_ok,
+            self.airbrake_est_body_cda,
+            self.airbrake_est_brake_cda,
+            self.blims_wind_est_speed,
+            self.blims_wind_est_from_deg,
+            self.blims_dist_to_target,
+            self.blims_fence_flags,
+            self.payload_link,
+            self.payload_last_ack,
+            self.payload_state,
+            self.payload_failed,
+            self.battery_v,
+            self.pyro_v,
         );
         wrapper.offset
     }
@@ -277,11 +390,51 @@ impl Packet {
 /// Tag byte written before each binary record in the data-log region.
 pub const FAST_RECORD_TAG: u8 = 0xFA;
 pub const FULL_RECORD_TAG: u8 = 0xFB;
+pub const SESSION_RECORD_TAG: u8 = 0xFC;
+pub const BLIMS_DIAG_RECORD_TAG: u8 = 0xFD;
+pub const CONFIG_RECORD_TAG: u8 = 0xFE;
+
+/// Session header, written once per boot when flash logging starts (kind 0)
+/// and again whenever GPS time sync is acquired or changes source (kind 1).
+/// Pairs a boot-relative timestamp with absolute UTC so the decoder can put
+/// every record of the session — including those logged before the GPS had
+/// a fix — on an absolute time axis.
+#[derive(Default)]
+pub struct SessionRecord {
+    pub kind: u8,
+    /// 0 = none, 1 = NAV-PVT, 2 = TIMEPULSE (see `time_sync::TimeSource`)
+    pub time_source: u8,
+    pub ms_since_boot_cfc: u32,
+    /// UTC epoch ms at `ms_since_boot_cfc` (0 = not yet synced)
+    pub utc_ms: u64,
+    pub t_acc_ns: u32,
+    pub flight_mode: u32,
+}
+
+impl SessionRecord {
+    pub const KIND_BOOT: u8 = 0;
+    pub const KIND_TIME_SYNC: u8 = 1;
+
+    /// Byte length of the serialised payload (tag byte not included).
+    pub const SIZE: usize = 22;
+
+    pub fn to_bytes(&self) -> [u8; Self::SIZE] {
+        let mut d = [0u8; Self::SIZE];
+        d[0] = self.kind;
+        d[1] = self.time_source;
+        d[2..6].copy_from_slice(&self.ms_since_boot_cfc.to_le_bytes());
+        d[6..14].copy_from_slice(&self.utc_ms.to_le_bytes());
+        d[14..18].copy_from_slice(&self.t_acc_ns.to_le_bytes());
+        d[18..22].copy_from_slice(&self.flight_mode.to_le_bytes());
+        d
+    }
+}
 
 /// High-rate record written at 20 Hz. Contains sensors that update at ≥20 Hz
 /// (IMU, baro, ADC, valves, events, airbrakes) plus BLiMS control outputs
-/// which change each guidance cycle. GPS config fields update at ≤1 Hz and
-/// are covered by the full record.
+/// which change each guidance cycle, and the measured actuator positions so
+/// command-vs-response can be compared at full rate. GPS config fields and
+/// ODrive health update at ≤1 Hz and are covered by the full record.
 #[derive(Default)]
 pub struct FastRecord {
     pub ms_since_boot_cfc: u32,
@@ -317,11 +470,16 @@ pub struct FastRecord {
     // BLiMS control outputs (change every guidance cycle)
     pub blims_brakeline_diff: f32,
     pub blims_phase_id: i8,
+    // ODrive encoder readback
+    pub airbrake_measured_deployment: f32,
+    pub blims_measured_brakeline: f32,
+    pub odrive_link: u8,
+    pub airbrake_ctrl_ok: u8,
 }
 
 impl FastRecord {
     /// Byte length of the serialised payload (tag byte not included).
-    pub const SIZE: usize = 92;
+    pub const SIZE: usize = 102;
 
     pub fn from_packet(p: &Packet) -> Self {
         Self {
@@ -357,6 +515,10 @@ impl FastRecord {
             predicted_apogee: p.predicted_apogee,
             blims_brakeline_diff: p.blims_brakeline_diff,
             blims_phase_id: p.blims_phase_id,
+            airbrake_measured_deployment: p.airbrake_measured_deployment,
+            blims_measured_brakeline: p.blims_measured_brakeline,
+            odrive_link: p.odrive_link,
+            airbrake_ctrl_ok: p.airbrake_ctrl_ok,
         }
     }
 
@@ -394,10 +556,186 @@ impl FastRecord {
         d[83..87].copy_from_slice(&self.predicted_apogee.to_le_bytes());
         d[87..91].copy_from_slice(&self.blims_brakeline_diff.to_le_bytes());
         d[91] = self.blims_phase_id as u8;
+        d[92..96].copy_from_slice(&self.airbrake_measured_deployment.to_le_bytes());
+        d[96..100].copy_from_slice(&self.blims_measured_brakeline.to_le_bytes());
+        d[100] = self.odrive_link;
+        d[101] = self.airbrake_ctrl_ok;
+        d
+    }
+}
+
+/// BLiMS controller internals, written every guidance cycle (20 Hz) while
+/// BLiMS is armed, for tuning after a drop: what the PI loop was asked to
+/// steer, what it saw, and the state behind `pid_p`/`pid_i`, which the fast
+/// and full records only carry the output of.
+#[derive(Default)]
+pub struct BlimsDiagRecord {
+    pub ms_since_boot_cfc: u32,
+    pub phase_id: i8,
+    /// `gps_state && fix_type >= 2` as BLiMS decided it
+    pub gps_valid: bool,
+    pub fix_type: u8,
+    pub heading_valid: bool,
+    pub desired_heading: f32,
+    pub heading: f32,
+    pub heading_error: f32,
+    pub pid_p: f32,
+    pub pid_i: f32,
+    /// Clamped heading-error integral (deg·s)
+    pub error_integral: f32,
+    /// Controller step (s), after clamping
+    pub dt: f32,
+    pub brakeline_diff: f32,
+    /// Boot ms at which control first left Held (0 = not yet)
+    pub activation_ms: u32,
+}
+
+impl BlimsDiagRecord {
+    /// Byte length of the serialised payload (tag byte not included).
+    pub const SIZE: usize = 44;
+
+    pub fn to_bytes(&self) -> [u8; Self::SIZE] {
+        let mut d = [0u8; Self::SIZE];
+        d[0..4].copy_from_slice(&self.ms_since_boot_cfc.to_le_bytes());
+        d[4] = self.phase_id as u8;
+        d[5] = self.gps_valid as u8;
+        d[6] = self.fix_type;
+        d[7] = self.heading_valid as u8;
+        d[8..12].copy_from_slice(&self.desired_heading.to_le_bytes());
+        d[12..16].copy_from_slice(&self.heading.to_le_bytes());
+        d[16..20].copy_from_slice(&self.heading_error.to_le_bytes());
+        d[20..24].copy_from_slice(&self.pid_p.to_le_bytes());
+        d[24..28].copy_from_slice(&self.pid_i.to_le_bytes());
+        d[28..32].copy_from_slice(&self.error_integral.to_le_bytes());
+        d[32..36].copy_from_slice(&self.dt.to_le_bytes());
+        d[36..40].copy_from_slice(&self.brakeline_diff.to_le_bytes());
+        d[40..44].copy_from_slice(&self.activation_ms.to_le_bytes());
         d
     }
 }
 
+/// Recovery beacon, sent over the radio in place of the full packet once
+/// the FSW is in beacon mode (`MAIN_LOG_TIMEOUT_MS` after main deploy):
+/// just what a recovery team needs to walk to the rocket.
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub struct BeaconPacket {
+    pub ms_since_boot_cfc: u32,
+    pub flight_mode: u8,
+    pub num_satellites: u8,
+    /// `state::health` bits
+    pub health: u16,
+    /// Last 3D fix (0, 0 if there has never been one)
+    pub latitude: f32,
+    pub longitude: f32,
+    /// Seconds since the last 3D fix (`u32::MAX` = never)
+    pub fix_age_s: u32,
+    /// NaN when not measured
+    pub battery_v: f32,
+    pub apogee_ft: u32,
+}
+
+impl BeaconPacket {
+    /// Byte length of the serialised payload (sync word not included).
+    pub const SIZE: usize = 28;
+    pub const NO_FIX: u32 = u32::MAX;
+
+    pub fn to_bytes(&self) -> [u8; Self::SIZE] {
+        let mut d = [0u8; Self::SIZE];
+        d[0..4].copy_from_slice(&self.ms_since_boot_cfc.to_le_bytes());
+        d[4] = self.flight_mode;
+        d[5] = self.num_satellites;
+        d[6..8].copy_from_slice(&self.health.to_le_bytes());
+        d[8..12].copy_from_slice(&self.latitude.to_le_bytes());
+        d[12..16].copy_from_slice(&self.longitude.to_le_bytes());
+        d[16..20].copy_from_slice(&self.fix_age_s.to_le_bytes());
+        d[20..24].copy_from_slice(&self.battery_v.to_le_bytes());
+        d[24..28].copy_from_slice(&self.apogee_ft.to_le_bytes());
+        d
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        if bytes.len() < Self::SIZE {
+            return Self::default();
+        }
+
+        Self {
+            ms_since_boot_cfc: u32::from_le_bytes(bytes[0..4].try_into().unwrap()),
+            flight_mode: bytes[4],
+            num_satellites: bytes[5],
+            health: u16::from_le_bytes(bytes[6..8].try_into().unwrap()),
+            latitude: f32::from_le_bytes(bytes[8..12].try_into().unwrap()),
+            longitude: f32::from_le_bytes(bytes[12..16].try_into().unwrap()),
+            fix_age_s: u32::from_le_bytes(bytes[16..20].try_into().unwrap()),
+            battery_v: f32::from_le_bytes(bytes[20..24].try_into().unwrap()),
+            apogee_ft: u32::from_le_bytes(bytes[24..28].try_into().unwrap()),
+        }
+    }
+}
+
+/// Pre-flight configuration the FSW is flying with. It only changes on the
+/// pad, so it goes out over the radio every `CONFIG_PACKET_PERIOD_MS`
+/// instead of in every full packet, and to flash whenever it changes.
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub struct ConfigPacket {
+    pub ms_since_boot_cfc: u32,
+    // Airbrake controller config (<AK,...>)
+    pub airbrake_target_apogee: f32, // m AGL
+    pub airbrake_max_rate: f32,      // deployment fraction per second
+    pub airbrake_min_vel: f32,       // m/s upward before the controller acts
+    pub airbrake_body_cda: f32,      // m², airbrakes retracted
+    pub airbrake_brake_cda: f32,     // m² added at full deployment
+    // BLiMS forecast wind profile (<WP,...>, 0 layers = none)
+    pub blims_wind_layers: u8,
+    pub blims_wind_checksum: u32, // WindProfile::checksum
+    // BLiMS geofence (<GF,...>, 0 zones = none)
+    pub blims_fence_zones: u8,
+    pub blims_fence_checksum: u32, // Geofence::checksum
+}
+
+impl ConfigPacket {
+    /// Byte length of the serialised payload (sync word / tag not included).
+    pub const SIZE: usize = 34;
+
+    pub fn to_bytes(&self) -> [u8; Self::SIZE] {
+        let mut d = [0u8; Self::SIZE];
+        d[0..4].copy_from_slice(&self.ms_since_boot_cfc.to_le_bytes());
+        d[4..8].copy_from_slice(&self.airbrake_target_apogee.to_le_bytes());
+        d[8..12].copy_from_slice(&self.airbrake_max_rate.to_le_bytes());
+        d[12..16].copy_from_slice(&self.airbrake_min_vel.to_le_bytes());
+        d[16..20].copy_from_slice(&self.airbrake_body_cda.to_le_bytes());
+        d[20..24].copy_from_slice(&self.airbrake_brake_cda.to_le_bytes());
+        d[24] = self.blims_wind_layers;
+        d[25..29].copy_from_slice(&self.blims_wind_checksum.to_le_bytes());
+        d[29] = self.blims_fence_zones;
+        d[30..34].copy_from_slice(&self.blims_fence_checksum.to_le_bytes());
+        d
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        if bytes.len() < Self::SIZE {
+            return Self::default();
+        }
+
+        Self {
+            ms_since_boot_cfc: u32::from_le_bytes(bytes[0..4].try_into().unwrap()),
+            airbrake_target_apogee: f32::from_le_bytes(bytes[4..8].try_into().unwrap()),
+            airbrake_max_rate: f32::from_le_bytes(bytes[8..12].try_into().unwrap()),
+            airbrake_min_vel: f32::from_le_bytes(bytes[12..16].try_into().unwrap()),
+            airbrake_body_cda: f32::from_le_bytes(bytes[16..20].try_into().unwrap()),
+            airbrake_brake_cda: f32::from_le_bytes(bytes[20..24].try_into().unwrap()),
+            blims_wind_layers: bytes[24],
+            blims_wind_checksum: u32::from_le_bytes(bytes[25..29].try_into().unwrap()),
+            blims_fence_zones: bytes[29],
+            blims_fence_checksum: u32::from_le_bytes(bytes[30..34].try_into().unwrap()),
+        }
+    }
+
+    /// Same configuration, whenever it was stamped
+    pub fn same_config(&self, other: &Self) -> bool {
+        Self { ms_since_boot_cfc: 0, ..*self } == Self { ms_since_boot_cfc: 0, ..*other }
+    }
+}
+
 struct WriteWrapper<'a> {
     buf: &'a mut [u8],
     offset: usize,
@@ -1,7 +1,9 @@
 use crate::constants;
 use crate::module::*;
 
-use crate::packet::{Packet, FastRecord};
+use crate::packet::{Packet, BeaconPacket, BlimsDiagRecord, ConfigPacket, FastRecord, SessionRecord};
+use crate::odrive_task;
+use crate::time_sync::TimeSync;
 
 use crate::driver::bmp390::Bmp390Sensor;
 use crate::driver::lsm6dsox::Lsm6dsoxSensor;
@@ -9,14 +11,18 @@ use crate::driver::rfd900x::Rfd900x;
 use crate::driver::ublox_max_m10s::{UbloxMaxM10s, GpsError};
 use crate::driver::ads1015::Ads1015Sensor;
 use crate::driver::onboard_flash::OnboardFlash;
+use crate::driver::payload::{LinkState, Payload};
+use crate::driver::supply::SupplyMonitor;
 
 use blims::blims_state::BlimsDataIn;
+use blims::{BlimsConfig, Geofence, LatLon, WindProfile};
+use controller_in_rust_v3::Config as AirbrakeConfig;
 
 use embassy_rp::gpio::{Input, Output};
-use embassy_rp::uart::{Async, Uart, UartTx};
+use embassy_rp::uart::{Async, Uart};
 use embassy_time::{Duration, Instant, with_timeout};
 
-use crate::actuator::{Ssa, Buzzer, Mav, SV, Chute, AirbrakeActuator};
+use crate::actuator::{Ssa, Buzzer, BuzzPattern, Mav, SV, Chute, AirbrakeActuator};
 
 #[repr(u32)]
 #[derive(Copy, Clone, Debug, PartialEq)]
@@ -26,6 +32,36 @@ pub enum SensorState {
     INVALID = 2,
 }
 
+/// Health bitfield from `FlightState::health`: a set bit is a failed
+/// subsystem. The buzzer fault code sounds each set bit's letter in `CODES`
+/// order.
+pub mod health {
+    pub const ALTIMETER: u16 = 1 << 0;
+    pub const IMU: u16 = 1 << 1;
+    pub const GPS: u16 = 1 << 2;
+    pub const FLASH: u16 = 1 << 3;
+    /// Data-log region exhausted (the snapshot ring still works)
+    pub const STORAGE_FULL: u16 = 1 << 4;
+    /// Payload link lost after having been up
+    pub const PAYLOAD: u16 = 1 << 5;
+    /// Board came up in Fault from the snapshot ring (set by `FlightLoop`)
+    pub const RECOVERED_FAULT: u16 = 1 << 6;
+    /// Battery or pyro rail below its arming minimum, or not measured
+    pub const LOW_VOLTAGE: u16 = 1 << 7;
+
+    /// Morse letter per bit: A I G F S P V R
+    pub const CODES: [(u16, &str); 8] = [
+        (ALTIMETER, ".-"),
+        (IMU, ".."),
+        (GPS, "--."),
+        (FLASH, "..-."),
+        (STORAGE_FULL, "..."),
+        (PAYLOAD, ".--."),
+        (LOW_VOLTAGE, "...-"),
+        (RECOVERED_FAULT, ".-."),
+    ];
+}
+
 #[repr(u32)]
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub enum FlightMode {
@@ -55,6 +91,11 @@ impl FlightMode {
 pub struct FlightState {
     // packet
     pub packet: Packet,
+    // pre-flight config, sent at 1 Hz rather than in every packet
+    pub config: ConfigPacket,
+    last_config_tx: Option<Instant>,
+    // Config in the last flash config record (None = none since boot or wipe)
+    logged_config: Option<ConfigPacket>,
     // state variables
     pub flight_mode: FlightMode,
     pub cycle_count: u32,
@@ -74,6 +115,10 @@ pub struct FlightState {
     gps: UbloxMaxM10s<'static, I2cDevice<'static>>,
     gps_ok: bool,
     gps_fail_count: u8,
+    /// Latest fix at full resolution, (lat, lon) degrees × 1e7, for BLiMS
+    gps_position_e7: (i32, i32),
+    /// GPS time of week of that fix (ms), so BLiMS can tell solutions apart
+    gps_itow: u32,
     gps_probe_count: u8,
 
     // imu
@@ -91,6 +136,12 @@ pub struct FlightState {
     // adc
     adc: Ads1015Sensor,
 
+    // supply rails
+    supply: SupplyMonitor,
+    /// Set when a rail drops below its minimum, cleared once both are
+    /// `SUPPLY_HYSTERESIS_V` above it again
+    supply_low: bool,
+
     // actuators
     arming_switch: Input<'static>,
     cfc_arm: Input<'static>,
@@ -102,9 +153,11 @@ pub struct FlightState {
     pub mav: Mav<'static>,
     pub sv: SV<'static>,
     pub airbrake_system: AirbrakeActuator<'static>,
+    // Controller config in use on Core 1 (persisted in flash, echoed in telemetry)
+    pub airbrake_config: AirbrakeConfig,
 
     // BLiMS parafoil guidance system
-    pub blims: Option<blims::Blims<'static>>,
+    pub blims: Option<blims::Blims<blims::PwmActuator<'static>>>,
     pub blims_armed: bool,
     // Upwind waypoint: steer here immediately after main deploy (>1000 ft AGL)
     pub blims_upwind_lat: f32,
@@ -113,11 +166,34 @@ pub struct FlightState {
     pub blims_downwind_lat: f32,
     pub blims_downwind_lon: f32,
     pub blims_wind_from_deg: f32,
+    // Forecast profile uploaded on the pad with <WP,...> (persisted in flash)
+    pub blims_wind_profile: WindProfile,
+    // Keep-out zones and landing zone uploaded on the pad with <GF,...>
+    // (persisted in flash)
+    pub blims_geofence: Geofence,
+    // Gains and phase altitudes set on the pad with <BK,...> (persisted in
+    // flash)
+    pub blims_config: BlimsConfig,
     blims_in_downwind_phase: bool,
+    blims_wind_profile_logged: bool,
+    blims_heading_valid: bool,
+    blims_fence_flags: u8,       // last cycle's, for logging changes
+    blims_landing_logged: bool,  // landing zone check made at Neutral
     blims_targets_dirty: bool, // force immediate snapshot when targets change
+    // This guidance cycle's controller internals, for the next flash write
+    blims_diag: Option<BlimsDiagRecord>,
 
     // telemetry
     radio: Rfd900x<'static>,
+    // Recovery beacon: only the GPS is read and only BeaconPackets are sent
+    beacon_mode: bool,
+    // Last 3D fix (lat, lon, when) for the beacon; kept through GPS dropouts
+    last_fix: Option<(f32, f32, Instant)>,
+
+    // GPS time sync: boot ms -> UTC mapping
+    pub time_sync: TimeSync,
+    // Session header waiting to be written ahead of the next flash record
+    session_pending: Option<u8>,
 
     // QSPI Flash
     flash: OnboardFlash<'static>,
@@ -130,13 +206,15 @@ pub struct FlightState {
     pub snap_launch_elapsed_ms: u32,
 
     // External Comms
-    pub payload_uart: UartTx<'static, Async>,
+    pub payload: Payload<PayloadUartLink>,
 
     #[cfg(feature = "sim_payload")]
     pub sim_radio_command: Option<crate::packet::Command>,
 }
 
 impl FlightState {
+    /// One argument per board peripheral, handed over from `main`.
+    #[allow(clippy::too_many_arguments)]
     pub async fn new(
         i2c_bus: &'static SharedI2c,
         spi_bus: &'static SharedSpi,
@@ -150,7 +228,8 @@ impl FlightState {
         sv: SV<'static>,
         airbrake_system: AirbrakeActuator<'static>,
         mut flash: OnboardFlash<'static>,
-        payload_uart: UartTx<'static, Async>,
+        payload_link: PayloadUartLink,
+        supply: SupplyMonitor,
     ) -> Self {
         let mut packet = Packet::default();
         let init_to = Duration::from_millis(constants::SENSOR_INIT_TIMEOUT_MS);
@@ -257,6 +336,79 @@ impl FlightState {
             }
         }
 
+        // Airbrake controller config saved on the pad with <AK,...>; anything
+        // missing or out of range falls back to the compiled-in defaults.
+        let mut airbrake_config = AirbrakeConfig::default();
+        if flash_ok {
+            match with_timeout(flash_to, flash.read_airbrake_config()).await {
+                Ok(Ok(Some(cfg))) if cfg.is_valid() => airbrake_config = cfg,
+                Ok(Ok(Some(_))) => log::warn!("Stored airbrake config out of range — using defaults"),
+                Ok(Ok(None)) => {}
+                Ok(Err(e)) => log::warn!("Airbrake config read failed: {:?}", e),
+                Err(_) => log::warn!("Airbrake config read TIMEOUT"),
+            }
+        }
+        log::info!(
+            "Airbrake config: target={:.1} m rate={:.2}/s min_vel={:.1} m/s body_cda={:.5} brake_cda={:.5}",
+            airbrake_config.target_apogee_m, airbrake_config.max_deploy_rate_per_s,
+            airbrake_config.min_activation_vel_mps,
+            airbrake_config.drag.body_cda_m2, airbrake_config.drag.brake_cda_m2,
+        );
+        crate::airbrake_task::AIRBRAKE_CONFIG.signal(airbrake_config);
+
+        // BLiMS wind profile uploaded on the pad with <WP,...>; without one
+        // BLiMS uses the single BLIMS_WIND_FROM_DEG / BLIMS_WIND_SPEED_MS.
+        let mut blims_wind_profile = WindProfile::EMPTY;
+        if flash_ok {
+            match with_timeout(flash_to, flash.read_wind_profile()).await {
+                Ok(Ok(Some(profile))) => blims_wind_profile = profile,
+                Ok(Ok(None)) => {}
+                Ok(Err(e)) => log::warn!("BLiMS wind profile read failed: {:?}", e),
+                Err(_) => log::warn!("BLiMS wind profile read TIMEOUT"),
+            }
+        }
+        log::info!(
+            "BLiMS wind profile: {} layers, checksum {:08X}",
+            blims_wind_profile.len(), blims_wind_profile.checksum()
+        );
+
+        // BLiMS geofence uploaded on the pad with <GF,...>; none means BLiMS
+        // flies straight to the downwind target.
+        let mut blims_geofence = Geofence::EMPTY;
+        if flash_ok {
+            match with_timeout(flash_to, flash.read_geofence()).await {
+                Ok(Ok(Some(fence))) => blims_geofence = fence,
+                Ok(Ok(None)) => {}
+                Ok(Err(e)) => log::warn!("BLiMS geofence read failed: {:?}", e),
+                Err(_) => log::warn!("BLiMS geofence read TIMEOUT"),
+            }
+        }
+        log::info!(
+            "BLiMS geofence: {} zones{}, checksum {:08X}",
+            blims_geofence.len(),
+            if blims_geofence.has_landing_zone() { " incl. landing zone" } else { "" },
+            blims_geofence.checksum()
+        );
+
+        // BLiMS gains and phase altitudes saved on the pad with <BK,...>;
+        // anything missing or out of range falls back to the defaults.
+        let mut blims_config = BlimsConfig::default();
+        if flash_ok {
+            match with_timeout(flash_to, flash.read_blims_config()).await {
+                Ok(Ok(Some(cfg))) if cfg.is_valid() => blims_config = cfg,
+                Ok(Ok(Some(_))) => log::warn!("Stored BLiMS config out of range — using defaults"),
+                Ok(Ok(None)) => {}
+                Ok(Err(e)) => log::warn!("BLiMS config read failed: {:?}", e),
+                Err(_) => log::warn!("BLiMS config read TIMEOUT"),
+            }
+        }
+        log::info!(
+            "BLiMS config: kp={} ki={} imax={:.2} in upwind={:.0} ft neutral={:.0} ft hyst={:.0} ft hold={} ms",
+            blims_config.kp, blims_config.ki, blims_config.integral_max_in,
+            blims_config.alt_upwind_ft, blims_config.alt_neutral_ft,
+            blims_config.phase_hysteresis_ft, blims_config.initial_hold_ms,
+        );
+
         // The snapshot ring is written at 1 Hz and captures flight_mode more
         // recently than the full packet write. Always trust it over the packet's
         // own flight_mode field so the two sources stay consistent.
@@ -327,19 +479,24 @@ impl FlightState {
         log::info!("Actuator state restored: mav={} sv={}", stored_mav_open, stored_sv_open);
 
         Self {
-            packet: packet,
+            packet,
+            config: ConfigPacket::default(),
+            last_config_tx: None,
+            logged_config: None,
             flight_mode: stored_mode,
             cycle_count: stored_cycle_count,
             key_armed: false,
             umbilical_connected: false,
-            altimeter: altimeter,
+            altimeter,
             altimeter_state: altimeter_init,
             altimeter_fail_count: 0,
-            gps: gps,
+            gps,
             gps_ok,
             gps_fail_count: 0,
+            gps_position_e7: (0, 0),
+            gps_itow: 0,
             gps_probe_count: 0,
-            imu: imu,
+            imu,
             imu_ok: true,
             imu_fail_count: 0,
             imu_probe_count: 0,
@@ -353,18 +510,23 @@ impl FlightState {
                 [0.0, 1.0, 0.0],
                 [0.0, 0.0, 1.0],
             ],
-            adc: adc,
-            arming_switch: arming_switch,
-            cfc_arm: cfc_arm,
+            adc,
+            supply,
+            supply_low: false,
+            arming_switch,
+            cfc_arm,
             cfc_arm_active: false,
             arming_altitude: stored_arming_altitude,
-            radio: radio,
+            radio,
+            beacon_mode: false,
+            last_fix: None,
             reference_pressure: 0.0,
             ssa,
             buzzer,
             mav,
             sv,
             airbrake_system,
+            airbrake_config,
             blims: None,
             blims_armed: false,
             blims_upwind_lat: stored_blims_upwind_lat,
@@ -372,13 +534,23 @@ impl FlightState {
             blims_downwind_lat: stored_blims_downwind_lat,
             blims_downwind_lon: stored_blims_downwind_lon,
             blims_wind_from_deg: constants::BLIMS_WIND_FROM_DEG,
+            blims_wind_profile,
+            blims_geofence,
+            blims_config,
             blims_in_downwind_phase: false,
+            blims_wind_profile_logged: false,
+            blims_heading_valid: false,
+            blims_fence_flags: 0,
+            blims_landing_logged: false,
             blims_targets_dirty: false,
+            blims_diag: None,
+            time_sync: TimeSync::new(),
+            session_pending: Some(SessionRecord::KIND_BOOT),
             flash,
             last_snapshot_log: Instant::now(),
             snap_launch_stage: stored_launch_stage,
             snap_launch_elapsed_ms: stored_launch_elapsed_ms,
-            payload_uart,
+            payload: Payload::new(payload_link),
 
             #[cfg(feature = "sim_payload")]
             sim_radio_command: None,
@@ -386,11 +558,17 @@ impl FlightState {
     }
 
     pub fn read_altimeter(&mut self) -> f32{
-        return self.packet.altitude;
+        self.packet.altitude
+    }
+
+    /// False when this cycle's altimeter read failed, or was skipped in
+    /// beacon mode, and `packet.altitude` still holds an earlier value.
+    pub fn altimeter_read_ok(&self) -> bool {
+        self.altimeter_fail_count == 0 && !self.beacon_mode
     }
 
     pub fn read_barometer(&mut self) -> f32{
-        return self.packet.pressure;
+        self.packet.pressure
     }
 
     pub async fn update_actuators(&mut self) {
@@ -404,6 +582,59 @@ impl FlightState {
         self.packet.mav_open = self.mav.is_open();
     }
 
+    /// Brakeline calibration the BLiMS PWM path sends through (the nominal
+    /// linear map while BLiMS isn't attached); the UART setpoint and the
+    /// measured brakeline go through the same table.
+    pub fn blims_calibration(&self) -> blims::CalibrationTable {
+        self.blims.as_ref().map_or(blims::CalibrationTable::LINEAR, |b| *b.calibration())
+    }
+
+    /// Hand this cycle's setpoints to the ODrive tasks and pick the command
+    /// path from their latest readback: while a UART link is up its PWM
+    /// output is held low so only one source drives the motor; when the link
+    /// drops, PWM resumes at the current setpoint. Call after the airbrake
+    /// and BLiMS setpoints for this cycle are final.
+    pub fn update_odrives(&mut self) {
+        let blims_cal = self.blims_calibration();
+
+        odrive_task::AIRBRAKE_ODRIVE_SETPOINT.signal(self.airbrake_system.pulse_us());
+        odrive_task::BLIMS_ODRIVE_SETPOINT.signal(blims_cal.pulse_us(self.packet.blims_brakeline_diff));
+
+        let ab = odrive_task::get_airbrake_readback();
+        self.airbrake_system.set_pwm_output(!ab.link_up);
+        if let Some(pulse_us) = ab.position_pulse_us {
+            self.packet.airbrake_measured_deployment = AirbrakeActuator::deployment_at(pulse_us);
+        }
+        self.packet.airbrake_odrive_state = ab.status.axis_state;
+        self.packet.airbrake_odrive_errors = ab.status.active_errors;
+        self.packet.airbrake_odrive_vbus = ab.status.vbus_v;
+        self.packet.airbrake_odrive_ibus = ab.status.ibus_a;
+
+        let bl = odrive_task::get_blims_readback();
+        if let Some(blims) = &mut self.blims {
+            blims.actuator_mut().set_pwm_output(!bl.link_up);
+        }
+        if let Some(pulse_us) = bl.position_pulse_us {
+            self.packet.blims_measured_brakeline = blims_cal.brakeline_in(pulse_us);
+        }
+        self.packet.blims_odrive_state = bl.status.axis_state;
+        self.packet.blims_odrive_errors = bl.status.active_errors;
+        self.packet.blims_odrive_vbus = bl.status.vbus_v;
+        self.packet.blims_odrive_ibus = bl.status.ibus_a;
+
+        self.packet.odrive_link = ab.link_up as u8 | (bl.link_up as u8) << 1;
+    }
+
+    /// Run the payload link for this cycle (receive, retries, heartbeat)
+    /// and copy its health into telemetry.
+    pub async fn update_payload(&mut self, flight_mode: u8) {
+        self.payload.update(flight_mode).await;
+        self.packet.payload_link = self.payload.link_state() as u8;
+        self.packet.payload_last_ack = self.payload.last_acked().map_or(0, |c| c.id());
+        self.packet.payload_state = self.payload.status.state;
+        self.packet.payload_failed = self.payload.failed();
+    }
+
     // Actuator wrappers with FRAM writing
 
     pub async fn trigger_drogue(&mut self) {
@@ -420,6 +651,39 @@ impl FlightState {
         log::info!("ACTUATOR: Buzzing {} times", num);
         self.buzzer.buzz(num);
     }
+
+    /// Play `pattern` unless one is already sounding; `what` is for the log.
+    pub fn buzz_pattern(&mut self, what: &str, pattern: &BuzzPattern) {
+        if self.buzzer.play(pattern) {
+            log::info!("ACTUATOR: Buzzing {}", what);
+        }
+    }
+
+    /// Failed subsystems as `health` bits; `FlightLoop` adds `RECOVERED_FAULT`.
+    pub fn health(&self) -> u16 {
+        let mut bits = 0;
+        if self.altimeter_state != SensorState::VALID {
+            bits |= health::ALTIMETER;
+        }
+        if !self.imu_ok {
+            bits |= health::IMU;
+        }
+        if !self.gps_ok {
+            bits |= health::GPS;
+        }
+        if !self.flash.flash_ok {
+            bits |= health::FLASH;
+        } else if self.flash.storage_full {
+            bits |= health::STORAGE_FULL;
+        }
+        if self.payload.link_state() == LinkState::Lost {
+            bits |= health::PAYLOAD;
+        }
+        if self.supply_low {
+            bits |= health::LOW_VOLTAGE;
+        }
+        bits
+    }
     pub async fn open_mav(&mut self, duration: u64) {
         log::info!("ACTUATOR: Opening MAV");
         self.mav.open(duration);
@@ -444,7 +708,8 @@ impl FlightState {
         self.update_actuators().await;
 
         // Monotonic timestamp: ms since CFC boot
-        self.packet.ms_since_boot_cfc = embassy_time::Instant::now().as_millis() as u32;
+        let boot_ms = embassy_time::Instant::now().as_millis();
+        self.packet.ms_since_boot_cfc = boot_ms as u32;
 
         // Update packet flight mode
         self.packet.flight_mode = self.flight_mode as u32;
@@ -452,6 +717,34 @@ impl FlightState {
         // key_armed is set only by umbilical <KA>/<KD> commands, not from GPIO
         self.umbilical_connected = crate::umbilical::is_connected();
         self.cfc_arm_active = self.cfc_arm.is_high();
+
+        #[cfg(not(feature = "hil"))]
+        if self.beacon_mode {
+            self.read_gps().await;
+        } else {
+            self.read_driver_sensors().await;
+        }
+        #[cfg(feature = "hil")]
+        self.read_hil_sensors();
+
+        // On the RP2350's own ADC, so read in beacon mode and under HIL too
+        self.read_supply().await;
+
+        // Absolute time for this cycle. A newly acquired sync also queues a
+        // session record so the decoder gets the anchor even if the boot
+        // header was written before the GPS had a fix.
+        if self.time_sync.take_changed() && self.session_pending.is_none() {
+            self.session_pending = Some(SessionRecord::KIND_TIME_SYNC);
+        }
+        self.packet.utc_ms = self.time_sync.utc_ms_at(boot_ms);
+
+        log::info!("Flight mode: {:?}\n", self.flight_mode);
+    }
+
+    /// Reads every sensor driver into the packet, with per-sensor failure
+    /// counting and pre-flight reconnection probing.
+    #[cfg(not(feature = "hil"))]
+    async fn read_driver_sensors(&mut self) {
         let read_to = Duration::from_millis(constants::SENSOR_READ_TIMEOUT_MS);
 
         // Read altimeter and update packet
@@ -492,59 +785,7 @@ impl FlightState {
             }
         }
 
-        // Read GPS and update packet
-        if self.gps_ok {
-            match with_timeout(read_to, self.gps.read_into_packet(&mut self.packet)).await {
-                Ok(Ok(_)) => {
-                    self.gps_fail_count = 0;
-                    log::info!(
-                        "GPS | Lat = {:.6}°, Lon = {:.6}°, Sats = {}, Time = {:.0} s",
-                        self.packet.latitude,
-                        self.packet.longitude,
-                        self.packet.num_satellites,
-                        self.packet.timestamp
-                    );
-                }
-                Ok(Err(GpsError::NoData)) => {
-                    // No fix yet — normal at 20 Hz vs 1 Hz GPS output; not an I2C fault.
-                }
-                Ok(Err(GpsError::I2cError)) => {
-                    log::error!("GPS: I2C error");
-                    self.gps_fail_count = self.gps_fail_count.saturating_add(1);
-                }
-                Err(_) => {
-                    log::error!("GPS: read TIMEOUT");
-                    self.gps_fail_count = self.gps_fail_count.saturating_add(1);
-                }
-            }
-
-            if self.gps_fail_count >= 5 {
-                if matches!(self.flight_mode, FlightMode::Startup | FlightMode::Standby)
-                    && self.gps_probe_count < 20
-                {
-                    // Pre-flight: probe for reconnection and reboot if the device
-                    // comes back so the full init sequence runs fresh.
-                    self.gps_probe_count += 1;
-                    log::warn!("GPS: lost — probing ({}/20)...", self.gps_probe_count);
-                    match with_timeout(read_to, self.gps.probe()).await {
-                        Ok(true) => {
-                            log::warn!("GPS: reconnected — rebooting for fresh init");
-                            cortex_m::peripheral::SCB::sys_reset();
-                        }
-                        _ => {
-                            if self.gps_probe_count >= 20 {
-                                self.gps_ok = false;
-                                log::error!("GPS: permanently disabled after 20 failed probes");
-                            }
-                        }
-                    }
-                } else {
-                    // In flight: never reboot mid-flight, just disable reads.
-                    self.gps_ok = false;
-                    log::error!("GPS: disabled — I2C lost during flight");
-                }
-            }
-        }
+        self.read_gps().await;
 
         // Read IMU and update packet.
         // read_into_packet() silently returns Ok(()) when !initialized, so errors
@@ -642,8 +883,205 @@ impl FlightState {
                 log::error!("ADS1015 ADC read TIMEOUT");
             }
         }
+    }
 
-        log::info!("Flight mode: {:?}\n", self.flight_mode);
+    /// Reads the battery and pyro rail into the packet (NaN when the read
+    /// fails) and logs a low-voltage event when either drops below its
+    /// arming minimum.
+    async fn read_supply(&mut self) {
+        let read_to = Duration::from_millis(constants::SENSOR_READ_TIMEOUT_MS);
+        let (battery, pyro) = match with_timeout(read_to, self.supply.read()).await {
+            Ok(Ok(rails)) => rails,
+            Ok(Err(e)) => {
+                log::error!("SUPPLY: ADC error: {:?}", e);
+                (f32::NAN, f32::NAN)
+            }
+            Err(_) => {
+                log::error!("SUPPLY: read TIMEOUT");
+                (f32::NAN, f32::NAN)
+            }
+        };
+        self.packet.battery_v = battery;
+        self.packet.pyro_v = pyro;
+
+        // Written so a NaN (unmeasured) rail counts as low
+        let above = |margin: f32| {
+            battery >= constants::BATTERY_MIN_ARM_V + margin && pyro >= constants::PYRO_MIN_ARM_V + margin
+        };
+        if !self.supply_low && !above(0.0) {
+            self.supply_low = true;
+            log::warn!(
+                "SUPPLY: low voltage in {:?}: battery {:.2} V (min {:.2}), pyro {:.2} V (min {:.2})",
+                self.flight_mode, battery, constants::BATTERY_MIN_ARM_V, pyro, constants::PYRO_MIN_ARM_V
+            );
+        } else if self.supply_low && above(constants::SUPPLY_HYSTERESIS_V) {
+            self.supply_low = false;
+            log::info!("SUPPLY: voltage recovered: battery {:.2} V, pyro {:.2} V", battery, pyro);
+        }
+    }
+
+    /// False while `LOW_VOLTAGE` is set; Startup→Standby arming waits on it.
+    pub fn supply_ok(&self) -> bool {
+        !self.supply_low
+    }
+
+    /// Reads the GPS into the packet, disabling it after repeated failures
+    /// (probing for reconnection pre-flight). The only read in beacon mode.
+    #[cfg(not(feature = "hil"))]
+    async fn read_gps(&mut self) {
+        if !self.gps_ok {
+            return;
+        }
+        let read_to = Duration::from_millis(constants::SENSOR_READ_TIMEOUT_MS);
+        match with_timeout(read_to, self.gps.read_into_packet(&mut self.packet)).await {
+            Ok(Ok(_)) => {
+                self.gps_fail_count = 0;
+                self.gps_position_e7 = self.gps.position_e7();
+                self.gps_itow = self.gps.itow();
+                if let Some(utc) = self.gps.take_utc() {
+                    self.time_sync.on_nav_pvt(utc, Instant::now().as_millis());
+                }
+                self.note_fix();
+                log::info!(
+                    "GPS | Lat = {:.6}°, Lon = {:.6}°, Sats = {}, Time = {:.0} s",
+                    self.packet.latitude,
+                    self.packet.longitude,
+                    self.packet.num_satellites,
+                    self.packet.timestamp
+                );
+            }
+            Ok(Err(GpsError::NoData)) => {
+                // No fix yet — normal at 20 Hz vs 1 Hz GPS output; not an I2C fault.
+            }
+            Ok(Err(GpsError::I2cError)) => {
+                log::error!("GPS: I2C error");
+                self.gps_fail_count = self.gps_fail_count.saturating_add(1);
+            }
+            Err(_) => {
+                log::error!("GPS: read TIMEOUT");
+                self.gps_fail_count = self.gps_fail_count.saturating_add(1);
+            }
+        }
+
+        if self.gps_fail_count >= 5 {
+            if matches!(self.flight_mode, FlightMode::Startup | FlightMode::Standby)
+                && self.gps_probe_count < 20
+            {
+                // Pre-flight: probe for reconnection and reboot if the device
+                // comes back so the full init sequence runs fresh.
+                self.gps_probe_count += 1;
+                log::warn!("GPS: lost — probing ({}/20)...", self.gps_probe_count);
+                match with_timeout(read_to, self.gps.probe()).await {
+                    Ok(true) => {
+                        log::warn!("GPS: reconnected — rebooting for fresh init");
+                        cortex_m::peripheral::SCB::sys_reset();
+                    }
+                    _ => {
+                        if self.gps_probe_count >= 20 {
+                            self.gps_ok = false;
+                            log::error!("GPS: permanently disabled after 20 failed probes");
+                        }
+                    }
+                }
+            } else {
+                // In flight: never reboot mid-flight, just disable reads.
+                self.gps_ok = false;
+                log::error!("GPS: disabled — I2C lost during flight");
+            }
+        }
+    }
+
+    /// HIL counterpart of `read_driver_sensors`: copies the latest host
+    /// frames into the packet. A stale frame counts as a failed read so the
+    /// altimeter debounce and fault paths behave as with real hardware; the
+    /// reconnect probe/reboot is skipped since there is nothing to probe.
+    #[cfg(feature = "hil")]
+    fn read_hil_sensors(&mut self) {
+        use crate::hil::{self, HilError};
+
+        match hil::read_baro() {
+            Ok(f) if (constants::PRESSURE_MIN_PA..=constants::PRESSURE_MAX_PA).contains(&f.pressure) => {
+                self.packet.pressure = f.pressure;
+                self.packet.temp = f.temp;
+                self.packet.altitude = crate::driver::bmp390::pressure_to_altitude(f.pressure);
+                self.altimeter_fail_count = 0;
+                self.altimeter_state = SensorState::VALID;
+            }
+            other => {
+                self.altimeter_fail_count = self.altimeter_fail_count.saturating_add(1);
+                match other {
+                    Ok(f) => log::error!("HIL baro pressure out of range: {} Pa", f.pressure),
+                    Err(e) => log::error!("HIL baro read failed: {:?}", e),
+                }
+                log::error!(
+                    "HIL baro failures: {}/{}",
+                    self.altimeter_fail_count,
+                    constants::ALTIMETER_FAIL_THRESHOLD
+                );
+                if self.altimeter_fail_count >= constants::ALTIMETER_FAIL_THRESHOLD {
+                    self.altimeter_state = SensorState::INVALID;
+                }
+            }
+        }
+
+        match hil::take_gps() {
+            Ok(f) => {
+                self.gps_fail_count = 0;
+                self.packet.latitude = f.latitude;
+                self.packet.longitude = f.longitude;
+                self.gps_position_e7 = (
+                    libm::round(f.latitude as f64 * 1e7) as i32,
+                    libm::round(f.longitude as f64 * 1e7) as i32,
+                );
+                self.gps_itow = f.t_ms;
+                self.packet.num_satellites = f.num_satellites;
+                self.packet.fix_type = f.fix_type;
+                self.packet.timestamp = f.t_ms as f32 / 1000.0;
+                self.packet.vel_n = f.vel_n;
+                self.packet.vel_e = f.vel_e;
+                self.packet.vel_d = f.vel_d;
+                self.packet.g_speed = libm::sqrt(f.vel_n * f.vel_n + f.vel_e * f.vel_e);
+                let mut heading = libm::atan2(f.vel_e, f.vel_n).to_degrees();
+                if heading < 0.0 {
+                    heading += 360.0;
+                }
+                self.packet.head_mot = (heading * 1e5) as i32;
+                self.packet.h_acc = f.h_acc;
+                self.packet.v_acc = f.v_acc;
+                self.note_fix();
+            }
+            Err(HilError::NoData) => {}
+            Err(HilError::Stale) => {
+                self.gps_fail_count = self.gps_fail_count.saturating_add(1);
+                log::error!("HIL GPS stale ({})", self.gps_fail_count);
+            }
+        }
+
+        match hil::read_imu() {
+            Ok(f) => {
+                self.imu_fail_count = 0;
+                [self.packet.accel_x, self.packet.accel_y, self.packet.accel_z] = f.accel;
+                [self.packet.gyro_x, self.packet.gyro_y, self.packet.gyro_z] = f.gyro;
+                if let Some([x, y, z]) = f.mag {
+                    self.packet.mag_x = x;
+                    self.packet.mag_y = y;
+                    self.packet.mag_z = z;
+                }
+            }
+            Err(e) => {
+                self.imu_fail_count = self.imu_fail_count.saturating_add(1);
+                log::error!("HIL IMU read failed ({}): {:?}", self.imu_fail_count, e);
+            }
+        }
+
+        match hil::read_adc() {
+            Ok(f) => {
+                self.packet.pt3 = f.pt3;
+                self.packet.pt4 = f.pt4;
+                self.packet.rtd = f.rtd;
+            }
+            Err(e) => log::error!("HIL ADC read failed: {:?}", e),
+        }
     }
 
     pub fn start_imu_calibration(&mut self) {
@@ -746,8 +1184,80 @@ impl FlightState {
             }
         }
 
+        let config_due = self
+            .last_config_tx
+            .is_none_or(|t| t.elapsed().as_millis() >= constants::CONFIG_PACKET_PERIOD_MS);
+        if config_due {
+            self.last_config_tx = Some(Instant::now());
+            self.config.ms_since_boot_cfc = self.packet.ms_since_boot_cfc;
+            if let Err(e) = self.radio.send_config(&self.config.to_bytes()).await {
+                log::warn!("RFD | Failed to transmit config: {:?}", e);
+            }
+        }
+
         // Emit telemetry as a parseable text line over USB
-        crate::umbilical::emit_telemetry(&self.packet);
+        crate::umbilical::emit_telemetry(&self.packet, &self.config);
+    }
+
+    /// Remember this cycle's position for the beacon if it is a 3D fix.
+    fn note_fix(&mut self) {
+        if self.packet.fix_type >= 3 {
+            self.last_fix = Some((self.packet.latitude, self.packet.longitude, Instant::now()));
+        }
+    }
+
+    pub fn beacon_mode(&self) -> bool {
+        self.beacon_mode
+    }
+
+    /// Switch to the recovery beacon: power the IMU down, put the altimeter
+    /// to sleep and stop the ADC's conversions, leaving the GPS for the
+    /// beacon position. The RP2350 ADC (battery) stays on for the beacon.
+    pub async fn enter_beacon_mode(&mut self) {
+        self.beacon_mode = true;
+        let to = Duration::from_millis(constants::SENSOR_READ_TIMEOUT_MS);
+        match with_timeout(to, self.imu.power_down()).await {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => log::error!("IMU: power-down failed: {:?}", e),
+            Err(_) => log::error!("IMU: power-down TIMEOUT"),
+        }
+        match with_timeout(to, self.altimeter.sleep()).await {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => log::error!("ALTIMETER: sleep failed: {:?}", e),
+            Err(_) => log::error!("ALTIMETER: sleep TIMEOUT"),
+        }
+        match with_timeout(to, self.adc.power_down()).await {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => log::error!("ADC: power-down failed: {:?}", e),
+            Err(_) => log::error!("ADC: power-down TIMEOUT"),
+        }
+        match self.last_fix {
+            Some((lat, lon, _)) => log::warn!("BEACON: recovery beacon on; last fix {:.6}, {:.6}", lat, lon),
+            None => log::warn!("BEACON: recovery beacon on; no GPS fix yet"),
+        }
+    }
+
+    /// Send the recovery beacon in place of the full packet.
+    pub async fn transmit_beacon(&mut self, apogee_ft: u32, health: u16) {
+        let (latitude, longitude, fix_age_s) = match self.last_fix {
+            Some((lat, lon, at)) => (lat, lon, at.elapsed().as_secs() as u32),
+            None => (0.0, 0.0, BeaconPacket::NO_FIX),
+        };
+        let beacon = BeaconPacket {
+            ms_since_boot_cfc: self.packet.ms_since_boot_cfc,
+            flight_mode: self.flight_mode as u8,
+            num_satellites: self.packet.num_satellites as u8,
+            health,
+            latitude,
+            longitude,
+            fix_age_s,
+            battery_v: self.packet.battery_v,
+            apogee_ft,
+        };
+        match self.radio.send_beacon(&beacon.to_bytes()).await {
+            Ok(_) => log::info!("RFD | Beacon transmitted (fix age {} s)", fix_age_s),
+            Err(e) => log::warn!("RFD | Failed to transmit beacon: {:?}", e),
+        }
     }
 
     pub async fn receive_radio(&mut self, buffer: &mut [u8]) -> Result<(), embassy_rp::uart::Error> {
@@ -797,7 +1307,7 @@ impl FlightState {
             } else if buf.starts_with(b"FM") {
                 // Example: "FM2" for Coast
                 if let Some(digit) = (buf[2] as char).to_digit(10) {
-                    return Some(crate::packet::Command::ForceMode(digit as u32));
+                    return Some(crate::packet::Command::ForceMode(digit));
                 }
             }
         }
@@ -811,6 +1321,17 @@ impl FlightState {
             return;
         }
         let to = Duration::from_millis(constants::FLASH_TIMEOUT_MS);
+        if let Some(kind) = self.session_pending.take() {
+            self.write_session_record(kind).await;
+        }
+        if self.logged_config.is_none_or(|c| !c.same_config(&self.config)) {
+            self.config.ms_since_boot_cfc = self.packet.ms_since_boot_cfc;
+            match with_timeout(to, self.flash.append_config_record(&self.config)).await {
+                Ok(Ok(_)) => self.logged_config = Some(self.config),
+                Ok(Err(e)) => log::warn!("Flash config-record append failed: {:?}", e),
+                Err(_) => log::warn!("Flash config-record append TIMEOUT"),
+            }
+        }
         if full {
             match with_timeout(to, self.flash.append_full_record(&self.packet)).await {
                 Ok(Ok(_)) => {}
@@ -825,6 +1346,32 @@ impl FlightState {
                 Err(_) => log::warn!("Flash fast-record append TIMEOUT"),
             }
         }
+        if let Some(diag) = self.blims_diag.take() {
+            match with_timeout(to, self.flash.append_blims_diag_record(&diag)).await {
+                Ok(Ok(_)) => {}
+                Ok(Err(e)) => log::warn!("Flash BLiMS diag-record append failed: {:?}", e),
+                Err(_) => log::warn!("Flash BLiMS diag-record append TIMEOUT"),
+            }
+        }
+    }
+
+    /// Write a session header pairing the current boot ms with UTC.
+    async fn write_session_record(&mut self, kind: u8) {
+        let boot_ms = Instant::now().as_millis();
+        let session = SessionRecord {
+            kind,
+            time_source: self.time_sync.source() as u8,
+            ms_since_boot_cfc: boot_ms as u32,
+            utc_ms: self.time_sync.utc_ms_at(boot_ms),
+            t_acc_ns: self.time_sync.t_acc_ns(),
+            flight_mode: self.flight_mode as u32,
+        };
+        let to = Duration::from_millis(constants::FLASH_TIMEOUT_MS);
+        match with_timeout(to, self.flash.append_session_record(&session)).await {
+            Ok(Ok(_)) => log::info!("Flash: session record written (kind={}, utc_ms={})", kind, session.utc_ms),
+            Ok(Err(e)) => log::warn!("Flash session-record append failed: {:?}", e),
+            Err(_) => log::warn!("Flash session-record append TIMEOUT"),
+        }
     }
 
     /// Reads the packet currently stored in the onboard QSPI Flash
@@ -985,6 +1532,9 @@ impl FlightState {
             Ok(Ok(_)) => {
                 log::info!("Flash storage wiped successfully.");
                 crate::umbilical::print_str("Flash wiped successfully.\n");
+                // The boot session header and config went with the wipe — re-emit them
+                self.session_pending = Some(SessionRecord::KIND_BOOT);
+                self.logged_config = None;
                 true
             }
             Ok(Err(e)) => {
@@ -1000,8 +1550,112 @@ impl FlightState {
         }
     }
 
+    /// Hand a new controller config to Core 1 and store it in flash so it
+    /// survives a reboot on the pad. The caller checks `is_valid()` and the
+    /// flight mode. Returns false if the flash write failed; Core 1 uses the
+    /// new config either way.
+    pub async fn set_airbrake_config(&mut self, cfg: AirbrakeConfig) -> bool {
+        self.airbrake_config = cfg;
+        crate::airbrake_task::AIRBRAKE_CONFIG.signal(cfg);
+        if !self.flash.flash_ok {
+            log::warn!("Airbrake config not persisted: flash not available");
+            return false;
+        }
+        let to = Duration::from_millis(constants::FLASH_TIMEOUT_MS);
+        match with_timeout(to, self.flash.write_airbrake_config(&cfg)).await {
+            Ok(Ok(_)) => true,
+            Ok(Err(e)) => {
+                log::warn!("Airbrake config write failed: {:?}", e);
+                false
+            }
+            Err(_) => {
+                log::warn!("Airbrake config write TIMEOUT");
+                false
+            }
+        }
+    }
+
+    /// Read back the airbrake config stored in flash, if any.
+    pub async fn stored_airbrake_config(&mut self) -> Option<AirbrakeConfig> {
+        if !self.flash.flash_ok {
+            return None;
+        }
+        let to = Duration::from_millis(constants::FLASH_TIMEOUT_MS);
+        match with_timeout(to, self.flash.read_airbrake_config()).await {
+            Ok(Ok(cfg)) => cfg,
+            _ => None,
+        }
+    }
+
+    /// Replace the BLiMS wind profile (empty clears it) and store it in
+    /// flash. The caller validates and checks the flight mode. Returns false
+    /// if the flash write failed; the profile is used until reboot either way.
+    pub async fn set_blims_wind_profile(&mut self, profile: WindProfile) -> bool {
+        self.blims_wind_profile = profile;
+        if !self.flash.flash_ok {
+            log::warn!("BLiMS wind profile not persisted: flash not available");
+            return false;
+        }
+        let to = Duration::from_millis(constants::FLASH_TIMEOUT_MS);
+        match with_timeout(to, self.flash.write_wind_profile(&profile)).await {
+            Ok(Ok(_)) => true,
+            Ok(Err(e)) => {
+                log::warn!("BLiMS wind profile write failed: {:?}", e);
+                false
+            }
+            Err(_) => {
+                log::warn!("BLiMS wind profile write TIMEOUT");
+                false
+            }
+        }
+    }
+
+    /// Replace the BLiMS geofence (empty clears it) and store it in flash,
+    /// as for the wind profile.
+    pub async fn set_blims_geofence(&mut self, fence: Geofence) -> bool {
+        self.blims_geofence = fence;
+        if !self.flash.flash_ok {
+            log::warn!("BLiMS geofence not persisted: flash not available");
+            return false;
+        }
+        let to = Duration::from_millis(constants::FLASH_TIMEOUT_MS);
+        match with_timeout(to, self.flash.write_geofence(&fence)).await {
+            Ok(Ok(_)) => true,
+            Ok(Err(e)) => {
+                log::warn!("BLiMS geofence write failed: {:?}", e);
+                false
+            }
+            Err(_) => {
+                log::warn!("BLiMS geofence write TIMEOUT");
+                false
+            }
+        }
+    }
+
+    /// Replace the BLiMS gains and phase altitudes and store them in flash,
+    /// as for the wind profile.
+    pub async fn set_blims_config(&mut self, cfg: BlimsConfig) -> bool {
+        self.blims_config = cfg;
+        if !self.flash.flash_ok {
+            log::warn!("BLiMS config not persisted: flash not available");
+            return false;
+        }
+        let to = Duration::from_millis(constants::FLASH_TIMEOUT_MS);
+        match with_timeout(to, self.flash.write_blims_config(&cfg)).await {
+            Ok(Ok(_)) => true,
+            Ok(Err(e)) => {
+                log::warn!("BLiMS config write failed: {:?}", e);
+                false
+            }
+            Err(_) => {
+                log::warn!("BLiMS config write TIMEOUT");
+                false
+            }
+        }
+    }
+
     /// Attach the BLiMS hardware to this FlightState.
-    pub fn set_blims(&mut self, blims: blims::Blims<'static>) {
+    pub fn set_blims(&mut self, blims: blims::Blims<blims::PwmActuator<'static>>) {
         self.blims = Some(blims);
     }
 
@@ -1022,10 +1676,10 @@ impl FlightState {
         self.packet.blims_downwind_lat = lat;
         self.packet.blims_downwind_lon = lon;
         self.blims_targets_dirty = true;
-        if self.blims_armed {
-            if let Some(b) = &mut self.blims {
-                b.set_downwind_target(lat, lon);
-            }
+        if self.blims_armed
+            && let Some(b) = &mut self.blims
+        {
+            b.set_downwind_target(lat as f64, lon as f64);
         }
     }
 
@@ -1036,14 +1690,24 @@ impl FlightState {
 
         // Arm on first call: push both waypoints and wind direction into the library.
         if !self.blims_armed {
-            blims.set_upwind_target(self.blims_upwind_lat, self.blims_upwind_lon);
-            blims.set_downwind_target(self.blims_downwind_lat, self.blims_downwind_lon);
+            blims.set_upwind_target(self.blims_upwind_lat as f64, self.blims_upwind_lon as f64);
+            blims.set_downwind_target(self.blims_downwind_lat as f64, self.blims_downwind_lon as f64);
             blims.set_wind_from_deg(self.blims_wind_from_deg);
+            blims.set_wind_speed_ms(constants::BLIMS_WIND_SPEED_MS);
+            blims.set_wind_profile(&self.blims_wind_profile);
+            blims.set_geofence(&self.blims_geofence);
+            blims.set_config(&self.blims_config);
+            blims.set_guidance_mode(constants::BLIMS_GUIDANCE_MODE);
+            blims.set_tracking_law(constants::BLIMS_TRACKING_LAW);
             log::info!(
-                "BLiMS: armed — upwind ({:.6}, {:.6}), downwind ({:.6}, {:.6}), wind from {:.1}°",
+                "BLiMS: armed ({:?}, {:?}) — upwind ({:.6}, {:.6}), downwind ({:.6}, {:.6}), wind from {:.1}°, profile {} layers, geofence {} zones, phases at {:.0}/{:.0} ft",
+                constants::BLIMS_GUIDANCE_MODE,
+                constants::BLIMS_TRACKING_LAW,
                 self.blims_upwind_lat, self.blims_upwind_lon,
                 self.blims_downwind_lat, self.blims_downwind_lon,
-                self.blims_wind_from_deg
+                self.blims_wind_from_deg, self.blims_wind_profile.len(),
+                self.blims_geofence.len(),
+                self.blims_config.alt_upwind_ft, self.blims_config.alt_neutral_ft
             );
             self.blims_armed = true;
             blims.enable();
@@ -1051,39 +1715,156 @@ impl FlightState {
 
         let alt_ft = self.packet.altitude * 3.28084_f32;
 
+        // Full-resolution fix, not the packet's f32 degrees
+        let (lat, lon) = self.gps_position_e7;
         let data_in = BlimsDataIn {
-            lat:         (self.packet.latitude  * 1e7_f32) as i32,
-            lon:         (self.packet.longitude * 1e7_f32) as i32,
+            itow:         self.gps_itow,
+            lat,
+            lon,
             altitude_ft:  alt_ft,
             fix_type:     self.packet.fix_type,
             gps_state:    self.packet.num_satellites > 0,
             head_mot:     self.packet.head_mot,
-            vel_n:        self.packet.vel_n as i32,
-            vel_e:        self.packet.vel_e as i32,
-            vel_d:        self.packet.vel_d as i32,
-            g_speed:      self.packet.g_speed as i32,
+            // Packet velocities are m/s; BLiMS takes NAV-PVT mm/s
+            vel_n:        (self.packet.vel_n * 1000.0) as i32,
+            vel_e:        (self.packet.vel_e * 1000.0) as i32,
+            vel_d:        (self.packet.vel_d * 1000.0) as i32,
+            g_speed:      (self.packet.g_speed * 1000.0) as i32,
             h_acc:        self.packet.h_acc,
             v_acc:        self.packet.v_acc,
             s_acc:        self.packet.s_acc,
             head_acc:     self.packet.head_acc,
+            // Calibrated gyro about the long axis, for the heading filter
+            yaw_rate_dps:   constants::BLIMS_YAW_GYRO_SIGN * self.packet.gyro_y,
+            yaw_rate_valid: self.imu_ok && !self.imu_calibrating,
         };
 
-        let out = blims.execute(&data_in);
+        let now_ms = Instant::now().as_millis();
+        let out = blims.execute(&data_in, now_ms);
+        self.blims_diag = Some(BlimsDiagRecord {
+            ms_since_boot_cfc: now_ms as u32,
+            phase_id:        out.phase_id,
+            gps_valid:       out.gps_valid,
+            fix_type:        data_in.fix_type,
+            heading_valid:   out.heading_valid,
+            desired_heading: out.desired_heading_deg,
+            heading:         out.heading_deg,
+            heading_error:   out.heading_error_deg,
+            pid_p:           out.pid_p,
+            pid_i:           out.pid_i,
+            error_integral:  out.error_integral,
+            dt:              out.dt_s,
+            brakeline_diff:  out.brakeline_diff_in,
+            activation_ms:   out.activation_time_ms.map_or(0, |ms| ms as u32),
+        });
         self.packet.blims_brakeline_diff = out.brakeline_diff_in;
         self.packet.blims_phase_id       = out.phase_id;
         self.packet.blims_pid_p          = out.pid_p;
         self.packet.blims_pid_i          = out.pid_i;
         self.packet.blims_bearing        = out.bearing;
         self.packet.blims_wind_from_deg  = self.blims_wind_from_deg;
+        self.packet.blims_wind_est_speed    = out.wind_speed_ms;
+        self.packet.blims_wind_est_from_deg = out.wind_from_deg;
+        self.packet.blims_dist_to_target    = out.distance_to_target_m;
+        self.packet.blims_fence_flags       = out.fence_flags;
 
-        // Log which waypoint is actively being tracked
         use blims::blims_state::Phase;
+        if !self.blims_in_downwind_phase
+            && (out.phase_id == Phase::Downwind as i8 || out.phase_id == Phase::DownwindLeg as i8)
+        {
+            self.blims_in_downwind_phase = true;
+            if out.phase_id == Phase::DownwindLeg as i8 {
+                let p = blims.pattern();
+                log::info!(
+                    "BLiMS: pattern downwind leg at {:.0} ft — legs downwind {:.0} m, base {:.0} m, final {:.0} m",
+                    alt_ft, p.downwind_m, p.base_m, p.final_m
+                );
+            } else {
+                log::info!("BLiMS: downwind at {:.0} ft", alt_ft);
+            }
+        }
+
+        // Without a trustworthy heading BLiMS holds the brakelines neutral
+        if out.heading_valid != self.blims_heading_valid {
+            self.blims_heading_valid = out.heading_valid;
+            if out.heading_valid {
+                log::info!("BLiMS: heading acquired ({:.0}°) at {:.0} ft", out.heading_deg, alt_ft);
+            } else {
+                log::warn!(
+                    "BLiMS: heading lost at {:.0} ft (ground speed {:.1} m/s) — brakelines neutral",
+                    alt_ft, self.packet.g_speed
+                );
+            }
+        }
+
+        // Geofence events: keep-out entered or left, landing point moved,
+        // where it came down
+        use blims::geofence::{FENCE_IN_KEEP_OUT, FENCE_NO_SAFE_TARGET, FENCE_OUTSIDE_LZ, FENCE_TARGET_MOVED};
+        let (was, now) = (self.blims_fence_flags, out.fence_flags);
+        let rose = |bit: u8| now & bit != 0 && was & bit == 0;
+        let fell = |bit: u8| now & bit == 0 && was & bit != 0;
+        let (lat, lon) = (out.landing_target.lat_deg, out.landing_target.lon_deg);
+        if rose(FENCE_IN_KEEP_OUT) {
+            log::warn!(
+                "BLiMS: entered keep-out zone at {:.0} ft ({:.6}, {:.6})",
+                alt_ft, self.packet.latitude, self.packet.longitude
+            );
+        } else if fell(FENCE_IN_KEEP_OUT) {
+            log::info!("BLiMS: left keep-out zone at {:.0} ft", alt_ft);
+        }
+        if rose(FENCE_TARGET_MOVED) {
+            log::warn!(
+                "BLiMS: downwind target unsafe or out of reach at {:.0} ft — landing at ({:.6}, {:.6}), {:.0} m away",
+                alt_ft, lat, lon,
+                out.landing_target.distance_m(LatLon::new(self.blims_downwind_lat as f64, self.blims_downwind_lon as f64))
+            );
+        } else if fell(FENCE_TARGET_MOVED) {
+            log::info!("BLiMS: back on the downwind target at {:.0} ft", alt_ft);
+        }
+        if rose(FENCE_NO_SAFE_TARGET) {
+            log::warn!("BLiMS: no safe landing point in reach at {:.0} ft — holding ({:.6}, {:.6})", alt_ft, lat, lon);
+        }
+        self.blims_fence_flags = now;
+        if !self.blims_landing_logged && out.phase_id == Phase::Neutral as i8 && !self.blims_geofence.is_empty() {
+            self.blims_landing_logged = true;
+            if now & FENCE_IN_KEEP_OUT != 0 {
+                log::warn!(
+                    "BLiMS: landing in a keep-out zone ({:.6}, {:.6})",
+                    self.packet.latitude, self.packet.longitude
+                );
+            }
+            if self.blims_geofence.has_landing_zone() {
+                if now & FENCE_OUTSIDE_LZ != 0 {
+                    log::warn!(
+                        "BLiMS: landing outside the landing zone ({:.6}, {:.6})",
+                        self.packet.latitude, self.packet.longitude
+                    );
+                } else {
+                    log::info!("BLiMS: landing inside the landing zone");
+                }
+            }
+        }
+
+        // Wind measured on the way down, for the next flight's profile
+        if !self.blims_wind_profile_logged && out.phase_id == Phase::Neutral as i8 {
+            self.blims_wind_profile_logged = true;
+            log::info!("BLiMS: estimated wind profile (band floor m AGL, m/s, from °):");
+            for (alt_m, w) in blims.wind_estimator().profile() {
+                log::info!(
+                    "BLiMS:   {:>5.0} m  {:>4.1} m/s  {:>3.0}°  (airspeed {:.1} m/s)",
+                    alt_m, w.speed_ms(), w.from_deg(), w.airspeed_ms
+                );
+            }
+        }
+
+        // Log which waypoint is actively being tracked
         if out.phase_id == Phase::Upwind as i8 {
             self.packet.blims_upwind_lat = self.blims_upwind_lat;
             self.packet.blims_upwind_lon = self.blims_upwind_lon;
         } else {
-            self.packet.blims_downwind_lat = self.blims_downwind_lat;
-            self.packet.blims_downwind_lon = self.blims_downwind_lon;
+            // The landing target: the downwind target unless the geofence moved it
+            self.packet.blims_downwind_lat = lat as f32;
+            self.packet.blims_downwind_lon = lon as f32;
         }
     }
 
@@ -1100,4 +1881,5 @@ impl FlightState {
         log::info!("{}", msg.as_str());
         crate::umbilical::print_str(msg.as_str());
     }
-}
\ No newline at end of file
+}
+
@@ -0,0 +1,185 @@
+//! GPS time synchronisation.
+//!
+//! Keeps a mapping from CFC boot milliseconds (`Instant::now()`) to absolute
+//! UTC so flash records and telemetry can be lined up with fill-station and
+//! ground logs after a reboot.
+//!
+//! Two sources feed the mapping:
+//!   - NAV-PVT: every solution with validDate + validTime + fullyResolved set
+//!     gives a (UTC, receive-time) pair. Receive time lags the navigation epoch
+//!     by the I²C polling latency (up to one 50 ms cycle plus module output
+//!     delay), so the sample with the largest UTC − boot offset is the one with
+//!     the least latency and is kept.
+//!   - TIMEPULSE (optional, `gps_timepulse` feature): the module drives its
+//!     TIMEPULSE pin high at the top of each UTC second. The edge is captured
+//!     by [`timepulse_task`] and paired with the next NAV-PVT, removing the
+//!     polling latency entirely.
+//!
+//! `utc_ms_at()` returns 0 until the first valid solution arrives.
+
+use core::sync::atomic::{AtomicU32, Ordering};
+
+use crate::constants;
+
+/// Boot ms (truncated to u32) of the most recent TIMEPULSE rising edge.
+/// 0 = no edge seen yet.
+static TIMEPULSE_EDGE_MS: AtomicU32 = AtomicU32::new(0);
+
+/// Where the current UTC mapping came from. Logged in session records and
+/// used to let a TIMEPULSE anchor take precedence over a NAV-PVT anchor.
+#[repr(u8)]
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
+pub enum TimeSource {
+    None = 0,
+    NavPvt = 1,
+    Timepulse = 2,
+}
+
+/// UTC time of one NAV-PVT solution.
+#[derive(Copy, Clone, Debug)]
+pub struct GpsUtc {
+    /// Unix epoch milliseconds of the navigation epoch.
+    pub epoch_ms: u64,
+    /// Module's own time accuracy estimate (ns).
+    pub t_acc_ns: u32,
+}
+
+/// Unix epoch milliseconds for a UTC calendar date/time as reported by
+/// NAV-PVT. `nano` is signed (−1e9..1e9) and may pull the result back into
+/// the previous second. Returns `None` for dates before 1970.
+pub fn epoch_ms_from_utc(
+    year: u16,
+    month: u8,
+    day: u8,
+    hour: u8,
+    min: u8,
+    sec: u8,
+    nano: i32,
+) -> Option<u64> {
+    if year < 1970 || month == 0 || month > 12 || day == 0 {
+        return None;
+    }
+    let days = days_from_civil(year as i64, month as i64, day as i64);
+    let secs = days * 86_400 + hour as i64 * 3600 + min as i64 * 60 + sec as i64;
+    let ms = secs * 1000 + (nano as i64) / 1_000_000;
+    if ms < 0 { None } else { Some(ms as u64) }
+}
+
+/// Days since 1970-01-01 for a proleptic Gregorian date (H. Hinnant's algorithm).
+fn days_from_civil(y: i64, m: i64, d: i64) -> i64 {
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = (if y >= 0 { y } else { y - 399 }) / 400;
+    let yoe = y - era * 400;
+    let mp = (m + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + d - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+pub struct TimeSync {
+    /// UTC epoch ms minus boot ms. Valid only when `source != None`.
+    offset_ms: i64,
+    source: TimeSource,
+    t_acc_ns: u32,
+    /// Boot ms at which the current offset was accepted.
+    anchored_at_ms: u64,
+    /// Set when the mapping is first acquired or its source changes, so the
+    /// caller can write a session record. Cleared by `take_changed()`.
+    changed: bool,
+}
+
+impl Default for TimeSync {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TimeSync {
+    pub const fn new() -> Self {
+        Self {
+            offset_ms: 0,
+            source: TimeSource::None,
+            t_acc_ns: 0,
+            anchored_at_ms: 0,
+            changed: false,
+        }
+    }
+
+    pub fn is_synced(&self) -> bool {
+        self.source != TimeSource::None
+    }
+
+    pub fn source(&self) -> TimeSource {
+        self.source
+    }
+
+    pub fn t_acc_ns(&self) -> u32 {
+        self.t_acc_ns
+    }
+
+    /// Feed one valid NAV-PVT solution received at `rx_boot_ms`.
+    pub fn on_nav_pvt(&mut self, utc: GpsUtc, rx_boot_ms: u64) {
+        // Prefer a TIMEPULSE edge from the same second: the pulse marks the
+        // top of the UTC second the solution belongs to.
+        let edge = TIMEPULSE_EDGE_MS.load(Ordering::Acquire);
+        let edge_age = (rx_boot_ms as u32).wrapping_sub(edge);
+        if edge != 0 && edge_age < 1000 {
+            let second_ms = (utc.epoch_ms + 500) / 1000 * 1000;
+            let edge_ms = rx_boot_ms - edge_age as u64;
+            self.accept(second_ms as i64 - edge_ms as i64, TimeSource::Timepulse, utc.t_acc_ns, rx_boot_ms);
+            return;
+        }
+
+        let sample = utc.epoch_ms as i64 - rx_boot_ms as i64;
+        if self.source == TimeSource::Timepulse
+            && rx_boot_ms.saturating_sub(self.anchored_at_ms) < constants::TIME_SYNC_MAX_AGE_MS
+        {
+            // Still holding a fresh pulse-aligned anchor; NAV-PVT can't improve on it.
+            return;
+        }
+        let stale = rx_boot_ms.saturating_sub(self.anchored_at_ms) >= constants::TIME_SYNC_MAX_AGE_MS;
+        if self.source == TimeSource::None || sample > self.offset_ms || stale {
+            self.accept(sample, TimeSource::NavPvt, utc.t_acc_ns, rx_boot_ms);
+        }
+    }
+
+    fn accept(&mut self, offset_ms: i64, source: TimeSource, t_acc_ns: u32, now_ms: u64) {
+        if source != self.source {
+            self.changed = true;
+            log::info!(
+                "TIME: synced via {:?} (offset {} ms, tAcc {} ns)",
+                source, offset_ms, t_acc_ns
+            );
+        }
+        self.offset_ms = offset_ms;
+        self.source = source;
+        self.t_acc_ns = t_acc_ns;
+        self.anchored_at_ms = now_ms;
+    }
+
+    /// UTC epoch ms corresponding to `boot_ms`, or 0 if not yet synced.
+    pub fn utc_ms_at(&self, boot_ms: u64) -> u64 {
+        if !self.is_synced() {
+            return 0;
+        }
+        (boot_ms as i64 + self.offset_ms).max(0) as u64
+    }
+
+    /// Returns true once after the mapping is first acquired or changes source.
+    pub fn take_changed(&mut self) -> bool {
+        core::mem::replace(&mut self.changed, false)
+    }
+}
+
+/// Captures TIMEPULSE rising edges from the GPS module. Only spawned when the
+/// `gps_timepulse` feature is enabled and the pin is wired.
+#[cfg(feature = "gps_timepulse")]
+#[embassy_executor::task]
+pub async fn timepulse_task(mut pin: embassy_rp::gpio::Input<'static>) -> ! {
+    loop {
+        pin.wait_for_rising_edge().await;
+        let now = embassy_time::Instant::now().as_millis() as u32;
+        // 0 is reserved for "no edge yet"
+        TIMEPULSE_EDGE_MS.store(now.max(1), Ordering::Release);
+    }
+}
@@ -1,17 +1,26 @@
-use embassy_executor::Spawner;
 use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
 use embassy_sync::channel::Channel;
-use embassy_time::{Instant, Timer};
+use embassy_time::Instant;
+#[cfg(target_os = "none")]
+use embassy_executor::Spawner;
+#[cfg(target_os = "none")]
+use embassy_time::Timer;
+#[cfg(target_os = "none")]
 use embassy_usb::class::cdc_acm::{Receiver, Sender};
+#[cfg(target_os = "none")]
 use embassy_usb::{UsbDevice, driver::EndpointError};
 
+use crate::airbrake_test::TestProfile;
+use controller_in_rust_v3::{Config as AirbrakeConfig, DragModel};
+use blims::{BlimsConfig, LatLon, WindLayer, ZoneKind};
 use crate::constants::HEARTBEAT_TIMEOUT_MS;
+#[cfg(target_os = "none")]
 use crate::module::{self, UsbDriver};
 use core::sync::atomic::{AtomicBool, AtomicU32, Ordering};
 
 /// Number of comma-separated fields the FSW emits after the `$TELEM,` prefix.
 /// Host-side parsers must match this exactly.
-pub const TELEM_FIELD_COUNT: usize = 54;
+pub const TELEM_FIELD_COUNT: usize = 88;
 
 /// Whether any heartbeat has ever been received. Separates the "never seen"
 /// state from the wrapping `LAST_HEARTBEAT_MS` value (RP2040 lacks AtomicU64,
@@ -86,8 +95,24 @@ pub enum UmbilicalCommand {
     TriggerMain,   // Remove this functionality for real code
     DrogueMode,    // Remove this functionality for real code
     MainMode,      // Remove this functionality for real code
-    DeployAirbrakes, // Remove this functionality for real code
-    RetractAirbrakes, // Remove this functionality for real code
+    DeployAirbrakes, // Step test to 100 % (ground test mode only)
+    RetractAirbrakes, // Abort any airbrake test and retract
+    AirbrakeTestEnable,
+    AirbrakeTestDisable,
+    AirbrakeTest(TestProfile),
+    SetAirbrakeConfig(AirbrakeConfig),
+    SetBlimsConfig(BlimsConfig),
+    // BLiMS wind profile upload, one 64-byte packet per command
+    WindProfileBegin(u8),              // layers to expect
+    WindProfileLayer(u8, WindLayer),   // index, layer
+    WindProfileCommit,
+    WindProfileClear,
+    // BLiMS geofence upload, likewise
+    GeofenceBegin(u8),                 // zones to expect
+    GeofenceZone(u8, ZoneKind, u8),    // zone, kind, vertices to expect
+    GeofenceVertex(u8, u8, LatLon),    // zone, vertex, position
+    GeofenceCommit,
+    GeofenceClear,
     TriggerBLiMS, // Remove this functionality for real code
     FaultMode,   // Remove this functionality for real code
 }
@@ -137,8 +162,9 @@ pub async fn print_bytes_async(data: &[u8]) {
 
 /// Emit a telemetry line in parseable CSV format.
 /// Format: `$TELEM,<flight_mode>,<pressure>,...,<sv_open>,<mav_open>\n`
+/// USB has the bandwidth, so the `ConfigPacket` fields stay in every line.
 /// Suppressed while a dump is in progress (see `begin_dump`/`end_dump`).
-pub fn emit_telemetry(packet: &crate::packet::Packet) {
+pub fn emit_telemetry(packet: &crate::packet::Packet, config: &crate::packet::ConfigPacket) {
     if DUMP_IN_PROGRESS.load(Ordering::Acquire) {
         return;
     }
@@ -146,9 +172,9 @@ pub fn emit_telemetry(packet: &crate::packet::Packet) {
     let len = {
         use core::fmt::Write;
         let mut w = BufWriter::new(&mut buf);
-        let _ = write!(
+        let _ = writeln!(
             w,
-            "$TELEM,{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{}\n",
+            "$TELEM,{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{}",
             packet.flight_mode,
             packet.pressure,
             packet.temp,
@@ -203,6 +229,40 @@ pub fn emit_telemetry(packet: &crate::packet::Packet) {
             packet.blims_downwind_lon,
             packet.blims_wind_from_deg,
             packet.ms_since_boot_cfc,
+            packet.utc_ms,
+            packet.airbrake_measured_deployment,
+            packet.airbrake_odrive_state,
+            packet.airbrake_odrive_errors,
+            packet.airbrake_odrive_vbus,
+            packet.airbrake_odrive_ibus,
+            packet.blims_measured_brakeline,
+            packet.blims_odrive_state,
+            packet.blims_odrive_errors,
+            packet.blims_odrive_vbus,
+            packet.blims_odrive_ibus,
+            packet.odrive_link,
+            packet.airbrake_ctrl_ok,
+            config.airbrake_target_apogee,
+            config.airbrake_max_rate,
+            config.airbrake_min_vel,
+            config.airbrake_body_cda,
+            config.airbrake_brake_cda,
+            packet.airbrake_est_body_cda,
+            packet.airbrake_est_brake_cda,
+            packet.blims_wind_est_speed,
+            packet.blims_wind_est_from_deg,
+            config.blims_wind_layers,
+            config.blims_wind_checksum,
+            packet.blims_dist_to_target,
+            packet.blims_fence_flags,
+            config.blims_fence_zones,
+            config.blims_fence_checksum,
+            packet.payload_link,
+            packet.payload_last_ack,
+            packet.payload_state,
+            packet.payload_failed,
+            packet.battery_v,
+            packet.pyro_v,
         );
         w.offset
     };
@@ -215,6 +275,189 @@ pub fn try_recv_command() -> Option<UmbilicalCommand> {
     COMMANDS.try_receive().ok()
 }
 
+/// Parses an airbrake test profile command. Returns `None` if `data` isn't
+/// one, `Some(None)` if it is but the numbers don't parse. Range checks are
+/// left to `TestProfile::is_valid` so the rejection is logged with the mode
+/// lockout.
+pub fn parse_airbrake_test(data: &[u8]) -> Option<Option<TestProfile>> {
+    if data.len() < 5 || data[0] != b'<' || data[1] != b'A' || data[3] != b',' || data[data.len() - 1] != b'>' {
+        return None;
+    }
+    let kind = data[2];
+    if !matches!(kind, b'S' | b'W' | b'C') {
+        return None;
+    }
+    let body = core::str::from_utf8(&data[4..data.len() - 1]).ok();
+    let mut nums = [0.0f32; 3];
+    let mut count = 0;
+    let parsed = body.and_then(|s| {
+        for part in s.split(',') {
+            *nums.get_mut(count)? = part.trim().parse().ok()?;
+            count += 1;
+        }
+        match (kind, count) {
+            (b'S', 1) => Some(TestProfile::Step { target: nums[0] }),
+            (b'W', 1) => Some(TestProfile::Sweep { rate: nums[0] }),
+            (b'C', 3) => Some(TestProfile::Chirp { f0_hz: nums[0], f1_hz: nums[1], duration_s: nums[2] }),
+            _ => None,
+        }
+    });
+    Some(parsed)
+}
+
+/// Parses `<AK,target_m,max_rate,min_vel_mps,body_cda_m2,brake_cda_m2>`.
+/// Returns `None` if `data` isn't one, `Some(None)` if it is but the numbers
+/// don't parse. Range checks are left to `Config::is_valid`, as for test
+/// profiles.
+pub fn parse_airbrake_config(data: &[u8]) -> Option<Option<AirbrakeConfig>> {
+    if data.len() < 5 || &data[..4] != b"<AK," || data[data.len() - 1] != b'>' {
+        return None;
+    }
+    let body = core::str::from_utf8(&data[4..data.len() - 1]).ok();
+    let mut nums = [0.0f32; 5];
+    let mut count = 0;
+    let parsed = body.and_then(|s| {
+        for part in s.split(',') {
+            *nums.get_mut(count)? = part.trim().parse().ok()?;
+            count += 1;
+        }
+        if count != nums.len() {
+            return None;
+        }
+        Some(AirbrakeConfig {
+            target_apogee_m: nums[0],
+            max_deploy_rate_per_s: nums[1],
+            min_activation_vel_mps: nums[2],
+            drag: DragModel { body_cda_m2: nums[3], brake_cda_m2: nums[4] },
+        })
+    });
+    Some(parsed)
+}
+
+/// Parses `<BK,kp,ki,integral_max_in,alt_upwind_ft,alt_neutral_ft,hysteresis_ft,initial_hold_ms>`.
+/// Returns `None` if `data` isn't one, `Some(None)` if it is but the numbers
+/// don't parse. Range checks are left to `BlimsConfig::is_valid`.
+pub fn parse_blims_config(data: &[u8]) -> Option<Option<BlimsConfig>> {
+    if data.len() < 5 || &data[..4] != b"<BK," || data[data.len() - 1] != b'>' {
+        return None;
+    }
+    let body = core::str::from_utf8(&data[4..data.len() - 1]).ok();
+    let mut nums = [0.0f32; 6];
+    let parsed = body.and_then(|s| {
+        let mut parts = s.split(',').map(str::trim);
+        for n in nums.iter_mut() {
+            *n = parts.next()?.parse().ok()?;
+        }
+        let initial_hold_ms = parts.next()?.parse().ok()?;
+        if parts.next().is_some() {
+            return None;
+        }
+        Some(BlimsConfig {
+            kp: nums[0],
+            ki: nums[1],
+            integral_max_in: nums[2],
+            alt_upwind_ft: nums[3],
+            alt_neutral_ft: nums[4],
+            phase_hysteresis_ft: nums[5],
+            initial_hold_ms,
+        })
+    });
+    Some(parsed)
+}
+
+/// Parses the BLiMS wind profile upload, one command per packet:
+/// `<WP,B,count>` begin, `<WP,L,index,alt_m,from_deg,speed_ms>` layer,
+/// `<WP,C>` commit, `<WP,X>` clear. Returns `None` if `data` isn't one,
+/// `Some(None)` if it is but doesn't parse. Ranges are checked on commit by
+/// `WindProfile::from_layers`.
+pub fn parse_wind_profile(data: &[u8]) -> Option<Option<UmbilicalCommand>> {
+    if data.len() < 6 || &data[..4] != b"<WP," || data[data.len() - 1] != b'>' {
+        return None;
+    }
+    let body = core::str::from_utf8(&data[4..data.len() - 1]).ok();
+    let parsed = body.and_then(|s| {
+        let mut parts = s.split(',').map(str::trim);
+        let cmd = match parts.next()? {
+            "B" => UmbilicalCommand::WindProfileBegin(parts.next()?.parse().ok()?),
+            "L" => {
+                let index = parts.next()?.parse().ok()?;
+                let altitude_m = parts.next()?.parse().ok()?;
+                let from_deg = parts.next()?.parse().ok()?;
+                let speed_ms = parts.next()?.parse().ok()?;
+                UmbilicalCommand::WindProfileLayer(index, WindLayer { altitude_m, from_deg, speed_ms })
+            }
+            "C" => UmbilicalCommand::WindProfileCommit,
+            "X" => UmbilicalCommand::WindProfileClear,
+            _ => return None,
+        };
+        if parts.next().is_some() {
+            return None;
+        }
+        Some(cmd)
+    });
+    Some(parsed)
+}
+
+/// Parses the BLiMS geofence upload, one command per packet: `<GF,B,zones>`
+/// begin, `<GF,Z,zone,K|L,vertices>` zone (keep-out or landing zone),
+/// `<GF,V,zone,vertex,lat,lon>` vertex, `<GF,C>` commit, `<GF,X>` clear.
+/// Returns `None` if `data` isn't one, `Some(None)` if it is but doesn't
+/// parse. Polygons are checked on commit by `Geofence::from_zones`.
+pub fn parse_geofence(data: &[u8]) -> Option<Option<UmbilicalCommand>> {
+    if data.len() < 6 || &data[..4] != b"<GF," || data[data.len() - 1] != b'>' {
+        return None;
+    }
+    let body = core::str::from_utf8(&data[4..data.len() - 1]).ok();
+    let parsed = body.and_then(|s| {
+        let mut parts = s.split(',').map(str::trim);
+        let cmd = match parts.next()? {
+            "B" => UmbilicalCommand::GeofenceBegin(parts.next()?.parse().ok()?),
+            "Z" => {
+                let zone = parts.next()?.parse().ok()?;
+                let kind = match parts.next()? {
+                    "K" => ZoneKind::KeepOut,
+                    "L" => ZoneKind::LandingZone,
+                    _ => return None,
+                };
+                UmbilicalCommand::GeofenceZone(zone, kind, parts.next()?.parse().ok()?)
+            }
+            "V" => {
+                let zone = parts.next()?.parse().ok()?;
+                let vertex = parts.next()?.parse().ok()?;
+                let lat_deg = parts.next()?.parse().ok()?;
+                let lon_deg = parts.next()?.parse().ok()?;
+                UmbilicalCommand::GeofenceVertex(zone, vertex, LatLon::new(lat_deg, lon_deg))
+            }
+            "C" => UmbilicalCommand::GeofenceCommit,
+            "X" => UmbilicalCommand::GeofenceClear,
+            _ => return None,
+        };
+        if parts.next().is_some() {
+            return None;
+        }
+        Some(cmd)
+    });
+    Some(parsed)
+}
+
+/// Removes every `<H>` from a received packet, once the heartbeat has been
+/// recorded, so a command sharing the packet with one still parses. Returns
+/// the length left.
+pub fn strip_heartbeats(data: &mut [u8]) -> usize {
+    let mut len = 0;
+    let mut i = 0;
+    while i < data.len() {
+        if data[i..].starts_with(b"<H>") {
+            i += 3;
+        } else {
+            data[len] = data[i];
+            len += 1;
+            i += 1;
+        }
+    }
+    len
+}
+
 /// Simulation helper: injects a command into the channel as if it came from USB.
 pub fn push_command(cmd: UmbilicalCommand) {
     let _ = COMMANDS.try_send(cmd);
@@ -231,6 +474,7 @@ pub fn inject_heartbeat() {
 /// Logs go out as text (readable in any serial monitor), commands come in as `<X>` tokens.
 /// In release builds the logger is compiled out so the wire carries only
 /// telemetry + explicit `print_str`/`print_bytes` output.
+#[cfg(target_os = "none")]
 pub fn setup(spawner: &Spawner, usb_driver: UsbDriver) {
     let (usb_device, usb_class) = module::init_usb_device(usb_driver);
     let (sender, receiver) = usb_class.split();
@@ -247,13 +491,13 @@ pub fn setup(spawner: &Spawner, usb_driver: UsbDriver) {
 // USB Serial Logger (replaces embassy-usb-logger) — debug builds only
 // ============================================================================
 
-#[cfg(debug_assertions)]
+#[cfg(all(debug_assertions, target_os = "none"))]
 struct UsbSerialLogger;
 
-#[cfg(debug_assertions)]
+#[cfg(all(debug_assertions, target_os = "none"))]
 static LOGGER: UsbSerialLogger = UsbSerialLogger;
 
-#[cfg(debug_assertions)]
+#[cfg(all(debug_assertions, target_os = "none"))]
 fn init_logger() {
     unsafe {
         let _ = log::set_logger_racy(&LOGGER);
@@ -261,7 +505,7 @@ fn init_logger() {
     log::set_max_level(log::LevelFilter::Info);
 }
 
-#[cfg(debug_assertions)]
+#[cfg(all(debug_assertions, target_os = "none"))]
 impl log::Log for UsbSerialLogger {
     fn enabled(&self, metadata: &log::Metadata) -> bool {
         metadata.level() <= log::max_level()
@@ -318,16 +562,25 @@ impl<'a> core::fmt::Write for BufWriter<'a> {
     }
 }
 
+/// Host (SIL) side of the outbound channel: the simulated ground station
+/// drains what `usb_sender_task` would have written to the CDC endpoint.
+#[cfg(not(target_os = "none"))]
+pub async fn recv_outbound() -> heapless::Vec<u8, 64> {
+    RAW_OUTBOUND.receive().await
+}
+
 // ============================================================================
 // USB Tasks
 // ============================================================================
 
+#[cfg(target_os = "none")]
 #[embassy_executor::task]
 async fn usb_task(mut usb_device: UsbDevice<'static, UsbDriver>) -> ! {
     usb_device.run().await
 }
 
 /// Reads text chunks from the outbound channel and writes them to the USB sender.
+#[cfg(target_os = "none")]
 #[embassy_executor::task]
 async fn usb_sender_task(mut sender: Sender<'static, UsbDriver>) -> ! {
     loop {
@@ -345,9 +598,12 @@ async fn usb_sender_task(mut sender: Sender<'static, UsbDriver>) -> ! {
 }
 
 /// Reads USB packets from the host and parses command tokens like `<L>`, `<M>`, etc.
+#[cfg(target_os = "none")]
 #[embassy_executor::task]
 async fn usb_receiver_task(mut receiver: Receiver<'static, UsbDriver>) -> ! {
     let mut buf = [0; 64];
+    #[cfg(feature = "hil")]
+    let mut hil_rx = crate::hil::HilReceiver::new();
     loop {
         receiver.wait_connection().await;
 
@@ -363,14 +619,86 @@ async fn usb_receiver_task(mut receiver: Receiver<'static, UsbDriver>) -> ! {
                 }
             };
 
-            let data = &buf[..n];
-
             // Heartbeat is hot-path: bump the timestamp. Use substring search
             // so we don't ignore heartbeats buffered with other commands.
-            if data.windows(3).any(|w| w == b"<H>") {
+            if buf[..n].windows(3).any(|w| w == b"<H>") {
                 record_heartbeat();
             }
-            if data == b"<H>" {
+
+            // HIL sensor frames (`$HIL,...\n`) may span packets and share one
+            // with commands: the assembler takes the frame bytes and leaves
+            // the rest for the parser below. A packet of frames alone skips
+            // the post-command sleep — frames arrive at sensor rate.
+            #[cfg(feature = "hil")]
+            let n = hil_rx.take_frames(&mut buf[..n]);
+
+            let n = strip_heartbeats(&mut buf[..n]);
+            if n == 0 {
+                continue;
+            }
+            let data = &buf[..n];
+
+            // Variable-length: airbrake test profiles `<AS,frac>`, `<AW,rate>`, `<AC,f0,f1,secs>`.
+            if let Some(profile) = parse_airbrake_test(data) {
+                match profile {
+                    Some(p) => {
+                        COMMANDS.try_send(UmbilicalCommand::AirbrakeTest(p)).ok();
+                    }
+                    None => log::warn!("Umbilical airbrake test parse failed"),
+                }
+                continue;
+            }
+
+            // Variable-length: airbrake controller config `<AK,target,rate,min_vel,body_cda,brake_cda>`.
+            if let Some(config) = parse_airbrake_config(data) {
+                match config {
+                    Some(c) => {
+                        COMMANDS.try_send(UmbilicalCommand::SetAirbrakeConfig(c)).ok();
+                    }
+                    None => log::warn!("Umbilical airbrake config parse failed"),
+                }
+                continue;
+            }
+
+            // Variable-length: BLiMS controller config `<BK,kp,ki,imax,upwind_ft,neutral_ft,hyst_ft,hold_ms>`.
+            if let Some(config) = parse_blims_config(data) {
+                match config {
+                    Some(c) => {
+                        COMMANDS.try_send(UmbilicalCommand::SetBlimsConfig(c)).ok();
+                    }
+                    None => {
+                        log::warn!("Umbilical BLiMS config parse failed");
+                        print_str("$BK,ERR,PARSE\n");
+                    }
+                }
+                continue;
+            }
+
+            // Variable-length: BLiMS wind profile upload `<WP,B|L|C|X,...>`.
+            if let Some(cmd) = parse_wind_profile(data) {
+                match cmd {
+                    Some(c) => {
+                        COMMANDS.try_send(c).ok();
+                    }
+                    None => {
+                        log::warn!("Umbilical wind profile parse failed");
+                        print_str("$WP,ERR,PARSE\n");
+                    }
+                }
+                continue;
+            }
+
+            // Variable-length: BLiMS geofence upload `<GF,B|Z|V|C|X,...>`.
+            if let Some(cmd) = parse_geofence(data) {
+                match cmd {
+                    Some(c) => {
+                        COMMANDS.try_send(c).ok();
+                    }
+                    None => {
+                        log::warn!("Umbilical geofence parse failed");
+                        print_str("$GF,ERR,PARSE\n");
+                    }
+                }
                 continue;
             }
 
@@ -446,6 +774,8 @@ async fn usb_receiver_task(mut receiver: Receiver<'static, UsbDriver>) -> ! {
                 b"<MR>" => Some(UmbilicalCommand::MainMode),
                 b"<A>" => Some(UmbilicalCommand::DeployAirbrakes),
                 b"<a>" => Some(UmbilicalCommand::RetractAirbrakes),
+                b"<AT1>" => Some(UmbilicalCommand::AirbrakeTestEnable),
+                b"<AT0>" => Some(UmbilicalCommand::AirbrakeTestDisable),
                 b"<B>" => Some(UmbilicalCommand::TriggerBLiMS),
                 b"<FU>" => Some(UmbilicalCommand::FaultMode),
                 _ => None,
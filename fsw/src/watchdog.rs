@@ -6,7 +6,8 @@
 //!
 //! Usage:
 //!   - In `main`, call [`init`] once with `Watchdog::new(p.WATCHDOG)`.
-//!   - In the flight loop, call [`feed`] around `execute()` as before.
+//!   - In the flight loop, call [`feed`] before `execute()` and [`feed_for`]
+//!     after it, covering the sleep until the next cycle.
 //!   - Inside long-running inline ops (e.g. `wipe_storage`), call [`feed`]
 //!     between sub-steps so the chip isn't reset mid-operation.
 
@@ -39,3 +40,13 @@ pub fn feed() {
         }
     });
 }
+
+/// Like [`feed`], but with a countdown of `timeout` instead, for a sleep
+/// longer than `WATCHDOG_TIMEOUT_MS` (the beacon-mode loop period).
+pub fn feed_for(timeout: Duration) {
+    WATCHDOG.lock(|cell| {
+        if let Some(wd) = cell.borrow_mut().as_mut() {
+            wd.feed(timeout);
+        }
+    });
+}
@@ -4,6 +4,16 @@
 //! umbilical serial port, captures the binary dump from onboard flash, decodes
 //! it into CSV, and saves it to a timestamped `.csv` file.
 //!
+//! BLiMS diagnostics records (controller internals at the 20 Hz guidance
+//! rate while BLiMS is armed) go to a separate `fsw_<timestamp>_blims.csv`.
+//!
+//! Every row gets an absolute UTC timestamp (`utc_ms`, `utc_iso`) derived
+//! from the session headers and full records the FSW writes once GPS time
+//! sync is available. Rows logged before the fix in the same boot session are
+//! back-filled from the first anchor, so the whole log lines up with
+//! fill-station and ground-station logs. Sessions that never synced are left
+//! blank.
+//!
 //! Within FSW directory:
 //!
 //!     tools\dump_flash.bat
@@ -23,7 +33,7 @@ use std::{
     time::{Duration, Instant},
 };
 
-use chrono::Local;
+use chrono::{DateTime, Local};
 use serialport::SerialPort;
 
 // ── Serial constants ──────────────────────────────────────────────────────────
@@ -46,8 +56,18 @@ const END_MARKER: &[u8]   = b"END FLASH BINARY DUMP";
 
 const FAST_TAG: u8  = 0xFA;
 const FULL_TAG: u8  = 0xFB;
-const FAST_SIZE: usize = 92;  // payload bytes (tag not included) — mirrors FastRecord::SIZE in packet.rs
-const FULL_SIZE: usize = 193; // payload bytes (tag not included) — mirrors Packet::SIZE in packet.rs
+const SESSION_TAG: u8 = 0xFC;
+const BLIMS_DIAG_TAG: u8 = 0xFD;
+const CONFIG_TAG: u8 = 0xFE;
+const FAST_SIZE: usize = 102; // payload bytes (tag not included) — mirrors FastRecord::SIZE in packet.rs
+const FULL_SIZE: usize = 270; // payload bytes (tag not included) — mirrors Packet::SIZE in packet.rs
+const SESSION_SIZE: usize = 22; // payload bytes (tag not included) — mirrors SessionRecord::SIZE in packet.rs
+const BLIMS_DIAG_SIZE: usize = 44; // payload bytes (tag not included) — mirrors BlimsDiagRecord::SIZE in packet.rs
+const CONFIG_SIZE: usize = 34; // payload bytes (tag not included) — mirrors ConfigPacket::SIZE in packet.rs
+
+/// A drop in ms_since_boot larger than this between consecutive records means
+/// the CFC rebooted (covers logs written before session headers existed).
+const REBOOT_BACKSTEP_MS: u32 = 1000;
 
 // Fast record payload offsets (mirrors FastRecord::to_bytes() in packet.rs)
 mod fast {
@@ -83,6 +103,10 @@ mod fast {
     pub const PREDICTED_APOGEE:       usize = 83;  // f32
     pub const BLIMS_BRAKELINE_DIFF:   usize = 87;  // f32
     pub const BLIMS_PHASE_ID:         usize = 91;  // i8
+    pub const AIRBRAKE_MEASURED:      usize = 92;  // f32
+    pub const BLIMS_MEASURED:         usize = 96;  // f32
+    pub const ODRIVE_LINK:            usize = 100; // u8
+    pub const AIRBRAKE_CTRL_OK:       usize = 101; // u8
 }
 
 // Full record payload offsets (mirrors Packet::to_bytes() in packet.rs)
@@ -141,9 +165,76 @@ mod full {
     pub const BLIMS_DOWNWIND_LON:     usize = 181; // f32
     pub const BLIMS_WIND_FROM_DEG:    usize = 185; // f32
     pub const MS_SINCE_BOOT_CFC:      usize = 189; // u32
+    pub const UTC_MS:                 usize = 193; // u64
+    pub const AIRBRAKE_MEASURED:      usize = 201; // f32
+    pub const AIRBRAKE_ODRIVE_STATE:  usize = 205; // u8
+    pub const AIRBRAKE_ODRIVE_ERRORS: usize = 206; // u32
+    pub const AIRBRAKE_ODRIVE_VBUS:   usize = 210; // f32
+    pub const AIRBRAKE_ODRIVE_IBUS:   usize = 214; // f32
+    pub const BLIMS_MEASURED:         usize = 218; // f32
+    pub const BLIMS_ODRIVE_STATE:     usize = 222; // u8
+    pub const BLIMS_ODRIVE_ERRORS:    usize = 223; // u32
+    pub const BLIMS_ODRIVE_VBUS:      usize = 227; // f32
+    pub const BLIMS_ODRIVE_IBUS:      usize = 231; // f32
+    pub const ODRIVE_LINK:            usize = 235; // u8
+    pub const AIRBRAKE_CTRL_OK:       usize = 236; // u8
+    pub const AIRBRAKE_EST_BODY_CDA:  usize = 237; // f32
+    pub const AIRBRAKE_EST_BRAKE_CDA: usize = 241; // f32
+    pub const BLIMS_WIND_EST_SPEED:   usize = 245; // f32
+    pub const BLIMS_WIND_EST_FROM:    usize = 249; // f32
+    pub const BLIMS_DIST_TO_TARGET:   usize = 253; // f32
+    pub const BLIMS_FENCE_FLAGS:      usize = 257; // u8
+    pub const PAYLOAD_LINK:           usize = 258; // u8
+    pub const PAYLOAD_LAST_ACK:       usize = 259; // u8
+    pub const PAYLOAD_STATE:          usize = 260; // u8
+    pub const PAYLOAD_FAILED:         usize = 261; // u8
+    pub const BATTERY_V:              usize = 262; // f32
+    pub const PYRO_V:                 usize = 266; // f32
+}
+
+// Config record payload offsets (mirrors ConfigPacket::to_bytes() in packet.rs)
+mod config {
+    pub const AIRBRAKE_TARGET_APOGEE: usize = 4;   // f32
+    pub const AIRBRAKE_MAX_RATE:      usize = 8;   // f32
+    pub const AIRBRAKE_MIN_VEL:       usize = 12;  // f32
+    pub const AIRBRAKE_BODY_CDA:      usize = 16;  // f32
+    pub const AIRBRAKE_BRAKE_CDA:     usize = 20;  // f32
+    pub const BLIMS_WIND_LAYERS:      usize = 24;  // u8
+    pub const BLIMS_WIND_CHECKSUM:    usize = 25;  // u32
+    pub const BLIMS_FENCE_ZONES:      usize = 29;  // u8
+    pub const BLIMS_FENCE_CHECKSUM:   usize = 30;  // u32
+}
+
+// Session record payload offsets (mirrors SessionRecord::to_bytes() in packet.rs)
+mod session {
+    pub const KIND:                   usize = 0;   // u8 (0 = boot, 1 = time sync)
+    pub const TIME_SOURCE:            usize = 1;   // u8 (0 = none, 1 = NAV-PVT, 2 = TIMEPULSE)
+    pub const MS_SINCE_BOOT:          usize = 2;   // u32
+    pub const UTC_MS:                 usize = 6;   // u64
+    pub const T_ACC_NS:               usize = 14;  // u32
+
+    pub const KIND_BOOT: u8 = 0;
+}
+
+// BLiMS diagnostics record payload offsets (mirrors BlimsDiagRecord::to_bytes() in packet.rs)
+mod blims_diag {
+    pub const MS_SINCE_BOOT:          usize = 0;   // u32
+    pub const PHASE_ID:               usize = 4;   // i8
+    pub const GPS_VALID:              usize = 5;   // u8
+    pub const FIX_TYPE:               usize = 6;   // u8
+    pub const HEADING_VALID:          usize = 7;   // u8
+    pub const DESIRED_HEADING:        usize = 8;   // f32
+    pub const HEADING:                usize = 12;  // f32
+    pub const HEADING_ERROR:          usize = 16;  // f32
+    pub const PID_P:                  usize = 20;  // f32
+    pub const PID_I:                  usize = 24;  // f32
+    pub const ERROR_INTEGRAL:         usize = 28;  // f32
+    pub const DT:                     usize = 32;  // f32
+    pub const BRAKELINE_DIFF:         usize = 36;  // f32
+    pub const ACTIVATION_MS:          usize = 40;  // u32 (0 = not yet)
 }
 
-// ── Carry-forward state for GPS / BLiMS (absent in fast records) ─────────────
+// ── Carry-forward state for GPS / BLiMS / ODrive / airbrake config (absent in fast records)
 
 #[derive(Default, Clone)]
 struct SlowFields {
@@ -169,6 +260,35 @@ struct SlowFields {
     blims_downwind_lat:  f32,
     blims_downwind_lon:  f32,
     blims_wind_from_deg: f32,
+    airbrake_odrive_state:  u8,
+    airbrake_odrive_errors: u32,
+    airbrake_odrive_vbus:   f32,
+    airbrake_odrive_ibus:   f32,
+    blims_odrive_state:     u8,
+    blims_odrive_errors:    u32,
+    blims_odrive_vbus:      f32,
+    blims_odrive_ibus:      f32,
+    airbrake_target_apogee: f32,
+    airbrake_max_rate:      f32,
+    airbrake_min_vel:       f32,
+    airbrake_body_cda:      f32,
+    airbrake_brake_cda:     f32,
+    airbrake_est_body_cda:  f32,
+    airbrake_est_brake_cda: f32,
+    blims_wind_est_speed:   f32,
+    blims_wind_est_from:    f32,
+    blims_wind_layers:      u8,
+    blims_wind_checksum:    u32,
+    blims_dist_to_target:   f32,
+    blims_fence_flags:      u8,
+    blims_fence_zones:      u8,
+    blims_fence_checksum:   u32,
+    payload_link:           u8,
+    payload_last_ack:       u8,
+    payload_state:          u8,
+    payload_failed:         u8,
+    battery_v:              f32,
+    pyro_v:                 f32,
 }
 
 // ── Decode helpers ────────────────────────────────────────────────────────────
@@ -185,8 +305,50 @@ fn f32le(b: &[u8], off: usize) -> f32 {
 fn f64le(b: &[u8], off: usize) -> f64 {
     f64::from_le_bytes(b[off..off+8].try_into().unwrap())
 }
+fn u64le(b: &[u8], off: usize) -> u64 {
+    u64::from_le_bytes(b[off..off+8].try_into().unwrap())
+}
+
+/// One decoded data row, waiting for its UTC column.
+struct Row {
+    session: usize,
+    ms_since_boot: u32,
+    /// utc_ms − ms_since_boot from the latest anchor at or before this row
+    /// in the same session (None if the session had not synced yet).
+    offset: Option<i64>,
+    /// Every column up to and including ms_since_boot_cfc, no trailing newline
+    /// (for BLiMS diagnostics rows, every column before utc_ms).
+    csv: String,
+    /// The ODrive, controller-health, airbrake config, drag estimate, BLiMS wind, distance and geofence columns that follow utc_ms,utc_iso.
+    tail: String,
+}
+
+/// Per-session decode state: the first and latest boot-ms -> UTC offsets.
+#[derive(Default)]
+struct Session {
+    first: Option<i64>,
+    latest: Option<i64>,
+    rows: usize,
+}
+
+impl Session {
+    fn anchor(&mut self, ms: u32, utc_ms: u64) {
+        if utc_ms == 0 {
+            return;
+        }
+        let off = utc_ms as i64 - ms as i64;
+        self.first.get_or_insert(off);
+        self.latest = Some(off);
+    }
+}
+
+fn format_utc(utc_ms: i64) -> String {
+    DateTime::from_timestamp_millis(utc_ms)
+        .map(|t| t.format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string())
+        .unwrap_or_default()
+}
 
-/// Emit one CSV data row from a full-record payload (193 bytes).
+/// Emit one CSV data row from a full-record payload (270 bytes).
 /// Column order mirrors Packet::CSV_HEADER in packet.rs exactly.
 fn csv_from_full(p: &[u8], slow: &mut SlowFields) -> String {
     slow.latitude            = f32le(p, full::LATITUDE);
@@ -213,7 +375,7 @@ fn csv_from_full(p: &[u8], slow: &mut SlowFields) -> String {
     slow.blims_wind_from_deg = f32le(p, full::BLIMS_WIND_FROM_DEG);
 
     format!(
-        "{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{}\n",
+        "{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{}",
         u32le(p, full::FLIGHT_MODE),
         f32le(p, full::PRESSURE),
         f32le(p, full::TEMP),
@@ -271,11 +433,11 @@ fn csv_from_full(p: &[u8], slow: &mut SlowFields) -> String {
     )
 }
 
-/// Emit one CSV data row from a fast-record payload (92 bytes), filling
+/// Emit one CSV data row from a fast-record payload (102 bytes), filling
 /// GPS / BLiMS-config columns (absent in fast records) from carry-forward `slow`.
 fn csv_from_fast(p: &[u8], slow: &SlowFields) -> String {
     format!(
-        "{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{}\n",
+        "{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{}",
         u32le(p, fast::FLIGHT_MODE),
         f32le(p, fast::PRESSURE),
         f32le(p, fast::TEMP),
@@ -333,16 +495,126 @@ fn csv_from_fast(p: &[u8], slow: &SlowFields) -> String {
     )
 }
 
-/// Walk the raw binary buffer and decode all records into CSV rows.
+/// Refreshes the carry-forward airbrake config, wind profile and geofence
+/// echoes from a config-record payload. The FSW writes one at boot and
+/// whenever the config changes, so rows after it carry its values.
+fn apply_config(p: &[u8], slow: &mut SlowFields) {
+    slow.airbrake_target_apogee = f32le(p, config::AIRBRAKE_TARGET_APOGEE);
+    slow.airbrake_max_rate      = f32le(p, config::AIRBRAKE_MAX_RATE);
+    slow.airbrake_min_vel       = f32le(p, config::AIRBRAKE_MIN_VEL);
+    slow.airbrake_body_cda      = f32le(p, config::AIRBRAKE_BODY_CDA);
+    slow.airbrake_brake_cda     = f32le(p, config::AIRBRAKE_BRAKE_CDA);
+    slow.blims_wind_layers      = p[config::BLIMS_WIND_LAYERS];
+    slow.blims_wind_checksum    = u32le(p, config::BLIMS_WIND_CHECKSUM);
+    slow.blims_fence_zones      = p[config::BLIMS_FENCE_ZONES];
+    slow.blims_fence_checksum   = u32le(p, config::BLIMS_FENCE_CHECKSUM);
+}
+
+/// Columns after utc_iso from a full-record payload; refreshes the
+/// carry-forward ODrive status, drag estimate, BLiMS wind, distance, geofence, payload link and supply fields.
+fn tail_csv_from_full(p: &[u8], slow: &mut SlowFields) -> String {
+    slow.airbrake_odrive_state  = p[full::AIRBRAKE_ODRIVE_STATE];
+    slow.airbrake_odrive_errors = u32le(p, full::AIRBRAKE_ODRIVE_ERRORS);
+    slow.airbrake_odrive_vbus   = f32le(p, full::AIRBRAKE_ODRIVE_VBUS);
+    slow.airbrake_odrive_ibus   = f32le(p, full::AIRBRAKE_ODRIVE_IBUS);
+    slow.blims_odrive_state     = p[full::BLIMS_ODRIVE_STATE];
+    slow.blims_odrive_errors    = u32le(p, full::BLIMS_ODRIVE_ERRORS);
+    slow.blims_odrive_vbus      = f32le(p, full::BLIMS_ODRIVE_VBUS);
+    slow.blims_odrive_ibus      = f32le(p, full::BLIMS_ODRIVE_IBUS);
+    slow.airbrake_est_body_cda  = f32le(p, full::AIRBRAKE_EST_BODY_CDA);
+    slow.airbrake_est_brake_cda = f32le(p, full::AIRBRAKE_EST_BRAKE_CDA);
+    slow.blims_wind_est_speed   = f32le(p, full::BLIMS_WIND_EST_SPEED);
+    slow.blims_wind_est_from    = f32le(p, full::BLIMS_WIND_EST_FROM);
+    slow.blims_dist_to_target   = f32le(p, full::BLIMS_DIST_TO_TARGET);
+    slow.blims_fence_flags      = p[full::BLIMS_FENCE_FLAGS];
+    slow.payload_link           = p[full::PAYLOAD_LINK];
+    slow.payload_last_ack       = p[full::PAYLOAD_LAST_ACK];
+    slow.payload_state          = p[full::PAYLOAD_STATE];
+    slow.payload_failed         = p[full::PAYLOAD_FAILED];
+    slow.battery_v              = f32le(p, full::BATTERY_V);
+    slow.pyro_v                 = f32le(p, full::PYRO_V);
+    tail_csv(
+        f32le(p, full::AIRBRAKE_MEASURED),
+        f32le(p, full::BLIMS_MEASURED),
+        p[full::ODRIVE_LINK],
+        p[full::AIRBRAKE_CTRL_OK],
+        slow,
+    )
+}
+
+/// Columns after utc_iso from a fast-record payload (ODrive status, airbrake
+/// config, drag estimate, BLiMS wind, distance, geofence, payload link and
+/// supply from carry-forward `slow`).
+fn tail_csv_from_fast(p: &[u8], slow: &SlowFields) -> String {
+    tail_csv(
+        f32le(p, fast::AIRBRAKE_MEASURED),
+        f32le(p, fast::BLIMS_MEASURED),
+        p[fast::ODRIVE_LINK],
+        p[fast::AIRBRAKE_CTRL_OK],
+        slow,
+    )
+}
+
+fn tail_csv(airbrake_measured: f32, blims_measured: f32, link: u8, ctrl_ok: u8, slow: &SlowFields) -> String {
+    format!(
+        "{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{}",
+        airbrake_measured,
+        slow.airbrake_odrive_state,
+        slow.airbrake_odrive_errors,
+        slow.airbrake_odrive_vbus,
+        slow.airbrake_odrive_ibus,
+        blims_measured,
+        slow.blims_odrive_state,
+        slow.blims_odrive_errors,
+        slow.blims_odrive_vbus,
+        slow.blims_odrive_ibus,
+        link,
+        ctrl_ok,
+        slow.airbrake_target_apogee,
+        slow.airbrake_max_rate,
+        slow.airbrake_min_vel,
+        slow.airbrake_body_cda,
+        slow.airbrake_brake_cda,
+        slow.airbrake_est_body_cda,
+        slow.airbrake_est_brake_cda,
+        slow.blims_wind_est_speed,
+        slow.blims_wind_est_from,
+        slow.blims_wind_layers,
+        slow.blims_wind_checksum,
+        slow.blims_dist_to_target,
+        slow.blims_fence_flags,
+        slow.blims_fence_zones,
+        slow.blims_fence_checksum,
+        slow.payload_link,
+        slow.payload_last_ack,
+        slow.payload_state,
+        slow.payload_failed,
+        slow.battery_v,
+        slow.pyro_v,
+    )
+}
+
+/// Walk the raw binary buffer and decode all records into rows.
 /// Stops at the first run of 0xFF bytes (erased flash) or end of buffer.
-/// Returns (fast_count, full_count, skipped_bytes).
-fn decode_binary(buf: &[u8], csv_rows: &mut Vec<String>) -> (usize, usize, usize) {
+/// Returns (fast_count, full_count, session_count, skipped_bytes).
+fn decode_binary(buf: &[u8], rows: &mut Vec<Row>, blims_rows: &mut Vec<Row>) -> (usize, usize, usize, usize) {
     let mut slow = SlowFields::default();
     let mut saw_full = false;
     let mut fast_count = 0usize;
     let mut full_count = 0usize;
     let mut skipped   = 0usize;
     let mut i = 0usize;
+    let mut sessions: Vec<Session> = vec![Session::default()];
+    let mut last_ms: Option<u32> = None;
+
+    // Open a new boot session unless the current one is still unused.
+    fn next_session(sessions: &mut Vec<Session>) {
+        let cur = sessions.last().unwrap();
+        if cur.rows > 0 || cur.first.is_some() {
+            sessions.push(Session::default());
+        }
+    }
+    let rebooted = |last: Option<u32>, ms: u32| last.is_some_and(|l| ms + REBOOT_BACKSTEP_MS < l);
 
     while i < buf.len() {
         // Erased flash sentinel — data ends here.
@@ -355,10 +627,21 @@ fn decode_binary(buf: &[u8], csv_rows: &mut Vec<String>) -> (usize, usize, usize
                 let end = i + 1 + FAST_SIZE;
                 if end > buf.len() { break; }
                 let payload = &buf[i+1..end];
-                if !saw_full {
-                    // GPS/BLiMS columns will be 0 — warn once at end.
+                let ms = u32le(payload, fast::MS_SINCE_BOOT);
+                if rebooted(last_ms, ms) {
+                    next_session(&mut sessions);
                 }
-                csv_rows.push(csv_from_fast(payload, &slow));
+                last_ms = Some(ms);
+                let idx = sessions.len() - 1;
+                let cur = &mut sessions[idx];
+                cur.rows += 1;
+                rows.push(Row {
+                    session:       idx,
+                    ms_since_boot: ms,
+                    offset:        cur.latest,
+                    csv:           csv_from_fast(payload, &slow),
+                    tail:          tail_csv_from_fast(payload, &slow),
+                });
                 fast_count += 1;
                 i = end;
             }
@@ -366,11 +649,75 @@ fn decode_binary(buf: &[u8], csv_rows: &mut Vec<String>) -> (usize, usize, usize
                 let end = i + 1 + FULL_SIZE;
                 if end > buf.len() { break; }
                 let payload = &buf[i+1..end];
-                csv_rows.push(csv_from_full(payload, &mut slow));
+                let ms = u32le(payload, full::MS_SINCE_BOOT_CFC);
+                if rebooted(last_ms, ms) {
+                    next_session(&mut sessions);
+                }
+                last_ms = Some(ms);
+                let idx = sessions.len() - 1;
+                let cur = &mut sessions[idx];
+                cur.anchor(ms, u64le(payload, full::UTC_MS));
+                cur.rows += 1;
+                rows.push(Row {
+                    session:       idx,
+                    ms_since_boot: ms,
+                    offset:        cur.latest,
+                    csv:           csv_from_full(payload, &mut slow),
+                    tail:          tail_csv_from_full(payload, &mut slow),
+                });
                 full_count += 1;
                 saw_full = true;
                 i = end;
             }
+            SESSION_TAG => {
+                let end = i + 1 + SESSION_SIZE;
+                if end > buf.len() { break; }
+                let payload = &buf[i+1..end];
+                let ms = u32le(payload, session::MS_SINCE_BOOT);
+                let utc_ms = u64le(payload, session::UTC_MS);
+                if payload[session::KIND] == session::KIND_BOOT || rebooted(last_ms, ms) {
+                    next_session(&mut sessions);
+                }
+                last_ms = Some(ms);
+                sessions.last_mut().unwrap().anchor(ms, utc_ms);
+                if utc_ms != 0 {
+                    println!(
+                        "  Session {}: UTC {} (time source {}, tAcc {} ns)",
+                        sessions.len() - 1,
+                        format_utc(utc_ms as i64),
+                        payload[session::TIME_SOURCE],
+                        u32le(payload, session::T_ACC_NS),
+                    );
+                }
+                i = end;
+            }
+            BLIMS_DIAG_TAG => {
+                let end = i + 1 + BLIMS_DIAG_SIZE;
+                if end > buf.len() { break; }
+                let payload = &buf[i+1..end];
+                let ms = u32le(payload, blims_diag::MS_SINCE_BOOT);
+                if rebooted(last_ms, ms) {
+                    next_session(&mut sessions);
+                }
+                last_ms = Some(ms);
+                let idx = sessions.len() - 1;
+                let cur = &mut sessions[idx];
+                cur.rows += 1;
+                blims_rows.push(Row {
+                    session:       idx,
+                    ms_since_boot: ms,
+                    offset:        cur.latest,
+                    csv:           csv_from_blims_diag(payload),
+                    tail:          String::new(),
+                });
+                i = end;
+            }
+            CONFIG_TAG => {
+                let end = i + 1 + CONFIG_SIZE;
+                if end > buf.len() { break; }
+                apply_config(&buf[i+1..end], &mut slow);
+                i = end;
+            }
             _ => {
                 // Unknown byte — skip forward one byte and keep scanning.
                 skipped += 1;
@@ -387,7 +734,56 @@ fn decode_binary(buf: &[u8], csv_rows: &mut Vec<String>) -> (usize, usize, usize
         );
     }
 
-    (fast_count, full_count, skipped)
+    // Back-fill rows logged before their session's first anchor.
+    let mut unsynced = 0usize;
+    for row in rows.iter_mut().chain(blims_rows.iter_mut()) {
+        if row.offset.is_none() {
+            row.offset = sessions[row.session].first;
+        }
+        if row.offset.is_none() {
+            unsynced += 1;
+        }
+    }
+    if unsynced > 0 {
+        eprintln!(
+            "WARNING: {} rows come from sessions without GPS time sync — utc columns left blank.",
+            unsynced
+        );
+    }
+
+    (fast_count, full_count, sessions.len(), skipped)
+}
+
+/// Emit one BLiMS diagnostics CSV row (without the utc columns) from a
+/// diag-record payload (44 bytes).
+fn csv_from_blims_diag(p: &[u8]) -> String {
+    format!(
+        "{},{},{},{},{},{},{},{},{},{},{},{},{},{}",
+        u32le(p, blims_diag::MS_SINCE_BOOT),
+        p[blims_diag::PHASE_ID] as i8,
+        p[blims_diag::GPS_VALID],
+        p[blims_diag::FIX_TYPE],
+        p[blims_diag::HEADING_VALID],
+        f32le(p, blims_diag::DESIRED_HEADING),
+        f32le(p, blims_diag::HEADING),
+        f32le(p, blims_diag::HEADING_ERROR),
+        f32le(p, blims_diag::PID_P),
+        f32le(p, blims_diag::PID_I),
+        f32le(p, blims_diag::ERROR_INTEGRAL),
+        f32le(p, blims_diag::DT),
+        f32le(p, blims_diag::BRAKELINE_DIFF),
+        u32le(p, blims_diag::ACTIVATION_MS),
+    )
+}
+
+/// Create `filename` in the working directory, or exit.
+fn create_csv(filename: &str) -> (std::path::PathBuf, BufWriter<File>) {
+    let out_path = std::env::current_dir().unwrap_or_default().join(filename);
+    let file = File::create(&out_path).unwrap_or_else(|e| {
+        eprintln!("ERROR: Could not create output file: {}", e);
+        std::process::exit(1);
+    });
+    (out_path, BufWriter::new(file))
 }
 
 // ── Port auto-detection ───────────────────────────────────────────────────────
@@ -582,12 +978,15 @@ fn main() {
     // ── Decode binary → CSV ───────────────────────────────────────────────────
 
     println!("Decoding {} binary bytes...", binary_buf.len());
-    let mut csv_rows: Vec<String> = Vec::new();
-    let (fast_count, full_count, skipped) = decode_binary(&binary_buf, &mut csv_rows);
+    let mut csv_rows: Vec<Row> = Vec::new();
+    let mut blims_rows: Vec<Row> = Vec::new();
+    let (fast_count, full_count, session_count, skipped) =
+        decode_binary(&binary_buf, &mut csv_rows, &mut blims_rows);
     println!(
-        "  {} fast records + {} full records = {} total rows  ({} bytes skipped)",
-        fast_count, full_count, csv_rows.len(), skipped
+        "  {} fast records + {} full records = {} total rows across {} session(s)  ({} bytes skipped)",
+        fast_count, full_count, csv_rows.len(), session_count, skipped
     );
+    println!("  {} BLiMS diagnostics records", blims_rows.len());
 
     if csv_rows.is_empty() {
         println!("WARNING: No valid records decoded — flash may contain old CSV data.");
@@ -598,16 +997,11 @@ fn main() {
     // ── Write CSV ─────────────────────────────────────────────────────────────
 
     let timestamp = Local::now().format("%Y%m%d_%H%M%S").to_string();
-    let filename  = format!("fsw_{}.csv", timestamp);
-    let out_path  = std::env::current_dir().unwrap_or_default().join(&filename);
+    let (out_path, mut writer) = create_csv(&format!("fsw_{}.csv", timestamp));
 
-    let file = File::create(&out_path).unwrap_or_else(|e| {
-        eprintln!("ERROR: Could not create output file: {}", e);
-        std::process::exit(1);
-    });
-    let mut writer = BufWriter::new(file);
-
-    // Header row (matches Packet::CSV_HEADER in packet.rs exactly)
+    // Header row (matches Packet::CSV_HEADER in packet.rs, plus utc_iso after utc_ms).
+    // utc_ms here is the resolved session time, so it is filled in for fast
+    // records and for rows logged before the GPS fix as well.
     writeln!(writer,
         "flight_mode,pressure,temp,altitude,latitude,longitude,num_satellites,timestamp,\
          mag_x,mag_y,mag_z,accel_x,accel_y,accel_z,gyro_x,gyro_y,gyro_z,\
@@ -617,14 +1011,59 @@ fn main() {
          vel_n,vel_e,vel_d,g_speed,s_acc,head_acc,fix_type,head_mot,\
          blims_brakeline_diff,blims_phase_id,blims_pid_p,blims_pid_i,blims_bearing,\
          blims_upwind_lat,blims_upwind_lon,blims_downwind_lat,blims_downwind_lon,\
-         blims_wind_from_deg,ms_since_boot_cfc"
+         blims_wind_from_deg,ms_since_boot_cfc,utc_ms,utc_iso,\
+         airbrake_measured_deployment,airbrake_odrive_state,airbrake_odrive_errors,\
+         airbrake_odrive_vbus,airbrake_odrive_ibus,\
+         blims_measured_brakeline,blims_odrive_state,blims_odrive_errors,\
+         blims_odrive_vbus,blims_odrive_ibus,odrive_link,airbrake_ctrl_ok,\
+         airbrake_target_apogee,airbrake_max_rate,airbrake_min_vel,\
+         airbrake_body_cda,airbrake_brake_cda,\
+         airbrake_est_body_cda,airbrake_est_brake_cda,\
+         blims_wind_est_speed,blims_wind_est_from_deg,\
+         blims_wind_layers,blims_wind_checksum,blims_dist_to_target,\
+         blims_fence_flags,blims_fence_zones,blims_fence_checksum,\
+         payload_link,payload_last_ack,payload_state,payload_failed,\
+         battery_v,pyro_v"
     ).expect("Failed to write header");
 
     for row in &csv_rows {
-        write!(writer, "{}", row).expect("Failed to write row");
+        match row.offset {
+            Some(off) => {
+                let utc_ms = row.ms_since_boot as i64 + off;
+                writeln!(writer, "{},{},{},{}", row.csv, utc_ms, format_utc(utc_ms), row.tail)
+            }
+            None => writeln!(writer, "{},,,{}", row.csv, row.tail),
+        }
+        .expect("Failed to write row");
     }
     writer.flush().expect("Failed to flush file");
 
     println!("Saved  →  {}", out_path.display());
     println!("         {} data rows", csv_rows.len());
+
+    if blims_rows.is_empty() {
+        return;
+    }
+    let (out_path, mut writer) = create_csv(&format!("fsw_{}_blims.csv", timestamp));
+    // Columns mirror BlimsDiagRecord in packet.rs; angles in degrees, the
+    // integral in deg·s, dt in s, the brakeline differential in inches.
+    writeln!(writer,
+        "ms_since_boot_cfc,phase_id,gps_valid,fix_type,heading_valid,\
+         desired_heading,heading,heading_error,pid_p,pid_i,error_integral,dt,\
+         brakeline_diff,activation_ms,utc_ms,utc_iso"
+    ).expect("Failed to write header");
+    for row in &blims_rows {
+        match row.offset {
+            Some(off) => {
+                let utc_ms = row.ms_since_boot as i64 + off;
+                writeln!(writer, "{},{},{}", row.csv, utc_ms, format_utc(utc_ms))
+            }
+            None => writeln!(writer, "{},,", row.csv),
+        }
+        .expect("Failed to write row");
+    }
+    writer.flush().expect("Failed to flush file");
+
+    println!("Saved  →  {}", out_path.display());
+    println!("         {} BLiMS diagnostics rows", blims_rows.len());
 }
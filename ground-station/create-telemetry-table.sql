@@ -72,6 +72,41 @@ CREATE TABLE telemetry_data (
     blims_downwind_lon DOUBLE PRECISION,   -- f32
     blims_wind_from_deg DOUBLE PRECISION,  -- f32
 
+    -- Timing
+    utc_ms BIGINT,                         -- u64 (UTC Unix epoch ms, 0 until GPS time sync)
+
+    -- ODrive Telemetry
+    airbrake_measured_deployment DOUBLE PRECISION, -- f32
+    airbrake_odrive_state SMALLINT,        -- u8
+    airbrake_odrive_errors BIGINT,         -- u32
+    airbrake_odrive_vbus DOUBLE PRECISION, -- f32 (V)
+    airbrake_odrive_ibus DOUBLE PRECISION, -- f32 (A)
+    blims_measured_brakeline DOUBLE PRECISION, -- f32 (inches)
+    blims_odrive_state SMALLINT,           -- u8
+    blims_odrive_errors BIGINT,            -- u32
+    blims_odrive_vbus DOUBLE PRECISION,    -- f32 (V)
+    blims_odrive_ibus DOUBLE PRECISION,    -- f32 (A)
+    odrive_link SMALLINT,                  -- u8 (bit0 airbrake, bit1 BLiMS)
+
+    -- Airbrake Controller & BLiMS Estimates
+    airbrake_ctrl_ok SMALLINT,             -- u8
+    airbrake_est_body_cda DOUBLE PRECISION, -- f32 (m^2)
+    airbrake_est_brake_cda DOUBLE PRECISION, -- f32 (m^2)
+    blims_wind_est_speed DOUBLE PRECISION, -- f32 (m/s)
+    blims_wind_est_from_deg DOUBLE PRECISION, -- f32
+    blims_dist_to_target DOUBLE PRECISION, -- f32 (m)
+    blims_fence_flags SMALLINT,            -- u8
+
+    -- Payload Link
+    payload_link SMALLINT,                 -- u8 (0=never heard, 1=up, 2=lost)
+    payload_last_ack SMALLINT,             -- u8
+    payload_state SMALLINT,                -- u8
+    payload_failed SMALLINT,               -- u8
+
+    -- Supply Rails
+    battery_v DOUBLE PRECISION,            -- f32 (V)
+    pyro_v DOUBLE PRECISION,               -- f32 (V)
+
     -- Fill Station Specific (Umbilical)
     pt_1_pressure DOUBLE PRECISION,    -- Fill Station PT1
     pt_2_pressure DOUBLE PRECISION,    -- Fill Station PT2
@@ -85,3 +120,46 @@ CREATE TABLE telemetry_data (
 
 SELECT create_hypertable('telemetry_data', 'time');
 CREATE INDEX idx_unit_id_time ON telemetry_data (unit_id, time DESC);
+
+-- Config frames ("CFG!", 1 Hz), published by RATS on rats/config/<unit_id>
+CREATE TABLE config_data (
+    time TIMESTAMPTZ NOT NULL,         -- Added by MQTT Broker
+    unit_id SMALLINT NOT NULL,         -- ID of source (1=RATS, etc.)
+    sync_word BIGINT,                  -- 32-bit unsigned integer
+
+    -- Rust 'ConfigPacket' Struct
+    ms_since_boot_cfc BIGINT,              -- u32
+    airbrake_target_apogee DOUBLE PRECISION, -- f32 (m AGL)
+    airbrake_max_rate DOUBLE PRECISION,    -- f32 (deployment/s)
+    airbrake_min_vel DOUBLE PRECISION,     -- f32 (m/s)
+    airbrake_body_cda DOUBLE PRECISION,    -- f32 (m^2)
+    airbrake_brake_cda DOUBLE PRECISION,   -- f32 (m^2)
+    blims_wind_layers SMALLINT,            -- u8 (0 = no profile)
+    blims_wind_checksum BIGINT,            -- u32
+    blims_fence_zones SMALLINT,            -- u8 (0 = no geofence)
+    blims_fence_checksum BIGINT            -- u32
+);
+
+SELECT create_hypertable('config_data', 'time');
+CREATE INDEX idx_config_unit_id_time ON config_data (unit_id, time DESC);
+
+-- Recovery beacons ("BCN!", every 5 s after landing), published by RATS on rats/beacon/<unit_id>
+CREATE TABLE beacon_data (
+    time TIMESTAMPTZ NOT NULL,         -- Added by MQTT Broker
+    unit_id SMALLINT NOT NULL,         -- ID of source (1=RATS, etc.)
+    sync_word BIGINT,                  -- 32-bit unsigned integer
+
+    -- Rust 'BeaconPacket' Struct
+    ms_since_boot_cfc BIGINT,          -- u32
+    flight_mode SMALLINT,              -- u8
+    num_satellites SMALLINT,           -- u8
+    health INTEGER,                    -- u16 (state::health bits)
+    latitude DOUBLE PRECISION,         -- f32 (last 3D fix)
+    longitude DOUBLE PRECISION,        -- f32
+    fix_age_s BIGINT,                  -- u32 (4294967295 = never)
+    battery_v DOUBLE PRECISION,        -- f32 (V)
+    apogee_ft BIGINT                   -- u32
+);
+
+SELECT create_hypertable('beacon_data', 'time');
+CREATE INDEX idx_beacon_unit_id_time ON beacon_data (unit_id, time DESC);
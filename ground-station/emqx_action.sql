@@ -74,6 +74,41 @@ INSERT INTO telemetry_data (
     blims_downwind_lon,
     blims_wind_from_deg,
 
+    -- Timing
+    utc_ms,
+
+    -- ODrive Telemetry
+    airbrake_measured_deployment,
+    airbrake_odrive_state,
+    airbrake_odrive_errors,
+    airbrake_odrive_vbus,
+    airbrake_odrive_ibus,
+    blims_measured_brakeline,
+    blims_odrive_state,
+    blims_odrive_errors,
+    blims_odrive_vbus,
+    blims_odrive_ibus,
+    odrive_link,
+
+    -- Airbrake Controller & BLiMS Estimates
+    airbrake_ctrl_ok,
+    airbrake_est_body_cda,
+    airbrake_est_brake_cda,
+    blims_wind_est_speed,
+    blims_wind_est_from_deg,
+    blims_dist_to_target,
+    blims_fence_flags,
+
+    -- Payload Link
+    payload_link,
+    payload_last_ack,
+    payload_state,
+    payload_failed,
+
+    -- Supply Rails
+    battery_v,
+    pyro_v,
+
     -- Fill Station Specific (Umbilical)
     pt_1_pressure,
     pt_2_pressure,
@@ -159,6 +194,41 @@ INSERT INTO telemetry_data (
     ${blims_downwind_lon},
     ${blims_wind_from_deg},
 
+    -- Timing
+    ${utc_ms},
+
+    -- ODrive Telemetry
+    ${airbrake_measured_deployment},
+    ${airbrake_odrive_state},
+    ${airbrake_odrive_errors},
+    ${airbrake_odrive_vbus},
+    ${airbrake_odrive_ibus},
+    ${blims_measured_brakeline},
+    ${blims_odrive_state},
+    ${blims_odrive_errors},
+    ${blims_odrive_vbus},
+    ${blims_odrive_ibus},
+    ${odrive_link},
+
+    -- Airbrake Controller & BLiMS Estimates
+    ${airbrake_ctrl_ok},
+    ${airbrake_est_body_cda},
+    ${airbrake_est_brake_cda},
+    ${blims_wind_est_speed},
+    ${blims_wind_est_from_deg},
+    ${blims_dist_to_target},
+    ${blims_fence_flags},
+
+    -- Payload Link
+    ${payload_link},
+    ${payload_last_ack},
+    ${payload_state},
+    ${payload_failed},
+
+    -- Supply Rails
+    ${battery_v},
+    ${pyro_v},
+
     -- Fill Station Specific
     ${pt_1_pressure},
     ${pt_2_pressure},
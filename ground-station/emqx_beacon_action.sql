@@ -0,0 +1,41 @@
+INSERT INTO beacon_data (
+    -- The Primary Live X-Axis Clock
+    time,
+    
+    -- Unit ID
+    unit_id,
+
+    -- Top-Level Packet Info
+    sync_word,
+
+    -- Rust 'BeaconPacket' Struct
+    ms_since_boot_cfc,
+    flight_mode,
+    num_satellites,
+    health,
+    latitude,
+    longitude,
+    fix_age_s,
+    battery_v,
+    apogee_ft
+) VALUES (
+    -- Convert EMQX's internal arrival clock into a standard PostgreSQL TIMESTAMPTZ
+    to_timestamp(${broker_arrival_ms} / 1000.0),
+    
+    -- Unit ID
+    ${unit_id},
+
+    -- Top-Level Packet Info
+    ${sync_word},
+
+    -- Rust 'BeaconPacket' Struct
+    ${ms_since_boot_cfc},
+    ${flight_mode},
+    ${num_satellites},
+    ${health},
+    ${latitude},
+    ${longitude},
+    ${fix_age_s},
+    ${battery_v},
+    ${apogee_ft}
+);
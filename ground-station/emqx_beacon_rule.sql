@@ -0,0 +1,21 @@
+SELECT
+    -- Top-Level Packet Info
+    timestamp as broker_arrival_ms,
+    payload.sync_word as sync_word,
+
+    -- Rust 'BeaconPacket' Struct
+    payload.ms_since_boot_cfc as ms_since_boot_cfc,
+    payload.flight_mode as flight_mode,
+    payload.num_satellites as num_satellites,
+    payload.health as health,
+    payload.latitude as latitude,
+    payload.longitude as longitude,
+    payload.fix_age_s as fix_age_s,
+    payload.battery_v as battery_v,
+    payload.apogee_ft as apogee_ft,
+
+    -- Unit ID routing (Dynamically grabbed from topic "rats/beacon/1")
+    int(nth(3, split(topic, '/'))) as unit_id
+  
+FROM
+    "rats/beacon/+"
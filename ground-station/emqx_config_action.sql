@@ -0,0 +1,43 @@
+INSERT INTO config_data (
+    -- The Primary Live X-Axis Clock
+    time,
+    
+    -- Unit ID
+    unit_id,
+
+    -- Top-Level Packet Info
+    sync_word,
+
+    -- Rust 'ConfigPacket' Struct
+    ms_since_boot_cfc,
+    airbrake_target_apogee,
+    airbrake_max_rate,
+    airbrake_min_vel,
+    airbrake_body_cda,
+    airbrake_brake_cda,
+    blims_wind_layers,
+    blims_wind_checksum,
+    blims_fence_zones,
+    blims_fence_checksum
+) VALUES (
+    -- Convert EMQX's internal arrival clock into a standard PostgreSQL TIMESTAMPTZ
+    to_timestamp(${broker_arrival_ms} / 1000.0),
+    
+    -- Unit ID
+    ${unit_id},
+
+    -- Top-Level Packet Info
+    ${sync_word},
+
+    -- Rust 'ConfigPacket' Struct
+    ${ms_since_boot_cfc},
+    ${airbrake_target_apogee},
+    ${airbrake_max_rate},
+    ${airbrake_min_vel},
+    ${airbrake_body_cda},
+    ${airbrake_brake_cda},
+    ${blims_wind_layers},
+    ${blims_wind_checksum},
+    ${blims_fence_zones},
+    ${blims_fence_checksum}
+);
@@ -0,0 +1,22 @@
+SELECT
+    -- Top-Level Packet Info
+    timestamp as broker_arrival_ms,
+    payload.sync_word as sync_word,
+
+    -- Rust 'ConfigPacket' Struct
+    payload.ms_since_boot_cfc as ms_since_boot_cfc,
+    payload.airbrake_target_apogee as airbrake_target_apogee,
+    payload.airbrake_max_rate as airbrake_max_rate,
+    payload.airbrake_min_vel as airbrake_min_vel,
+    payload.airbrake_body_cda as airbrake_body_cda,
+    payload.airbrake_brake_cda as airbrake_brake_cda,
+    payload.blims_wind_layers as blims_wind_layers,
+    payload.blims_wind_checksum as blims_wind_checksum,
+    payload.blims_fence_zones as blims_fence_zones,
+    payload.blims_fence_checksum as blims_fence_checksum,
+
+    -- Unit ID routing (Dynamically grabbed from topic "rats/config/1")
+    int(nth(3, split(topic, '/'))) as unit_id
+  
+FROM
+    "rats/config/+"
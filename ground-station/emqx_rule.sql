@@ -69,6 +69,41 @@ SELECT
     payload.blims_downwind_lon as blims_downwind_lon,
     payload.blims_wind_from_deg as blims_wind_from_deg,
 
+    -- Timing
+    payload.utc_ms as utc_ms,
+
+    -- ODrive Telemetry
+    payload.airbrake_measured_deployment as airbrake_measured_deployment,
+    payload.airbrake_odrive_state as airbrake_odrive_state,
+    payload.airbrake_odrive_errors as airbrake_odrive_errors,
+    payload.airbrake_odrive_vbus as airbrake_odrive_vbus,
+    payload.airbrake_odrive_ibus as airbrake_odrive_ibus,
+    payload.blims_measured_brakeline as blims_measured_brakeline,
+    payload.blims_odrive_state as blims_odrive_state,
+    payload.blims_odrive_errors as blims_odrive_errors,
+    payload.blims_odrive_vbus as blims_odrive_vbus,
+    payload.blims_odrive_ibus as blims_odrive_ibus,
+    payload.odrive_link as odrive_link,
+
+    -- Airbrake Controller & BLiMS Estimates
+    payload.airbrake_ctrl_ok as airbrake_ctrl_ok,
+    payload.airbrake_est_body_cda as airbrake_est_body_cda,
+    payload.airbrake_est_brake_cda as airbrake_est_brake_cda,
+    payload.blims_wind_est_speed as blims_wind_est_speed,
+    payload.blims_wind_est_from_deg as blims_wind_est_from_deg,
+    payload.blims_dist_to_target as blims_dist_to_target,
+    payload.blims_fence_flags as blims_fence_flags,
+
+    -- Payload Link
+    payload.payload_link as payload_link,
+    payload.payload_last_ack as payload_last_ack,
+    payload.payload_state as payload_state,
+    payload.payload_failed as payload_failed,
+
+    -- Supply Rails
+    payload.battery_v as battery_v,
+    payload.pyro_v as pyro_v,
+
     -- Fill Station Specific (Umbilical)
     payload.pt_1_pressure as pt_1_pressure,
     payload.pt_2_pressure as pt_2_pressure,
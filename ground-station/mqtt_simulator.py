@@ -11,6 +11,7 @@ import math
 MQTT_BROKER_IP = "192.168.1.206" 
 MQTT_PORT = 1883
 MQTT_TOPIC = "rats/raw/1"  # unit_id 0 = Fill Station, 1 = RATS
+MQTT_CONFIG_TOPIC = "rats/config/1"  # 1 Hz "CFG!" frames from RATS
 PUBLISH_RATE_HZ = 20       # Telemetry publish rate in Hz
 
 # ==============================================================================
@@ -37,11 +38,13 @@ print("Starting telemetry simulation... Press Ctrl+C to stop.")
 # Base values for simulation
 fill_progress = 0.0
 time_step = 0.0
+frame_count = 0
 
 try:
     while True:
         # Simulate gradual pressurization, filling, and movement
         time_step += 0.05
+        frame_count += 1
         fill_progress = (fill_progress + 0.5) % 150.0  # Loops 0 to 50kg
         
         # Calculate seconds since midnight UTC (simulating MAX-M10S Time of Day)
@@ -54,7 +57,7 @@ try:
         # Build the JSON payload matching the unified Schema
         payload = {
             # Top-Level Radio
-            "sync_word": 4277009100,
+            "sync_word": 0x3E5D5967,  # "CRT!"
             
             # Shared Telemetry (Rust 'Packet')
             "flight_mode": 1,
@@ -112,22 +115,54 @@ try:
             "head_mot": 1131000 + int(random.uniform(-5000, 5000)),
 
             # BLiMS Outputs
-            "blims_motor_position": math.sin(time_step) * 5.0,
+            "blims_brakeline_diff": math.sin(time_step) * 5.0,
             "blims_phase_id": 2,
             "blims_pid_p": 1.5,
             "blims_pid_i": 0.05,
             "blims_bearing": 11.3 + random.uniform(-0.1, 0.1),
-            "blims_loiter_step": 0,
-            "blims_heading_des": 11.0,
-            "blims_heading_error": 0.3,
-            "blims_error_integral": 0.02,
-            "blims_dist_to_target_m": 4500.0 - (time_step * 25.0),
 
             # BLiMS Config
-            "blims_target_lat": 42.4500,
-            "blims_target_lon": -76.4800,
+            "blims_upwind_lat": 42.4500,
+            "blims_upwind_lon": -76.4850,
+            "blims_downwind_lat": 42.4500,
+            "blims_downwind_lon": -76.4750,
             "blims_wind_from_deg": 270.0,
-            
+
+            # Timing
+            "utc_ms": int(time.time() * 1000),
+
+            # ODrive Telemetry
+            "airbrake_measured_deployment": 0.0,
+            "airbrake_odrive_state": 1,
+            "airbrake_odrive_errors": 0,
+            "airbrake_odrive_vbus": 24.0 + random.uniform(-0.1, 0.1),
+            "airbrake_odrive_ibus": 0.1 + random.uniform(-0.05, 0.05),
+            "blims_measured_brakeline": math.sin(time_step) * 5.0,
+            "blims_odrive_state": 8,
+            "blims_odrive_errors": 0,
+            "blims_odrive_vbus": 24.0 + random.uniform(-0.1, 0.1),
+            "blims_odrive_ibus": 0.5 + random.uniform(-0.1, 0.1),
+            "odrive_link": 3,
+
+            # Airbrake Controller & BLiMS Estimates
+            "airbrake_ctrl_ok": 1,
+            "airbrake_est_body_cda": 0.0068,
+            "airbrake_est_brake_cda": 0.0035,
+            "blims_wind_est_speed": 4.0 + random.uniform(-0.5, 0.5),
+            "blims_wind_est_from_deg": 270.0 + random.uniform(-5, 5),
+            "blims_dist_to_target": 4500.0 - (time_step * 25.0),
+            "blims_fence_flags": 0,
+
+            # Payload Link
+            "payload_link": 1,
+            "payload_last_ack": 0,
+            "payload_state": 0,
+            "payload_failed": 0,
+
+            # Supply Rails
+            "battery_v": 7.4 + random.uniform(-0.05, 0.05),
+            "pyro_v": 7.4 + random.uniform(-0.05, 0.05),
+
             # Fill Station Specific
             "pt_1_pressure": 4500.0 + random.uniform(-10, 10),                
             "pt_2_pressure": 800.0 + random.uniform(-5, 5),                   
@@ -142,6 +177,23 @@ try:
         client.publish(MQTT_TOPIC, json.dumps(payload))
         print(f"Published to {MQTT_TOPIC} | Sys Time: {sys_time_ms}ms | GPS Time: {gps_time_of_day:.3f}s")
         
+        # Config frame once a second, like the FSW's CONFIG_PACKET_PERIOD_MS
+        if frame_count % PUBLISH_RATE_HZ == 0:
+            config = {
+                "sync_word": 0x21474643,  # "CFG!"
+                "ms_since_boot_cfc": sys_time_ms,
+                "airbrake_target_apogee": 3048.0,
+                "airbrake_max_rate": 0.5,
+                "airbrake_min_vel": 30.0,
+                "airbrake_body_cda": 0.0068,
+                "airbrake_brake_cda": 0.0035,
+                "blims_wind_layers": 0,
+                "blims_wind_checksum": 0,
+                "blims_fence_zones": 0,
+                "blims_fence_checksum": 0,
+            }
+            client.publish(MQTT_CONFIG_TOPIC, json.dumps(config))
+
         # Wait to match target publish rate (10Hz)
         time.sleep(1.0 / PUBLISH_RATE_HZ)
 
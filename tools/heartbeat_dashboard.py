@@ -51,6 +51,22 @@ TELEM_FIELDS = [
     "blims_downwind_lat", "blims_downwind_lon",
     "blims_wind_from_deg",
     "ms_since_boot_cfc",
+    "utc_ms",
+    "airbrake_measured_deployment", "airbrake_odrive_state",
+    "airbrake_odrive_errors", "airbrake_odrive_vbus", "airbrake_odrive_ibus",
+    "blims_measured_brakeline", "blims_odrive_state",
+    "blims_odrive_errors", "blims_odrive_vbus", "blims_odrive_ibus",
+    "odrive_link",
+    "airbrake_ctrl_ok",
+    "airbrake_target_apogee", "airbrake_max_rate", "airbrake_min_vel",
+    "airbrake_body_cda", "airbrake_brake_cda",
+    "airbrake_est_body_cda", "airbrake_est_brake_cda",
+    "blims_wind_est_speed", "blims_wind_est_from_deg",
+    "blims_wind_layers", "blims_wind_checksum",
+    "blims_dist_to_target",
+    "blims_fence_flags", "blims_fence_zones", "blims_fence_checksum",
+    "payload_link", "payload_last_ack", "payload_state", "payload_failed",
+    "battery_v", "pyro_v",
 ]
 
 MODE_NAMES = {
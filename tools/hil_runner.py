@@ -0,0 +1,537 @@
+#!/usr/bin/env python3
+"""
+HIL Runner — drives an FSW built with `--features hil` from a trajectory CSV.
+
+What it does:
+  - Loads an OpenRocket export or a recorded flight (dump_flash / Packet CSV).
+  - Streams `$HIL,...` sensor frames (baro, IMU, GPS, ADC) over the USB
+    umbilical in real time, interpolated from the trajectory.
+  - Arms the FSW the way the fill station does (<W> wipe flash, <KA>), sends
+    <L>, and drops heartbeats at liftoff as if the umbilical ripped away.
+  - Records every `$TELEM` line (with the runner's sim time) to a CSV and all
+    other FSW output to a log file.
+  - Checks pass/fail expectations against the captured telemetry and exits
+    non-zero if any fail.
+
+Trajectory formats:
+  OpenRocket   "Export as CSV" with field comments enabled. Uses Time,
+               Altitude, Vertical velocity/acceleration, Air pressure and
+               temperature, Position North/East of launch, and roll/pitch/yaw
+               rates when present. t=0 is ignition; <L> is sent
+               LAUNCH_PREVENT_S earlier so MAV opens at t=0.
+  Recorded     Any CSV with a Packet::CSV_HEADER-style header (dump_flash
+               output, sim logs). Time is ms_since_boot_cfc; t=0 is the first
+               Ascent row, where <L> is sent.
+
+Expectations (repeatable --expect):
+  mode:<Mode>@<t_min>..<t_max>   first entry into <Mode> falls inside the window
+  max:<field><op><value>         run maximum of <field> satisfies the comparison
+  min:<field><op><value>         run minimum of <field> satisfies the comparison
+  never:<field><op><value>       comparison is never true on any sample
+  ops: < <= > >= == !=
+  `never:flight_mode==6` (no Fault) is always checked unless --allow-fault.
+
+Usage:
+  python3 hil_runner.py flight.csv
+  python3 hil_runner.py or_export.csv --lat 32.94 --lon -106.91 --ground-alt 1400 \\
+      --expect mode:DrogueDeployed@20..30 --expect max:airbrake_deployment<=1.0
+"""
+
+import argparse
+import bisect
+import csv
+import math
+import os
+import re
+import sys
+import threading
+import time
+
+import serial
+
+from heartbeat_dashboard import TELEM_FIELDS, MODE_NAMES, find_port, parse_telemetry
+
+BAUD = 115200
+HEARTBEAT_INTERVAL_S = 1.0
+SENSOR_HZ = 20.0
+GPS_HZ = 5.0
+G = 9.80665
+
+# Matches constants::LAUNCH_SV_PREVENT_MS — <L> opens SV for this long before MAV.
+LAUNCH_PREVENT_S = 2.0
+WIPE_TIMEOUT_S = 60.0
+ARM_TIMEOUT_S = 15.0
+STREAM_TIMEOUT_S = 10.0
+
+MODE_IDS = {name: mode_id for mode_id, name in MODE_NAMES.items()}
+
+# OpenRocket unit suffix -> SI multiplier
+UNIT_SCALE = {
+    "m": 1.0, "ft": 0.3048, "km": 1000.0,
+    "m/s": 1.0, "ft/s": 0.3048, "mph": 0.44704,
+    "m/s²": 1.0, "ft/s²": 0.3048, "G": G,
+    "mbar": 100.0, "Pa": 1.0, "kPa": 1000.0, "psi": 6894.76, "inHg": 3386.39,
+    "°/s": 1.0, "r/s": 180.0 / math.pi, "°": 1.0,
+}
+
+
+# ----------------------------------------------------------------------------
+# Trajectory loading
+# ----------------------------------------------------------------------------
+
+def isa_pressure(alt_msl_m):
+    """Standard-atmosphere static pressure (Pa), inverse of the FSW's NOAA formula."""
+    return 101325.0 * (1.0 - alt_msl_m / 44330.0) ** (1.0 / 0.190295)
+
+
+def _or_column(header, name):
+    """Find an OpenRocket column by name; returns (index, SI scale) or None."""
+    for i, col in enumerate(header):
+        m = re.match(r"\s*#?\s*(.+?)\s*\((.+)\)\s*$", col)
+        if m and m.group(1) == name:
+            unit = m.group(2)
+            if unit == "°F":
+                return i, "F"
+            return i, UNIT_SCALE.get(unit, 1.0)
+    return None
+
+
+def load_openrocket(path, args):
+    header = None
+    rows = []
+    with open(path, newline="", encoding="utf-8", errors="replace") as f:
+        for line in f:
+            if line.startswith("#"):
+                if "Time (" in line:
+                    header = next(csv.reader([line.lstrip("# ")]))
+                continue
+            if line.strip():
+                rows.append(next(csv.reader([line])))
+    if header is None:
+        raise ValueError("no '# Time (s),...' header line — export with field comments enabled")
+
+    cols = {name: _or_column(header, name) for name in (
+        "Time", "Altitude", "Vertical velocity", "Vertical acceleration",
+        "Air pressure", "Air temperature", "Position North of launch",
+        "Position East of launch", "Roll rate", "Pitch rate", "Yaw rate",
+    )}
+    for required in ("Time", "Altitude", "Vertical acceleration"):
+        if cols[required] is None:
+            raise ValueError(f"OpenRocket export is missing '{required}'")
+
+    def get(row, name, default=0.0):
+        c = cols[name]
+        if c is None or c[0] >= len(row) or not row[c[0]].strip() or row[c[0]].strip() == "NaN":
+            return default
+        v = float(row[c[0]])
+        if c[1] == "F":
+            return (v - 32.0) * 5.0 / 9.0
+        return v * c[1]
+
+    lat0, lon0 = args.lat, args.lon
+    m_per_deg_lon = 111_320.0 * math.cos(math.radians(lat0))
+    samples = []
+    for row in rows:
+        alt = get(row, "Altitude")
+        north = get(row, "Position North of launch")
+        east = get(row, "Position East of launch")
+        pressure = get(row, "Air pressure", None)
+        if pressure is None:
+            pressure = isa_pressure(args.ground_alt + alt)
+        a_vert = get(row, "Vertical acceleration")
+        samples.append({
+            "t": get(row, "Time"),
+            "pressure": pressure,
+            "temp": get(row, "Air temperature", 15.0),
+            # Specific force on the vertical (board -Y) axis; reads -1 g on the pad
+            "accel_x": 0.0,
+            "accel_y": -(a_vert + G),
+            "accel_z": 0.0,
+            "gyro_x": get(row, "Pitch rate"),
+            "gyro_y": get(row, "Roll rate"),
+            "gyro_z": get(row, "Yaw rate"),
+            "latitude": lat0 + north / 111_320.0,
+            "longitude": lon0 + east / m_per_deg_lon,
+            "north": north,
+            "east": east,
+            "vel_d": -get(row, "Vertical velocity"),
+        })
+
+    # Horizontal velocity from position differences
+    for prev, cur in zip(samples, samples[1:]):
+        dt = cur["t"] - prev["t"]
+        cur["vel_n"] = (cur["north"] - prev["north"]) / dt if dt > 0 else 0.0
+        cur["vel_e"] = (cur["east"] - prev["east"]) / dt if dt > 0 else 0.0
+    if samples:
+        samples[0]["vel_n"] = samples[0]["vel_e"] = 0.0
+    return samples, -LAUNCH_PREVENT_S
+
+
+def load_recorded(path, args):
+    with open(path, newline="") as f:
+        reader = csv.DictReader(f)
+        rows = list(reader)
+    if not rows:
+        raise ValueError("recorded CSV has no rows")
+
+    def num(row, key, default=0.0):
+        v = (row.get(key) or "").strip()
+        try:
+            return float(v)
+        except ValueError:
+            return default
+
+    t_launch = None
+    for row in rows:
+        if num(row, "flight_mode") >= MODE_IDS["Ascent"]:
+            t_launch = num(row, "ms_since_boot_cfc") / 1000.0
+            break
+    if t_launch is None:
+        raise ValueError("recorded CSV never reaches Ascent — nothing to launch on")
+
+    samples = []
+    last_t = None
+    for row in rows:
+        t = num(row, "ms_since_boot_cfc") / 1000.0 - t_launch
+        if last_t is not None and t <= last_t:
+            continue  # reboot backstep or duplicate — keep time monotonic
+        last_t = t
+        s = {"t": t}
+        for key in ("pressure", "temp", "accel_x", "accel_y", "accel_z",
+                    "gyro_x", "gyro_y", "gyro_z", "mag_x", "mag_y", "mag_z",
+                    "latitude", "longitude", "vel_n", "vel_e", "vel_d",
+                    "pt3", "pt4", "rtd"):
+            s[key] = num(row, key)
+        s["num_satellites"] = num(row, "num_satellites", 12)
+        s["fix_type"] = num(row, "fix_type", 3)
+        s["h_acc"] = num(row, "h_acc", 2000)
+        s["v_acc"] = num(row, "v_acc", 3000)
+        samples.append(s)
+    return samples, 0.0
+
+
+def load_trajectory(path, args):
+    with open(path, encoding="utf-8", errors="replace") as f:
+        head = f.read(4096)
+    if head.lstrip().startswith("#"):
+        return load_openrocket(path, args)
+    return load_recorded(path, args)
+
+
+class Trajectory:
+    def __init__(self, samples):
+        self.samples = samples
+        self.times = [s["t"] for s in samples]
+
+    @property
+    def start(self):
+        return self.times[0]
+
+    @property
+    def end(self):
+        return self.times[-1]
+
+    def at(self, t):
+        """Linearly interpolated sample at time t (held at the ends)."""
+        if t <= self.times[0]:
+            return self.samples[0]
+        if t >= self.times[-1]:
+            return self.samples[-1]
+        i = bisect.bisect_right(self.times, t)
+        a, b = self.samples[i - 1], self.samples[i]
+        span = b["t"] - a["t"]
+        w = (t - a["t"]) / span if span > 0 else 0.0
+        return {k: a[k] + (b.get(k, a[k]) - a[k]) * w for k in a}
+
+
+# ----------------------------------------------------------------------------
+# Frames
+# ----------------------------------------------------------------------------
+
+def frame_baro(t_ms, s):
+    return f"$HIL,B,{t_ms},{s['pressure']:.2f},{s['temp']:.2f}\n"
+
+
+def frame_imu(t_ms, s):
+    line = (f"$HIL,I,{t_ms},{s['accel_x']:.3f},{s['accel_y']:.3f},{s['accel_z']:.3f},"
+            f"{s['gyro_x']:.3f},{s['gyro_y']:.3f},{s['gyro_z']:.3f}")
+    if "mag_x" in s:
+        line += f",{s['mag_x']:.3f},{s['mag_y']:.3f},{s['mag_z']:.3f}"
+    return line + "\n"
+
+
+def frame_gps(t_ms, s):
+    return (f"$HIL,G,{t_ms},{s['latitude']:.7f},{s['longitude']:.7f},"
+            f"{int(s.get('num_satellites', 12))},{int(s.get('fix_type', 3))},"
+            f"{s['vel_n']:.3f},{s['vel_e']:.3f},{s['vel_d']:.3f},"
+            f"{int(s.get('h_acc', 2000))},{int(s.get('v_acc', 3000))}\n")
+
+
+def frame_adc(t_ms, s):
+    return f"$HIL,A,{t_ms},{s.get('pt3', 0.0):.1f},{s.get('pt4', 0.0):.1f},{s.get('rtd', 20.0):.1f}\n"
+
+
+# ----------------------------------------------------------------------------
+# Expectations
+# ----------------------------------------------------------------------------
+
+OPS = {
+    "<=": lambda a, b: a <= b, ">=": lambda a, b: a >= b,
+    "==": lambda a, b: a == b, "!=": lambda a, b: a != b,
+    "<": lambda a, b: a < b, ">": lambda a, b: a > b,
+}
+CMP_RE = re.compile(r"^(\w+)(<=|>=|==|!=|<|>)(-?[\d.eE+-]+)$")
+
+
+def check_expectation(spec, rows):
+    """Returns (passed, detail) for one --expect spec over captured rows."""
+    kind, _, rest = spec.partition(":")
+    if kind == "mode":
+        m = re.match(r"^(\w+)@(-?[\d.]+)\.\.(-?[\d.]+)$", rest)
+        if not m or m.group(1) not in MODE_IDS:
+            return False, "bad spec"
+        mode_id = MODE_IDS[m.group(1)]
+        lo, hi = float(m.group(2)), float(m.group(3))
+        first = next((r["sim_t"] for r in rows if r["flight_mode"] == mode_id), None)
+        if first is None:
+            return False, "never entered"
+        return lo <= first <= hi, f"entered at t={first:.2f} s"
+
+    m = CMP_RE.match(rest)
+    if kind not in ("max", "min", "never") or not m or m.group(1) not in TELEM_FIELDS:
+        return False, "bad spec"
+    field, op, value = m.group(1), OPS[m.group(2)], float(m.group(3))
+    values = [r[field] for r in rows]
+    if not values:
+        return False, "no telemetry"
+    if kind == "never":
+        hit = next((r for r in rows if op(r[field], value)), None)
+        if hit is None:
+            return True, "never true"
+        return False, f"true at t={hit['sim_t']:.2f} s ({field}={hit[field]})"
+    extreme = max(values) if kind == "max" else min(values)
+    return op(extreme, value), f"{kind}={extreme}"
+
+
+# ----------------------------------------------------------------------------
+# Serial I/O
+# ----------------------------------------------------------------------------
+
+class Run:
+    def __init__(self, ser, telem_path, log_path):
+        self.ser = ser
+        self.lock = threading.Lock()
+        self.write_lock = threading.Lock()
+        self.rows = []
+        self.latest = None
+        self.sim_t = None
+        self.heartbeat = True
+        self.stop = False
+        self.read_error = None
+        self.log_lines = []
+        self.telem_file = open(telem_path, "w", newline="")
+        self.telem_csv = csv.writer(self.telem_file)
+        self.telem_csv.writerow(["host_time", "sim_t"] + TELEM_FIELDS)
+        self.log_file = open(log_path, "w")
+
+    def write(self, data):
+        with self.write_lock:
+            self.ser.write(data)
+
+    def command(self, token):
+        print(f"  -> {token}")
+        self.write(token.encode())
+        # FSW sleeps 100 ms after each command packet; don't coalesce the next one
+        time.sleep(0.15)
+
+    def saw_log(self, needle):
+        with self.lock:
+            return any(needle in line for line in self.log_lines)
+
+    def reader(self):
+        buf = b""
+        while not self.stop:
+            try:
+                chunk = self.ser.read(512)
+            except Exception as e:
+                self.read_error = str(e)
+                return
+            if not chunk:
+                continue
+            buf += chunk
+            while b"\n" in buf:
+                line, buf = buf.split(b"\n", 1)
+                text = line.decode("utf-8", errors="replace").strip()
+                if not text:
+                    continue
+                now = time.time()
+                if text.startswith("$TELEM,"):
+                    telem = parse_telemetry(text[7:])
+                    if telem is None:
+                        continue
+                    with self.lock:
+                        telem["sim_t"] = self.sim_t
+                        self.latest = telem
+                        if self.sim_t is not None:
+                            self.rows.append(telem)
+                    self.telem_csv.writerow(
+                        [f"{now:.3f}", "" if self.sim_t is None else f"{self.sim_t:.3f}"]
+                        + [telem[k] for k in TELEM_FIELDS])
+                else:
+                    with self.lock:
+                        self.log_lines.append(text)
+                    self.log_file.write(f"{now:.3f} {text}\n")
+
+    def heartbeat_loop(self):
+        while not self.stop:
+            if self.heartbeat:
+                try:
+                    self.write(b"<H>")
+                except Exception:
+                    pass
+            time.sleep(HEARTBEAT_INTERVAL_S)
+
+    def mode(self):
+        with self.lock:
+            return None if self.latest is None else self.latest["flight_mode"]
+
+    def close(self):
+        self.stop = True
+        self.telem_file.close()
+        self.log_file.close()
+
+
+def stream(run, traj, sim_t, next_gps):
+    """Send one cycle of frames for sim time sim_t; returns the next GPS time."""
+    s = traj.at(sim_t)
+    t_ms = max(0, int(round((sim_t - traj.start) * 1000)))
+    out = frame_baro(t_ms, s) + frame_imu(t_ms, s) + frame_adc(t_ms, s)
+    if sim_t >= next_gps:
+        out += frame_gps(t_ms, s)
+        next_gps = sim_t + 1.0 / GPS_HZ
+    run.write(out.encode())
+    return next_gps
+
+
+def wait_until(run, traj, pad_sample_t, predicate, timeout, what):
+    """Keep streaming the pad sample until predicate() holds or timeout."""
+    deadline = time.time() + timeout
+    next_gps = 0.0
+    while time.time() < deadline:
+        next_gps = stream(run, traj, pad_sample_t, next_gps)
+        if predicate():
+            return True
+        if run.read_error:
+            raise RuntimeError(f"serial read failed: {run.read_error}")
+        time.sleep(1.0 / SENSOR_HZ)
+    raise RuntimeError(f"timed out waiting for {what}")
+
+
+def main():
+    ap = argparse.ArgumentParser(description="Stream a trajectory into a HIL FSW build.")
+    ap.add_argument("trajectory", help="OpenRocket export or recorded-flight CSV")
+    ap.add_argument("--port", help="FSW serial port (auto-detected if omitted)")
+    ap.add_argument("--lat", type=float, default=32.9405, help="pad latitude for OpenRocket runs")
+    ap.add_argument("--lon", type=float, default=-106.9117, help="pad longitude for OpenRocket runs")
+    ap.add_argument("--ground-alt", type=float, default=1401.0,
+                    help="pad altitude MSL (m), used when the export has no air pressure")
+    ap.add_argument("--pad-s", type=float, default=5.0, help="pad dwell in Standby before <L>")
+    ap.add_argument("--tail-s", type=float, default=10.0, help="hold the last sample this long after the trajectory ends")
+    ap.add_argument("--speed", type=float, default=1.0, help="sim-time multiplier (1.0 = real time)")
+    ap.add_argument("--out", default=None, help="telemetry CSV path (default hil_<timestamp>.csv)")
+    ap.add_argument("--expect", action="append", default=[], help="pass/fail expectation (see module doc)")
+    ap.add_argument("--allow-fault", action="store_true", help="don't fail the run on Fault mode")
+    args = ap.parse_args()
+
+    samples, launch_cmd_t = load_trajectory(args.trajectory, args)
+    traj = Trajectory(samples)
+    print(f"Loaded {len(samples)} samples, t = {traj.start:.2f} .. {traj.end:.2f} s")
+
+    port = args.port or find_port()
+    if not port:
+        print("No FSW serial port found. Pass one with --port.", file=sys.stderr)
+        sys.exit(2)
+    ser = serial.Serial(port, BAUD, timeout=0.05, write_timeout=1.0)
+
+    stem = args.out or time.strftime("hil_%Y%m%d_%H%M%S.csv")
+    run = Run(ser, stem, os.path.splitext(stem)[0] + ".log")
+    threading.Thread(target=run.reader, daemon=True).start()
+    threading.Thread(target=run.heartbeat_loop, daemon=True).start()
+
+    pad_t = traj.start
+    try:
+        print("Waiting for FSW telemetry (the flight loop starts once frames arrive)...")
+        wait_until(run, traj, pad_t, lambda: run.mode() is not None, STREAM_TIMEOUT_S, "telemetry")
+        if run.mode() != MODE_IDS["Startup"]:
+            raise RuntimeError(
+                f"FSW is in {MODE_NAMES.get(run.mode(), run.mode())}, not Startup — "
+                "send <X> (wipe FRAM + reboot) and rerun")
+
+        print("Arming:")
+        run.command("<W>")
+        wait_until(run, traj, pad_t, lambda: run.saw_log("Flash wiped successfully"),
+                   WIPE_TIMEOUT_S, "flash wipe")
+        run.command("<KA>")
+        wait_until(run, traj, pad_t, lambda: run.mode() == MODE_IDS["Standby"],
+                   ARM_TIMEOUT_S, "Standby")
+        print("  Standby reached")
+
+        # Real-time playback from (launch command - pad dwell) to end + tail
+        sim_t = min(traj.start, launch_cmd_t) - args.pad_s
+        end_t = traj.end + args.tail_s
+        launched = False
+        next_gps = sim_t
+        wall0 = time.time()
+        sim0 = sim_t
+        print(f"Streaming {end_t - sim0:.1f} s of sim time...")
+        while sim_t <= end_t:
+            with run.lock:
+                run.sim_t = sim_t
+            if not launched and sim_t >= launch_cmd_t:
+                run.command("<L>")
+                launched = True
+            if run.heartbeat and sim_t >= 0.0 and launched:
+                run.heartbeat = False  # umbilical rips away at liftoff
+                print("  liftoff: heartbeats stopped")
+            next_gps = stream(run, traj, sim_t, next_gps)
+            if run.read_error:
+                raise RuntimeError(f"serial read failed: {run.read_error}")
+
+            sim_t += 1.0 / SENSOR_HZ
+            sleep_for = wall0 + (sim_t - sim0) / args.speed - time.time()
+            if sleep_for > 0:
+                time.sleep(sleep_for)
+    except RuntimeError as e:
+        print(f"ABORT: {e}", file=sys.stderr)
+        run.close()
+        ser.close()
+        sys.exit(2)
+
+    time.sleep(0.5)  # let the last telemetry lines drain
+    run.close()
+    ser.close()
+
+    with run.lock:
+        rows = list(run.rows)
+    print(f"\nCaptured {len(rows)} telemetry rows -> {stem}")
+    transitions = []
+    for r in rows:
+        if not transitions or transitions[-1][1] != r["flight_mode"]:
+            transitions.append((r["sim_t"], r["flight_mode"]))
+    for t, mode in transitions:
+        print(f"  t={t:7.2f} s  {MODE_NAMES.get(mode, mode)}")
+
+    expectations = list(args.expect)
+    if not args.allow_fault:
+        expectations.append(f"never:flight_mode=={MODE_IDS['Fault']}")
+    failed = 0
+    print("\nExpectations:")
+    for spec in expectations:
+        ok, detail = check_expectation(spec, rows)
+        failed += not ok
+        print(f"  [{'PASS' if ok else 'FAIL'}] {spec}  ({detail})")
+    sys.exit(1 if failed else 0)
+
+
+if __name__ == "__main__":
+    main()
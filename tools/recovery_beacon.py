@@ -0,0 +1,158 @@
+#!/usr/bin/env python3
+"""
+Recovery Beacon — last-known rocket position from the ground RFD900x.
+
+Listens on the ground radio's serial port and decodes two frame types:
+  - "CRT!" full telemetry packets (Packet, 270 bytes): position taken from
+    latitude/longitude while the GPS reports satellites
+  - "BCN!" recovery beacons (BeaconPacket, 28 bytes), which the FSW sends
+    every BEACON_PERIOD_MS once MAIN_LOG_TIMEOUT_MS has passed under the
+    main: last 3D fix, its age, satellites, battery, apogee, health bits
+
+"CFG!" config packets (ConfigPacket, 34 bytes, 1 Hz) are framed and skipped.
+
+Prints a line per beacon and keeps the last-known position on screen; with
+--log every decoded position is appended to a CSV as well.
+
+Usage:
+  python3 recovery_beacon.py                         # auto-detect port
+  python3 recovery_beacon.py /dev/ttyUSB0 --baud 57600 --log recovery.csv
+"""
+
+import argparse
+import glob
+import math
+import struct
+import sys
+import time
+
+import serial
+
+SYNC_PACKET = bytes([0x67, 0x59, 0x5D, 0x3E])  # "CRT!" (rfd900x::SYNC_WORD)
+SYNC_BEACON = bytes([0x42, 0x43, 0x4E, 0x21])  # "BCN!" (rfd900x::BEACON_SYNC_WORD)
+SYNC_CONFIG = bytes([0x43, 0x46, 0x47, 0x21])  # "CFG!" (rfd900x::CONFIG_SYNC_WORD)
+PACKET_SIZE = 270
+BEACON_SIZE = 28
+CONFIG_SIZE = 34
+NO_FIX = 0xFFFFFFFF
+
+# BeaconPacket::to_bytes: ms, mode, sats, health, lat, lon, fix age, battery, apogee
+BEACON_FORMAT = "<IBBHffIfI"
+
+MODE_NAMES = {
+    0: "Startup", 1: "Standby", 2: "Ascent", 3: "Coast",
+    4: "DrogueDeployed", 5: "MainDeployed", 6: "Fault",
+}
+
+# state::health bits, by bit position
+HEALTH_BITS = ["ALT", "IMU", "GPS", "FLASH", "FULL", "PAYLOAD", "RECOVERED_FAULT", "LOW_VOLTAGE"]
+
+
+def find_port():
+    for pat in ("/dev/cu.usbserial*", "/dev/ttyUSB*"):
+        ports = glob.glob(pat)
+        if ports:
+            return ports[0]
+    return None
+
+
+def decode_beacon(body):
+    ms, mode, sats, health, lat, lon, age, battery, apogee = struct.unpack(BEACON_FORMAT, body)
+    return {
+        "ms_since_boot_cfc": ms, "flight_mode": mode, "num_satellites": sats,
+        "health": health, "latitude": lat, "longitude": lon,
+        "fix_age_s": None if age == NO_FIX else age,
+        "battery_v": None if math.isnan(battery) else battery, "apogee_ft": apogee,
+    }
+
+
+def decode_packet_position(body):
+    """flight_mode, latitude, longitude, num_satellites from a full Packet."""
+    mode, _pressure, _temp, _alt, lat, lon, sats = struct.unpack_from("<IfffffI", body)
+    return mode, lat, lon, sats
+
+
+def health_names(bits):
+    names = [name for i, name in enumerate(HEALTH_BITS) if bits & (1 << i)]
+    return "+".join(names) if names else "OK"
+
+
+def frames(stream):
+    """Yield (sync, body) for every complete frame read from the port."""
+    buf = bytearray()
+    while True:
+        buf += stream.read(stream.in_waiting or 1)
+        while len(buf) >= 4:
+            if buf[:4] == SYNC_PACKET:
+                size = PACKET_SIZE
+            elif buf[:4] == SYNC_BEACON:
+                size = BEACON_SIZE
+            elif buf[:4] == SYNC_CONFIG:
+                size = CONFIG_SIZE
+            else:
+                del buf[0]
+                continue
+            if len(buf) < 4 + size:
+                break
+            yield bytes(buf[:4]), bytes(buf[4:4 + size])
+            del buf[:4 + size]
+
+
+def main():
+    ap = argparse.ArgumentParser(description="Show the rocket's last-known position from the radio.")
+    ap.add_argument("port", nargs="?", help="ground RFD900x serial port (auto-detected if omitted)")
+    ap.add_argument("--baud", type=int, default=57600, help="ground radio serial rate")
+    ap.add_argument("--log", default=None, help="append decoded positions to this CSV")
+    args = ap.parse_args()
+
+    port = args.port or find_port()
+    if not port:
+        print("No ground radio found; pass the port explicitly.")
+        sys.exit(1)
+    log = open(args.log, "a") if args.log else None
+    print(f"Listening on {port} at {args.baud} baud. Ctrl+C to exit.")
+
+    last = None  # (lat, lon, source)
+    last_status = 0.0
+    try:
+        with serial.Serial(port, args.baud, timeout=1) as stream:
+            for sync, body in frames(stream):
+                now = time.time()
+                if sync == SYNC_CONFIG:
+                    continue
+                if sync == SYNC_BEACON:
+                    b = decode_beacon(body)
+                    age = "never" if b["fix_age_s"] is None else f"{b['fix_age_s']} s"
+                    battery = "--" if b["battery_v"] is None else f"{b['battery_v']:.2f} V"
+                    print(
+                        f"\nBEACON  {b['latitude']:.6f}, {b['longitude']:.6f}  fix age {age}  "
+                        f"sats {b['num_satellites']}  batt {battery}  apogee {b['apogee_ft']} ft  "
+                        f"{MODE_NAMES.get(b['flight_mode'], b['flight_mode'])}  {health_names(b['health'])}"
+                    )
+                    if b["fix_age_s"] is None:
+                        continue
+                    last = (b["latitude"], b["longitude"], "beacon")
+                else:
+                    # Full telemetry arrives at 20 Hz; keep its position at 1 Hz
+                    mode, lat, lon, sats = decode_packet_position(body)
+                    if sats == 0 or now - last_status < 1.0:
+                        continue
+                    last = (lat, lon, MODE_NAMES.get(mode, str(mode)))
+                last_status = now
+                print(
+                    f"\rlast known: {last[0]:.6f}, {last[1]:.6f} ({last[2]})  "
+                    f"https://maps.google.com/?q={last[0]:.6f},{last[1]:.6f}  ",
+                    end="", flush=True,
+                )
+                if log:
+                    log.write(f"{now:.1f},{last[0]:.6f},{last[1]:.6f},{last[2]}\n")
+                    log.flush()
+    except KeyboardInterrupt:
+        pass
+    finally:
+        if log:
+            log.close()
+
+
+if __name__ == "__main__":
+    main()